@@ -0,0 +1,52 @@
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::rc::Retained;
+    use objc2::runtime::{NSObjectProtocol, ProtocolObject};
+    use objc2_foundation::{NSActivityOptions, NSProcessInfo, NSString};
+
+    pub struct Activity(Retained<ProtocolObject<dyn NSObjectProtocol>>);
+
+    #[allow(unused_unsafe)]
+    pub fn begin(reason: &str) -> Activity {
+        // Latency-critical keeps the audio callbacks on time; user-initiated blocks App Nap and idle sleep
+        let options = NSActivityOptions::UserInitiated | NSActivityOptions::LatencyCritical;
+        let reason = NSString::from_str(reason);
+        Activity(unsafe { NSProcessInfo::processInfo().beginActivityWithOptions_reason(options, &reason) })
+    }
+
+    #[allow(unused_unsafe)]
+    pub fn end(activity: &Activity) {
+        unsafe { NSProcessInfo::processInfo().endActivity(&activity.0) };
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    /// Other platforms don't suspend background audio
+    pub struct Activity;
+
+    pub fn begin(_reason: &str) -> Activity {
+        Activity
+    }
+
+    pub fn end(_activity: &Activity) {}
+}
+
+/// Keeps macOS from suspending the audio thread until dropped. App Nap throttles apps whose
+/// windows are hidden, which is how Eva spends most of her time, and an idle Mac going to sleep
+/// stops the microphone.
+pub struct KeepAwake(platform::Activity);
+
+impl KeepAwake {
+    pub fn begin(reason: &str) -> Self {
+        log::debug!("☕ Holding off App Nap and idle sleep: {}", reason);
+        Self(platform::begin(reason))
+    }
+}
+
+impl Drop for KeepAwake {
+    fn drop(&mut self) {
+        platform::end(&self.0);
+        log::debug!("☕ App Nap and idle sleep allowed again");
+    }
+}
@@ -0,0 +1,148 @@
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Frames per callback in low-latency mode; ~5ms at 48kHz
+const LOW_LATENCY_FRAMES: u32 = 256;
+
+/// Frames per callback of the last microphone stream opened, as requested of the device
+static REQUESTED: Mutex<Option<RequestedBufferSize>> = Mutex::new(None);
+/// Frames the device actually delivered in its latest callback
+static CALLBACK_FRAMES: AtomicU32 = AtomicU32::new(0);
+
+/// How much audio the microphone hands over per callback.
+///
+/// Smaller buffers reach the wake word sooner but wake the CPU more often.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "frames", rename_all = "kebab-case")]
+pub enum CaptureBufferSize {
+    /// Whatever the device and driver choose
+    #[default]
+    Default,
+    /// Small buffers, for quicker responses on capable hardware
+    LowLatency,
+    /// A specific number of frames, clamped to what the device supports
+    Fixed(u32),
+}
+
+impl CaptureBufferSize {
+    /// The cpal buffer size to request from a device supporting `supported`
+    pub fn resolve(self, supported: &cpal::SupportedBufferSize) -> cpal::BufferSize {
+        let frames = match self {
+            CaptureBufferSize::Default => return cpal::BufferSize::Default,
+            CaptureBufferSize::LowLatency => LOW_LATENCY_FRAMES,
+            CaptureBufferSize::Fixed(frames) => frames.max(1),
+        };
+        match supported {
+            cpal::SupportedBufferSize::Range { min, max } => cpal::BufferSize::Fixed(frames.clamp(*min, *max)),
+            cpal::SupportedBufferSize::Unknown => cpal::BufferSize::Fixed(frames),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RequestedBufferSize {
+    setting: CaptureBufferSize,
+    requested_frames: Option<u32>,
+    device_range: Option<(u32, u32)>,
+    sample_rate: u32,
+}
+
+/// Returned by `get_capture_buffer_info`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaptureBufferInfo {
+    pub setting: CaptureBufferSize,
+    /// Frames asked of the device; `None` leaves it to the driver
+    pub requested_frames: Option<u32>,
+    /// Smallest and largest buffers the device reports, when it does
+    pub device_min_frames: Option<u32>,
+    pub device_max_frames: Option<u32>,
+    /// What the device delivered in its latest callback; `None` before the first one
+    pub callback_frames: Option<u32>,
+    pub callback_ms: Option<f64>,
+    pub sample_rate: u32,
+}
+
+/// Note the size requested for a newly opened microphone stream
+pub fn record_requested(
+    setting: CaptureBufferSize,
+    requested: &cpal::BufferSize,
+    supported: &cpal::SupportedBufferSize,
+    sample_rate: u32,
+) {
+    let requested_frames = match requested {
+        cpal::BufferSize::Fixed(frames) => Some(*frames),
+        cpal::BufferSize::Default => None,
+    };
+    let device_range = match supported {
+        cpal::SupportedBufferSize::Range { min, max } => Some((*min, *max)),
+        cpal::SupportedBufferSize::Unknown => None,
+    };
+    *REQUESTED.lock().unwrap_or_else(|e| e.into_inner()) = Some(RequestedBufferSize {
+        setting,
+        requested_frames,
+        device_range,
+        sample_rate,
+    });
+    CALLBACK_FRAMES.store(0, Ordering::Relaxed);
+}
+
+/// Called from the capture callback; lock-free
+pub fn record_callback_frames(frames: usize) {
+    CALLBACK_FRAMES.store(frames as u32, Ordering::Relaxed);
+}
+
+/// The buffer size the microphone stream settled on; empty until wake word detection has started
+#[tauri::command]
+pub async fn get_capture_buffer_info(state: tauri::State<'_, SettingsState>) -> Result<CaptureBufferInfo, String> {
+    let requested = REQUESTED.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let Some(requested) = requested else {
+        return Ok(CaptureBufferInfo {
+            setting: state.lock().await.audio_input.buffer_size,
+            ..Default::default()
+        });
+    };
+
+    let callback_frames = Some(CALLBACK_FRAMES.load(Ordering::Relaxed)).filter(|frames| *frames > 0);
+    Ok(CaptureBufferInfo {
+        setting: requested.setting,
+        requested_frames: requested.requested_frames,
+        device_min_frames: requested.device_range.map(|(min, _)| min),
+        device_max_frames: requested.device_range.map(|(_, max)| max),
+        callback_frames,
+        callback_ms: callback_frames.map(|frames| frames as f64 * 1000.0 / requested.sample_rate as f64),
+        sample_rate: requested.sample_rate,
+    })
+}
+
+/// Change the microphone buffer size, restarting wake word detection if it's running
+#[tauri::command]
+pub async fn set_capture_buffer_size(
+    buffer_size: CaptureBufferSize,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if buffer_size == CaptureBufferSize::Fixed(0) {
+        return Err("A fixed buffer needs at least one frame".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.audio_input.buffer_size = buffer_size;
+    settings.save(&app)?;
+    drop(settings);
+
+    app.state::<WakeWordHandle>()
+        .set_buffer_size(buffer_size, app.clone())
+        .await
+        .map_err(|e| format!("Failed to restart wake word detection: {}", e))?;
+
+    log::info!("🎚️ Capture buffer size set to {:?}", buffer_size);
+    Ok(match buffer_size {
+        CaptureBufferSize::Default => "Capture buffer left to the device".to_string(),
+        CaptureBufferSize::LowLatency => format!("Low-latency capture ({} frames)", LOW_LATENCY_FRAMES),
+        CaptureBufferSize::Fixed(frames) => format!("Capture buffer set to {} frames", frames),
+    })
+}
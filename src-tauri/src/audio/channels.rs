@@ -0,0 +1,153 @@
+use crate::audio::{CaptureBufferSize, CaptureHost, CaptureSource};
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+/// How multi-channel input is reduced to the mono signal Eva listens to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "index", rename_all = "kebab-case")]
+pub enum ChannelStrategy {
+    /// First channel
+    #[default]
+    Left,
+    /// Second channel, or the only one on mono devices
+    Right,
+    /// Mean of all channels
+    Average,
+    /// A specific 0-based channel, e.g. input 2 of an audio interface is index 1
+    Channel(usize),
+}
+
+impl ChannelStrategy {
+    /// Append the mono signal of interleaved `samples` to `out`.
+    ///
+    /// Channels past the device's count fall back to the last channel.
+    pub fn downmix_into(&self, samples: &[f32], channels: usize, out: &mut Vec<f32>) {
+        if channels <= 1 {
+            out.extend_from_slice(samples);
+            return;
+        }
+
+        let frames = samples.chunks_exact(channels);
+        match self {
+            ChannelStrategy::Average => {
+                let scale = 1.0 / channels as f32;
+                out.extend(frames.map(|frame| frame.iter().sum::<f32>() * scale));
+            }
+            _ => {
+                let index = self.channel_index().min(channels - 1);
+                out.extend(frames.map(|frame| frame[index]));
+            }
+        }
+    }
+
+    pub fn downmix(&self, samples: &[f32], channels: usize) -> Vec<f32> {
+        let mut mono = Vec::with_capacity(samples.len() / channels.max(1));
+        self.downmix_into(samples, channels, &mut mono);
+        mono
+    }
+
+    fn channel_index(&self) -> usize {
+        match self {
+            ChannelStrategy::Left | ChannelStrategy::Average => 0,
+            ChannelStrategy::Right => 1,
+            ChannelStrategy::Channel(index) => *index,
+        }
+    }
+}
+
+/// Capture settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioInputSettings {
+    pub channel_strategy: ChannelStrategy,
+    pub capture_source: CaptureSource,
+    /// Microphone buffer used by the wake word
+    pub buffer_size: CaptureBufferSize,
+    /// Device name or, on Linux, PulseAudio/PipeWire source node name; `None` picks automatically
+    pub input_device: Option<String>,
+    /// Never pick a monitor (loopback) source, which would have Eva hear system audio
+    pub exclude_monitors: bool,
+    pub capture_host: CaptureHost,
+    /// Open the microphone at the wake word's 16 kHz when it supports that
+    pub prefer_native_rate: bool,
+}
+
+impl Default for AudioInputSettings {
+    fn default() -> Self {
+        Self {
+            channel_strategy: ChannelStrategy::default(),
+            capture_source: CaptureSource::default(),
+            buffer_size: CaptureBufferSize::default(),
+            input_device: None,
+            exclude_monitors: true,
+            capture_host: CaptureHost::default(),
+            prefer_native_rate: false,
+        }
+    }
+}
+
+/// Pick the input channel(s), restarting wake word detection if it's running
+#[tauri::command]
+pub async fn set_channel_strategy(
+    strategy: ChannelStrategy,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.audio_input.channel_strategy = strategy;
+    settings.save(&app)?;
+    drop(settings);
+
+    app.state::<WakeWordHandle>()
+        .set_channel_strategy(strategy, app.clone())
+        .await
+        .map_err(|e| format!("Failed to restart wake word detection: {}", e))?;
+
+    log::info!("🎚️ Input channel strategy set to {:?}", strategy);
+    Ok(format!("Input channel set to {:?}", strategy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three stereo frames, left then right
+    const STEREO: [f32; 6] = [0.2, 0.6, -0.4, 0.0, 1.0, -1.0];
+
+    #[test]
+    fn average_mixes_stereo_to_mono() {
+        assert_eq!(ChannelStrategy::Average.downmix(&STEREO, 2), vec![0.4, -0.2, 0.0]);
+    }
+
+    #[test]
+    fn picks_one_channel() {
+        assert_eq!(ChannelStrategy::Left.downmix(&STEREO, 2), vec![0.2, -0.4, 1.0]);
+        assert_eq!(ChannelStrategy::Right.downmix(&STEREO, 2), vec![0.6, 0.0, -1.0]);
+        assert_eq!(ChannelStrategy::Channel(1).downmix(&STEREO, 2), vec![0.6, 0.0, -1.0]);
+
+        let four = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+        assert_eq!(ChannelStrategy::Channel(2).downmix(&four, 4), vec![0.3, 0.7]);
+    }
+
+    #[test]
+    fn out_of_range_channels_fall_back_to_the_last() {
+        assert_eq!(ChannelStrategy::Channel(5).downmix(&STEREO, 2), vec![0.6, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn mono_input_passes_through() {
+        let mono = [0.1, -0.2, 0.3];
+        for strategy in [ChannelStrategy::Right, ChannelStrategy::Average, ChannelStrategy::Channel(3)] {
+            assert_eq!(strategy.downmix(&mono, 1), mono);
+        }
+    }
+
+    #[test]
+    fn downmix_into_appends() {
+        let mut out = vec![9.0];
+        ChannelStrategy::Left.downmix_into(&STEREO, 2, &mut out);
+        assert_eq!(out, vec![9.0, 0.2, -0.4, 1.0]);
+    }
+}
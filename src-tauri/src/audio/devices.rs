@@ -0,0 +1,276 @@
+use crate::error::AudioCaptureError;
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Chosen microphone, read when a capture stream opens; `None` picks automatically
+static SELECTED: Mutex<Option<String>> = Mutex::new(None);
+static EXCLUDE_MONITORS: AtomicBool = AtomicBool::new(true);
+
+/// A microphone as `list_input_devices` reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputDevice {
+    /// What to pass to `set_input_device`
+    pub id: String,
+    pub label: String,
+    /// PulseAudio/PipeWire node name, for sources reached through the sound server (Linux)
+    pub node_name: Option<String>,
+    pub is_default: bool,
+    /// Loopback of an output (system audio) rather than a microphone
+    pub is_monitor: bool,
+}
+
+/// Apply the saved choice at startup, before the wake word opens the microphone
+pub fn restore(settings: &super::AudioInputSettings) {
+    *SELECTED.lock().unwrap_or_else(|e| e.into_inner()) = settings.input_device.clone();
+    EXCLUDE_MONITORS.store(settings.exclude_monitors, Ordering::Relaxed);
+}
+
+/// Loopback devices hand Eva whatever the speakers play
+fn looks_like_monitor(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("monitor") || name.contains("loopback")
+}
+
+#[cfg(target_os = "linux")]
+mod sound_server {
+    use std::process::Command;
+
+    /// ALSA devices that route through PulseAudio or PipeWire, most specific first
+    pub const BRIDGE_DEVICES: &[&str] = &["pipewire", "pulse", "default"];
+
+    pub struct Source {
+        pub node_name: String,
+        pub description: String,
+    }
+
+    impl Source {
+        pub fn is_monitor(&self) -> bool {
+            is_monitor(&self.node_name)
+        }
+    }
+
+    pub fn is_monitor(node_name: &str) -> bool {
+        node_name.ends_with(".monitor")
+    }
+
+    fn pactl(args: &[&str]) -> Option<String> {
+        // Parsed by its English labels
+        let output = Command::new("pactl").args(args).env("LC_ALL", "C").output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Sources known to PulseAudio, or to PipeWire through pipewire-pulse
+    pub fn sources() -> Vec<Source> {
+        let Some(listing) = pactl(&["list", "sources"]) else {
+            return Vec::new();
+        };
+        let mut sources = Vec::new();
+        let mut node_name: Option<String> = None;
+        for line in listing.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("Name: ") {
+                node_name = Some(name.to_string());
+            } else if let Some(description) = line.strip_prefix("Description: ") {
+                if let Some(node_name) = node_name.take() {
+                    sources.push(Source { node_name, description: description.to_string() });
+                }
+            }
+        }
+        sources
+    }
+
+    pub fn default_source() -> Option<String> {
+        if let Some(name) = pactl(&["get-default-source"]) {
+            return Some(name.trim().to_string()).filter(|name| !name.is_empty());
+        }
+        // pactl before 15 has no get-default-source
+        pactl(&["info"])?
+            .lines()
+            .find_map(|line| line.strip_prefix("Default Source: "))
+            .map(|name| name.trim().to_string())
+    }
+
+    /// Route the next stream opened on a bridge device to `node_name`; `None` follows the server default
+    pub fn target(node_name: Option<&str>) {
+        match node_name {
+            Some(node_name) => {
+                std::env::set_var("PULSE_SOURCE", node_name);
+                std::env::set_var("PIPEWIRE_NODE", node_name);
+            }
+            None => {
+                std::env::remove_var("PULSE_SOURCE");
+                std::env::remove_var("PIPEWIRE_NODE");
+            }
+        }
+    }
+}
+
+fn find_device(host: &cpal::Host, name: &str) -> Option<cpal::Device> {
+    host.input_devices()
+        .ok()?
+        .find(|device| device.name().map(|device_name| device_name == name).unwrap_or(false))
+}
+
+#[cfg(target_os = "linux")]
+fn bridge_device(host: &cpal::Host) -> Option<cpal::Device> {
+    sound_server::BRIDGE_DEVICES.iter().find_map(|name| find_device(host, name))
+}
+
+/// Open a sound server source through the ALSA bridge device
+#[cfg(target_os = "linux")]
+fn select_source(host: &cpal::Host, node_name: &str) -> Option<cpal::Device> {
+    let device = bridge_device(host)?;
+    sound_server::target(Some(node_name));
+    log::info!("🎤 Using sound server source {}", node_name);
+    Some(device)
+}
+
+/// The default source, unless it's a monitor and those are excluded
+#[cfg(target_os = "linux")]
+fn select_default_source(host: &cpal::Host) -> Option<cpal::Device> {
+    sound_server::target(None);
+    let default = sound_server::default_source()?;
+    if !EXCLUDE_MONITORS.load(Ordering::Relaxed) || !sound_server::is_monitor(&default) {
+        return None;
+    }
+
+    log::warn!("🎤 Default source {} is a monitor of an output; skipping it", default);
+    let source = sound_server::sources().into_iter().find(|source| !source.is_monitor())?;
+    select_source(host, &source.node_name)
+}
+
+/// The microphone capture should open: the chosen one if it's still there, otherwise the
+/// default, skipping monitor (loopback) devices unless they're allowed
+pub fn select_input_device(host: &cpal::Host) -> Result<cpal::Device, AudioCaptureError> {
+    let selected = SELECTED.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(name) = &selected {
+            if sound_server::sources().iter().any(|source| &source.node_name == name) {
+                if let Some(device) = select_source(host, name) {
+                    return Ok(device);
+                }
+            }
+        }
+        sound_server::target(None);
+    }
+
+    if let Some(name) = &selected {
+        match find_device(host, name) {
+            Some(device) => return Ok(device),
+            None => log::warn!("🎤 Input device {} not found; picking one automatically", name),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(device) = select_default_source(host) {
+        return Ok(device);
+    }
+
+    let default = host.default_input_device().ok_or(AudioCaptureError::NoInputDevice)?;
+    let default_name = default.name().unwrap_or_default();
+    if !EXCLUDE_MONITORS.load(Ordering::Relaxed) || !looks_like_monitor(&default_name) {
+        return Ok(default);
+    }
+
+    log::warn!("🎤 Default input {} looks like a monitor; skipping it", default_name);
+    host.input_devices()
+        .map_err(|e| AudioCaptureError::DeviceUnavailable(e.to_string()))?
+        .find(|device| device.name().map(|name| !looks_like_monitor(&name)).unwrap_or(false))
+        .ok_or(AudioCaptureError::NoInputDevice)
+}
+
+/// Input devices, plus on Linux the PulseAudio/PipeWire sources behind them. Monitors are
+/// left out while they're excluded.
+#[tauri::command]
+pub async fn list_input_devices() -> Result<Vec<InputDevice>, String> {
+    let host = super::capture_host();
+    let default_name = host.default_input_device().and_then(|device| device.name().ok());
+    let mut devices: Vec<InputDevice> = host
+        .input_devices()
+        .map_err(|e| format!("Failed to list input devices: {}", e))?
+        .filter_map(|device| device.name().ok())
+        .map(|name| InputDevice {
+            id: name.clone(),
+            label: name.clone(),
+            node_name: None,
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            is_monitor: looks_like_monitor(&name),
+        })
+        .collect();
+
+    #[cfg(target_os = "linux")]
+    {
+        let default_source = sound_server::default_source();
+        devices.extend(sound_server::sources().into_iter().map(|source| InputDevice {
+            id: source.node_name.clone(),
+            is_default: default_source.as_deref() == Some(source.node_name.as_str()),
+            is_monitor: source.is_monitor(),
+            label: source.description,
+            node_name: Some(source.node_name),
+        }));
+    }
+
+    if EXCLUDE_MONITORS.load(Ordering::Relaxed) {
+        devices.retain(|device| !device.is_monitor);
+    }
+    Ok(devices)
+}
+
+async fn restart_capture(app: &AppHandle) -> Result<(), String> {
+    app.state::<WakeWordHandle>()
+        .restart(app.clone())
+        .await
+        .map_err(|e| format!("Failed to restart wake word detection: {}", e))
+}
+
+/// Capture from a device or sound server source by its `list_input_devices` id, or choose
+/// automatically with `None`
+#[tauri::command]
+pub async fn set_input_device(
+    id: Option<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.audio_input.input_device = id.clone();
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    *SELECTED.lock().unwrap_or_else(|e| e.into_inner()) = id.clone();
+    restart_capture(&app).await?;
+
+    let label = id.as_deref().unwrap_or("the default microphone");
+    log::info!("🎤 Input device set to {}", label);
+    Ok(identity.render(&format!("{{name}} will listen on {}", label)))
+}
+
+/// Whether monitor (loopback) sources can be picked, automatically or from the list
+#[tauri::command]
+pub async fn set_exclude_monitors(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.audio_input.exclude_monitors = enabled;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    EXCLUDE_MONITORS.store(enabled, Ordering::Relaxed);
+    restart_capture(&app).await?;
+
+    log::info!("🎤 Monitor sources {}", if enabled { "excluded" } else { "allowed" });
+    Ok(if enabled {
+        identity.render("{name} won't listen to monitors of your speakers")
+    } else {
+        "Monitor sources can be picked as a microphone".to_string()
+    })
+}
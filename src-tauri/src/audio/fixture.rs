@@ -0,0 +1,172 @@
+use crate::audio::{resample_clip, ChannelStrategy};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+/// WAV file, or directory of WAV files, played in place of the microphone
+const FAKE_MIC_ENV: &str = "EVA_FAKE_MIC";
+/// Silence between the files of a fixture directory
+const GAP_SECS: u32 = 1;
+
+static FIXTURE: OnceLock<Option<Fixture>> = OnceLock::new();
+
+/// Recorded audio standing in for the microphone, for end-to-end tests without audio hardware.
+///
+/// It plays once in real time from the moment the first reader opens; every reader hears
+/// the same point in time, so capture started after a wake word picks up right after it.
+/// Silence follows the end.
+pub struct Fixture {
+    path: PathBuf,
+    /// Mono, at `sample_rate`
+    samples: Vec<f32>,
+    sample_rate: u32,
+    started: OnceLock<Instant>,
+    /// Copies resampled for the pipelines that read it
+    resampled: Mutex<Vec<(u32, Arc<Vec<f32>>)>>,
+}
+
+/// Reads a fixture at one sample rate, following the shared clock
+pub struct FixtureReader {
+    samples: Arc<Vec<f32>>,
+    sample_rate: u32,
+    started: Instant,
+    position: usize,
+}
+
+/// `.wav` files at `path`: the file itself, or those directly inside the directory, sorted by name
+pub fn wav_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let entries = std::fs::read_dir(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|file| {
+            file.is_file()
+                && file
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("wav"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Interleaved samples scaled to -1.0..1.0, with the channel count and sample rate
+pub fn read_wav(path: &Path) -> Result<(Vec<f32>, usize, u32), String> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| format!("Failed to open WAV: {}", e))?;
+    let spec = reader.spec();
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>(),
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 * scale))
+                .collect::<Result<Vec<_>, _>>()
+        }
+    }
+    .map_err(|e| format!("Failed to read WAV: {}", e))?;
+    Ok((samples, spec.channels as usize, spec.sample_rate))
+}
+
+impl Fixture {
+    /// Concatenate the files at `path`, resampled to the first file's rate
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let files = wav_files(path)?;
+        let mut samples = Vec::new();
+        let mut sample_rate = 0;
+        for file in &files {
+            let (interleaved, channels, rate) =
+                read_wav(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+            if sample_rate == 0 {
+                sample_rate = rate;
+            } else {
+                samples.extend(std::iter::repeat(0.0).take((GAP_SECS * sample_rate) as usize));
+            }
+            let mono = ChannelStrategy::Average.downmix(&interleaved, channels);
+            samples.extend(resample_clip(mono, rate, sample_rate)?);
+        }
+        if samples.is_empty() {
+            return Err(format!("No audio in {}", path.display()));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            samples,
+            sample_rate,
+            started: OnceLock::new(),
+            resampled: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Start reading at `sample_rate` from the current point; the first reader starts playback
+    pub fn reader(&self, sample_rate: u32) -> Result<FixtureReader, String> {
+        let samples = {
+            let mut resampled = self.resampled.lock().unwrap_or_else(|e| e.into_inner());
+            match resampled.iter().find(|(rate, _)| *rate == sample_rate) {
+                Some((_, samples)) => samples.clone(),
+                None => {
+                    let samples = Arc::new(resample_clip(self.samples.clone(), self.sample_rate, sample_rate)?);
+                    resampled.push((sample_rate, samples.clone()));
+                    samples
+                }
+            }
+        };
+
+        let started = *self.started.get_or_init(|| {
+            log::info!("🧪 Playing {} as the microphone", self.path.display());
+            Instant::now()
+        });
+        let mut reader = FixtureReader {
+            samples,
+            sample_rate,
+            started,
+            position: 0,
+        };
+        reader.position = reader.due();
+        Ok(reader)
+    }
+}
+
+impl FixtureReader {
+    /// Samples the clock has reached so far
+    fn due(&self) -> usize {
+        (self.started.elapsed().as_secs_f64() * self.sample_rate as f64) as usize
+    }
+
+    /// Append everything played since the last read; silence once the fixture is over
+    pub fn read_into(&mut self, out: &mut Vec<f32>) {
+        let due = self.due();
+        if due <= self.position {
+            return;
+        }
+        let available = self.samples.len().clamp(self.position, due);
+        out.extend_from_slice(&self.samples[self.position..available]);
+        out.extend(std::iter::repeat(0.0).take(due - available));
+        self.position = due;
+    }
+}
+
+/// The fixture named by `EVA_FAKE_MIC`, loaded on first use; `None` means use real devices
+pub fn fake_microphone() -> Option<&'static Fixture> {
+    FIXTURE
+        .get_or_init(|| {
+            let path = std::env::var_os(FAKE_MIC_ENV)?;
+            match Fixture::load(Path::new(&path)) {
+                Ok(fixture) => {
+                    log::warn!("🧪 {} is set - using {} instead of the microphone", FAKE_MIC_ENV, fixture.path.display());
+                    Some(fixture)
+                }
+                Err(e) => {
+                    log::error!("🧪 Ignoring {}: {}", FAKE_MIC_ENV, e);
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
@@ -0,0 +1,144 @@
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use cpal::traits::DeviceTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+static HOST: Mutex<CaptureHost> = Mutex::new(CaptureHost::System);
+static PREFER_NATIVE_RATE: AtomicBool = AtomicBool::new(false);
+
+/// Audio API the microphone is opened through.
+///
+/// `System` is WASAPI on Windows, in event-driven shared mode; cpal has no WASAPI exclusive
+/// mode, so drivers that need exclusive access for low latency go through ASIO instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaptureHost {
+    /// WASAPI on Windows, CoreAudio on macOS, ALSA on Linux
+    #[default]
+    System,
+    /// Steinberg ASIO drivers; Windows builds with the `asio` feature only
+    Asio,
+}
+
+/// Returned by `get_capture_hosts`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureHostsState {
+    pub available: Vec<CaptureHost>,
+    pub selected: CaptureHost,
+    pub prefer_native_rate: bool,
+}
+
+/// Apply the saved host at startup, before the wake word opens the microphone
+pub fn restore(settings: &super::AudioInputSettings) {
+    *HOST.lock().unwrap_or_else(|e| e.into_inner()) = settings.capture_host;
+    PREFER_NATIVE_RATE.store(settings.prefer_native_rate, Ordering::Relaxed);
+}
+
+pub fn available_hosts() -> Vec<CaptureHost> {
+    let mut hosts = vec![CaptureHost::System];
+    #[cfg(all(target_os = "windows", feature = "asio"))]
+    if cpal::available_hosts().contains(&cpal::HostId::Asio) {
+        hosts.push(CaptureHost::Asio);
+    }
+    hosts
+}
+
+/// Host the microphone opens on; falls back to the system one when ASIO isn't there
+pub fn capture_host() -> cpal::Host {
+    #[cfg(all(target_os = "windows", feature = "asio"))]
+    if *HOST.lock().unwrap_or_else(|e| e.into_inner()) == CaptureHost::Asio {
+        match cpal::host_from_id(cpal::HostId::Asio) {
+            Ok(host) => return host,
+            Err(e) => log::warn!("🎤 ASIO unavailable ({}); using WASAPI", e),
+        }
+    }
+    cpal::default_host()
+}
+
+/// The device's config at `sample_rate` when it offers it and that's preferred, so nothing needs
+/// resampling; otherwise its default config
+pub fn input_config(
+    device: &cpal::Device,
+    sample_rate: u32,
+) -> Result<cpal::SupportedStreamConfig, cpal::DefaultStreamConfigError> {
+    if PREFER_NATIVE_RATE.load(Ordering::Relaxed) {
+        let rate = cpal::SampleRate(sample_rate);
+        let native = device.supported_input_configs().ok().and_then(|mut configs| {
+            configs.find(|config| config.min_sample_rate() <= rate && rate <= config.max_sample_rate())
+        });
+        match native {
+            Some(config) => return Ok(config.with_sample_rate(rate)),
+            None => log::info!("🎤 Device has no {} Hz mode; using its default", sample_rate),
+        }
+    }
+    device.default_input_config()
+}
+
+async fn restart_capture(app: &AppHandle) -> Result<(), String> {
+    app.state::<WakeWordHandle>()
+        .restart(app.clone())
+        .await
+        .map_err(|e| format!("Failed to restart wake word detection: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_capture_hosts(state: tauri::State<'_, SettingsState>) -> Result<CaptureHostsState, String> {
+    let settings = state.lock().await;
+    Ok(CaptureHostsState {
+        available: available_hosts(),
+        selected: settings.audio_input.capture_host,
+        prefer_native_rate: settings.audio_input.prefer_native_rate,
+    })
+}
+
+/// Switch the audio API and reopen the microphone; the input device choice may not exist there
+#[tauri::command]
+pub async fn set_capture_host(
+    host: CaptureHost,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if !available_hosts().contains(&host) {
+        return Err(format!("{:?} isn't available in this build", host));
+    }
+
+    let mut settings = state.lock().await;
+    settings.audio_input.capture_host = host;
+    settings.save(&app)?;
+    drop(settings);
+
+    *HOST.lock().unwrap_or_else(|e| e.into_inner()) = host;
+    restart_capture(&app).await?;
+
+    log::info!("🎤 Capture host set to {:?}", host);
+    Ok(match host {
+        CaptureHost::System => "Microphone opens through the system audio API".to_string(),
+        CaptureHost::Asio => "Microphone opens through ASIO".to_string(),
+    })
+}
+
+/// Open the microphone at 16 kHz when it supports it, skipping the resampler
+#[tauri::command]
+pub async fn set_prefer_native_rate(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.audio_input.prefer_native_rate = enabled;
+    settings.save(&app)?;
+    drop(settings);
+
+    PREFER_NATIVE_RATE.store(enabled, Ordering::Relaxed);
+    restart_capture(&app).await?;
+
+    log::info!("🎤 Native 16 kHz capture {}", if enabled { "preferred" } else { "off" });
+    Ok(if enabled {
+        "Microphone opens at 16 kHz when it can".to_string()
+    } else {
+        "Microphone opens at its default rate".to_string()
+    })
+}
@@ -0,0 +1,62 @@
+use crate::error::AudioCaptureError;
+use cpal::traits::DeviceTrait;
+use cpal::{FromSample, Sample, SampleFormat, SizedSample};
+
+/// Initial capacity of the conversion buffer; it grows once if a device sends more
+const CONVERT_BUFFER_CAPACITY: usize = 8192;
+
+/// Open an input stream in the device's own sample format and hand `on_samples` interleaved
+/// f32 in -1.0..1.0, so capture code doesn't care whether the device speaks u8, i32 or f64.
+pub fn build_input_stream<D, E>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sample_format: SampleFormat,
+    on_samples: D,
+    on_error: E,
+) -> Result<cpal::Stream, AudioCaptureError>
+where
+    D: FnMut(&[f32]) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    match sample_format {
+        SampleFormat::I8 => build::<i8, _, _>(device, config, on_samples, on_error),
+        SampleFormat::I16 => build::<i16, _, _>(device, config, on_samples, on_error),
+        SampleFormat::I32 => build::<i32, _, _>(device, config, on_samples, on_error),
+        SampleFormat::I64 => build::<i64, _, _>(device, config, on_samples, on_error),
+        SampleFormat::U8 => build::<u8, _, _>(device, config, on_samples, on_error),
+        SampleFormat::U16 => build::<u16, _, _>(device, config, on_samples, on_error),
+        SampleFormat::U32 => build::<u32, _, _>(device, config, on_samples, on_error),
+        SampleFormat::U64 => build::<u64, _, _>(device, config, on_samples, on_error),
+        SampleFormat::F32 => build::<f32, _, _>(device, config, on_samples, on_error),
+        SampleFormat::F64 => build::<f64, _, _>(device, config, on_samples, on_error),
+        format => Err(AudioCaptureError::UnsupportedFormat(format!("{:?}", format))),
+    }
+}
+
+fn build<T, D, E>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut on_samples: D,
+    on_error: E,
+) -> Result<cpal::Stream, AudioCaptureError>
+where
+    T: SizedSample + Send + 'static,
+    f32: FromSample<T>,
+    D: FnMut(&[f32]) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    // Reused across callbacks so the real-time thread doesn't allocate
+    let mut converted = Vec::<f32>::with_capacity(CONVERT_BUFFER_CAPACITY);
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                converted.clear();
+                converted.extend(data.iter().map(|&sample| sample.to_sample::<f32>()));
+                on_samples(&converted);
+            },
+            on_error,
+            None,
+        )
+        .map_err(|e| AudioCaptureError::Stream(format!("Failed to build input stream: {}", e)))
+}
@@ -0,0 +1,232 @@
+use crate::audio::{self, FixtureReader};
+use crate::privacy;
+use crate::settings::SettingsState;
+use crate::sleep_inhibit::{self, InhibitReason};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Rate the Realtime API expects, same as the microphone path
+const SYSTEM_AUDIO_SAMPLE_RATE: u32 = 24000;
+/// Audio per `system-audio` event (100ms)
+const CHUNK_SAMPLES: usize = SYSTEM_AUDIO_SAMPLE_RATE as usize / 10;
+const CHUNK_DURATION: Duration = Duration::from_millis(100);
+
+/// Bumped on every start/stop so only the latest capture thread keeps running
+static CAPTURE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// What Eva hears while recording a conversation turn.
+///
+/// The wake word always listens to the microphone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaptureSource {
+    #[default]
+    Microphone,
+    /// Whatever plays on the default output device, e.g. a meeting to translate or summarize
+    SystemAudio,
+}
+
+/// Event payload for `system-audio`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemAudioChunk {
+    pub audio: String, // base64 PCM16 mono
+    pub sample_rate: u32,
+}
+
+/// System audio capture uses WASAPI loopback, so it's Windows only for now
+pub fn is_supported() -> bool {
+    cfg!(target_os = "windows")
+}
+
+fn emit_chunk(app: &AppHandle, samples: &[f32]) {
+    let bytes: Vec<u8> = samples
+        .iter()
+        .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+        .collect();
+    let chunk = SystemAudioChunk {
+        audio: base64::engine::general_purpose::STANDARD.encode(bytes),
+        sample_rate: SYSTEM_AUDIO_SAMPLE_RATE,
+    };
+    if let Err(e) = app.emit("system-audio", &chunk) {
+        log::error!("Failed to emit system audio: {}", e);
+    }
+}
+
+/// Stream the `EVA_FAKE_MIC` recording as `system-audio` until the generation changes
+fn play_fixture(app: AppHandle, generation: u64, mut reader: FixtureReader) {
+    let mut samples = Vec::new();
+    while CAPTURE_GENERATION.load(Ordering::Relaxed) == generation {
+        std::thread::sleep(CHUNK_DURATION);
+        reader.read_into(&mut samples);
+        // It stands in for the microphone, so muting silences it too
+        if privacy::is_mic_muted() {
+            samples.clear();
+            continue;
+        }
+        while samples.len() >= CHUNK_SAMPLES {
+            emit_chunk(&app, &samples[..CHUNK_SAMPLES]);
+            samples.drain(..CHUNK_SAMPLES);
+        }
+    }
+    log::info!("🧪 Fake microphone capture stopped");
+}
+
+#[cfg(target_os = "windows")]
+mod wasapi {
+    use super::{emit_chunk, CAPTURE_GENERATION, CHUNK_SAMPLES, SYSTEM_AUDIO_SAMPLE_RATE};
+    use crate::audio::{self, ChannelStrategy, StreamResampler};
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tauri::AppHandle;
+    /// How often the capture thread checks whether it was stopped
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Capture the default output device until the generation changes.
+    ///
+    /// `ready` reports whether the stream opened. WASAPI delivers nothing while
+    /// nothing plays, so silence simply means no events.
+    pub fn run(app: AppHandle, generation: u64, ready: mpsc::Sender<Result<(), String>>) {
+        let (sender, receiver) = mpsc::channel::<Vec<f32>>();
+        let (stream, mut resampler) = match open_stream(sender) {
+            Ok(opened) => opened,
+            Err(e) => {
+                let _ = ready.send(Err(e));
+                return;
+            }
+        };
+        let _ = ready.send(Ok(()));
+
+        let mut resampled = Vec::new();
+        while CAPTURE_GENERATION.load(Ordering::Relaxed) == generation {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(mono) => {
+                    if let Err(e) = resampler.process_into(&mono, &mut resampled) {
+                        log::error!("{}", e);
+                        break;
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            while resampled.len() >= CHUNK_SAMPLES {
+                emit_chunk(&app, &resampled[..CHUNK_SAMPLES]);
+                resampled.drain(..CHUNK_SAMPLES);
+            }
+        }
+
+        drop(stream);
+        log::info!("🔁 System audio capture stopped");
+    }
+
+    fn open_stream(sender: mpsc::Sender<Vec<f32>>) -> Result<(cpal::Stream, StreamResampler), String> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or("No output device available")?;
+        let config = device.default_output_config()
+            .map_err(|e| format!("Failed to get output config: {}", e))?;
+        let channels = config.channels() as usize;
+        let resampler = StreamResampler::new(config.sample_rate().0, SYSTEM_AUDIO_SAMPLE_RATE)?;
+
+        // cpal opens an input stream on a render device in WASAPI loopback mode
+        let stream_config: cpal::StreamConfig = config.clone().into();
+        let stream = audio::build_input_stream(
+            &device,
+            &stream_config,
+            config.sample_format(),
+            move |samples: &[f32]| {
+                let _ = sender.send(ChannelStrategy::Average.downmix(samples, channels));
+            },
+            |err| log::error!("System audio stream error: {}", err),
+        )
+        .map_err(|e| format!("Failed to open system audio: {}", e))?;
+        stream.play()
+            .map_err(|e| format!("Failed to start system audio capture: {}", e))?;
+
+        log::info!("🔁 Capturing system audio from {}", device.name().unwrap_or_default());
+        Ok((stream, resampler))
+    }
+}
+
+#[tauri::command]
+pub async fn system_audio_available() -> Result<bool, String> {
+    Ok(is_supported())
+}
+
+/// The configured source, or the microphone where system audio isn't supported.
+///
+/// With `EVA_FAKE_MIC` set, capture arrives as `system-audio` events played from the recording.
+#[tauri::command]
+pub async fn get_capture_source(state: tauri::State<'_, SettingsState>) -> Result<CaptureSource, String> {
+    if audio::fake_microphone().is_some() {
+        return Ok(CaptureSource::SystemAudio);
+    }
+    let source = state.lock().await.audio_input.capture_source;
+    Ok(if is_supported() { source } else { CaptureSource::Microphone })
+}
+
+#[tauri::command]
+pub async fn set_capture_source(
+    source: CaptureSource,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if source == CaptureSource::SystemAudio && !is_supported() {
+        return Err("System audio capture is only available on Windows".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.audio_input.capture_source = source;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    log::info!("🔁 Capture source set to {:?}", source);
+    Ok(identity.render(match source {
+        CaptureSource::Microphone => "{name} will listen to the microphone",
+        CaptureSource::SystemAudio => "{name} will listen to system audio",
+    }))
+}
+
+/// Start streaming system audio as `system-audio` events, replacing any running capture
+#[tauri::command]
+pub async fn start_system_audio_capture(app: AppHandle) -> Result<(), String> {
+    let generation = CAPTURE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+
+    if let Some(fixture) = audio::fake_microphone() {
+        let reader = fixture.reader(SYSTEM_AUDIO_SAMPLE_RATE)?;
+        std::thread::spawn(move || play_fixture(app, generation, reader));
+        sleep_inhibit::hold(InhibitReason::Transcription);
+        return Ok(());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let (ready, opened) = std::sync::mpsc::channel();
+        std::thread::spawn(move || wasapi::run(app, generation, ready));
+        tokio::task::spawn_blocking(move || opened.recv())
+            .await
+            .map_err(|e| format!("System audio capture task failed: {}", e))?
+            .map_err(|_| "System audio capture thread exited".to_string())??;
+        sleep_inhibit::hold(InhibitReason::Transcription);
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (app, generation);
+        Err("System audio capture is only available on Windows".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn stop_system_audio_capture() -> Result<(), String> {
+    CAPTURE_GENERATION.fetch_add(1, Ordering::Relaxed);
+    sleep_inhibit::release(InhibitReason::Transcription);
+    Ok(())
+}
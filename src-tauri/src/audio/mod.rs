@@ -1,10 +1,20 @@
-/// Audio processing module for wake word detection
-pub mod config;
-pub mod debug;
-pub mod processor;
-pub mod stream;
+/// Audio processing shared by the capture pipelines
+pub mod buffer_size;
+pub mod channels;
+pub mod devices;
+pub mod fixture;
+pub mod host;
+pub mod input;
+pub mod loopback;
+pub mod overflow;
+pub mod resampler;
 
-pub use config::*;
-pub use debug::*;
-pub use processor::*;
-pub use stream::*;
+pub use buffer_size::*;
+pub use channels::*;
+pub use devices::*;
+pub use fixture::*;
+pub use host::*;
+pub use input::*;
+pub use loopback::*;
+pub use overflow::*;
+pub use resampler::*;
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// Queue that had to drop audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AudioOverflowSource {
+    /// Capture callback -> Porcupine frame queue
+    WakeWord,
+    /// Frontend queue of audio waiting to be sent to the Realtime API
+    RealtimeSend,
+}
+
+/// Event payload for `audio-overflow`.
+///
+/// Queues drop their oldest audio when full, so a stalled consumer catches up
+/// on live audio instead of falling further behind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioOverflowEvent {
+    pub source: AudioOverflowSource,
+    /// Audio dropped since the previous event
+    pub dropped_ms: u64,
+    /// Audio dropped since the queue was created
+    pub total_dropped_ms: u64,
+}
+
+pub fn emit_overflow(app: &AppHandle, event: &AudioOverflowEvent) {
+    log::warn!(
+        "⚠️  {:?} audio queue overflowed, dropped {}ms ({}ms total)",
+        event.source, event.dropped_ms, event.total_dropped_ms
+    );
+    if let Err(e) = app.emit("audio-overflow", event) {
+        log::error!("Failed to emit audio overflow event: {}", e);
+    }
+}
+
+/// Re-emit an overflow from a frontend queue so all `audio-overflow` events come from one place
+#[tauri::command]
+pub async fn report_audio_overflow(
+    source: AudioOverflowSource,
+    dropped_ms: u64,
+    total_dropped_ms: u64,
+    app: AppHandle,
+) -> Result<(), String> {
+    emit_overflow(&app, &AudioOverflowEvent { source, dropped_ms, total_dropped_ms });
+    Ok(())
+}
+
+/// Event payload for `audio-congestion`, sent when the Realtime upload falls behind and
+/// again when it catches up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioCongestionEvent {
+    /// The sender is in degraded mode: fewer, larger appends, buffering locally in between
+    pub congested: bool,
+    /// Captured audio not yet on the wire
+    pub backlog_ms: u64,
+}
+
+/// Re-emit the frontend sender's congestion state as `audio-congestion`
+#[tauri::command]
+pub async fn report_audio_congestion(congested: bool, backlog_ms: u64, app: AppHandle) -> Result<(), String> {
+    if congested {
+        log::warn!("🐢 Realtime upload can't keep up, {}ms behind - streaming in degraded mode", backlog_ms);
+    } else {
+        log::info!("📶 Realtime upload caught up");
+    }
+    app.emit("audio-congestion", AudioCongestionEvent { congested, backlog_ms })
+        .map_err(|e| format!("Failed to emit audio congestion event: {}", e))
+}
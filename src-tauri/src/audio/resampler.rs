@@ -0,0 +1,169 @@
+use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
+
+/// Input frames per resampler pass; ~21ms at 48kHz
+const STREAM_CHUNK_FRAMES: usize = 1024;
+
+fn sinc_parameters() -> SincInterpolationParameters {
+    SincInterpolationParameters {
+        sinc_len: 256,
+        f_cutoff: 0.95,
+        interpolation: SincInterpolationType::Linear,
+        oversampling_factor: 256,
+        window: WindowFunction::BlackmanHarris2,
+    }
+}
+
+/// Mono sinc resampler for capture callbacks, which deliver buffers of any length.
+///
+/// Input is queued until a full chunk is available, so output trails the input
+/// by up to one chunk. The sinc filter's own delay is trimmed from the start, so
+/// output sample `n` lines up with input time `n / output_rate` whatever the device buffer size.
+pub struct StreamResampler {
+    /// `None` when the rates already match
+    resampler: Option<SincFixedIn<f32>>,
+    pending: Vec<f32>,
+    output: Vec<f32>,
+    /// Leading output frames still to drop, the filter delay
+    delay_frames: usize,
+}
+
+impl StreamResampler {
+    pub fn new(input_sample_rate: u32, output_sample_rate: u32) -> Result<Self, String> {
+        if input_sample_rate == output_sample_rate {
+            return Ok(Self {
+                resampler: None,
+                pending: Vec::new(),
+                output: Vec::new(),
+                delay_frames: 0,
+            });
+        }
+
+        let resampler = SincFixedIn::<f32>::new(
+            output_sample_rate as f64 / input_sample_rate as f64,
+            1.0,
+            sinc_parameters(),
+            STREAM_CHUNK_FRAMES,
+            1,
+        ).map_err(|e| format!("Failed to create resampler: {}", e))?;
+
+        Ok(Self {
+            output: vec![0.0; resampler.output_frames_max()],
+            pending: Vec::with_capacity(STREAM_CHUNK_FRAMES * 2),
+            delay_frames: resampler.output_delay(),
+            resampler: Some(resampler),
+        })
+    }
+
+    /// Resample `samples` and append whatever output is ready to `out`
+    pub fn process_into(&mut self, samples: &[f32], out: &mut Vec<f32>) -> Result<(), String> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            out.extend_from_slice(samples);
+            return Ok(());
+        };
+
+        self.pending.extend_from_slice(samples);
+
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= resampler.input_frames_next() {
+            let (frames_in, frames_out) = resampler
+                .process_into_buffer(&[&self.pending[consumed..]], &mut [&mut self.output[..]], None)
+                .map_err(|e| format!("Resampling error: {}", e))?;
+            let skip = self.delay_frames.min(frames_out);
+            self.delay_frames -= skip;
+            out.extend_from_slice(&self.output[skip..frames_out]);
+            consumed += frames_in;
+        }
+        self.pending.drain(..consumed);
+
+        Ok(())
+    }
+}
+
+/// Resample a complete mono clip in one pass, aligned with the input and trimmed to its duration
+pub fn resample_clip(samples: Vec<f32>, input_sample_rate: u32, output_sample_rate: u32) -> Result<Vec<f32>, String> {
+    if input_sample_rate == output_sample_rate || samples.is_empty() {
+        return Ok(samples);
+    }
+
+    let ratio = output_sample_rate as f64 / input_sample_rate as f64;
+    let expected_frames = (samples.len() as f64 * ratio).round() as usize;
+    let mut resampler = SincFixedIn::<f32>::new(
+        ratio,
+        1.0,
+        sinc_parameters(),
+        samples.len(),
+        1,
+    ).map_err(|e| format!("Failed to create resampler: {}", e))?;
+    let delay_frames = resampler.output_delay();
+
+    let mut output = resampler.process(&[samples], None)
+        .map_err(|e| format!("Resampling error: {}", e))?
+        .remove(0);
+
+    // Flush the samples still held back by the sinc filter delay
+    let tail = resampler.process_partial::<Vec<f32>>(None, None)
+        .map_err(|e| format!("Resampling error: {}", e))?;
+    output.extend_from_slice(&tail[0]);
+
+    // The first frames are the filter warming up; very short clips come up short and are padded
+    output.drain(..delay_frames.min(output.len()));
+    output.resize(expected_frames, 0.0);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn impulse(len: usize, at: usize) -> Vec<f32> {
+        let mut samples = vec![0.0; len];
+        samples[at] = 1.0;
+        samples
+    }
+
+    fn peak(samples: &[f32]) -> usize {
+        (0..samples.len()).max_by(|&a, &b| samples[a].abs().total_cmp(&samples[b].abs())).unwrap()
+    }
+
+    fn stream(samples: &[f32], input_rate: u32, output_rate: u32, chunk: usize) -> Vec<f32> {
+        let mut resampler = StreamResampler::new(input_rate, output_rate).unwrap();
+        let mut out = Vec::new();
+        for part in samples.chunks(chunk) {
+            resampler.process_into(part, &mut out).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn stream_output_lines_up_with_the_input_whatever_the_buffer_size() {
+        // (input rate, output rate, impulse position, where it lands)
+        for (input_rate, output_rate, at, expected) in [(48000, 16000, 3000, 1000), (16000, 48000, 1000, 3000)] {
+            let input = impulse(at * 3, at);
+            for chunk in [input.len(), 37, 480, 1024] {
+                let out = stream(&input, input_rate, output_rate, chunk);
+                assert_eq!(peak(&out), expected, "{} -> {} in chunks of {}", input_rate, output_rate, chunk);
+            }
+        }
+    }
+
+    #[test]
+    fn stream_passes_matching_rates_through() {
+        let input = impulse(100, 10);
+        assert_eq!(stream(&input, 16000, 16000, 7), input);
+    }
+
+    #[test]
+    fn clips_line_up_and_keep_their_duration() {
+        let out = resample_clip(impulse(9000, 3000), 48000, 16000).unwrap();
+        assert_eq!(out.len(), 3000);
+        assert_eq!(peak(&out), 1000);
+
+        let out = resample_clip(impulse(2000, 1000), 16000, 48000).unwrap();
+        assert_eq!(out.len(), 6000);
+        assert_eq!(peak(&out), 3000);
+
+        assert_eq!(resample_clip(vec![0.0; 1001], 44100, 16000).unwrap().len(), 363);
+        // Shorter than the filter delay, so padded out to the expected length
+        assert_eq!(resample_clip(vec![0.0; 10], 48000, 16000).unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// Audio encoding on the Realtime WebSocket. G.711 is 8kHz, a sixth of PCM16's bandwidth;
+/// the API doesn't offer compressed formats such as Opus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeAudioFormat {
+    /// 24kHz 16-bit PCM
+    #[default]
+    Pcm16,
+    G711Ulaw,
+    G711Alaw,
+}
+
+impl RealtimeAudioFormat {
+    fn kbps(self) -> u32 {
+        match self {
+            RealtimeAudioFormat::Pcm16 => 384,
+            RealtimeAudioFormat::G711Ulaw | RealtimeAudioFormat::G711Alaw => 64,
+        }
+    }
+}
+
+/// `input_audio_format` / `output_audio_format` for the Realtime session. The frontend
+/// encodes and decodes, so the rest of Eva always sees 24kHz PCM16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioFormatSettings {
+    pub input: RealtimeAudioFormat,
+    pub output: RealtimeAudioFormat,
+}
+
+#[tauri::command]
+pub async fn get_audio_format(state: tauri::State<'_, SettingsState>) -> Result<AudioFormatSettings, String> {
+    Ok(state.lock().await.audio_format)
+}
+
+/// Save the wire formats; they take effect when the Realtime session next connects
+#[tauri::command]
+pub async fn set_audio_format(
+    audio_format: AudioFormatSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.audio_format = audio_format;
+    settings.save(&app)?;
+    drop(settings);
+
+    if let Err(e) = app.emit("audio-format-changed", &audio_format) {
+        log::error!("Failed to emit audio format change: {}", e);
+    }
+
+    log::info!("🎚️ Realtime audio format set to {:?}", audio_format);
+    Ok(format!(
+        "Sending {} kbps, receiving {} kbps from the next connection",
+        audio_format.input.kbps(),
+        audio_format.output.kbps()
+    ))
+}
@@ -0,0 +1,114 @@
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use crate::tray;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::ManagerExt;
+
+/// Passed by the login item so a launch at login can be told apart from a manual one
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// What Eva does when the OS launches it at login.
+///
+/// Whether it launches at all is the OS login item itself, so it isn't stored here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutostartSettings {
+    /// Stay in the tray instead of opening the main window
+    pub start_minimized: bool,
+    /// Start wake word listening right away
+    pub listen_on_start: bool,
+}
+
+impl Default for AutostartSettings {
+    fn default() -> Self {
+        Self {
+            start_minimized: true,
+            listen_on_start: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutostartStatus {
+    /// Registered as a login item
+    pub enabled: bool,
+    /// This run was started by the login item
+    pub launched_at_login: bool,
+    pub start_minimized: bool,
+    pub listen_on_start: bool,
+}
+
+pub fn launched_at_login() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+/// Show the main window unless this is a minimized launch at login, and start
+/// listening if configured. The window starts hidden, so it never flashes.
+pub fn apply_on_launch(app: &AppHandle, settings: &AutostartSettings) {
+    let at_login = launched_at_login();
+
+    if at_login && settings.start_minimized {
+        log::info!("🚀 Launched at login, staying in the tray");
+    } else {
+        tray::show_main_window(app);
+    }
+
+    if at_login && settings.listen_on_start {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let porcupine = app.state::<WakeWordHandle>().inner().clone();
+            match porcupine.start_listening(app.clone()).await {
+                Ok(_) => log::info!("🚀 Wake word listening started at login"),
+                Err(e) => log::warn!("Failed to start wake word listening at login: {}", e),
+            }
+        });
+    }
+}
+
+#[tauri::command]
+pub async fn get_autostart(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<AutostartStatus, String> {
+    let enabled = app.autolaunch().is_enabled().map_err(|e| e.to_string())?;
+    let settings = state.lock().await.autostart.clone();
+    Ok(AutostartStatus {
+        enabled,
+        launched_at_login: launched_at_login(),
+        start_minimized: settings.start_minimized,
+        listen_on_start: settings.listen_on_start,
+    })
+}
+
+#[tauri::command]
+pub async fn set_autostart(
+    enabled: bool,
+    start_minimized: bool,
+    listen_on_start: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let autolaunch = app.autolaunch();
+    let registration = if enabled { autolaunch.enable() } else { autolaunch.disable() };
+    registration.map_err(|e| format!("Failed to update login item: {}", e))?;
+
+    let mut settings = state.lock().await;
+    settings.autostart.start_minimized = start_minimized;
+    settings.autostart.listen_on_start = listen_on_start;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    log::info!(
+        "🚀 Launch at login {} (minimized: {}, listen: {})",
+        if enabled { "enabled" } else { "disabled" },
+        start_minimized,
+        listen_on_start
+    );
+    Ok(if enabled {
+        identity.render("{name} will start when you log in")
+    } else {
+        identity.render("{name} won't start at login")
+    })
+}
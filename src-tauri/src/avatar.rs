@@ -0,0 +1,482 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+/// Custom protocol avatar files are served on; the webview loads
+/// `avatar://localhost/<id>/<file>` (`http://avatar.localhost/...` on Windows)
+pub const PROTOCOL: &str = "avatar";
+
+const AVATARS_DIR: &str = "avatars";
+const METADATA_FILE: &str = "avatar.json";
+/// Live2D models are copied with their whole folder, so refuse to copy something huge by mistake
+const MAX_IMPORT_BYTES: u64 = 512 * 1024 * 1024;
+
+/// glTF binary header: magic, version, length; then the JSON chunk's length and type
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_JSON_CHUNK: &[u8; 4] = b"JSON";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AvatarSettings {
+    /// Id of the avatar Eva appears as; `None` uses the built-in look
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AvatarKind {
+    Vrm,
+    Live2d,
+}
+
+/// Stored next to the model as `avatar.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AvatarMetadata {
+    name: String,
+    kind: AvatarKind,
+    /// Model file the viewer loads, relative to the avatar folder
+    entry: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvatarInfo {
+    pub id: String,
+    pub name: String,
+    pub kind: AvatarKind,
+    /// Path to request on the avatar protocol, e.g. `my-model/model.vrm`
+    pub entry: String,
+    pub active: bool,
+}
+
+fn avatars_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(AVATARS_DIR))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Checks the glTF container and the VRM extension; returns the model's title if it has one
+fn validate_vrm(path: &Path) -> Result<Option<String>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if bytes.get(0..4) != Some(GLB_MAGIC.as_slice()) || read_u32(&bytes, 4) != Some(2) {
+        return Err("Not a VRM file (expected glTF 2.0 binary)".to_string());
+    }
+    if read_u32(&bytes, 8).map(|length| length as usize) != Some(bytes.len()) {
+        return Err("VRM file is truncated".to_string());
+    }
+    let json_length = read_u32(&bytes, 12).ok_or("VRM file is truncated")? as usize;
+    if bytes.get(16..20) != Some(GLB_JSON_CHUNK.as_slice()) {
+        return Err("VRM file has no glTF JSON chunk".to_string());
+    }
+    let json = bytes.get(20..20 + json_length).ok_or("VRM file is truncated")?;
+    let gltf: serde_json::Value =
+        serde_json::from_slice(json).map_err(|e| format!("Invalid glTF JSON: {}", e))?;
+
+    // VRM 0.x uses the `VRM` extension, VRM 1.0 `VRMC_vrm`
+    let extensions = &gltf["extensions"];
+    let title = if extensions.get("VRMC_vrm").is_some() {
+        extensions["VRMC_vrm"]["meta"]["name"].as_str()
+    } else if extensions.get("VRM").is_some() {
+        extensions["VRM"]["meta"]["title"].as_str()
+    } else {
+        return Err("glTF file has no VRM extension".to_string());
+    };
+    Ok(title.filter(|title| !title.trim().is_empty()).map(|title| title.trim().to_string()))
+}
+
+/// Checks the Cubism 3+ model settings file and that the files it references exist
+fn validate_live2d(path: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let model: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid model3.json: {}", e))?;
+    let dir = path.parent().ok_or("Model file has no folder")?;
+    // Only the model's folder is copied, so references have to stay inside it
+    let root = dir.canonicalize().map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let references = &model["FileReferences"];
+    let moc = references["Moc"].as_str().ok_or("model3.json has no Moc file")?;
+    let textures: Vec<&str> = references["Textures"]
+        .as_array()
+        .map(|textures| textures.iter().filter_map(|texture| texture.as_str()).collect())
+        .unwrap_or_default();
+    if textures.is_empty() {
+        return Err("model3.json lists no textures".to_string());
+    }
+    for file in std::iter::once(moc).chain(textures) {
+        let Ok(resolved) = dir.join(file).canonicalize() else {
+            return Err(format!("Missing model file: {}", file));
+        };
+        if !resolved.starts_with(&root) {
+            return Err(format!("Model file is outside the model's folder: {}", file));
+        }
+        if !resolved.is_file() {
+            return Err(format!("Missing model file: {}", file));
+        }
+    }
+    Ok(())
+}
+
+fn folder_size(dir: &Path) -> Result<u64, String> {
+    let mut total = 0;
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let meta = entry.metadata().map_err(|e| e.to_string())?;
+        total += if meta.is_dir() { folder_size(&entry.path())? } else { meta.len() };
+        if total > MAX_IMPORT_BYTES {
+            break;
+        }
+    }
+    Ok(total)
+}
+
+/// Links are refused rather than followed, so an import can't pull in files from elsewhere
+fn copy_folder(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(from).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        if file_type.is_symlink() {
+            return Err(format!(
+                "The model's folder contains a link ({}); copy the file in instead",
+                entry.path().display()
+            ));
+        }
+        if file_type.is_dir() {
+            copy_folder(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// A folder name from the model name, unique among installed avatars
+fn unique_id(dir: &Path, name: &str) -> String {
+    let mut slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    slug = slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+    if slug.is_empty() {
+        slug = "avatar".to_string();
+    }
+
+    let mut id = slug.clone();
+    let mut suffix = 2;
+    while dir.join(&id).exists() {
+        id = format!("{}-{}", slug, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+fn load_metadata(dir: &Path) -> Option<AvatarMetadata> {
+    let contents = fs::read_to_string(dir.join(METADATA_FILE)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn install(app: &AppHandle, source: &Path) -> Result<AvatarInfo, String> {
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or("No file selected")?;
+    let lower = file_name.to_lowercase();
+    let avatars = avatars_dir(app)?;
+    fs::create_dir_all(&avatars).map_err(|e| format!("Failed to create avatars folder: {}", e))?;
+
+    let (metadata, id) = if lower.ends_with(".vrm") {
+        let stem = file_name[..file_name.len() - ".vrm".len()].to_string();
+        let name = validate_vrm(source)?.unwrap_or(stem);
+        let id = unique_id(&avatars, &name);
+        let dir = avatars.join(&id);
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        fs::copy(source, dir.join("model.vrm")).map_err(|e| format!("Failed to copy model: {}", e))?;
+        (AvatarMetadata { name, kind: AvatarKind::Vrm, entry: "model.vrm".to_string() }, id)
+    } else if lower.ends_with(".model3.json") {
+        validate_live2d(source)?;
+        let folder = source.parent().ok_or("Model file has no folder")?;
+        if folder_size(folder)? > MAX_IMPORT_BYTES {
+            return Err(format!(
+                "The model's folder is over {} MB; put the model in a folder of its own",
+                MAX_IMPORT_BYTES / 1024 / 1024
+            ));
+        }
+        let name = file_name[..file_name.len() - ".model3.json".len()].to_string();
+        let id = unique_id(&avatars, &name);
+        copy_folder(folder, &avatars.join(&id)).map_err(|e| format!("Failed to copy model: {}", e))?;
+        (AvatarMetadata { name, kind: AvatarKind::Live2d, entry: file_name }, id)
+    } else {
+        return Err("Choose a .vrm file or a Live2D .model3.json file".to_string());
+    };
+
+    let contents = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
+    fs::write(avatars.join(&id).join(METADATA_FILE), contents).map_err(|e| e.to_string())?;
+
+    log::info!("🧍 Imported {:?} avatar \"{}\" as {}", metadata.kind, metadata.name, id);
+    Ok(AvatarInfo {
+        entry: format!("{}/{}", id, metadata.entry),
+        id,
+        name: metadata.name,
+        kind: metadata.kind,
+        active: false,
+    })
+}
+
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|hex| std::str::from_utf8(hex).ok());
+        match (bytes[i], hex.and_then(|hex| u8::from_str_radix(hex, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()).as_deref() {
+        Some("vrm") | Some("glb") => "model/gltf-binary",
+        Some("json") => "application/json",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("wav") => "audio/wav",
+        Some("mp3") => "audio/mpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+fn respond(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(body)
+        .unwrap_or_default()
+}
+
+/// Handler for the avatar protocol; only files inside the avatars folder are served
+pub fn serve(app: &AppHandle, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let not_found = || respond(StatusCode::NOT_FOUND, "text/plain", b"Not found".to_vec());
+
+    let Ok(root) = avatars_dir(app).and_then(|dir| dir.canonicalize().map_err(|e| e.to_string())) else {
+        return not_found();
+    };
+    let relative = percent_decode(request.uri().path().trim_start_matches('/'));
+    // Canonicalizing resolves `..` and symlinks, so anything outside the root is refused
+    let Ok(path) = root.join(&relative).canonicalize() else {
+        return not_found();
+    };
+    if !path.starts_with(&root) || !path.is_file() {
+        return not_found();
+    }
+
+    match fs::read(&path) {
+        Ok(bytes) => respond(StatusCode::OK, content_type(&path), bytes),
+        Err(e) => {
+            log::warn!("Failed to serve avatar file {}: {}", path.display(), e);
+            not_found()
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_avatars(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<Vec<AvatarInfo>, String> {
+    let active = state.lock().await.avatar.active.clone();
+    let Ok(entries) = fs::read_dir(avatars_dir(&app)?) else {
+        return Ok(Vec::new());
+    };
+
+    let mut avatars: Vec<AvatarInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let metadata = load_metadata(&entry.path())?;
+            Some(AvatarInfo {
+                entry: format!("{}/{}", id, metadata.entry),
+                active: active.as_deref() == Some(id.as_str()),
+                id,
+                name: metadata.name,
+                kind: metadata.kind,
+            })
+        })
+        .collect();
+    avatars.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(avatars)
+}
+
+/// Import a .vrm or Live2D .model3.json, asking the user to pick one when `path` is `None`
+#[tauri::command]
+pub async fn import_avatar(path: Option<String>, app: AppHandle) -> Result<AvatarInfo, String> {
+    let source = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            app.dialog()
+                .file()
+                .set_title("Import avatar")
+                .add_filter("Avatar model", &["vrm", "json"])
+                .pick_file(move |file| {
+                    let _ = tx.send(file);
+                });
+            rx.await
+                .map_err(|_| "File picker closed unexpectedly".to_string())?
+                .ok_or_else(|| "Import cancelled".to_string())?
+                .into_path()
+                .map_err(|e| format!("Invalid avatar file: {}", e))?
+        }
+    };
+
+    let install_app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || install(&install_app, &source))
+        .await
+        .map_err(|e| format!("Import task failed: {}", e))?
+}
+
+/// Switch the assistant's appearance; `None` goes back to the built-in look
+#[tauri::command]
+pub async fn set_active_avatar(
+    id: Option<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if let Some(id) = &id {
+        let dir = avatars_dir(&app)?.join(id);
+        if id.contains(['/', '\\']) || id.starts_with('.') || load_metadata(&dir).is_none() {
+            return Err(format!("Avatar not found: {}", id));
+        }
+    }
+
+    let mut settings = state.lock().await;
+    settings.avatar.active = id.clone();
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    if let Err(e) = app.emit("avatar-changed", &id) {
+        log::error!("Failed to emit avatar change: {}", e);
+    }
+
+    let label = id.as_deref().unwrap_or("the default look");
+    log::info!("🧍 Active avatar set to {}", label);
+    Ok(identity.render(&format!("{{name}} now appears as {}", label)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("eva-avatar-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn glb(json: &str) -> Vec<u8> {
+        let total = (20 + json.len()) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(GLB_MAGIC);
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&total.to_le_bytes());
+        bytes.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(GLB_JSON_CHUNK);
+        bytes.extend_from_slice(json.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn validate_vrm_reads_titles_and_rejects_other_files() {
+        let dir = temp_dir("vrm");
+        let check = |name: &str, bytes: &[u8]| {
+            let path = dir.join(name);
+            fs::write(&path, bytes).unwrap();
+            validate_vrm(&path)
+        };
+
+        let vrm1 = glb(r#"{"extensions":{"VRMC_vrm":{"meta":{"name":" Aiko "}}}}"#);
+        assert_eq!(check("v1.vrm", &vrm1), Ok(Some("Aiko".to_string())));
+        let vrm0 = glb(r#"{"extensions":{"VRM":{"meta":{"title":""}}}}"#);
+        assert_eq!(check("v0.vrm", &vrm0), Ok(None));
+
+        assert!(check("plain.vrm", &glb(r#"{"asset":{"version":"2.0"}}"#)).is_err());
+        assert!(check("truncated.vrm", &vrm1[..vrm1.len() - 1]).is_err());
+        assert!(check("text.vrm", b"not a model").is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn percent_decode_handles_escapes() {
+        assert_eq!(percent_decode("my%20model/model.vrm"), "my model/model.vrm");
+        assert_eq!(percent_decode("%E3%81%82.png"), "あ.png");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn live2d_references_must_stay_in_the_model_folder() {
+        let dir = temp_dir("live2d");
+        let model = dir.join("model");
+        fs::create_dir_all(model.join("textures")).unwrap();
+        fs::write(model.join("model.moc3"), b"moc").unwrap();
+        fs::write(model.join("textures/skin.png"), b"png").unwrap();
+        fs::write(dir.join("secret.png"), b"secret").unwrap();
+
+        let check = |texture: &str| {
+            let settings = format!(
+                r#"{{"FileReferences":{{"Moc":"model.moc3","Textures":["{}"]}}}}"#,
+                texture
+            );
+            let path = model.join("model.model3.json");
+            fs::write(&path, settings).unwrap();
+            validate_live2d(&path)
+        };
+
+        assert_eq!(check("textures/skin.png"), Ok(()));
+        assert_eq!(check("textures/../textures/skin.png"), Ok(()));
+        assert!(check("../secret.png").unwrap_err().contains("outside"));
+        assert!(check("textures/missing.png").unwrap_err().contains("Missing"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_folder_refuses_links() {
+        let dir = temp_dir("copy");
+        let model = dir.join("model");
+        fs::create_dir_all(&model).unwrap();
+        fs::write(model.join("model.moc3"), b"moc").unwrap();
+        fs::write(dir.join("secret.png"), b"secret").unwrap();
+        copy_folder(&model, &dir.join("plain")).unwrap();
+        assert!(dir.join("plain/model.moc3").is_file());
+
+        std::os::unix::fs::symlink(dir.join("secret.png"), model.join("texture.png")).unwrap();
+        assert!(copy_folder(&model, &dir.join("linked")).is_err());
+        assert!(!dir.join("linked/texture.png").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::porcupine_service::WakeWordHandle;
+use crate::quiet_hours;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Process/application names or bundle IDs (lowercase substrings) that indicate a call when recording
+const CONFERENCING_APPS: &[&str] = &[
+    "zoom",
+    "cpthost", // Zoom's in-meeting helper on macOS
+    "teams",
+    "webex",
+    "skype",
+    "slack",
+    "discord",
+    "facetime",
+];
+
+/// Browsers record for Google Meet and other web calls, but also for any page that asks for the
+/// microphone, so they only count with `include_browsers`
+const BROWSERS: &[&str] = &["chrome", "chromium", "firefox", "msedge", "edgemac", "brave"];
+
+/// Whether wake word listening is currently suspended by the call monitor
+static PAUSED_FOR_CALL: AtomicBool = AtomicBool::new(false);
+
+/// Call detection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CallDetectionSettings {
+    /// Suspend wake word listening while a call is detected
+    pub auto_pause: bool,
+    pub poll_interval_secs: u64,
+    /// Treat a browser using the microphone as a call
+    pub include_browsers: bool,
+}
+
+impl Default for CallDetectionSettings {
+    fn default() -> Self {
+        Self {
+            auto_pause: true,
+            poll_interval_secs: 5,
+            include_browsers: false,
+        }
+    }
+}
+
+/// Event payload for `call-detected` / `call-ended`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallStateEvent {
+    pub in_call: bool,
+    pub apps: Vec<String>,
+    pub paused_listening: bool,
+}
+
+/// Names of applications currently recording from a microphone
+#[cfg(target_os = "linux")]
+async fn microphone_users() -> Vec<String> {
+    // PulseAudio and PipeWire (via pipewire-pulse) expose active recordings as source outputs
+    let output = match tokio::process::Command::new("pactl")
+        .args(["list", "source-outputs"])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("application.name = ")
+                .or_else(|| line.strip_prefix("application.process.binary = "))
+        })
+        .map(|value| value.trim_matches('"').to_string())
+        .collect()
+}
+
+/// Names of applications currently recording from a microphone
+#[cfg(target_os = "windows")]
+async fn microphone_users() -> Vec<String> {
+    // The privacy consent store records a zero stop time for apps that are recording right now
+    const CONSENT_STORE_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+    let output = match tokio::process::Command::new("reg")
+        .args(["query", CONSENT_STORE_KEY, "/s"])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    let mut users = Vec::new();
+    let mut current_key = String::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if line.starts_with("HKEY_") {
+            current_key = line.trim().to_string();
+        } else if line.contains("LastUsedTimeStop") && line.trim_end().ends_with("0x0") {
+            // NonPackaged keys encode the executable path with '#' as separator
+            if let Some(name) = current_key.rsplit(['\\', '#']).next() {
+                users.push(name.to_string());
+            }
+        }
+    }
+    users
+}
+
+/// Bundle IDs of applications currently recording from a microphone
+#[cfg(target_os = "macos")]
+async fn microphone_users() -> Vec<String> {
+    match tokio::task::spawn_blocking(core_audio::recording_bundle_ids).await {
+        Ok(Some(bundle_ids)) => bundle_ids,
+        // Before macOS 14.2 there is no per-process microphone query
+        _ => in_call_processes().await,
+    }
+}
+
+/// Helpers that only run during calls, for systems without CoreAudio process objects
+#[cfg(target_os = "macos")]
+async fn in_call_processes() -> Vec<String> {
+    const IN_CALL_PROCESSES: &[&str] = &["CptHost"];
+
+    let output = match tokio::process::Command::new("ps")
+        .args(["-axco", "command"])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim())
+        .filter(|name| IN_CALL_PROCESSES.contains(name))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// CoreAudio's process objects (macOS 14.2+), which report whether each client is recording
+#[cfg(target_os = "macos")]
+mod core_audio {
+    use std::ffi::{c_char, c_void, CStr};
+
+    #[repr(C)]
+    struct PropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    const SYSTEM_OBJECT: u32 = 1;
+    const SCOPE_GLOBAL: u32 = 0x676c_6f62; // 'glob'
+    const ELEMENT_MAIN: u32 = 0;
+    const PROCESS_OBJECT_LIST: u32 = 0x7072_7323; // 'prs#'
+    const PROCESS_PID: u32 = 0x7070_6964; // 'ppid'
+    const PROCESS_BUNDLE_ID: u32 = 0x7062_6964; // 'pbid'
+    const PROCESS_IS_RUNNING_INPUT: u32 = 0x7069_7269; // 'piri'
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyDataSize(
+            object: u32,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            data_size: *mut u32,
+        ) -> i32;
+        fn AudioObjectGetPropertyData(
+            object: u32,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            data_size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringGetCString(string: *const c_void, buffer: *mut c_char, size: isize, encoding: u32) -> u8;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    fn address(selector: u32) -> PropertyAddress {
+        PropertyAddress { selector, scope: SCOPE_GLOBAL, element: ELEMENT_MAIN }
+    }
+
+    /// A fixed-size property of `object`, or `None` if CoreAudio doesn't have it
+    fn property<T: Default>(object: u32, selector: u32) -> Option<T> {
+        let mut value = T::default();
+        let mut size = std::mem::size_of::<T>() as u32;
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                object,
+                &address(selector),
+                0,
+                std::ptr::null(),
+                &mut size,
+                &mut value as *mut T as *mut c_void,
+            )
+        };
+        (status == 0).then_some(value)
+    }
+
+    fn bundle_id(process: u32) -> Option<String> {
+        let string: usize = property(process, PROCESS_BUNDLE_ID)?;
+        let string = string as *const c_void;
+        if string.is_null() {
+            return None;
+        }
+        let mut buffer = [0 as c_char; 256];
+        let copied = unsafe {
+            CFStringGetCString(string, buffer.as_mut_ptr(), buffer.len() as isize, CF_STRING_ENCODING_UTF8)
+        };
+        unsafe { CFRelease(string) };
+        if copied == 0 {
+            return None;
+        }
+        let bundle_id = unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned();
+        (!bundle_id.is_empty()).then_some(bundle_id)
+    }
+
+    /// Bundle IDs of other processes recording right now; `None` where process objects don't exist
+    pub fn recording_bundle_ids() -> Option<Vec<String>> {
+        let list = address(PROCESS_OBJECT_LIST);
+        let mut size = 0u32;
+        let status = unsafe { AudioObjectGetPropertyDataSize(SYSTEM_OBJECT, &list, 0, std::ptr::null(), &mut size) };
+        if status != 0 {
+            return None;
+        }
+
+        let mut processes = vec![0u32; size as usize / std::mem::size_of::<u32>()];
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                SYSTEM_OBJECT,
+                &list,
+                0,
+                std::ptr::null(),
+                &mut size,
+                processes.as_mut_ptr() as *mut c_void,
+            )
+        };
+        if status != 0 {
+            return None;
+        }
+        processes.truncate(size as usize / std::mem::size_of::<u32>());
+
+        // Our own wake word capture is always recording
+        let own_pid = std::process::id() as i32;
+        Some(
+            processes
+                .into_iter()
+                .filter(|&process| property::<u32>(process, PROCESS_IS_RUNNING_INPUT).unwrap_or(0) != 0)
+                .filter(|&process| property::<i32>(process, PROCESS_PID) != Some(own_pid))
+                .filter_map(bundle_id)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+async fn microphone_users() -> Vec<String> {
+    Vec::new()
+}
+
+/// Whether wake word listening is suspended until the current call ends
+pub fn is_paused_for_call() -> bool {
+    PAUSED_FOR_CALL.load(Ordering::Relaxed)
+}
+
+/// Conferencing apps, and browsers if `include_browsers`, that are currently using the microphone
+pub async fn detect_active_calls(include_browsers: bool) -> Vec<String> {
+    let mut apps: Vec<String> = microphone_users()
+        .await
+        .into_iter()
+        .filter(|name| {
+            let name = name.to_lowercase();
+            CONFERENCING_APPS.iter().any(|app| name.contains(app))
+                || (include_browsers && BROWSERS.iter().any(|browser| name.contains(browser)))
+        })
+        .collect();
+    apps.sort();
+    apps.dedup();
+    apps
+}
+
+/// Background task that suspends wake word listening while a call is in progress
+pub async fn run_call_monitor(app: AppHandle) {
+    let porcupine = app.state::<WakeWordHandle>().inner().clone();
+    let settings_state = app.state::<SettingsState>().inner().clone();
+
+    let mut in_call = false;
+
+    log::info!("📞 Call detection monitor started");
+
+    loop {
+        let settings = settings_state.lock().await.call_detection.clone();
+        tokio::time::sleep(Duration::from_secs(settings.poll_interval_secs.max(1))).await;
+
+        // Override turned off mid-call - give the microphone back to Eva right away
+        if !settings.auto_pause {
+            if PAUSED_FOR_CALL.swap(false, Ordering::Relaxed) {
+                resume_listening(&app, &porcupine).await;
+            }
+            in_call = false;
+            continue;
+        }
+
+        let apps = detect_active_calls(settings.include_browsers).await;
+        let call_active = !apps.is_empty();
+
+        if call_active && !in_call {
+            in_call = true;
+            log::info!("📞 Call detected ({}), suspending wake word listening", apps.join(", "));
+
+            if porcupine.is_listening() {
+                match porcupine.stop_listening().await {
+                    Ok(_) => PAUSED_FOR_CALL.store(true, Ordering::Relaxed),
+                    Err(e) => log::warn!("Failed to pause wake word detection for call: {}", e),
+                }
+            }
+
+            let event = CallStateEvent {
+                in_call: true,
+                apps,
+                paused_listening: PAUSED_FOR_CALL.load(Ordering::Relaxed),
+            };
+            if let Err(e) = app.emit("call-detected", &event) {
+                log::error!("Failed to emit call detected event: {}", e);
+            }
+        } else if !call_active && in_call {
+            in_call = false;
+            log::info!("📞 Call ended");
+
+            if PAUSED_FOR_CALL.swap(false, Ordering::Relaxed) {
+                resume_listening(&app, &porcupine).await;
+            }
+
+            let event = CallStateEvent { in_call: false, apps, paused_listening: false };
+            if let Err(e) = app.emit("call-ended", &event) {
+                log::error!("Failed to emit call ended event: {}", e);
+            }
+        }
+    }
+}
+
+async fn resume_listening(app: &AppHandle, porcupine: &WakeWordHandle) {
+    if porcupine.is_listening() || quiet_hours::is_active() {
+        return;
+    }
+
+    match porcupine.start_listening(app.clone()).await {
+        Ok(_) => log::info!("🎤 Wake word listening resumed after call"),
+        Err(e) => log::error!("Failed to resume wake word detection after call: {}", e),
+    }
+}
+
+#[tauri::command]
+pub async fn get_call_state(state: tauri::State<'_, SettingsState>) -> Result<CallStateEvent, String> {
+    let include_browsers = state.lock().await.call_detection.include_browsers;
+    let apps = detect_active_calls(include_browsers).await;
+    Ok(CallStateEvent {
+        in_call: !apps.is_empty(),
+        apps,
+        paused_listening: PAUSED_FOR_CALL.load(Ordering::Relaxed),
+    })
+}
+
+#[tauri::command]
+pub async fn set_call_auto_pause(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.call_detection.auto_pause = enabled;
+    settings.save(&app)?;
+
+    log::info!("Call auto-pause {}", if enabled { "enabled" } else { "disabled" });
+    Ok(format!("Call auto-pause {}", if enabled { "enabled" } else { "disabled" }))
+}
+
+/// Count browsers using the microphone as calls, for Google Meet and other web calls
+#[tauri::command]
+pub async fn set_call_browser_detection(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.call_detection.include_browsers = enabled;
+    settings.save(&app)?;
+
+    log::info!("Browser call detection {}", if enabled { "enabled" } else { "disabled" });
+    Ok(if enabled {
+        "Browsers using the microphone count as calls".to_string()
+    } else {
+        "Only conferencing apps count as calls".to_string()
+    })
+}
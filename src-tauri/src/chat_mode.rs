@@ -0,0 +1,164 @@
+use crate::chat_window::{self, Route};
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use crate::tray::MAIN_WINDOW;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Checked before any microphone capture starts
+static TEXT_ONLY: AtomicBool = AtomicBool::new(false);
+/// Typed messages are answered out loud even in text-only mode
+static SPOKEN_REPLIES: AtomicBool = AtomicBool::new(false);
+/// Wake word listening was stopped when text-only mode was enabled and should resume afterwards
+static STOPPED_FOR_TEXT_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// How the user talks to Eva
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatModeSettings {
+    /// Plain chat client: no capture, no playback, text-only Realtime session
+    pub text_only: bool,
+    /// In text-only mode, still reply with voice to typed messages
+    pub spoken_replies: bool,
+}
+
+/// Event payload for `chat-mode-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatModeEvent {
+    pub text_only: bool,
+    pub spoken_replies: bool,
+}
+
+/// Event payload for `chat-message`; the frontend forwards it to the active backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessageEvent {
+    pub text: String,
+    pub timestamp: u64,
+    /// Answer with voice; always true outside text-only mode
+    pub spoken: bool,
+}
+
+pub fn is_text_only() -> bool {
+    TEXT_ONLY.load(Ordering::Relaxed)
+}
+
+fn spoken_replies() -> bool {
+    SPOKEN_REPLIES.load(Ordering::Relaxed)
+}
+
+fn mode_event() -> ChatModeEvent {
+    ChatModeEvent {
+        text_only: is_text_only(),
+        spoken_replies: spoken_replies(),
+    }
+}
+
+fn emit_mode(app: &AppHandle) {
+    if let Err(e) = app.emit("chat-mode-changed", &mode_event()) {
+        log::error!("Failed to emit chat mode event: {}", e);
+    }
+}
+
+/// Apply the persisted mode at startup
+pub fn restore(settings: &ChatModeSettings) {
+    TEXT_ONLY.store(settings.text_only, Ordering::Relaxed);
+    SPOKEN_REPLIES.store(settings.spoken_replies, Ordering::Relaxed);
+    if settings.text_only {
+        log::info!("💬 Text-only chat mode (restored from settings)");
+    }
+}
+
+#[tauri::command]
+pub async fn get_text_only_mode() -> Result<bool, String> {
+    Ok(is_text_only())
+}
+
+#[tauri::command]
+pub async fn get_chat_mode() -> Result<ChatModeEvent, String> {
+    Ok(mode_event())
+}
+
+/// Voice replies to typed messages while in text-only mode; the microphone stays off
+#[tauri::command]
+pub async fn set_spoken_replies(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.chat_mode.spoken_replies = enabled;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    SPOKEN_REPLIES.store(enabled, Ordering::Relaxed);
+    emit_mode(&app);
+
+    if enabled {
+        log::info!("🔊 Typed messages get spoken replies");
+        Ok(identity.render("{name} will answer typed messages out loud"))
+    } else {
+        log::info!("💬 Typed messages get text replies");
+        Ok(identity.render("{name} will answer typed messages in text"))
+    }
+}
+
+#[tauri::command]
+pub async fn set_text_only_mode(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.chat_mode.text_only = enabled;
+    settings.save(&app)?;
+    drop(settings);
+
+    TEXT_ONLY.store(enabled, Ordering::Relaxed);
+
+    let porcupine = app.state::<WakeWordHandle>().inner().clone();
+    if enabled && porcupine.is_listening() {
+        match porcupine.stop_listening().await {
+            Ok(_) => STOPPED_FOR_TEXT_ONLY.store(true, Ordering::Relaxed),
+            Err(e) => log::warn!("Failed to stop wake word detection for text-only mode: {}", e),
+        }
+    } else if !enabled && STOPPED_FOR_TEXT_ONLY.swap(false, Ordering::Relaxed) && !porcupine.is_listening() {
+        if let Err(e) = porcupine.start_listening(app.clone()).await {
+            log::error!("Failed to resume wake word detection after text-only mode: {}", e);
+        }
+    }
+
+    emit_mode(&app);
+
+    if enabled {
+        log::info!("💬 Text-only chat mode enabled");
+        Ok("Text-only chat mode enabled".to_string())
+    } else {
+        log::info!("🎤 Voice chat mode enabled");
+        Ok("Voice chat mode enabled".to_string())
+    }
+}
+
+/// Send a typed message to Eva, in either mode; `spoken` overrides the spoken replies setting
+#[tauri::command]
+pub async fn send_chat_message(text: String, spoken: Option<bool>, app: AppHandle) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Message is empty".to_string());
+    }
+
+    let event = ChatMessageEvent {
+        text: text.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+        spoken: !is_text_only() || spoken.unwrap_or_else(spoken_replies),
+    };
+    // Only the main window holds the Realtime session; the chat window just sends from its input box
+    chat_window::emit_routed(&app, Route::Window(MAIN_WINDOW), "chat-message", &event)
+        .map_err(|e| format!("Failed to emit chat message: {}", e))?;
+
+    Ok("Message sent".to_string())
+}
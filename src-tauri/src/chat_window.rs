@@ -0,0 +1,163 @@
+use crate::conversations::Speaker;
+use crate::identity::IdentitySettings;
+use crate::settings::SettingsState;
+use crate::tray::MAIN_WINDOW;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+pub const CHAT_WINDOW: &str = "chat";
+/// Turns kept for a chat window opened mid-conversation
+const HISTORY_LIMIT: usize = 100;
+
+static HISTORY: Mutex<VecDeque<ChatTurn>> = Mutex::new(VecDeque::new());
+
+/// A message shown in the chat window; the main window reports them as the conversation goes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatTurn {
+    pub role: Speaker,
+    pub text: String,
+    pub timestamp: u64,
+}
+
+/// Event payload for `windows-changed`, also returned by `get_open_windows`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenWindows {
+    /// The main window (avatar, overlay and the Realtime session) is showing
+    pub main: bool,
+    pub chat: bool,
+}
+
+/// Which windows an event goes to
+#[derive(Debug, Clone, Copy)]
+pub enum Route {
+    /// Every open window
+    Broadcast,
+    /// Only the window with this label, and nowhere if it isn't open
+    Window(&'static str),
+}
+
+/// Emit `event` along `route`; a targeted event for a closed window is dropped
+pub fn emit_routed<S: Serialize + Clone>(app: &AppHandle, route: Route, event: &str, payload: S) -> tauri::Result<()> {
+    match route {
+        Route::Broadcast => app.emit(event, payload),
+        Route::Window(label) if app.get_webview_window(label).is_some() => app.emit_to(label, event, payload),
+        Route::Window(_) => Ok(()),
+    }
+}
+
+fn open_windows(app: &AppHandle) -> OpenWindows {
+    OpenWindows {
+        main: app
+            .get_webview_window(MAIN_WINDOW)
+            .and_then(|window| window.is_visible().ok())
+            .unwrap_or(false),
+        chat: app.get_webview_window(CHAT_WINDOW).is_some(),
+    }
+}
+
+fn emit_windows_changed(app: &AppHandle) {
+    if let Err(e) = emit_routed(app, Route::Broadcast, "windows-changed", open_windows(app)) {
+        log::error!("Failed to emit windows changed: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_open_windows(app: AppHandle) -> Result<OpenWindows, String> {
+    Ok(open_windows(&app))
+}
+
+fn title(identity: &IdentitySettings) -> String {
+    identity.render("{name} - Chat")
+}
+
+/// Retitle the chat window, if it's open, after the assistant is renamed
+pub fn rename(app: &AppHandle, identity: &IdentitySettings) {
+    let Some(window) = app.get_webview_window(CHAT_WINDOW) else {
+        return;
+    };
+    if let Err(e) = window.set_title(&title(identity)) {
+        log::warn!("Failed to retitle the chat window: {}", e);
+    }
+}
+
+/// Open the chat and history window, or bring it to the front if it's already open
+#[tauri::command]
+pub async fn open_chat_window(app: AppHandle) -> Result<String, String> {
+    if let Some(window) = app.get_webview_window(CHAT_WINDOW) {
+        window
+            .show()
+            .and_then(|_| window.unminimize())
+            .and_then(|_| window.set_focus())
+            .map_err(|e| format!("Failed to show the chat window: {}", e))?;
+        return Ok("Chat window focused".to_string());
+    }
+
+    let identity = app.state::<SettingsState>().lock().await.identity.clone();
+    let window = WebviewWindowBuilder::new(&app, CHAT_WINDOW, WebviewUrl::App("index.html?window=chat".into()))
+        .title(title(&identity))
+        .inner_size(420.0, 640.0)
+        .min_inner_size(320.0, 400.0)
+        .build()
+        .map_err(|e| format!("Failed to open the chat window: {}", e))?;
+
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            log::info!("🪟 Chat window closed");
+            emit_windows_changed(&handle);
+        }
+    });
+
+    log::info!("🪟 Chat window opened");
+    emit_windows_changed(&app);
+    Ok("Chat window opened".to_string())
+}
+
+#[tauri::command]
+pub async fn close_chat_window(app: AppHandle) -> Result<String, String> {
+    let Some(window) = app.get_webview_window(CHAT_WINDOW) else {
+        return Ok("Chat window is not open".to_string());
+    };
+    window.close().map_err(|e| format!("Failed to close the chat window: {}", e))?;
+    Ok("Chat window closed".to_string())
+}
+
+/// Recent turns, for a chat window that just opened
+#[tauri::command]
+pub async fn get_chat_history() -> Result<Vec<ChatTurn>, String> {
+    Ok(HISTORY.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect())
+}
+
+/// Called by the main window for each message; forwarded to the chat window as `chat-turn` when it's open
+#[tauri::command]
+pub async fn publish_chat_turn(role: Speaker, text: String, app: AppHandle) -> Result<(), String> {
+    let turn = ChatTurn {
+        role,
+        text,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+    };
+
+    {
+        let mut history = HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+        if history.len() == HISTORY_LIMIT {
+            history.pop_front();
+        }
+        history.push_back(turn.clone());
+    }
+
+    emit_routed(&app, Route::Window(CHAT_WINDOW), "chat-turn", turn)
+        .map_err(|e| format!("Failed to emit chat turn: {}", e))
+}
+
+/// Forget the history, e.g. when the user resets it in the main window
+#[tauri::command]
+pub async fn clear_chat_history(app: AppHandle) -> Result<(), String> {
+    HISTORY.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    emit_routed(&app, Route::Window(CHAT_WINDOW), "chat-history-cleared", ())
+        .map_err(|e| format!("Failed to emit chat history cleared: {}", e))
+}
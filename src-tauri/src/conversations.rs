@@ -0,0 +1,690 @@
+use crate::privacy;
+use crate::settings::SettingsState;
+use crate::users;
+use base64::Engine;
+use chrono::{Local, TimeZone};
+use hound::{WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+const CONVERSATIONS_DIR: &str = "conversations";
+const TRANSCRIPT_FILE: &str = "transcript.json";
+/// Per-reply recordings inside a conversation's directory, one `<reply id>.wav` each
+const REPLIES_DIR: &str = "replies";
+/// Realtime API PCM16 rate, used for both capture and playback audio
+const AUDIO_SAMPLE_RATE: u32 = 24000;
+
+/// Serializes transcript and WAV appends; chunks arrive from concurrent invokes
+static ARCHIVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Local conversation archive used for exports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversationSettings {
+    pub save_transcripts: bool,
+    /// Keep the raw user and assistant audio next to the transcript
+    pub save_audio: bool,
+    /// Keep each spoken reply as its own recording, replayable from the history
+    pub save_replies: bool,
+    /// Reply recordings older than this are deleted; 0 keeps them until the count limit
+    pub reply_retention_days: u64,
+    /// Oldest reply recordings beyond this count are deleted
+    pub max_reply_recordings: usize,
+}
+
+impl Default for ConversationSettings {
+    fn default() -> Self {
+        Self {
+            save_transcripts: true,
+            save_audio: false,
+            save_replies: false,
+            reply_retention_days: 30,
+            max_reply_recordings: 500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Speaker {
+    User,
+    Assistant,
+}
+
+impl Speaker {
+    fn audio_file(&self) -> &'static str {
+        match self {
+            Speaker::User => "user.wav",
+            Speaker::Assistant => "assistant.wav",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    pub role: Speaker,
+    pub text: String,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+    /// Names the reply's recording in `replies/`, if one was saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Transcript {
+    pub session_id: String,
+    pub assistant_name: String,
+    /// Unix timestamp in milliseconds
+    pub started_at: u64,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    fn load(dir: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(dir.join(TRANSCRIPT_FILE))
+            .map_err(|e| format!("Failed to read transcript: {}", e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid transcript: {}", e))
+    }
+
+    fn save(&self, dir: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize transcript: {}", e))?;
+        fs::write(dir.join(TRANSCRIPT_FILE), contents)
+            .map_err(|e| format!("Failed to write transcript: {}", e))
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Conversation with {}\n\n", self.assistant_name);
+        markdown.push_str(&format!("_Started {}_\n", format_time(self.started_at, "%Y-%m-%d %H:%M")));
+
+        for entry in &self.entries {
+            let speaker = match entry.role {
+                Speaker::User => "You",
+                Speaker::Assistant => self.assistant_name.as_str(),
+            };
+            markdown.push_str(&format!(
+                "\n**{}** ({}): {}\n",
+                speaker,
+                format_time(entry.timestamp, "%H:%M:%S"),
+                entry.text
+            ));
+        }
+        markdown
+    }
+}
+
+/// Returned by `start_conversation`; `None` when archiving is disabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationStart {
+    pub session_id: String,
+    pub save_audio: bool,
+    pub save_replies: bool,
+}
+
+/// One archived conversation, returned by `list_conversations`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub session_id: String,
+    pub started_at: u64,
+    pub turns: usize,
+    pub has_audio: bool,
+    pub has_replies: bool,
+}
+
+/// A reply recording still on disk, returned by `list_conversation_replies`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplyRecording {
+    pub reply_id: String,
+    pub text: String,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+/// A reply recording as PCM16 for the playback service, returned by `read_reply_audio`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplyAudio {
+    /// Base64 PCM16, mono
+    pub audio: String,
+    pub sample_rate: u32,
+    pub duration_ms: u64,
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn format_time(millis: u64, format: &str) -> String {
+    Local
+        .timestamp_millis_opt(millis as i64)
+        .single()
+        .map(|time| time.format(format).to_string())
+        .unwrap_or_default()
+}
+
+fn conversations_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(users::scoped(CONVERSATIONS_DIR)))
+}
+
+/// Ids come from the frontend and become file names, so keep them to a safe charset
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Directory for one session
+fn session_dir(app: &AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_id(session_id) {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+
+    let dir = conversations_dir(app)?.join(session_id);
+    if !dir.is_dir() {
+        return Err(format!("Unknown conversation: {}", session_id));
+    }
+    Ok(dir)
+}
+
+fn reply_path(dir: &Path, reply_id: &str) -> Result<PathBuf, String> {
+    if !is_safe_id(reply_id) {
+        return Err(format!("Invalid reply id: {}", reply_id));
+    }
+    Ok(dir.join(REPLIES_DIR).join(format!("{}.wav", reply_id)))
+}
+
+fn decode_pcm(audio: String) -> Result<Vec<i16>, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(audio)
+        .map_err(|e| format!("Invalid audio payload: {}", e))?;
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+        .collect())
+}
+
+fn append_audio(path: &Path, samples: &[i16], sample_rate: u32) -> Result<(), String> {
+    let mut writer = if path.exists() {
+        WavWriter::append(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?
+    } else {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        WavWriter::create(path, spec).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?
+    };
+
+    for &sample in samples {
+        writer
+            .write_sample(sample)
+            .map_err(|e| format!("Failed to write audio: {}", e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize audio: {}", e))
+}
+
+/// Delete reply recordings past the retention age, then the oldest beyond the count limit
+fn prune_replies(conversations: &Path, settings: &ConversationSettings) {
+    let Ok(sessions) = fs::read_dir(conversations) else {
+        return;
+    };
+    let mut recordings: Vec<(PathBuf, std::time::SystemTime)> = sessions
+        .filter_map(|entry| fs::read_dir(entry.ok()?.path().join(REPLIES_DIR)).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let modified = fs::metadata(&path).ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    recordings.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let max_age = std::time::Duration::from_secs(settings.reply_retention_days * 24 * 60 * 60);
+    let expired = |modified: &std::time::SystemTime| {
+        settings.reply_retention_days > 0 && modified.elapsed().is_ok_and(|age| age > max_age)
+    };
+    let mut removed = 0;
+    for (index, (path, modified)) in recordings.iter().enumerate() {
+        if index >= settings.max_reply_recordings || expired(modified) {
+            match fs::remove_file(path) {
+                Ok(()) => removed += 1,
+                Err(e) => log::warn!("Failed to delete {}: {}", path.display(), e),
+            }
+        }
+    }
+    if removed > 0 {
+        log::info!("🗂️ Deleted {} old reply recordings", removed);
+    }
+}
+
+/// Delete conversations that started more than `retention_days` ago
+fn prune_transcripts(conversations: &Path, retention_days: u64) {
+    let Ok(sessions) = fs::read_dir(conversations) else {
+        return;
+    };
+    let cutoff = now_millis().saturating_sub(retention_days * 24 * 60 * 60 * 1000);
+    let mut removed = 0;
+    for dir in sessions.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        let Ok(transcript) = Transcript::load(&dir) else {
+            continue;
+        };
+        if transcript.started_at >= cutoff {
+            continue;
+        }
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => removed += 1,
+            Err(e) => log::warn!("Failed to delete {}: {}", dir.display(), e),
+        }
+    }
+    if removed > 0 {
+        log::info!("🗂️ Deleted {} conversations past the retention period", removed);
+    }
+}
+
+/// Delete conversations past the privacy retention period in the background; 0 keeps them all
+pub fn prune_expired(app: &AppHandle, retention_days: u64) {
+    if retention_days == 0 {
+        return;
+    }
+    let Ok(conversations) = conversations_dir(app) else {
+        return;
+    };
+    tokio::task::spawn_blocking(move || {
+        let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        prune_transcripts(&conversations, retention_days);
+    });
+}
+
+/// Delete every saved conversation with its audio; returns how many there were
+pub async fn purge(app: &AppHandle) -> Result<usize, String> {
+    let conversations = conversations_dir(app)?;
+    tokio::task::spawn_blocking(move || {
+        let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let Ok(sessions) = fs::read_dir(&conversations) else {
+            return Ok(0);
+        };
+        let count = sessions.filter_map(|entry| entry.ok()).filter(|entry| entry.path().is_dir()).count();
+        fs::remove_dir_all(&conversations)
+            .map_err(|e| format!("Failed to delete saved conversations: {}", e))?;
+        Ok(count)
+    })
+    .await
+    .map_err(|e| format!("Purge task failed: {}", e))?
+}
+
+fn spawn_prune(app: &AppHandle, settings: ConversationSettings) {
+    let Ok(conversations) = conversations_dir(app) else {
+        return;
+    };
+    tokio::task::spawn_blocking(move || {
+        let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        prune_replies(&conversations, &settings);
+    });
+}
+
+fn write_zip(
+    dir: &Path,
+    destination: &Path,
+    format: ExportFormat,
+    include_audio: bool,
+) -> Result<PathBuf, String> {
+    let transcript = Transcript::load(dir)?;
+    let zip_path = destination.join(format!("eva-conversation-{}.zip", transcript.session_id));
+
+    let file = fs::File::create(&zip_path)
+        .map_err(|e| format!("Failed to create {}: {}", zip_path.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let (name, contents) = match format {
+        ExportFormat::Markdown => ("transcript.md", transcript.to_markdown()),
+        ExportFormat::Json => (
+            "transcript.json",
+            serde_json::to_string_pretty(&transcript)
+                .map_err(|e| format!("Failed to serialize transcript: {}", e))?,
+        ),
+    };
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+    zip.write_all(contents.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+
+    if include_audio {
+        for speaker in [Speaker::User, Speaker::Assistant] {
+            let path = dir.join(speaker.audio_file());
+            if !path.exists() {
+                continue;
+            }
+            let audio = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            // WAV data barely compresses
+            let stored = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored);
+            zip.start_file(speaker.audio_file(), stored)
+                .map_err(|e| format!("Failed to add {}: {}", speaker.audio_file(), e))?;
+            zip.write_all(&audio)
+                .map_err(|e| format!("Failed to write {}: {}", speaker.audio_file(), e))?;
+        }
+
+        let replies = fs::read_dir(dir.join(REPLIES_DIR)).into_iter().flatten();
+        for path in replies.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let name = format!("{}/{}", REPLIES_DIR, file_name);
+            let audio = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let stored = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored);
+            zip.start_file(name.as_str(), stored)
+                .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+            zip.write_all(&audio)
+                .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        }
+    }
+
+    zip.finish().map_err(|e| format!("Failed to finish zip: {}", e))?;
+    Ok(zip_path)
+}
+
+/// Ask the user where to save the export
+async fn pick_destination(app: &AppHandle) -> Result<PathBuf, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Export conversation")
+        .pick_folder(move |folder| {
+            let _ = tx.send(folder);
+        });
+
+    rx.await
+        .map_err(|_| "Folder picker closed unexpectedly".to_string())?
+        .ok_or_else(|| "Export cancelled".to_string())?
+        .into_path()
+        .map_err(|e| format!("Invalid export folder: {}", e))
+}
+
+/// Begin archiving a new session
+#[tauri::command]
+pub async fn start_conversation(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<Option<ConversationStart>, String> {
+    let settings = state.lock().await;
+    if !settings.conversations.save_transcripts {
+        return Ok(None);
+    }
+    let archive = settings.conversations.clone();
+    let retention_days = settings.privacy.transcript_retention_days;
+    let assistant_name = settings.identity.assistant_name.clone();
+    drop(settings);
+
+    spawn_prune(&app, archive.clone());
+    prune_expired(&app, retention_days);
+
+    let session_id = Local::now().format("%Y%m%d-%H%M%S-%3f").to_string();
+    let dir = conversations_dir(&app)?.join(&session_id);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create conversation directory: {}", e))?;
+
+    Transcript {
+        session_id: session_id.clone(),
+        assistant_name,
+        started_at: now_millis(),
+        entries: Vec::new(),
+    }
+    .save(&dir)?;
+
+    log::info!("🗂️ Archiving conversation {}", session_id);
+    Ok(Some(ConversationStart {
+        session_id,
+        save_audio: archive.save_audio && privacy::audio_storage_allowed(),
+        save_replies: archive.save_replies && privacy::audio_storage_allowed(),
+    }))
+}
+
+#[tauri::command]
+pub async fn append_conversation_turn(
+    session_id: String,
+    role: Speaker,
+    text: String,
+    reply_id: Option<String>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let dir = session_dir(&app, &session_id)?;
+    let reply_id = reply_id.filter(|id| is_safe_id(id));
+    let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut transcript = Transcript::load(&dir)?;
+    transcript.entries.push(TranscriptEntry {
+        role,
+        text,
+        timestamp: now_millis(),
+        reply_id,
+    });
+    transcript.save(&dir)
+}
+
+/// Append base64 PCM16 (24 kHz mono) to the speaker's track
+#[tauri::command]
+pub async fn append_conversation_audio(
+    session_id: String,
+    speaker: Speaker,
+    audio: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if !state.lock().await.conversations.save_audio || !privacy::audio_storage_allowed() {
+        return Ok(());
+    }
+
+    let dir = session_dir(&app, &session_id)?;
+    let samples = decode_pcm(audio)?;
+
+    tokio::task::spawn_blocking(move || {
+        let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        append_audio(&dir.join(speaker.audio_file()), &samples, AUDIO_SAMPLE_RATE)
+    })
+    .await
+    .map_err(|e| format!("Audio writer task failed: {}", e))?
+}
+
+/// Append base64 PCM16 to the recording of one reply; `sample_rate` defaults to the Realtime API's
+#[tauri::command]
+pub async fn append_reply_audio(
+    session_id: String,
+    reply_id: String,
+    audio: String,
+    sample_rate: Option<u32>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if !state.lock().await.conversations.save_replies || !privacy::audio_storage_allowed() {
+        return Ok(());
+    }
+
+    let dir = session_dir(&app, &session_id)?;
+    let path = reply_path(&dir, &reply_id)?;
+    let samples = decode_pcm(audio)?;
+
+    tokio::task::spawn_blocking(move || {
+        let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        fs::create_dir_all(dir.join(REPLIES_DIR))
+            .map_err(|e| format!("Failed to create replies directory: {}", e))?;
+        append_audio(&path, &samples, sample_rate.unwrap_or(AUDIO_SAMPLE_RATE))
+    })
+    .await
+    .map_err(|e| format!("Audio writer task failed: {}", e))?
+}
+
+/// Replies of a conversation whose recordings are still kept, oldest first
+#[tauri::command]
+pub async fn list_conversation_replies(session_id: String, app: AppHandle) -> Result<Vec<ReplyRecording>, String> {
+    let dir = session_dir(&app, &session_id)?;
+    let transcript = Transcript::load(&dir)?;
+
+    Ok(transcript
+        .entries
+        .into_iter()
+        .filter_map(|entry| {
+            let reply_id = entry.reply_id?;
+            reply_path(&dir, &reply_id).ok()?.exists().then_some(ReplyRecording {
+                reply_id,
+                text: entry.text,
+                timestamp: entry.timestamp,
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn read_reply_audio(session_id: String, reply_id: String, app: AppHandle) -> Result<ReplyAudio, String> {
+    let dir = session_dir(&app, &session_id)?;
+    let path = reply_path(&dir, &reply_id)?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut reader = hound::WavReader::open(&path)
+            .map_err(|_| "That recording is no longer kept".to_string())?;
+        let sample_rate = reader.spec().sample_rate;
+        let samples = reader
+            .samples::<i16>()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read recording: {}", e))?;
+
+        let bytes: Vec<u8> = samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+        Ok(ReplyAudio {
+            audio: base64::engine::general_purpose::STANDARD.encode(bytes),
+            sample_rate,
+            duration_ms: samples.len() as u64 * 1000 / sample_rate.max(1) as u64,
+        })
+    })
+    .await
+    .map_err(|e| format!("Audio reader task failed: {}", e))?
+}
+
+/// Archived conversations, newest first
+#[tauri::command]
+pub async fn list_conversations(app: AppHandle) -> Result<Vec<ConversationSummary>, String> {
+    let Ok(entries) = fs::read_dir(conversations_dir(&app)?) else {
+        return Ok(Vec::new());
+    };
+
+    let mut list: Vec<ConversationSummary> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|dir| {
+            let transcript = Transcript::load(&dir).ok()?;
+            Some(ConversationSummary {
+                turns: transcript.entries.len(),
+                has_audio: dir.join(Speaker::User.audio_file()).exists()
+                    || dir.join(Speaker::Assistant.audio_file()).exists(),
+                has_replies: fs::read_dir(dir.join(REPLIES_DIR))
+                    .is_ok_and(|mut replies| replies.next().is_some()),
+                session_id: transcript.session_id,
+                started_at: transcript.started_at,
+            })
+        })
+        .collect();
+    list.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(list)
+}
+
+/// Bundle a conversation's transcript, and optionally its audio, into a zip.
+///
+/// Prompts for a folder when `destination` is not given. Returns the zip path.
+#[tauri::command]
+pub async fn export_conversation(
+    session_id: String,
+    format: ExportFormat,
+    include_audio: Option<bool>,
+    destination: Option<String>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let dir = session_dir(&app, &session_id)?;
+    let destination = match destination {
+        Some(path) => PathBuf::from(path),
+        None => pick_destination(&app).await?,
+    };
+    let include_audio = include_audio.unwrap_or(true);
+
+    let zip_path = tokio::task::spawn_blocking(move || {
+        let _guard = ARCHIVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        write_zip(&dir, &destination, format, include_audio)
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))??;
+
+    log::info!("📦 Exported conversation {} to {}", session_id, zip_path.display());
+    Ok(zip_path.display().to_string())
+}
+
+#[tauri::command]
+pub async fn set_conversation_archive(
+    save_transcripts: bool,
+    save_audio: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.conversations.save_transcripts = save_transcripts;
+    settings.conversations.save_audio = save_audio;
+    settings.save(&app)?;
+
+    log::info!(
+        "🗂️ Conversation archive: transcripts {}, audio {}",
+        save_transcripts, save_audio
+    );
+    Ok(format!(
+        "Conversation archive updated (transcripts: {}, audio: {})",
+        if save_transcripts { "on" } else { "off" },
+        if save_audio { "on" } else { "off" }
+    ))
+}
+
+/// Save replies as recordings and how long to keep them; old recordings are pruned right away
+#[tauri::command]
+pub async fn set_reply_recording(
+    save_replies: bool,
+    retention_days: u64,
+    max_recordings: usize,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.conversations.save_replies = save_replies;
+    settings.conversations.reply_retention_days = retention_days;
+    settings.conversations.max_reply_recordings = max_recordings;
+    settings.save(&app)?;
+    let archive = settings.conversations.clone();
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    spawn_prune(&app, archive);
+
+    log::info!(
+        "🗂️ Reply recordings {} (keep {} days, at most {})",
+        if save_replies { "on" } else { "off" },
+        retention_days,
+        max_recordings
+    );
+    Ok(if save_replies {
+        identity.render("{name}'s replies will be saved")
+    } else {
+        "Reply recording off".to_string()
+    })
+}
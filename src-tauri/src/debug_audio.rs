@@ -0,0 +1,261 @@
+use crate::privacy;
+use crate::settings::SettingsState;
+use hound::{WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+const DEBUG_AUDIO_DIR: &str = "debug_audio";
+const DEBUG_AUDIO_PREFIX: &str = "processed_audio_";
+/// Each WAV file covers at most this much audio
+const SEGMENT_SECS: u32 = 60;
+/// Oldest recordings are deleted once the directory grows past this
+const MAX_TOTAL_BYTES: u64 = 200 * 1024 * 1024;
+/// Rewrite the WAV header this often so a crash loses at most this much audio
+const FLUSH_INTERVAL_SECS: u32 = 1;
+
+/// Checked by the processing thread for every frame, so toggling takes effect immediately
+static DEBUG_AUDIO_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Debug recording of the audio fed to Porcupine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebugAudioSettings {
+    pub enabled: bool,
+}
+
+/// One recorded segment, returned by `list_debug_recordings`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugRecording {
+    pub file_name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub duration_ms: u64,
+    /// Unix timestamp in milliseconds
+    pub modified: u64,
+}
+
+pub fn is_enabled() -> bool {
+    DEBUG_AUDIO_ENABLED.load(Ordering::Relaxed) && privacy::audio_storage_allowed()
+}
+
+/// Apply the persisted setting at startup; `EVA_DEBUG_AUDIO` still forces recording on
+pub fn restore(settings: &DebugAudioSettings) {
+    let enabled = settings.enabled || std::env::var("EVA_DEBUG_AUDIO").is_ok();
+    DEBUG_AUDIO_ENABLED.store(enabled, Ordering::Relaxed);
+    if enabled {
+        log::info!("🎵 Debug audio recording enabled - saving to {}/", DEBUG_AUDIO_DIR);
+    }
+}
+
+/// Writes processed frames into size-limited WAV segments
+pub struct DebugAudioRecorder {
+    sample_rate: u32,
+    writer: Option<WavWriter<BufWriter<fs::File>>>,
+    segment_index: u32,
+    samples_since_flush: u32,
+}
+
+impl DebugAudioRecorder {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            writer: None,
+            segment_index: 0,
+            samples_since_flush: 0,
+        }
+    }
+
+    /// Record a frame if debug recording is on; closes the open segment when it's turned off
+    pub fn write_frame(&mut self, frame: &[i16]) {
+        if !is_enabled() {
+            self.finish();
+            return;
+        }
+
+        let segment_full = self
+            .writer
+            .as_ref()
+            .is_some_and(|writer| writer.duration() >= SEGMENT_SECS * self.sample_rate);
+        if segment_full {
+            self.finish();
+        }
+
+        if self.writer.is_none() {
+            match self.open_segment() {
+                Ok(writer) => self.writer = Some(writer),
+                Err(e) => {
+                    log::error!("Failed to start debug audio segment: {}", e);
+                    return;
+                }
+            }
+        }
+
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+        for &sample in frame {
+            if let Err(e) = writer.write_sample(sample) {
+                log::error!("Failed to write debug audio sample: {}", e);
+                break;
+            }
+        }
+
+        self.samples_since_flush += frame.len() as u32;
+        if self.samples_since_flush >= FLUSH_INTERVAL_SECS * self.sample_rate {
+            self.samples_since_flush = 0;
+            if let Err(e) = writer.flush() {
+                log::error!("Failed to flush debug audio: {}", e);
+            }
+        }
+    }
+
+    /// Finalize the open segment, if any
+    pub fn finish(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            let seconds = writer.duration() as f32 / self.sample_rate as f32;
+            match writer.finalize() {
+                Ok(_) => log::info!("🎵 Debug audio segment saved ({:.1}s)", seconds),
+                Err(e) => log::error!("Failed to finalize debug WAV file: {}", e),
+            }
+        }
+        self.samples_since_flush = 0;
+    }
+
+    fn open_segment(&mut self) -> Result<WavWriter<BufWriter<fs::File>>, String> {
+        fs::create_dir_all(DEBUG_AUDIO_DIR)
+            .map_err(|e| format!("Failed to create debug directory: {}", e))?;
+        evict_oldest(Path::new(DEBUG_AUDIO_DIR), MAX_TOTAL_BYTES);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.segment_index += 1;
+        let filename = format!(
+            "{}/{}{}_{:03}.wav",
+            DEBUG_AUDIO_DIR, DEBUG_AUDIO_PREFIX, timestamp, self.segment_index
+        );
+
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = WavWriter::create(&filename, spec)
+            .map_err(|e| format!("Failed to create WAV writer: {}", e))?;
+
+        log::info!("🎵 Recording debug audio to: {}", filename);
+        Ok(writer)
+    }
+}
+
+impl Drop for DebugAudioRecorder {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Recordings in the debug directory, oldest first
+fn recordings(dir: &Path) -> Vec<(PathBuf, fs::Metadata)> {
+    let mut files: Vec<(PathBuf, fs::Metadata)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| {
+                    entry.file_name().to_string_lossy().starts_with(DEBUG_AUDIO_PREFIX)
+                })
+                .filter_map(|entry| entry.metadata().ok().map(|meta| (entry.path(), meta)))
+                .filter(|(_, meta)| meta.is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_key(|(_, meta)| meta.modified().unwrap_or(UNIX_EPOCH));
+    files
+}
+
+/// Delete the oldest recordings until the directory fits in `max_bytes`
+fn evict_oldest(dir: &Path, max_bytes: u64) {
+    let files = recordings(dir);
+    let mut total: u64 = files.iter().map(|(_, meta)| meta.len()).sum();
+
+    for (path, meta) in files {
+        if total <= max_bytes {
+            break;
+        }
+        match fs::remove_file(&path) {
+            Ok(_) => {
+                total -= meta.len();
+                log::info!("🗑️  Evicted old debug recording: {}", path.display());
+            }
+            Err(e) => log::warn!("Failed to delete {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Delete every debug recording; returns how many were removed
+pub fn purge() -> usize {
+    recordings(Path::new(DEBUG_AUDIO_DIR))
+        .into_iter()
+        .filter(|(path, _)| match fs::remove_file(path) {
+            Ok(_) => true,
+            Err(e) => {
+                log::warn!("Failed to delete {}: {}", path.display(), e);
+                false
+            }
+        })
+        .count()
+}
+
+#[tauri::command]
+pub async fn set_debug_audio(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.debug_audio.enabled = enabled;
+    settings.save(&app)?;
+
+    DEBUG_AUDIO_ENABLED.store(enabled, Ordering::Relaxed);
+
+    if enabled {
+        log::info!("🎵 Debug audio recording enabled");
+        Ok(format!("Debug audio recording enabled - saving to {}/", DEBUG_AUDIO_DIR))
+    } else {
+        log::info!("🎵 Debug audio recording disabled");
+        Ok("Debug audio recording disabled".to_string())
+    }
+}
+
+/// Recorded segments, newest first
+#[tauri::command]
+pub async fn list_debug_recordings() -> Result<Vec<DebugRecording>, String> {
+    let mut list: Vec<DebugRecording> = recordings(Path::new(DEBUG_AUDIO_DIR))
+        .into_iter()
+        .map(|(path, meta)| {
+            let duration_ms = hound::WavReader::open(&path)
+                .map(|reader| reader.duration() as u64 * 1000 / reader.spec().sample_rate.max(1) as u64)
+                .unwrap_or(0);
+            DebugRecording {
+                file_name: path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+                path: fs::canonicalize(&path).unwrap_or(path).display().to_string(),
+                size_bytes: meta.len(),
+                duration_ms,
+                modified: meta
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+            }
+        })
+        .collect();
+    list.reverse();
+    Ok(list)
+}
@@ -0,0 +1,304 @@
+use crate::audio;
+use crate::error::EvaError;
+use crate::identity::IdentitySettings;
+use crate::mic_permission::{self, MicrophonePermission};
+use crate::porcupine_service::WakeWordHandle;
+use crate::proxy;
+use crate::settings::SettingsState;
+use crate::wake_word::WakeWordError;
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const OPENAI_MODELS_URL: &str = "https://api.openai.com/v1/models";
+const AUTH_CHECK_TIMEOUT_SECS: u64 = 10;
+/// The model the Realtime session connects to, when the caller doesn't name one
+const OPENAI_REALTIME_MODEL: &str = "gpt-4o-realtime-preview-2024-10-01";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but something needs attention
+    Warn,
+    Fail,
+    /// Could not run, usually because an earlier check failed
+    Skipped,
+}
+
+/// One step of the diagnostics run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    /// Stable identifier for the setup wizard, e.g. `microphone-permission`
+    pub id: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub recovery_hint: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn new(id: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { id: id.to_string(), status, detail: detail.into(), recovery_hint: None }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.recovery_hint = Some(hint.into());
+        self
+    }
+
+    fn from_error(id: &str, error: EvaError) -> Self {
+        Self {
+            id: id.to_string(),
+            status: CheckStatus::Fail,
+            detail: error.to_string(),
+            recovery_hint: error.recovery_hint(),
+        }
+    }
+}
+
+/// Machine-readable result of `run_diagnostics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    /// No check failed (warnings and skips allowed)
+    pub ok: bool,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+fn check_access_key(service: &WakeWordHandle) -> DiagnosticCheck {
+    match service.access_key_source() {
+        Some(source) => DiagnosticCheck::new("picovoice-access-key", CheckStatus::Pass, format!("Found in {}", source)),
+        None => DiagnosticCheck::from_error(
+            "picovoice-access-key",
+            WakeWordError::AccessKey("No Picovoice access key found".to_string()).into(),
+        ),
+    }
+}
+
+async fn check_microphone_permission(identity: &IdentitySettings) -> DiagnosticCheck {
+    match mic_permission::current_permission().await {
+        MicrophonePermission::Granted => DiagnosticCheck::new("microphone-permission", CheckStatus::Pass, "Granted"),
+        MicrophonePermission::Undetermined => {
+            DiagnosticCheck::new("microphone-permission", CheckStatus::Warn, "Not requested yet")
+                .hint("Allow microphone access when prompted")
+        }
+        MicrophonePermission::Denied => DiagnosticCheck::new("microphone-permission", CheckStatus::Fail, "Denied")
+            .hint(identity.render("Allow {name} in your system privacy settings for the microphone")),
+    }
+}
+
+fn check_input_devices() -> DiagnosticCheck {
+    let host = audio::capture_host();
+    let devices: Vec<String> = host.input_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default();
+    let default_device = host.default_input_device().and_then(|device| device.name().ok());
+
+    match default_device {
+        Some(name) => DiagnosticCheck::new(
+            "input-devices",
+            CheckStatus::Pass,
+            format!("Default input: {} ({} devices: {})", name, devices.len(), devices.join(", ")),
+        ),
+        None if !devices.is_empty() => DiagnosticCheck::new(
+            "input-devices",
+            CheckStatus::Warn,
+            format!("No default input, but found: {}", devices.join(", ")),
+        )
+        .hint("Pick a default microphone in your system sound settings"),
+        None => DiagnosticCheck::new("input-devices", CheckStatus::Fail, "No input devices found")
+            .hint("Connect a microphone"),
+    }
+}
+
+async fn check_porcupine(service: &WakeWordHandle, key_ok: bool) -> DiagnosticCheck {
+    if !key_ok {
+        return DiagnosticCheck::new("porcupine-init", CheckStatus::Skipped, "Needs an access key");
+    }
+    if service.is_listening() {
+        return DiagnosticCheck::new("porcupine-init", CheckStatus::Pass, "Wake word detection is running");
+    }
+
+    match service.verify_initialization().await {
+        Ok(_) => DiagnosticCheck::new("porcupine-init", CheckStatus::Pass, "Porcupine initialized"),
+        Err(e) => DiagnosticCheck::from_error("porcupine-init", e.into()),
+    }
+}
+
+async fn check_openai_auth(api_key: Option<String>) -> DiagnosticCheck {
+    let Some(api_key) = api_key.filter(|key| !key.trim().is_empty()) else {
+        return DiagnosticCheck::new("openai-auth", CheckStatus::Skipped, "No OpenAI API key provided");
+    };
+
+    let client = match proxy::client_builder()
+        .timeout(Duration::from_secs(AUTH_CHECK_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return DiagnosticCheck::new("openai-auth", CheckStatus::Fail, format!("Failed to create HTTP client: {}", e)),
+    };
+
+    match client.get(OPENAI_MODELS_URL).bearer_auth(api_key.trim()).send().await {
+        Ok(response) if response.status().is_success() => {
+            DiagnosticCheck::new("openai-auth", CheckStatus::Pass, "API key accepted")
+        }
+        Ok(response) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+            DiagnosticCheck::new("openai-auth", CheckStatus::Fail, "API key rejected")
+                .hint("Re-enter your OpenAI API key")
+        }
+        Ok(response) => DiagnosticCheck::new(
+            "openai-auth",
+            CheckStatus::Warn,
+            format!("Unexpected response: {}", response.status()),
+        ),
+        Err(e) => DiagnosticCheck::new("openai-auth", CheckStatus::Fail, format!("Could not reach OpenAI: {}", e))
+            .hint("Check your internet connection or proxy settings"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OpenAiKeyStatus {
+    /// The key works and the account can use the Realtime model
+    Valid,
+    InvalidKey,
+    /// The key works, but this account or project has no access to the model
+    ModelUnavailable,
+    /// Out of quota or rate limited; the key itself is fine
+    QuotaExceeded,
+    /// OpenAI couldn't be reached, so nothing is known about the key
+    Unreachable,
+    /// Any other response
+    Unexpected,
+}
+
+/// Result of `validate_openai_key`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiKeyValidation {
+    pub status: OpenAiKeyStatus,
+    pub model: String,
+    pub detail: String,
+    pub recovery_hint: Option<String>,
+}
+
+impl OpenAiKeyValidation {
+    fn new(status: OpenAiKeyStatus, model: &str, detail: impl Into<String>, hint: Option<&str>) -> Self {
+        Self {
+            status,
+            model: model.to_string(),
+            detail: detail.into(),
+            recovery_hint: hint.map(|hint| hint.to_string()),
+        }
+    }
+}
+
+/// Check an OpenAI key before it is saved by looking up the Realtime model with it.
+///
+/// One authenticated GET answers both questions: 401 means a bad key, 404 a key that
+/// works but can't see the model. `model` defaults to the one the session uses.
+#[tauri::command]
+pub async fn validate_openai_key(key: String, model: Option<String>) -> Result<OpenAiKeyValidation, String> {
+    let model = model
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+        .unwrap_or_else(|| OPENAI_REALTIME_MODEL.to_string());
+    let key = key.trim();
+    if key.is_empty() {
+        return Ok(OpenAiKeyValidation::new(
+            OpenAiKeyStatus::InvalidKey,
+            &model,
+            "No API key entered",
+            Some("Create a key at https://platform.openai.com/api-keys"),
+        ));
+    }
+
+    let client = proxy::client_builder()
+        .timeout(Duration::from_secs(AUTH_CHECK_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let validation = match client
+        .get(format!("{}/{}", OPENAI_MODELS_URL, model))
+        .bearer_auth(key)
+        .send()
+        .await
+    {
+        Ok(response) => match response.status() {
+            status if status.is_success() => OpenAiKeyValidation::new(
+                OpenAiKeyStatus::Valid,
+                &model,
+                format!("API key accepted, {} is available", model),
+                None,
+            ),
+            reqwest::StatusCode::UNAUTHORIZED => OpenAiKeyValidation::new(
+                OpenAiKeyStatus::InvalidKey,
+                &model,
+                "API key rejected",
+                Some("Check the key was copied completely, or create a new one at https://platform.openai.com/api-keys"),
+            ),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::FORBIDDEN => OpenAiKeyValidation::new(
+                OpenAiKeyStatus::ModelUnavailable,
+                &model,
+                format!("API key accepted, but {} isn't available to this account", model),
+                Some("Add billing or enable the model for this key's project in the OpenAI dashboard"),
+            ),
+            reqwest::StatusCode::TOO_MANY_REQUESTS => OpenAiKeyValidation::new(
+                OpenAiKeyStatus::QuotaExceeded,
+                &model,
+                "API key accepted, but the account is out of quota or rate limited",
+                Some("Check your billing at https://platform.openai.com/account/billing"),
+            ),
+            status => OpenAiKeyValidation::new(
+                OpenAiKeyStatus::Unexpected,
+                &model,
+                format!("Unexpected response: {}", status),
+                None,
+            ),
+        },
+        Err(e) => OpenAiKeyValidation::new(
+            OpenAiKeyStatus::Unreachable,
+            &model,
+            format!("Could not reach OpenAI: {}", e),
+            Some("Check your internet connection or proxy settings"),
+        ),
+    };
+
+    log::info!("🔑 OpenAI key check for {}: {:?}", model, validation.status);
+    Ok(validation)
+}
+
+/// Run the setup checks in order: keys, microphone, devices, Porcupine, OpenAI.
+///
+/// The OpenAI key lives in the frontend, so it is passed in; omit it to skip the auth check.
+#[tauri::command]
+pub async fn run_diagnostics(
+    openai_api_key: Option<String>,
+    state: tauri::State<'_, WakeWordHandle>,
+    settings_state: tauri::State<'_, SettingsState>,
+) -> Result<DiagnosticsReport, String> {
+    log::info!("🩺 Running diagnostics");
+    let identity = settings_state.lock().await.identity.clone();
+
+    let access_key = check_access_key(&state);
+    let key_ok = access_key.status == CheckStatus::Pass;
+
+    let devices = tokio::task::spawn_blocking(check_input_devices)
+        .await
+        .map_err(|e| format!("Device enumeration failed: {}", e))?;
+
+    let checks = vec![
+        access_key,
+        check_microphone_permission(&identity).await,
+        devices,
+        check_porcupine(&state, key_ok).await,
+        check_openai_auth(openai_api_key).await,
+    ];
+
+    for check in &checks {
+        log::info!("🩺 {}: {:?} - {}", check.id, check.status, check.detail);
+    }
+
+    Ok(DiagnosticsReport {
+        ok: checks.iter().all(|check| check.status != CheckStatus::Fail),
+        checks,
+    })
+}
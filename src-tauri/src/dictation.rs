@@ -0,0 +1,385 @@
+use crate::chat_mode;
+use crate::earcons::{self, EarconCue};
+use crate::settings::SettingsState;
+use crate::tray;
+use enigo::{Direction, Enigo, Key, Keyboard};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+/// What was typed last ended mid-line, so the next utterance starts with a space
+static NEEDS_SPACE: AtomicBool = AtomicBool::new(false);
+/// The registered dictation hotkey, so other global shortcuts aren't taken for it
+static HOTKEY: Mutex<Option<Shortcut>> = Mutex::new(None);
+
+/// How spoken punctuation joins the words around it
+#[derive(Clone, Copy)]
+enum Spacing {
+    /// Attached to the word before: "," "."
+    AfterWord,
+    /// Attached to the word after: "(" and an opening quote
+    BeforeWord,
+    /// Starts a new line with no space on either side
+    Line,
+}
+
+/// Spoken punctuation; multi-word commands come first so they win over their last word
+const PUNCTUATION_COMMANDS: &[(&str, &str, Spacing)] = &[
+    ("new paragraph", "\n\n", Spacing::Line),
+    ("new line", "\n", Spacing::Line),
+    ("full stop", ".", Spacing::AfterWord),
+    ("question mark", "?", Spacing::AfterWord),
+    ("exclamation mark", "!", Spacing::AfterWord),
+    ("exclamation point", "!", Spacing::AfterWord),
+    ("open quote", "\"", Spacing::BeforeWord),
+    ("close quote", "\"", Spacing::AfterWord),
+    ("open bracket", "(", Spacing::BeforeWord),
+    ("close bracket", ")", Spacing::AfterWord),
+    ("period", ".", Spacing::AfterWord),
+    ("comma", ",", Spacing::AfterWord),
+    ("colon", ":", Spacing::AfterWord),
+    ("semicolon", ";", Spacing::AfterWord),
+];
+
+/// Punctuation the transcriber may have guessed next to a spoken command, replaced by it
+const GUESSED_PUNCTUATION: &[char] = &[',', '.', ';', ':', '!', '?'];
+
+/// Typing what the user says into whichever app has focus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DictationSettings {
+    /// Global shortcut that starts and stops dictation, e.g. "CommandOrControl+Alt+D"; empty for none
+    pub hotkey: String,
+    /// Saying this ends dictation; it isn't typed
+    pub stop_phrase: String,
+}
+
+impl Default for DictationSettings {
+    fn default() -> Self {
+        Self {
+            hotkey: "CommandOrControl+Alt+D".to_string(),
+            stop_phrase: "stop dictation".to_string(),
+        }
+    }
+}
+
+/// Event payload for `dictation-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictationEvent {
+    pub active: bool,
+}
+
+/// Returned by `get_dictation`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictationStatus {
+    pub active: bool,
+    pub hotkey: String,
+    pub stop_phrase: String,
+}
+
+/// What `dictate` did with a transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictationOutcome {
+    pub typed: String,
+    /// The stop phrase was said and dictation has ended
+    pub stopped: bool,
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// A word without the punctuation and case the transcriber gave it
+fn bare(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase()
+}
+
+fn punctuation_at(words: &[String]) -> Option<(usize, &'static str, Spacing)> {
+    PUNCTUATION_COMMANDS.iter().find_map(|(phrase, symbol, spacing)| {
+        let phrase: Vec<&str> = phrase.split(' ').collect();
+        let matches = words.len() >= phrase.len() && words.iter().zip(&phrase).all(|(word, part)| word == part);
+        matches.then_some((phrase.len(), *symbol, *spacing))
+    })
+}
+
+/// Text to type for one transcript: spoken punctuation applied, and everything from the stop
+/// phrase on dropped. Returns whether the stop phrase was said.
+fn render(text: &str, stop_phrase: &str, needs_space: bool) -> (String, bool) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let bare_words: Vec<String> = words.iter().map(|word| bare(word)).collect();
+    let stop_words: Vec<String> = stop_phrase.split_whitespace().map(bare).collect();
+
+    let stop_at = (!stop_words.is_empty())
+        .then(|| bare_words.windows(stop_words.len()).position(|window| window == stop_words.as_slice()))
+        .flatten();
+    let end = stop_at.unwrap_or(words.len());
+
+    let mut typed = String::new();
+    // No space before the next word: start of a line, or right after "(" or an opening quote
+    let mut glued = !needs_space;
+    let mut i = 0;
+    while i < end {
+        match punctuation_at(&bare_words[i..end]) {
+            Some((used, symbol, spacing)) => {
+                match spacing {
+                    Spacing::AfterWord => {
+                        typed.truncate(typed.trim_end_matches(GUESSED_PUNCTUATION).len());
+                        typed.push_str(symbol);
+                        glued = false;
+                    }
+                    Spacing::BeforeWord => {
+                        if !glued {
+                            typed.push(' ');
+                        }
+                        typed.push_str(symbol);
+                        glued = true;
+                    }
+                    Spacing::Line => {
+                        typed.truncate(typed.trim_end().len());
+                        typed.push_str(symbol);
+                        glued = true;
+                    }
+                }
+                i += used;
+            }
+            None => {
+                if !glued {
+                    typed.push(' ');
+                }
+                typed.push_str(words[i]);
+                glued = false;
+                i += 1;
+            }
+        }
+    }
+    (typed, stop_at.is_some())
+}
+
+/// Send `text` to the focused app as keystrokes, with Return for each new line
+fn type_text(text: &str) -> Result<(), String> {
+    let keystroke_error = |e: enigo::InputError| format!("Failed to type into the focused app: {}", e);
+    // macOS asks for Accessibility access the first time
+    let mut enigo = Enigo::new(&enigo::Settings::default())
+        .map_err(|e| format!("Can't send keystrokes: {}", e))?;
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            enigo.key(Key::Return, Direction::Click).map_err(keystroke_error)?;
+        }
+        if !line.is_empty() {
+            enigo.text(line).map_err(keystroke_error)?;
+        }
+    }
+    Ok(())
+}
+
+fn emit_changed(app: &AppHandle, active: bool) {
+    if let Err(e) = app.emit("dictation-changed", DictationEvent { active }) {
+        log::error!("Failed to emit dictation change: {}", e);
+    }
+}
+
+/// Start typing transcripts into the focused app; the frontend opens the microphone on
+/// `dictation-changed`
+pub async fn start(app: &AppHandle) -> Result<(), String> {
+    if chat_mode::is_text_only() {
+        return Err("Dictation needs the microphone, which is off in text-only chat mode".to_string());
+    }
+    if ACTIVE.swap(true, Ordering::Relaxed) {
+        return Ok(());
+    }
+    NEEDS_SPACE.store(false, Ordering::Relaxed);
+
+    log::info!("⌨️  Dictation started");
+    emit_changed(app, true);
+    earcons::play(app, EarconCue::ListeningStart).await;
+    tray::set_status(app, Some("Dictating")).await;
+    Ok(())
+}
+
+pub async fn stop(app: &AppHandle) {
+    if !ACTIVE.swap(false, Ordering::Relaxed) {
+        return;
+    }
+
+    log::info!("⌨️  Dictation stopped");
+    emit_changed(app, false);
+    tray::set_status(app, None).await;
+}
+
+/// Toggle dictation when its hotkey is pressed; other shortcuts are left to their owners
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    if HOTKEY.lock().unwrap_or_else(|e| e.into_inner()).as_ref() != Some(shortcut) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if is_active() {
+            stop(&app).await;
+        } else if let Err(e) = start(&app).await {
+            log::warn!("⌨️  Couldn't start dictation: {}", e);
+        }
+    });
+}
+
+/// Move the hotkey from `old` to `new`; either may be empty for none
+pub fn replace_hotkey(app: &AppHandle, old: &str, new: &str) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    if !old.is_empty() {
+        if let Err(e) = shortcuts.unregister(old) {
+            log::warn!("Failed to unregister dictation hotkey {}: {}", old, e);
+        }
+    }
+    let mut hotkey = HOTKEY.lock().unwrap_or_else(|e| e.into_inner());
+    *hotkey = None;
+    if new.is_empty() {
+        return Ok(());
+    }
+    let shortcut: Shortcut = new.parse().map_err(|e| format!("Invalid hotkey {}: {}", new, e))?;
+    shortcuts
+        .register(shortcut)
+        .map_err(|e| format!("Failed to register dictation hotkey {}: {}", new, e))?;
+    *hotkey = Some(shortcut);
+    Ok(())
+}
+
+/// Register the saved hotkey at startup
+pub fn restore(app: &AppHandle, settings: &DictationSettings) {
+    if let Err(e) = replace_hotkey(app, "", &settings.hotkey) {
+        log::warn!("⌨️  {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_dictation(state: tauri::State<'_, SettingsState>) -> Result<DictationStatus, String> {
+    let settings = state.lock().await;
+    Ok(DictationStatus {
+        active: is_active(),
+        hotkey: settings.dictation.hotkey.clone(),
+        stop_phrase: settings.dictation.stop_phrase.clone(),
+    })
+}
+
+#[tauri::command]
+pub async fn start_dictation(app: AppHandle) -> Result<String, String> {
+    start(&app).await?;
+    Ok("Dictating into the focused app".to_string())
+}
+
+#[tauri::command]
+pub async fn stop_dictation(app: AppHandle) -> Result<String, String> {
+    stop(&app).await;
+    Ok("Dictation stopped".to_string())
+}
+
+/// Change the hotkey and stop phrase; the hotkey is checked by registering it
+#[tauri::command]
+pub async fn set_dictation(
+    hotkey: String,
+    stop_phrase: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let hotkey = hotkey.trim().to_string();
+    let stop_phrase = stop_phrase.trim().to_lowercase();
+    if !hotkey.is_empty() {
+        hotkey
+            .parse::<Shortcut>()
+            .map_err(|e| format!("Invalid hotkey {}: {}", hotkey, e))?;
+    }
+    if stop_phrase.is_empty() {
+        return Err("The stop phrase can't be empty".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    if let Err(e) = replace_hotkey(&app, &settings.dictation.hotkey, &hotkey) {
+        // Put the old one back rather than leave none
+        let _ = replace_hotkey(&app, "", &settings.dictation.hotkey);
+        return Err(e);
+    }
+    settings.dictation.hotkey = hotkey.clone();
+    settings.dictation.stop_phrase = stop_phrase.clone();
+    settings.save(&app)?;
+    drop(settings);
+
+    log::info!("⌨️  Dictation hotkey {:?}, stop phrase \"{}\"", hotkey, stop_phrase);
+    Ok(if hotkey.is_empty() {
+        format!("Say \"{}\" to stop dictating", stop_phrase)
+    } else {
+        format!("Press {} to dictate; say \"{}\" to stop", hotkey, stop_phrase)
+    })
+}
+
+/// Type one transcript into the focused app, ending dictation at the stop phrase
+#[tauri::command]
+pub async fn dictate(
+    text: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<DictationOutcome, String> {
+    if !is_active() {
+        return Err("Dictation isn't running".to_string());
+    }
+    let stop_phrase = state.lock().await.dictation.stop_phrase.clone();
+
+    let (typed, stopped) = render(&text, &stop_phrase, NEEDS_SPACE.load(Ordering::Relaxed));
+    if !typed.is_empty() {
+        NEEDS_SPACE.store(!typed.ends_with('\n'), Ordering::Relaxed);
+        let keystrokes = typed.clone();
+        tokio::task::spawn_blocking(move || type_text(&keystrokes))
+            .await
+            .map_err(|e| format!("Typing task failed: {}", e))??;
+        log::info!("⌨️  Typed {} characters", typed.chars().count());
+    }
+    if stopped {
+        stop(&app).await;
+    }
+    Ok(DictationOutcome { typed, stopped })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STOP_PHRASE: &str = "stop dictation";
+
+    fn typed(text: &str) -> String {
+        render(text, STOP_PHRASE, false).0
+    }
+
+    #[test]
+    fn multi_word_commands_win_over_their_last_word() {
+        assert_eq!(typed("first point new paragraph second point"), "first point\n\nsecond point");
+        assert_eq!(typed("is it ready question mark"), "is it ready?");
+        assert_eq!(typed("she said open quote hi close quote"), "she said \"hi\"");
+        assert_eq!(typed("see open bracket below close bracket period"), "see (below).");
+    }
+
+    #[test]
+    fn stop_phrase_mid_utterance_drops_the_rest() {
+        assert_eq!(
+            render("send the report stop dictation and delete it", STOP_PHRASE, false),
+            ("send the report".to_string(), true)
+        );
+        assert_eq!(render("Stop dictation.", STOP_PHRASE, false), (String::new(), true));
+        assert_eq!(render("keep going", STOP_PHRASE, false), ("keep going".to_string(), false));
+    }
+
+    #[test]
+    fn spoken_command_replaces_guessed_punctuation() {
+        assert_eq!(typed("Hello, comma world."), "Hello, world.");
+        assert_eq!(typed("Is it ready? Question mark."), "Is it ready?");
+        assert_eq!(typed("Done. New line. next"), "Done.\nnext");
+    }
+
+    #[test]
+    fn continues_the_previous_utterance() {
+        assert_eq!(render("and then", STOP_PHRASE, true).0, " and then");
+        assert_eq!(render("comma and then", STOP_PHRASE, true).0, ", and then");
+    }
+}
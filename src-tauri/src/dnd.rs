@@ -0,0 +1,266 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Why do-not-disturb is on, while it is
+static REASON: Mutex<Option<DndReason>> = Mutex::new(None);
+/// Copies of the behavior settings, read from places that can't await the settings lock
+static SUPPRESS_PROACTIVE: AtomicBool = AtomicBool::new(true);
+static SUPPRESS_CUES: AtomicBool = AtomicBool::new(true);
+static SUPPRESS_WAKE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DndReason {
+    /// The OS focus / do-not-disturb mode is on
+    Focus,
+    /// The foreground app is fullscreen (games, videos, presentations)
+    Fullscreen,
+}
+
+/// What the OS do-not-disturb state is detected from, and what it silences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DndSettings {
+    pub detect_focus: bool,
+    pub detect_fullscreen: bool,
+    /// Hold back greetings and check-ins Eva would start on her own
+    pub suppress_proactive: bool,
+    /// Mute earcons
+    pub suppress_cues: bool,
+    /// Ignore the wake word too
+    pub suppress_wake: bool,
+    pub poll_interval_secs: u64,
+}
+
+impl Default for DndSettings {
+    fn default() -> Self {
+        Self {
+            detect_focus: true,
+            detect_fullscreen: true,
+            suppress_proactive: true,
+            suppress_cues: true,
+            suppress_wake: false,
+            poll_interval_secs: 10,
+        }
+    }
+}
+
+/// Event payload for `dnd-state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DndStateEvent {
+    pub active: bool,
+    pub reason: Option<DndReason>,
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    async fn run(program: &str, args: &[&str]) -> Option<String> {
+        let output = tokio::process::Command::new(program).args(args).output().await.ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub async fn focus_mode() -> bool {
+        // GNOME turns banners off for do-not-disturb
+        if let Some(banners) = run("gsettings", &["get", "org.gnome.desktop.notifications", "show-banners"]).await {
+            if banners == "false" {
+                return true;
+            }
+        }
+        // KDE and other notification servers expose it on the freedesktop interface
+        run(
+            "dbus-send",
+            &[
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.Notifications",
+                "/org/freedesktop/Notifications",
+                "org.freedesktop.DBus.Properties.Get",
+                "string:org.freedesktop.Notifications",
+                "string:Inhibited",
+            ],
+        )
+        .await
+        .is_some_and(|reply| reply.contains("boolean true"))
+    }
+
+    /// X11 only; Wayland doesn't let other clients inspect windows
+    pub async fn fullscreen_app() -> bool {
+        let Some(active) = run("xprop", &["-root", "_NET_ACTIVE_WINDOW"]).await else {
+            return false;
+        };
+        // "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"
+        let Some(id) = active.rsplit(' ').next().filter(|id| id.starts_with("0x") && *id != "0x0") else {
+            return false;
+        };
+        run("xprop", &["-id", id, "_NET_WM_STATE"])
+            .await
+            .is_some_and(|state| state.contains("_NET_WM_STATE_FULLSCREEN"))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME,
+        QUNS_RUNNING_D3D_FULL_SCREEN,
+    };
+
+    // Focus Assist has no public API; the shell's notification state is what apps are meant to honor
+    fn notification_state() -> Option<windows::Win32::UI::Shell::QUERY_USER_NOTIFICATION_STATE> {
+        unsafe { SHQueryUserNotificationState().ok() }
+    }
+
+    pub async fn focus_mode() -> bool {
+        notification_state() == Some(QUNS_QUIET_TIME)
+    }
+
+    pub async fn fullscreen_app() -> bool {
+        matches!(
+            notification_state(),
+            Some(QUNS_BUSY) | Some(QUNS_RUNNING_D3D_FULL_SCREEN) | Some(QUNS_PRESENTATION_MODE)
+        )
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    pub async fn focus_mode() -> bool {
+        // macOS 12+ keeps the active Focus assertions here; the list is empty when no Focus is on
+        let Some(home) = std::env::var_os("HOME") else {
+            return false;
+        };
+        let path = std::path::Path::new(&home).join("Library/DoNotDisturb/DB/Assertions.json");
+        let Ok(contents) = tokio::fs::read_to_string(path).await else {
+            return false;
+        };
+        let Ok(assertions) = serde_json::from_str::<serde_json::Value>(&contents) else {
+            return false;
+        };
+        assertions["data"]
+            .as_array()
+            .is_some_and(|data| {
+                data.iter().any(|entry| {
+                    entry["storeAssertionRecords"].as_array().is_some_and(|records| !records.is_empty())
+                })
+            })
+    }
+
+    /// Needs accessibility access; without it the check quietly reports no fullscreen app
+    pub async fn fullscreen_app() -> bool {
+        const SCRIPT: &str = "tell application \"System Events\" to get value of attribute \"AXFullScreen\" \
+                              of front window of (first process whose frontmost is true)";
+        let Ok(output) = tokio::process::Command::new("osascript").args(["-e", SCRIPT]).output().await else {
+            return false;
+        };
+        output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true"
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub async fn focus_mode() -> bool {
+        false
+    }
+
+    pub async fn fullscreen_app() -> bool {
+        false
+    }
+}
+
+fn reason() -> Option<DndReason> {
+    *REASON.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn is_active() -> bool {
+    reason().is_some()
+}
+
+/// Proactive remarks should wait
+pub fn silences_proactive() -> bool {
+    is_active() && SUPPRESS_PROACTIVE.load(Ordering::Relaxed)
+}
+
+/// Earcons should stay silent
+pub fn silences_cues() -> bool {
+    is_active() && SUPPRESS_CUES.load(Ordering::Relaxed)
+}
+
+/// Wake words should be ignored
+pub fn silences_wake() -> bool {
+    is_active() && SUPPRESS_WAKE.load(Ordering::Relaxed)
+}
+
+fn remember_behavior(settings: &DndSettings) {
+    SUPPRESS_PROACTIVE.store(settings.suppress_proactive, Ordering::Relaxed);
+    SUPPRESS_CUES.store(settings.suppress_cues, Ordering::Relaxed);
+    SUPPRESS_WAKE.store(settings.suppress_wake, Ordering::Relaxed);
+}
+
+async fn detect(settings: &DndSettings) -> Option<DndReason> {
+    if settings.detect_focus && platform::focus_mode().await {
+        Some(DndReason::Focus)
+    } else if settings.detect_fullscreen && platform::fullscreen_app().await {
+        Some(DndReason::Fullscreen)
+    } else {
+        None
+    }
+}
+
+/// Background task that follows the OS do-not-disturb state and fullscreen apps
+pub async fn run_dnd_monitor(app: AppHandle) {
+    let settings_state = app.state::<SettingsState>().inner().clone();
+
+    log::info!("🔕 Do-not-disturb monitor started");
+
+    loop {
+        let settings = settings_state.lock().await.dnd.clone();
+        remember_behavior(&settings);
+
+        let detected = detect(&settings).await;
+        let previous = std::mem::replace(&mut *REASON.lock().unwrap_or_else(|e| e.into_inner()), detected);
+        if detected != previous {
+            match detected {
+                Some(reason) => log::info!("🔕 Do-not-disturb on ({:?})", reason),
+                None => log::info!("🔔 Do-not-disturb off"),
+            }
+
+            let event = DndStateEvent { active: detected.is_some(), reason: detected };
+            if let Err(e) = app.emit("dnd-state", &event) {
+                log::error!("Failed to emit do-not-disturb state: {}", e);
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(settings.poll_interval_secs.max(2))).await;
+    }
+}
+
+#[tauri::command]
+pub async fn get_dnd_state() -> Result<DndStateEvent, String> {
+    let reason = reason();
+    Ok(DndStateEvent { active: reason.is_some(), reason })
+}
+
+/// Detection changes take effect at the monitor's next check
+#[tauri::command]
+pub async fn set_dnd_behavior(
+    behavior: DndSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    remember_behavior(&behavior);
+
+    let mut settings = state.lock().await;
+    settings.dnd = behavior;
+    settings.save(&app)?;
+    drop(settings);
+
+    log::info!("🔕 Do-not-disturb behavior updated");
+    Ok("Do-not-disturb behavior saved".to_string())
+}
@@ -0,0 +1,412 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Grace period before restoring volume, so gaps between response audio chunks don't pump it
+const RESTORE_DELAY: Duration = Duration::from_millis(600);
+
+/// Lower other apps' audio while Eva listens or speaks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuckingSettings {
+    pub enabled: bool,
+    /// Volume other apps are lowered to, as a fraction of their current volume
+    pub level: f32,
+}
+
+impl Default for DuckingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            level: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuckReason {
+    /// Capturing the user's request after the wake word
+    Listening,
+    /// Eva's response is playing
+    Speaking,
+}
+
+struct DuckState {
+    listening: bool,
+    speaking: bool,
+    /// Volumes to put back; `Some` while ducked
+    saved: Option<platform::SavedVolumes>,
+    /// Bumped on every change so a stale delayed restore does nothing
+    generation: u64,
+}
+
+static STATE: tokio::sync::Mutex<DuckState> = tokio::sync::Mutex::const_new(DuckState {
+    listening: false,
+    speaking: false,
+    saved: None,
+    generation: 0,
+});
+
+/// Whether `pid` is `ancestor` or one of its descendants, e.g. the webview process playing Eva's voice
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn descends_from(mut pid: u32, ancestor: u32, parent_of: impl Fn(u32) -> Option<u32>) -> bool {
+    // Bounded, since pid reuse can make the chain loop
+    for _ in 0..32 {
+        if pid == ancestor {
+            return true;
+        }
+        match parent_of(pid) {
+            Some(parent) if parent != 0 && parent != pid => pid = parent,
+            _ => return false,
+        }
+    }
+    false
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::descends_from;
+
+    /// Sink inputs are per stream, so Eva's own voice is left alone
+    pub const OTHER_APPS_ONLY: bool = true;
+
+    /// Sink input index and its original volume in percent
+    pub type SavedVolumes = Vec<(u32, u32)>;
+
+    struct SinkInput {
+        index: u32,
+        volume_percent: Option<u32>,
+        pid: Option<u32>,
+    }
+
+    fn parent_pid(pid: u32) -> Option<u32> {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        // The command name may contain spaces, so parse after its closing parenthesis
+        stat.rsplit_once(')')?.1.split_whitespace().nth(1)?.parse().ok()
+    }
+
+    async fn sink_inputs() -> Result<Vec<SinkInput>, String> {
+        let output = tokio::process::Command::new("pactl")
+            .args(["list", "sink-inputs"])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run pactl: {}", e))?;
+        if !output.status.success() {
+            return Err("pactl list sink-inputs failed".to_string());
+        }
+
+        let mut inputs: Vec<SinkInput> = Vec::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let line = line.trim();
+            if let Some(index) = line.strip_prefix("Sink Input #") {
+                if let Ok(index) = index.parse() {
+                    inputs.push(SinkInput { index, volume_percent: None, pid: None });
+                }
+            } else if let Some(input) = inputs.last_mut() {
+                if let Some(volume) = line.strip_prefix("Volume:") {
+                    // "front-left: 65536 / 100% / 0.00 dB, ..."; channels are ducked together from the first
+                    input.volume_percent = volume
+                        .split('/')
+                        .nth(1)
+                        .and_then(|percent| percent.trim().trim_end_matches('%').parse().ok());
+                } else if let Some(pid) = line.strip_prefix("application.process.id = ") {
+                    input.pid = pid.trim_matches('"').parse().ok();
+                }
+            }
+        }
+        Ok(inputs)
+    }
+
+    async fn set_volume(index: u32, percent: u32) -> Result<(), String> {
+        let status = tokio::process::Command::new("pactl")
+            .args(["set-sink-input-volume", &index.to_string(), &format!("{}%", percent)])
+            .status()
+            .await
+            .map_err(|e| format!("Failed to run pactl: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("Failed to set volume of sink input {}", index))
+        }
+    }
+
+    pub async fn duck(level: f32) -> Result<SavedVolumes, String> {
+        let own = std::process::id();
+        let mut saved = Vec::new();
+        for input in sink_inputs().await? {
+            let Some(percent) = input.volume_percent else {
+                continue;
+            };
+            if input.pid.is_some_and(|pid| descends_from(pid, own, parent_pid)) {
+                continue;
+            }
+            if set_volume(input.index, (percent as f32 * level).round() as u32).await.is_ok() {
+                saved.push((input.index, percent));
+            }
+        }
+        Ok(saved)
+    }
+
+    pub async fn restore(saved: SavedVolumes) -> Result<(), String> {
+        // Streams that ended meanwhile just fail to update
+        for (index, percent) in saved {
+            let _ = set_volume(index, percent).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::descends_from;
+    use std::collections::HashMap;
+    use windows::core::Interface;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::Media::Audio::{
+        eConsole, eRender, IAudioSessionControl2, IAudioSessionManager2, IMMDeviceEnumerator,
+        ISimpleAudioVolume, MMDeviceEnumerator,
+    };
+    use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED};
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+
+    /// WASAPI session volume is per process, so Eva's own voice is left alone
+    pub const OTHER_APPS_ONLY: bool = true;
+
+    /// Process id and the original volume of its audio session
+    pub type SavedVolumes = Vec<(u32, f32)>;
+
+    fn parent_pids() -> HashMap<u32, u32> {
+        let mut parents = HashMap::new();
+        unsafe {
+            let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+                return parents;
+            };
+            let mut entry = PROCESSENTRY32W {
+                dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+                ..Default::default()
+            };
+            if Process32FirstW(snapshot, &mut entry).is_ok() {
+                loop {
+                    parents.insert(entry.th32ProcessID, entry.th32ParentProcessID);
+                    if Process32NextW(snapshot, &mut entry).is_err() {
+                        break;
+                    }
+                }
+            }
+            let _ = CloseHandle(snapshot);
+        }
+        parents
+    }
+
+    /// Call `f` with the process id and volume control of every session on the default output
+    fn for_each_session(mut f: impl FnMut(u32, &ISimpleAudioVolume)) -> windows::core::Result<()> {
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let manager: IAudioSessionManager2 = enumerator
+                .GetDefaultAudioEndpoint(eRender, eConsole)?
+                .Activate(CLSCTX_ALL, None)?;
+            let sessions = manager.GetSessionEnumerator()?;
+            for index in 0..sessions.GetCount()? {
+                let control = sessions.GetSession(index)?;
+                let Ok(pid) = control.cast::<IAudioSessionControl2>().and_then(|c| c.GetProcessId()) else {
+                    continue;
+                };
+                if let Ok(volume) = control.cast::<ISimpleAudioVolume>() {
+                    f(pid, &volume);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn duck(level: f32) -> Result<SavedVolumes, String> {
+        tauri::async_runtime::spawn_blocking(move || {
+            let own = std::process::id();
+            let parents = parent_pids();
+            let mut saved = Vec::new();
+            for_each_session(|pid, volume| unsafe {
+                // Process 0 is the system sounds session
+                if pid == 0 || descends_from(pid, own, |pid| parents.get(&pid).copied()) {
+                    return;
+                }
+                if let Ok(original) = volume.GetMasterVolume() {
+                    if volume.SetMasterVolume(original * level, std::ptr::null()).is_ok() {
+                        saved.push((pid, original));
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to duck audio sessions: {}", e))?;
+            Ok(saved)
+        })
+        .await
+        .map_err(|e| format!("Ducking task failed: {}", e))?
+    }
+
+    pub async fn restore(saved: SavedVolumes) -> Result<(), String> {
+        tauri::async_runtime::spawn_blocking(move || {
+            for_each_session(|pid, volume| unsafe {
+                if let Some((_, original)) = saved.iter().find(|(saved_pid, _)| *saved_pid == pid) {
+                    let _ = volume.SetMasterVolume(*original, std::ptr::null());
+                }
+            })
+            .map_err(|e| format!("Failed to restore audio sessions: {}", e))
+        })
+        .await
+        .map_err(|e| format!("Ducking task failed: {}", e))?
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    /// There's no public per-app volume on macOS, so ducking lowers the output volume
+    /// and is lifted while Eva speaks
+    pub const OTHER_APPS_ONLY: bool = false;
+
+    /// Original output volume, 0-100
+    pub type SavedVolumes = u32;
+
+    async fn osascript(script: &str) -> Result<String, String> {
+        let output = tokio::process::Command::new("osascript")
+            .args(["-e", script])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run osascript: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub async fn duck(level: f32) -> Result<SavedVolumes, String> {
+        let current: u32 = osascript("output volume of (get volume settings)")
+            .await?
+            .parse()
+            .map_err(|_| "Output volume unavailable for this device".to_string())?;
+        osascript(&format!("set volume output volume {}", (current as f32 * level).round() as u32)).await?;
+        Ok(current)
+    }
+
+    pub async fn restore(saved: SavedVolumes) -> Result<(), String> {
+        osascript(&format!("set volume output volume {}", saved)).await.map(|_| ())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub const OTHER_APPS_ONLY: bool = true;
+
+    pub type SavedVolumes = ();
+
+    pub async fn duck(_level: f32) -> Result<SavedVolumes, String> {
+        Err("Audio ducking isn't supported on this platform".to_string())
+    }
+
+    pub async fn restore(_saved: SavedVolumes) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+async fn restore_locked(state: &mut DuckState) {
+    if let Some(saved) = state.saved.take() {
+        match platform::restore(saved).await {
+            Ok(()) => log::info!("🔉 Restored other apps' audio"),
+            Err(e) => log::error!("Failed to restore ducked audio: {}", e),
+        }
+    }
+}
+
+/// Duck or restore to match the active reasons; returns whether audio is ducked
+async fn apply(app: &AppHandle, reason: DuckReason, active: bool) -> bool {
+    let settings = app.state::<SettingsState>().lock().await.ducking.clone();
+    let mut state = STATE.lock().await;
+    match reason {
+        DuckReason::Listening => state.listening = active,
+        DuckReason::Speaking => state.speaking = active,
+    }
+    state.generation += 1;
+
+    let wanted = settings.enabled
+        && if platform::OTHER_APPS_ONLY {
+            state.listening || state.speaking
+        } else {
+            state.listening && !state.speaking
+        };
+
+    if wanted && state.saved.is_none() {
+        match platform::duck(settings.level).await {
+            Ok(saved) => {
+                log::info!("🔉 Ducked other apps' audio to {:.0}% ({:?})", settings.level * 100.0, reason);
+                state.saved = Some(saved);
+            }
+            Err(e) => log::warn!("Failed to duck audio: {}", e),
+        }
+    } else if !wanted && state.saved.is_some() {
+        if state.listening || state.speaking {
+            // macOS only: Eva is about to speak through the ducked output
+            restore_locked(&mut state).await;
+        } else {
+            let generation = state.generation;
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(RESTORE_DELAY).await;
+                let mut state = STATE.lock().await;
+                if state.generation == generation {
+                    restore_locked(&mut state).await;
+                }
+            });
+        }
+    }
+
+    state.saved.is_some()
+}
+
+/// Put back any ducked volumes right away, e.g. on exit
+pub async fn restore_now() {
+    let mut state = STATE.lock().await;
+    state.listening = false;
+    state.speaking = false;
+    state.generation += 1;
+    restore_locked(&mut state).await;
+}
+
+#[tauri::command]
+pub async fn start_ducking(reason: DuckReason, app: AppHandle) -> Result<bool, String> {
+    Ok(apply(&app, reason, true).await)
+}
+
+#[tauri::command]
+pub async fn stop_ducking(reason: DuckReason, app: AppHandle) -> Result<bool, String> {
+    Ok(apply(&app, reason, false).await)
+}
+
+#[tauri::command]
+pub async fn set_ducking(
+    enabled: bool,
+    level: f32,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let level = level.clamp(0.0, 1.0);
+
+    let mut settings = state.lock().await;
+    settings.ducking.enabled = enabled;
+    settings.ducking.level = level;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    if !enabled {
+        restore_now().await;
+    }
+
+    log::info!("🔉 Audio ducking {} ({:.0}%)", if enabled { "enabled" } else { "disabled" }, level * 100.0);
+    Ok(if enabled {
+        identity.render(&format!("Other apps will be lowered to {:.0}% while {{name}} listens or speaks", level * 100.0))
+    } else {
+        "Audio ducking disabled".to_string()
+    })
+}
@@ -0,0 +1,168 @@
+use crate::dnd;
+use crate::settings::SettingsState;
+use crate::tts::SpeechAudio;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+use tauri::{AppHandle, Emitter, Manager};
+
+const EARCON_SAMPLE_RATE: u32 = 24000;
+const ATTACK_MS: u32 = 5;
+const RELEASE_MS: u32 = 25;
+
+/// Short sounds played on state transitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EarconCue {
+    /// Wake word or push-to-talk opened the microphone
+    ListeningStart,
+    /// The user's turn was received and is being answered
+    Acknowledgment,
+    Error,
+    /// A timer, alarm or reminder went off
+    Alarm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CueSettings {
+    pub enabled: bool,
+    /// 0.0 - 1.0
+    pub volume: f32,
+}
+
+impl Default for CueSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            volume: 0.4,
+        }
+    }
+}
+
+/// Per-cue earcon settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EarconSettings {
+    pub listening_start: CueSettings,
+    pub acknowledgment: CueSettings,
+    pub error: CueSettings,
+    pub alarm: CueSettings,
+}
+
+impl EarconSettings {
+    fn cue(&self, cue: EarconCue) -> &CueSettings {
+        match cue {
+            EarconCue::ListeningStart => &self.listening_start,
+            EarconCue::Acknowledgment => &self.acknowledgment,
+            EarconCue::Error => &self.error,
+            EarconCue::Alarm => &self.alarm,
+        }
+    }
+
+    fn cue_mut(&mut self, cue: EarconCue) -> &mut CueSettings {
+        match cue {
+            EarconCue::ListeningStart => &mut self.listening_start,
+            EarconCue::Acknowledgment => &mut self.acknowledgment,
+            EarconCue::Error => &mut self.error,
+            EarconCue::Alarm => &mut self.alarm,
+        }
+    }
+}
+
+/// Event payload for `earcon`; the frontend plays it through the playback service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarconEvent {
+    pub cue: EarconCue,
+    #[serde(flatten)]
+    pub audio: SpeechAudio,
+}
+
+/// Built-in sounds are synthesized rather than bundled, so there are no assets to ship
+fn render(cue: EarconCue, volume: f32) -> Vec<i16> {
+    // (frequency in Hz, duration in ms) for each note
+    let notes: &[(f32, u32)] = match cue {
+        EarconCue::ListeningStart => &[(660.0, 70), (880.0, 110)],
+        EarconCue::Acknowledgment => &[(988.0, 80)],
+        EarconCue::Error => &[(440.0, 120), (330.0, 200)],
+        // Three double beeps, with silent (0 Hz) gaps
+        EarconCue::Alarm => &[
+            (880.0, 120), (0.0, 60), (880.0, 120), (0.0, 300),
+            (880.0, 120), (0.0, 60), (880.0, 120), (0.0, 300),
+            (880.0, 120), (0.0, 60), (880.0, 120),
+        ],
+    };
+    notes.iter().flat_map(|&(frequency, ms)| tone(frequency, ms, volume)).collect()
+}
+
+/// Sine tone with a short fade in and out so notes don't click
+fn tone(frequency: f32, duration_ms: u32, volume: f32) -> Vec<i16> {
+    let total = (EARCON_SAMPLE_RATE * duration_ms / 1000) as usize;
+    let attack = (EARCON_SAMPLE_RATE * ATTACK_MS / 1000) as usize;
+    let release = (EARCON_SAMPLE_RATE * RELEASE_MS / 1000) as usize;
+    let amplitude = volume.clamp(0.0, 1.0) * i16::MAX as f32;
+
+    (0..total)
+        .map(|i| {
+            let envelope = (i as f32 / attack as f32)
+                .min((total - i) as f32 / release as f32)
+                .min(1.0);
+            let phase = 2.0 * PI * frequency * i as f32 / EARCON_SAMPLE_RATE as f32;
+            (phase.sin() * envelope * amplitude) as i16
+        })
+        .collect()
+}
+
+/// Play a cue if it's enabled in settings
+pub async fn play(app: &AppHandle, cue: EarconCue) {
+    if dnd::silences_cues() {
+        return;
+    }
+    let settings = app.state::<SettingsState>().lock().await.earcons.cue(cue).clone();
+    if !settings.enabled || settings.volume <= 0.0 {
+        return;
+    }
+
+    let event = EarconEvent {
+        cue,
+        audio: SpeechAudio::from_samples(&render(cue, settings.volume), EARCON_SAMPLE_RATE),
+    };
+    if let Err(e) = app.emit("earcon", &event) {
+        log::error!("Failed to emit earcon event: {}", e);
+    }
+}
+
+/// `play` for callers outside the async runtime, such as the audio processing thread
+pub fn spawn_play(app: &AppHandle, cue: EarconCue) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { play(&app, cue).await });
+}
+
+/// Trigger a cue for transitions the frontend owns, or to preview one
+#[tauri::command]
+pub async fn play_earcon(cue: EarconCue, app: AppHandle) -> Result<(), String> {
+    play(&app, cue).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_earcon(
+    cue: EarconCue,
+    enabled: Option<bool>,
+    volume: Option<f32>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    let cue_settings = settings.earcons.cue_mut(cue);
+    if let Some(enabled) = enabled {
+        cue_settings.enabled = enabled;
+    }
+    if let Some(volume) = volume {
+        cue_settings.volume = volume.clamp(0.0, 1.0);
+    }
+    let updated = cue_settings.clone();
+    settings.save(&app)?;
+
+    log::info!("🔔 Earcon {:?}: enabled={}, volume={:.2}", cue, updated.enabled, updated.volume);
+    Ok(format!("Earcon {:?} updated", cue))
+}
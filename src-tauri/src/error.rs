@@ -0,0 +1,146 @@
+use crate::earcons::{self, EarconCue};
+use crate::identity;
+use crate::wake_word::WakeWordError;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use tauri::{AppHandle, Emitter};
+
+/// Realtime API failures; the WebSocket lives in the frontend, which reports them here
+#[derive(Debug, Clone, thiserror::Error, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "kebab-case")]
+pub enum RealtimeError {
+    #[error("Realtime connection failed: {0}")]
+    Connection(String),
+    #[error("Realtime authentication failed: {0}")]
+    Authentication(String),
+    #[error("OpenAI quota exceeded: {0}")]
+    QuotaExceeded(String),
+    #[error("Realtime session error: {0}")]
+    Session(String),
+    #[error("Realtime certificate check failed: {0}")]
+    Certificate(String),
+}
+
+/// Microphone capture failures
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AudioCaptureError {
+    #[error("No input device available")]
+    NoInputDevice,
+    #[error("Input device unavailable: {0}")]
+    DeviceUnavailable(String),
+    #[error("Unsupported sample format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Audio stream failed: {0}")]
+    Stream(String),
+}
+
+/// Crate-wide error returned from commands and broadcast on the `eva-error` channel.
+///
+/// Serializes as `{ code, message, recovery_hint }` so the frontend can branch on `code`.
+#[derive(Debug, thiserror::Error)]
+pub enum EvaError {
+    #[error(transparent)]
+    WakeWord(#[from] WakeWordError),
+    #[error(transparent)]
+    Realtime(#[from] RealtimeError),
+    #[error(transparent)]
+    AudioCapture(#[from] AudioCaptureError),
+    #[error("Settings error: {0}")]
+    Settings(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl EvaError {
+    /// Stable, dotted identifier for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            EvaError::WakeWord(e) => match e {
+                WakeWordError::PorcupineInit(_) => "wake_word.init_failed",
+                WakeWordError::AudioDevice(_) => "wake_word.audio_device",
+                WakeWordError::AccessKey(_) => "wake_word.access_key",
+                WakeWordError::Resampling(_) => "wake_word.resampling",
+                WakeWordError::AlreadyListening => "wake_word.already_listening",
+                WakeWordError::NotListening => "wake_word.not_listening",
+                WakeWordError::TextOnlyMode => "wake_word.text_only_mode",
+                WakeWordError::ServiceStopped => "wake_word.service_stopped",
+            },
+            EvaError::Realtime(e) => match e {
+                RealtimeError::Connection(_) => "realtime.connection",
+                RealtimeError::Authentication(_) => "realtime.authentication",
+                RealtimeError::QuotaExceeded(_) => "realtime.quota_exceeded",
+                RealtimeError::Session(_) => "realtime.session",
+                RealtimeError::Certificate(_) => "realtime.certificate",
+            },
+            EvaError::AudioCapture(e) => match e {
+                AudioCaptureError::NoInputDevice => "audio.no_input_device",
+                AudioCaptureError::DeviceUnavailable(_) => "audio.device_unavailable",
+                AudioCaptureError::UnsupportedFormat(_) => "audio.unsupported_format",
+                AudioCaptureError::Stream(_) => "audio.stream",
+            },
+            EvaError::Settings(_) => "settings",
+            EvaError::Internal(_) => "internal",
+        }
+    }
+
+    /// What the user can do about it, if anything, with the assistant's configured name
+    pub fn recovery_hint(&self) -> Option<String> {
+        let template = match self {
+            EvaError::WakeWord(WakeWordError::AccessKey(_)) => {
+                Some("Set PV_ACCESS_KEY or store your Picovoice access key in the system keychain")
+            }
+            EvaError::WakeWord(WakeWordError::PorcupineInit(_)) => {
+                Some("Check that your Picovoice access key is valid and the wake word model matches this platform")
+            }
+            EvaError::WakeWord(WakeWordError::AudioDevice(_))
+            | EvaError::AudioCapture(AudioCaptureError::NoInputDevice)
+            | EvaError::AudioCapture(AudioCaptureError::DeviceUnavailable(_)) => {
+                Some("Connect a microphone and allow {name} to use it in your system privacy settings")
+            }
+            EvaError::WakeWord(WakeWordError::TextOnlyMode) => {
+                Some("Switch back to voice chat to use the wake word")
+            }
+            EvaError::Realtime(RealtimeError::Connection(_)) => {
+                Some("Check your internet connection; {name} retries automatically")
+            }
+            EvaError::Realtime(RealtimeError::Authentication(_)) => {
+                Some("Re-enter your OpenAI (or Azure) API key")
+            }
+            EvaError::Realtime(RealtimeError::Certificate(_)) => {
+                Some("If your network inspects TLS traffic, add your organization's root certificate under Network Proxy")
+            }
+            EvaError::Realtime(RealtimeError::QuotaExceeded(_)) => {
+                Some("Check your billing at https://platform.openai.com/account/billing")
+            }
+            _ => None,
+        };
+        template.map(|template| identity::current().render(template))
+    }
+
+    /// Broadcast on the `eva-error` channel and play the error cue
+    pub fn emit(&self, app: &AppHandle) {
+        if let Err(e) = app.emit("eva-error", self) {
+            log::error!("Failed to emit error event: {}", e);
+        }
+        earcons::spawn_play(app, EarconCue::Error);
+    }
+}
+
+impl Serialize for EvaError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("EvaError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("recovery_hint", &self.recovery_hint())?;
+        state.end()
+    }
+}
+
+/// Route a frontend Realtime error through the shared `eva-error` channel
+#[tauri::command]
+pub async fn report_realtime_error(error: RealtimeError, app: AppHandle) -> Result<(), String> {
+    let error = EvaError::from(error);
+    log::error!("{}", error);
+    error.emit(&app);
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const MAX_WINDOW_SECS: u64 = 60;
+
+/// Set while the session is waiting for a follow-up without the wake word
+static WINDOW_OPEN: AtomicBool = AtomicBool::new(false);
+/// Bumped on every open/close so a stale timer can't close a newer window
+static WINDOW_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Follow-up turns after a response, without repeating the wake word
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FollowupSettings {
+    pub enabled: bool,
+    /// How long the session stays hot after a response completes
+    pub window_secs: u64,
+}
+
+impl Default for FollowupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FollowupCloseReason {
+    /// Nobody spoke; the frontend stops capturing and Eva waits for the wake word
+    Timeout,
+    /// The user started a follow-up turn
+    Speech,
+    /// The session ended or the user stopped recording
+    Cancelled,
+}
+
+/// Event payload for `followup-window-open`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowupOpenEvent {
+    pub duration_ms: u64,
+}
+
+/// Event payload for `followup-window-closed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowupClosedEvent {
+    pub reason: FollowupCloseReason,
+}
+
+fn close(app: &AppHandle, reason: FollowupCloseReason) -> bool {
+    if !WINDOW_OPEN.swap(false, Ordering::Relaxed) {
+        return false;
+    }
+    WINDOW_GENERATION.fetch_add(1, Ordering::Relaxed);
+
+    log::info!("⏳ Follow-up window closed ({:?})", reason);
+    if let Err(e) = app.emit("followup-window-closed", &FollowupClosedEvent { reason }) {
+        log::error!("Failed to emit follow-up window event: {}", e);
+    }
+    true
+}
+
+/// A response finished: keep listening for a follow-up, restarting any open window.
+///
+/// Returns whether a window was opened.
+#[tauri::command]
+pub async fn open_followup_window(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let settings = state.lock().await.followup.clone();
+    if !settings.enabled {
+        return Ok(false);
+    }
+
+    let generation = WINDOW_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    WINDOW_OPEN.store(true, Ordering::Relaxed);
+
+    let duration = Duration::from_secs(settings.window_secs);
+    log::info!("⏳ Follow-up window open for {}s", settings.window_secs);
+    app.emit("followup-window-open", &FollowupOpenEvent { duration_ms: duration.as_millis() as u64 })
+        .map_err(|e| format!("Failed to emit follow-up window event: {}", e))?;
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(duration).await;
+        if WINDOW_GENERATION.load(Ordering::Relaxed) == generation {
+            close(&app, FollowupCloseReason::Timeout);
+        }
+    });
+
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn close_followup_window(
+    reason: FollowupCloseReason,
+    app: AppHandle,
+) -> Result<bool, String> {
+    Ok(close(&app, reason))
+}
+
+#[tauri::command]
+pub async fn set_followup_window(
+    enabled: bool,
+    window_secs: u64,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if window_secs == 0 || window_secs > MAX_WINDOW_SECS {
+        return Err(format!("Follow-up window must be between 1 and {} seconds", MAX_WINDOW_SECS));
+    }
+
+    let mut settings = state.lock().await;
+    settings.followup.enabled = enabled;
+    settings.followup.window_secs = window_secs;
+    settings.save(&app)?;
+    drop(settings);
+
+    if !enabled {
+        close(&app, FollowupCloseReason::Cancelled);
+    }
+
+    log::info!("⏳ Follow-up window {} ({}s)", if enabled { "enabled" } else { "disabled" }, window_secs);
+    Ok(if enabled {
+        format!("Follow-up window set to {}s", window_secs)
+    } else {
+        "Follow-up window disabled".to_string()
+    })
+}
@@ -0,0 +1,108 @@
+use crate::audio;
+use crate::chat_mode;
+use crate::debug_audio;
+use crate::dictation;
+use crate::identity;
+use crate::language::{self, LanguageChangedEvent};
+use crate::persona::{self, PersonaChangedEvent};
+use crate::playback;
+use crate::porcupine_service::WakeWordHandle;
+use crate::power;
+use crate::privacy;
+use crate::profiles::{self, ProfileChangedEvent};
+use crate::proxy;
+use crate::settings::EvaSettings;
+use crate::sleep_inhibit;
+use crate::speaker_id;
+use crate::wake_cooldown;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Compared through their serialized form, so settings types don't all need `PartialEq`
+fn changed<T: Serialize>(old: &T, new: &T) -> bool {
+    serde_json::to_value(old).ok() != serde_json::to_value(new).ok()
+}
+
+fn emit<T: Serialize + Clone>(app: &AppHandle, event: &str, payload: T) {
+    if let Err(e) = app.emit(event, payload) {
+        log::error!("Failed to emit {}: {}", event, e);
+    }
+}
+
+/// Push settings replaced wholesale (`update_settings`) to the running services, the way their
+/// own commands would: copies read outside the settings lock, the wake word listener, and events
+/// the frontend applies to the live session with `session.update`
+pub async fn apply(app: &AppHandle, old: &EvaSettings, new: &EvaSettings) {
+    identity::restore(&new.identity);
+    privacy::restore(&new.privacy);
+    power::restore(&new.power);
+    sleep_inhibit::restore(&new.sleep_inhibit);
+    wake_cooldown::restore(&new.wake_cooldown);
+    proxy::restore(&new.proxy);
+    profiles::restore(&new.profiles);
+    audio::devices::restore(&new.audio_input);
+    audio::host::restore(&new.audio_input);
+    chat_mode::restore(&new.chat_mode);
+    debug_audio::restore(&new.debug_audio);
+    if old.dictation.hotkey != new.dictation.hotkey {
+        if let Err(e) = dictation::replace_hotkey(app, &old.dictation.hotkey, &new.dictation.hotkey) {
+            log::error!("{}", e);
+        }
+    }
+    if changed(&old.speaker_id, &new.speaker_id) {
+        speaker_id::reload(app, &new.speaker_id);
+    }
+
+    let wake_word = app.state::<WakeWordHandle>();
+    let models = profiles::wake_word_models(new);
+    if changed(&profiles::wake_word_models(old), &models) {
+        log::info!("🔄 Wake word settings changed; rebuilding Porcupine");
+        if let Err(e) = wake_word.set_models(models, app.clone()).await {
+            log::error!("Failed to apply wake word settings: {}", e);
+        }
+    }
+    if changed(&old.audio_input.channel_strategy, &new.audio_input.channel_strategy) {
+        if let Err(e) = wake_word.set_channel_strategy(new.audio_input.channel_strategy, app.clone()).await {
+            log::error!("Failed to apply input channel: {}", e);
+        }
+    }
+    if changed(&old.audio_input.buffer_size, &new.audio_input.buffer_size) {
+        if let Err(e) = wake_word.set_buffer_size(new.audio_input.buffer_size, app.clone()).await {
+            log::error!("Failed to apply capture buffer size: {}", e);
+        }
+    }
+    let device = |settings: &EvaSettings| {
+        let input = &settings.audio_input;
+        (input.input_device.clone(), input.exclude_monitors, input.capture_host, input.prefer_native_rate)
+    };
+    if device(old) != device(new) {
+        log::info!("🔄 Input device settings changed; reopening the microphone");
+        if let Err(e) = wake_word.restart(app.clone()).await {
+            log::error!("Failed to reopen the microphone: {}", e);
+        }
+    }
+
+    if old.persona.active != new.persona.active
+        || old.persona.active_instructions() != new.persona.active_instructions()
+    {
+        persona::emit_changed(
+            app,
+            &PersonaChangedEvent {
+                active: new.persona.active.clone(),
+                instructions: new.persona.active_instructions().to_string(),
+            },
+        );
+    }
+    if changed(&old.language, &new.language) {
+        language::emit_changed(app, &LanguageChangedEvent::from(&new.language));
+    }
+    if changed(&old.profiles, &new.profiles) {
+        emit(app, "profile-changed", ProfileChangedEvent::new(new.profiles.effective()));
+    }
+    if changed(&old.audio_format, &new.audio_format) {
+        emit(app, "audio-format-changed", new.audio_format);
+    }
+    if changed(&old.playback, &new.playback) {
+        playback::emit_changed(app, &new.playback);
+    }
+}
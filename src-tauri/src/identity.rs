@@ -0,0 +1,89 @@
+use crate::chat_window;
+use crate::settings::SettingsState;
+use crate::tray;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+pub const DEFAULT_ASSISTANT_NAME: &str = "Eva";
+const MAX_ASSISTANT_NAME_LEN: usize = 32;
+
+/// Mirrors `IdentitySettings` for code that can't await the settings lock, e.g. error serialization
+static CURRENT: Mutex<Option<IdentitySettings>> = Mutex::new(None);
+
+/// Assistant identity shared by every user-facing string
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdentitySettings {
+    pub assistant_name: String,
+}
+
+impl Default for IdentitySettings {
+    fn default() -> Self {
+        Self {
+            assistant_name: DEFAULT_ASSISTANT_NAME.to_string(),
+        }
+    }
+}
+
+impl IdentitySettings {
+    /// Substitute `{name}` placeholders in a user-facing template
+    pub fn render(&self, template: &str) -> String {
+        template.replace("{name}", &self.assistant_name)
+    }
+}
+
+pub fn restore(settings: &IdentitySettings) {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner()) = Some(settings.clone());
+}
+
+/// The saved identity, or the default one before `restore`
+pub fn current() -> IdentitySettings {
+    CURRENT.lock().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default()
+}
+
+/// Event payload for `assistant-name-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityChangedEvent {
+    pub assistant_name: String,
+}
+
+#[tauri::command]
+pub async fn get_assistant_name(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<String, String> {
+    Ok(state.lock().await.identity.assistant_name.clone())
+}
+
+#[tauri::command]
+pub async fn set_assistant_name(
+    name: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Assistant name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_ASSISTANT_NAME_LEN {
+        return Err(format!("Assistant name must be at most {} characters", MAX_ASSISTANT_NAME_LEN));
+    }
+
+    let mut settings = state.lock().await;
+    settings.identity.assistant_name = name.clone();
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    restore(&identity);
+    log::info!("Assistant renamed to {}", name);
+    tray::refresh(&app).await;
+    chat_window::rename(&app, &identity);
+
+    let event = IdentityChangedEvent { assistant_name: name.clone() };
+    if let Err(e) = app.emit("assistant-name-changed", &event) {
+        log::error!("Failed to emit assistant name change: {}", e);
+    }
+
+    Ok(format!("Assistant is now called {}", name))
+}
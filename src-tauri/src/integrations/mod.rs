@@ -0,0 +1,2 @@
+/// Bridges between Eva and other systems
+pub mod mqtt;
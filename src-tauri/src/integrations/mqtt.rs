@@ -0,0 +1,259 @@
+use crate::conversations::Speaker;
+use crate::remote::{self, RemoteCommand};
+use crate::secrets;
+use crate::settings::SettingsState;
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager};
+
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+/// Wait before polling again after a connection error; rumqttc reconnects on the next poll
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// Requests queued for the event loop before `try_publish` starts failing
+const REQUEST_QUEUE_SIZE: usize = 64;
+
+/// App events published as they happen, as `<prefix>/<topic>` with the event's JSON payload
+const FORWARDED_EVENTS: &[(&str, &str)] = &[
+    ("wake-word-detected", "event/wake-word"),
+    ("reminder-fired", "event/reminder"),
+    ("mic-muted", "state/mic-muted"),
+    ("wake-word-toggled", "state/listening"),
+    ("chat-mode-changed", "state/chat-mode"),
+    ("quiet-hours-changed", "state/quiet-hours"),
+    ("call-detected", "state/call"),
+    ("call-ended", "state/call"),
+];
+
+const PASSWORD_SECRET: &str = "mqtt-password";
+
+/// Bumped on every (re)configuration so only the latest connection loop keeps running
+static CONNECTION_GENERATION: AtomicU64 = AtomicU64::new(0);
+static CONNECTED: AtomicBool = AtomicBool::new(false);
+static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+struct Connection {
+    client: AsyncClient,
+    prefix: String,
+}
+
+/// Smart-home bridge: publish what Eva does, take commands from `<prefix>/command/<name>`.
+///
+/// The password lives in the system keychain, not here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttSettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    /// Topic root, e.g. `eva` gives `eva/event/wake-word` and `eva/command/ask`
+    pub topic_prefix: String,
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 1883,
+            client_id: "eva-desktop".to_string(),
+            username: None,
+            topic_prefix: "eva".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttStatus {
+    pub enabled: bool,
+    pub connected: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TranscriptMessage {
+    role: Speaker,
+    text: String,
+}
+
+/// Publish to `<prefix>/<topic>` if connected; never blocks, drops the message if the queue is full
+pub fn publish(topic: &str, payload: impl Into<Vec<u8>>) {
+    let Ok(connection) = CONNECTION.lock() else {
+        return;
+    };
+    let Some(connection) = connection.as_ref() else {
+        return;
+    };
+
+    let topic = format!("{}/{}", connection.prefix, topic);
+    if let Err(e) = connection.client.try_publish(&topic, QoS::AtMostOnce, false, payload) {
+        log::warn!("Failed to publish to {}: {}", topic, e);
+    }
+}
+
+/// Forward app events to the broker; registered once, they publish only while connected
+pub fn init(app: &AppHandle) {
+    for &(event, topic) in FORWARDED_EVENTS {
+        app.listen_any(event, move |event| publish(topic, event.payload()));
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { restart(&app).await });
+}
+
+/// (Re)connect with the current settings, or disconnect if MQTT is off
+async fn restart(app: &AppHandle) {
+    let generation = {
+        let mut connection = match CONNECTION.lock() {
+            Ok(connection) => connection,
+            Err(_) => return,
+        };
+        if let Some(previous) = connection.take() {
+            let _ = previous.client.try_disconnect();
+        }
+        CONNECTION_GENERATION.fetch_add(1, Ordering::Relaxed) + 1
+    };
+    CONNECTED.store(false, Ordering::Relaxed);
+
+    let settings = app.state::<SettingsState>().lock().await.mqtt.clone();
+    if !settings.enabled {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { run(app, settings, generation).await });
+}
+
+async fn run(app: AppHandle, settings: MqttSettings, generation: u64) {
+    let prefix = settings.topic_prefix.trim_end_matches('/').to_string();
+    let status_topic = format!("{}/status", prefix);
+    let command_topic = format!("{}/command/+", prefix);
+
+    let mut options = MqttOptions::new(&settings.client_id, &settings.host, settings.port);
+    options.set_keep_alive(KEEP_ALIVE);
+    options.set_last_will(LastWill::new(&status_topic, "offline", QoS::AtLeastOnce, true));
+    if let Some(username) = settings.username.as_deref().filter(|name| !name.is_empty()) {
+        options.set_credentials(username, secrets::get(PASSWORD_SECRET).unwrap_or_default());
+    }
+
+    let (client, mut event_loop) = AsyncClient::new(options, REQUEST_QUEUE_SIZE);
+    if let Ok(mut connection) = CONNECTION.lock() {
+        // Checked under the lock so a superseded loop can't replace a newer client
+        if CONNECTION_GENERATION.load(Ordering::Relaxed) != generation {
+            return;
+        }
+        *connection = Some(Connection { client: client.clone(), prefix: prefix.clone() });
+    }
+
+    log::info!("🏠 Connecting to MQTT broker {}:{}", settings.host, settings.port);
+
+    while CONNECTION_GENERATION.load(Ordering::Relaxed) == generation {
+        match event_loop.poll().await {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                CONNECTED.store(true, Ordering::Relaxed);
+                log::info!("🏠 Connected to MQTT broker, listening on {}", command_topic);
+                if let Err(e) = client.try_subscribe(&command_topic, QoS::AtLeastOnce) {
+                    log::warn!("Failed to subscribe to {}: {}", command_topic, e);
+                }
+                let _ = client.try_publish(&status_topic, QoS::AtLeastOnce, true, "online");
+            }
+            Ok(Event::Incoming(Packet::Publish(message))) => {
+                handle_command(&app, &prefix, &message.topic, &message.payload);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                if CONNECTED.swap(false, Ordering::Relaxed) {
+                    log::warn!("🏠 MQTT connection lost: {}", e);
+                } else {
+                    log::debug!("MQTT connection failed: {}", e);
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+
+    log::info!("🏠 MQTT connection closed");
+}
+
+/// `<prefix>/command/{ask,announce,mute,unmute,toggle-listening,profile}`; the payload is the text,
+/// the profile name or, for `mute`, an optional `true`/`false`
+fn handle_command(app: &AppHandle, prefix: &str, topic: &str, payload: &[u8]) {
+    let Some(name) = topic.strip_prefix(prefix).and_then(|rest| rest.strip_prefix("/command/")) else {
+        return;
+    };
+    let text = String::from_utf8_lossy(payload).trim().to_string();
+
+    let command = match name {
+        "ask" => RemoteCommand::ask(&text),
+        "announce" => RemoteCommand::announce(&text),
+        "mute" => Some(RemoteCommand::Mute(!matches!(text.to_lowercase().as_str(), "false" | "off" | "0"))),
+        "unmute" => Some(RemoteCommand::Mute(false)),
+        "toggle-listening" => Some(RemoteCommand::ToggleListening),
+        "profile" => Some(RemoteCommand::Profile(text)),
+        _ => None,
+    };
+
+    match command {
+        Some(command) => remote::dispatch(app, command, remote::CommandSource::Mqtt),
+        None => log::warn!("Ignoring MQTT command on {}", topic),
+    }
+}
+
+/// Conversation turns come from the frontend, which owns the Realtime session
+#[tauri::command]
+pub async fn mqtt_publish_transcript(role: Speaker, text: String) -> Result<(), String> {
+    let message = TranscriptMessage { role, text };
+    let payload = serde_json::to_vec(&message).map_err(|e| e.to_string())?;
+    publish("event/transcript", payload);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_mqtt_status(state: tauri::State<'_, SettingsState>) -> Result<MqttStatus, String> {
+    Ok(MqttStatus {
+        enabled: state.lock().await.mqtt.enabled,
+        connected: CONNECTED.load(Ordering::Relaxed),
+    })
+}
+
+/// Save the connection settings and reconnect; `password` of `None` keeps the stored one,
+/// an empty string removes it
+#[tauri::command]
+pub async fn set_mqtt_settings(
+    settings: MqttSettings,
+    password: Option<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if settings.enabled && settings.host.trim().is_empty() {
+        return Err("MQTT broker host is required".to_string());
+    }
+    if settings.topic_prefix.trim_matches('/').is_empty() {
+        return Err("MQTT topic prefix is required".to_string());
+    }
+
+    if let Some(password) = password {
+        secrets::set(PASSWORD_SECRET, &password)?;
+    }
+
+    let mut current = state.lock().await;
+    current.mqtt = MqttSettings {
+        host: settings.host.trim().to_string(),
+        ..settings
+    };
+    current.save(&app)?;
+    let enabled = current.mqtt.enabled;
+    drop(current);
+
+    restart(&app).await;
+
+    log::info!("🏠 MQTT {}", if enabled { "enabled" } else { "disabled" });
+    Ok(if enabled {
+        "MQTT settings saved, connecting...".to_string()
+    } else {
+        "MQTT disabled".to_string()
+    })
+}
@@ -0,0 +1,246 @@
+use crate::privacy;
+use crate::reminders;
+use crate::response_cache;
+use crate::settings::SettingsState;
+use crate::tools::media;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+/// Longer sentences are left to the model
+const MAX_COMMAND_WORDS: usize = 12;
+
+const STOP_PHRASES: &[&str] = &[
+    "stop", "stop it", "stop talking", "be quiet", "shut up", "shush", "hush", "enough", "that's enough",
+    "never mind", "nevermind", "cancel",
+];
+const MIC_WORDS: &[&str] = &["the", "mic", "microphone", "yourself"];
+/// Everything a volume command may contain besides a number
+const VOLUME_WORDS: &[&str] = &[
+    "volume", "turn", "set", "change", "make", "the", "it", "sound", "music", "to", "at", "percent", "a",
+    "bit", "little", "up", "down", "louder", "quieter", "softer", "higher", "lower", "raise", "increase",
+    "decrease",
+];
+const VOLUME_UP_WORDS: &[&str] = &["up", "louder", "higher", "raise", "increase"];
+const VOLUME_DOWN_WORDS: &[&str] = &["down", "quieter", "softer", "lower", "decrease"];
+/// Everything a timer command may contain besides the duration
+const TIMER_WORDS: &[&str] = &["set", "start", "a", "an", "timer", "for", "of", "and"];
+
+const NUMBER_WORDS: &[(&str, u64)] = &[
+    ("a", 1), ("an", 1), ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6),
+    ("seven", 7), ("eight", 8), ("nine", 9), ("ten", 10), ("eleven", 11), ("twelve", 12), ("thirteen", 13),
+    ("fourteen", 14), ("fifteen", 15), ("sixteen", 16), ("seventeen", 17), ("eighteen", 18),
+    ("nineteen", 19), ("twenty", 20), ("thirty", 30), ("forty", 40), ("fifty", 50), ("sixty", 60),
+    ("seventy", 70), ("eighty", 80), ("ninety", 90), ("hundred", 100),
+];
+
+/// Simple commands understood without the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Intent {
+    /// Stop talking; the frontend cuts off the current response
+    Stop,
+    Mute,
+    Unmute,
+    VolumeUp,
+    VolumeDown,
+    SetVolume { percent: u32 },
+    Timer { seconds: u64 },
+}
+
+/// Offline command handling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntentSettings {
+    /// Handle simple commands locally before asking the model
+    pub enabled: bool,
+}
+
+impl Default for IntentSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// What `handle_offline_intent` did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentOutcome {
+    pub intent: Intent,
+    /// Said back to the user; empty for `stop`
+    pub reply: String,
+}
+
+/// "5", "five", "twenty five"; returns the value and how many words it took
+fn parse_number(words: &[&str]) -> Option<(u64, usize)> {
+    let word_value = |word: &str| NUMBER_WORDS.iter().find(|(name, _)| *name == word).map(|(_, value)| *value);
+    let first = *words.first()?;
+    if let Ok(value) = first.trim_end_matches('%').parse::<u64>() {
+        return Some((value, 1));
+    }
+    let value = word_value(first)?;
+    match words.get(1).and_then(|next| word_value(next)) {
+        Some(ones) if (20..100).contains(&value) && value % 10 == 0 && ones < 10 => Some((value + ones, 2)),
+        _ => Some((value, 1)),
+    }
+}
+
+fn unit_seconds(word: &str) -> Option<u64> {
+    match word {
+        "second" | "seconds" | "sec" | "secs" => Some(1),
+        "minute" | "minutes" | "min" | "mins" => Some(60),
+        "hour" | "hours" | "hr" | "hrs" => Some(60 * 60),
+        _ => None,
+    }
+}
+
+/// "a timer for 10 minutes", "five minute timer", "timer for 1 hour and 30 minutes", "half an hour timer"
+fn match_timer(words: &[&str]) -> Option<Intent> {
+    if !words.contains(&"timer") {
+        return None;
+    }
+    let mut seconds = 0;
+    let mut i = 0;
+    while i < words.len() {
+        if words[i] == "half" && words.get(i + 1..i + 3) == Some(["an", "hour"].as_slice()) {
+            seconds += 30 * 60;
+            i += 3;
+        } else if let Some((value, used)) = parse_number(&words[i..]).filter(|_| !TIMER_WORDS.contains(&words[i])) {
+            let unit = words.get(i + used).and_then(|word| unit_seconds(word))?;
+            seconds += value * unit;
+            i += used + 1;
+        } else if matches!(words[i], "a" | "an") && words.get(i + 1).and_then(|word| unit_seconds(word)).is_some() {
+            // "a minute", "an hour"
+            seconds += unit_seconds(words[i + 1])?;
+            i += 2;
+        } else if TIMER_WORDS.contains(&words[i]) {
+            i += 1;
+        } else {
+            return None;
+        }
+    }
+    (seconds > 0).then_some(Intent::Timer { seconds })
+}
+
+fn match_volume(words: &[&str]) -> Option<Intent> {
+    let mut number = None;
+    let mut i = 0;
+    while i < words.len() {
+        if VOLUME_WORDS.contains(&words[i]) {
+            i += 1;
+        } else if let Some((value, used)) = parse_number(&words[i..]) {
+            number = Some(value);
+            i += used;
+        } else {
+            return None;
+        }
+    }
+
+    let mentions_volume = words.iter().any(|word| matches!(*word, "volume" | "louder" | "quieter" | "softer"))
+        || words.starts_with(&["turn"]);
+    if !mentions_volume {
+        return None;
+    }
+    if let Some(percent) = number {
+        return Some(Intent::SetVolume { percent: percent.min(100) as u32 });
+    }
+    let up = words.iter().any(|word| VOLUME_UP_WORDS.contains(word));
+    let down = words.iter().any(|word| VOLUME_DOWN_WORDS.contains(word));
+    match (up, down) {
+        (true, false) => Some(Intent::VolumeUp),
+        (false, true) => Some(Intent::VolumeDown),
+        _ => None,
+    }
+}
+
+fn match_mute(words: &[&str]) -> Option<Intent> {
+    let intent = match *words.first()? {
+        "mute" => Intent::Mute,
+        "unmute" => Intent::Unmute,
+        _ => return None,
+    };
+    words[1..].iter().all(|word| MIC_WORDS.contains(word)).then_some(intent)
+}
+
+/// Match a normalized utterance against the command grammar
+pub fn match_intent(normalized: &str) -> Option<Intent> {
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    if words.is_empty() || words.len() > MAX_COMMAND_WORDS {
+        return None;
+    }
+    if STOP_PHRASES.contains(&normalized) {
+        return Some(Intent::Stop);
+    }
+    match_mute(&words).or_else(|| match_timer(&words)).or_else(|| match_volume(&words))
+}
+
+fn describe_duration(seconds: u64) -> String {
+    let plural = |value: u64, unit: &str| format!("{} {}{}", value, unit, if value == 1 { "" } else { "s" });
+    let parts: Vec<String> = [(seconds / 3600, "hour"), (seconds % 3600 / 60, "minute"), (seconds % 60, "second")]
+        .into_iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| plural(value, unit))
+        .collect();
+    parts.join(" ")
+}
+
+async fn execute(app: &AppHandle, intent: &Intent) -> Result<String, String> {
+    match intent {
+        Intent::Stop => Ok(String::new()),
+        Intent::Mute | Intent::Unmute => {
+            let muted = matches!(intent, Intent::Mute);
+            privacy::set_mic_muted(muted, app.state::<SettingsState>(), app.clone()).await?;
+            Ok(if muted { "Microphone muted" } else { "Microphone unmuted" }.to_string())
+        }
+        Intent::VolumeUp => media::call(serde_json::json!({ "action": "volume_up" })).await,
+        Intent::VolumeDown => media::call(serde_json::json!({ "action": "volume_down" })).await,
+        Intent::SetVolume { percent } => {
+            media::call(serde_json::json!({ "action": "set_volume", "volume": percent })).await
+        }
+        Intent::Timer { seconds } => {
+            reminders::call(app, serde_json::json!({ "kind": "timer", "in_seconds": seconds }))?;
+            Ok(format!("Timer set for {}", describe_duration(*seconds)))
+        }
+    }
+}
+
+/// Run `text` as an offline command if it is one; `None` hands it to the model
+#[tauri::command]
+pub async fn handle_offline_intent(
+    text: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<Option<IntentOutcome>, String> {
+    let settings = state.lock().await;
+    if !settings.intents.enabled {
+        return Ok(None);
+    }
+    let normalized = response_cache::normalize(&text, &settings.identity.assistant_name);
+    drop(settings);
+
+    let Some(intent) = match_intent(&normalized) else {
+        return Ok(None);
+    };
+    log::info!("🧭 Offline command: {:?}", intent);
+
+    let reply = match execute(&app, &intent).await {
+        Ok(reply) => reply,
+        Err(e) => {
+            log::warn!("Offline command {:?} failed: {}", intent, e);
+            format!("That didn't work: {}", e)
+        }
+    };
+    Ok(Some(IntentOutcome { intent, reply }))
+}
+
+#[tauri::command]
+pub async fn set_offline_intents(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.intents.enabled = enabled;
+    settings.save(&app)?;
+
+    log::info!("🧭 Offline commands {}", if enabled { "enabled" } else { "disabled" });
+    Ok(format!("Offline commands {}", if enabled { "enabled" } else { "disabled" }))
+}
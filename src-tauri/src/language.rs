@@ -0,0 +1,217 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// Languages with a display name for the instructions; other locales are passed through as codes
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("th", "Thai"),
+    ("ja", "Japanese"),
+    ("zh", "Chinese"),
+    ("ko", "Korean"),
+    ("vi", "Vietnamese"),
+    ("id", "Indonesian"),
+    ("hi", "Hindi"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("it", "Italian"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+];
+
+/// ISO 639-1 code of a BCP 47 locale, empty for an empty locale
+fn language_code(locale: &str) -> String {
+    locale.split(['-', '_']).next().unwrap_or_default().to_lowercase()
+}
+
+fn language_name(locale: &str) -> Option<String> {
+    let code = language_code(locale);
+    if code.is_empty() {
+        return None;
+    }
+    Some(
+        LANGUAGE_NAMES
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| format!("the language with locale code {}", locale)),
+    )
+}
+
+/// Language Eva listens and responds in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageSettings {
+    /// BCP 47 locale such as "th-TH" or "ja"; empty to follow whatever the user speaks
+    pub locale: String,
+    /// Takes over from `locale` while it's on
+    pub translation: TranslationSettings,
+}
+
+/// Speak a translation of what the user says instead of answering it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslationSettings {
+    /// Locale the user speaks; empty to detect it
+    pub from: String,
+    /// Locale Eva translates into; empty turns translation mode off
+    pub to: String,
+}
+
+impl TranslationSettings {
+    pub fn is_active(&self) -> bool {
+        !self.to.is_empty()
+    }
+}
+
+impl LanguageSettings {
+    /// ISO 639-1 code used as the transcription hint, empty for auto-detect. Translation mode
+    /// transcribes the language being translated from.
+    pub fn transcription_language(&self) -> String {
+        if self.translation.is_active() {
+            language_code(&self.translation.from)
+        } else {
+            language_code(&self.locale)
+        }
+    }
+
+    pub fn language_name(&self) -> Option<String> {
+        language_name(&self.locale)
+    }
+
+    /// Appended to the persona instructions
+    pub fn instructions_hint(&self) -> String {
+        if self.translation.is_active() {
+            let from = language_name(&self.translation.from).unwrap_or_else(|| "whatever language they speak".to_string());
+            let to = language_name(&self.translation.to).unwrap_or_default();
+            return format!(
+                "You are in translation mode. The user speaks {}. Reply only with a spoken translation of what they said into {}, keeping its meaning, tone and names. Don't answer questions or carry out requests in it, and don't add comments of your own; translate those too.",
+                from, to
+            );
+        }
+        match self.language_name() {
+            Some(name) => format!(
+                "Always respond in {}, even if these instructions are written in another language, unless the user asks you to switch.",
+                name
+            ),
+            None => "Respond in the same language the user speaks.".to_string(),
+        }
+    }
+}
+
+/// Event payload for `language-changed`, also returned by the commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageChangedEvent {
+    pub locale: String,
+    pub language_name: Option<String>,
+    pub instructions_hint: String,
+    pub transcription_language: String,
+    /// Set while translation mode is on
+    pub translation: Option<TranslationSettings>,
+}
+
+impl From<&LanguageSettings> for LanguageChangedEvent {
+    fn from(settings: &LanguageSettings) -> Self {
+        Self {
+            locale: settings.locale.clone(),
+            language_name: settings.language_name(),
+            instructions_hint: settings.instructions_hint(),
+            transcription_language: settings.transcription_language(),
+            translation: Some(settings.translation.clone()).filter(TranslationSettings::is_active),
+        }
+    }
+}
+
+fn is_valid_locale(locale: &str) -> bool {
+    locale.is_empty()
+        || (locale.len() <= 16
+            && locale.split(['-', '_']).all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric())
+            }))
+}
+
+pub fn emit_changed(app: &AppHandle, event: &LanguageChangedEvent) {
+    if let Err(e) = app.emit("language-changed", event) {
+        log::error!("Failed to emit language change: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_language(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<LanguageChangedEvent, String> {
+    Ok((&state.lock().await.language).into())
+}
+
+/// Set the conversation language; an empty locale follows the user's language.
+///
+/// Also updates the local Whisper language hint. The frontend pushes the new
+/// instructions and transcription language to the live session.
+#[tauri::command]
+pub async fn set_language(
+    locale: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<LanguageChangedEvent, String> {
+    let locale = locale.trim().to_string();
+    if !is_valid_locale(&locale) {
+        return Err(format!("Invalid locale: {}", locale));
+    }
+
+    let mut settings = state.lock().await;
+    settings.language.locale = locale;
+    settings.stt.language = settings.language.transcription_language();
+    settings.save(&app)?;
+    let event = LanguageChangedEvent::from(&settings.language);
+    drop(settings);
+
+    emit_changed(&app, &event);
+
+    log::info!(
+        "🌐 Language set to {}",
+        event.language_name.as_deref().unwrap_or("auto-detect")
+    );
+    Ok(event)
+}
+
+/// Translate speech in `from` (empty to detect it) into spoken `to`; an empty `to` turns
+/// translation mode off and goes back to the conversation language.
+///
+/// Transcription follows `from`, locally and in the live session the frontend updates.
+#[tauri::command]
+pub async fn set_translation_mode(
+    from: String,
+    to: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<LanguageChangedEvent, String> {
+    let translation = TranslationSettings { from: from.trim().to_string(), to: to.trim().to_string() };
+    for locale in [&translation.from, &translation.to] {
+        if !is_valid_locale(locale) {
+            return Err(format!("Invalid locale: {}", locale));
+        }
+    }
+    if translation.is_active() && language_code(&translation.from) == language_code(&translation.to) {
+        return Err("Pick two different languages to translate between".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.language.translation = translation;
+    settings.stt.language = settings.language.transcription_language();
+    settings.save(&app)?;
+    let event = LanguageChangedEvent::from(&settings.language);
+    drop(settings);
+
+    emit_changed(&app, &event);
+
+    match &event.translation {
+        Some(translation) => log::info!(
+            "🌐 Translation mode on: {} → {}",
+            language_name(&translation.from).unwrap_or_else(|| "auto-detect".to_string()),
+            language_name(&translation.to).unwrap_or_default()
+        ),
+        None => log::info!("🌐 Translation mode off"),
+    }
+    Ok(event)
+}
@@ -0,0 +1,136 @@
+use crate::metrics;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Turns kept for the rolling statistics
+const MAX_TURNS: usize = 100;
+/// Turns returned individually by `get_latency_metrics`
+const RECENT_TURNS: usize = 20;
+
+static TURNS: Mutex<VecDeque<TurnLatency>> = Mutex::new(VecDeque::new());
+
+/// Timings of one conversation turn, measured by the frontend; spans it couldn't observe are `None`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TurnLatency {
+    /// Wake word detected -> first byte of Eva's audio received
+    pub wake_to_first_audio_ms: Option<u64>,
+    /// Microphone chunk captured -> handed to the backend, averaged over the turn
+    pub capture_to_send_ms: Option<u64>,
+    /// User turn sent (audio committed or text sent) -> first response delta
+    pub api_first_delta_ms: Option<u64>,
+    /// First audio byte received -> playback started
+    pub playback_start_ms: Option<u64>,
+    pub backend: String,
+    /// Unix timestamp in milliseconds, filled in when recorded
+    pub recorded_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpanStats {
+    pub span: String,
+    pub count: usize,
+    pub last_ms: u64,
+    pub average_ms: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+}
+
+/// Returned by `get_latency_metrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyMetrics {
+    pub turns: usize,
+    pub spans: Vec<SpanStats>,
+    /// Newest first
+    pub recent: Vec<TurnLatency>,
+}
+
+type SpanGetter = fn(&TurnLatency) -> Option<u64>;
+
+const SPANS: &[(&str, SpanGetter)] = &[
+    ("wake_to_first_audio", |turn| turn.wake_to_first_audio_ms),
+    ("capture_to_send", |turn| turn.capture_to_send_ms),
+    ("api_first_delta", |turn| turn.api_first_delta_ms),
+    ("playback_start", |turn| turn.playback_start_ms),
+];
+
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index]
+}
+
+fn span_stats(turns: &VecDeque<TurnLatency>, span: &str, get: SpanGetter) -> Option<SpanStats> {
+    let values: Vec<u64> = turns.iter().filter_map(get).collect();
+    let last_ms = *values.last()?;
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+    Some(SpanStats {
+        span: span.to_string(),
+        count: values.len(),
+        last_ms,
+        average_ms: values.iter().sum::<u64>() / values.len() as u64,
+        p50_ms: percentile(&sorted, 0.5),
+        p95_ms: percentile(&sorted, 0.95),
+    })
+}
+
+fn describe(turn: &TurnLatency) -> String {
+    SPANS
+        .iter()
+        .filter_map(|(span, get)| get(turn).map(|ms| format!("{} {} ms", span, ms)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Store a finished turn's timings and broadcast them as `latency-summary`
+#[tauri::command]
+pub async fn record_latency_turn(mut turn: TurnLatency, app: AppHandle) -> Result<(), String> {
+    turn.recorded_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+
+    {
+        let mut turns = TURNS.lock().map_err(|_| "Latency lock poisoned".to_string())?;
+        turns.push_back(turn.clone());
+        while turns.len() > MAX_TURNS {
+            turns.pop_front();
+        }
+    }
+
+    for (span, get) in SPANS {
+        if let Some(ms) = get(&turn) {
+            metrics::observe_latency(*span, ms);
+        }
+    }
+
+    log::info!("⏱️ Turn latency ({}): {}", turn.backend, describe(&turn));
+    if let Err(e) = app.emit("latency-summary", &turn) {
+        log::error!("Failed to emit latency summary: {}", e);
+    }
+    Ok(())
+}
+
+fn all_span_stats(turns: &VecDeque<TurnLatency>) -> Vec<SpanStats> {
+    SPANS
+        .iter()
+        .filter_map(|(span, get)| span_stats(turns, span, *get))
+        .collect()
+}
+
+/// Rolling statistics of each span over the kept turns
+pub fn span_summaries() -> Vec<SpanStats> {
+    all_span_stats(&TURNS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+#[tauri::command]
+pub async fn get_latency_metrics() -> Result<LatencyMetrics, String> {
+    let turns = TURNS.lock().map_err(|_| "Latency lock poisoned".to_string())?;
+    Ok(LatencyMetrics {
+        turns: turns.len(),
+        spans: all_span_stats(&turns),
+        recent: turns.iter().rev().take(RECENT_TURNS).cloned().collect(),
+    })
+}
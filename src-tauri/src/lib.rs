@@ -2,69 +2,133 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use tauri::Manager;
+use tauri::webview::PageLoadEvent;
+use tauri_plugin_deep_link::DeepLinkExt;
 use std::sync::Arc;
-use cpal::traits::{HostTrait, DeviceTrait, StreamTrait};
-use std::path::Path;
+use cpal::traits::{DeviceTrait, StreamTrait};
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
-use cpal::SampleFormat;
 
+mod app_nap;
+mod audio;
+mod audio_format;
+mod autostart;
+mod avatar;
+mod call_detection;
+mod chat_mode;
+mod chat_window;
+mod conversations;
+mod debug_audio;
+mod diagnostics;
+mod dictation;
+mod dnd;
+mod ducking;
+mod earcons;
+mod error;
+mod followup;
+mod hot_reload;
+mod identity;
+mod integrations;
+mod intents;
+mod language;
+mod latency;
+mod llm_endpoint;
+mod logging;
+mod memory;
+mod metrics;
+mod mic_permission;
+mod mock_realtime;
+mod notifications;
+mod overlay;
+mod persona;
+mod playback;
 mod porcupine_service;
+mod power;
+mod pre_wake;
+mod privacy;
+mod proactive;
+mod profiles;
+mod proxy;
+mod push_to_talk;
+mod quiet_hours;
+mod realtime_relay;
+mod reminders;
+mod remote;
+mod response_cache;
+mod response_profile;
+mod secret_migration;
+mod secrets;
+mod session_idle;
+mod settings;
+mod single_instance;
+mod sleep_inhibit;
+mod speaker_id;
+mod status;
+mod tools;
+mod tray;
+mod tts;
+mod turn_detection;
+mod usage;
+mod users;
+mod wake_cooldown;
 mod wake_word;
+mod whisper_stt;
 
-use porcupine_service::PorcupineService;
+use error::{AudioCaptureError, EvaError};
+use porcupine_service::WakeWordHandle;
+use settings::{EvaSettings, SettingsState};
+use wake_word::WakeWordError;
 
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
 async fn start_wake_word(
-    state: tauri::State<'_, Arc<tokio::sync::Mutex<PorcupineService>>>,
+    state: tauri::State<'_, WakeWordHandle>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, EvaError> {
     log::info!("Starting wake word detection");
     
-    let service = state.inner().clone();
-    let mut service_guard = service.lock().await;
-    
-    match service_guard.start_listening(app).await {
+    match state.start_listening(app.clone()).await {
         Ok(_) => {
             log::info!("Wake word detection started successfully");
             Ok("Wake word detection started successfully".to_string())
         }
+        // A launch at login may have started it before the window loaded
+        Err(WakeWordError::AlreadyListening) => Ok("Wake word detection already running".to_string()),
         Err(e) => {
             log::error!("Failed to start wake word detection: {}", e);
-            Err(format!("Failed to start wake word detection: {}", e))
+            let expected = matches!(e, WakeWordError::TextOnlyMode);
+            let error = EvaError::from(e);
+            // Text-only mode is a user choice, not something to alert about
+            if !expected {
+                error.emit(&app);
+            }
+            Err(error)
         }
     }
 }
 
 #[tauri::command]
 async fn stop_wake_word(
-    state: tauri::State<'_, Arc<tokio::sync::Mutex<PorcupineService>>>,
-) -> Result<String, String> {
+    state: tauri::State<'_, WakeWordHandle>,
+) -> Result<String, EvaError> {
     log::info!("Stopping wake word detection");
     
-    let service = state.inner().clone();
-    let mut service_guard = service.lock().await;
-    
-    match service_guard.stop_listening().await {
+    match state.stop_listening().await {
         Ok(_) => {
             log::info!("Wake word detection stopped successfully");
             Ok("Wake word detection stopped successfully".to_string())
         }
         Err(e) => {
             log::error!("Failed to stop wake word detection: {}", e);
-            Err(format!("Failed to stop wake word detection: {}", e))
+            Err(e.into())
         }
     }
 }
 
 #[tauri::command]
 async fn wake_word_status(
-    state: tauri::State<'_, Arc<tokio::sync::Mutex<PorcupineService>>>,
+    state: tauri::State<'_, WakeWordHandle>,
 ) -> Result<String, String> {
-    let service = state.inner().clone();
-    let service_guard = service.lock().await;
-    
-    let status = if service_guard.is_listening() {
+    let status = if state.is_listening() {
         "Listening for wake words"
     } else {
         "Not listening"
@@ -74,39 +138,38 @@ async fn wake_word_status(
 }
 
 #[tauri::command]
-async fn test_microphone() -> Result<String, String> {
+async fn test_microphone() -> Result<String, EvaError> {
     log::info!("Testing microphone access");
     
-    match cpal::default_host().default_input_device() {
+    match audio::select_input_device(&audio::capture_host()).ok() {
         Some(device) => {
             match device.name() {
                 Ok(name) => {
-                    log::info!("Default input device found: {}", name);
+                    log::info!("Input device found: {}", name);
                     Ok(format!("Microphone accessible: {}", name))
                 }
                 Err(e) => {
                     log::error!("Failed to get device name: {}", e);
-                    Err(format!("Failed to get device name: {}", e))
+                    Err(AudioCaptureError::DeviceUnavailable(e.to_string()).into())
                 }
             }
         }
         None => {
             log::error!("No input device available");
-            Err("No input device available".to_string())
+            Err(AudioCaptureError::NoInputDevice.into())
         }
     }
 }
 
 #[tauri::command]
-async fn test_audio_levels() -> Result<String, String> {
+async fn test_audio_levels() -> Result<String, EvaError> {
     log::info!("Starting audio level test");
 
-    let host = cpal::default_host();
-    let device = host.default_input_device()
-        .ok_or("No input device available")?;
+    let host = audio::capture_host();
+    let device = audio::select_input_device(&host)?;
 
     let config = device.default_input_config()
-        .map_err(|e| format!("Failed to get input config: {}", e))?;
+        .map_err(|e| AudioCaptureError::DeviceUnavailable(format!("Failed to get input config: {}", e)))?;
 
     log::info!("Using audio config: {:?}", config);
 
@@ -122,89 +185,33 @@ async fn test_audio_levels() -> Result<String, String> {
     let config_clone = config.clone();
     let device_clone = device.clone();
     let task_handle = tokio::task::spawn_blocking(move || {
-        let stream = match config_clone.sample_format() {
-            SampleFormat::F32 => {
-                device_clone.build_input_stream(
-                    &config_clone.into(),
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        let level = data.iter().map(|&s| s.abs()).fold(0.0f32, f32::max);
-                        let level_u32 = (level * 1000.0) as u32;
-                        
-                        loop {
-                            let current = max_level_clone.load(Ordering::Relaxed);
-                            if level_u32 <= current || max_level_clone.compare_exchange_weak(current, level_u32, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
-                                break;
-                            }
-                        }
-                        
-                        sample_count_clone.fetch_add(data.len() as u64, Ordering::Relaxed);
-                        
-                        if start_time.elapsed().as_secs() >= 10 {
-                            is_running_clone.store(false, Ordering::Relaxed);
-                        }
-                    },
-                    |err| log::error!("Audio stream error: {}", err),
-                    None,
-                )
-            }
-            SampleFormat::I16 => {
-                device_clone.build_input_stream(
-                    &config_clone.into(),
-                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                        let level = data.iter().map(|&s| (s as f32 / 32768.0).abs()).fold(0.0f32, f32::max);
-                        let level_u32 = (level * 1000.0) as u32;
-                        
-                        loop {
-                            let current = max_level_clone.load(Ordering::Relaxed);
-                            if level_u32 <= current || max_level_clone.compare_exchange_weak(current, level_u32, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
-                                break;
-                            }
-                        }
-                        
-                        sample_count_clone.fetch_add(data.len() as u64, Ordering::Relaxed);
-                        
-                        if start_time.elapsed().as_secs() >= 10 {
-                            is_running_clone.store(false, Ordering::Relaxed);
-                        }
-                    },
-                    |err| log::error!("Audio stream error: {}", err),
-                    None,
-                )
-            }
-            SampleFormat::U16 => {
-                device_clone.build_input_stream(
-                    &config_clone.into(),
-                    move |data: &[u16], _: &cpal::InputCallbackInfo| {
-                        let level = data.iter().map(|&s| ((s as f32 - 32768.0) / 32768.0).abs()).fold(0.0f32, f32::max);
-                        let level_u32 = (level * 1000.0) as u32;
-                        
-                        loop {
-                            let current = max_level_clone.load(Ordering::Relaxed);
-                            if level_u32 <= current || max_level_clone.compare_exchange_weak(current, level_u32, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
-                                break;
-                            }
-                        }
-                        
-                        sample_count_clone.fetch_add(data.len() as u64, Ordering::Relaxed);
-                        
-                        if start_time.elapsed().as_secs() >= 10 {
-                            is_running_clone.store(false, Ordering::Relaxed);
-                        }
-                    },
-                    |err| log::error!("Audio stream error: {}", err),
-                    None,
-                )
-            }
-            format => return Err(format!("Unsupported sample format: {:?}", format)),
-        };
-
-        let stream = match stream {
-            Ok(stream) => stream,
-            Err(e) => return Err(format!("Failed to create audio stream: {}", e)),
-        };
+        let sample_format = config_clone.sample_format();
+        let stream = audio::build_input_stream(
+            &device_clone,
+            &config_clone.into(),
+            sample_format,
+            move |data: &[f32]| {
+                let level = data.iter().map(|&s| s.abs()).fold(0.0f32, f32::max);
+                let level_u32 = (level * 1000.0) as u32;
+                
+                loop {
+                    let current = max_level_clone.load(Ordering::Relaxed);
+                    if level_u32 <= current || max_level_clone.compare_exchange_weak(current, level_u32, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+                        break;
+                    }
+                }
+                
+                sample_count_clone.fetch_add(data.len() as u64, Ordering::Relaxed);
+                
+                if start_time.elapsed().as_secs() >= 10 {
+                    is_running_clone.store(false, Ordering::Relaxed);
+                }
+            },
+            |err| log::error!("Audio stream error: {}", err),
+        )?;
 
         if let Err(e) = stream.play() {
-            return Err(format!("Failed to start audio stream: {}", e));
+            return Err(AudioCaptureError::Stream(format!("Failed to start audio stream: {}", e)));
         }
 
         // Wait for 10 seconds (blocking)
@@ -214,7 +221,7 @@ async fn test_audio_levels() -> Result<String, String> {
     });
 
     // Wait for the task to complete
-    task_handle.await.map_err(|e| format!("Task failed: {}", e))??;
+    task_handle.await.map_err(|e| EvaError::Internal(format!("Task failed: {}", e)))??;
 
     let duration = start_time.elapsed().as_secs_f32();
     let final_sample_count = sample_count.load(Ordering::Relaxed);
@@ -233,31 +240,10 @@ async fn test_audio_levels() -> Result<String, String> {
 }
 
 #[tauri::command]
-async fn get_current_wake_word() -> Result<String, String> {
-    // Check for custom wake word model first
-    let custom_model_path = "models/Hi-Eva.ppn";
-    
-    if Path::new(custom_model_path).exists() {
-        Ok("Hi Eva".to_string())
-    } else {
-        // Determine which built-in keyword is being used
-        let keyword_name = if std::env::var("WAKE_WORD_KEYWORD").is_ok() {
-            match std::env::var("WAKE_WORD_KEYWORD").unwrap().as_str() {
-                "alexa" => "Alexa",
-                "computer" => "Computer",
-                "jarvis" => "Jarvis",
-                "hey-google" => "Hey Google",
-                "ok-google" => "Ok Google",
-                "picovoice" => "Picovoice",
-                "porcupine" => "Porcupine",
-                _ => "Computer", // Default fallback
-            }
-        } else {
-            "Computer" // Default keyword
-        };
-        
-        Ok(keyword_name.to_string())
-    }
+async fn get_current_wake_word(
+    state: tauri::State<'_, WakeWordHandle>,
+) -> Result<String, String> {
+    Ok(state.keyword_name())
 }
 
 // OpenAI Realtime API Commands - REMOVED
@@ -266,63 +252,189 @@ async fn get_current_wake_word() -> Result<String, String> {
 
 // Integration Commands - Wake Word Only
 
+/// Start wake word detection on its own; no Realtime session is needed.
+///
+/// The frontend connects (or reconnects) when the wake word fires, so a
+/// failed connection surfaces then, as an `eva-error` event.
 #[tauri::command]
 async fn start_eva_listening(
-    porcupine_state: tauri::State<'_, Arc<tokio::sync::Mutex<PorcupineService>>>,
+    porcupine_state: tauri::State<'_, WakeWordHandle>,
+    settings_state: tauri::State<'_, SettingsState>,
     app: tauri::AppHandle,
-) -> Result<String, String> {
-    log::info!("Starting Eva wake word listening mode");
+) -> Result<String, EvaError> {
+    let identity = settings_state.lock().await.identity.clone();
+    log::info!("Starting {} wake word listening mode", identity.assistant_name);
     
     // Start wake word detection
-    let porcupine_service = porcupine_state.inner().clone();
-    let mut porcupine_guard = porcupine_service.lock().await;
-    
-    match porcupine_guard.start_listening(app).await {
+    match porcupine_state.start_listening(app.clone()).await {
         Ok(_) => {
-            log::info!("Eva wake word listening started successfully");
-            Ok("Eva is now listening for wake words! Say 'Hi Eva' to trigger.".to_string())
+            log::info!("{} wake word listening started successfully", identity.assistant_name);
+            Ok(format!(
+                "{} Say '{}' to trigger.",
+                identity.render("{name} is now listening for wake words!"),
+                porcupine_state.keyword_name()
+            ))
         }
         Err(e) => {
-            log::error!("Failed to start Eva listening mode: {}", e);
-            Err(format!("Failed to start Eva listening mode: {}", e))
+            log::error!("Failed to start {} listening mode: {}", identity.assistant_name, e);
+            let error = EvaError::from(e);
+            error.emit(&app);
+            Err(error)
         }
     }
 }
 
 #[tauri::command]
 async fn stop_eva_listening(
-    porcupine_state: tauri::State<'_, Arc<tokio::sync::Mutex<PorcupineService>>>,
+    porcupine_state: tauri::State<'_, WakeWordHandle>,
+    settings_state: tauri::State<'_, SettingsState>,
 ) -> Result<String, String> {
-    log::info!("Stopping Eva wake word listening mode");
+    let identity = settings_state.lock().await.identity.clone();
+    log::info!("Stopping {} wake word listening mode", identity.assistant_name);
     
     // Stop wake word detection
-    let porcupine_service = porcupine_state.inner().clone();
-    let mut porcupine_guard = porcupine_service.lock().await;
-    if let Err(e) = porcupine_guard.stop_listening().await {
+    if let Err(e) = porcupine_state.stop_listening().await {
         log::warn!("Failed to stop wake word detection: {}", e);
     }
     
-    log::info!("Eva wake word listening mode stopped");
-    Ok("Eva stopped listening for wake words.".to_string())
+    log::info!("{} wake word listening mode stopped", identity.assistant_name);
+    Ok(identity.render("{name} stopped listening for wake words."))
 }
 
 pub fn run() {
-    // Initialize logging
-    env_logger::init();
-    
-    log::info!("🎤 Eva Desktop - Wake word detection ready");
-    
     tauri::Builder::default()
+        // Registered first so a second launch exits before touching the microphone
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            single_instance::handle_second_launch(app, args);
+        }))
+        // A reloaded webview has lost its state; those whose listeners come up later ask again
+        // with request_state_sync
+        .on_page_load(|webview, payload| match payload.event() {
+            // Remote questions queue until the reloaded page listens again
+            PageLoadEvent::Started if webview.label() == tray::MAIN_WINDOW => remote::frontend_unloaded(),
+            PageLoadEvent::Finished => {
+                status::emit_snapshot(webview.app_handle().clone(), webview.label().to_string());
+            }
+            PageLoadEvent::Started => {}
+        })
         .setup(|app| {
-            // Initialize Porcupine service for wake word detection
-            let porcupine_service = Arc::new(tokio::sync::Mutex::new(PorcupineService::new()));
-            app.manage(porcupine_service);
+            // Logging needs the app log directory, so it starts with the app
+            logging::init(app.handle());
+            log::info!("🎤 Eva Desktop - Wake word detection ready");
+
+            // Load persisted settings before any background task reads them
+            let settings = EvaSettings::load(app.handle());
+
+            // Wake word detection runs as an actor so status reads never wait on start/stop
+            app.manage(WakeWordHandle::spawn(
+                profiles::wake_word_models(&settings),
+                settings.audio_input.channel_strategy,
+                settings.audio_input.buffer_size,
+            ));
+
+            identity::restore(&settings.identity);
+            privacy::restore(&settings.privacy);
+            power::restore(&settings.power);
+            sleep_inhibit::restore(&settings.sleep_inhibit);
+            wake_cooldown::restore(&settings.wake_cooldown);
+            proxy::restore(&settings.proxy);
+            profiles::restore(&settings.profiles);
+            users::restore(&settings.users);
+            audio::devices::restore(&settings.audio_input);
+            audio::host::restore(&settings.audio_input);
+            secrets::restore(app.handle(), &settings.secrets);
+            // Keys still configured through env vars; the frontend offers to move them to the keychain
+            secret_migration::detect(&settings.secret_migration);
+            chat_mode::restore(&settings.chat_mode);
+            debug_audio::restore(&settings.debug_audio);
+            speaker_id::restore(app.handle(), &settings.speaker_id);
+            dictation::restore(app.handle(), &settings.dictation);
+            let autostart_settings = settings.autostart.clone();
+            let overlay_settings = settings.overlay.clone();
+            let identity_settings = settings.identity.clone();
+            app.manage(Arc::new(tokio::sync::Mutex::new(settings)));
+
+            if let Err(e) = tray::init(app.handle(), &identity_settings) {
+                log::warn!("Failed to create tray icon: {}", e);
+            }
+
+            // Float as an overlay again if that's how Eva was left
+            overlay::restore(app.handle(), &overlay_settings);
+
+            // The window starts hidden; a launch at login may stay tray-only
+            autostart::apply_on_launch(app.handle(), &autostart_settings);
+
+            // `eva://` links and CLI flags; later launches arrive through the single-instance plugin
+            remote::handle_launch_args(app.handle());
+            let deep_link_app = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    match remote::RemoteCommand::from_url(&url) {
+                        Some(command) => remote::dispatch(&deep_link_app, command, remote::CommandSource::DeepLink),
+                        None => log::warn!("Ignoring unknown deep link: {}", url),
+                    }
+                }
+            });
+            // Installers register the scheme; this covers dev builds and unregistered AppImages
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                log::warn!("Failed to register the eva:// scheme: {}", e);
+            }
+
+            // Suspend wake word listening while conferencing apps hold the microphone
+            tauri::async_runtime::spawn(call_detection::run_call_monitor(app.handle().clone()));
+
+            // Disable wake word listening during the configured quiet hours
+            tauri::async_runtime::spawn(quiet_hours::run_quiet_hours_scheduler(app.handle().clone()));
+
+            // Follow the OS focus mode and fullscreen apps to keep Eva from interrupting
+            tauri::async_runtime::spawn(dnd::run_dnd_monitor(app.handle().clone()));
+
+            // Switch assistant profiles to follow the foreground app, if enabled
+            tauri::async_runtime::spawn(profiles::run_profile_monitor(app.handle().clone()));
+
+            // Ease off the wake word loop while running on battery
+            tauri::async_runtime::spawn(power::run_power_monitor(app.handle().clone()));
+
+            // Timers, alarms and reminders set by voice; saved ones carry over from the last run
+            reminders::restore(app.handle());
+            tauri::async_runtime::spawn(reminders::run_reminder_scheduler(app.handle().clone()));
+
+            // Greetings and check-ins Eva starts on her own, if enabled
+            tauri::async_runtime::spawn(proactive::run_proactive_scheduler(app.handle().clone()));
+
+            // Publish events to and take commands from an MQTT broker, if configured
+            integrations::mqtt::init(app.handle());
+
+            // Prometheus scrape endpoint on localhost, if enabled
+            metrics::init(app.handle());
             
             log::info!("Eva Desktop initialized successfully - wake word detection ready");
             Ok(())
         })
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(dictation::handle_shortcut)
+                .build(),
+        )
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(
+            tauri_plugin_autostart::Builder::new()
+                .arg(autostart::AUTOSTART_ARG)
+                .build(),
+        )
         .plugin(tauri_plugin_store::Builder::new().build())
+        // Imported VRM/Live2D models, read off the main thread since they can be large
+        .register_asynchronous_uri_scheme_protocol(avatar::PROTOCOL, |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(avatar::serve(&app, request));
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             start_wake_word,
             stop_wake_word,
@@ -331,8 +443,220 @@ pub fn run() {
             test_audio_levels,
             get_current_wake_word,
             start_eva_listening,
-            stop_eva_listening
+            stop_eva_listening,
+            settings::get_settings,
+            settings::update_settings,
+            call_detection::get_call_state,
+            call_detection::set_call_auto_pause,
+            call_detection::set_call_browser_detection,
+            push_to_talk::push_to_talk_pressed,
+            push_to_talk::push_to_talk_released,
+            identity::get_assistant_name,
+            identity::set_assistant_name,
+            whisper_stt::transcribe_audio,
+            whisper_stt::whisper_available,
+            whisper_stt::set_stt_backend,
+            tts::synthesize_speech,
+            tts::set_tts_engine,
+            llm_endpoint::set_llm_endpoint,
+            llm_endpoint::list_llm_models,
+            llm_endpoint::chat_completion,
+            llm_endpoint::cancel_chat_completion,
+            persona::get_personas,
+            persona::set_persona,
+            persona::save_persona_profile,
+            persona::switch_persona,
+            persona::delete_persona_profile,
+            memory::get_memory,
+            memory::save_memory,
+            memory::clear_memory,
+            memory::set_memory_enabled,
+            usage::record_usage,
+            usage::get_usage_stats,
+            usage::set_usage_budget,
+            usage::reset_usage_stats,
+            status::eva_status,
+            status::request_state_sync,
+            status::report_realtime_status,
+            error::report_realtime_error,
+            mic_permission::check_microphone_permission,
+            mic_permission::request_microphone_permission,
+            diagnostics::run_diagnostics,
+            privacy::get_mic_muted,
+            privacy::set_mic_muted,
+            quiet_hours::get_quiet_hours,
+            quiet_hours::set_quiet_hours,
+            wake_word::get_wake_word_stats,
+            wake_word::reset_wake_word_stats,
+            wake_word::report_false_wake_word,
+            pre_wake::take_pre_wake_audio,
+            earcons::play_earcon,
+            earcons::set_earcon,
+            chat_mode::get_text_only_mode,
+            chat_mode::set_text_only_mode,
+            chat_mode::send_chat_message,
+            logging::get_recent_logs,
+            logging::open_log_directory,
+            debug_audio::set_debug_audio,
+            debug_audio::list_debug_recordings,
+            conversations::start_conversation,
+            conversations::append_conversation_turn,
+            conversations::append_conversation_audio,
+            conversations::list_conversations,
+            conversations::export_conversation,
+            conversations::set_conversation_archive,
+            speaker_id::get_voice_profile_status,
+            speaker_id::enroll_voice,
+            speaker_id::set_voice_lock,
+            speaker_id::clear_voice_profile,
+            followup::open_followup_window,
+            followup::close_followup_window,
+            followup::set_followup_window,
+            response_profile::get_response_profile,
+            response_profile::set_response_profile,
+            language::get_language,
+            language::set_language,
+            language::set_translation_mode,
+            wake_word::set_wake_word_model,
+            wake_word::set_wake_word,
+            wake_word::set_wake_word_sensitivity,
+            audio::channels::set_channel_strategy,
+            audio::devices::list_input_devices,
+            audio::devices::set_input_device,
+            audio::devices::set_exclude_monitors,
+            audio::host::get_capture_hosts,
+            audio::host::set_capture_host,
+            audio::host::set_prefer_native_rate,
+            audio::overflow::report_audio_overflow,
+            playback::list_output_devices,
+            playback::set_output_device,
+            playback::set_output_volume,
+            ducking::start_ducking,
+            ducking::stop_ducking,
+            ducking::set_ducking,
+            session_idle::touch_session_activity,
+            session_idle::stop_session_idle_timer,
+            session_idle::set_idle_disconnect,
+            audio::loopback::system_audio_available,
+            audio::loopback::get_capture_source,
+            audio::loopback::set_capture_source,
+            audio::loopback::start_system_audio_capture,
+            audio::loopback::stop_system_audio_capture,
+            notifications::notify_response,
+            notifications::set_notifications_enabled,
+            autostart::get_autostart,
+            autostart::set_autostart,
+            remote::take_pending_questions,
+            integrations::mqtt::mqtt_publish_transcript,
+            integrations::mqtt::get_mqtt_status,
+            integrations::mqtt::set_mqtt_settings,
+            tools::list_tools,
+            tools::call_tool,
+            tools::home_assistant::get_home_assistant,
+            tools::home_assistant::set_home_assistant,
+            reminders::list_reminders,
+            reminders::cancel_reminder,
+            tools::calendar::get_calendar,
+            tools::calendar::set_calendar,
+            tools::calendar::refresh_calendar,
+            tools::actions::get_actions,
+            tools::actions::set_actions,
+            tools::plugins::list_plugins,
+            tools::plugins::set_plugin_enabled,
+            tools::plugins::open_plugins_folder,
+            avatar::list_avatars,
+            avatar::import_avatar,
+            avatar::set_active_avatar,
+            overlay::set_overlay_mode,
+            overlay::set_click_through,
+            overlay::set_window_position_preset,
+            proactive::set_proactive_settings,
+            proactive::proactive_ready,
+            dnd::get_dnd_state,
+            dnd::set_dnd_behavior,
+            response_cache::lookup_cached_response,
+            response_cache::remember_response,
+            response_cache::list_cached_responses,
+            response_cache::clear_response_cache,
+            response_cache::set_response_cache_settings,
+            intents::handle_offline_intent,
+            intents::set_offline_intents,
+            latency::record_latency_turn,
+            latency::get_latency_metrics,
+            metrics::record_realtime_reconnect,
+            metrics::set_metrics_endpoint,
+            conversations::append_reply_audio,
+            conversations::list_conversation_replies,
+            conversations::read_reply_audio,
+            conversations::set_reply_recording,
+            privacy::set_data_privacy,
+            privacy::purge_all_data,
+            wake_word::test_wake_word,
+            mock_realtime::get_mock_realtime_script,
+            audio::buffer_size::get_capture_buffer_info,
+            audio::buffer_size::set_capture_buffer_size,
+            power::get_power_state,
+            power::set_power_profile,
+            turn_detection::get_turn_detection,
+            turn_detection::set_turn_detection,
+            chat_mode::get_chat_mode,
+            chat_mode::set_spoken_replies,
+            chat_window::get_open_windows,
+            chat_window::open_chat_window,
+            chat_window::close_chat_window,
+            chat_window::get_chat_history,
+            chat_window::publish_chat_turn,
+            chat_window::clear_chat_history,
+            wake_cooldown::get_wake_cooldown,
+            wake_cooldown::set_wake_cooldown,
+            wake_word::validate_picovoice_key,
+            diagnostics::validate_openai_key,
+            proxy::get_proxy,
+            proxy::set_proxy,
+            proxy::proxied_request,
+            realtime_relay::realtime_relay_connect,
+            realtime_relay::realtime_relay_send,
+            realtime_relay::realtime_relay_close,
+            audio::overflow::report_audio_congestion,
+            audio_format::get_audio_format,
+            audio_format::set_audio_format,
+            profiles::get_profiles,
+            profiles::get_active_profile,
+            profiles::save_profile,
+            profiles::delete_profile,
+            profiles::switch_profile,
+            profiles::set_profile_auto_switch,
+            users::get_users,
+            users::save_user,
+            users::delete_user,
+            users::switch_user,
+            users::set_user_switch_by_voice,
+            users::get_user_api_key,
+            secret_migration::get_env_secret_offers,
+            secret_migration::answer_env_secret_offers,
+            secret_migration::get_stored_openai_key,
+            secrets::get_secret_store,
+            secrets::set_secret_backend,
+            sleep_inhibit::get_sleep_inhibit,
+            sleep_inhibit::set_sleep_inhibit,
+            dictation::get_dictation,
+            dictation::start_dictation,
+            dictation::stop_dictation,
+            dictation::set_dictation,
+            dictation::dictate
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            tauri::RunEvent::Exit => {
+                // Don't leave other apps' audio lowered
+                tauri::async_runtime::block_on(ducking::restore_now());
+                // The Linux inhibitor is a child process that would outlive Eva
+                sleep_inhibit::release_all();
+            }
+            // Clicking a reply notification activates the app
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Reopen { .. } => notifications::handle_activation(app),
+            _ => {}
+        });
 }
@@ -0,0 +1,239 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Streams that the frontend asked to stop
+static CANCELLED_REQUESTS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// OpenAI-compatible chat-completions endpoint (Ollama, LM Studio, vLLM...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmEndpointSettings {
+    /// Use this endpoint instead of the Realtime API for text conversations
+    pub enabled: bool,
+    /// Base URL including the `/v1` prefix
+    pub base_url: String,
+    pub model: String,
+    /// Optional bearer token; local servers usually don't need one
+    pub api_key: String,
+}
+
+impl Default for LlmEndpointSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: "http://localhost:11434/v1".to_string(),
+            model: "llama3.2".to_string(),
+            api_key: String::new(),
+        }
+    }
+}
+
+impl LlmEndpointSettings {
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if self.api_key.is_empty() {
+            builder
+        } else {
+            builder.bearer_auth(&self.api_key)
+        }
+    }
+}
+
+/// A chat message in chat-completions format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// Event payload for `llm-delta`
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmDeltaEvent {
+    pub request_id: String,
+    pub delta: String,
+}
+
+/// Event payload for `llm-done`
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmDoneEvent {
+    pub request_id: String,
+    pub text: String,
+    pub cancelled: bool,
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    crate::proxy::client_builder()
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn take_cancelled(request_id: &str) -> bool {
+    CANCELLED_REQUESTS
+        .lock()
+        .ok()
+        .and_then(|mut cancelled| cancelled.as_mut().map(|set| set.remove(request_id)))
+        .unwrap_or(false)
+}
+
+/// Pull the text delta out of one SSE `data:` payload
+fn parse_stream_line(line: &str) -> Option<String> {
+    let data = line.strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return None;
+    }
+
+    let chunk: serde_json::Value = serde_json::from_str(data).ok()?;
+    chunk["choices"][0]["delta"]["content"]
+        .as_str()
+        .map(|content| content.to_string())
+}
+
+#[tauri::command]
+pub async fn set_llm_endpoint(
+    base_url: String,
+    model: Option<String>,
+    api_key: Option<String>,
+    enabled: Option<bool>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let base_url = base_url.trim().to_string();
+    if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+        return Err("Endpoint URL must start with http:// or https://".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.llm_endpoint.base_url = base_url.clone();
+    if let Some(model) = model {
+        settings.llm_endpoint.model = model;
+    }
+    if let Some(api_key) = api_key {
+        settings.llm_endpoint.api_key = api_key;
+    }
+    if let Some(enabled) = enabled {
+        settings.llm_endpoint.enabled = enabled;
+    }
+    settings.save(&app)?;
+
+    log::info!("LLM endpoint set to {} ({})", base_url, settings.llm_endpoint.model);
+    Ok(format!("LLM endpoint set to {}", base_url))
+}
+
+/// Model ids served by the configured endpoint, for the model picker
+#[tauri::command]
+pub async fn list_llm_models(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<Vec<String>, String> {
+    let endpoint = state.lock().await.llm_endpoint.clone();
+    let client = http_client()?;
+
+    let response = endpoint.request(client.get(endpoint.url("models")))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", endpoint.base_url, e))?
+        .error_for_status()
+        .map_err(|e| format!("Endpoint rejected model listing: {}", e))?;
+
+    let body: serde_json::Value = response.json()
+        .await
+        .map_err(|e| format!("Invalid model list response: {}", e))?;
+
+    let mut models: Vec<String> = body["data"]
+        .as_array()
+        .map(|models| {
+            models.iter()
+                .filter_map(|model| model["id"].as_str().map(|id| id.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort();
+
+    Ok(models)
+}
+
+/// Stream a chat completion, emitting `llm-delta` events and returning the full text
+#[tauri::command]
+pub async fn chat_completion(
+    request_id: String,
+    messages: Vec<ChatMessage>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let settings = state.lock().await;
+    let endpoint = settings.llm_endpoint.clone();
+    let max_tokens = settings.response_profile.profile.max_output_tokens();
+    drop(settings);
+    let client = http_client()?;
+
+    log::info!("💬 Chat completion {} via {} ({})", request_id, endpoint.base_url, endpoint.model);
+
+    let mut body = serde_json::json!({
+        "model": endpoint.model,
+        "messages": messages,
+        "stream": true,
+    });
+    if let Some(max_tokens) = max_tokens {
+        body["max_tokens"] = max_tokens.into();
+    }
+
+    let mut response = endpoint.request(client.post(endpoint.url("chat/completions")))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", endpoint.base_url, e))?
+        .error_for_status()
+        .map_err(|e| format!("Chat completion failed: {}", e))?;
+
+    let mut text = String::new();
+    let mut pending = String::new();
+    let mut cancelled = false;
+
+    while let Some(chunk) = response.chunk()
+        .await
+        .map_err(|e| format!("Chat stream interrupted: {}", e))?
+    {
+        if take_cancelled(&request_id) {
+            cancelled = true;
+            break;
+        }
+
+        pending.push_str(&String::from_utf8_lossy(&chunk));
+
+        // SSE events can be split across chunks, so only consume complete lines
+        while let Some(newline) = pending.find('\n') {
+            let line: String = pending.drain(..=newline).collect();
+            if let Some(delta) = parse_stream_line(line.trim()) {
+                text.push_str(&delta);
+                let event = LlmDeltaEvent { request_id: request_id.clone(), delta };
+                if let Err(e) = app.emit("llm-delta", &event) {
+                    log::error!("Failed to emit LLM delta: {}", e);
+                }
+            }
+        }
+    }
+
+    let event = LlmDoneEvent { request_id: request_id.clone(), text: text.clone(), cancelled };
+    if let Err(e) = app.emit("llm-done", &event) {
+        log::error!("Failed to emit LLM done event: {}", e);
+    }
+
+    Ok(text)
+}
+
+#[tauri::command]
+pub async fn cancel_chat_completion(request_id: String) -> Result<(), String> {
+    let mut cancelled = CANCELLED_REQUESTS.lock()
+        .map_err(|_| "Cancellation lock poisoned".to_string())?;
+    cancelled.get_or_insert_with(HashSet::new).insert(request_id);
+    Ok(())
+}
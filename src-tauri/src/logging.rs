@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_opener::OpenerExt;
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
+
+const LOG_FILE_PREFIX: &str = "eva";
+const LOG_FILE_SUFFIX: &str = "log";
+/// Daily files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_LOG_LINES: usize = 200;
+
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Keeps the background file writer running for the whole process
+static LOG_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+/// One parsed line from the log file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Log to the console and to daily rotated files in the app log directory.
+///
+/// `log` macros used throughout the crate are bridged into tracing. `RUST_LOG`
+/// still controls the filter, defaulting to `info`.
+pub fn init(app: &AppHandle) {
+    let file_layer = match open_log_dir(app) {
+        Ok(dir) => match Builder::new()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(&dir)
+        {
+            Ok(appender) => {
+                let (writer, guard) = tracing_appender::non_blocking(appender);
+                let _ = LOG_GUARD.set(guard);
+                let _ = LOG_DIR.set(dir);
+                Some(fmt::layer().with_ansi(false).with_writer(writer))
+            }
+            Err(e) => {
+                eprintln!("Failed to create log file appender: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            eprintln!("Failed to resolve log directory: {}", e);
+            None
+        }
+    };
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .try_init()
+    {
+        eprintln!("Failed to initialize logging: {}", e);
+    }
+
+    if let Some(dir) = LOG_DIR.get() {
+        log::info!("📝 Writing logs to {}", dir.display());
+    }
+}
+
+fn open_log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn log_dir() -> Result<&'static PathBuf, String> {
+    LOG_DIR.get().ok_or_else(|| "File logging is not available".to_string())
+}
+
+/// Parse `<timestamp> <LEVEL> <target>: <message>` as written by the fmt layer
+fn parse_line(line: &str) -> Option<LogEntry> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    let rest = rest.trim_start();
+    let (level, rest) = rest.split_once(' ')?;
+    Level::from_str(level).ok()?;
+    let (target, message) = rest.trim_start().split_once(": ")?;
+
+    Some(LogEntry {
+        timestamp: timestamp.to_string(),
+        level: level.to_string(),
+        target: target.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Log files, newest first; rotated names sort by date
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files.reverse();
+    files
+}
+
+fn read_recent(dir: &Path, lines: usize, min_level: Option<Level>) -> Vec<LogEntry> {
+    let mut recent: Vec<LogEntry> = Vec::new();
+
+    for file in log_files(dir) {
+        let Ok(contents) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+
+        let mut entries: Vec<LogEntry> = Vec::new();
+        for line in contents.lines() {
+            match parse_line(line) {
+                Some(entry) => entries.push(entry),
+                // Multi-line messages continue the previous entry
+                None => {
+                    if let Some(last) = entries.last_mut() {
+                        last.message.push('\n');
+                        last.message.push_str(line);
+                    }
+                }
+            }
+        }
+
+        if let Some(min_level) = min_level {
+            entries.retain(|entry| Level::from_str(&entry.level).is_ok_and(|level| level <= min_level));
+        }
+
+        entries.append(&mut recent);
+        recent = entries;
+        if recent.len() >= lines {
+            break;
+        }
+    }
+
+    let skip = recent.len().saturating_sub(lines);
+    recent.split_off(skip)
+}
+
+/// Most recent log entries, oldest first.
+///
+/// `level_filter` keeps entries at or above a level, e.g. `warn` returns warnings and errors.
+#[tauri::command]
+pub async fn get_recent_logs(
+    lines: Option<usize>,
+    level_filter: Option<String>,
+) -> Result<Vec<LogEntry>, String> {
+    let dir = log_dir()?;
+    let min_level = level_filter
+        .map(|level| Level::from_str(&level).map_err(|_| format!("Unknown log level: {}", level)))
+        .transpose()?;
+    let lines = lines.unwrap_or(DEFAULT_LOG_LINES);
+
+    tokio::task::spawn_blocking(move || read_recent(dir, lines, min_level))
+        .await
+        .map_err(|e| format!("Log reader task failed: {}", e))
+}
+
+/// Reveal the log directory so files can be attached to bug reports
+#[tauri::command]
+pub async fn open_log_directory(app: AppHandle) -> Result<String, String> {
+    let dir = log_dir()?;
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open log directory: {}", e))?;
+    Ok(dir.display().to_string())
+}
@@ -0,0 +1,131 @@
+use crate::settings::SettingsState;
+use crate::users;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const MEMORY_STORE_FILE: &str = "memory.json";
+const MEMORY_KEY: &str = "memory";
+
+/// Cross-session memory settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemorySettings {
+    /// Summarize conversations on session end and recall them in new sessions
+    pub enabled: bool,
+    /// Oldest facts are dropped beyond this count
+    pub max_facts: usize,
+}
+
+impl Default for MemorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_facts: 50,
+        }
+    }
+}
+
+/// What Eva remembers about the user, kept in its own store (one per user) so it can be wiped independently
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversationMemory {
+    pub summary: String,
+    pub facts: Vec<String>,
+    /// Unix timestamp (seconds) of the last update
+    pub updated_at: u64,
+}
+
+impl ConversationMemory {
+    fn load(app: &AppHandle) -> Result<Self, String> {
+        let store = app.store(users::scoped(MEMORY_STORE_FILE))
+            .map_err(|e| format!("Failed to open memory store: {}", e))?;
+
+        Ok(store.get(MEMORY_KEY)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default())
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app.store(users::scoped(MEMORY_STORE_FILE))
+            .map_err(|e| format!("Failed to open memory store: {}", e))?;
+
+        let value = serde_json::to_value(self)
+            .map_err(|e| format!("Failed to serialize memory: {}", e))?;
+
+        store.set(MEMORY_KEY, value);
+        store.save()
+            .map_err(|e| format!("Failed to save memory: {}", e))
+    }
+}
+
+/// Stored memory, empty when memory is disabled
+#[tauri::command]
+pub async fn get_memory(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<ConversationMemory, String> {
+    if !state.lock().await.memory.enabled {
+        return Ok(ConversationMemory::default());
+    }
+    ConversationMemory::load(&app)
+}
+
+/// Replace the summary and merge new facts into the stored memory
+#[tauri::command]
+pub async fn save_memory(
+    summary: String,
+    facts: Vec<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let settings = state.lock().await.memory.clone();
+    if !settings.enabled {
+        return Err("Conversation memory is disabled".to_string());
+    }
+
+    let mut memory = ConversationMemory::load(&app)?;
+    memory.summary = summary.trim().to_string();
+
+    for fact in facts {
+        let fact = fact.trim().to_string();
+        if !fact.is_empty() && !memory.facts.iter().any(|known| known.eq_ignore_ascii_case(&fact)) {
+            memory.facts.push(fact);
+        }
+    }
+    if memory.facts.len() > settings.max_facts {
+        let excess = memory.facts.len() - settings.max_facts;
+        memory.facts.drain(..excess);
+    }
+
+    memory.updated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    memory.save(&app)?;
+
+    log::info!("🧠 Memory updated ({} facts)", memory.facts.len());
+    Ok(format!("Remembered {} facts", memory.facts.len()))
+}
+
+#[tauri::command]
+pub async fn clear_memory(app: AppHandle) -> Result<String, String> {
+    ConversationMemory::default().save(&app)?;
+    log::info!("🧠 Memory cleared");
+    Ok("Memory cleared".to_string())
+}
+
+#[tauri::command]
+pub async fn set_memory_enabled(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.memory.enabled = enabled;
+    settings.save(&app)?;
+
+    log::info!("Conversation memory {}", if enabled { "enabled" } else { "disabled" });
+    Ok(format!("Conversation memory {}", if enabled { "enabled" } else { "disabled" }))
+}
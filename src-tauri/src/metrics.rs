@@ -0,0 +1,295 @@
+use crate::dnd;
+use crate::latency;
+use crate::privacy;
+use crate::settings::SettingsState;
+use crate::wake_word::WakeWordStats;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const LATENCY_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+/// Scrapers send a short GET; anything bigger isn't one
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+static SERVER: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+static LATENCY: Mutex<Vec<LatencyHistogram>> = Mutex::new(Vec::new());
+static REALTIME_RECONNECT_ATTEMPTS: AtomicU64 = AtomicU64::new(0);
+static REALTIME_RECONNECTS: AtomicU64 = AtomicU64::new(0);
+
+/// Prometheus scrape endpoint on localhost, for people who graph their assistant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9464,
+        }
+    }
+}
+
+/// Every observation of one latency span since launch; unlike `latency`'s window, nothing is dropped
+struct LatencyHistogram {
+    span: &'static str,
+    /// Per bucket, not cumulative
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum_ms: u64,
+}
+
+/// Count a latency measurement from a finished turn
+pub fn observe_latency(span: &'static str, ms: u64) {
+    let mut histograms = LATENCY.lock().unwrap_or_else(|e| e.into_inner());
+    let index = match histograms.iter().position(|histogram| histogram.span == span) {
+        Some(index) => index,
+        None => {
+            histograms.push(LatencyHistogram {
+                span,
+                buckets: [0; LATENCY_BUCKETS.len()],
+                count: 0,
+                sum_ms: 0,
+            });
+            histograms.len() - 1
+        }
+    };
+
+    let histogram = &mut histograms[index];
+    let seconds = ms as f64 / 1000.0;
+    if let Some(bucket) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+        histogram.buckets[bucket] += 1;
+    }
+    histogram.count += 1;
+    histogram.sum_ms += ms;
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+fn render() -> String {
+    let mut out = String::new();
+
+    let wake = WakeWordStats::snapshot().unwrap_or_default();
+    write_metric(
+        &mut out,
+        "eva_wake_word_frames_processed_total",
+        "counter",
+        "Audio frames run through wake word detection.",
+        wake.frames_processed,
+    );
+    write_metric(&mut out, "eva_wake_word_detections_total", "counter", "Wake word detections.", wake.detections);
+    write_metric(
+        &mut out,
+        "eva_wake_word_false_triggers_total",
+        "counter",
+        "Detections cancelled because no speech followed.",
+        wake.false_triggers,
+    );
+    write_metric(
+        &mut out,
+        "eva_wake_word_cooldown_skips_total",
+        "counter",
+        "Detections ignored inside the re-trigger cooldown.",
+        wake.cooldown_skips,
+    );
+    write_metric(
+        &mut out,
+        "eva_realtime_reconnect_attempts_total",
+        "counter",
+        "Realtime WebSocket reconnect attempts.",
+        REALTIME_RECONNECT_ATTEMPTS.load(Ordering::Relaxed),
+    );
+    write_metric(
+        &mut out,
+        "eva_realtime_reconnects_total",
+        "counter",
+        "Realtime WebSocket reconnects that succeeded.",
+        REALTIME_RECONNECTS.load(Ordering::Relaxed),
+    );
+    write_metric(&mut out, "eva_mic_muted", "gauge", "1 while the microphone is muted.", privacy::is_mic_muted() as u8);
+    write_metric(&mut out, "eva_dnd_active", "gauge", "1 while do-not-disturb is detected.", dnd::is_active() as u8);
+
+    let _ = writeln!(out, "# HELP eva_turn_latency_seconds Latency of each span of a conversation turn.");
+    let _ = writeln!(out, "# TYPE eva_turn_latency_seconds histogram");
+    for histogram in LATENCY.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+        let mut cumulative = 0;
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "eva_turn_latency_seconds_bucket{{span=\"{}\",le=\"{}\"}} {}",
+                histogram.span, bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "eva_turn_latency_seconds_bucket{{span=\"{}\",le=\"+Inf\"}} {}",
+            histogram.span, histogram.count
+        );
+        let _ = writeln!(
+            out,
+            "eva_turn_latency_seconds_sum{{span=\"{}\"}} {}",
+            histogram.span,
+            histogram.sum_ms as f64 / 1000.0
+        );
+        let _ = writeln!(out, "eva_turn_latency_seconds_count{{span=\"{}\"}} {}", histogram.span, histogram.count);
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP eva_turn_latency_recent_seconds Latency percentiles over the most recent turns."
+    );
+    let _ = writeln!(out, "# TYPE eva_turn_latency_recent_seconds gauge");
+    for stats in latency::span_summaries() {
+        for (quantile, ms) in [("0.5", stats.p50_ms), ("0.95", stats.p95_ms)] {
+            let _ = writeln!(
+                out,
+                "eva_turn_latency_recent_seconds{{span=\"{}\",quantile=\"{}\"}} {}",
+                stats.span,
+                quantile,
+                ms as f64 / 1000.0
+            );
+        }
+    }
+
+    out
+}
+
+async fn read_request_line(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 || request.len() + read > MAX_REQUEST_BYTES {
+            return None;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    request.lines().next().map(str::to_string)
+}
+
+async fn serve(mut stream: TcpStream) {
+    let Ok(Some(request_line)) = tokio::time::timeout(REQUEST_TIMEOUT, read_request_line(&mut stream)).await else {
+        return;
+    };
+
+    // "GET /metrics HTTP/1.1"
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+    let (status, body) = match (method, path.split('?').next().unwrap_or_default()) {
+        ("GET", "/metrics") => ("200 OK", render()),
+        ("GET", _) => ("404 Not Found", "Not found, try /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "Only GET is supported\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        CONTENT_TYPE,
+        body.len(),
+        body
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        log::debug!("Failed to answer metrics request: {}", e);
+    }
+    let _ = stream.shutdown().await;
+}
+
+async fn run(listener: TcpListener) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tauri::async_runtime::spawn(serve(stream));
+            }
+            Err(e) => {
+                log::warn!("Metrics endpoint failed to accept a connection: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+/// Stop the running endpoint and start it again with `settings`, if enabled
+async fn restart(settings: &MetricsSettings) -> Result<(), String> {
+    let previous = SERVER.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(previous) = previous {
+        previous.abort();
+        // Wait for the old listener to be dropped so its port can be bound again; the
+        // cancellation error is expected
+        let _ = previous.await;
+    }
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    // Localhost only; the numbers say when someone is talking to Eva
+    let listener = TcpListener::bind(("127.0.0.1", settings.port))
+        .await
+        .map_err(|e| format!("Failed to listen on port {}: {}", settings.port, e))?;
+    log::info!("📈 Metrics endpoint listening on http://127.0.0.1:{}/metrics", settings.port);
+
+    let handle = tauri::async_runtime::spawn(run(listener));
+    *SERVER.lock().unwrap_or_else(|e| e.into_inner()) = Some(handle);
+    Ok(())
+}
+
+/// Start the endpoint if it was left enabled
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let settings = app.state::<SettingsState>().lock().await.metrics.clone();
+        if let Err(e) = restart(&settings).await {
+            log::error!("📈 {}", e);
+        }
+    });
+}
+
+/// Called by the frontend as the Realtime WebSocket tries to reconnect and once it has
+#[tauri::command]
+pub async fn record_realtime_reconnect(succeeded: bool) -> Result<(), String> {
+    let counter = if succeeded { &REALTIME_RECONNECTS } else { &REALTIME_RECONNECT_ATTEMPTS };
+    counter.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_metrics_endpoint(
+    enabled: bool,
+    port: u16,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if port == 0 {
+        return Err("Choose a port between 1 and 65535".to_string());
+    }
+    let settings = MetricsSettings { enabled, port };
+    restart(&settings).await?;
+
+    let mut current = state.lock().await;
+    current.metrics = settings;
+    current.save(&app)?;
+    drop(current);
+
+    log::info!("📈 Metrics endpoint {}", if enabled { "enabled" } else { "disabled" });
+    Ok(if enabled {
+        format!("Metrics available at http://127.0.0.1:{}/metrics", port)
+    } else {
+        "Metrics endpoint disabled".to_string()
+    })
+}
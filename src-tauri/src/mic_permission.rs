@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+/// OS-level microphone access for Eva
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MicrophonePermission {
+    Granted,
+    /// Denied by the user or restricted by policy; only the system settings can change it
+    Denied,
+    /// Not asked yet, the next capture attempt (or a request) shows the OS prompt
+    Undetermined,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::MicrophonePermission;
+    use block2::RcBlock;
+    use objc2::runtime::Bool;
+    use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAudio};
+    use objc2_foundation::{NSBundle, NSString};
+    use std::sync::Mutex;
+
+    pub const PRIVACY_SETTINGS_URL: Option<&str> =
+        Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone");
+
+    fn status() -> MicrophonePermission {
+        let Some(media_type) = (unsafe { AVMediaTypeAudio }) else {
+            return MicrophonePermission::Undetermined;
+        };
+
+        match unsafe { AVCaptureDevice::authorizationStatusForMediaType(media_type) } {
+            AVAuthorizationStatus::Authorized => MicrophonePermission::Granted,
+            AVAuthorizationStatus::NotDetermined => MicrophonePermission::Undetermined,
+            _ => MicrophonePermission::Denied,
+        }
+    }
+
+    /// macOS kills an app that asks for the microphone without saying why in its Info.plist
+    #[allow(unused_unsafe)]
+    fn usage_description_declared() -> bool {
+        let key = NSString::from_str("NSMicrophoneUsageDescription");
+        unsafe { NSBundle::mainBundle().objectForInfoDictionaryKey(&key) }.is_some()
+    }
+
+    /// Show the system prompt; macOS only prompts once, later calls answer immediately
+    pub async fn request() -> MicrophonePermission {
+        let Some(media_type) = (unsafe { AVMediaTypeAudio }) else {
+            return MicrophonePermission::Undetermined;
+        };
+        if !usage_description_declared() {
+            log::error!("🎙️  Info.plist has no NSMicrophoneUsageDescription; not asking for the microphone");
+            return MicrophonePermission::Undetermined;
+        }
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let tx = Mutex::new(Some(tx));
+        let handler = RcBlock::new(move |granted: Bool| {
+            if let Some(tx) = tx.lock().ok().and_then(|mut tx| tx.take()) {
+                let _ = tx.send(granted.as_bool());
+            }
+        });
+
+        unsafe { AVCaptureDevice::requestAccessForMediaType_completionHandler(media_type, &handler) };
+
+        match rx.await {
+            Ok(true) => MicrophonePermission::Granted,
+            Ok(false) => MicrophonePermission::Denied,
+            Err(_) => status(),
+        }
+    }
+
+    pub async fn check() -> MicrophonePermission {
+        status()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::MicrophonePermission;
+
+    pub const PRIVACY_SETTINGS_URL: Option<&str> = Some("ms-settings:privacy-microphone");
+
+    const CONSENT_KEYS: &[&str] = &[
+        // Machine-wide switch set by policy or "Microphone access for this device"
+        r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone",
+        r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone",
+        // Desktop apps are governed by the "Let desktop apps access your microphone" switch
+        r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged",
+    ];
+
+    async fn consent_value(key: &str) -> Option<String> {
+        let output = tokio::process::Command::new("reg")
+            .args(["query", key, "/v", "Value"])
+            .output()
+            .await
+            .ok()
+            .filter(|output| output.status.success())?;
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim_start().starts_with("Value"))
+            .and_then(|line| line.split_whitespace().last())
+            .map(|value| value.to_string())
+    }
+
+    pub async fn check() -> MicrophonePermission {
+        let mut seen_allow = false;
+        for key in CONSENT_KEYS {
+            match consent_value(key).await.as_deref() {
+                Some("Deny") => return MicrophonePermission::Denied,
+                Some("Allow") => seen_allow = true,
+                _ => {}
+            }
+        }
+
+        if seen_allow {
+            MicrophonePermission::Granted
+        } else {
+            MicrophonePermission::Undetermined
+        }
+    }
+
+    /// Windows has no per-app prompt for desktop apps; access is on unless switched off
+    pub async fn request() -> MicrophonePermission {
+        check().await
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::MicrophonePermission;
+
+    pub const PRIVACY_SETTINGS_URL: Option<&str> = None;
+
+    /// Linux desktops don't gate microphone access for native apps
+    pub async fn check() -> MicrophonePermission {
+        MicrophonePermission::Granted
+    }
+
+    pub async fn request() -> MicrophonePermission {
+        check().await
+    }
+}
+
+/// Current microphone permission without prompting the user
+pub async fn current_permission() -> MicrophonePermission {
+    platform::check().await
+}
+
+/// Ask for access if it hasn't been decided yet, so the prompt comes before capture starts
+/// rather than the stream silently delivering nothing until it's answered
+pub async fn ensure_requested() -> MicrophonePermission {
+    match current_permission().await {
+        MicrophonePermission::Undetermined => {
+            let permission = platform::request().await;
+            log::info!("🎙️  Microphone permission: {:?}", permission);
+            permission
+        }
+        permission => permission,
+    }
+}
+
+#[tauri::command]
+pub async fn check_microphone_permission() -> Result<MicrophonePermission, String> {
+    Ok(current_permission().await)
+}
+
+/// Trigger the OS prompt, or open the privacy settings when access was already denied
+#[tauri::command]
+pub async fn request_microphone_permission(app: AppHandle) -> Result<MicrophonePermission, String> {
+    let permission = match current_permission().await {
+        MicrophonePermission::Undetermined => platform::request().await,
+        permission => permission,
+    };
+
+    log::info!("🎙️  Microphone permission: {:?}", permission);
+
+    if let (MicrophonePermission::Denied, Some(url)) = (permission, platform::PRIVACY_SETTINGS_URL) {
+        app.opener()
+            .open_url(url, None::<&str>)
+            .map_err(|e| format!("Failed to open privacy settings: {}", e))?;
+    }
+
+    Ok(permission)
+}
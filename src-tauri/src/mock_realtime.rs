@@ -0,0 +1,35 @@
+use serde_json::Value;
+
+/// Replaces the Realtime API with the frontend's mock server: `1` for the built-in reply,
+/// or the path of a JSON script
+const MOCK_REALTIME_ENV: &str = "EVA_MOCK_REALTIME";
+
+/// Canned server events for the mock Realtime server: one list per response, in order.
+///
+/// `None` when `EVA_MOCK_REALTIME` isn't set; an empty script means the built-in reply.
+#[tauri::command]
+pub async fn get_mock_realtime_script() -> Result<Option<Vec<Vec<Value>>>, String> {
+    let Some(value) = std::env::var_os(MOCK_REALTIME_ENV) else {
+        return Ok(None);
+    };
+    if value == "1" || value.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    let path = std::path::PathBuf::from(value);
+    let json = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let script: Vec<Vec<Value>> = serde_json::from_str(&json)
+        .map_err(|e| format!("{} should be a list of responses, each a list of server events: {}", path.display(), e))?;
+    if let Some((turn, _)) = script
+        .iter()
+        .enumerate()
+        .find(|(_, events)| events.iter().any(|event| !event["type"].is_string()))
+    {
+        return Err(format!("Response {} of {} has an event without a \"type\"", turn + 1, path.display()));
+    }
+
+    log::warn!("🧪 {} is set - replaying {} scripted responses instead of calling the API", MOCK_REALTIME_ENV, script.len());
+    Ok(Some(script))
+}
@@ -0,0 +1,100 @@
+use crate::settings::SettingsState;
+use crate::tray;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Longest reply preview before it is cut with an ellipsis
+const MAX_BODY_CHARS: usize = 120;
+
+/// A notification was shown and the user hasn't come back to Eva since
+static PENDING_FOCUS: AtomicBool = AtomicBool::new(false);
+
+/// Native notifications for replies that finish while Eva's window is out of sight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+fn window_hidden(app: &AppHandle) -> bool {
+    let Some(window) = app.get_webview_window(tray::MAIN_WINDOW) else {
+        return true;
+    };
+    !window.is_visible().unwrap_or(true) || window.is_minimized().unwrap_or(false)
+}
+
+/// First non-empty line of the reply, shortened for the notification body
+fn preview(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default();
+    if line.chars().count() <= MAX_BODY_CHARS {
+        return line.to_string();
+    }
+    let mut shortened: String = line.chars().take(MAX_BODY_CHARS - 1).collect();
+    shortened.push('…');
+    shortened
+}
+
+/// The app was activated, e.g. by clicking a notification; focus the window it pointed to.
+///
+/// Desktop notifications can't report clicks themselves, so this hangs off
+/// `RunEvent::Reopen`, which macOS sends when a notification activates Eva.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn handle_activation(app: &AppHandle) {
+    if PENDING_FOCUS.swap(false, Ordering::Relaxed) {
+        tray::show_main_window(app);
+    }
+}
+
+/// Show a reply as a native notification if the main window is minimized or hidden.
+///
+/// Returns whether a notification was shown.
+#[tauri::command]
+pub async fn notify_response(
+    text: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let settings = state.lock().await;
+    let enabled = settings.notifications.enabled;
+    let title = settings.identity.assistant_name.clone();
+    drop(settings);
+
+    let body = preview(&text);
+    if !enabled || body.is_empty() || !window_hidden(&app) {
+        return Ok(false);
+    }
+
+    app.notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+    PENDING_FOCUS.store(true, Ordering::Relaxed);
+
+    log::info!("🔔 Reply notification shown");
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn set_notifications_enabled(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.notifications.enabled = enabled;
+    settings.save(&app)?;
+    drop(settings);
+
+    log::info!("🔔 Reply notifications {}", if enabled { "enabled" } else { "disabled" });
+    Ok(format!("Reply notifications {}", if enabled { "enabled" } else { "disabled" }))
+}
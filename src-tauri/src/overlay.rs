@@ -0,0 +1,182 @@
+use crate::settings::SettingsState;
+use crate::tray::MAIN_WINDOW;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, WebviewWindow};
+
+/// Gap between the overlay and the screen edge, in logical pixels
+const EDGE_MARGIN: f64 = 24.0;
+/// Extra room at the bottom so the overlay clears the taskbar or dock
+const BOTTOM_CLEARANCE: f64 = 56.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PositionPreset {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    Center,
+}
+
+/// Eva as a borderless, always-on-top companion floating over other apps
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlaySettings {
+    pub enabled: bool,
+    /// Clicks pass through to the apps underneath; only applies while the overlay is on
+    pub click_through: bool,
+    /// Where the overlay sits; `None` keeps wherever the user dragged it
+    pub position: Option<PositionPreset>,
+}
+
+fn main_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+    app.get_webview_window(MAIN_WINDOW).ok_or_else(|| "Main window not found".to_string())
+}
+
+fn move_to_preset(window: &WebviewWindow, preset: PositionPreset) -> tauri::Result<()> {
+    let Some(monitor) = window.current_monitor()?.or(window.primary_monitor()?) else {
+        return Ok(());
+    };
+    let scale = monitor.scale_factor();
+    let origin = monitor.position();
+    let screen = monitor.size();
+    let window_size = window.outer_size()?;
+
+    let margin = (EDGE_MARGIN * scale) as i32;
+    let bottom_margin = ((EDGE_MARGIN + BOTTOM_CLEARANCE) * scale) as i32;
+    let left = origin.x + margin;
+    let right = origin.x + screen.width as i32 - window_size.width as i32 - margin;
+    let top = origin.y + margin;
+    let bottom = origin.y + screen.height as i32 - window_size.height as i32 - bottom_margin;
+
+    let (x, y) = match preset {
+        PositionPreset::TopLeft => (left, top),
+        PositionPreset::TopRight => (right, top),
+        PositionPreset::BottomLeft => (left, bottom),
+        PositionPreset::BottomRight => (right, bottom),
+        PositionPreset::Center => (
+            origin.x + (screen.width as i32 - window_size.width as i32) / 2,
+            origin.y + (screen.height as i32 - window_size.height as i32) / 2,
+        ),
+    };
+    window.set_position(PhysicalPosition::new(x, y))
+}
+
+/// Make the window match `settings`
+fn apply(app: &AppHandle, settings: &OverlaySettings) -> Result<(), String> {
+    let window = main_window(app)?;
+    let result = window
+        .set_decorations(!settings.enabled)
+        .and_then(|_| window.set_always_on_top(settings.enabled))
+        .and_then(|_| window.set_skip_taskbar(settings.enabled))
+        .and_then(|_| window.set_shadow(!settings.enabled))
+        .and_then(|_| window.set_ignore_cursor_events(settings.enabled && settings.click_through));
+    result.map_err(|e| format!("Failed to update the window: {}", e))?;
+
+    if let (true, Some(preset)) = (settings.enabled, settings.position) {
+        move_to_preset(&window, preset).map_err(|e| format!("Failed to move the window: {}", e))?;
+    }
+
+    // The frontend drops its background so the transparent window shows through
+    if let Err(e) = app.emit("overlay-changed", settings) {
+        log::error!("Failed to emit overlay change: {}", e);
+    }
+    Ok(())
+}
+
+/// Put the window back the way it was left, called once the main window exists
+pub fn restore(app: &AppHandle, settings: &OverlaySettings) {
+    if !settings.enabled {
+        return;
+    }
+    match apply(app, settings) {
+        Ok(()) => log::info!("🪟 Overlay mode restored"),
+        Err(e) => log::warn!("Failed to restore overlay mode: {}", e),
+    }
+}
+
+/// A click-through window can't be clicked back, so the tray icon turns it off
+pub async fn release_click_through(app: &AppHandle) {
+    let state = app.state::<SettingsState>();
+    let mut settings = state.lock().await;
+    if !(settings.overlay.enabled && settings.overlay.click_through) {
+        return;
+    }
+    settings.overlay.click_through = false;
+    if let Err(e) = settings.save(app) {
+        log::error!("Failed to save overlay settings: {}", e);
+    }
+    let overlay = settings.overlay.clone();
+    drop(settings);
+
+    match apply(app, &overlay) {
+        Ok(()) => log::info!("🪟 Click-through turned off from the tray"),
+        Err(e) => log::warn!("Failed to turn off click-through: {}", e),
+    }
+}
+
+async fn update(
+    app: &AppHandle,
+    state: &tauri::State<'_, SettingsState>,
+    change: impl FnOnce(&mut OverlaySettings),
+) -> Result<OverlaySettings, String> {
+    let mut settings = state.lock().await;
+    change(&mut settings.overlay);
+    settings.save(app)?;
+    let overlay = settings.overlay.clone();
+    drop(settings);
+
+    apply(app, &overlay)?;
+    Ok(overlay)
+}
+
+#[tauri::command]
+pub async fn set_overlay_mode(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    update(&app, &state, |overlay| overlay.enabled = enabled).await?;
+    let identity = state.lock().await.identity.clone();
+
+    log::info!("🪟 Overlay mode {}", if enabled { "on" } else { "off" });
+    Ok(if enabled {
+        identity.render("{name} now floats on top of other apps")
+    } else {
+        identity.render("{name} is back in a normal window")
+    })
+}
+
+#[tauri::command]
+pub async fn set_click_through(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let overlay = update(&app, &state, |overlay| overlay.click_through = enabled).await?;
+    let identity = state.lock().await.identity.clone();
+
+    log::info!("🪟 Click-through {}", if enabled { "on" } else { "off" });
+    Ok(match (enabled, overlay.enabled) {
+        (true, true) => identity.render("Clicks now pass through {name}. Click the tray icon to undo."),
+        (true, false) => identity.render("Clicks will pass through {name} in overlay mode"),
+        (false, _) => identity.render("{name} can be clicked again"),
+    })
+}
+
+/// Snap the overlay to a corner or the center; `None` leaves it where it's dragged
+#[tauri::command]
+pub async fn set_window_position_preset(
+    preset: Option<PositionPreset>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    update(&app, &state, |overlay| overlay.position = preset).await?;
+
+    log::info!("🪟 Overlay position set to {:?}", preset);
+    Ok(match preset {
+        Some(preset) => format!("Overlay position set to {:?}", preset),
+        None => "The overlay stays where you drag it".to_string(),
+    })
+}
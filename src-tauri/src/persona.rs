@@ -0,0 +1,204 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+pub const DEFAULT_PERSONA_NAME: &str = "Default";
+pub const DEFAULT_INSTRUCTIONS: &str = "You are {name}, a very cute AI assistant. Respond in a friendly, helpful, and slightly playful manner. Keep your responses concise but warm.";
+const MAX_INSTRUCTIONS_LEN: usize = 8000;
+
+/// Named set of system instructions; `{name}` is replaced with the assistant name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonaProfile {
+    pub name: String,
+    pub instructions: String,
+}
+
+/// Persona profiles and which one is in use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonaSettings {
+    pub active: String,
+    pub profiles: Vec<PersonaProfile>,
+}
+
+impl Default for PersonaSettings {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PERSONA_NAME.to_string(),
+            profiles: vec![PersonaProfile {
+                name: DEFAULT_PERSONA_NAME.to_string(),
+                instructions: DEFAULT_INSTRUCTIONS.to_string(),
+            }],
+        }
+    }
+}
+
+impl PersonaSettings {
+    /// Instructions of the active profile, falling back to the built-in persona
+    pub fn active_instructions(&self) -> &str {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name == self.active)
+            .map(|profile| profile.instructions.as_str())
+            .unwrap_or(DEFAULT_INSTRUCTIONS)
+    }
+
+    fn profile_mut(&mut self, name: &str) -> Option<&mut PersonaProfile> {
+        self.profiles.iter_mut().find(|profile| profile.name == name)
+    }
+
+    /// Make `name` active if it exists and isn't already; returns the event to emit once saved
+    pub fn activate(&mut self, name: &str) -> Option<PersonaChangedEvent> {
+        if self.active == name || !self.profiles.iter().any(|profile| profile.name == name) {
+            return None;
+        }
+        self.active = name.to_string();
+        Some(PersonaChangedEvent {
+            active: self.active.clone(),
+            instructions: self.active_instructions().to_string(),
+        })
+    }
+}
+
+/// Event payload for `persona-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonaChangedEvent {
+    pub active: String,
+    pub instructions: String,
+}
+
+fn validate_instructions(instructions: &str) -> Result<String, String> {
+    let instructions = instructions.trim();
+    if instructions.is_empty() {
+        return Err("Persona instructions cannot be empty".to_string());
+    }
+    if instructions.chars().count() > MAX_INSTRUCTIONS_LEN {
+        return Err(format!("Persona instructions must be at most {} characters", MAX_INSTRUCTIONS_LEN));
+    }
+    Ok(instructions.to_string())
+}
+
+pub fn emit_changed(app: &AppHandle, event: &PersonaChangedEvent) {
+    if let Err(e) = app.emit("persona-changed", event) {
+        log::error!("Failed to emit persona change: {}", e);
+    }
+}
+
+/// Save settings and tell the frontend to push the new instructions to the live session
+async fn apply(
+    state: &tauri::State<'_, SettingsState>,
+    app: &AppHandle,
+    update: impl FnOnce(&mut PersonaSettings) -> Result<(), String>,
+) -> Result<PersonaChangedEvent, String> {
+    let mut settings = state.lock().await;
+    update(&mut settings.persona)?;
+    settings.save(app)?;
+
+    let event = PersonaChangedEvent {
+        active: settings.persona.active.clone(),
+        instructions: settings.persona.active_instructions().to_string(),
+    };
+    drop(settings);
+
+    emit_changed(app, &event);
+    Ok(event)
+}
+
+#[tauri::command]
+pub async fn get_personas(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<PersonaSettings, String> {
+    Ok(state.lock().await.persona.clone())
+}
+
+/// Replace the active persona's instructions
+#[tauri::command]
+pub async fn set_persona(
+    instructions: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let instructions = validate_instructions(&instructions)?;
+
+    let event = apply(&state, &app, |persona| {
+        let active = persona.active.clone();
+        match persona.profile_mut(&active) {
+            Some(profile) => profile.instructions = instructions,
+            None => persona.profiles.push(PersonaProfile { name: active, instructions }),
+        }
+        Ok(())
+    }).await?;
+
+    log::info!("Persona '{}' instructions updated", event.active);
+    Ok(format!("Persona '{}' updated", event.active))
+}
+
+/// Create or overwrite a named persona profile and make it active
+#[tauri::command]
+pub async fn save_persona_profile(
+    name: String,
+    instructions: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Persona name cannot be empty".to_string());
+    }
+    let instructions = validate_instructions(&instructions)?;
+
+    apply(&state, &app, |persona| {
+        match persona.profile_mut(&name) {
+            Some(profile) => profile.instructions = instructions,
+            None => persona.profiles.push(PersonaProfile { name: name.clone(), instructions }),
+        }
+        persona.active = name.clone();
+        Ok(())
+    }).await?;
+
+    log::info!("Persona '{}' saved", name);
+    Ok(format!("Persona '{}' saved", name))
+}
+
+#[tauri::command]
+pub async fn switch_persona(
+    name: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    apply(&state, &app, |persona| {
+        if !persona.profiles.iter().any(|profile| profile.name == name) {
+            return Err(format!("Unknown persona: {}", name));
+        }
+        persona.active = name.clone();
+        Ok(())
+    }).await?;
+
+    log::info!("Switched to persona '{}'", name);
+    Ok(format!("Switched to persona '{}'", name))
+}
+
+#[tauri::command]
+pub async fn delete_persona_profile(
+    name: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    apply(&state, &app, |persona| {
+        if persona.profiles.len() <= 1 {
+            return Err("At least one persona must remain".to_string());
+        }
+        let before = persona.profiles.len();
+        persona.profiles.retain(|profile| profile.name != name);
+        if persona.profiles.len() == before {
+            return Err(format!("Unknown persona: {}", name));
+        }
+        if persona.active == name {
+            persona.active = persona.profiles[0].name.clone();
+        }
+        Ok(())
+    }).await?;
+
+    log::info!("Persona '{}' deleted", name);
+    Ok(format!("Persona '{}' deleted", name))
+}
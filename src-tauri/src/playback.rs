@@ -0,0 +1,104 @@
+use crate::settings::SettingsState;
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// Where and how loud Eva's voice plays.
+///
+/// Playback itself happens in the webview; it follows `playback-settings-changed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaybackSettings {
+    /// Output device name as listed by `list_output_devices`; `None` follows the system default
+    pub output_device: Option<String>,
+    /// 0.0 - 1.0, applied to Eva's voice only; sound cues keep their own volume
+    pub volume: f32,
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        Self {
+            output_device: None,
+            volume: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputDevice {
+    pub name: String,
+    pub is_default: bool,
+}
+
+fn output_device_names() -> Result<Vec<String>, String> {
+    let devices = cpal::default_host()
+        .output_devices()
+        .map_err(|e| format!("Failed to list output devices: {}", e))?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+pub fn emit_changed(app: &AppHandle, settings: &PlaybackSettings) {
+    if let Err(e) = app.emit("playback-settings-changed", settings) {
+        log::error!("Failed to emit playback settings change: {}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn list_output_devices() -> Result<Vec<OutputDevice>, String> {
+    let default_name = cpal::default_host()
+        .default_output_device()
+        .and_then(|device| device.name().ok());
+
+    Ok(output_device_names()?
+        .into_iter()
+        .map(|name| OutputDevice {
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect())
+}
+
+/// Route Eva's voice to a device, or back to the system default with `None`
+#[tauri::command]
+pub async fn set_output_device(
+    name: Option<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if let Some(name) = &name {
+        if !output_device_names()?.contains(name) {
+            return Err(format!("Output device not found: {}", name));
+        }
+    }
+
+    let mut settings = state.lock().await;
+    settings.playback.output_device = name.clone();
+    settings.save(&app)?;
+    let updated = settings.playback.clone();
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    emit_changed(&app, &updated);
+
+    let label = name.as_deref().unwrap_or("system default");
+    log::info!("🔈 Output device set to {}", label);
+    Ok(identity.render(&format!("{{name}}'s voice will play on {}", label)))
+}
+
+#[tauri::command]
+pub async fn set_output_volume(
+    volume: f32,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.playback.volume = volume.clamp(0.0, 1.0);
+    settings.save(&app)?;
+    let updated = settings.playback.clone();
+    drop(settings);
+
+    emit_changed(&app, &updated);
+
+    log::info!("🔈 Output volume set to {:.2}", updated.volume);
+    Ok(format!("Output volume set to {:.0}%", updated.volume * 100.0))
+}
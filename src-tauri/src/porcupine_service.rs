@@ -1,114 +1,119 @@
-use crate::wake_word::{WakeWordEvent, WakeWordError};
+use crate::app_nap::KeepAwake;
+use crate::audio::{self, AudioOverflowEvent, AudioOverflowSource, CaptureBufferSize, ChannelStrategy, Fixture, StreamResampler};
+use crate::chat_mode;
+use crate::debug_audio::{self, DebugAudioRecorder};
+use crate::dnd;
+use crate::earcons::{self, EarconCue};
+use crate::mic_permission::{self, MicrophonePermission};
+use crate::pre_wake::{self, PreWakeRecorder, PreWakeTap};
+use crate::power;
+use crate::privacy;
+use crate::secret_migration::PICOVOICE_KEY_SECRET;
+use crate::secrets;
+use crate::session_idle;
+use crate::speaker_id;
+use crate::users;
+use crate::wake_cooldown::{DetectionGate, Suppressed};
+use crate::wake_word::{WakeWordEvent, WakeWordError, WakeWordModelSettings, WakeWordStats, WakeWordTestResult};
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, SampleFormat, StreamConfig};
 use porcupine::{BuiltinKeywords, Porcupine, PorcupineBuilder};
-use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Emitter};
-use hound::{WavWriter, WavSpec};
-use std::fs;
-use keyring;
+use tokio::sync::{mpsc, oneshot};
 
 const PORCUPINE_SAMPLE_RATE: u32 = 16000;
 const PORCUPINE_FRAME_LENGTH: usize = 512;
+/// Frames queued between the capture callback and Porcupine (~0.5s)
+const FRAME_QUEUE_FRAMES: usize = 16;
+/// Backlog the processing thread keeps before dropping the oldest frames (~256ms)
+const MAX_FRAME_BACKLOG: usize = FRAME_QUEUE_FRAMES / 2;
+/// Overflows are reported as `audio-overflow` events at most this often
+const OVERFLOW_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+const FRAME_DURATION_MS: u64 = (PORCUPINE_FRAME_LENGTH as u64 * 1000) / PORCUPINE_SAMPLE_RATE as u64;
+/// Initial capacity of the per-callback scratch buffers; they grow once if a device sends more
+const CALLBACK_BUFFER_CAPACITY: usize = 8192;
+/// How often the processing thread checks for new frames
+const FRAME_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(5);
+/// While saving power, frames quieter than this skip Porcupine...
+const POWER_SAVE_SILENCE_AMPLITUDE: i16 = 300;
+/// ...once nothing louder was heard for this many frames (~0.5s), so a wake word isn't cut short
+const POWER_SAVE_HANGOVER_FRAMES: usize = 16;
+/// Polling slows down too; the frame queue holds enough that nothing is dropped
+const POWER_SAVE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(25);
 
-// Thread-safe service that doesn't hold non-Send types
-pub struct PorcupineService {
+/// Commands waiting for the wake word actor
+const COMMAND_QUEUE_SIZE: usize = 16;
+
+/// Wake word state, owned by the actor task behind `WakeWordHandle`
+struct PorcupineService {
     is_listening: Arc<AtomicBool>,
     access_key: Option<String>,
-    stop_sender: Option<tokio::sync::oneshot::Sender<()>>,
+    stop_sender: Option<oneshot::Sender<()>>,
+    /// Cleared to end the current session; each session has its own, so a finishing one can't
+    /// touch the next
+    session: Option<Arc<AtomicBool>>,
+    /// The current session's audio loop, awaited before another one starts
+    audio_task: Option<tokio::task::JoinHandle<Result<(), WakeWordError>>>,
+    /// Hands a new Porcupine and its keyword name to the running audio loop
+    swap_sender: Option<std::sync::mpsc::Sender<(Porcupine, String)>>,
+    models: WakeWordModelSettings,
+    channel_strategy: ChannelStrategy,
+    buffer_size: CaptureBufferSize,
 }
 
 impl PorcupineService {
-    pub fn new() -> Self {
+    fn new() -> Self {
         Self {
             is_listening: Arc::new(AtomicBool::new(false)),
             access_key: None,
             stop_sender: None,
+            session: None,
+            audio_task: None,
+            swap_sender: None,
+            models: WakeWordModelSettings::default(),
+            channel_strategy: ChannelStrategy::default(),
+            buffer_size: CaptureBufferSize::default(),
         }
     }
 
-    /// Create debug directory for audio files
-    fn ensure_debug_directory() -> Result<String, WakeWordError> {
-        let debug_dir = "debug_audio";
-        if !Path::new(debug_dir).exists() {
-            fs::create_dir_all(debug_dir)
-                .map_err(|e| WakeWordError::AudioDevice(format!("Failed to create debug directory: {}", e)))?;
-        }
-        Ok(debug_dir.to_string())
+    /// Input channel selection used the next time the capture stream opens
+    fn set_channel_strategy(&mut self, strategy: ChannelStrategy) {
+        self.channel_strategy = strategy;
     }
 
-    /// Create a WAV writer for debugging audio chunks
-    fn create_debug_wav_writer(filename: &str) -> Result<WavWriter<std::io::BufWriter<std::fs::File>>, WakeWordError> {
-        let spec = WavSpec {
-            channels: 1,
-            sample_rate: PORCUPINE_SAMPLE_RATE,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
-        
-        WavWriter::create(filename, spec)
-            .map_err(|e| WakeWordError::AudioDevice(format!("Failed to create WAV writer: {}", e)))
+    /// Frames per capture callback requested the next time the capture stream opens
+    fn set_buffer_size(&mut self, buffer_size: CaptureBufferSize) {
+        self.buffer_size = buffer_size;
+    }
+
+    /// Model files used the next time Porcupine is created
+    fn set_models(&mut self, models: WakeWordModelSettings) {
+        self.models = models;
+    }
+
+    /// Name of the keyword Porcupine listens for, as shown to the user
+    fn keyword_name(&self) -> String {
+        display_keyword(&self.models)
     }
 
     /// Initialize Porcupine with access key - now returns the instance instead of storing it
     async fn create_porcupine(&mut self) -> Result<Porcupine, WakeWordError> {
-        let access_key = self.get_access_key().await?;
-        
-        // Check for custom wake word model first
-        let custom_model_path = "models/Hi-Eva.ppn";
-        
-        let porcupine = if Path::new(custom_model_path).exists() {
-            log::info!("Using custom wake word model: {}", custom_model_path);
-            PorcupineBuilder::new_with_keyword_paths(&access_key, &[custom_model_path])
-                .sensitivities(&[1.0f32]) // MAXIMUM sensitivity for custom model
-                .init()
-                .map_err(|e| WakeWordError::PorcupineInit(e.to_string()))?
-        } else {
-            // Try different keywords - you can change this to test different ones
-            let keyword = if std::env::var("WAKE_WORD_KEYWORD").is_ok() {
-                // Allow environment variable to override
-                match std::env::var("WAKE_WORD_KEYWORD").unwrap().as_str() {
-                    "alexa" => BuiltinKeywords::Alexa,
-                    "computer" => BuiltinKeywords::Computer,
-                    "jarvis" => BuiltinKeywords::Jarvis,
-                    "hey-google" => BuiltinKeywords::HeyGoogle,
-                    "ok-google" => BuiltinKeywords::OkGoogle,
-                    "picovoice" => BuiltinKeywords::Picovoice,
-                    _ => BuiltinKeywords::Porcupine, // Default fallback
-                }
-            } else {
-                BuiltinKeywords::Computer // Try "Computer" instead of "Porcupine" - might be easier to pronounce
-            };
-            
-            let keyword_name = match keyword {
-                BuiltinKeywords::Alexa => "Alexa",
-                BuiltinKeywords::Computer => "Computer", 
-                BuiltinKeywords::Jarvis => "Jarvis",
-                BuiltinKeywords::HeyGoogle => "Hey Google",
-                BuiltinKeywords::OkGoogle => "Ok Google", 
-                BuiltinKeywords::Picovoice => "Picovoice",
-                _ => "Porcupine",
-            };
-            
-            log::info!("Using built-in wake word: {} (instead of Hi Eva)", keyword_name);
-            log::info!("⚠️  SAY '{}' TO TRIGGER WAKE WORD", keyword_name.to_uppercase());
-            log::info!("🔊 Using MAXIMUM sensitivity (1.0) for better detection");
-            
-            PorcupineBuilder::new_with_keywords(&access_key, &[keyword])
-                .sensitivities(&[1.0f32]) // MAXIMUM sensitivity - should be very responsive but may have false positives
-                .init()
-                .map_err(|e| WakeWordError::PorcupineInit(e.to_string()))?
-        };
+        let models = self.models.clone();
+        self.create_porcupine_with(&models, models.sensitivity).await
+    }
 
-        log::info!("Porcupine initialized successfully");
-        log::info!("Expected sample rate: {} Hz", porcupine.sample_rate());
-        log::info!("Expected frame length: {} samples", porcupine.frame_length());
-        
-        Ok(porcupine)
+    /// Initialize Porcupine for `models` instead of the configured ones, e.g. for offline tests
+    async fn create_porcupine_with(
+        &mut self,
+        models: &WakeWordModelSettings,
+        sensitivity: f32,
+    ) -> Result<Porcupine, WakeWordError> {
+        let access_key = self.get_access_key().await?;
+        build_porcupine(access_key, models.clone(), sensitivity).await
     }
 
     /// Get access key from keychain or environment variable
@@ -118,19 +123,15 @@ impl PorcupineService {
         }
 
         // Try to get from secure keychain first
-        if let Ok(key) = self.get_key_from_keychain() {
-            log::info!("✅ Access key loaded from secure keychain");
+        if let Some(key) = secrets::get(PICOVOICE_KEY_SECRET) {
+            log::info!("✅ Access key loaded from secure storage");
             self.access_key = Some(key.clone());
             return Ok(key);
         }
 
-        // Fall back to environment variable
+        // Fall back to environment variable; moving it to the keychain is offered at startup
         if let Ok(key) = std::env::var("PV_ACCESS_KEY") {
             log::info!("✅ Access key loaded from environment variable");
-            // Store in keychain for future use
-            if let Err(e) = self.store_key_in_keychain(&key) {
-                log::warn!("Failed to store key in keychain: {}", e);
-            }
             self.access_key = Some(key.clone());
             return Ok(key);
         }
@@ -140,48 +141,56 @@ impl PorcupineService {
         ))
     }
 
-    /// Store access key in system keychain
-    fn store_key_in_keychain(&self, key: &str) -> Result<(), WakeWordError> {
-        let entry = keyring::Entry::new("eva-desktop", "picovoice-access-key")
-            .map_err(|e| WakeWordError::AccessKey(format!("Failed to create keychain entry: {}", e)))?;
-        
-        entry.set_password(key)
-            .map_err(|e| WakeWordError::AccessKey(format!("Failed to store key in keychain: {}", e)))?;
-        
-        log::info!("🔐 Access key stored securely in system keychain");
-        Ok(())
-    }
-
-    /// Get access key from system keychain
-    fn get_key_from_keychain(&self) -> Result<String, WakeWordError> {
-        let entry = keyring::Entry::new("eva-desktop", "picovoice-access-key")
-            .map_err(|e| WakeWordError::AccessKey(format!("Failed to create keychain entry: {}", e)))?;
-        
-        entry.get_password()
-            .map_err(|e| WakeWordError::AccessKey(format!("Failed to get key from keychain: {}", e)))
-    }
-
     /// Start listening for wake words
-    pub async fn start_listening(&mut self, app_handle: AppHandle) -> Result<(), WakeWordError> {
+    async fn start_listening(&mut self, app_handle: AppHandle) -> Result<(), WakeWordError> {
         if self.is_listening.load(Ordering::Relaxed) {
             return Err(WakeWordError::AlreadyListening);
         }
 
+        if chat_mode::is_text_only() {
+            return Err(WakeWordError::TextOnlyMode);
+        }
+
+        // Fail early instead of opening a stream that only ever delivers silence
+        if audio::fake_microphone().is_none()
+            && mic_permission::ensure_requested().await == MicrophonePermission::Denied
+        {
+            return Err(WakeWordError::AudioDevice("Microphone permission denied".to_string()));
+        }
+
+        // A loop that ended on its own still has to be gone before the next one opens the device
+        if let Some(task) = self.audio_task.take() {
+            let _ = task.await;
+        }
+
         // Create Porcupine instance
         let porcupine = self.create_porcupine().await?;
+        let keyword_name = self.keyword_name();
+        let channel_strategy = self.channel_strategy;
+        let buffer_size = self.buffer_size;
         
         // Set up the audio processing task
-        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+        let (stop_tx, stop_rx) = oneshot::channel();
         self.stop_sender = Some(stop_tx);
+        let (swap_tx, swap_rx) = std::sync::mpsc::channel();
+        self.swap_sender = Some(swap_tx);
         
         let is_listening = self.is_listening.clone();
         is_listening.store(true, Ordering::Relaxed);
+        let session = Arc::new(AtomicBool::new(true));
+        self.session = Some(session.clone());
         
         // Spawn the audio processing task in a blocking thread
-        tokio::task::spawn_blocking(move || {
+        self.audio_task = Some(tokio::task::spawn_blocking(move || {
             // Use a blocking runtime for the audio processing
-            Self::run_audio_processing_blocking(porcupine, app_handle, is_listening.clone(), stop_rx)
-        });
+            let result = Self::run_audio_processing_blocking(porcupine, keyword_name, channel_strategy, buffer_size, app_handle, session.clone(), stop_rx, swap_rx);
+            // Still set means the loop ended by itself (e.g. the device failed) rather than
+            // being stopped, and this is still the current session
+            if session.swap(false, Ordering::Relaxed) {
+                is_listening.store(false, Ordering::Relaxed);
+            }
+            result
+        }));
         
         log::info!("🎤 Wake word detection started - listening for wake words");
         Ok(())
@@ -189,142 +198,51 @@ impl PorcupineService {
 
     /// Main audio processing loop that runs in a blocking thread
     fn run_audio_processing_blocking(
-        porcupine: Porcupine,
+        mut porcupine: Porcupine,
+        mut keyword_name: String,
+        channel_strategy: ChannelStrategy,
+        buffer_size: CaptureBufferSize,
         app_handle: AppHandle,
-        is_listening: Arc<AtomicBool>,
-        stop_rx: tokio::sync::oneshot::Receiver<()>,
+        session: Arc<AtomicBool>,
+        stop_rx: oneshot::Receiver<()>,
+        swap_rx: std::sync::mpsc::Receiver<(Porcupine, String)>,
     ) -> Result<(), WakeWordError> {
-        // Get audio device with enhanced debugging
-        let host = cpal::default_host();
-        log::info!("🎙️  Audio host: {:?}", host.id());
-        
-        // List all input devices for debugging
-        if let Ok(devices) = host.input_devices() {
-            log::info!("🎤 Available input devices:");
-            for (i, device) in devices.enumerate() {
-                if let Ok(name) = device.name() {
-                    log::info!("  {}. {}", i + 1, name);
-                    if let Ok(configs) = device.supported_input_configs() {
-                        for config in configs {
-                            log::info!("     - Sample rate: {}-{} Hz, Channels: {}, Format: {:?}", 
-                                     config.min_sample_rate().0, 
-                                     config.max_sample_rate().0,
-                                     config.channels(),
-                                     config.sample_format());
-                        }
-                    }
-                }
-            }
-        }
-        
-        let device = host.default_input_device()
-            .ok_or_else(|| {
-                log::error!("❌ No input device available!");
-                log::error!("💡 Possible solutions:");
-                log::error!("   1. Check microphone permissions in macOS System Settings > Privacy & Security > Microphone");
-                log::error!("   2. Make sure your microphone is connected and working");
-                log::error!("   3. Try running: sudo killall coreaudiod (to restart audio service)");
-                WakeWordError::AudioDevice("No input device available".to_string())
-            })?;
+        // Released when this returns, however listening ends
+        let _keep_awake = KeepAwake::begin("Listening for the wake word");
 
-        let device_name = device.name()
-            .map_err(|e| WakeWordError::AudioDevice(format!("Failed to get device name: {}", e)))?;
+        // Lock-free queue so the capture callback never blocks or allocates per frame
+        let (producer, mut consumer) = rtrb::RingBuffer::<i16>::new(PORCUPINE_FRAME_LENGTH * FRAME_QUEUE_FRAMES);
+        // Frames the callback couldn't queue because the ring was full
+        let rejected_frames = Arc::new(AtomicUsize::new(0));
+        let mut total_dropped_frames = 0u64;
+        let mut unreported_dropped_frames = 0u64;
+        let mut last_overflow_report: Option<std::time::Instant> = None;
         
-        log::info!("✅ Using audio device: {}", device_name);
-
-        // Get the default input config with better error handling
-        let config = device.default_input_config()
-            .map_err(|e| {
-                log::error!("❌ Failed to get default input config: {}", e);
-                log::error!("💡 This might be a permission issue - check macOS microphone permissions");
-                WakeWordError::AudioDevice(format!("Failed to get default input config: {}", e))
-            })?;
-
-        log::info!("🔧 Device config - Sample rate: {} Hz, Channels: {}, Sample format: {:?}", 
-                  config.sample_rate().0, config.channels(), config.sample_format());
-
-        let input_sample_rate = config.sample_rate().0;
-        let channels = config.channels() as usize;
-
-        // Create resampler if needed
-        let resampler = if input_sample_rate != PORCUPINE_SAMPLE_RATE {
-            log::info!("🔄 Setting up resampler: {} Hz -> {} Hz", input_sample_rate, PORCUPINE_SAMPLE_RATE);
-            
-            let params = SincInterpolationParameters {
-                sinc_len: 256,
-                f_cutoff: 0.95,
-                interpolation: SincInterpolationType::Linear,
-                oversampling_factor: 256,
-                window: WindowFunction::BlackmanHarris2,
-            };
-
-            Some(SincFixedIn::<f32>::new(
-                PORCUPINE_SAMPLE_RATE as f64 / input_sample_rate as f64,
-                2.0, // max_resample_ratio_relative
-                params,
-                PORCUPINE_FRAME_LENGTH,
-                channels,
-            ).map_err(|e| WakeWordError::Resampling(format!("Failed to create resampler: {}", e)))?)
-        } else {
-            log::info!("✅ No resampling needed - device already at 16kHz");
-            None
-        };
+        // Debug recording can be toggled at runtime, so the recorder always exists
+        let mut debug_recorder = DebugAudioRecorder::new(PORCUPINE_SAMPLE_RATE);
 
-        // Create audio processing pipeline using std::sync instead of tokio
-        let (tx, rx) = std::sync::mpsc::channel::<Vec<i16>>();
-        
-        // Set up debug audio logging if enabled
-        let debug_enabled = std::env::var("EVA_DEBUG_AUDIO").is_ok();
-        let mut debug_wav_writer = if debug_enabled {
-            let debug_dir = Self::ensure_debug_directory()?;
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            let debug_filename = format!("{}/processed_audio_{}.wav", debug_dir, timestamp);
-            log::info!("🎵 Debug mode enabled - saving processed audio to: {}", debug_filename);
-            Some(Self::create_debug_wav_writer(&debug_filename)?)
-        } else {
-            None
-        };
-        
-        // Create the audio stream based on sample format with enhanced error handling
-        log::info!("🎵 Creating audio stream...");
-        let stream = match config.sample_format() {
-            SampleFormat::F32 => {
-                log::info!("📊 Using F32 sample format");
-                Self::create_audio_stream::<f32>(device, config.into(), resampler, tx, channels, is_listening.clone())?
-            },
-            SampleFormat::I16 => {
-                log::info!("📊 Using I16 sample format");
-                Self::create_audio_stream::<i16>(device, config.into(), resampler, tx, channels, is_listening.clone())?
-            },
-            SampleFormat::U16 => {
-                log::info!("📊 Using U16 sample format");
-                Self::create_audio_stream::<u16>(device, config.into(), resampler, tx, channels, is_listening.clone())?
-            },
-            _ => {
-                log::error!("❌ Unsupported sample format: {:?}", config.sample_format());
-                return Err(WakeWordError::AudioDevice("Unsupported sample format".to_string()));
+        // Only one of these runs; `EVA_FAKE_MIC` swaps the device for a recording
+        let (stream, fixture_feed, mut pre_wake_recorder) = match audio::fake_microphone() {
+            Some(fixture) => {
+                let (feed, recorder) = FixtureFeed::spawn(fixture, producer, rejected_frames.clone(), session.clone())?;
+                (None, Some(feed), recorder)
+            }
+            None => {
+                let (stream, recorder) = Self::open_input_stream(producer, rejected_frames.clone(), channel_strategy, buffer_size, session.clone())?;
+                (Some(stream), None, recorder)
             }
         };
 
-        // Start the stream with better error handling
-        log::info!("▶️  Starting audio stream...");
-        stream.play().map_err(|e| {
-            log::error!("❌ Failed to start audio stream: {}", e);
-            log::error!("💡 This might be a permission issue - check macOS microphone permissions");
-            WakeWordError::AudioDevice(format!("Failed to start audio stream: {}", e))
-        })?;
-        
-        log::info!("✅ Audio stream started successfully!");
-
         // Process audio frames in a blocking manner
         let mut stop_rx = stop_rx;
+        let mut audio_frame = [0i16; PORCUPINE_FRAME_LENGTH];
         let mut frame_count = 0;
+        let mut frames_since_sound = 0;
+        let mut power_skipped_frames = 0u64;
         let mut last_frame_time = std::time::Instant::now();
-        let mut last_detection_time = std::time::Instant::now() - std::time::Duration::from_secs(10); // Initialize to allow first detection
-        let cooldown_duration = std::time::Duration::from_secs(2);
+        // Per-keyword cooldowns and same-utterance suppression, timed by the audio itself
+        let mut detection_gate = DetectionGate::new();
+        let mut warned_no_audio = false;
         log::info!("🎧 Starting audio processing loop...");
         
         loop {
@@ -334,34 +252,83 @@ impl PorcupineService {
                 break;
             }
 
-            // Check for audio frames with a timeout
-            match rx.recv_timeout(std::time::Duration::from_millis(100)) {
-                Ok(audio_frame) => {
+            // Switch keyword or models between frames, keeping the stream open
+            if let Ok((new_porcupine, new_keyword_name)) = swap_rx.try_recv() {
+                porcupine = new_porcupine;
+                keyword_name = new_keyword_name;
+                detection_gate = DetectionGate::new();
+                log::info!("🔄 Now listening for '{}'", keyword_name);
+            }
+
+            // Keep recent audio at 24kHz so speech right after the wake word isn't lost
+            pre_wake_recorder.drain();
+
+            // Drop-oldest: if Porcupine fell behind, skip ahead to recent audio
+            let backlog = consumer.slots() / PORCUPINE_FRAME_LENGTH;
+            let mut dropped_frames = rejected_frames.swap(0, Ordering::Relaxed);
+            if backlog > MAX_FRAME_BACKLOG {
+                let skip = backlog - MAX_FRAME_BACKLOG;
+                if let Ok(chunk) = consumer.read_chunk(skip * PORCUPINE_FRAME_LENGTH) {
+                    chunk.commit_all();
+                    dropped_frames += skip;
+                }
+            }
+            // Sustained overload drops on every pass; the webview hears about it at most once a second
+            unreported_dropped_frames += dropped_frames as u64;
+            let report_due = last_overflow_report.map_or(true, |at| at.elapsed() >= OVERFLOW_REPORT_INTERVAL);
+            if unreported_dropped_frames > 0 && report_due {
+                total_dropped_frames += unreported_dropped_frames;
+                audio::emit_overflow(&app_handle, &AudioOverflowEvent {
+                    source: AudioOverflowSource::WakeWord,
+                    dropped_ms: unreported_dropped_frames * FRAME_DURATION_MS,
+                    total_dropped_ms: total_dropped_frames * FRAME_DURATION_MS,
+                });
+                unreported_dropped_frames = 0;
+                last_overflow_report = Some(std::time::Instant::now());
+            }
+
+            // Poll for the next complete frame
+            match consumer.read_chunk(PORCUPINE_FRAME_LENGTH) {
+                Ok(chunk) => {
+                    let (first, second) = chunk.as_slices();
+                    audio_frame[..first.len()].copy_from_slice(first);
+                    audio_frame[first.len()..].copy_from_slice(second);
+                    chunk.commit_all();
+
                     frame_count += 1;
+                    WakeWordStats::record_frame();
                     last_frame_time = std::time::Instant::now();
                     
                     // Calculate audio statistics for debugging
                     let max_amplitude = audio_frame.iter().map(|&x| x.abs()).max().unwrap_or(0);
                     let avg_amplitude = audio_frame.iter().map(|&x| x.abs() as f32).sum::<f32>() / audio_frame.len() as f32;
                     
-                    // Save audio frame to debug file if enabled
-                    if let Some(ref mut writer) = debug_wav_writer {
-                        for &sample in &audio_frame {
-                            if let Err(e) = writer.write_sample(sample) {
-                                log::error!("Failed to write debug audio sample: {}", e);
-                                break;
-                            }
-                        }
-                        
+                    // Save audio frame to a debug segment if enabled
+                    debug_recorder.write_frame(&audio_frame);
+                    if debug_audio::is_enabled() && frame_count % 10 == 0 {
                         // Log progress every 10 frames (about every 320ms at 16kHz) with audio stats
-                        if frame_count % 10 == 0 {
-                            log::info!("🎵 Frame {}: {} samples, Max: {}, Avg: {:.1}", 
-                                     frame_count, audio_frame.len(), max_amplitude, avg_amplitude);
-                        }
+                        log::info!("🎵 Frame {}: {} samples, Max: {}, Avg: {:.1}", 
+                                 frame_count, audio_frame.len(), max_amplitude, avg_amplitude);
                     } else if frame_count % 10 == 0 {
                         // Log even without debug mode for audio level monitoring (every 320ms)
                         log::info!("🎵 Frame {}: Max amplitude: {}, Avg: {:.1}", frame_count, max_amplitude, avg_amplitude);
                     }
+
+                    detection_gate.observe_frame(max_amplitude, FRAME_DURATION_MS);
+
+                    // On battery, silence doesn't need a neural network
+                    if max_amplitude >= POWER_SAVE_SILENCE_AMPLITUDE {
+                        frames_since_sound = 0;
+                    } else {
+                        frames_since_sound += 1;
+                    }
+                    if power::is_saving() && frames_since_sound > POWER_SAVE_HANGOVER_FRAMES {
+                        power_skipped_frames += 1;
+                        if power_skipped_frames % 1000 == 0 {
+                            log::debug!("🔋 {} silent frames skipped to save power", power_skipped_frames);
+                        }
+                        continue;
+                    }
                     
                     match porcupine.process(&audio_frame) {
                         Ok(keyword_index) => {
@@ -373,41 +340,44 @@ impl PorcupineService {
                             }
                             
                             if keyword_index >= 0 {
-                                // Check cooldown period to prevent rapid re-triggers
-                                let time_since_last_detection = last_detection_time.elapsed();
-                                if time_since_last_detection < cooldown_duration {
-                                    if frame_count % 50 == 0 { // Log occasionally during cooldown
-                                        log::info!("🔄 Wake word detected but in cooldown period ({:.1}s remaining)", 
-                                                 (cooldown_duration - time_since_last_detection).as_secs_f32());
+                                // Prevent rapid re-triggers and repeats within one utterance
+                                match detection_gate.check(&keyword_name) {
+                                    Ok(()) => {}
+                                    Err(Suppressed::Cooldown(remaining_ms)) => {
+                                        WakeWordStats::record_cooldown_skip();
+                                        if frame_count % 50 == 0 { // Log occasionally during cooldown
+                                            log::info!("🔄 Wake word detected but in cooldown period ({:.1}s remaining)",
+                                                     remaining_ms as f32 / 1000.0);
+                                        }
+                                        continue; // Skip this detection but keep processing
+                                    }
+                                    Err(Suppressed::SameUtterance) => {
+                                        WakeWordStats::record_utterance_skip();
+                                        log::info!("🔄 Wake word detected again in the same utterance - ignored");
+                                        continue;
                                     }
-                                    continue; // Skip this detection but keep processing
                                 }
-                                
-                                last_detection_time = std::time::Instant::now();
+
+                                // Voice lock: ignore wake words from voices other than the enrolled one
+                                if !speaker_id::verify_wake_speaker(&app_handle) {
+                                    log::info!("🔒 Wake word ignored - speaker not recognized");
+                                    continue;
+                                }
+
+                                if dnd::silences_wake() {
+                                    log::info!("🔕 Wake word ignored - do-not-disturb is on");
+                                    continue;
+                                }
+
+                                WakeWordStats::record_detection(avg_amplitude, max_amplitude);
+                                pre_wake::hold();
+                                session_idle::spawn_touch(&app_handle);
+                                users::spawn_identify(&app_handle);
                                 log::info!("🎉 WAKE WORD DETECTED! Keyword index: {} (at frame {})", keyword_index, frame_count);
                                 log::info!("🔊 Audio stats when detected - Max: {}, Avg: {:.1}", max_amplitude, avg_amplitude);
                                 
-                                let wake_word = if Path::new("models/Hi-Eva.ppn").exists() {
-                                    "Hi Eva".to_string() // Custom model
-                                } else {
-                                    // Determine which built-in keyword was used
-                                    if std::env::var("WAKE_WORD_KEYWORD").is_ok() {
-                                        match std::env::var("WAKE_WORD_KEYWORD").unwrap().as_str() {
-                                            "alexa" => "Alexa".to_string(),
-                                            "computer" => "Computer".to_string(),
-                                            "jarvis" => "Jarvis".to_string(),
-                                            "hey-google" => "Hey Google".to_string(),
-                                            "ok-google" => "Ok Google".to_string(),
-                                            "picovoice" => "Picovoice".to_string(),
-                                            _ => "Porcupine".to_string(),
-                                        }
-                                    } else {
-                                        "Computer".to_string() // Default to Computer
-                                    }
-                                };
-                                
                                 let event = WakeWordEvent::new(
-                                    wake_word,
+                                    keyword_name.clone(),
                                     1.0, // Porcupine doesn't provide confidence scores
                                 );
                                 
@@ -415,7 +385,8 @@ impl PorcupineService {
                                     log::error!("Failed to emit wake word event: {}", e);
                                 } else {
                                     log::info!("✅ Wake word event emitted successfully");
-                                    log::info!("⏸️  Next detection available in {:.1}s", cooldown_duration.as_secs_f32());
+                                    earcons::spawn_play(&app_handle, EarconCue::ListeningStart);
+                                    log::info!("⏸️  Next detection available after the utterance ends and the cooldown passes");
                                 }
                             } else if max_amplitude > 500 {
                                 // Log when we have audio but no detection
@@ -427,9 +398,15 @@ impl PorcupineService {
                         }
                     }
                 }
-                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                Err(_) if consumer.is_abandoned() => {
+                    log::warn!("Audio processing channel disconnected");
+                    // Capture stream dropped
+                    break;
+                }
+                Err(_) => {
                     // Check if we haven't received audio for too long
-                    if last_frame_time.elapsed() > std::time::Duration::from_secs(5) && frame_count == 0 {
+                    if !warned_no_audio && last_frame_time.elapsed() > std::time::Duration::from_secs(5) && frame_count == 0 {
+                        warned_no_audio = true;
                         log::warn!("⚠️  No audio frames received for 5 seconds!");
                         log::warn!("💡 Possible issues:");
                         log::warn!("   1. Microphone permission not granted");
@@ -437,162 +414,802 @@ impl PorcupineService {
                         log::warn!("   3. Audio stream creation failed silently");
                         log::warn!("🔧 Try: System Settings > Privacy & Security > Microphone > Enable for this app");
                     }
-                    // Timeout - continue loop to check stop signal
+                    // Nothing queued yet - wait, then check the stop signal again
+                    std::thread::sleep(if power::is_saving() { POWER_SAVE_POLL_INTERVAL } else { FRAME_POLL_INTERVAL });
                     continue;
                 }
-                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
-                    log::warn!("Audio processing channel disconnected");
-                    // Channel closed
-                    break;
-                }
             }
         }
 
-        // Finalize debug WAV file if it was being written
-        if let Some(writer) = debug_wav_writer {
-            if let Err(e) = writer.finalize() {
-                log::error!("Failed to finalize debug WAV file: {}", e);
-            } else {
-                log::info!("🎵 Debug audio file saved successfully ({} frames processed)", frame_count);
-            }
-        }
+        // Finalize the open debug segment, if any
+        debug_recorder.finish();
 
         drop(stream); // Explicitly drop the stream
-        is_listening.store(false, Ordering::Relaxed);
+        drop(fixture_feed);
         Ok(())
     }
 
-    /// Create audio stream for specific sample type with resampling
-    fn create_audio_stream<T>(
-        device: Device,
+    /// Open the default input device and start a stream that queues 16kHz frames into `producer`,
+    /// along with the recorder for its pre-wake audio
+    fn open_input_stream(
+        producer: rtrb::Producer<i16>,
+        rejected_frames: Arc<AtomicUsize>,
+        channel_strategy: ChannelStrategy,
+        buffer_size: CaptureBufferSize,
+        is_listening: Arc<AtomicBool>,
+    ) -> Result<(cpal::Stream, PreWakeRecorder), WakeWordError> {
+        // Get audio device with enhanced debugging
+        let host = audio::capture_host();
+        log::info!("🎙️  Audio host: {:?}", host.id());
+        
+        // List all input devices for debugging
+        if let Ok(devices) = host.input_devices() {
+            log::info!("🎤 Available input devices:");
+            for (i, device) in devices.enumerate() {
+                if let Ok(name) = device.name() {
+                    log::info!("  {}. {}", i + 1, name);
+                    if let Ok(configs) = device.supported_input_configs() {
+                        for config in configs {
+                            log::info!("     - Sample rate: {}-{} Hz, Channels: {}, Format: {:?}", 
+                                     config.min_sample_rate().0, 
+                                     config.max_sample_rate().0,
+                                     config.channels(),
+                                     config.sample_format());
+                        }
+                    }
+                }
+            }
+        }
+        
+        let device = audio::select_input_device(&host)
+            .map_err(|e| {
+                log::error!("❌ No input device available: {}", e);
+                log::error!("💡 Possible solutions:");
+                log::error!("   1. Check microphone permissions in macOS System Settings > Privacy & Security > Microphone");
+                log::error!("   2. Make sure your microphone is connected and working");
+                log::error!("   3. Try running: sudo killall coreaudiod (to restart audio service)");
+                WakeWordError::AudioDevice("No input device available".to_string())
+            })?;
+
+        let device_name = device.name()
+            .map_err(|e| WakeWordError::AudioDevice(format!("Failed to get device name: {}", e)))?;
+        
+        log::info!("✅ Using audio device: {}", device_name);
+
+        // Get the input config with better error handling
+        let config = audio::input_config(&device, PORCUPINE_SAMPLE_RATE)
+            .map_err(|e| {
+                log::error!("❌ Failed to get default input config: {}", e);
+                log::error!("💡 This might be a permission issue - check macOS microphone permissions");
+                WakeWordError::AudioDevice(format!("Failed to get default input config: {}", e))
+            })?;
+
+        log::info!("🔧 Device config - Sample rate: {} Hz, Channels: {}, Sample format: {:?}", 
+                  config.sample_rate().0, config.channels(), config.sample_format());
+
+        let input_sample_rate = config.sample_rate().0;
+        let channels = config.channels() as usize;
+
+        // Create resampler if needed
+        if input_sample_rate != PORCUPINE_SAMPLE_RATE {
+            log::info!("🔄 Setting up resampler: {} Hz -> {} Hz", input_sample_rate, PORCUPINE_SAMPLE_RATE);
+        } else {
+            log::info!("✅ No resampling needed - device already at 16kHz");
+        }
+        let resampler = StreamResampler::new(input_sample_rate, PORCUPINE_SAMPLE_RATE)
+            .map_err(WakeWordError::Resampling)?;
+        let (pre_wake_tap, pre_wake_recorder) = pre_wake::channel(input_sample_rate)
+            .map_err(WakeWordError::Resampling)?;
+
+        // Any sample format works; samples arrive converted to f32
+        log::info!("🎵 Creating audio stream ({:?} samples)...", config.sample_format());
+        let sample_format = config.sample_format();
+        let mut stream_config: StreamConfig = config.clone().into();
+        stream_config.buffer_size = buffer_size.resolve(config.buffer_size());
+        log::info!("📦 Buffer size: {:?} (device supports {:?})", stream_config.buffer_size, config.buffer_size());
+        audio::record_requested(buffer_size, &stream_config.buffer_size, config.buffer_size(), input_sample_rate);
+        let stream = Self::create_audio_stream(&device, stream_config, sample_format, resampler, producer, pre_wake_tap, rejected_frames.clone(), channels, channel_strategy, is_listening.clone())?;
+
+        // Start the stream with better error handling
+        log::info!("▶️  Starting audio stream...");
+        stream.play().map_err(|e| {
+            log::error!("❌ Failed to start audio stream: {}", e);
+            log::error!("💡 This might be a permission issue - check macOS microphone permissions");
+            WakeWordError::AudioDevice(format!("Failed to start audio stream: {}", e))
+        })?;
+        
+        log::info!("✅ Audio stream started successfully!");
+
+        Ok((stream, pre_wake_recorder))
+    }
+
+    /// Create audio stream with resampling
+    fn create_audio_stream(
+        device: &Device,
         config: StreamConfig,
-        mut resampler: Option<SincFixedIn<f32>>,
-        tx: std::sync::mpsc::Sender<Vec<i16>>,
+        sample_format: SampleFormat,
+        mut resampler: StreamResampler,
+        mut producer: rtrb::Producer<i16>,
+        mut pre_wake_tap: PreWakeTap,
+        rejected_frames: Arc<AtomicUsize>,
         channels: usize,
+        channel_strategy: ChannelStrategy,
         is_listening: Arc<AtomicBool>,
-    ) -> Result<cpal::Stream, WakeWordError>
-    where
-        T: cpal::Sample + cpal::SizedSample + Send + 'static,
-        f32: cpal::FromSample<T>,
-    {
-        let mut audio_buffer = Vec::<f32>::new();
+    ) -> Result<cpal::Stream, WakeWordError> {
+        // Reused across callbacks so the real-time thread doesn't allocate
+        let mut mono_samples = Vec::<f32>::with_capacity(CALLBACK_BUFFER_CAPACITY);
+        let mut audio_buffer = Vec::<f32>::with_capacity(CALLBACK_BUFFER_CAPACITY);
         let mut callback_count = 0;
         let mut total_samples_received = 0;
 
-        let stream = device.build_input_stream(
+        let stream = audio::build_input_stream(
+            device,
             &config,
-            move |data: &[T], _: &cpal::InputCallbackInfo| {
+            sample_format,
+            move |samples: &[f32]| {
                 callback_count += 1;
-                total_samples_received += data.len();
+                total_samples_received += samples.len();
+                audio::record_callback_frames(samples.len() / channels.max(1));
                 
                 // Log first few callbacks for debugging
                 if callback_count <= 5 {
-                    log::info!("🎤 Audio callback #{}: {} samples received", callback_count, data.len());
+                    log::info!("🎤 Audio callback #{}: {} samples received", callback_count, samples.len());
                 }
                 
-                if !is_listening.load(Ordering::Relaxed) {
+                if !is_listening.load(Ordering::Relaxed) || privacy::is_mic_muted() {
                     return;
                 }
 
-                // Convert samples to f32
-                let samples: Vec<f32> = data.iter().map(|&s| cpal::Sample::to_sample(s)).collect();
-                
                 // Calculate input level for debugging (reduced logging)
                 let max_input = samples.iter().map(|&x| x.abs()).fold(0.0f32, f32::max);
                 if callback_count <= 3 || callback_count % 500 == 0 {
                     log::info!("📊 Callback #{}: {} samples, max level: {:.6}, total received: {}", 
-                             callback_count, data.len(), max_input, total_samples_received);
+                             callback_count, samples.len(), max_input, total_samples_received);
                 }
                 
-                // Convert to mono if stereo (take left channel)
-                let mono_samples: Vec<f32> = if channels == 2 {
-                    samples.chunks(2).map(|chunk| chunk[0]).collect()
-                } else {
-                    samples
-                };
-
-                // Apply resampling if needed
-                let resampled_samples = if let Some(ref mut rs) = resampler {
-                    // Prepare input for resampler (single channel)
-                    let input = vec![mono_samples];
-                    
-                    match rs.process(&input, None) {
-                        Ok(output) => output[0].clone(),
-                        Err(e) => {
-                            log::error!("Resampling error: {}", e);
-                            return;
-                        }
-                    }
-                } else {
-                    mono_samples
-                };
-
-                // Add to buffer
-                audio_buffer.extend(resampled_samples);
-
-                // Process complete frames
-                while audio_buffer.len() >= PORCUPINE_FRAME_LENGTH {
-                    // Convert to i16 (Porcupine expects 16-bit PCM)
-                    let frame: Vec<i16> = audio_buffer
-                        .drain(..PORCUPINE_FRAME_LENGTH)
-                        .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
-                        .collect();
-
-                    // Calculate frame level for debugging
-                    let frame_max = frame.iter().map(|&x| x.abs()).max().unwrap_or(0);
+                // Reduce to mono using the configured channel(s)
+                mono_samples.clear();
+                channel_strategy.downmix_into(samples, channels, &mut mono_samples);
+
+                // Hand recent audio to the processing thread for the pre-wake buffer
+                pre_wake_tap.push(&mono_samples);
+
+                // Resample to 16kHz and add to buffer
+                if let Err(e) = resampler.process_into(&mono_samples, &mut audio_buffer) {
+                    log::error!("{}", e);
+                    return;
+                }
+
+                // Queue complete frames
+                let complete = audio_buffer.len() - audio_buffer.len() % PORCUPINE_FRAME_LENGTH;
+                for frame in audio_buffer[..complete].chunks_exact(PORCUPINE_FRAME_LENGTH) {
                     if callback_count <= 10 {
-                        log::info!("🔊 Sending frame with {} samples, max amplitude: {}", frame.len(), frame_max);
+                        let frame_max = frame.iter().map(|&x| x.abs()).fold(0.0f32, f32::max);
+                        log::info!("🔊 Sending frame with {} samples, max level: {:.6}", frame.len(), frame_max);
                     }
 
-                    // Send frame for processing
-                    if let Err(_) = tx.send(frame) {
-                        log::error!("Failed to send audio frame for processing");
-                        return;
+                    // Convert to i16 (Porcupine expects 16-bit PCM) straight into the queue
+                    match producer.write_chunk_uninit(PORCUPINE_FRAME_LENGTH) {
+                        Ok(chunk) => {
+                            chunk.fill_from_iter(
+                                frame.iter().map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16),
+                            );
+                        }
+                        Err(_) if producer.is_abandoned() => {
+                            log::error!("Failed to send audio frame for processing");
+                            return;
+                        }
+                        Err(_) => {
+                            // Porcupine stalled; drop the frame rather than block the audio thread.
+                            // The processing thread reports it with its own drops.
+                            rejected_frames.fetch_add(1, Ordering::Relaxed);
+                        }
                     }
                 }
+                audio_buffer.drain(..complete);
             },
             |err| {
                 log::error!("❌ Audio stream error: {}", err);
                 log::error!("💡 This might indicate a permission or hardware issue");
             },
-            None,
         ).map_err(|e| {
-            log::error!("❌ Failed to build input stream: {}", e);
+            log::error!("❌ {}", e);
             log::error!("💡 Check microphone permissions and device availability");
-            WakeWordError::AudioDevice(format!("Failed to build input stream: {}", e))
+            WakeWordError::AudioDevice(e.to_string())
         })?;
 
         Ok(stream)
     }
 
     /// Stop listening for wake words
-    pub async fn stop_listening(&mut self) -> Result<(), WakeWordError> {
+    async fn stop_listening(&mut self) -> Result<(), WakeWordError> {
         if !self.is_listening.load(Ordering::Relaxed) {
             return Err(WakeWordError::NotListening);
         }
 
         self.is_listening.store(false, Ordering::Relaxed);
+        pre_wake::clear();
         
         // Send stop signal to the audio processing task
+        if let Some(session) = self.session.take() {
+            session.store(false, Ordering::Relaxed);
+        }
         if let Some(stop_sender) = self.stop_sender.take() {
             let _ = stop_sender.send(()); // Ignore send errors (task might have already stopped)
         }
+        self.swap_sender = None;
+        // Wait for the loop to release the device, so a restart can't race it
+        if let Some(task) = self.audio_task.take() {
+            let _ = task.await;
+        }
 
         log::info!("🔇 Wake word detection stopped");
         Ok(())
     }
 
     /// Check if currently listening
-    pub fn is_listening(&self) -> bool {
+    fn is_listening(&self) -> bool {
         self.is_listening.load(Ordering::Relaxed)
     }
+
+    /// Stop and start again so new models or channel settings take effect
+    async fn restart_if_listening(&mut self, app_handle: AppHandle) -> Result<(), WakeWordError> {
+        if !self.is_listening() {
+            return Ok(());
+        }
+        self.stop_listening().await?;
+        self.start_listening(app_handle).await
+    }
+
+    /// Hand Porcupine for the current models to the running audio loop, which swaps it in
+    /// between frames; the old one keeps listening while the new one initializes. Restarts
+    /// when the loop is gone.
+    async fn apply_models(&mut self, app_handle: AppHandle) -> Result<(), WakeWordError> {
+        if !self.is_listening() {
+            return Ok(());
+        }
+        let porcupine = self.create_porcupine().await?;
+        let keyword_name = self.keyword_name();
+        match &self.swap_sender {
+            Some(swap_sender) if swap_sender.send((porcupine, keyword_name)).is_ok() => Ok(()),
+            _ => self.restart_if_listening(app_handle).await,
+        }
+    }
+
+    /// Where the access key would be loaded from, without caching it
+    fn access_key_source() -> Option<&'static str> {
+        if secrets::get(PICOVOICE_KEY_SECRET).is_some() {
+            Some("keychain")
+        } else if std::env::var("PV_ACCESS_KEY").is_ok() {
+            Some("environment")
+        } else {
+            None
+        }
+    }
+
+    /// Initialize and immediately release Porcupine to validate the key and model
+    async fn verify_initialization(&mut self) -> Result<(), WakeWordError> {
+        self.create_porcupine().await.map(drop)
+    }
+
+}
+
+/// Run recordings through the resampling + detection pipeline with a separate Porcupine instance
+async fn test_files(
+    access_key: String,
+    models: WakeWordModelSettings,
+    sensitivity: f32,
+    files: Vec<PathBuf>,
+    channel_strategy: ChannelStrategy,
+) -> Result<Vec<WakeWordTestResult>, WakeWordError> {
+    let keyword = display_keyword(&models);
+    let mut porcupine = build_porcupine(access_key, models, sensitivity).await?;
+
+    tokio::task::spawn_blocking(move || {
+        files
+            .iter()
+            .map(|path| test_file(&mut porcupine, &keyword, path, channel_strategy))
+            .collect()
+    })
+    .await
+    .map_err(|_| WakeWordError::ServiceStopped)
+}
+
+/// Plays the `EVA_FAKE_MIC` recording into the frame queue in real time, standing in for a capture stream
+struct FixtureFeed {
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl FixtureFeed {
+    fn spawn(
+        fixture: &'static Fixture,
+        mut producer: rtrb::Producer<i16>,
+        rejected_frames: Arc<AtomicUsize>,
+        is_listening: Arc<AtomicBool>,
+    ) -> Result<(Self, PreWakeRecorder), WakeWordError> {
+        let mut reader = fixture.reader(PORCUPINE_SAMPLE_RATE).map_err(WakeWordError::Resampling)?;
+        let (mut pre_wake_tap, pre_wake_recorder) = pre_wake::channel(PORCUPINE_SAMPLE_RATE).map_err(WakeWordError::Resampling)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+
+        let thread = std::thread::Builder::new()
+            .name("eva-fake-mic".to_string())
+            .spawn(move || {
+                let mut audio_buffer = Vec::<f32>::with_capacity(CALLBACK_BUFFER_CAPACITY);
+                while !thread_stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(std::time::Duration::from_millis(FRAME_DURATION_MS));
+
+                    // Same gating as the device callback: the recording keeps playing while nobody listens
+                    let start = audio_buffer.len();
+                    reader.read_into(&mut audio_buffer);
+                    if !is_listening.load(Ordering::Relaxed) || privacy::is_mic_muted() {
+                        audio_buffer.clear();
+                        continue;
+                    }
+                    pre_wake_tap.push(&audio_buffer[start..]);
+
+                    let complete = audio_buffer.len() - audio_buffer.len() % PORCUPINE_FRAME_LENGTH;
+                    for frame in audio_buffer[..complete].chunks_exact(PORCUPINE_FRAME_LENGTH) {
+                        match producer.write_chunk_uninit(PORCUPINE_FRAME_LENGTH) {
+                            Ok(chunk) => {
+                                chunk.fill_from_iter(
+                                    frame.iter().map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16),
+                                );
+                            }
+                            Err(_) if producer.is_abandoned() => return,
+                            Err(_) => {
+                                rejected_frames.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                    audio_buffer.drain(..complete);
+                }
+            })
+            .map_err(|e| WakeWordError::AudioDevice(format!("Failed to start the fake microphone: {}", e)))?;
+
+        log::info!("🧪 Listening for wake words in {}", fixture.path().display());
+        let feed = Self {
+            stop,
+            thread: Some(thread),
+        };
+        Ok((feed, pre_wake_recorder))
+    }
+}
+
+impl Drop for FixtureFeed {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Load Porcupine's model files, which blocks; called through `build_porcupine`
+fn init_porcupine(
+    access_key: &str,
+    models: &WakeWordModelSettings,
+    sensitivity: f32,
+) -> Result<Porcupine, WakeWordError> {
+    // Check for custom wake word model first
+    let porcupine = if let Some(keyword_path) = models.custom_keyword() {
+        log::info!("Using custom wake word model: {}", keyword_path.display());
+        let mut builder = PorcupineBuilder::new_with_keyword_paths(access_key, &[keyword_path]);
+        builder.sensitivities(&[sensitivity]);
+
+        // Non-English keywords only load with the language model they were trained for
+        if let Some(model_path) = models.language_model() {
+            log::info!("Using Porcupine language model: {}", model_path.display());
+            builder.model_path(model_path);
+        }
+
+        builder.init()
+            .map_err(|e| WakeWordError::PorcupineInit(e.to_string()))?
+    } else {
+        if models.language_model().is_some() {
+            log::warn!("Ignoring Porcupine language model: built-in keywords are English only");
+        }
+
+        // Chosen with set_wake_word, else WAKE_WORD_KEYWORD, else "Computer" - easier to pronounce than "Porcupine"
+        let (keyword_id, keyword_name) = models.builtin_keyword();
+        let keyword = match keyword_id {
+            "alexa" => BuiltinKeywords::Alexa,
+            "computer" => BuiltinKeywords::Computer,
+            "jarvis" => BuiltinKeywords::Jarvis,
+            "hey-google" => BuiltinKeywords::HeyGoogle,
+            "ok-google" => BuiltinKeywords::OkGoogle,
+            "picovoice" => BuiltinKeywords::Picovoice,
+            _ => BuiltinKeywords::Porcupine,
+        };
+        
+        log::info!("Using built-in wake word: {} (instead of Hi Eva)", keyword_name);
+        log::info!("⚠️  SAY '{}' TO TRIGGER WAKE WORD", keyword_name.to_uppercase());
+        log::info!("🔊 Using sensitivity {:.2}", sensitivity);
+        
+        PorcupineBuilder::new_with_keywords(access_key, &[keyword])
+            .sensitivities(&[sensitivity]) // Maximum by default - very responsive but may have false positives
+            .init()
+            .map_err(|e| WakeWordError::PorcupineInit(e.to_string()))?
+    };
+
+    log::info!("Porcupine initialized successfully");
+    log::info!("Expected sample rate: {} Hz", porcupine.sample_rate());
+    log::info!("Expected frame length: {} samples", porcupine.frame_length());
+    
+    Ok(porcupine)
+}
+
+/// Build Porcupine off the async runtime, so the actor's task isn't blocked while models load
+async fn build_porcupine(
+    access_key: String,
+    models: WakeWordModelSettings,
+    sensitivity: f32,
+) -> Result<Porcupine, WakeWordError> {
+    tokio::task::spawn_blocking(move || init_porcupine(&access_key, &models, sensitivity))
+        .await
+        .map_err(|_| WakeWordError::ServiceStopped)?
+}
+
+/// Name of the keyword `models` listen for, as shown to the user
+fn display_keyword(models: &WakeWordModelSettings) -> String {
+    models
+        .keyword_name()
+        .unwrap_or_else(|| models.builtin_keyword().1.to_string())
+}
+
+/// Feed one recording to Porcupine frame by frame, as the live pipeline would
+fn test_file(
+    porcupine: &mut Porcupine,
+    keyword: &str,
+    path: &Path,
+    channel_strategy: ChannelStrategy,
+) -> WakeWordTestResult {
+    let mut result = WakeWordTestResult {
+        file: path.display().to_string(),
+        duration_ms: 0,
+        detections_ms: Vec::new(),
+        cooldown_skips: 0,
+        utterance_skips: 0,
+        error: None,
+    };
+
+    let audio = audio::read_wav(path).and_then(|(samples, channels, sample_rate)| {
+        let mono = channel_strategy.downmix(&samples, channels);
+        audio::resample_clip(mono, sample_rate, PORCUPINE_SAMPLE_RATE)
+    });
+    let audio = match audio {
+        Ok(audio) => audio,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    result.duration_ms = audio.len() as u64 * 1000 / PORCUPINE_SAMPLE_RATE as u64;
+
+    let mut frame = vec![0i16; PORCUPINE_FRAME_LENGTH];
+    let mut gate = DetectionGate::new();
+    for chunk in audio.chunks_exact(PORCUPINE_FRAME_LENGTH) {
+        for (out, sample) in frame.iter_mut().zip(chunk) {
+            *out = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        }
+        let at_ms = gate.now_ms();
+        gate.observe_frame(frame.iter().map(|&x| x.saturating_abs()).max().unwrap_or(0), FRAME_DURATION_MS);
+        match porcupine.process(&frame) {
+            Ok(keyword_index) if keyword_index >= 0 => match gate.check(keyword) {
+                Ok(()) => result.detections_ms.push(at_ms),
+                Err(Suppressed::Cooldown(_)) => result.cooldown_skips += 1,
+                Err(Suppressed::SameUtterance) => result.utterance_skips += 1,
+            },
+            Ok(_) => {}
+            Err(e) => {
+                result.error = Some(format!("Porcupine processing error: {}", e));
+                break;
+            }
+        }
+    }
+
+    log::info!(
+        "🧪 {}: {} detections in {:.1}s",
+        result.file,
+        result.detections_ms.len(),
+        result.duration_ms as f32 / 1000.0
+    );
+    result
 }
 
 impl Drop for PorcupineService {
     fn drop(&mut self) {
         self.is_listening.store(false, Ordering::Relaxed);
+        if let Some(session) = self.session.take() {
+            session.store(false, Ordering::Relaxed);
+        }
         if let Some(stop_sender) = self.stop_sender.take() {
             let _ = stop_sender.send(());
         }
     }
 }
+
+type Reply = oneshot::Sender<Result<(), WakeWordError>>;
+
+/// Requests the wake word actor handles one at a time
+enum WakeWordCommand {
+    Start { app: AppHandle, reply: Reply },
+    Stop { reply: Reply },
+    SetModels { models: WakeWordModelSettings, app: AppHandle, reply: Reply },
+    SetChannelStrategy { strategy: ChannelStrategy, app: AppHandle, reply: Reply },
+    SetBufferSize { buffer_size: CaptureBufferSize, app: AppHandle, reply: Reply },
+    Restart { app: AppHandle, reply: Reply },
+    VerifyInitialization { reply: Reply },
+    TestFiles {
+        models: WakeWordModelSettings,
+        sensitivity: f32,
+        files: Vec<PathBuf>,
+        reply: oneshot::Sender<Result<Vec<WakeWordTestResult>, WakeWordError>>,
+    },
+}
+
+/// Cloneable handle to the task that owns the wake word service.
+///
+/// Start, stop and reconfigure queue up behind each other in the actor; status
+/// reads come from shared state, so they never wait on an operation in flight.
+#[derive(Clone)]
+pub struct WakeWordHandle {
+    commands: mpsc::Sender<WakeWordCommand>,
+    is_listening: Arc<AtomicBool>,
+    keyword_name: Arc<RwLock<String>>,
+}
+
+impl WakeWordHandle {
+    /// Spawn the actor on the Tauri runtime
+    pub fn spawn(models: WakeWordModelSettings, channel_strategy: ChannelStrategy, buffer_size: CaptureBufferSize) -> Self {
+        let mut service = PorcupineService::new();
+        service.set_models(models);
+        service.set_channel_strategy(channel_strategy);
+        service.set_buffer_size(buffer_size);
+
+        let (commands, receiver) = mpsc::channel(COMMAND_QUEUE_SIZE);
+        let handle = Self {
+            commands,
+            is_listening: service.is_listening.clone(),
+            keyword_name: Arc::new(RwLock::new(service.keyword_name())),
+        };
+
+        tauri::async_runtime::spawn(run_actor(service, receiver, handle.keyword_name.clone()));
+        handle
+    }
+
+    async fn request(&self, command: impl FnOnce(Reply) -> WakeWordCommand) -> Result<(), WakeWordError> {
+        let (reply, response) = oneshot::channel();
+        self.commands.send(command(reply)).await
+            .map_err(|_| WakeWordError::ServiceStopped)?;
+        response.await.map_err(|_| WakeWordError::ServiceStopped)?
+    }
+
+    /// Start listening for wake words
+    pub async fn start_listening(&self, app: AppHandle) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::Start { app, reply }).await
+    }
+
+    /// Stop listening for wake words
+    pub async fn stop_listening(&self) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::Stop { reply }).await
+    }
+
+    /// Switch wake word models, swapping Porcupine inside detection if it is running
+    pub async fn set_models(&self, models: WakeWordModelSettings, app: AppHandle) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::SetModels { models, app, reply }).await
+    }
+
+    /// Switch input channel selection, restarting detection if it is running
+    pub async fn set_channel_strategy(&self, strategy: ChannelStrategy, app: AppHandle) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::SetChannelStrategy { strategy, app, reply }).await
+    }
+
+    /// Switch the capture buffer size, restarting detection if it is running
+    pub async fn set_buffer_size(&self, buffer_size: CaptureBufferSize, app: AppHandle) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::SetBufferSize { buffer_size, app, reply }).await
+    }
+
+    /// Reopen the microphone if detection is running, e.g. after the input device changed
+    pub async fn restart(&self, app: AppHandle) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::Restart { app, reply }).await
+    }
+
+    /// Initialize and immediately release Porcupine to validate the key and model
+    pub async fn verify_initialization(&self) -> Result<(), WakeWordError> {
+        self.request(|reply| WakeWordCommand::VerifyInitialization { reply }).await
+    }
+
+    /// Run WAV recordings through detection offline, without touching the live listener
+    pub async fn test_files(
+        &self,
+        models: WakeWordModelSettings,
+        sensitivity: f32,
+        files: Vec<PathBuf>,
+    ) -> Result<Vec<WakeWordTestResult>, WakeWordError> {
+        let (reply, response) = oneshot::channel();
+        self.commands.send(WakeWordCommand::TestFiles { models, sensitivity, files, reply }).await
+            .map_err(|_| WakeWordError::ServiceStopped)?;
+        response.await.map_err(|_| WakeWordError::ServiceStopped)?
+    }
+
+    /// Check if currently listening
+    pub fn is_listening(&self) -> bool {
+        self.is_listening.load(Ordering::Relaxed)
+    }
+
+    /// Name of the keyword Porcupine listens for, as shown to the user
+    pub fn keyword_name(&self) -> String {
+        self.keyword_name.read().map(|name| name.clone()).unwrap_or_default()
+    }
+
+    /// Where the access key would be loaded from, without caching it
+    pub fn access_key_source(&self) -> Option<&'static str> {
+        PorcupineService::access_key_source()
+    }
+}
+
+async fn run_actor(
+    mut service: PorcupineService,
+    mut commands: mpsc::Receiver<WakeWordCommand>,
+    keyword_name: Arc<RwLock<String>>,
+) {
+    // Replies are dropped silently when the caller stopped waiting
+    while let Some(command) = commands.recv().await {
+        match command {
+            WakeWordCommand::Start { app, reply } => {
+                let _ = reply.send(service.start_listening(app).await);
+            }
+            WakeWordCommand::Stop { reply } => {
+                let _ = reply.send(service.stop_listening().await);
+            }
+            WakeWordCommand::SetModels { models, app, reply } => {
+                let previous = service.models.clone();
+                service.set_models(models);
+                let result = service.apply_models(app).await;
+                // Status keeps showing the keyword that's actually loaded when this fails
+                match &result {
+                    Ok(()) => {
+                        if let Ok(mut name) = keyword_name.write() {
+                            *name = service.keyword_name();
+                        }
+                    }
+                    Err(_) => service.set_models(previous),
+                }
+                let _ = reply.send(result);
+            }
+            WakeWordCommand::SetChannelStrategy { strategy, app, reply } => {
+                service.set_channel_strategy(strategy);
+                let _ = reply.send(service.restart_if_listening(app).await);
+            }
+            WakeWordCommand::SetBufferSize { buffer_size, app, reply } => {
+                service.set_buffer_size(buffer_size);
+                let _ = reply.send(service.restart_if_listening(app).await);
+            }
+            WakeWordCommand::Restart { app, reply } => {
+                let _ = reply.send(service.restart_if_listening(app).await);
+            }
+            WakeWordCommand::VerifyInitialization { reply } => {
+                let _ = reply.send(service.verify_initialization().await);
+            }
+            WakeWordCommand::TestFiles { models, sensitivity, files, reply } => {
+                // Runs beside the actor, so start and stop aren't queued behind a long batch
+                match service.get_access_key().await {
+                    Ok(access_key) => {
+                        let channel_strategy = service.channel_strategy;
+                        tauri::async_runtime::spawn(async move {
+                            let _ = reply.send(test_files(access_key, models, sensitivity, files, channel_strategy).await);
+                        });
+                    }
+                    Err(e) => {
+                        let _ = reply.send(Err(e));
+                    }
+                }
+            }
+        }
+    }
+
+    log::info!("Wake word actor stopped");
+}
+
+/// Create and drop a Porcupine instance with `access_key`, which activates it with Picovoice.
+/// Blocks on the network; the error is Porcupine's own, status included.
+pub fn try_access_key(access_key: &str) -> Result<(), String> {
+    PorcupineBuilder::new_with_keywords(access_key, &[BuiltinKeywords::Computer])
+        .init()
+        .map(drop)
+        .map_err(|e| format!("{:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A recording of the default wake word; see tests/fixtures/README.md
+    const WAKE_WORD_FIXTURE: &str = "tests/fixtures/hi-eva.wav";
+
+    /// Half a second of a half-scale 440Hz tone, written as a 16kHz WAV named after the test
+    fn tone_fixture(name: &str) -> &'static Fixture {
+        let path = std::env::temp_dir().join(format!("eva-{}-{}.wav", name, std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: PORCUPINE_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).expect("Failed to create the WAV");
+        for i in 0..PORCUPINE_SAMPLE_RATE / 2 {
+            let t = i as f32 / PORCUPINE_SAMPLE_RATE as f32;
+            let sample = (t * 440.0 * std::f32::consts::TAU).sin() * 0.5;
+            writer.write_sample((sample * i16::MAX as f32) as i16).expect("Failed to write the WAV");
+        }
+        writer.finalize().expect("Failed to finish the WAV");
+
+        let fixture = Fixture::load(&path).expect("Failed to load the fixture");
+        let _ = std::fs::remove_file(&path);
+        Box::leak(Box::new(fixture))
+    }
+
+    #[test]
+    fn fixture_feed_queues_the_recording_as_frames() {
+        let (producer, mut consumer) = rtrb::RingBuffer::<i16>::new(PORCUPINE_FRAME_LENGTH * 64);
+        let rejected_frames = Arc::new(AtomicUsize::new(0));
+        let is_listening = Arc::new(AtomicBool::new(true));
+        let (feed, _pre_wake_recorder) =
+            FixtureFeed::spawn(tone_fixture("feed-frames"), producer, rejected_frames.clone(), is_listening)
+                .expect("Failed to start the feed");
+
+        // Stop inside the tone, so every queued frame carries it
+        std::thread::sleep(std::time::Duration::from_millis(400));
+        drop(feed);
+
+        let mut frames = Vec::new();
+        while let Ok(chunk) = consumer.read_chunk(PORCUPINE_FRAME_LENGTH) {
+            let (first, second) = chunk.as_slices();
+            frames.push([first, second].concat());
+            chunk.commit_all();
+        }
+
+        // 400ms is 12.5 frames of 32ms; the feed thread wakes a little late each time
+        assert!((8..=13).contains(&frames.len()), "{} frames queued", frames.len());
+        assert_eq!(rejected_frames.load(Ordering::Relaxed), 0);
+        for frame in &frames {
+            let peak = frame.iter().map(|sample| sample.saturating_abs()).max().unwrap_or(0);
+            assert!((15_000..=17_000).contains(&peak), "frame peak {}", peak);
+        }
+    }
+
+    #[test]
+    fn fixture_feed_drops_audio_while_not_listening() {
+        let (producer, consumer) = rtrb::RingBuffer::<i16>::new(PORCUPINE_FRAME_LENGTH * 64);
+        let is_listening = Arc::new(AtomicBool::new(false));
+        let (feed, _pre_wake_recorder) =
+            FixtureFeed::spawn(tone_fixture("feed-muted"), producer, Arc::new(AtomicUsize::new(0)), is_listening)
+                .expect("Failed to start the feed");
+
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        drop(feed);
+
+        assert_eq!(consumer.slots(), 0);
+    }
+
+    #[test]
+    #[ignore = "needs PV_ACCESS_KEY and tests/fixtures/hi-eva.wav"]
+    fn fixture_recording_triggers_a_detection() {
+        let access_key = std::env::var("PV_ACCESS_KEY").expect("PV_ACCESS_KEY is not set");
+        let models = WakeWordModelSettings::default();
+        let mut porcupine = init_porcupine(&access_key, &models, models.sensitivity).expect("Porcupine failed to load");
+
+        let result = test_file(
+            &mut porcupine,
+            &display_keyword(&models),
+            Path::new(WAKE_WORD_FIXTURE),
+            ChannelStrategy::Average,
+        );
+
+        assert_eq!(result.error, None);
+        assert!(result.duration_ms > 0);
+        assert!(!result.detections_ms.is_empty(), "no wake word detected in {}", WAKE_WORD_FIXTURE);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Battery state is rechecked this often
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+static PROFILE: Mutex<PowerProfile> = Mutex::new(PowerProfile::Auto);
+static ON_BATTERY: AtomicBool = AtomicBool::new(false);
+/// Whether the wake word loop should cut back, read once per frame
+static SAVING: AtomicBool = AtomicBool::new(false);
+
+/// How hard the wake word loop works
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PowerProfile {
+    /// Save power while running on battery
+    #[default]
+    Auto,
+    /// Always listen at full rate
+    Performance,
+    /// Always save power
+    PowerSaver,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSettings {
+    pub profile: PowerProfile,
+}
+
+/// Event payload for `power-state`, also returned by `get_power_state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerState {
+    pub profile: PowerProfile,
+    pub on_battery: bool,
+    /// The wake word loop is skipping silent frames and polling less often
+    pub saving: bool,
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    /// On battery when a battery is present and no mains adapter is online
+    pub async fn on_battery() -> bool {
+        let Ok(mut entries) = tokio::fs::read_dir("/sys/class/power_supply").await else {
+            return false;
+        };
+        let mut has_battery = false;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let kind = tokio::fs::read_to_string(path.join("type")).await.unwrap_or_default();
+            match kind.trim() {
+                "Mains" | "USB" => {
+                    let online = tokio::fs::read_to_string(path.join("online")).await.unwrap_or_default();
+                    if online.trim() == "1" {
+                        return false;
+                    }
+                }
+                "Battery" => has_battery = true,
+                _ => {}
+            }
+        }
+        has_battery
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    pub async fn on_battery() -> bool {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        // ACLineStatus: 0 offline, 1 online, 255 unknown
+        unsafe { GetSystemPowerStatus(&mut status).is_ok() && status.ACLineStatus == 0 }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    pub async fn on_battery() -> bool {
+        // "Now drawing from 'Battery Power'"
+        let Ok(output) = tokio::process::Command::new("pmset").args(["-g", "batt"]).output().await else {
+            return false;
+        };
+        output.status.success() && String::from_utf8_lossy(&output.stdout).contains("'Battery Power'")
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub async fn on_battery() -> bool {
+        false
+    }
+}
+
+fn profile() -> PowerProfile {
+    *PROFILE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn state() -> PowerState {
+    PowerState {
+        profile: profile(),
+        on_battery: ON_BATTERY.load(Ordering::Relaxed),
+        saving: is_saving(),
+    }
+}
+
+/// The wake word loop should save power
+pub fn is_saving() -> bool {
+    SAVING.load(Ordering::Relaxed)
+}
+
+/// Recompute `SAVING`; true when it changed
+fn update_saving() -> bool {
+    let saving = match profile() {
+        PowerProfile::Auto => ON_BATTERY.load(Ordering::Relaxed),
+        PowerProfile::Performance => false,
+        PowerProfile::PowerSaver => true,
+    };
+    SAVING.swap(saving, Ordering::Relaxed) != saving
+}
+
+fn emit_state(app: &AppHandle) {
+    let state = state();
+    log::info!(
+        "🔋 {} ({:?}, {})",
+        if state.saving { "Saving power" } else { "Full-rate wake word" },
+        state.profile,
+        if state.on_battery { "on battery" } else { "on AC" }
+    );
+    if let Err(e) = app.emit("power-state", &state) {
+        log::error!("Failed to emit power state: {}", e);
+    }
+}
+
+/// Apply the saved profile at startup
+pub fn restore(settings: &PowerSettings) {
+    *PROFILE.lock().unwrap_or_else(|e| e.into_inner()) = settings.profile;
+    update_saving();
+}
+
+/// Background task that follows whether the machine runs on battery
+pub async fn run_power_monitor(app: AppHandle) {
+    log::info!("🔋 Power monitor started");
+    loop {
+        let on_battery = platform::on_battery().await;
+        ON_BATTERY.store(on_battery, Ordering::Relaxed);
+        if update_saving() {
+            emit_state(&app);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[tauri::command]
+pub async fn get_power_state() -> Result<PowerState, String> {
+    Ok(state())
+}
+
+#[tauri::command]
+pub async fn set_power_profile(
+    profile: PowerProfile,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.power.profile = profile;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    *PROFILE.lock().unwrap_or_else(|e| e.into_inner()) = profile;
+    if update_saving() {
+        emit_state(&app);
+    }
+
+    log::info!("🔋 Power profile set to {:?}", profile);
+    Ok(identity.render(match profile {
+        PowerProfile::Auto => "{name} saves power while on battery",
+        PowerProfile::Performance => "{name} always listens at full rate",
+        PowerProfile::PowerSaver => "{name} always saves power",
+    }))
+}
@@ -0,0 +1,156 @@
+use crate::audio::StreamResampler;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Sample rate the Realtime API expects for input audio
+pub const PRE_WAKE_SAMPLE_RATE: u32 = 24000;
+/// How much audio to keep while idle; a wake word extends it with `hold` until it is taken
+const PRE_WAKE_SECONDS: f32 = 1.5;
+const PRE_WAKE_CAPACITY: usize = (PRE_WAKE_SAMPLE_RATE as f32 * PRE_WAKE_SECONDS) as usize;
+/// Longest audio kept after a wake word while the frontend reconnects a dropped session
+const HELD_SECONDS: f32 = 10.0;
+const HELD_CAPACITY: usize = (PRE_WAKE_SAMPLE_RATE as f32 * HELD_SECONDS) as usize;
+/// A hold nobody took expires, so stale audio isn't sent with the next wake word
+const HOLD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Most recent 24kHz PCM16 audio from the wake word capture stream
+static PRE_WAKE_BUFFER: Mutex<VecDeque<i16>> = Mutex::new(VecDeque::new());
+/// Set after a wake word; the buffer grows instead of dropping audio until it's taken
+static HOLD_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Buffered audio returned by `take_pre_wake_audio`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreWakeAudio {
+    /// Base64 little-endian PCM16, same encoding as Realtime input audio
+    pub audio: String,
+    pub sample_rate: u32,
+    pub duration_ms: u64,
+}
+
+/// Capture-side end of the pre-wake queue: copies callback audio into a preallocated ring,
+/// so the real-time thread never locks or allocates for it
+pub struct PreWakeTap {
+    producer: rtrb::Producer<f32>,
+}
+
+impl PreWakeTap {
+    /// Queue mono samples for `PreWakeRecorder::drain`; if it fell behind, the newest audio is dropped
+    pub fn push(&mut self, mono_samples: &[f32]) {
+        let count = mono_samples.len().min(self.producer.slots());
+        if let Ok(chunk) = self.producer.write_chunk_uninit(count) {
+            chunk.fill_from_iter(mono_samples.iter().copied());
+        }
+    }
+}
+
+/// Processing-side end: resamples queued audio to 24kHz and feeds the shared buffer
+pub struct PreWakeRecorder {
+    consumer: rtrb::Consumer<f32>,
+    resampler: StreamResampler,
+    resampled: Vec<f32>,
+}
+
+/// Queue between a capture callback at `input_sample_rate` and the thread that buffers its audio
+pub fn channel(input_sample_rate: u32) -> Result<(PreWakeTap, PreWakeRecorder), String> {
+    // A second of capture, far more than the processing thread takes between drains
+    let (producer, consumer) = rtrb::RingBuffer::new(input_sample_rate as usize);
+    let recorder = PreWakeRecorder {
+        consumer,
+        resampler: StreamResampler::new(input_sample_rate, PRE_WAKE_SAMPLE_RATE)?,
+        resampled: Vec::new(),
+    };
+    Ok((PreWakeTap { producer }, recorder))
+}
+
+impl PreWakeRecorder {
+    /// Resample everything the tap queued to 24kHz and append it, dropping the oldest audio
+    pub fn drain(&mut self) {
+        let Ok(chunk) = self.consumer.read_chunk(self.consumer.slots()) else {
+            return;
+        };
+        if chunk.is_empty() {
+            return;
+        }
+
+        self.resampled.clear();
+        let (first, second) = chunk.as_slices();
+        let result = self.resampler.process_into(first, &mut self.resampled)
+            .and_then(|()| self.resampler.process_into(second, &mut self.resampled));
+        chunk.commit_all();
+        if let Err(e) = result {
+            log::error!("Pre-wake buffer: {}", e);
+            return;
+        }
+
+        if let Ok(mut buffer) = PRE_WAKE_BUFFER.lock() {
+            buffer.extend(
+                self.resampled
+                    .iter()
+                    .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16),
+            );
+            let overflow = buffer.len().saturating_sub(capacity());
+            buffer.drain(..overflow);
+        }
+    }
+}
+
+fn capacity() -> usize {
+    match HOLD_UNTIL.lock().ok().and_then(|hold| *hold) {
+        Some(until) if Instant::now() < until => HELD_CAPACITY,
+        _ => PRE_WAKE_CAPACITY,
+    }
+}
+
+/// Keep everything from now until `take_pre_wake_audio`, covering a reconnect after the wake word
+pub fn hold() {
+    if let Ok(mut hold) = HOLD_UNTIL.lock() {
+        *hold = Some(Instant::now() + HOLD_TIMEOUT);
+    }
+}
+
+fn release() {
+    if let Ok(mut hold) = HOLD_UNTIL.lock() {
+        *hold = None;
+    }
+}
+
+/// Forget buffered audio, e.g. when the capture stream stops
+pub fn clear() {
+    release();
+    if let Ok(mut buffer) = PRE_WAKE_BUFFER.lock() {
+        buffer.clear();
+    }
+}
+
+/// Copy of the buffered audio, leaving it in place for `take_pre_wake_audio`
+pub fn snapshot() -> Vec<i16> {
+    PRE_WAKE_BUFFER.lock()
+        .map(|buffer| buffer.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Hand the buffered audio to the frontend, which prepends it to the Realtime input.
+///
+/// The buffer is drained so the same audio is never sent twice.
+#[tauri::command]
+pub async fn take_pre_wake_audio() -> Result<Option<PreWakeAudio>, String> {
+    release();
+    let samples: Vec<i16> = PRE_WAKE_BUFFER.lock()
+        .map_err(|_| "Pre-wake buffer lock poisoned".to_string())?
+        .drain(..)
+        .collect();
+
+    if samples.is_empty() {
+        return Ok(None);
+    }
+
+    let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    Ok(Some(PreWakeAudio {
+        audio: base64::engine::general_purpose::STANDARD.encode(bytes),
+        sample_rate: PRE_WAKE_SAMPLE_RATE,
+        duration_ms: samples.len() as u64 * 1000 / PRE_WAKE_SAMPLE_RATE as u64,
+    }))
+}
@@ -0,0 +1,163 @@
+use crate::conversations;
+use crate::debug_audio;
+use crate::memory;
+use crate::porcupine_service::WakeWordHandle;
+use crate::quiet_hours;
+use crate::response_cache;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Checked by the capture callback; muted frames never reach wake word detection
+static MIC_MUTED: AtomicBool = AtomicBool::new(false);
+/// Wake word listening was stopped by a hardware mute and should resume on unmute
+static STOPPED_FOR_MUTE: AtomicBool = AtomicBool::new(false);
+/// Mirrors `PrivacySettings::never_store_audio` for the writers that can't await the settings lock
+static NEVER_STORE_AUDIO: AtomicBool = AtomicBool::new(false);
+
+/// Microphone and stored data privacy settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacySettings {
+    /// Restored on startup so a muted assistant stays muted across restarts
+    pub mic_muted: bool,
+    /// Close the input stream while muted so the OS recording indicator turns off
+    pub release_device_on_mute: bool,
+    /// Never write audio to disk, whatever the archive and debug recording settings say
+    pub never_store_audio: bool,
+    /// Saved conversations older than this are deleted; 0 keeps them
+    pub transcript_retention_days: u64,
+}
+
+/// What `purge_all_data` deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeReport {
+    pub conversations: usize,
+    pub debug_recordings: usize,
+}
+
+/// Event payload for `mic-muted`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MicMutedEvent {
+    pub muted: bool,
+    /// The input device was closed, not just ignored
+    pub device_released: bool,
+}
+
+pub fn is_mic_muted() -> bool {
+    MIC_MUTED.load(Ordering::Relaxed)
+}
+
+/// Whether any audio may be written to disk
+pub fn audio_storage_allowed() -> bool {
+    !NEVER_STORE_AUDIO.load(Ordering::Relaxed)
+}
+
+/// Apply the persisted mute state at startup
+pub fn restore(settings: &PrivacySettings) {
+    MIC_MUTED.store(settings.mic_muted, Ordering::Relaxed);
+    NEVER_STORE_AUDIO.store(settings.never_store_audio, Ordering::Relaxed);
+    if settings.mic_muted {
+        log::info!("🔇 Microphone muted (restored from settings)");
+    }
+}
+
+#[tauri::command]
+pub async fn get_mic_muted() -> Result<bool, String> {
+    Ok(is_mic_muted())
+}
+
+#[tauri::command]
+pub async fn set_mic_muted(
+    muted: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<MicMutedEvent, String> {
+    let mut settings = state.lock().await;
+    settings.privacy.mic_muted = muted;
+    settings.save(&app)?;
+    let release_device = settings.privacy.release_device_on_mute;
+    drop(settings);
+
+    MIC_MUTED.store(muted, Ordering::Relaxed);
+
+    let porcupine = app.state::<WakeWordHandle>().inner().clone();
+    let mut device_released = false;
+
+    if muted && release_device && porcupine.is_listening() {
+        match porcupine.stop_listening().await {
+            Ok(_) => {
+                STOPPED_FOR_MUTE.store(true, Ordering::Relaxed);
+                device_released = true;
+            }
+            Err(e) => log::warn!("Failed to release microphone on mute: {}", e),
+        }
+    } else if !muted
+        && STOPPED_FOR_MUTE.swap(false, Ordering::Relaxed)
+        && !porcupine.is_listening()
+        && !quiet_hours::is_active()
+    {
+        if let Err(e) = porcupine.start_listening(app.clone()).await {
+            log::error!("Failed to resume wake word detection after unmute: {}", e);
+        }
+    }
+
+    log::info!("{} Microphone {}", if muted { "🔇" } else { "🎤" }, if muted { "muted" } else { "unmuted" });
+
+    let event = MicMutedEvent { muted, device_released };
+    if let Err(e) = app.emit("mic-muted", &event) {
+        log::error!("Failed to emit mic muted event: {}", e);
+    }
+
+    Ok(event)
+}
+
+/// Turning `never_store_audio` on stops new audio from being written; what's already saved stays
+/// until it's purged
+#[tauri::command]
+pub async fn set_data_privacy(
+    never_store_audio: bool,
+    transcript_retention_days: u64,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.privacy.never_store_audio = never_store_audio;
+    settings.privacy.transcript_retention_days = transcript_retention_days;
+    settings.save(&app)?;
+    drop(settings);
+
+    NEVER_STORE_AUDIO.store(never_store_audio, Ordering::Relaxed);
+    conversations::prune_expired(&app, transcript_retention_days);
+
+    log::info!(
+        "🔒 Data privacy: audio to disk {}, transcripts kept {}",
+        if never_store_audio { "never" } else { "as configured" },
+        if transcript_retention_days == 0 {
+            "indefinitely".to_string()
+        } else {
+            format!("{} days", transcript_retention_days)
+        }
+    );
+    Ok("Privacy settings saved".to_string())
+}
+
+/// Delete saved conversations and their audio, debug recordings, memory and cached answers
+#[tauri::command]
+pub async fn purge_all_data(app: AppHandle) -> Result<PurgeReport, String> {
+    let conversations = conversations::purge(&app).await?;
+    let debug_recordings = debug_audio::purge();
+    memory::clear_memory(app.clone()).await?;
+    response_cache::clear_response_cache(app.clone()).await?;
+
+    log::info!(
+        "🔒 Purged all data: {} conversations, {} debug recordings, memory and cached answers",
+        conversations,
+        debug_recordings
+    );
+    if let Err(e) = app.emit("data-purged", ()) {
+        log::error!("Failed to emit data purged event: {}", e);
+    }
+    Ok(PurgeReport { conversations, debug_recordings })
+}
@@ -0,0 +1,266 @@
+use crate::call_detection;
+use crate::dnd;
+use crate::privacy;
+use crate::quiet_hours;
+use crate::settings::SettingsState;
+use chrono::{Local, NaiveDate, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Unix ms of the last conversation activity, see `note_activity`
+static LAST_ACTIVITY: AtomicU64 = AtomicU64::new(0);
+/// Whether the idle check-in already ran for the current quiet stretch
+static IDLE_FIRED: AtomicBool = AtomicBool::new(false);
+static START_FIRED: AtomicBool = AtomicBool::new(false);
+static RATE_LIMIT: Mutex<RateLimit> = Mutex::new(RateLimit {
+    last_spoken: 0,
+    day: None,
+    spoken_today: 0,
+    greetings_fired: Vec::new(),
+});
+
+struct RateLimit {
+    last_spoken: u64,
+    day: Option<NaiveDate>,
+    spoken_today: u32,
+    /// "HH:MM" of the timed greetings already spoken today
+    greetings_fired: Vec<String>,
+}
+
+/// A greeting or reminder Eva speaks at a time of day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedGreeting {
+    /// Local "HH:MM"
+    pub at: String,
+    /// What Eva should get across, e.g. "Good morning, remind me to stretch"
+    pub message: String,
+}
+
+/// Eva speaking first, without being asked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProactiveSettings {
+    pub enabled: bool,
+    pub greet_on_start: bool,
+    pub greetings: Vec<TimedGreeting>,
+    /// Check in after this long without a conversation; 0 turns it off
+    pub idle_minutes: u64,
+    /// Least time between two proactive remarks
+    pub min_interval_minutes: u64,
+    pub max_per_day: u32,
+}
+
+impl Default for ProactiveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            greet_on_start: true,
+            greetings: Vec::new(),
+            idle_minutes: 0,
+            min_interval_minutes: 30,
+            max_per_day: 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProactiveTrigger {
+    Start,
+    TimeOfDay,
+    Idle,
+}
+
+/// Event payload for `proactive-prompt`; the frontend has the active backend respond to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProactivePromptEvent {
+    pub trigger: ProactiveTrigger,
+    /// Added to the instructions for this one response
+    pub instructions: String,
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Someone talked to Eva; restarts the idle check-in
+pub fn note_activity() {
+    LAST_ACTIVITY.store(now_millis(), Ordering::Relaxed);
+    IDLE_FIRED.store(false, Ordering::Relaxed);
+}
+
+/// Why Eva should keep quiet right now, if she should
+fn do_not_disturb() -> Option<&'static str> {
+    if quiet_hours::is_active() {
+        Some("quiet hours")
+    } else if call_detection::is_paused_for_call() {
+        Some("a call is in progress")
+    } else if privacy::is_mic_muted() {
+        Some("the microphone is muted")
+    } else if dnd::silences_proactive() {
+        Some("do-not-disturb is on")
+    } else {
+        None
+    }
+}
+
+fn part_of_day(time: NaiveTime) -> &'static str {
+    match time.hour() {
+        5..=11 => "morning",
+        12..=17 => "afternoon",
+        _ => "evening",
+    }
+}
+
+/// The rate limit, with the daily counters reset when the date has changed
+fn rate_limit() -> std::sync::MutexGuard<'static, RateLimit> {
+    let mut limit = RATE_LIMIT.lock().unwrap_or_else(|e| e.into_inner());
+    let today = Local::now().date_naive();
+    if limit.day != Some(today) {
+        limit.day = Some(today);
+        limit.spoken_today = 0;
+        limit.greetings_fired.clear();
+    }
+    limit
+}
+
+/// Claims a slot under the rate limit; `false` means stay quiet
+fn take_slot(settings: &ProactiveSettings, trigger: ProactiveTrigger) -> bool {
+    let mut limit = rate_limit();
+
+    let now = now_millis();
+    if now.saturating_sub(limit.last_spoken) < settings.min_interval_minutes * 60 * 1000 {
+        log::info!("🗨️ Skipping {:?} remark, spoke less than {} minutes ago", trigger, settings.min_interval_minutes);
+        return false;
+    }
+    if limit.spoken_today >= settings.max_per_day {
+        log::info!("🗨️ Skipping {:?} remark, daily limit of {} reached", trigger, settings.max_per_day);
+        return false;
+    }
+    limit.last_spoken = now;
+    limit.spoken_today += 1;
+    true
+}
+
+fn speak(app: &AppHandle, settings: &ProactiveSettings, trigger: ProactiveTrigger, instructions: String) {
+    if let Some(reason) = do_not_disturb() {
+        log::info!("🗨️ Not speaking up ({:?}): {}", trigger, reason);
+        return;
+    }
+    if !take_slot(settings, trigger) {
+        return;
+    }
+
+    log::info!("🗨️ Speaking up ({:?})", trigger);
+    let event = ProactivePromptEvent { trigger, instructions };
+    if let Err(e) = app.emit("proactive-prompt", &event) {
+        log::error!("Failed to emit proactive prompt: {}", e);
+    }
+}
+
+/// The timed greeting due this minute that hasn't been spoken today
+fn due_greeting(settings: &ProactiveSettings, now: NaiveTime) -> Option<TimedGreeting> {
+    let current = now.format("%H:%M").to_string();
+    let mut limit = rate_limit();
+    let greeting = settings
+        .greetings
+        .iter()
+        .find(|greeting| greeting.at == current && !limit.greetings_fired.contains(&greeting.at))?;
+    limit.greetings_fired.push(greeting.at.clone());
+    Some(greeting.clone())
+}
+
+/// Background task that watches the time of day and idle triggers
+pub async fn run_proactive_scheduler(app: AppHandle) {
+    let settings_state = app.state::<SettingsState>().inner().clone();
+    note_activity();
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+
+        let settings = settings_state.lock().await.proactive.clone();
+        if !settings.enabled {
+            continue;
+        }
+
+        let now = Local::now().time();
+        if let Some(greeting) = due_greeting(&settings, now) {
+            let instructions = format!(
+                "It's {}. Without being asked, say this to the user in one or two short sentences, \
+                 in your own words: {}",
+                now.format("%H:%M"),
+                greeting.message
+            );
+            speak(&app, &settings, ProactiveTrigger::TimeOfDay, instructions);
+        }
+
+        let idle_ms = now_millis().saturating_sub(LAST_ACTIVITY.load(Ordering::Relaxed));
+        if settings.idle_minutes > 0
+            && idle_ms >= settings.idle_minutes * 60 * 1000
+            && !IDLE_FIRED.swap(true, Ordering::Relaxed)
+        {
+            let instructions = format!(
+                "The user hasn't talked to you for {} minutes. Check in with one short, friendly \
+                 sentence, such as a light remark or a useful reminder. Don't ask more than one question.",
+                idle_ms / 60_000
+            );
+            speak(&app, &settings, ProactiveTrigger::Idle, instructions);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn set_proactive_settings(
+    mut settings: ProactiveSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    for greeting in &mut settings.greetings {
+        // Stored zero-padded, the way the scheduler compares them
+        greeting.at = NaiveTime::parse_from_str(greeting.at.trim(), "%H:%M")
+            .map_err(|_| format!("Invalid time \"{}\", expected HH:MM", greeting.at))?
+            .format("%H:%M")
+            .to_string();
+        if greeting.message.trim().is_empty() {
+            return Err(format!("The {} greeting has no message", greeting.at));
+        }
+    }
+
+    let mut current = state.lock().await;
+    current.proactive = settings;
+    current.save(&app)?;
+    let enabled = current.proactive.enabled;
+    let identity = current.identity.clone();
+    drop(current);
+
+    log::info!("🗨️ Proactive remarks {}", if enabled { "enabled" } else { "disabled" });
+    Ok(if enabled {
+        identity.render("{name} will speak up on her own")
+    } else {
+        identity.render("{name} will only speak when spoken to")
+    })
+}
+
+/// The frontend is listening for prompts; greets once per launch if enabled
+#[tauri::command]
+pub async fn proactive_ready(state: tauri::State<'_, SettingsState>, app: AppHandle) -> Result<(), String> {
+    let settings = state.lock().await.proactive.clone();
+    if !settings.enabled || !settings.greet_on_start || START_FIRED.swap(true, Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    let instructions = format!(
+        "The user just opened the app and it's {}. Greet them warmly in one short sentence.",
+        part_of_day(Local::now().time())
+    );
+    speak(&app, &settings, ProactiveTrigger::Start, instructions);
+    Ok(())
+}
@@ -0,0 +1,440 @@
+use crate::persona;
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::{EvaSettings, SettingsState};
+use crate::wake_word::WakeWordModelSettings;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Voices the Realtime API offers
+pub const REALTIME_VOICES: &[&str] = &["alloy", "ash", "ballad", "coral", "echo", "sage", "shimmer", "verse"];
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Profile picked for the foreground application; overrides the chosen one while that app is in front
+static AUTOMATIC: Mutex<Option<String>> = Mutex::new(None);
+/// Tools the effective profile offers, read by `list_tools` / `call_tool`; `None` when unrestricted
+static ALLOWED_TOOLS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Wake word, persona, voice and tools to use together, e.g. one set for coding and one for games
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssistantProfile {
+    pub name: String,
+    /// `.ppn` keyword file; empty keeps the one from the wake word settings
+    pub keyword_path: String,
+    /// Persona profile to switch to; empty keeps the active persona
+    pub persona: String,
+    /// Realtime voice, one of `REALTIME_VOICES`; empty for the default
+    pub voice: String,
+    /// Tool names offered to the model; `None` offers every available tool
+    pub tools: Option<Vec<String>>,
+    /// Executable names that switch to this profile while in the foreground, e.g. "code" or "steam.exe"
+    pub apps: Vec<String>,
+}
+
+impl AssistantProfile {
+    fn matches_app(&self, app_name: &str) -> bool {
+        let app_name = normalize_app(app_name);
+        self.apps.iter().any(|app| normalize_app(app) == app_name)
+    }
+}
+
+/// Assistant profiles and which one the user picked
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    pub profiles: Vec<AssistantProfile>,
+    /// Chosen profile; empty when the regular settings apply
+    pub active: String,
+    /// Switch profiles to follow the foreground application
+    pub auto_switch: bool,
+}
+
+impl ProfileSettings {
+    fn profile(&self, name: &str) -> Option<&AssistantProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// The foreground application's profile, else the chosen one
+    fn effective(&self) -> Option<&AssistantProfile> {
+        let automatic = automatic();
+        automatic
+            .as_deref()
+            .and_then(|name| self.profile(name))
+            .or_else(|| self.profile(&self.active))
+    }
+}
+
+/// Event payload for `profile-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileChangedEvent {
+    /// Effective profile; `None` when the regular settings apply
+    pub active: Option<String>,
+    /// Picked for the foreground application rather than by the user
+    pub automatic: bool,
+    /// Realtime voice; empty for the default
+    pub voice: String,
+    pub tools: Option<Vec<String>>,
+}
+
+impl ProfileChangedEvent {
+    pub fn new(profile: Option<&AssistantProfile>) -> Self {
+        Self {
+            active: profile.map(|profile| profile.name.clone()),
+            automatic: profile.is_some_and(|profile| automatic().as_deref() == Some(profile.name.as_str())),
+            voice: profile.map(|profile| profile.voice.clone()).unwrap_or_default(),
+            tools: profile.and_then(|profile| profile.tools.clone()),
+        }
+    }
+}
+
+/// "Steam.exe", "steam" and "Steam.app" all name the same application
+fn normalize_app(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    name.strip_suffix(".exe")
+        .or_else(|| name.strip_suffix(".app"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    async fn run(program: &str, args: &[&str]) -> Option<String> {
+        let output = tokio::process::Command::new(program).args(args).output().await.ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// X11 only; Wayland doesn't let other clients inspect windows
+    pub async fn foreground_app() -> Option<String> {
+        let active = run("xprop", &["-root", "_NET_ACTIVE_WINDOW"]).await?;
+        // "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"
+        let id = active.rsplit(' ').next().filter(|id| id.starts_with("0x") && *id != "0x0")?;
+        // "_NET_WM_PID(CARDINAL) = 4242"
+        let pid: u32 = run("xprop", &["-id", id, "_NET_WM_PID"]).await?.rsplit(' ').next()?.parse().ok()?;
+        let name = tokio::fs::read_to_string(format!("/proc/{}/comm", pid)).await.ok()?;
+        Some(name.trim().to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    pub async fn foreground_app() -> Option<String> {
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(GetForegroundWindow(), Some(&mut pid as *mut u32)) };
+        if pid == 0 {
+            return None;
+        }
+
+        let mut path = [0u16; 1024];
+        let mut len = path.len() as u32;
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let queried = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(path.as_mut_ptr()), &mut len);
+            let _ = CloseHandle(process);
+            queried.ok()?;
+        }
+        let path = String::from_utf16_lossy(&path[..len as usize]);
+        std::path::Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    pub async fn foreground_app() -> Option<String> {
+        const SCRIPT: &str = "tell application \"System Events\" to get name of first process whose frontmost is true";
+        let output = tokio::process::Command::new("osascript").args(["-e", SCRIPT]).output().await.ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub async fn foreground_app() -> Option<String> {
+        None
+    }
+}
+
+fn automatic() -> Option<String> {
+    AUTOMATIC.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn remember_tools(profile: Option<&AssistantProfile>) {
+    *ALLOWED_TOOLS.lock().unwrap_or_else(|e| e.into_inner()) = profile.and_then(|profile| profile.tools.clone());
+}
+
+/// Whether the effective profile offers `tool` to the model
+pub fn allows_tool(tool: &str) -> bool {
+    ALLOWED_TOOLS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map_or(true, |tools| tools.iter().any(|allowed| allowed == tool))
+}
+
+/// Wake word models with the effective profile's keyword, if it has one
+pub fn wake_word_models(settings: &EvaSettings) -> WakeWordModelSettings {
+    let mut models = settings.wake_word.clone();
+    if let Some(profile) = settings.profiles.effective().filter(|profile| !profile.keyword_path.is_empty()) {
+        models.keyword_path = profile.keyword_path.clone();
+    }
+    models
+}
+
+/// Apply the chosen profile's tools at startup; its wake word is picked up through `wake_word_models`
+pub fn restore(settings: &ProfileSettings) {
+    remember_tools(settings.effective());
+}
+
+/// Switch to whatever profile is now effective: wake word, persona and tools here, voice and
+/// the session's tool list in the frontend through `profile-changed`
+async fn activate(app: &AppHandle, previous: Option<AssistantProfile>) -> Result<ProfileChangedEvent, String> {
+    let state = app.state::<SettingsState>();
+    let mut settings = state.lock().await;
+    let profile = settings.profiles.effective().cloned();
+    remember_tools(profile.as_ref());
+
+    // Persona switches stick, as if the user had picked the persona
+    let persona_event = match profile.as_ref() {
+        Some(profile) => settings.persona.activate(&profile.persona),
+        None => None,
+    };
+    if persona_event.is_some() {
+        settings.save(app)?;
+    }
+    let models = wake_word_models(&settings);
+    drop(settings);
+
+    if let Some(event) = persona_event {
+        persona::emit_changed(app, &event);
+    }
+
+    // Restarting detection drops audio mid-word, so only when the keyword actually changes
+    let keyword = |profile: Option<&AssistantProfile>| profile.map(|profile| profile.keyword_path.clone()).unwrap_or_default();
+    if keyword(previous.as_ref()) != keyword(profile.as_ref()) {
+        app.state::<WakeWordHandle>()
+            .set_models(models, app.clone())
+            .await
+            .map_err(|e| format!("Failed to switch the wake word: {}", e))?;
+    }
+
+    let event = ProfileChangedEvent::new(profile.as_ref());
+    if let Err(e) = app.emit("profile-changed", &event) {
+        log::error!("Failed to emit profile change: {}", e);
+    }
+    Ok(event)
+}
+
+/// Background task that switches profiles to follow the foreground application
+pub async fn run_profile_monitor(app: AppHandle) {
+    let settings_state = app.state::<SettingsState>().inner().clone();
+    // Eva's own window coming to the front keeps the current profile
+    let own_app = std::env::current_exe()
+        .ok()
+        .and_then(|path| path.file_name().map(|name| normalize_app(&name.to_string_lossy())));
+    let mut last_app: Option<String> = None;
+
+    log::info!("🗂️ Profile monitor started");
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let settings = settings_state.lock().await.profiles.clone();
+        let foreground = if settings.auto_switch {
+            platform::foreground_app().await.map(|name| normalize_app(&name))
+        } else {
+            None
+        };
+        if foreground.is_some() && foreground == own_app {
+            continue;
+        }
+        if foreground == last_app {
+            continue;
+        }
+        last_app = foreground.clone();
+
+        let matched = foreground.as_deref().and_then(|name| {
+            settings
+                .profiles
+                .iter()
+                .find(|profile| profile.matches_app(name))
+                .map(|profile| profile.name.clone())
+        });
+        let previous = settings.effective().cloned();
+        if matched == automatic() {
+            continue;
+        }
+        *AUTOMATIC.lock().unwrap_or_else(|e| e.into_inner()) = matched;
+        if settings.effective() == previous.as_ref() {
+            continue;
+        }
+
+        match activate(&app, previous).await {
+            Ok(event) => log::info!(
+                "🗂️ Profile {} for {}",
+                event.active.as_deref().unwrap_or("(none)"),
+                foreground.as_deref().unwrap_or("no app")
+            ),
+            Err(e) => log::warn!("Failed to switch profile: {}", e),
+        }
+    }
+}
+
+fn validate(profile: &AssistantProfile, settings: &EvaSettings) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if !profile.keyword_path.is_empty() {
+        if !profile.keyword_path.ends_with(".ppn") {
+            return Err("Keyword file must be a Porcupine .ppn file".to_string());
+        }
+        if !Path::new(&profile.keyword_path).exists() {
+            return Err(format!("Keyword file not found: {}", profile.keyword_path));
+        }
+    }
+    if !profile.persona.is_empty() && !settings.persona.profiles.iter().any(|persona| persona.name == profile.persona) {
+        return Err(format!("Unknown persona: {}", profile.persona));
+    }
+    if !profile.voice.is_empty() && !REALTIME_VOICES.contains(&profile.voice.as_str()) {
+        return Err(format!("Unknown voice: {}", profile.voice));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_profiles(state: tauri::State<'_, SettingsState>) -> Result<ProfileSettings, String> {
+    Ok(state.lock().await.profiles.clone())
+}
+
+#[tauri::command]
+pub async fn get_active_profile(state: tauri::State<'_, SettingsState>) -> Result<ProfileChangedEvent, String> {
+    Ok(ProfileChangedEvent::new(state.lock().await.profiles.effective()))
+}
+
+/// Create or overwrite a profile by name; changes to the effective profile apply right away
+#[tauri::command]
+pub async fn save_profile(
+    profile: AssistantProfile,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let profile = AssistantProfile {
+        name: profile.name.trim().to_string(),
+        keyword_path: profile.keyword_path.trim().to_string(),
+        persona: profile.persona.trim().to_string(),
+        voice: profile.voice.trim().to_lowercase(),
+        tools: profile.tools.map(|tools| {
+            tools.into_iter().map(|tool| tool.trim().to_string()).filter(|tool| !tool.is_empty()).collect()
+        }),
+        apps: profile.apps.into_iter().map(|app| app.trim().to_string()).filter(|app| !app.is_empty()).collect(),
+    };
+
+    let mut settings = state.lock().await;
+    validate(&profile, &settings)?;
+    let previous = settings.profiles.effective().cloned();
+    match settings.profiles.profiles.iter_mut().find(|existing| existing.name == profile.name) {
+        Some(existing) => *existing = profile.clone(),
+        None => settings.profiles.profiles.push(profile.clone()),
+    }
+    settings.save(&app)?;
+    let changed = settings.profiles.effective() != previous.as_ref();
+    drop(settings);
+
+    if changed {
+        activate(&app, previous).await?;
+    }
+    log::info!("🗂️ Profile '{}' saved", profile.name);
+    Ok(format!("Profile '{}' saved", profile.name))
+}
+
+#[tauri::command]
+pub async fn delete_profile(
+    name: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    let previous = settings.profiles.effective().cloned();
+    let before = settings.profiles.profiles.len();
+    settings.profiles.profiles.retain(|profile| profile.name != name);
+    if settings.profiles.profiles.len() == before {
+        return Err(format!("Unknown profile: {}", name));
+    }
+    if settings.profiles.active == name {
+        settings.profiles.active.clear();
+    }
+    settings.save(&app)?;
+    let changed = settings.profiles.effective() != previous.as_ref();
+    drop(settings);
+
+    if changed {
+        activate(&app, previous).await?;
+    }
+    log::info!("🗂️ Profile '{}' deleted", name);
+    Ok(format!("Profile '{}' deleted", name))
+}
+
+/// Pick a profile, or none with an empty name. Takes over from an automatic switch until the
+/// foreground application changes again.
+#[tauri::command]
+pub async fn switch_profile(
+    name: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    if !name.is_empty() && settings.profiles.profile(&name).is_none() {
+        return Err(format!("Unknown profile: {}", name));
+    }
+    let previous = settings.profiles.effective().cloned();
+    settings.profiles.active = name.clone();
+    settings.save(&app)?;
+    drop(settings);
+
+    *AUTOMATIC.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    activate(&app, previous).await?;
+
+    if name.is_empty() {
+        log::info!("🗂️ Profiles off");
+        Ok("Using the regular settings".to_string())
+    } else {
+        log::info!("🗂️ Switched to profile '{}'", name);
+        Ok(format!("Switched to profile '{}'", name))
+    }
+}
+
+/// Takes effect at the monitor's next check
+#[tauri::command]
+pub async fn set_profile_auto_switch(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.profiles.auto_switch = enabled;
+    settings.save(&app)?;
+    drop(settings);
+
+    log::info!("🗂️ Automatic profile switching {}", if enabled { "on" } else { "off" });
+    Ok(if enabled {
+        "Profiles follow the foreground app".to_string()
+    } else {
+        "Profiles only change when you switch them".to_string()
+    })
+}
@@ -0,0 +1,372 @@
+use crate::secrets;
+use crate::settings::SettingsState;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const PASSWORD_SECRET: &str = "proxy-password";
+const CONNECT_TIMEOUT_SECS: u64 = 15;
+/// A CONNECT response longer than this isn't a proxy we can talk to
+const MAX_CONNECT_RESPONSE_BYTES: usize = 8192;
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+/// Local servers (Ollama, Home Assistant) stay reachable with a manual proxy set
+const NO_PROXY: &str = "localhost,127.0.0.1,::1,.local,10.0.0.0/8,172.16.0.0/12,192.168.0.0/16";
+
+static SETTINGS: Mutex<ProxySettings> = Mutex::new(ProxySettings {
+    mode: ProxyMode::System,
+    url: String::new(),
+    username: None,
+    ca_bundle_path: String::new(),
+});
+
+/// Where Eva's connections to OpenAI and integrations go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyMode {
+    /// The OS settings: HTTP(S)_PROXY for Eva's own requests, the system proxy for the webview
+    #[default]
+    System,
+    /// Always connect directly
+    Off,
+    /// The proxy at `url`; the Realtime WebSocket and OpenAI requests are relayed through Eva to use it
+    Manual,
+}
+
+/// HTTP or SOCKS proxy; the password lives in the system keychain, not here
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    /// `http://host:port`, `socks5://host:port` or `socks5h://host:port`
+    pub url: String,
+    pub username: Option<String>,
+    /// PEM file of extra root certificates, e.g. the CA of a TLS-inspecting proxy; trusted on
+    /// top of the built-in roots, and only by connections Eva makes itself
+    pub ca_bundle_path: String,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            mode: ProxyMode::System,
+            url: String::new(),
+            username: None,
+            ca_bundle_path: String::new(),
+        }
+    }
+}
+
+impl ProxySettings {
+    fn proxy_url(&self) -> Result<reqwest::Url, String> {
+        let url = reqwest::Url::parse(self.url.trim()).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+        if !matches!(url.scheme(), "http" | "socks5" | "socks5h") {
+            return Err(format!(
+                "Unsupported proxy scheme \"{}\": use http://, socks5:// or socks5h://",
+                url.scheme()
+            ));
+        }
+        if url.host_str().is_none() {
+            return Err("Proxy URL has no host".to_string());
+        }
+        Ok(url)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.mode == ProxyMode::Manual {
+            self.proxy_url()?;
+        }
+        if !self.ca_bundle_path.is_empty() {
+            load_ca_bundle(&self.ca_bundle_path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The certificates in a PEM bundle; a file without any is an error
+fn load_ca_bundle(path: &str) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, String> {
+    let pem = std::fs::read(path).map_err(|e| format!("Failed to read certificate file {}: {}", path, e))?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid certificate file {}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("No PEM certificates found in {}", path));
+    }
+    Ok(certs)
+}
+
+/// The configured extra root certificates, if any load
+fn custom_roots() -> Option<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    let path = current().ca_bundle_path;
+    if path.is_empty() {
+        return None;
+    }
+    match load_ca_bundle(&path) {
+        Ok(certs) => Some(certs),
+        Err(e) => {
+            log::warn!("🔒 Ignoring custom CA: {}", e);
+            None
+        }
+    }
+}
+
+/// TLS setup for the relayed Realtime WebSocket: the built-in roots plus the custom CA.
+/// `None` without a custom CA, leaving tokio-tungstenite's default.
+pub fn tls_connector() -> Option<tokio_tungstenite::Connector> {
+    let custom = custom_roots()?;
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let (added, ignored) = roots.add_parsable_certificates(custom);
+    if ignored > 0 {
+        log::warn!("🔒 Skipped {} unusable certificate(s) in the custom CA bundle", ignored);
+    }
+    log::debug!("🔒 Trusting {} custom root certificate(s)", added);
+
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| log::warn!("🔒 Ignoring custom CA: {}", e))
+        .ok()?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Some(tokio_tungstenite::Connector::Rustls(Arc::new(config)))
+}
+
+fn current() -> ProxySettings {
+    SETTINGS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Username and password for the manual proxy, if it needs them
+fn credentials(settings: &ProxySettings) -> Option<(String, String)> {
+    settings
+        .username
+        .clone()
+        .filter(|username| !username.is_empty())
+        .map(|username| (username, secrets::get(PASSWORD_SECRET).unwrap_or_default()))
+}
+
+/// The manual proxy, if one is configured and valid
+fn manual_proxy() -> Option<(reqwest::Url, Option<(String, String)>)> {
+    let settings = current();
+    if settings.mode != ProxyMode::Manual {
+        return None;
+    }
+    match settings.proxy_url() {
+        Ok(url) => Some((url, credentials(&settings))),
+        Err(e) => {
+            log::warn!("🌐 Ignoring proxy: {}", e);
+            None
+        }
+    }
+}
+
+/// Every HTTP client Eva creates starts here so it follows the proxy and CA settings
+pub fn client_builder() -> reqwest::ClientBuilder {
+    let mut builder = reqwest::Client::builder();
+    for cert in custom_roots().unwrap_or_default() {
+        match reqwest::Certificate::from_der(&cert) {
+            Ok(cert) => builder = builder.add_root_certificate(cert),
+            Err(e) => log::warn!("🔒 Skipped a custom CA certificate: {}", e),
+        }
+    }
+
+    match current().mode {
+        ProxyMode::System => builder,
+        ProxyMode::Off => builder.no_proxy(),
+        ProxyMode::Manual => {
+            let Some((mut url, credentials)) = manual_proxy() else {
+                return builder;
+            };
+            if let Some((username, password)) = credentials {
+                // reqwest takes SOCKS credentials from the URL only
+                let _ = url.set_username(&username);
+                let _ = url.set_password(Some(&password));
+            }
+            match reqwest::Proxy::all(url) {
+                Ok(proxy) => builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_string(NO_PROXY))),
+                Err(e) => {
+                    log::warn!("🌐 Ignoring proxy: {}", e);
+                    builder
+                }
+            }
+        }
+    }
+}
+
+/// Open a TCP connection to `host:port`, tunnelled through the manual proxy if there is one
+pub async fn connect(host: &str, port: u16) -> Result<TcpStream, String> {
+    let connecting = async {
+        let Some((proxy, credentials)) = manual_proxy() else {
+            return TcpStream::connect((host, port))
+                .await
+                .map_err(|e| format!("Failed to connect to {}: {}", host, e));
+        };
+
+        let proxy_host = proxy.host_str().unwrap_or_default();
+        if proxy.scheme() == "http" {
+            http_connect(proxy_host, proxy.port().unwrap_or(8080), host, port, credentials).await
+        } else {
+            let proxy_addr = (proxy_host, proxy.port().unwrap_or(1080));
+            let stream = match credentials {
+                Some((username, password)) => {
+                    tokio_socks::tcp::Socks5Stream::connect_with_password(proxy_addr, (host, port), &username, &password)
+                        .await
+                }
+                None => tokio_socks::tcp::Socks5Stream::connect(proxy_addr, (host, port)).await,
+            };
+            stream
+                .map(|stream| stream.into_inner())
+                .map_err(|e| format!("SOCKS proxy couldn't reach {}: {}", host, e))
+        }
+    };
+
+    tokio::time::timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS), connecting)
+        .await
+        .map_err(|_| format!("Timed out connecting to {}", host))?
+}
+
+/// Ask an HTTP proxy for a tunnel with `CONNECT`
+async fn http_connect(
+    proxy_host: &str,
+    proxy_port: u16,
+    host: &str,
+    port: u16,
+    credentials: Option<(String, String)>,
+) -> Result<TcpStream, String> {
+    let mut stream = TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .map_err(|e| format!("Failed to reach proxy {}: {}", proxy_host, e))?;
+
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some((username, password)) = credentials {
+        let token = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", token));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to talk to proxy: {}", e))?;
+
+    // Byte by byte, so nothing past the headers (the tunnelled TLS handshake) is consumed
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > MAX_CONNECT_RESPONSE_BYTES {
+            return Err("Proxy sent an oversized CONNECT response".to_string());
+        }
+        match stream.read(&mut byte).await {
+            Ok(0) => return Err("Proxy closed the connection".to_string()),
+            Ok(_) => response.push(byte[0]),
+            Err(e) => return Err(format!("Failed to read from proxy: {}", e)),
+        }
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => Ok(stream),
+        Some("407") => Err("Proxy authentication required: check the proxy username and password".to_string()),
+        _ => Err(format!("Proxy refused to connect to {}: {}", host, status_line)),
+    }
+}
+
+/// Response to `proxied_request`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProxiedResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub body: String,
+}
+
+/// An HTTPS request from the frontend, sent through the proxy settings; webview `fetch` can't use a manual proxy
+#[tauri::command]
+pub async fn proxied_request(
+    url: String,
+    method: String,
+    headers: HashMap<String, String>,
+    body: Option<String>,
+) -> Result<ProxiedResponse, String> {
+    if !url.starts_with("https://") {
+        return Err("Only https:// requests can be proxied".to_string());
+    }
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method: {}", method))?;
+
+    let client = client_builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.request(method, &url);
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        request = request.body(body);
+    }
+
+    let response = request.send().await.map_err(|e| format!("Request failed: {}", e))?;
+    let status = response.status();
+    Ok(ProxiedResponse {
+        status: status.as_u16(),
+        status_text: status.canonical_reason().unwrap_or_default().to_string(),
+        body: response.text().await.map_err(|e| format!("Failed to read response: {}", e))?,
+    })
+}
+
+/// Apply the saved proxy at startup
+pub fn restore(settings: &ProxySettings) {
+    *SETTINGS.lock().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+}
+
+#[tauri::command]
+pub async fn get_proxy(state: tauri::State<'_, SettingsState>) -> Result<ProxySettings, String> {
+    Ok(state.lock().await.proxy.clone())
+}
+
+/// Save the proxy; a `None` password keeps the stored one. New connections use it, open ones
+/// (including the Realtime session) keep their route until they reconnect.
+#[tauri::command]
+pub async fn set_proxy(
+    proxy: ProxySettings,
+    password: Option<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let proxy = ProxySettings {
+        url: proxy.url.trim().to_string(),
+        ca_bundle_path: proxy.ca_bundle_path.trim().to_string(),
+        username: proxy.username.map(|username| username.trim().to_string()).filter(|username| !username.is_empty()),
+        ..proxy
+    };
+    proxy.validate()?;
+
+    if let Some(password) = password {
+        secrets::set(PASSWORD_SECRET, &password)?;
+    }
+
+    let mut settings = state.lock().await;
+    settings.proxy = proxy.clone();
+    settings.save(&app)?;
+    drop(settings);
+
+    restore(&proxy);
+    if let Err(e) = app.emit("proxy-changed", &proxy) {
+        log::error!("Failed to emit proxy change: {}", e);
+    }
+
+    log::info!("🌐 Proxy set to {:?} {}", proxy.mode, proxy.url);
+    let route = match proxy.mode {
+        ProxyMode::System => "Using the system proxy".to_string(),
+        ProxyMode::Off => "Connecting directly, without a proxy".to_string(),
+        ProxyMode::Manual => format!("Connecting through {}", proxy.url),
+    };
+    Ok(if proxy.ca_bundle_path.is_empty() {
+        route
+    } else {
+        format!("{}, trusting the certificates in {}", route, proxy.ca_bundle_path)
+    })
+}
@@ -0,0 +1,70 @@
+use crate::chat_mode;
+use crate::earcons::{self, EarconCue};
+use crate::settings::SettingsState;
+use crate::wake_word::WakeWordEvent;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter};
+
+/// Set while the push-to-talk key is held, so key auto-repeat can't retrigger a wake
+static PUSH_TO_TALK_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Push-to-talk settings for the focused window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PushToTalkSettings {
+    pub enabled: bool,
+    /// `KeyboardEvent.code` of the key to hold, e.g. "Space" or "F13"
+    pub key: String,
+}
+
+impl Default for PushToTalkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            key: "Space".to_string(),
+        }
+    }
+}
+
+/// Push-to-talk key pressed: wake Eva exactly as a detected wake word would
+#[tauri::command]
+pub async fn push_to_talk_pressed(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if !state.lock().await.push_to_talk.enabled {
+        return Err("Push-to-talk is disabled".to_string());
+    }
+    if chat_mode::is_text_only() {
+        return Err("Push-to-talk is off in text-only chat mode".to_string());
+    }
+
+    if PUSH_TO_TALK_ACTIVE.swap(true, Ordering::Relaxed) {
+        return Ok("Push-to-talk already active".to_string());
+    }
+
+    log::info!("⌨️  Push-to-talk pressed");
+
+    let event = WakeWordEvent::push_to_talk();
+    app.emit("wake-word-detected", &event)
+        .map_err(|e| format!("Failed to emit push-to-talk event: {}", e))?;
+    earcons::play(&app, EarconCue::ListeningStart).await;
+
+    Ok("Push-to-talk started".to_string())
+}
+
+/// Push-to-talk key released: end the user's turn
+#[tauri::command]
+pub async fn push_to_talk_released(app: AppHandle) -> Result<String, String> {
+    if !PUSH_TO_TALK_ACTIVE.swap(false, Ordering::Relaxed) {
+        return Ok("Push-to-talk not active".to_string());
+    }
+
+    log::info!("⌨️  Push-to-talk released");
+
+    app.emit("push-to-talk-released", WakeWordEvent::push_to_talk())
+        .map_err(|e| format!("Failed to emit push-to-talk release event: {}", e))?;
+
+    Ok("Push-to-talk ended".to_string())
+}
@@ -0,0 +1,195 @@
+use crate::porcupine_service::WakeWordHandle;
+use crate::settings::SettingsState;
+use crate::tray;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const CHECK_INTERVAL_SECS: u64 = 30;
+const TIME_FORMAT: &str = "%H:%M";
+
+/// Whether the current time falls inside the configured quiet hours
+static QUIET_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Wake word listening was stopped by the scheduler and should resume afterwards
+static STOPPED_FOR_QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Daily window (local time) in which wake word listening is disabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHoursSettings {
+    pub enabled: bool,
+    /// `HH:MM`, may be later than `end` for windows spanning midnight
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for QuietHoursSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "23:00".to_string(),
+            end: "07:00".to_string(),
+        }
+    }
+}
+
+impl QuietHoursSettings {
+    fn window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = NaiveTime::parse_from_str(&self.start, TIME_FORMAT).ok()?;
+        let end = NaiveTime::parse_from_str(&self.end, TIME_FORMAT).ok()?;
+        Some((start, end))
+    }
+
+    fn contains(&self, now: NaiveTime) -> bool {
+        match self.window() {
+            Some((start, end)) if self.enabled && start != end => {
+                if start < end {
+                    now >= start && now < end
+                } else {
+                    now >= start || now < end
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Event payload for `quiet-hours-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHoursEvent {
+    pub active: bool,
+    pub start: String,
+    pub end: String,
+}
+
+pub fn is_active() -> bool {
+    QUIET_ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Background task that toggles wake word listening at the quiet hours boundaries
+pub async fn run_quiet_hours_scheduler(app: AppHandle) {
+    let porcupine = app.state::<WakeWordHandle>().inner().clone();
+    let settings_state = app.state::<SettingsState>().inner().clone();
+
+    log::info!("🌙 Quiet hours scheduler started");
+
+    loop {
+        let settings = settings_state.lock().await.quiet_hours.clone();
+        let quiet = settings.contains(chrono::Local::now().time());
+
+        if quiet != QUIET_ACTIVE.load(Ordering::Relaxed) {
+            QUIET_ACTIVE.store(quiet, Ordering::Relaxed);
+
+            if quiet {
+                log::info!("🌙 Quiet hours started ({}-{}), disabling wake word listening", settings.start, settings.end);
+                if porcupine.is_listening() {
+                    match porcupine.stop_listening().await {
+                        Ok(_) => STOPPED_FOR_QUIET.store(true, Ordering::Relaxed),
+                        Err(e) => log::warn!("Failed to stop wake word detection for quiet hours: {}", e),
+                    }
+                }
+            } else {
+                log::info!("☀️ Quiet hours ended");
+                if STOPPED_FOR_QUIET.swap(false, Ordering::Relaxed) && !porcupine.is_listening() {
+                    if let Err(e) = porcupine.start_listening(app.clone()).await {
+                        log::error!("Failed to resume wake word detection after quiet hours: {}", e);
+                    }
+                }
+            }
+
+            tray::set_status(&app, quiet.then_some("Quiet hours")).await;
+
+            let event = QuietHoursEvent { active: quiet, start: settings.start, end: settings.end };
+            if let Err(e) = app.emit("quiet-hours-changed", &event) {
+                log::error!("Failed to emit quiet hours event: {}", e);
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
+
+#[tauri::command]
+pub async fn get_quiet_hours(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<QuietHoursEvent, String> {
+    let settings = state.lock().await.quiet_hours.clone();
+    Ok(QuietHoursEvent { active: is_active(), start: settings.start, end: settings.end })
+}
+
+/// Takes effect at the scheduler's next check
+#[tauri::command]
+pub async fn set_quiet_hours(
+    enabled: bool,
+    start: String,
+    end: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let quiet_hours = QuietHoursSettings { enabled, start: start.trim().to_string(), end: end.trim().to_string() };
+    if quiet_hours.window().is_none() {
+        return Err("Quiet hours must be given as HH:MM".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.quiet_hours = quiet_hours;
+    settings.save(&app)?;
+
+    if enabled {
+        log::info!("Quiet hours set to {}-{}", start, end);
+        Ok(format!("Quiet hours set to {}-{}", start, end))
+    } else {
+        log::info!("Quiet hours disabled");
+        Ok("Quiet hours disabled".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quiet(start: &str, end: &str) -> QuietHoursSettings {
+        QuietHoursSettings { enabled: true, start: start.to_string(), end: end.to_string() }
+    }
+
+    fn at(time: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(time, TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn same_day_window() {
+        let settings = quiet("13:00", "15:30");
+        assert!(settings.contains(at("14:00")));
+        assert!(!settings.contains(at("12:59")));
+        assert!(!settings.contains(at("16:00")));
+        assert!(!settings.contains(at("02:00")));
+    }
+
+    #[test]
+    fn window_wrapping_past_midnight() {
+        let settings = quiet("23:00", "07:00");
+        assert!(settings.contains(at("23:30")));
+        assert!(settings.contains(at("00:00")));
+        assert!(settings.contains(at("06:59")));
+        assert!(!settings.contains(at("12:00")));
+        assert!(!settings.contains(at("22:59")));
+    }
+
+    #[test]
+    fn start_is_inside_and_end_is_outside() {
+        for settings in [quiet("13:00", "15:30"), quiet("23:00", "07:00")] {
+            assert!(settings.contains(at(&settings.start)));
+            assert!(!settings.contains(at(&settings.end)));
+        }
+    }
+
+    #[test]
+    fn disabled_empty_or_invalid_windows_are_never_quiet() {
+        let disabled = QuietHoursSettings { enabled: false, ..quiet("00:00", "23:59") };
+        assert!(!disabled.contains(at("12:00")));
+        assert!(!quiet("08:00", "08:00").contains(at("08:00")));
+        assert!(!quiet("8pm", "07:00").contains(at("22:00")));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::error::{EvaError, RealtimeError};
+use crate::proxy;
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+/// Open relays, by id, each fed by the frontend's `realtime_relay_send` calls
+static CONNECTIONS: Mutex<Option<HashMap<u64, mpsc::UnboundedSender<Message>>>> = Mutex::new(None);
+
+/// Event payload for `realtime-relay`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RelayEvent {
+    Message { id: u64, data: String },
+    Error { id: u64, message: String },
+    Closed { id: u64, code: u16, reason: String },
+}
+
+fn emit(app: &AppHandle, event: RelayEvent) {
+    if let Err(e) = app.emit("realtime-relay", &event) {
+        log::error!("Failed to emit Realtime relay event: {}", e);
+    }
+}
+
+/// A handshake that failed because the server's certificate isn't trusted, e.g. one
+/// re-signed by a TLS-inspecting proxy whose CA Eva doesn't know
+fn certificate_error(error: &WsError) -> Option<String> {
+    let WsError::Io(io) = error else {
+        return None;
+    };
+    match io.get_ref()?.downcast_ref::<rustls::Error>()? {
+        error @ (rustls::Error::InvalidCertificate(_) | rustls::Error::NoCertificatesPresented) => {
+            Some(error.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn connection(id: u64) -> Option<mpsc::UnboundedSender<Message>> {
+    CONNECTIONS
+        .lock()
+        .ok()
+        .and_then(|connections| connections.as_ref().and_then(|connections| connections.get(&id).cloned()))
+}
+
+/// Open the Realtime WebSocket from here instead of the webview, so it can go through the
+/// manual proxy and trust the custom CA. Returns the relay id; server messages arrive as `realtime-relay` events.
+#[tauri::command]
+pub async fn realtime_relay_connect(url: String, protocols: Vec<String>, app: AppHandle) -> Result<u64, String> {
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| format!("Invalid Realtime URL: {}", e))?;
+    if !protocols.is_empty() {
+        let protocols = protocols
+            .join(", ")
+            .parse()
+            .map_err(|e| format!("Invalid WebSocket protocol: {}", e))?;
+        request.headers_mut().insert("Sec-WebSocket-Protocol", protocols);
+    }
+
+    let host = request.uri().host().ok_or("Realtime URL has no host")?.to_string();
+    let port = request
+        .uri()
+        .port_u16()
+        .unwrap_or(if request.uri().scheme_str() == Some("ws") { 80 } else { 443 });
+
+    let stream = proxy::connect(&host, port).await?;
+    let (socket, _) = match tokio_tungstenite::client_async_tls_with_config(request, stream, None, proxy::tls_connector()).await {
+        Ok(connected) => connected,
+        Err(e) => {
+            if let Some(reason) = certificate_error(&e) {
+                let error = EvaError::from(RealtimeError::Certificate(format!("{}: {}", host, reason)));
+                log::error!("🔒 {}", error);
+                error.emit(&app);
+            }
+            return Err(format!("Realtime WebSocket handshake failed: {}", e));
+        }
+    };
+
+    let id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, mut outgoing) = mpsc::unbounded_channel();
+    CONNECTIONS
+        .lock()
+        .map_err(|_| "Relay lock poisoned".to_string())?
+        .get_or_insert_with(HashMap::new)
+        .insert(id, sender);
+
+    let (mut write, mut read) = socket.split();
+    tauri::async_runtime::spawn(async move {
+        let (code, reason) = loop {
+            tokio::select! {
+                message = outgoing.recv() => match message {
+                    Some(message) => {
+                        if let Err(e) = write.send(message).await {
+                            emit(&app, RelayEvent::Error { id, message: e.to_string() });
+                            break (1006, e.to_string());
+                        }
+                    }
+                    None => break (1000, String::new()),
+                },
+                message = read.next() => match message {
+                    Some(Ok(Message::Text(data))) => emit(&app, RelayEvent::Message { id, data }),
+                    Some(Ok(Message::Close(frame))) => {
+                        break frame
+                            .map(|frame| (u16::from(frame.code), frame.reason.to_string()))
+                            .unwrap_or((1005, String::new()));
+                    }
+                    // Pings are answered by tungstenite; the Realtime API sends no binary frames
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        emit(&app, RelayEvent::Error { id, message: e.to_string() });
+                        break (1006, e.to_string());
+                    }
+                    None => break (1006, "Connection lost".to_string()),
+                },
+            }
+        };
+
+        if let Ok(mut connections) = CONNECTIONS.lock() {
+            if let Some(connections) = connections.as_mut() {
+                connections.remove(&id);
+            }
+        }
+        log::info!("🌐 Realtime relay {} closed ({})", id, code);
+        emit(&app, RelayEvent::Closed { id, code, reason });
+    });
+
+    log::info!("🌐 Realtime relay {} connected to {}", id, host);
+    Ok(id)
+}
+
+/// Forward one client event; sent in call order
+#[tauri::command]
+pub async fn realtime_relay_send(id: u64, data: String) -> Result<(), String> {
+    connection(id)
+        .ok_or_else(|| "Realtime relay is closed".to_string())?
+        .send(Message::Text(data))
+        .map_err(|_| "Realtime relay is closed".to_string())
+}
+
+/// Start the closing handshake; `realtime-relay` reports `closed` once it's done
+#[tauri::command]
+pub async fn realtime_relay_close(id: u64) -> Result<(), String> {
+    if let Some(sender) = connection(id) {
+        let _ = sender.send(Message::Close(None));
+    }
+    Ok(())
+}
@@ -0,0 +1,271 @@
+use crate::earcons::{self, EarconCue};
+use crate::tools::ToolDefinition;
+use chrono::{Duration as ChronoDuration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+pub const TOOL_NAME: &str = "set_reminder";
+
+const REMINDERS_FILE: &str = "reminders.json";
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Pending reminders, soonest first; mirrored to `reminders.json` so they survive a restart
+static REMINDERS: Mutex<Vec<Reminder>> = Mutex::new(Vec::new());
+/// Keeps ids unique for reminders created in the same millisecond
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderKind {
+    /// Counts down from now, e.g. "a timer for 10 minutes"
+    Timer,
+    /// Goes off at a time of day, e.g. "wake me at 7"
+    Alarm,
+    /// Either of the above, with something to remember
+    Reminder,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: String,
+    pub kind: ReminderKind,
+    pub label: String,
+    /// Unix timestamp in milliseconds
+    pub due_at: u64,
+    /// Unix timestamp in milliseconds
+    pub created_at: u64,
+}
+
+impl Reminder {
+    fn title(&self) -> &'static str {
+        match self.kind {
+            ReminderKind::Timer => "⏲️ Timer done",
+            ReminderKind::Alarm => "⏰ Alarm",
+            ReminderKind::Reminder => "📝 Reminder",
+        }
+    }
+}
+
+/// Tool arguments: `in_seconds` for a countdown or `at` ("HH:MM", local time) for a time of day
+#[derive(Debug, Deserialize)]
+struct ToolArguments {
+    kind: ReminderKind,
+    #[serde(default)]
+    label: String,
+    in_seconds: Option<u64>,
+    at: Option<String>,
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn reminders_file(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(REMINDERS_FILE))
+}
+
+fn save(app: &AppHandle, reminders: &[Reminder]) {
+    let result = reminders_file(app).and_then(|path| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let contents = serde_json::to_string_pretty(reminders).map_err(|e| e.to_string())?;
+        fs::write(path, contents).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        log::error!("Failed to save reminders: {}", e);
+    }
+}
+
+fn emit_changed(app: &AppHandle, reminders: &[Reminder]) {
+    if let Err(e) = app.emit("reminders-changed", reminders) {
+        log::error!("Failed to emit reminders change: {}", e);
+    }
+}
+
+/// Next occurrence of a local "HH:MM" time, tomorrow if it has already passed today
+fn next_occurrence(at: &str) -> Result<u64, String> {
+    let time = NaiveTime::parse_from_str(at.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time \"{}\", expected HH:MM", at))?;
+    let now = Local::now();
+    let mut date = now.date_naive();
+    if time <= now.time() {
+        date += ChronoDuration::days(1);
+    }
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|due| due.timestamp_millis() as u64)
+        .ok_or_else(|| format!("{} doesn't exist in the local time zone", at))
+}
+
+/// Add a reminder and persist it; returns the stored reminder
+fn add(app: &AppHandle, kind: ReminderKind, label: &str, due_at: u64) -> Result<Reminder, String> {
+    let created_at = now_millis();
+    let reminder = Reminder {
+        id: format!("{}-{}", created_at, NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+        kind,
+        label: label.trim().to_string(),
+        due_at,
+        created_at,
+    };
+
+    let mut reminders = REMINDERS.lock().map_err(|_| "Reminders lock poisoned".to_string())?;
+    reminders.push(reminder.clone());
+    reminders.sort_by_key(|reminder| reminder.due_at);
+    save(app, &reminders);
+    emit_changed(app, &reminders);
+
+    log::info!("⏰ {:?} \"{}\" set for {}", kind, reminder.label, due_at);
+    Ok(reminder)
+}
+
+/// Load saved reminders; ones that came due while Eva was closed fire on the first check
+pub fn restore(app: &AppHandle) {
+    let Ok(path) = reminders_file(app) else {
+        return;
+    };
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return;
+    };
+
+    match serde_json::from_str::<Vec<Reminder>>(&contents) {
+        Ok(saved) => {
+            log::info!("⏰ Restored {} reminder(s)", saved.len());
+            if let Ok(mut reminders) = REMINDERS.lock() {
+                *reminders = saved;
+                reminders.sort_by_key(|reminder| reminder.due_at);
+            }
+        }
+        Err(e) => log::warn!("Ignoring invalid reminders file: {}", e),
+    }
+}
+
+/// Fire reminders as they come due
+pub async fn run_reminder_scheduler(app: AppHandle) {
+    log::info!("⏰ Reminder scheduler started");
+
+    loop {
+        let due: Vec<Reminder> = match REMINDERS.lock() {
+            Ok(mut reminders) => {
+                let now = now_millis();
+                let split = reminders.partition_point(|reminder| reminder.due_at <= now);
+                let due: Vec<Reminder> = reminders.drain(..split).collect();
+                if !due.is_empty() {
+                    save(&app, &reminders);
+                    emit_changed(&app, &reminders);
+                }
+                due
+            }
+            Err(_) => Vec::new(),
+        };
+
+        for reminder in due {
+            fire(&app, &reminder).await;
+        }
+
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+async fn fire(app: &AppHandle, reminder: &Reminder) {
+    log::info!("⏰ {:?} \"{}\" went off", reminder.kind, reminder.label);
+
+    let body = if reminder.label.is_empty() {
+        reminder.title().to_string()
+    } else {
+        reminder.label.clone()
+    };
+    if let Err(e) = app.notification().builder().title(reminder.title()).body(body).show() {
+        log::warn!("Failed to show reminder notification: {}", e);
+    }
+
+    earcons::play(app, EarconCue::Alarm).await;
+
+    if let Err(e) = app.emit("reminder-fired", reminder) {
+        log::error!("Failed to emit reminder event: {}", e);
+    }
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: "Set a timer, alarm or reminder. Use in_seconds for \"in 10 minutes\" and \
+            at for a time of day."
+            .to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "kind": {
+                    "type": "string",
+                    "enum": ["timer", "alarm", "reminder"],
+                },
+                "label": {
+                    "type": "string",
+                    "description": "What it's for, e.g. \"pasta\" or \"call mom\"",
+                },
+                "in_seconds": {
+                    "type": "integer",
+                    "description": "Seconds from now",
+                },
+                "at": {
+                    "type": "string",
+                    "description": "Local time of day as HH:MM (24-hour)",
+                },
+            },
+            "required": ["kind"],
+        }),
+    }
+}
+
+pub fn call(app: &AppHandle, arguments: serde_json::Value) -> Result<String, String> {
+    let arguments: ToolArguments = serde_json::from_value(arguments)
+        .map_err(|e| format!("Invalid reminder arguments: {}", e))?;
+
+    let due_at = match (arguments.in_seconds, arguments.at.as_deref()) {
+        (Some(seconds), _) => now_millis().saturating_add(seconds.saturating_mul(1000)),
+        (None, Some(at)) => next_occurrence(at)?,
+        (None, None) => return Err("Either in_seconds or at is required".to_string()),
+    };
+
+    let reminder = add(app, arguments.kind, &arguments.label, due_at)?;
+    let when = Local
+        .timestamp_millis_opt(reminder.due_at as i64)
+        .single()
+        .map(|time| time.format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+    Ok(format!("{:?} set for {}", reminder.kind, when))
+}
+
+#[tauri::command]
+pub async fn list_reminders() -> Result<Vec<Reminder>, String> {
+    REMINDERS
+        .lock()
+        .map(|reminders| reminders.clone())
+        .map_err(|_| "Reminders lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_reminder(id: String, app: AppHandle) -> Result<String, String> {
+    let mut reminders = REMINDERS.lock().map_err(|_| "Reminders lock poisoned".to_string())?;
+    let index = reminders
+        .iter()
+        .position(|reminder| reminder.id == id)
+        .ok_or_else(|| format!("Unknown reminder: {}", id))?;
+    let cancelled = reminders.remove(index);
+    save(&app, &reminders);
+    emit_changed(&app, &reminders);
+
+    log::info!("⏰ {:?} \"{}\" cancelled", cancelled.kind, cancelled.label);
+    Ok(format!("{:?} cancelled", cancelled.kind))
+}
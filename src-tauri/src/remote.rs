@@ -0,0 +1,297 @@
+use crate::chat_mode;
+use crate::porcupine_service::WakeWordHandle;
+use crate::privacy;
+use crate::profiles;
+use crate::settings::SettingsState;
+use crate::tray;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// Deep links look like `eva://ask?text=...`
+pub const URL_SCHEME: &str = "eva";
+
+pub const SHOW_ARG: &str = "--show";
+pub const ASK_ARG: &str = "--ask";
+pub const ANNOUNCE_ARG: &str = "--announce";
+pub const TOGGLE_LISTENING_ARG: &str = "--toggle-listening";
+pub const MUTE_ARG: &str = "--mute";
+pub const UNMUTE_ARG: &str = "--unmute";
+pub const PROFILE_ARG: &str = "--profile";
+
+/// Set once the frontend listens for chat messages; earlier questions wait in `PENDING_QUESTIONS`
+static FRONTEND_READY: AtomicBool = AtomicBool::new(false);
+static PENDING_QUESTIONS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Actions other apps and scripts can trigger in the running instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteCommand {
+    /// Bring the main window forward
+    Show,
+    /// Send a typed question, exactly as if it came from the chat box
+    Ask(String),
+    /// Speak text aloud without involving the model
+    Announce(String),
+    ToggleListening,
+    Mute(bool),
+    /// Switch assistant profile; an empty name goes back to the regular settings
+    Profile(String),
+}
+
+/// Where a remote command came from, which decides whether the user confirms it first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandSource {
+    /// An `eva://` link, which any web page or app can open
+    DeepLink,
+    /// A command line flag, typed by the user or run by their own scripts
+    Cli,
+    /// The MQTT broker the user configured, which runs unattended home automations
+    Mqtt,
+}
+
+impl CommandSource {
+    /// Showing the window and muting are harmless; anything else from a link needs the user's OK
+    fn needs_confirmation(self, command: &RemoteCommand) -> bool {
+        self == Self::DeepLink && !matches!(command, RemoteCommand::Show | RemoteCommand::Mute(true))
+    }
+}
+
+/// Event payload for `wake-word-toggled`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WakeWordToggledEvent {
+    pub listening: bool,
+}
+
+/// Event payload for `announce`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnounceEvent {
+    pub text: String,
+}
+
+impl RemoteCommand {
+    /// `eva://show`, `eva://ask?text=...`, `eva://announce?text=...`, `eva://toggle-listening`,
+    /// `eva://mute`, `eva://unmute`, `eva://profile?name=...`
+    pub fn from_url(url: &Url) -> Option<Self> {
+        if url.scheme() != URL_SCHEME {
+            return None;
+        }
+
+        let text = || url.query_pairs().find(|(key, _)| key == "text").map(|(_, text)| text);
+        match url.host_str()? {
+            "show" => Some(Self::Show),
+            "ask" => text().and_then(|text| Self::ask(&text)),
+            "announce" => text().and_then(|text| Self::announce(&text)),
+            "toggle-listening" => Some(Self::ToggleListening),
+            "mute" => Some(Self::Mute(true)),
+            "unmute" => Some(Self::Mute(false)),
+            "profile" => Some(Self::Profile(
+                url.query_pairs()
+                    .find(|(key, _)| key == "name")
+                    .map(|(_, name)| name.trim().to_string())
+                    .unwrap_or_default(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// First command in a command line: one of the flags above, or an `eva://` URL,
+    /// which is how Windows and Linux hand deep links to the app
+    pub fn from_args(args: &[String]) -> Option<(Self, CommandSource)> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let command = match arg.as_str() {
+                SHOW_ARG => Some(Self::Show),
+                ASK_ARG => args.next().and_then(|text| Self::ask(text.as_str())),
+                ANNOUNCE_ARG => args.next().and_then(|text| Self::announce(text.as_str())),
+                TOGGLE_LISTENING_ARG => Some(Self::ToggleListening),
+                MUTE_ARG => Some(Self::Mute(true)),
+                UNMUTE_ARG => Some(Self::Mute(false)),
+                PROFILE_ARG => Some(Self::Profile(args.next().map(|name| name.trim().to_string()).unwrap_or_default())),
+                other => {
+                    let link = other.parse::<Url>().ok().and_then(|url| Self::from_url(&url));
+                    if link.is_some() {
+                        return link.map(|command| (command, CommandSource::DeepLink));
+                    }
+                    None
+                }
+            };
+            if let Some(command) = command {
+                return Some((command, CommandSource::Cli));
+            }
+        }
+        None
+    }
+
+    /// `None` for blank text
+    pub fn ask(text: &str) -> Option<Self> {
+        let text = text.trim();
+        (!text.is_empty()).then(|| Self::Ask(text.to_string()))
+    }
+
+    /// `None` for blank text
+    pub fn announce(text: &str) -> Option<Self> {
+        let text = text.trim();
+        (!text.is_empty()).then(|| Self::Announce(text.to_string()))
+    }
+}
+
+/// Run a command in the background; failures are logged, there is no caller to report to
+pub fn dispatch(app: &AppHandle, command: RemoteCommand, source: CommandSource) {
+    log::info!("📨 Remote command from {:?}: {:?}", source, command);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if source.needs_confirmation(&command) && !confirm(&app, &command).await {
+            log::info!("📨 Remote command declined");
+            return;
+        }
+        if let Err(e) = execute(&app, command).await {
+            log::warn!("Remote command failed: {}", e);
+        }
+    });
+}
+
+/// Same handlers the UI uses, so remote actions behave identically
+async fn execute(app: &AppHandle, command: RemoteCommand) -> Result<(), String> {
+    match command {
+        RemoteCommand::Show => tray::show_main_window(app),
+        RemoteCommand::Ask(text) => {
+            // Checked under the lock so a question can't slip past `take_pending_questions`
+            {
+                let mut pending = PENDING_QUESTIONS.lock()
+                    .map_err(|_| "Pending questions lock poisoned".to_string())?;
+                if !FRONTEND_READY.load(Ordering::Relaxed) {
+                    pending.push(text);
+                    return Ok(());
+                }
+            }
+            chat_mode::send_chat_message(text, None, app.clone()).await?;
+        }
+        // The frontend speaks it with the local TTS voice
+        RemoteCommand::Announce(text) => app
+            .emit("announce", &AnnounceEvent { text })
+            .map_err(|e| format!("Failed to emit announce event: {}", e))?,
+        RemoteCommand::ToggleListening => toggle_listening(app).await?,
+        RemoteCommand::Mute(muted) => {
+            privacy::set_mic_muted(muted, app.state::<SettingsState>(), app.clone()).await?;
+        }
+        RemoteCommand::Profile(name) => {
+            profiles::switch_profile(name, app.state::<SettingsState>(), app.clone()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Any app or web page can open an `eva://` link, so the user sees what it wants to do first.
+/// Closing the dialog counts as no.
+async fn confirm(app: &AppHandle, command: &RemoteCommand) -> bool {
+    let identity = app.state::<SettingsState>().lock().await.identity.clone();
+    let quoted = |intro: &str, text: &str, outro: &str| {
+        format!("{}\n\n\"{}\"{}", identity.render(intro), text, identity.render(outro))
+    };
+    let message = match command {
+        RemoteCommand::Ask(text) => quoted(
+            "A link wants to ask {name}:",
+            text,
+            "\n\n{name} may use its tools on this computer to answer.",
+        ),
+        RemoteCommand::Announce(text) => quoted("A link wants {name} to say:", text, ""),
+        RemoteCommand::ToggleListening => identity.render("A link wants to turn {name}'s wake word listening on or off."),
+        RemoteCommand::Mute(_) => identity.render("A link wants to unmute {name}'s microphone."),
+        RemoteCommand::Profile(name) if name.is_empty() => {
+            identity.render("A link wants to switch {name} back to the regular settings.")
+        }
+        RemoteCommand::Profile(name) => quoted("A link wants to switch {name} to the profile", name, ""),
+        RemoteCommand::Show => return true,
+    };
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(message)
+        .title(identity.render("Allow this link to control {name}?"))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Allow".to_string(),
+            "Don't allow".to_string(),
+        ))
+        .show(move |accepted| {
+            let _ = tx.send(accepted);
+        });
+    rx.await.unwrap_or(false)
+}
+
+async fn toggle_listening(app: &AppHandle) -> Result<(), String> {
+    let porcupine = app.state::<WakeWordHandle>().inner().clone();
+    if porcupine.is_listening() {
+        porcupine.stop_listening().await
+    } else {
+        porcupine.start_listening(app.clone()).await
+    }
+    .map_err(|e| format!("Failed to toggle wake word listening: {}", e))?;
+
+    let event = WakeWordToggledEvent { listening: porcupine.is_listening() };
+    log::info!("📨 Wake word listening {}", if event.listening { "started" } else { "stopped" });
+    if let Err(e) = app.emit("wake-word-toggled", &event) {
+        log::error!("Failed to emit wake word toggle event: {}", e);
+    }
+    Ok(())
+}
+
+/// Act on the arguments (or Windows/Linux deep link) this instance was launched with
+pub fn handle_launch_args(app: &AppHandle) {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some((command, source)) = RemoteCommand::from_args(&args) {
+        dispatch(app, command, source);
+    }
+}
+
+/// The main window is (re)loading and its chat listener is gone; questions wait until it asks again
+pub fn frontend_unloaded() {
+    let _pending = PENDING_QUESTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    FRONTEND_READY.store(false, Ordering::Relaxed);
+}
+
+/// The frontend is listening for chat messages; hand it the questions asked before it was
+#[tauri::command]
+pub async fn take_pending_questions() -> Result<Vec<String>, String> {
+    let mut pending = PENDING_QUESTIONS.lock()
+        .map_err(|_| "Pending questions lock poisoned".to_string())?;
+    FRONTEND_READY.store(true, Ordering::Relaxed);
+    Ok(std::mem::take(&mut *pending))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn links_in_arguments_count_as_deep_links() {
+        assert_eq!(
+            RemoteCommand::from_args(&args(&["--ask", "what time is it"])),
+            Some((RemoteCommand::Ask("what time is it".to_string()), CommandSource::Cli))
+        );
+        assert_eq!(
+            RemoteCommand::from_args(&args(&["eva://unmute"])),
+            Some((RemoteCommand::Mute(false), CommandSource::DeepLink))
+        );
+    }
+
+    #[test]
+    fn deep_links_only_show_and_mute_without_asking() {
+        let link = CommandSource::DeepLink;
+        assert!(!link.needs_confirmation(&RemoteCommand::Show));
+        assert!(!link.needs_confirmation(&RemoteCommand::Mute(true)));
+        assert!(link.needs_confirmation(&RemoteCommand::Mute(false)));
+        assert!(link.needs_confirmation(&RemoteCommand::ToggleListening));
+        assert!(link.needs_confirmation(&RemoteCommand::Ask("hi".to_string())));
+        assert!(link.needs_confirmation(&RemoteCommand::Announce("hi".to_string())));
+        assert!(link.needs_confirmation(&RemoteCommand::Profile("work".to_string())));
+        assert!(!CommandSource::Cli.needs_confirmation(&RemoteCommand::ToggleListening));
+        assert!(!CommandSource::Mqtt.needs_confirmation(&RemoteCommand::Ask("hi".to_string())));
+    }
+}
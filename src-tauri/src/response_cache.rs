@@ -0,0 +1,310 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const CACHE_STORE_FILE: &str = "response_cache.json";
+const CACHE_KEY: &str = "entries";
+
+/// Longer questions are rarely a plain lookup
+const MAX_QUERY_WORDS: usize = 20;
+/// Unit conversions don't change
+const CONVERSION_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+/// Time zone offsets only move with daylight saving, so recheck daily
+const TIME_ZONE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Words that carry no meaning for the lookup
+const FILLER_WORDS: &[&str] = &["hey", "hi", "ok", "okay", "so", "um", "uh", "please", "eva"];
+const FILLER_PREFIXES: &[&str] = &[
+    "can you tell me",
+    "could you tell me",
+    "do you know",
+    "tell me",
+    "i want to know",
+];
+/// Leading words of a question rather than a request ("remind me at 3pm", "add 2 kg of rice")
+const QUESTION_STARTS: &[&str] = &[
+    "what", "what's", "whats", "how", "convert", "will", "is", "does", "weather", "forecast",
+];
+/// Words that point back into the conversation, so the answer isn't standalone
+const CONTEXT_WORDS: &[&str] = &["that", "this", "these", "those", "them", "again", "instead"];
+/// Exchange rates move too fast to cache
+const CURRENCY_WORDS: &[&str] = &[
+    "dollar", "dollars", "euro", "euros", "yen", "pound sterling", "usd", "eur", "gbp", "jpy", "bitcoin",
+    "btc", "currency", "exchange rate",
+];
+const UNIT_WORDS: &[&str] = &[
+    "km", "kilometers", "kilometres", "mile", "miles", "meter", "meters", "metre", "metres", "cm",
+    "centimeters", "mm", "inch", "inches", "foot", "feet", "ft", "yard", "yards", "kg", "kilograms", "gram",
+    "grams", "g", "pound", "pounds", "lb", "lbs", "ounce", "ounces", "oz", "stone", "liter", "liters",
+    "litre", "litres", "ml", "gallon", "gallons", "quart", "quarts", "pint", "pints", "cup", "cups",
+    "tablespoon", "tablespoons", "teaspoon", "teaspoons", "celsius", "fahrenheit", "kelvin", "mph", "kph",
+    "kmh", "acre", "acres", "hectare", "hectares",
+];
+const TIME_ZONE_WORDS: &[&str] = &[
+    "utc", "gmt", "est", "edt", "cst", "cdt", "mst", "mdt", "pst", "pdt", "cet", "cest", "bst", "ist", "jst",
+    "aest", "timezone",
+];
+const WEATHER_WORDS: &[&str] = &[
+    "weather", "forecast", "rain", "raining", "snow", "snowing", "sunny", "humidity", "humid", "windy",
+];
+
+/// Optional cache for standalone factual questions, answered locally instead of by the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseCacheSettings {
+    pub enabled: bool,
+    /// How long a weather answer stays current
+    pub weather_ttl_minutes: u64,
+    /// Oldest answers are dropped beyond this count
+    pub max_entries: usize,
+}
+
+impl Default for ResponseCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weather_ttl_minutes: 30,
+            max_entries: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QueryKind {
+    UnitConversion,
+    TimeZone,
+    Weather,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Normalized question the entry is looked up by
+    pub query: String,
+    pub kind: QueryKind,
+    pub response: String,
+    /// Unix timestamps (seconds)
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub hits: u32,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Lowercase words without punctuation or filler, so rephrasings of the same question meet
+pub fn normalize(query: &str, assistant_name: &str) -> String {
+    let cleaned: String = query
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ':' || c == '.' || c == '\'' { c } else { ' ' })
+        .collect();
+    let assistant_name = assistant_name.to_lowercase();
+    let mut normalized = cleaned
+        .split_whitespace()
+        .map(|word| word.trim_end_matches('.'))
+        .filter(|word| !word.is_empty() && !FILLER_WORDS.contains(word) && *word != assistant_name)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    for prefix in FILLER_PREFIXES {
+        if let Some(rest) = normalized.strip_prefix(prefix) {
+            normalized = rest.trim_start().to_string();
+        }
+    }
+    normalized
+}
+
+fn has_word(words: &[&str], list: &[&str]) -> bool {
+    words.iter().any(|word| list.contains(word))
+}
+
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+}
+
+/// "3pm", "3 pm", "15:30"
+fn has_clock_time(words: &[&str]) -> bool {
+    words.iter().enumerate().any(|(i, word)| {
+        let am_pm = word.strip_suffix("am").or_else(|| word.strip_suffix("pm"));
+        let spaced = (*word == "am" || *word == "pm") && i > 0 && is_digits(&words[i - 1].replace(':', ""));
+        let clock = word.split_once(':').is_some_and(|(h, m)| is_digits(h) && m.len() == 2 && is_digits(m));
+        am_pm.is_some_and(|hour| is_digits(&hour.replace(':', ""))) || spaced || clock
+    })
+}
+
+/// Which kind of standalone factual question this is; `None` for anything conversational
+pub fn classify(normalized: &str) -> Option<QueryKind> {
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let asks = words.first().is_some_and(|first| QUESTION_STARTS.contains(first));
+    if !asks || words.len() > MAX_QUERY_WORDS || has_word(&words, CONTEXT_WORDS) {
+        return None;
+    }
+    if CURRENCY_WORDS.iter().any(|currency| normalized.contains(currency)) {
+        return None;
+    }
+
+    let has_number = words.iter().any(|word| word.chars().any(|c| c.is_ascii_digit()));
+    let converts = words[0] == "convert" || normalized.contains("how many") || normalized.contains(" in ");
+
+    if has_word(&words, WEATHER_WORDS) {
+        Some(QueryKind::Weather)
+    } else if normalized.contains("time difference")
+        || (has_clock_time(&words)
+            && (has_word(&words, TIME_ZONE_WORDS) || normalized.contains("time zone") || normalized.contains(" in ")))
+    {
+        // "What time is it in Tokyo" depends on the current time, so only explicit times count
+        Some(QueryKind::TimeZone)
+    } else if converts && has_word(&words, UNIT_WORDS) && (has_number || normalized.starts_with("how many")) {
+        Some(QueryKind::UnitConversion)
+    } else {
+        None
+    }
+}
+
+fn ttl_secs(kind: QueryKind, settings: &ResponseCacheSettings) -> u64 {
+    match kind {
+        QueryKind::UnitConversion => CONVERSION_TTL_SECS,
+        QueryKind::TimeZone => TIME_ZONE_TTL_SECS,
+        QueryKind::Weather => settings.weather_ttl_minutes * 60,
+    }
+}
+
+fn load(app: &AppHandle) -> Result<Vec<CacheEntry>, String> {
+    let store = app.store(CACHE_STORE_FILE)
+        .map_err(|e| format!("Failed to open response cache: {}", e))?;
+
+    Ok(store.get(CACHE_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn save(app: &AppHandle, entries: &[CacheEntry]) -> Result<(), String> {
+    let store = app.store(CACHE_STORE_FILE)
+        .map_err(|e| format!("Failed to open response cache: {}", e))?;
+
+    let value = serde_json::to_value(entries)
+        .map_err(|e| format!("Failed to serialize response cache: {}", e))?;
+
+    store.set(CACHE_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save response cache: {}", e))
+}
+
+/// A cached answer to `query`, if caching is on and one is still fresh
+#[tauri::command]
+pub async fn lookup_cached_response(
+    query: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<Option<CacheEntry>, String> {
+    let settings = state.lock().await;
+    if !settings.response_cache.enabled {
+        return Ok(None);
+    }
+    let normalized = normalize(&query, &settings.identity.assistant_name);
+    drop(settings);
+    if classify(&normalized).is_none() {
+        return Ok(None);
+    }
+
+    let now = now_secs();
+    let mut entries = load(&app)?;
+    let before = entries.len();
+    entries.retain(|entry| entry.expires_at > now);
+    let hit = entries.iter_mut().find(|entry| entry.query == normalized).map(|entry| {
+        entry.hits += 1;
+        entry.clone()
+    });
+    if hit.is_some() || entries.len() != before {
+        save(&app, &entries)?;
+    }
+
+    if let Some(entry) = &hit {
+        log::info!("⚡ Answered \"{}\" from the response cache ({:?})", entry.query, entry.kind);
+    }
+    Ok(hit)
+}
+
+/// Cache `response` as the answer to `query` if it's a standalone factual question
+#[tauri::command]
+pub async fn remember_response(
+    query: String,
+    response: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let settings = state.lock().await;
+    let cache_settings = settings.response_cache.clone();
+    let normalized = normalize(&query, &settings.identity.assistant_name);
+    drop(settings);
+
+    let response = response.trim();
+    if !cache_settings.enabled || response.is_empty() {
+        return Ok(false);
+    }
+    let Some(kind) = classify(&normalized) else {
+        return Ok(false);
+    };
+
+    let now = now_secs();
+    let mut entries = load(&app)?;
+    entries.retain(|entry| entry.expires_at > now && entry.query != normalized);
+    entries.push(CacheEntry {
+        query: normalized,
+        kind,
+        response: response.to_string(),
+        created_at: now,
+        expires_at: now + ttl_secs(kind, &cache_settings),
+        hits: 0,
+    });
+    if entries.len() > cache_settings.max_entries {
+        let excess = entries.len() - cache_settings.max_entries;
+        entries.drain(..excess);
+    }
+    save(&app, &entries)?;
+
+    log::info!("⚡ Cached a {:?} answer ({} entries)", kind, entries.len());
+    Ok(true)
+}
+
+/// Fresh cache entries, newest first
+#[tauri::command]
+pub async fn list_cached_responses(app: AppHandle) -> Result<Vec<CacheEntry>, String> {
+    let now = now_secs();
+    let mut entries: Vec<CacheEntry> = load(&app)?
+        .into_iter()
+        .filter(|entry| entry.expires_at > now)
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn clear_response_cache(app: AppHandle) -> Result<String, String> {
+    save(&app, &[])?;
+    log::info!("⚡ Response cache cleared");
+    Ok("Response cache cleared".to_string())
+}
+
+#[tauri::command]
+pub async fn set_response_cache_settings(
+    settings: ResponseCacheSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let enabled = settings.enabled;
+    let mut current = state.lock().await;
+    current.response_cache = settings;
+    current.save(&app)?;
+    drop(current);
+
+    log::info!("⚡ Response cache {}", if enabled { "enabled" } else { "disabled" });
+    Ok(format!("Response cache {}", if enabled { "enabled" } else { "disabled" }))
+}
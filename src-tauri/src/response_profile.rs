@@ -0,0 +1,89 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// How much Eva says per response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResponseProfile {
+    Brief,
+    #[default]
+    Normal,
+    Detailed,
+}
+
+impl ResponseProfile {
+    /// Cap sent as `max_response_output_tokens`; `None` means no limit ("inf")
+    pub fn max_output_tokens(&self) -> Option<u32> {
+        match self {
+            ResponseProfile::Brief => Some(150),
+            ResponseProfile::Normal => Some(600),
+            ResponseProfile::Detailed => None,
+        }
+    }
+
+    /// Appended to the persona instructions
+    pub fn style_hint(&self) -> &'static str {
+        match self {
+            ResponseProfile::Brief => {
+                "Answer in one or two short sentences. Skip pleasantries and only elaborate when asked."
+            }
+            ResponseProfile::Normal => "",
+            ResponseProfile::Detailed => {
+                "Give thorough answers with explanations, examples, and relevant context."
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseProfileSettings {
+    pub profile: ResponseProfile,
+}
+
+/// Event payload for `response-profile-changed`, also returned by the commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseProfileEvent {
+    pub profile: ResponseProfile,
+    pub max_output_tokens: Option<u32>,
+    pub style_hint: String,
+}
+
+impl From<ResponseProfile> for ResponseProfileEvent {
+    fn from(profile: ResponseProfile) -> Self {
+        Self {
+            profile,
+            max_output_tokens: profile.max_output_tokens(),
+            style_hint: profile.style_hint().to_string(),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_response_profile(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<ResponseProfileEvent, String> {
+    Ok(state.lock().await.response_profile.profile.into())
+}
+
+/// Switch the profile; the frontend pushes it to the live session with `session.update`
+#[tauri::command]
+pub async fn set_response_profile(
+    profile: ResponseProfile,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<ResponseProfileEvent, String> {
+    let mut settings = state.lock().await;
+    settings.response_profile.profile = profile;
+    settings.save(&app)?;
+    drop(settings);
+
+    let event = ResponseProfileEvent::from(profile);
+    if let Err(e) = app.emit("response-profile-changed", &event) {
+        log::error!("Failed to emit response profile change: {}", e);
+    }
+
+    log::info!("💬 Response profile set to {:?}", profile);
+    Ok(event)
+}
@@ -0,0 +1,126 @@
+use crate::secrets;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+pub const PICOVOICE_KEY_SECRET: &str = "picovoice-access-key";
+pub const OPENAI_KEY_SECRET: &str = "openai-api-key";
+
+/// Environment variables that used to be the way to configure keys: (variable, keychain secret, label)
+const ENV_SECRETS: &[(&str, &str, &str)] = &[
+    ("PV_ACCESS_KEY", PICOVOICE_KEY_SECRET, "Picovoice access key"),
+    ("OPENAI_API_KEY", OPENAI_KEY_SECRET, "OpenAI API key"),
+];
+
+/// Found at startup, waiting for the user to answer the prompt
+static PENDING: Mutex<Vec<EnvSecretOffer>> = Mutex::new(Vec::new());
+
+/// Keys the user chose to keep in the environment; they aren't offered again
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretMigrationSettings {
+    pub declined: Vec<String>,
+}
+
+/// An environment variable Eva offers to move into the keychain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvSecretOffer {
+    pub env_var: String,
+    pub label: String,
+    /// The keychain already holds a different value, which would be replaced
+    pub replaces_stored: bool,
+}
+
+fn env_value(var: &str) -> Option<String> {
+    std::env::var(var)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn secret_for(var: &str) -> Option<&'static str> {
+    ENV_SECRETS
+        .iter()
+        .find(|(env_var, _, _)| *env_var == var)
+        .map(|(_, secret, _)| *secret)
+}
+
+/// Look for keys set in the environment that the keychain doesn't have yet. Runs in setup;
+/// the frontend asks the user once it's up.
+pub fn detect(settings: &SecretMigrationSettings) {
+    let offers: Vec<EnvSecretOffer> = ENV_SECRETS
+        .iter()
+        .filter(|(var, _, _)| !settings.declined.iter().any(|declined| declined == var))
+        .filter_map(|(var, secret, label)| {
+            let value = env_value(var)?;
+            let stored = secrets::get(secret);
+            (stored.as_deref() != Some(value.as_str())).then(|| EnvSecretOffer {
+                env_var: var.to_string(),
+                label: label.to_string(),
+                replaces_stored: stored.is_some(),
+            })
+        })
+        .collect();
+
+    if !offers.is_empty() {
+        let vars: Vec<&str> = offers.iter().map(|offer| offer.env_var.as_str()).collect();
+        log::info!("🔐 Found {} in the environment; offering to move to the keychain", vars.join(", "));
+    }
+    *PENDING.lock().unwrap_or_else(|e| e.into_inner()) = offers;
+}
+
+#[tauri::command]
+pub async fn get_env_secret_offers() -> Result<Vec<EnvSecretOffer>, String> {
+    Ok(PENDING.lock().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+/// Store the offered keys in the keychain so the environment variables can go, or remember
+/// not to offer them again
+#[tauri::command]
+pub async fn answer_env_secret_offers(
+    accept: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let offers = PENDING.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if offers.is_empty() {
+        return Ok("No keys to move".to_string());
+    }
+    let labels: Vec<&str> = offers.iter().map(|offer| offer.label.as_str()).collect();
+
+    if accept {
+        for offer in &offers {
+            let (Some(secret), Some(value)) = (secret_for(&offer.env_var), env_value(&offer.env_var)) else {
+                continue;
+            };
+            secrets::set(secret, &value)?;
+        }
+    } else {
+        let mut settings = state.lock().await;
+        settings
+            .secret_migration
+            .declined
+            .extend(offers.iter().map(|offer| offer.env_var.clone()));
+        settings.save(&app)?;
+    }
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).clear();
+
+    if accept {
+        log::info!("🔐 Moved {} from the environment to the keychain", labels.join(" and "));
+        Ok(format!(
+            "Saved the {} to the keychain; the environment variables are no longer needed",
+            labels.join(" and ")
+        ))
+    } else {
+        log::info!("🔐 Keeping {} in the environment", labels.join(" and "));
+        let identity = state.lock().await.identity.clone();
+        Ok(identity.render(&format!("{{name}} will keep reading the {} from the environment", labels.join(" and "))))
+    }
+}
+
+/// OpenAI key from the keychain, else `OPENAI_API_KEY`; the frontend uses it when no key was entered in the UI
+#[tauri::command]
+pub async fn get_stored_openai_key() -> Result<Option<String>, String> {
+    Ok(secrets::get(OPENAI_KEY_SECRET).or_else(|| env_value("OPENAI_API_KEY")))
+}
@@ -0,0 +1,312 @@
+use crate::settings::SettingsState;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Keychain service every Eva secret is stored under; integration credentials live
+/// here rather than in the settings store
+const KEYCHAIN_SERVICE: &str = "eva-desktop";
+/// Encrypted fallback store in the app data directory
+const SECRETS_FILE: &str = "secrets.enc";
+const NONCE_LEN: usize = 12;
+
+static BACKEND: Mutex<SecretBackend> = Mutex::new(SecretBackend::Auto);
+/// Set by `restore`; the file store is unavailable until then
+static FILE_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// Held across each read-modify-write of the secrets file, so concurrent saves don't lose entries
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Where secrets are kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretBackend {
+    /// The system keychain, or the encrypted file when there is none (Linux without a
+    /// Secret Service, e.g. headless or minimal desktops)
+    #[default]
+    Auto,
+    /// Only the system keychain
+    Keychain,
+    /// Only the encrypted file
+    File,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretStoreSettings {
+    pub backend: SecretBackend,
+}
+
+/// Returned by `get_secret_store`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretStoreStatus {
+    pub backend: SecretBackend,
+    pub keychain_available: bool,
+}
+
+fn backend() -> SecretBackend {
+    *BACKEND.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Pick the backend and locate the file store; runs in setup before anything reads a secret
+pub fn restore(app: &AppHandle, settings: &SecretStoreSettings) {
+    *BACKEND.lock().unwrap_or_else(|e| e.into_inner()) = settings.backend;
+    match app.path().app_data_dir() {
+        Ok(dir) => *FILE_PATH.lock().unwrap_or_else(|e| e.into_inner()) = Some(dir.join(SECRETS_FILE)),
+        Err(e) => log::warn!("No app data directory for the secrets file: {}", e),
+    }
+}
+
+fn entry(name: &str) -> Result<keyring::Entry, keyring::Error> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, name)
+}
+
+/// The keychain itself failed, as opposed to the secret not being in it
+fn keychain_unavailable(error: &keyring::Error) -> bool {
+    matches!(error, keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_))
+}
+
+fn keychain_available() -> bool {
+    match entry("availability-check").and_then(|entry| entry.get_password()) {
+        Ok(_) | Err(keyring::Error::NoEntry) => true,
+        Err(e) => !keychain_unavailable(&e),
+    }
+}
+
+/// Derived from the machine and user, so the file is useless copied elsewhere. It keeps secrets
+/// out of plain sight; anyone who can run code as this user can still read them.
+fn file_key() -> Key<Aes256Gcm> {
+    let machine_id = ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| std::fs::read_to_string(path).ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .unwrap_or_default();
+    let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(KEYCHAIN_SERVICE.as_bytes());
+    hasher.update(machine_id.trim().as_bytes());
+    hasher.update(user.as_bytes());
+    hasher.finalize()
+}
+
+fn file_path() -> Result<PathBuf, String> {
+    FILE_PATH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .ok_or_else(|| "The secrets file isn't available yet".to_string())
+}
+
+/// Secret name to base64 of nonce followed by ciphertext
+fn read_file() -> Result<BTreeMap<String, String>, String> {
+    let path = file_path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let contents = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read secrets file: {}", e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse secrets file: {}", e))
+}
+
+/// Written to a temporary file and renamed over the old one, so a crash never leaves it half written
+fn write_file(secrets: &BTreeMap<String, String>) -> Result<(), String> {
+    let path = file_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let contents = serde_json::to_string_pretty(secrets).map_err(|e| e.to_string())?;
+
+    let temp_path = path.with_extension("enc.tmp");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(&temp_path).and_then(|mut file| {
+        use std::io::Write;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| std::fs::rename(&temp_path, &path)) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to write secrets file: {}", e));
+    }
+    Ok(())
+}
+
+fn file_get(name: &str) -> Option<String> {
+    let encoded = read_file().ok()?.remove(name)?;
+    let bytes = STANDARD.decode(encoded).ok()?;
+    if bytes.len() <= NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    match Aes256Gcm::new(&file_key()).decrypt(Nonce::from_slice(nonce), ciphertext) {
+        Ok(plaintext) => String::from_utf8(plaintext).ok(),
+        Err(_) => {
+            log::warn!("🔐 Can't decrypt {} from the secrets file; was it copied from another machine?", name);
+            None
+        }
+    }
+}
+
+fn file_set(name: &str, value: &str) -> Result<(), String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = Aes256Gcm::new(&file_key())
+        .encrypt(&nonce, value.as_bytes())
+        .map_err(|e| format!("Failed to encrypt {}: {}", name, e))?;
+
+    let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut secrets = read_file()?;
+    secrets.insert(name.to_string(), STANDARD.encode([nonce.as_slice(), &ciphertext].concat()));
+    write_file(&secrets)?;
+    log::info!("🔐 {} stored in encrypted secrets file", name);
+    Ok(())
+}
+
+/// `true` if there was one to remove
+fn file_delete(name: &str) -> bool {
+    let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Ok(mut secrets) = read_file() else {
+        return false;
+    };
+    if secrets.remove(name).is_none() {
+        return false;
+    }
+    match write_file(&secrets) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Failed to remove {} from secrets file: {}", name, e);
+            false
+        }
+    }
+}
+
+fn keychain_get(name: &str) -> Option<String> {
+    match entry(name).and_then(|entry| entry.get_password()) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            log::debug!("No {} in keychain: {}", name, e);
+            None
+        }
+    }
+}
+
+/// `None` if the secret was never stored or can't be read. Falls back to the other store, which
+/// holds keys saved before the backend changed (or while the keychain was unavailable).
+pub fn get(name: &str) -> Option<String> {
+    if backend() == SecretBackend::File {
+        return file_get(name).or_else(|| keychain_get(name));
+    }
+    keychain_get(name).or_else(|| file_get(name))
+}
+
+/// An empty value removes the secret
+pub fn set(name: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        delete(name);
+        return Ok(());
+    }
+
+    let backend = backend();
+    if backend == SecretBackend::File {
+        file_set(name, value)?;
+        // Moved out of the keychain it was saved to before the switch
+        if entry(name).and_then(|entry| entry.delete_credential()).is_ok() {
+            log::info!("🔐 {} removed from system keychain", name);
+        }
+        return Ok(());
+    }
+
+    match entry(name).and_then(|entry| entry.set_password(value)) {
+        Ok(()) => {
+            log::info!("🔐 {} stored in system keychain", name);
+            // Don't leave an older copy behind from when the keychain was unavailable
+            file_delete(name);
+            Ok(())
+        }
+        Err(e) if backend == SecretBackend::Auto && keychain_unavailable(&e) => {
+            log::warn!("🔐 System keychain unavailable ({}); using the encrypted secrets file", e);
+            file_set(name, value)
+        }
+        Err(e) => Err(format!("Failed to store {} in keychain: {}", name, e)),
+    }
+}
+
+pub fn delete(name: &str) {
+    if backend() != SecretBackend::File {
+        match entry(name).and_then(|entry| entry.delete_credential()) {
+            Ok(()) => log::info!("🔐 {} removed from system keychain", name),
+            Err(e) => log::debug!("No {} to remove from keychain: {}", name, e),
+        }
+    }
+    if file_delete(name) {
+        log::info!("🔐 {} removed from encrypted secrets file", name);
+    }
+}
+
+#[tauri::command]
+pub async fn get_secret_store(state: tauri::State<'_, SettingsState>) -> Result<SecretStoreStatus, String> {
+    let backend = state.lock().await.secrets.backend;
+    let keychain_available = tokio::task::spawn_blocking(keychain_available).await.unwrap_or(false);
+    Ok(SecretStoreStatus { backend, keychain_available })
+}
+
+/// Keys already saved are still read from the store they were saved to; saving one again moves
+/// it to the new backend
+#[tauri::command]
+pub async fn set_secret_backend(
+    backend: SecretBackend,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.secrets.backend = backend;
+    settings.save(&app)?;
+    drop(settings);
+
+    *BACKEND.lock().unwrap_or_else(|e| e.into_inner()) = backend;
+    log::info!("🔐 Secret store set to {:?}", backend);
+    Ok(match backend {
+        SecretBackend::Auto => "Keys go to the system keychain, or the encrypted file without one",
+        SecretBackend::Keychain => "Keys go to the system keychain only",
+        SecretBackend::File => "Keys go to the encrypted file only",
+    }
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_store_round_trips_secrets() {
+        let path = std::env::temp_dir().join(format!("eva-secrets-test-{}.enc", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        *FILE_PATH.lock().unwrap() = Some(path.clone());
+
+        assert_eq!(file_get("api_key"), None);
+        file_set("api_key", "sk-first").unwrap();
+        file_set("other_key", "kept").unwrap();
+        file_set("api_key", "sk-second").unwrap();
+        assert_eq!(file_get("api_key").as_deref(), Some("sk-second"));
+
+        // Encrypted on disk, not stored as written
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("sk-second"));
+
+        assert!(file_delete("api_key"));
+        assert!(!file_delete("api_key"));
+        assert_eq!(file_get("api_key"), None);
+        assert_eq!(file_get("other_key").as_deref(), Some("kept"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
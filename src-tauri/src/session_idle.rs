@@ -0,0 +1,108 @@
+use crate::proactive;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const MAX_IDLE_MINUTES: u64 = 24 * 60;
+
+/// Bumped on every activity so only the latest timer can fire
+static ACTIVITY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Disconnect the Realtime session when Eva hasn't been used for a while.
+///
+/// The next wake word reconnects; the pre-wake buffer holds what the user says meanwhile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdleDisconnectSettings {
+    pub enabled: bool,
+    pub idle_minutes: u64,
+}
+
+impl Default for IdleDisconnectSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_minutes: 10,
+        }
+    }
+}
+
+/// Event payload for `session-idle-timeout`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionIdleEvent {
+    pub idle_minutes: u64,
+}
+
+/// Restart the idle timer, e.g. on connect, a wake word, or a response
+pub async fn touch(app: &AppHandle) {
+    proactive::note_activity();
+    let generation = ACTIVITY_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+
+    let settings = app.state::<SettingsState>().lock().await.idle_disconnect.clone();
+    if !settings.enabled {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(settings.idle_minutes * 60)).await;
+        if ACTIVITY_GENERATION.load(Ordering::Relaxed) != generation {
+            return;
+        }
+
+        log::info!("💤 No activity for {} minutes, disconnecting the session", settings.idle_minutes);
+        let event = SessionIdleEvent { idle_minutes: settings.idle_minutes };
+        if let Err(e) = app.emit("session-idle-timeout", &event) {
+            log::error!("Failed to emit session idle event: {}", e);
+        }
+    });
+}
+
+/// `touch` for callers outside the async runtime, such as the audio processing thread
+pub fn spawn_touch(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { touch(&app).await });
+}
+
+#[tauri::command]
+pub async fn touch_session_activity(app: AppHandle) -> Result<(), String> {
+    touch(&app).await;
+    Ok(())
+}
+
+/// The session disconnected; cancel any pending idle timeout
+#[tauri::command]
+pub async fn stop_session_idle_timer() -> Result<(), String> {
+    ACTIVITY_GENERATION.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_idle_disconnect(
+    enabled: bool,
+    idle_minutes: u64,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if idle_minutes == 0 || idle_minutes > MAX_IDLE_MINUTES {
+        return Err(format!("Idle timeout must be between 1 and {} minutes", MAX_IDLE_MINUTES));
+    }
+
+    let mut settings = state.lock().await;
+    settings.idle_disconnect.enabled = enabled;
+    settings.idle_disconnect.idle_minutes = idle_minutes;
+    settings.save(&app)?;
+    drop(settings);
+
+    // Re-arm with the new timeout, or cancel the pending one
+    touch(&app).await;
+
+    log::info!("💤 Idle disconnect {} ({} min)", if enabled { "enabled" } else { "disabled" }, idle_minutes);
+    Ok(if enabled {
+        format!("Session will disconnect after {} idle minutes", idle_minutes)
+    } else {
+        "Idle disconnect disabled".to_string()
+    })
+}
@@ -0,0 +1,171 @@
+use crate::audio::AudioInputSettings;
+use crate::audio_format::AudioFormatSettings;
+use crate::autostart::AutostartSettings;
+use crate::avatar::AvatarSettings;
+use crate::call_detection::CallDetectionSettings;
+use crate::chat_mode::ChatModeSettings;
+use crate::conversations::ConversationSettings;
+use crate::debug_audio::DebugAudioSettings;
+use crate::dictation::DictationSettings;
+use crate::dnd::DndSettings;
+use crate::ducking::DuckingSettings;
+use crate::earcons::EarconSettings;
+use crate::followup::FollowupSettings;
+use crate::hot_reload;
+use crate::identity::IdentitySettings;
+use crate::integrations::mqtt::MqttSettings;
+use crate::intents::IntentSettings;
+use crate::language::LanguageSettings;
+use crate::llm_endpoint::LlmEndpointSettings;
+use crate::memory::MemorySettings;
+use crate::metrics::MetricsSettings;
+use crate::notifications::NotificationSettings;
+use crate::overlay::OverlaySettings;
+use crate::persona::PersonaSettings;
+use crate::playback::PlaybackSettings;
+use crate::power::PowerSettings;
+use crate::privacy::PrivacySettings;
+use crate::proactive::ProactiveSettings;
+use crate::profiles::ProfileSettings;
+use crate::proxy::ProxySettings;
+use crate::quiet_hours::QuietHoursSettings;
+use crate::push_to_talk::PushToTalkSettings;
+use crate::response_cache::ResponseCacheSettings;
+use crate::response_profile::ResponseProfileSettings;
+use crate::secret_migration::SecretMigrationSettings;
+use crate::secrets::SecretStoreSettings;
+use crate::session_idle::IdleDisconnectSettings;
+use crate::sleep_inhibit::SleepInhibitSettings;
+use crate::speaker_id::SpeakerIdSettings;
+use crate::tools::actions::ActionSettings;
+use crate::tools::plugins::PluginSettings;
+use crate::tts::TtsSettings;
+use crate::turn_detection::TurnDetectionSettings;
+use crate::usage::UsageSettings;
+use crate::users::UserSettings;
+use crate::wake_cooldown::WakeCooldownSettings;
+use crate::wake_word::WakeWordModelSettings;
+use crate::whisper_stt::SttSettings;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+pub const SETTINGS_STORE_FILE: &str = "settings.json";
+const SETTINGS_KEY: &str = "settings";
+
+/// Shared settings handle managed by Tauri
+pub type SettingsState = Arc<tokio::sync::Mutex<EvaSettings>>;
+
+/// User settings persisted in the plugin store.
+///
+/// Every section defaults independently so settings written by older builds keep loading.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvaSettings {
+    pub identity: IdentitySettings,
+    pub call_detection: CallDetectionSettings,
+    pub push_to_talk: PushToTalkSettings,
+    pub stt: SttSettings,
+    pub tts: TtsSettings,
+    pub llm_endpoint: LlmEndpointSettings,
+    pub persona: PersonaSettings,
+    pub memory: MemorySettings,
+    pub usage: UsageSettings,
+    pub privacy: PrivacySettings,
+    pub quiet_hours: QuietHoursSettings,
+    pub earcons: EarconSettings,
+    pub chat_mode: ChatModeSettings,
+    pub debug_audio: DebugAudioSettings,
+    pub conversations: ConversationSettings,
+    pub speaker_id: SpeakerIdSettings,
+    pub followup: FollowupSettings,
+    pub response_profile: ResponseProfileSettings,
+    pub language: LanguageSettings,
+    pub wake_word: WakeWordModelSettings,
+    pub audio_input: AudioInputSettings,
+    pub playback: PlaybackSettings,
+    pub ducking: DuckingSettings,
+    pub idle_disconnect: IdleDisconnectSettings,
+    pub notifications: NotificationSettings,
+    pub autostart: AutostartSettings,
+    pub mqtt: MqttSettings,
+    pub actions: ActionSettings,
+    pub plugins: PluginSettings,
+    pub avatar: AvatarSettings,
+    pub overlay: OverlaySettings,
+    pub proactive: ProactiveSettings,
+    pub dnd: DndSettings,
+    pub response_cache: ResponseCacheSettings,
+    pub intents: IntentSettings,
+    pub metrics: MetricsSettings,
+    pub power: PowerSettings,
+    pub turn_detection: TurnDetectionSettings,
+    pub wake_cooldown: WakeCooldownSettings,
+    pub proxy: ProxySettings,
+    pub audio_format: AudioFormatSettings,
+    pub profiles: ProfileSettings,
+    pub users: UserSettings,
+    pub secret_migration: SecretMigrationSettings,
+    pub secrets: SecretStoreSettings,
+    pub sleep_inhibit: SleepInhibitSettings,
+    pub dictation: DictationSettings,
+}
+
+impl EvaSettings {
+    /// Load settings from the store, falling back to defaults on missing or invalid data
+    pub fn load(app: &AppHandle) -> Self {
+        let store = match app.store(SETTINGS_STORE_FILE) {
+            Ok(store) => store,
+            Err(e) => {
+                log::warn!("Failed to open settings store, using defaults: {}", e);
+                return Self::default();
+            }
+        };
+
+        match store.get(SETTINGS_KEY) {
+            Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+                log::warn!("Invalid settings in store, using defaults: {}", e);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    /// Persist settings to the store
+    pub fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app.store(SETTINGS_STORE_FILE)
+            .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+        let value = serde_json::to_value(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+        store.set(SETTINGS_KEY, value);
+        store.save()
+            .map_err(|e| format!("Failed to save settings: {}", e))
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<EvaSettings, String> {
+    Ok(state.lock().await.clone())
+}
+
+#[tauri::command]
+pub async fn update_settings(
+    settings: EvaSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    log::info!("Updating settings");
+
+    settings.save(&app)?;
+    let old = std::mem::replace(&mut *state.lock().await, settings.clone());
+
+    // Running services pick up the changes without stopping Eva
+    hot_reload::apply(&app, &old, &settings).await;
+
+    Ok("Settings saved".to_string())
+}
@@ -0,0 +1,18 @@
+use crate::autostart;
+use crate::remote::{self, RemoteCommand};
+use crate::tray;
+
+/// A second launch only forwards its arguments here, so it never opens a
+/// competing audio stream; this runs in the first instance.
+pub fn handle_second_launch(app: &tauri::AppHandle, args: Vec<String>) {
+    // args[0] is the executable
+    let args = args.get(1..).unwrap_or_default();
+    log::info!("🪟 Second launch forwarded to the running instance: {:?}", args);
+
+    match RemoteCommand::from_args(args) {
+        Some((command, source)) => remote::dispatch(app, command, source),
+        // Logged in again while Eva was already running; nothing to do
+        None if args.iter().any(|arg| arg == autostart::AUTOSTART_ARG) => {}
+        None => tray::show_main_window(app),
+    }
+}
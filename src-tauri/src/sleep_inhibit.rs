@@ -0,0 +1,268 @@
+use crate::identity;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// Rendered with the assistant's configured name
+const INHIBIT_REASON: &str = "{name} is in a conversation";
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static STATE: Mutex<InhibitState> = Mutex::new(InhibitState { reasons: Vec::new(), inhibitor: None });
+
+/// Keep the machine from sleeping while a conversation or transcription is running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SleepInhibitSettings {
+    pub enabled: bool,
+}
+
+impl Default for SleepInhibitSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// What is keeping the machine awake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InhibitReason {
+    /// The Realtime session is connected
+    Conversation,
+    /// System audio is being captured, e.g. a meeting to transcribe
+    Transcription,
+}
+
+/// Returned by `get_sleep_inhibit`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SleepInhibitStatus {
+    pub enabled: bool,
+    pub reasons: Vec<InhibitReason>,
+    /// An inhibitor is actually held; false when the platform refused one
+    pub active: bool,
+}
+
+struct InhibitState {
+    reasons: Vec<InhibitReason>,
+    inhibitor: Option<platform::Inhibitor>,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::{c_char, c_void, CString};
+
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+    const IOPM_ASSERTION_LEVEL_ON: u32 = 255;
+    const IO_RETURN_SUCCESS: i32 = 0;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(alloc: *const c_void, c_str: *const c_char, encoding: u32) -> *const c_void;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPMAssertionCreateWithName(
+            assertion_type: *const c_void,
+            level: u32,
+            name: *const c_void,
+            assertion_id: *mut u32,
+        ) -> i32;
+        fn IOPMAssertionRelease(assertion_id: u32) -> i32;
+    }
+
+    /// An IOPMAssertion keeping idle sleep away
+    pub struct Inhibitor(u32);
+
+    fn cf_string(text: &str) -> Option<*const c_void> {
+        let text = CString::new(text).ok()?;
+        let string = unsafe { CFStringCreateWithCString(std::ptr::null(), text.as_ptr(), CF_STRING_ENCODING_UTF8) };
+        (!string.is_null()).then_some(string)
+    }
+
+    pub fn acquire(_who: &str, reason: &str) -> Result<Inhibitor, String> {
+        let assertion_type = cf_string("PreventUserIdleSystemSleep").ok_or("Failed to create assertion type")?;
+        let Some(name) = cf_string(reason) else {
+            unsafe { CFRelease(assertion_type) };
+            return Err("Failed to create assertion name".to_string());
+        };
+
+        let mut assertion_id = 0u32;
+        let result =
+            unsafe { IOPMAssertionCreateWithName(assertion_type, IOPM_ASSERTION_LEVEL_ON, name, &mut assertion_id) };
+        unsafe {
+            CFRelease(name);
+            CFRelease(assertion_type);
+        }
+
+        if result == IO_RETURN_SUCCESS {
+            Ok(Inhibitor(assertion_id))
+        } else {
+            Err(format!("IOPMAssertionCreateWithName failed ({:#x})", result))
+        }
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            unsafe { IOPMAssertionRelease(self.0) };
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::sync::mpsc;
+    use windows::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED};
+
+    /// The execution state belongs to the thread that set it, so a thread holds it until released
+    pub struct Inhibitor {
+        release: Option<mpsc::Sender<()>>,
+        thread: Option<std::thread::JoinHandle<()>>,
+    }
+
+    pub fn acquire(_who: &str, _reason: &str) -> Result<Inhibitor, String> {
+        let (release, released) = mpsc::channel::<()>();
+        let (ready, acquired) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            let previous = unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) };
+            let _ = ready.send(previous.0 != 0);
+            // Until the sender is dropped
+            let _ = released.recv();
+            unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+        });
+
+        if acquired.recv().unwrap_or(false) {
+            Ok(Inhibitor { release: Some(release), thread: Some(thread) })
+        } else {
+            Err("SetThreadExecutionState failed".to_string())
+        }
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            drop(self.release.take());
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use std::process::{Child, Command, Stdio};
+
+    /// A `systemd-inhibit` child holding a logind sleep and idle lock for as long as it runs
+    pub struct Inhibitor(Child);
+
+    pub fn acquire(who: &str, reason: &str) -> Result<Inhibitor, String> {
+        let who = format!("--who={}", who);
+        let why = format!("--why={}", reason);
+        Command::new("systemd-inhibit")
+            .args(["--what=sleep:idle", who.as_str(), why.as_str(), "--mode=block", "sleep", "infinity"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map(Inhibitor)
+            .map_err(|e| format!("Failed to run systemd-inhibit: {}", e))
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+}
+
+fn lock_state() -> std::sync::MutexGuard<'static, InhibitState> {
+    STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Take or drop the inhibitor to match the current reasons
+fn update(state: &mut InhibitState) {
+    let wanted = ENABLED.load(Ordering::Relaxed) && !state.reasons.is_empty();
+    if wanted && state.inhibitor.is_none() {
+        let identity = identity::current();
+        match platform::acquire(&identity.assistant_name, &identity.render(INHIBIT_REASON)) {
+            Ok(inhibitor) => {
+                state.inhibitor = Some(inhibitor);
+                log::info!("☕ Keeping the machine awake ({:?})", state.reasons);
+            }
+            Err(e) => log::warn!("☕ Couldn't keep the machine awake: {}", e),
+        }
+    } else if !wanted && state.inhibitor.take().is_some() {
+        log::info!("☕ Machine may sleep again");
+    }
+}
+
+pub fn restore(settings: &SleepInhibitSettings) {
+    ENABLED.store(settings.enabled, Ordering::Relaxed);
+}
+
+/// Keep the machine awake for `reason` until it's released
+pub fn hold(reason: InhibitReason) {
+    let mut state = lock_state();
+    if !state.reasons.contains(&reason) {
+        state.reasons.push(reason);
+    }
+    update(&mut state);
+}
+
+pub fn release(reason: InhibitReason) {
+    let mut state = lock_state();
+    state.reasons.retain(|held| *held != reason);
+    update(&mut state);
+}
+
+/// Drop the inhibitor whatever holds it, on exit
+pub fn release_all() {
+    let mut state = lock_state();
+    state.reasons.clear();
+    update(&mut state);
+}
+
+/// `hold` or `release` as `active` says
+pub fn set(reason: InhibitReason, active: bool) {
+    if active {
+        hold(reason);
+    } else {
+        release(reason);
+    }
+}
+
+#[tauri::command]
+pub async fn get_sleep_inhibit() -> Result<SleepInhibitStatus, String> {
+    let state = lock_state();
+    Ok(SleepInhibitStatus {
+        enabled: ENABLED.load(Ordering::Relaxed),
+        reasons: state.reasons.clone(),
+        active: state.inhibitor.is_some(),
+    })
+}
+
+#[tauri::command]
+pub async fn set_sleep_inhibit(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.sleep_inhibit.enabled = enabled;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    ENABLED.store(enabled, Ordering::Relaxed);
+    update(&mut lock_state());
+
+    log::info!("☕ Sleep inhibit {}", if enabled { "enabled" } else { "disabled" });
+    Ok(if enabled {
+        identity.render("The computer stays awake while {name} is in a conversation")
+    } else {
+        "The computer may sleep during conversations".to_string()
+    })
+}
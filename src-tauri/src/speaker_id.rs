@@ -0,0 +1,454 @@
+use crate::pre_wake::{self, PRE_WAKE_SAMPLE_RATE};
+use crate::settings::SettingsState;
+use crate::users;
+use crate::whisper_stt::resample_for_whisper;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+pub const DEFAULT_SPEAKER_MODEL_PATH: &str = "models/speaker-embedding.onnx";
+const PROFILE_STORE_FILE: &str = "voice_profile.json";
+const PROFILE_KEY: &str = "profile";
+/// Shorter clips give unreliable embeddings
+const MIN_ENROLLMENT_SECS: f32 = 3.0;
+
+/// Speaker verification settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeakerIdSettings {
+    /// Ignore wake words spoken by anyone but the enrolled voice
+    pub voice_lock: bool,
+    /// ONNX speaker-embedding model taking 16kHz mono audio shaped `[1, samples]`
+    pub model_path: String,
+    /// Minimum cosine similarity to the enrolled voice
+    pub threshold: f32,
+}
+
+impl Default for SpeakerIdSettings {
+    fn default() -> Self {
+        Self {
+            voice_lock: false,
+            model_path: DEFAULT_SPEAKER_MODEL_PATH.to_string(),
+            threshold: 0.65,
+        }
+    }
+}
+
+/// Enrolled voice, kept in its own store (one per user) so it can be wiped independently
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceProfile {
+    /// L2-normalized mean embedding of all enrollment clips
+    pub embedding: Vec<f32>,
+    pub enrollments: u32,
+    /// Unix timestamp (seconds) of the last enrollment
+    pub updated_at: u64,
+}
+
+impl VoiceProfile {
+    fn load(app: &AppHandle) -> Result<Option<Self>, String> {
+        Self::load_for(app, &users::active_user())
+    }
+
+    fn load_for(app: &AppHandle, user: &str) -> Result<Option<Self>, String> {
+        let store = app.store(users::user_file(user, PROFILE_STORE_FILE))
+            .map_err(|e| format!("Failed to open voice profile store: {}", e))?;
+
+        Ok(store.get(PROFILE_KEY)
+            .and_then(|value| serde_json::from_value::<Self>(value).ok())
+            .filter(|profile| !profile.embedding.is_empty()))
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let store = app.store(users::scoped(PROFILE_STORE_FILE))
+            .map_err(|e| format!("Failed to open voice profile store: {}", e))?;
+
+        let value = serde_json::to_value(self)
+            .map_err(|e| format!("Failed to serialize voice profile: {}", e))?;
+
+        store.set(PROFILE_KEY, value);
+        store.save()
+            .map_err(|e| format!("Failed to save voice profile: {}", e))
+    }
+}
+
+/// Returned by the voice commands so the UI can show enrollment state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceProfileStatus {
+    pub enrolled: bool,
+    pub enrollments: u32,
+    pub voice_lock: bool,
+    /// Feature compiled in and model present
+    pub available: bool,
+}
+
+/// Event payload for `speaker-rejected`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerRejectedEvent {
+    pub similarity: f32,
+    pub threshold: f32,
+}
+
+/// Everything the processing thread needs to verify a wake word
+struct VoiceLock {
+    embedding: Vec<f32>,
+    model_path: String,
+    threshold: f32,
+}
+
+/// Set while voice lock is on; checked by the wake word processing thread
+static VOICE_LOCK: Mutex<Option<VoiceLock>> = Mutex::new(None);
+
+#[cfg(feature = "speaker-id")]
+mod engine {
+    use ort::session::Session;
+    use ort::value::Tensor;
+    use std::sync::Mutex;
+
+    struct LoadedModel {
+        path: String,
+        session: Session,
+    }
+
+    /// Keep the last model around; creating a session parses the whole graph
+    static MODEL: Mutex<Option<LoadedModel>> = Mutex::new(None);
+
+    pub fn embed(model_path: &str, samples: &[f32]) -> Result<Vec<f32>, String> {
+        let mut model = MODEL.lock().map_err(|_| "Speaker model lock poisoned".to_string())?;
+
+        let loaded_path = model.as_ref().map(|loaded| loaded.path.as_str());
+        if loaded_path != Some(model_path) {
+            log::info!("🧑 Loading speaker embedding model: {}", model_path);
+            let session = Session::builder()
+                .and_then(|builder| builder.commit_from_file(model_path))
+                .map_err(|e| format!("Failed to load speaker model '{}': {}", model_path, e))?;
+            *model = Some(LoadedModel { path: model_path.to_string(), session });
+        }
+        let Some(loaded) = model.as_mut() else {
+            return Err("Speaker model not loaded".to_string());
+        };
+
+        let input = Tensor::from_array(([1usize, samples.len()], samples.to_vec()))
+            .map_err(|e| format!("Failed to create speaker model input: {}", e))?;
+        let outputs = loaded.session.run(ort::inputs![input])
+            .map_err(|e| format!("Speaker embedding failed: {}", e))?;
+        let (_, embedding) = outputs[0].try_extract_tensor::<f32>()
+            .map_err(|e| format!("Invalid speaker embedding: {}", e))?;
+
+        Ok(embedding.to_vec())
+    }
+}
+
+#[cfg(not(feature = "speaker-id"))]
+mod engine {
+    use crate::identity;
+
+    pub fn embed(_model_path: &str, _samples: &[f32]) -> Result<Vec<f32>, String> {
+        Err(identity::current()
+            .render("Speaker verification is unavailable: {name} was built without the `speaker-id` feature"))
+    }
+}
+
+fn normalize(mut embedding: Vec<f32>) -> Vec<f32> {
+    let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        embedding.iter_mut().for_each(|v| *v /= norm);
+    }
+    embedding
+}
+
+/// Cosine similarity of two normalized embeddings
+fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// The candidate whose enrolled voice is closest to `embedding`, if any reaches `threshold`;
+/// embeddings from a different model (another length) are skipped
+fn closest_speaker<'a>(
+    embedding: &[f32],
+    candidates: impl IntoIterator<Item = (&'a String, Vec<f32>)>,
+    threshold: f32,
+) -> Option<&'a String> {
+    candidates
+        .into_iter()
+        .filter(|(_, enrolled)| enrolled.len() == embedding.len())
+        .map(|(user, enrolled)| (user, similarity(embedding, &enrolled)))
+        .filter(|(_, score)| *score >= threshold)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(user, _)| user)
+}
+
+fn embed_clip(model_path: &str, samples: &[i16], sample_rate: u32) -> Result<Vec<f32>, String> {
+    let samples: Vec<f32> = samples.iter().map(|&s| s as f32 / 32768.0).collect();
+    let samples = resample_for_whisper(samples, sample_rate)?;
+    engine::embed(model_path, &samples).map(normalize)
+}
+
+fn is_available(model_path: &str) -> bool {
+    cfg!(feature = "speaker-id") && std::path::Path::new(model_path).exists()
+}
+
+fn set_lock(settings: &SpeakerIdSettings, profile: Option<&VoiceProfile>) {
+    let lock = profile
+        .filter(|_| settings.voice_lock)
+        .map(|profile| VoiceLock {
+            embedding: profile.embedding.clone(),
+            model_path: settings.model_path.clone(),
+            threshold: settings.threshold,
+        });
+    if let Ok(mut current) = VOICE_LOCK.lock() {
+        *current = lock;
+    }
+}
+
+/// Apply the persisted voice lock at startup
+pub fn restore(app: &AppHandle, settings: &SpeakerIdSettings) {
+    if !settings.voice_lock {
+        return;
+    }
+    match VoiceProfile::load(app) {
+        Ok(Some(profile)) => {
+            set_lock(settings, Some(&profile));
+            log::info!("🔒 Voice lock enabled (restored from settings)");
+        }
+        Ok(None) => log::warn!("Voice lock is on but no voice is enrolled; ignoring"),
+        Err(e) => log::error!("Failed to load voice profile: {}", e),
+    }
+}
+
+/// Point voice lock at the active user's enrolled voice after a user switch
+pub fn reload(app: &AppHandle, settings: &SpeakerIdSettings) {
+    match VoiceProfile::load(app) {
+        Ok(profile) => set_lock(settings, profile.as_ref()),
+        Err(e) => {
+            log::error!("Failed to load voice profile: {}", e);
+            set_lock(settings, None);
+        }
+    }
+}
+
+/// Which of `users` said the wake word, going by their enrolled voices; `None` when nobody's
+/// voice is close enough or nothing can be compared. CPU-bound, so not for the async runtime.
+pub fn identify_wake_speaker(app: &AppHandle, settings: &SpeakerIdSettings, users: &[String]) -> Option<String> {
+    if !is_available(&settings.model_path) {
+        return None;
+    }
+    let wake_audio = pre_wake::snapshot();
+    if wake_audio.is_empty() {
+        return None;
+    }
+
+    let embedding = match embed_clip(&settings.model_path, &wake_audio, PRE_WAKE_SAMPLE_RATE) {
+        Ok(embedding) => embedding,
+        Err(e) => {
+            log::error!("Speaker identification failed: {}", e);
+            return None;
+        }
+    };
+    let enrolled = users.iter().filter_map(|user| {
+        let profile = VoiceProfile::load_for(app, user).ok().flatten()?;
+        Some((user, profile.embedding))
+    });
+    closest_speaker(&embedding, enrolled, settings.threshold).cloned()
+}
+
+/// Check the audio around a wake word against the enrolled voice.
+///
+/// Called from the wake word processing thread. Returns `true` when the wake
+/// word should be accepted; errors fail open so a broken model can't lock the user out.
+pub fn verify_wake_speaker(app: &AppHandle) -> bool {
+    let Ok(lock) = VOICE_LOCK.lock() else {
+        return true;
+    };
+    let Some(lock) = lock.as_ref() else {
+        return true;
+    };
+
+    let wake_audio = pre_wake::snapshot();
+    if wake_audio.is_empty() {
+        return true;
+    }
+
+    let started = std::time::Instant::now();
+    let score = match embed_clip(&lock.model_path, &wake_audio, PRE_WAKE_SAMPLE_RATE) {
+        Ok(embedding) => similarity(&embedding, &lock.embedding),
+        Err(e) => {
+            log::error!("Speaker verification failed, accepting wake word: {}", e);
+            return true;
+        }
+    };
+    log::info!(
+        "🧑 Speaker similarity {:.2} (threshold {:.2}, {}ms)",
+        score, lock.threshold, started.elapsed().as_millis()
+    );
+
+    if score >= lock.threshold {
+        return true;
+    }
+
+    let event = SpeakerRejectedEvent { similarity: score, threshold: lock.threshold };
+    if let Err(e) = app.emit("speaker-rejected", &event) {
+        log::error!("Failed to emit speaker rejected event: {}", e);
+    }
+    false
+}
+
+#[tauri::command]
+pub async fn get_voice_profile_status(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<VoiceProfileStatus, String> {
+    let settings = state.lock().await.speaker_id.clone();
+    let profile = VoiceProfile::load(&app)?;
+
+    Ok(VoiceProfileStatus {
+        enrolled: profile.is_some(),
+        enrollments: profile.map(|p| p.enrollments).unwrap_or(0),
+        voice_lock: settings.voice_lock,
+        available: is_available(&settings.model_path),
+    })
+}
+
+/// Add a PCM16 mono clip of the user speaking to their voice profile.
+///
+/// Each enrollment is averaged into the profile, so several clips in different
+/// conditions make verification more robust.
+#[tauri::command]
+pub async fn enroll_voice(
+    samples: Vec<i16>,
+    sample_rate: u32,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<VoiceProfileStatus, String> {
+    if sample_rate == 0 {
+        return Err("Invalid sample rate".to_string());
+    }
+    let seconds = samples.len() as f32 / sample_rate as f32;
+    if seconds < MIN_ENROLLMENT_SECS {
+        return Err(format!("Enrollment needs at least {:.0}s of speech (got {:.1}s)", MIN_ENROLLMENT_SECS, seconds));
+    }
+
+    let settings = state.lock().await.speaker_id.clone();
+    let model_path = settings.model_path.clone();
+
+    // Model inference is CPU-bound, keep it off the async runtime
+    let embedding = tokio::task::spawn_blocking(move || embed_clip(&model_path, &samples, sample_rate))
+        .await
+        .map_err(|e| format!("Enrollment task failed: {}", e))??;
+
+    let mut profile = VoiceProfile::load(&app)?.unwrap_or_default();
+    profile.embedding = if profile.embedding.len() == embedding.len() {
+        let weight = profile.enrollments as f32;
+        let mean = profile.embedding.iter()
+            .zip(&embedding)
+            .map(|(old, new)| (old * weight + new) / (weight + 1.0))
+            .collect();
+        normalize(mean)
+    } else {
+        // First enrollment, or the model changed and old embeddings are meaningless
+        profile.enrollments = 0;
+        embedding
+    };
+    profile.enrollments += 1;
+    profile.updated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    profile.save(&app)?;
+
+    set_lock(&settings, Some(&profile));
+
+    log::info!("🧑 Voice enrolled ({:.1}s clip, {} total)", seconds, profile.enrollments);
+    Ok(VoiceProfileStatus {
+        enrolled: true,
+        enrollments: profile.enrollments,
+        voice_lock: settings.voice_lock,
+        available: true,
+    })
+}
+
+#[tauri::command]
+pub async fn set_voice_lock(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let profile = VoiceProfile::load(&app)?;
+    if enabled && profile.is_none() {
+        return Err("Enroll your voice before turning on voice lock".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    if enabled && !is_available(&settings.speaker_id.model_path) {
+        return Err(format!(
+            "Speaker verification is unavailable: needs the `speaker-id` feature and a model at {}",
+            settings.speaker_id.model_path
+        ));
+    }
+    settings.speaker_id.voice_lock = enabled;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    set_lock(&settings.speaker_id, profile.as_ref());
+
+    if enabled {
+        log::info!("🔒 Voice lock enabled");
+        Ok(identity.render("Voice lock enabled - {name} only responds to your voice"))
+    } else {
+        log::info!("🔓 Voice lock disabled");
+        Ok("Voice lock disabled".to_string())
+    }
+}
+
+/// Forget the enrolled voice; also turns voice lock off
+#[tauri::command]
+pub async fn clear_voice_profile(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    VoiceProfile::default().save(&app)?;
+
+    let mut settings = state.lock().await;
+    settings.speaker_id.voice_lock = false;
+    settings.save(&app)?;
+    set_lock(&settings.speaker_id, None);
+
+    log::info!("🧑 Voice profile cleared");
+    Ok("Voice profile cleared".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similarity_of_normalized_embeddings_is_their_cosine() {
+        let a = normalize(vec![3.0, 4.0]);
+        assert!((similarity(&a, &normalize(vec![6.0, 8.0])) - 1.0).abs() < 1e-6);
+        assert!(similarity(&a, &normalize(vec![-4.0, 3.0])).abs() < 1e-6);
+        assert!((similarity(&a, &normalize(vec![-3.0, -4.0])) + 1.0).abs() < 1e-6);
+        assert_eq!(normalize(vec![0.0, 0.0]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn closest_speaker_needs_the_threshold() {
+        let (alice, bob, carol) = ("alice".to_string(), "bob".to_string(), "carol".to_string());
+        let voice = normalize(vec![1.0, 0.2, 0.0]);
+        let candidates = || {
+            vec![
+                (&alice, normalize(vec![1.0, 0.0, 0.0])),
+                (&bob, normalize(vec![0.0, 1.0, 0.0])),
+                // Enrolled with another model
+                (&carol, vec![1.0, 0.2]),
+            ]
+        };
+
+        // cos(voice, alice) is about 0.98, cos(voice, bob) about 0.20
+        assert_eq!(closest_speaker(&voice, candidates(), 0.65), Some(&alice));
+        assert_eq!(closest_speaker(&voice, candidates(), 0.15), Some(&alice));
+        assert_eq!(closest_speaker(&voice, candidates(), 0.99), None);
+        assert_eq!(closest_speaker(&normalize(vec![0.0, 1.0, 0.0]), candidates(), 0.65), Some(&bob));
+        assert_eq!(closest_speaker(&voice, Vec::new(), 0.0), None);
+    }
+}
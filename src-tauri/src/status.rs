@@ -0,0 +1,180 @@
+use crate::audio::{self, InputDevice};
+use crate::call_detection;
+use crate::porcupine_service::WakeWordHandle;
+use crate::privacy;
+use crate::quiet_hours;
+use crate::settings::SettingsState;
+use crate::sleep_inhibit::{self, InhibitReason};
+use cpal::traits::DeviceTrait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Realtime connection state as last reported by the frontend, which owns the WebSocket
+static REALTIME_STATUS: Mutex<RealtimeStatus> = Mutex::new(RealtimeStatus {
+    connected: false,
+    reconnecting: false,
+    provider: None,
+    session_id: None,
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WakeWordState {
+    Listening,
+    Stopped,
+    /// Stopped by the call monitor and resumed when the call ends
+    PausedForCall,
+    /// Stopped by the quiet hours scheduler
+    PausedForQuietHours,
+}
+
+/// Overall assistant phase, derived from the subsystem states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EvaPhase {
+    Idle,
+    WaitingForWakeWord,
+    Conversing,
+    SuspendedForCall,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RealtimeStatus {
+    pub connected: bool,
+    pub reconnecting: bool,
+    /// `openai` or `azure`
+    pub provider: Option<String>,
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioStatus {
+    pub input_device: Option<String>,
+    pub input_sample_rate: Option<u32>,
+}
+
+/// Typed snapshot returned by `eva_status`.
+///
+/// Fields are only ever added, so frontends built against an older shape keep parsing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaStatus {
+    pub assistant_name: String,
+    pub phase: EvaPhase,
+    pub wake_word: WakeWordState,
+    pub mic_muted: bool,
+    pub openai: RealtimeStatus,
+    pub audio: AudioStatus,
+}
+
+/// Everything a (re)loaded frontend needs to catch up, sent as `eva-state-snapshot`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub status: EvaStatus,
+    pub input_devices: Vec<InputDevice>,
+    /// SHA-256 of the saved settings; `get_settings` only needs refetching when it changes
+    pub settings_digest: String,
+}
+
+fn audio_status() -> AudioStatus {
+    let device = audio::select_input_device(&audio::capture_host()).ok();
+    AudioStatus {
+        input_device: device.as_ref().and_then(|d| d.name().ok()),
+        input_sample_rate: device
+            .and_then(|d| d.default_input_config().ok())
+            .map(|config| config.sample_rate().0),
+    }
+}
+
+#[tauri::command]
+pub async fn eva_status(app: AppHandle) -> Result<EvaStatus, String> {
+    current_status(&app).await
+}
+
+async fn current_status(app: &AppHandle) -> Result<EvaStatus, String> {
+    let assistant_name = app.state::<SettingsState>().lock().await.identity.assistant_name.clone();
+
+    let wake_word = if app.state::<WakeWordHandle>().is_listening() {
+        WakeWordState::Listening
+    } else if call_detection::is_paused_for_call() {
+        WakeWordState::PausedForCall
+    } else if quiet_hours::is_active() {
+        WakeWordState::PausedForQuietHours
+    } else {
+        WakeWordState::Stopped
+    };
+
+    let openai = REALTIME_STATUS.lock()
+        .map_err(|_| "Realtime status lock poisoned".to_string())?
+        .clone();
+
+    let phase = match wake_word {
+        WakeWordState::PausedForCall => EvaPhase::SuspendedForCall,
+        _ if openai.connected => EvaPhase::Conversing,
+        WakeWordState::Listening => EvaPhase::WaitingForWakeWord,
+        WakeWordState::Stopped | WakeWordState::PausedForQuietHours => EvaPhase::Idle,
+    };
+
+    // Device queries can block on some audio backends
+    let audio = tokio::task::spawn_blocking(audio_status)
+        .await
+        .map_err(|e| format!("Audio status task failed: {}", e))?;
+
+    Ok(EvaStatus {
+        assistant_name,
+        phase,
+        wake_word,
+        mic_muted: privacy::is_mic_muted(),
+        openai,
+        audio,
+    })
+}
+
+async fn state_snapshot(app: &AppHandle) -> Result<StateSnapshot, String> {
+    let status = current_status(app).await?;
+    let input_devices = audio::list_input_devices().await.unwrap_or_else(|e| {
+        log::warn!("State snapshot without input devices: {}", e);
+        Vec::new()
+    });
+    let settings = serde_json::to_vec(&*app.state::<SettingsState>().lock().await)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let settings_digest = Sha256::digest(&settings).iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    Ok(StateSnapshot { status, input_devices, settings_digest })
+}
+
+/// Send `eva-state-snapshot` to the webview labelled `label`
+pub fn emit_snapshot(app: AppHandle, label: String) {
+    tauri::async_runtime::spawn(async move {
+        match state_snapshot(&app).await {
+            Ok(snapshot) => {
+                if let Err(e) = app.emit_to(label.as_str(), "eva-state-snapshot", snapshot) {
+                    log::error!("Failed to emit state snapshot: {}", e);
+                }
+            }
+            Err(e) => log::error!("Failed to build state snapshot: {}", e),
+        }
+    });
+}
+
+/// Resend `eva-state-snapshot` to the calling webview, e.g. once its listeners are set up
+/// after a reload
+#[tauri::command]
+pub async fn request_state_sync(webview: tauri::Webview, app: AppHandle) -> Result<(), String> {
+    emit_snapshot(app, webview.label().to_string());
+    Ok(())
+}
+
+/// Called by the frontend whenever its Realtime connection state changes
+#[tauri::command]
+pub async fn report_realtime_status(status: RealtimeStatus) -> Result<(), String> {
+    // Keep the machine awake for as long as a conversation is connected
+    sleep_inhibit::set(InhibitReason::Conversation, status.connected);
+
+    let mut current = REALTIME_STATUS.lock()
+        .map_err(|_| "Realtime status lock poisoned".to_string())?;
+    *current = status;
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::ToolDefinition;
+
+pub const TOOL_NAME: &str = "run_action";
+
+/// Shell snippets that run longer are killed
+const SHELL_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest snippet output read back to the model
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionKind {
+    /// Launch an application by name or path
+    App,
+    /// Run a shell snippet (`sh -c`, or `cmd /C` on Windows)
+    Shell,
+}
+
+/// One thing Eva may do on the user's behalf; the model only ever picks a `name`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllowedAction {
+    /// What the user calls it, e.g. "Spotify" or "lock screen"
+    pub name: String,
+    pub kind: ActionKind,
+    /// App name/path, or the shell snippet
+    pub target: String,
+}
+
+/// The allowlist; anything not listed here can't be run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionSettings {
+    pub actions: Vec<AllowedAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Arguments {
+    name: String,
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn launch_app(target: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        command.arg("-a").arg(target);
+        command
+    };
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]).arg(target);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new(target);
+
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to launch {}: {}", target, e))
+}
+
+async fn run_shell(snippet: &str) -> Result<String, String> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/C").arg(snippet);
+        command
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c").arg(snippet);
+        command
+    };
+    command.stdin(Stdio::null()).kill_on_drop(true);
+
+    let output = tokio::time::timeout(SHELL_TIMEOUT, command.output())
+        .await
+        .map_err(|_| format!("Timed out after {}s", SHELL_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Failed to run: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Exited with {}: {}", output.status, truncate(stderr.trim(), MAX_OUTPUT_CHARS)));
+    }
+    Ok(truncate(&stdout, MAX_OUTPUT_CHARS))
+}
+
+/// `None` when nothing is allowlisted, so the tool isn't offered at all
+pub fn definition(settings: &ActionSettings) -> Option<ToolDefinition> {
+    if settings.actions.is_empty() {
+        return None;
+    }
+
+    let names: Vec<&str> = settings.actions.iter().map(|action| action.name.as_str()).collect();
+    Some(ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: "Open an app or run a shortcut the user has set up, e.g. \"open Spotify\". \
+            Only the listed names are available."
+            .to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "enum": names },
+            },
+            "required": ["name"],
+        }),
+    })
+}
+
+pub async fn call(app: &AppHandle, arguments: serde_json::Value) -> Result<String, String> {
+    let arguments: Arguments = serde_json::from_value(arguments)
+        .map_err(|e| format!("Invalid action arguments: {}", e))?;
+
+    let action = {
+        let settings = app.state::<SettingsState>().lock().await;
+        settings
+            .actions
+            .actions
+            .iter()
+            .find(|action| action.name.eq_ignore_ascii_case(arguments.name.trim()))
+            .cloned()
+            .ok_or_else(|| format!("\"{}\" isn't an allowed action", arguments.name))?
+    };
+
+    log::info!("⚡ Running action \"{}\" ({:?})", action.name, action.kind);
+    match action.kind {
+        ActionKind::App => launch_app(&action.target).map(|_| format!("Opened {}", action.name)),
+        ActionKind::Shell => run_shell(&action.target).await.map(|output| {
+            if output.is_empty() {
+                format!("Ran {}", action.name)
+            } else {
+                format!("Ran {}:\n{}", action.name, output)
+            }
+        }),
+    }
+}
+
+#[tauri::command]
+pub async fn get_actions(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<Vec<AllowedAction>, String> {
+    Ok(state.lock().await.actions.actions.clone())
+}
+
+/// Replace the allowlist; takes effect on the next connect
+#[tauri::command]
+pub async fn set_actions(
+    actions: Vec<AllowedAction>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut names = std::collections::HashSet::new();
+    for action in &actions {
+        let name = action.name.trim().to_lowercase();
+        if name.is_empty() || action.target.trim().is_empty() {
+            return Err("Every action needs a name and a target".to_string());
+        }
+        if !names.insert(name) {
+            return Err(format!("Duplicate action name: {}", action.name));
+        }
+    }
+
+    let mut settings = state.lock().await;
+    settings.actions.actions = actions
+        .into_iter()
+        .map(|action| AllowedAction {
+            name: action.name.trim().to_string(),
+            target: action.target.trim().to_string(),
+            ..action
+        })
+        .collect();
+    settings.save(&app)?;
+    let count = settings.actions.actions.len();
+    drop(settings);
+
+    log::info!("⚡ {} allowed action(s) saved", count);
+    Ok(format!("{} allowed action(s) saved. Reconnect to apply.", count))
+}
@@ -0,0 +1,499 @@
+use crate::secrets;
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, Months, NaiveDate, NaiveDateTime,
+    TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::ToolDefinition;
+
+pub const TOOL_NAME: &str = "calendar_events";
+
+const URL_SECRET: &str = "calendar-url";
+const USERNAME_SECRET: &str = "calendar-username";
+const PASSWORD_SECRET: &str = "calendar-password";
+const REQUEST_TIMEOUT_SECS: u64 = 20;
+/// Tool calls within this long of the last fetch answer from the cache
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+const MAX_DAYS: u32 = 14;
+/// Upper bound on recurrence expansion, for rules with neither COUNT nor UNTIL
+const MAX_RECURRENCE_STEPS: u32 = 20_000;
+
+static CACHE: Mutex<Option<CachedCalendar>> = Mutex::new(None);
+
+struct CachedCalendar {
+    fetched_at: Instant,
+    /// Unix timestamp in milliseconds, for the settings panel
+    fetched_at_millis: u64,
+    events: Vec<CalendarEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarStatus {
+    pub url: String,
+    pub username: Option<String>,
+    pub password_configured: bool,
+    /// Unix timestamp in milliseconds of the last successful fetch
+    pub last_refreshed: Option<u64>,
+    pub event_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct Arguments {
+    /// 0 for today, 1 for tomorrow...
+    #[serde(default)]
+    day_offset: i64,
+    #[serde(default = "default_days")]
+    days: u32,
+}
+
+fn default_days() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The common subset of RRULE: FREQ, INTERVAL, COUNT, UNTIL and weekly BYDAY
+#[derive(Debug, Clone)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+    /// Monday first
+    by_day: Vec<Weekday>,
+}
+
+/// Times are local; UTC times are converted and TZID times are taken as local time
+#[derive(Debug, Clone)]
+struct CalendarEvent {
+    summary: String,
+    location: Option<String>,
+    start: NaiveDateTime,
+    duration: ChronoDuration,
+    all_day: bool,
+    rule: Option<RecurrenceRule>,
+}
+
+impl RecurrenceRule {
+    fn parse(value: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+
+        for part in value.split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            match key {
+                "FREQ" => {
+                    frequency = match value {
+                        "DAILY" => Some(Frequency::Daily),
+                        "WEEKLY" => Some(Frequency::Weekly),
+                        "MONTHLY" => Some(Frequency::Monthly),
+                        "YEARLY" => Some(Frequency::Yearly),
+                        _ => None,
+                    }
+                }
+                "INTERVAL" => interval = value.parse::<u32>().unwrap_or(1).max(1),
+                "COUNT" => count = value.parse().ok(),
+                "UNTIL" => until = parse_date_time(value, false).map(|(time, _)| time),
+                "BYDAY" => by_day = value.split(',').filter_map(parse_weekday).collect(),
+                _ => {}
+            }
+        }
+        by_day.sort_by_key(|day: &Weekday| day.num_days_from_monday());
+        by_day.dedup();
+
+        Some(Self {
+            frequency: frequency?,
+            interval,
+            count,
+            until,
+            by_day,
+        })
+    }
+
+    /// Occurrence starts for one step of the rule, earliest first
+    fn step(&self, start: NaiveDateTime, step: u32) -> Vec<NaiveDateTime> {
+        let n = step.saturating_mul(self.interval);
+        let after_days = |days: i64| start.checked_add_signed(ChronoDuration::days(days));
+        match self.frequency {
+            Frequency::Daily => after_days(n.into()).into_iter().collect(),
+            Frequency::Weekly if self.by_day.is_empty() => after_days(i64::from(n) * 7).into_iter().collect(),
+            Frequency::Weekly => {
+                let Some(week) = after_days(i64::from(n) * 7) else {
+                    return Vec::new();
+                };
+                let monday = week - ChronoDuration::days(week.weekday().num_days_from_monday().into());
+                self.by_day
+                    .iter()
+                    .map(|day| monday + ChronoDuration::days(day.num_days_from_monday().into()))
+                    .filter(|occurrence| *occurrence >= start)
+                    .collect()
+            }
+            Frequency::Monthly => start.checked_add_months(Months::new(n)).into_iter().collect(),
+            Frequency::Yearly => start
+                .checked_add_months(Months::new(n.saturating_mul(12)))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl CalendarEvent {
+    /// Starts of the occurrences overlapping `[from, to)`
+    fn occurrences(&self, from: NaiveDateTime, to: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let overlaps = |start: NaiveDateTime| start < to && (start + self.duration > from || start >= from);
+
+        let Some(rule) = &self.rule else {
+            return overlaps(self.start).then_some(self.start).into_iter().collect();
+        };
+
+        let mut occurrences = Vec::new();
+        let mut produced = 0;
+        for step in 0..MAX_RECURRENCE_STEPS {
+            for start in rule.step(self.start, step) {
+                let past_until = rule.until.is_some_and(|until| start > until);
+                let past_count = rule.count.is_some_and(|count| produced >= count);
+                if past_until || past_count || start >= to {
+                    return occurrences;
+                }
+                produced += 1;
+                if overlaps(start) {
+                    occurrences.push(start);
+                }
+            }
+        }
+        occurrences
+    }
+
+    fn describe(&self, start: NaiveDateTime) -> String {
+        let mut line = if self.all_day {
+            format!("All day: {}", self.summary)
+        } else {
+            let end = start + self.duration;
+            format!("{}-{} {}", start.format("%H:%M"), end.format("%H:%M"), self.summary)
+        };
+        if let Some(location) = &self.location {
+            line.push_str(&format!(" ({})", location));
+        }
+        line
+    }
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    // Ordinals like `1MO` only make sense for monthly rules, which ignore BYDAY
+    match code.trim_start_matches(|c: char| c == '+' || c == '-' || c.is_ascii_digit()) {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// `20240514`, `20240514T090000` or `20240514T090000Z`; returns local time and whether it's a date
+fn parse_date_time(value: &str, date_only: bool) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+    if date_only || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_hms_opt(0, 0, 0)?, true));
+    }
+
+    match value.strip_suffix('Z') {
+        Some(utc) => {
+            let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+            let local: DateTime<Local> = Utc.from_utc_datetime(&time).with_timezone(&Local);
+            Some((local.naive_local(), false))
+        }
+        None => Some((NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?, false)),
+    }
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// VEVENTs from an iCalendar document. Exceptions (EXDATE, RECURRENCE-ID) and DURATION
+/// aren't supported, so a moved instance of a recurring event may show up twice.
+fn parse_ics(ics: &str) -> Vec<CalendarEvent> {
+    // Long lines are folded onto continuation lines starting with a space or tab
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match line.strip_prefix([' ', '\t']) {
+            Some(continuation) if !lines.is_empty() => {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(continuation);
+                }
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<Vec<(String, String, String)>> = None;
+    for line in &lines {
+        match line.as_str() {
+            "BEGIN:VEVENT" => current = Some(Vec::new()),
+            "END:VEVENT" => {
+                if let Some(event) = current.take().and_then(|properties| build_event(&properties)) {
+                    events.push(event);
+                }
+            }
+            _ => {
+                if let (Some(properties), Some((key, value))) = (current.as_mut(), line.split_once(':')) {
+                    let (name, params) = key.split_once(';').unwrap_or((key, ""));
+                    properties.push((name.to_uppercase(), params.to_uppercase(), value.to_string()));
+                }
+            }
+        }
+    }
+    events
+}
+
+fn build_event(properties: &[(String, String, String)]) -> Option<CalendarEvent> {
+    let property = |name: &str| properties.iter().find(|(key, _, _)| key == name);
+
+    let (_, params, value) = property("DTSTART")?;
+    let (start, all_day) = parse_date_time(value, params.contains("VALUE=DATE"))?;
+    let end = property("DTEND").and_then(|(_, params, value)| {
+        parse_date_time(value, params.contains("VALUE=DATE")).map(|(end, _)| end)
+    });
+    let duration = match end {
+        Some(end) if end > start => end - start,
+        _ if all_day => ChronoDuration::days(1),
+        _ => ChronoDuration::zero(),
+    };
+
+    Some(CalendarEvent {
+        summary: property("SUMMARY")
+            .map(|(_, _, value)| unescape(value))
+            .unwrap_or_else(|| "(no title)".to_string()),
+        location: property("LOCATION")
+            .map(|(_, _, value)| unescape(value))
+            .filter(|location| !location.trim().is_empty()),
+        start,
+        duration,
+        all_day,
+        rule: property("RRULE").and_then(|(_, _, value)| RecurrenceRule::parse(value)),
+    })
+}
+
+/// `webcal://` is how calendar apps advertise subscriptions; it's plain HTTPS underneath
+fn normalize_url(url: &str) -> String {
+    match url.trim().strip_prefix("webcal://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.trim().to_string(),
+    }
+}
+
+async fn fetch(url: &str, username: Option<&str>, password: Option<&str>) -> Result<Vec<CalendarEvent>, String> {
+    let client = crate::proxy::client_builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut request = client.get(url);
+    if let Some(username) = username {
+        request = request.basic_auth(username, password);
+    }
+
+    let body = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach calendar: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Calendar server rejected the request: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read calendar: {}", e))?;
+
+    if !body.contains("BEGIN:VCALENDAR") {
+        return Err("That URL didn't return an iCalendar (.ics) feed".to_string());
+    }
+    Ok(parse_ics(&body))
+}
+
+fn store_cache(events: Vec<CalendarEvent>) -> usize {
+    let count = events.len();
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = Some(CachedCalendar {
+            fetched_at: Instant::now(),
+            fetched_at_millis: Utc::now().timestamp_millis() as u64,
+            events,
+        });
+    }
+    count
+}
+
+/// Fetch with the stored credentials and replace the cache; returns the event count
+async fn refresh() -> Result<usize, String> {
+    let url = secrets::get(URL_SECRET).ok_or("No calendar is set up")?;
+    let username = secrets::get(USERNAME_SECRET);
+    let password = secrets::get(PASSWORD_SECRET);
+
+    let events = fetch(&url, username.as_deref(), password.as_deref()).await?;
+    let count = store_cache(events);
+    log::info!("📅 Calendar refreshed, {} events", count);
+    Ok(count)
+}
+
+fn cached_events() -> Option<Vec<CalendarEvent>> {
+    let cache = CACHE.lock().ok()?;
+    let cache = cache.as_ref()?;
+    (cache.fetched_at.elapsed() < CACHE_TTL).then(|| cache.events.clone())
+}
+
+pub fn is_configured() -> bool {
+    secrets::get(URL_SECRET).is_some()
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: "List the events on the user's calendar, e.g. for \"what's on my schedule \
+            today\" or \"am I free tomorrow afternoon\"."
+            .to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "day_offset": {
+                    "type": "integer",
+                    "description": "First day to list: 0 is today, 1 tomorrow, -1 yesterday",
+                },
+                "days": {
+                    "type": "integer",
+                    "description": format!("Number of days to list, 1 to {}", MAX_DAYS),
+                },
+            },
+        }),
+    }
+}
+
+pub async fn call(arguments: serde_json::Value) -> Result<String, String> {
+    let arguments: Arguments = serde_json::from_value(arguments)
+        .map_err(|e| format!("Invalid calendar arguments: {}", e))?;
+
+    let events = match cached_events() {
+        Some(events) => events,
+        None => {
+            refresh().await?;
+            cached_events().unwrap_or_default()
+        }
+    };
+
+    let first_day = Local::now().date_naive() + ChronoDuration::days(arguments.day_offset);
+    let mut lines = Vec::new();
+    for offset in 0..arguments.days.clamp(1, MAX_DAYS) {
+        let day = first_day + ChronoDuration::days(offset.into());
+        let from = day.and_hms_opt(0, 0, 0).unwrap_or_default();
+        let to = from + ChronoDuration::days(1);
+
+        let mut day_events: Vec<(NaiveDateTime, &CalendarEvent)> = events
+            .iter()
+            .flat_map(|event| event.occurrences(from, to).into_iter().map(move |start| (start, event)))
+            .collect();
+        // All-day events first, then by start time
+        day_events.sort_by_key(|(start, event)| (!event.all_day, *start));
+
+        lines.push(format!("{}:", day.format("%A %-d %B")));
+        if day_events.is_empty() {
+            lines.push("- Nothing scheduled".to_string());
+        }
+        for (start, event) in day_events {
+            lines.push(format!("- {}", event.describe(start)));
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+#[tauri::command]
+pub async fn get_calendar() -> Result<CalendarStatus, String> {
+    let (last_refreshed, event_count) = CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.as_ref().map(|cache| (Some(cache.fetched_at_millis), cache.events.len())))
+        .unwrap_or((None, 0));
+
+    Ok(CalendarStatus {
+        url: secrets::get(URL_SECRET).unwrap_or_default(),
+        username: secrets::get(USERNAME_SECRET),
+        password_configured: secrets::get(PASSWORD_SECRET).is_some(),
+        last_refreshed,
+        event_count,
+    })
+}
+
+/// Check the feed loads with these credentials, then store them in the keychain. An empty
+/// URL removes the calendar; a `None` password keeps the stored one.
+#[tauri::command]
+pub async fn set_calendar(
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<String, String> {
+    let url = normalize_url(&url);
+    if url.is_empty() {
+        for secret in [URL_SECRET, USERNAME_SECRET, PASSWORD_SECRET] {
+            secrets::delete(secret);
+        }
+        if let Ok(mut cache) = CACHE.lock() {
+            *cache = None;
+        }
+        log::info!("📅 Calendar removed");
+        return Ok("Calendar removed".to_string());
+    }
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("Calendar URL must start with https://, http:// or webcal://".to_string());
+    }
+
+    let username = username.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+    let password = match password.filter(|password| !password.is_empty()) {
+        Some(password) => Some(password),
+        None => secrets::get(PASSWORD_SECRET),
+    };
+
+    let events = fetch(&url, username.as_deref(), password.as_deref()).await?;
+
+    secrets::set(URL_SECRET, &url)?;
+    secrets::set(USERNAME_SECRET, username.as_deref().unwrap_or_default())?;
+    secrets::set(PASSWORD_SECRET, password.as_deref().unwrap_or_default())?;
+    let count = store_cache(events);
+
+    log::info!("📅 Calendar subscribed, {} events", count);
+    Ok(format!("Calendar connected ({} events)", count))
+}
+
+/// Fetch the calendar now instead of waiting for the cache to expire
+#[tauri::command]
+pub async fn refresh_calendar() -> Result<String, String> {
+    let count = refresh().await?;
+    Ok(format!("Calendar refreshed ({} events)", count))
+}
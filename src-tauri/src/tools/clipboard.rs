@@ -0,0 +1,54 @@
+use crate::settings::SettingsState;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use super::ToolDefinition;
+
+pub const TOOL_NAME: &str = "read_clipboard";
+
+/// Longest clipboard text handed to the model
+const MAX_CHARS: usize = 20_000;
+/// How much of the clipboard the confirmation dialog shows
+const PREVIEW_CHARS: usize = 200;
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: "Read the text the user last copied, e.g. for \"summarize what I just \
+            copied\". The user is asked to allow it each time."
+            .to_string(),
+        parameters: serde_json::json!({ "type": "object", "properties": {} }),
+    }
+}
+
+pub async fn call(app: &AppHandle) -> Result<String, String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| format!("The clipboard has no text: {}", e))?;
+    if text.trim().is_empty() {
+        return Err("The clipboard is empty".to_string());
+    }
+
+    let identity = app.state::<SettingsState>().lock().await.identity.clone();
+    let message = format!(
+        "{} ({} characters):\n\n{}",
+        identity.render("{name} wants to read your clipboard"),
+        text.chars().count(),
+        truncate(text.trim(), PREVIEW_CHARS)
+    );
+    if !super::confirm(app, "Share clipboard?", message).await {
+        log::info!("📋 Clipboard sharing declined");
+        return Err("The user declined to share their clipboard".to_string());
+    }
+
+    log::info!("📋 Clipboard shared ({} characters)", text.chars().count());
+    Ok(truncate(&text, MAX_CHARS))
+}
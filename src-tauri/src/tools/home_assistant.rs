@@ -0,0 +1,261 @@
+use crate::secrets;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::ToolDefinition;
+
+pub const TOOL_NAME: &str = "home_assistant";
+
+const URL_SECRET: &str = "home-assistant-url";
+const TOKEN_SECRET: &str = "home-assistant-token";
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Entities the tool may switch; sensors and the like are only ever read
+const CONTROLLABLE_DOMAINS: &[&str] = &[
+    "light",
+    "switch",
+    "fan",
+    "cover",
+    "climate",
+    "media_player",
+    "input_boolean",
+    "scene",
+    "script",
+];
+
+/// Words that don't help tell one device from another
+const FILLER_WORDS: &[&str] = &["the", "a", "an", "my", "in", "of", "all"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomeAssistantStatus {
+    pub url: String,
+    pub token_configured: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct Arguments {
+    action: Action,
+    target: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Action {
+    TurnOn,
+    TurnOff,
+    Toggle,
+    Status,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct EntityState {
+    entity_id: String,
+    state: String,
+    #[serde(default)]
+    attributes: serde_json::Value,
+}
+
+impl EntityState {
+    fn domain(&self) -> &str {
+        self.entity_id.split('.').next().unwrap_or_default()
+    }
+
+    fn friendly_name(&self) -> &str {
+        self.attributes["friendly_name"].as_str().unwrap_or(&self.entity_id)
+    }
+
+    /// Words from the friendly name and entity id, e.g. `light.living_room` gives `light living room`
+    fn words(&self) -> Vec<String> {
+        let mut words = normalized_words(self.friendly_name());
+        words.extend(normalized_words(&self.entity_id.replace(['.', '_'], " ")));
+        words
+    }
+}
+
+/// Lowercase, without punctuation, filler words or plural `s`, so "the Lights" matches "light"
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| !word.is_empty() && !FILLER_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+struct Connection {
+    url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl Connection {
+    fn from_secrets() -> Result<Self, String> {
+        let url = secrets::get(URL_SECRET).ok_or("Home Assistant is not set up")?;
+        let token = secrets::get(TOKEN_SECRET).ok_or("Home Assistant token is missing")?;
+        Self::new(url, token)
+    }
+
+    fn new(url: String, token: String) -> Result<Self, String> {
+        let client = crate::proxy::client_builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            client,
+        })
+    }
+
+    async fn get(&self, path: &str) -> Result<reqwest::Response, String> {
+        self.client
+            .get(format!("{}/api/{}", self.url, path))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Home Assistant at {}: {}", self.url, e))?
+            .error_for_status()
+            .map_err(|e| format!("Home Assistant rejected the request: {}", e))
+    }
+
+    async fn states(&self) -> Result<Vec<EntityState>, String> {
+        self.get("states")
+            .await?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Home Assistant states response: {}", e))
+    }
+
+    async fn call_service(&self, service: &str, entity_id: &str) -> Result<(), String> {
+        self.client
+            .post(format!("{}/api/services/homeassistant/{}", self.url, service))
+            .bearer_auth(&self.token)
+            .json(&serde_json::json!({ "entity_id": entity_id }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Home Assistant at {}: {}", self.url, e))?
+            .error_for_status()
+            .map_err(|e| format!("Home Assistant rejected {}: {}", service, e))?;
+        Ok(())
+    }
+}
+
+/// The entity whose words cover every word of `target` with the fewest left over,
+/// or an exact entity id; `controllable_only` skips sensors and other read-only domains
+fn find_entity<'a>(
+    states: &'a [EntityState],
+    target: &str,
+    controllable_only: bool,
+) -> Option<&'a EntityState> {
+    let target = target.trim();
+    if let Some(exact) = states.iter().find(|entity| entity.entity_id == target) {
+        return Some(exact);
+    }
+
+    let wanted = normalized_words(target);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    states
+        .iter()
+        .filter(|entity| !controllable_only || CONTROLLABLE_DOMAINS.contains(&entity.domain()))
+        .filter_map(|entity| {
+            let words = entity.words();
+            wanted
+                .iter()
+                .all(|word| words.contains(word))
+                .then_some((words.len(), entity))
+        })
+        .min_by_key(|(extra, _)| *extra)
+        .map(|(_, entity)| entity)
+}
+
+pub fn is_configured() -> bool {
+    secrets::get(URL_SECRET).is_some() && secrets::get(TOKEN_SECRET).is_some()
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: "Control or check a smart home device through Home Assistant, e.g. turn \
+            off the living room lights or check whether the garage door is open."
+            .to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["turn_on", "turn_off", "toggle", "status"],
+                },
+                "target": {
+                    "type": "string",
+                    "description": "Device or room as the user said it, e.g. \"living room lights\"",
+                },
+            },
+            "required": ["action", "target"],
+        }),
+    }
+}
+
+pub async fn call(arguments: serde_json::Value) -> Result<String, String> {
+    let arguments: Arguments = serde_json::from_value(arguments)
+        .map_err(|e| format!("Invalid Home Assistant arguments: {}", e))?;
+    let connection = Connection::from_secrets()?;
+
+    let states = connection.states().await?;
+    let controllable_only = !matches!(arguments.action, Action::Status);
+    let entity = find_entity(&states, &arguments.target, controllable_only)
+        .ok_or_else(|| format!("No Home Assistant device matches \"{}\"", arguments.target))?;
+    let name = entity.friendly_name();
+
+    let service = match arguments.action {
+        Action::Status => return Ok(format!("{} is {}", name, entity.state)),
+        Action::TurnOn => "turn_on",
+        Action::TurnOff => "turn_off",
+        Action::Toggle => "toggle",
+    };
+
+    connection.call_service(service, &entity.entity_id).await?;
+    log::info!("🏡 Home Assistant {} {}", service, entity.entity_id);
+    Ok(format!("Called {} on {}", service, name))
+}
+
+#[tauri::command]
+pub async fn get_home_assistant() -> Result<HomeAssistantStatus, String> {
+    Ok(HomeAssistantStatus {
+        url: secrets::get(URL_SECRET).unwrap_or_default(),
+        token_configured: secrets::get(TOKEN_SECRET).is_some(),
+    })
+}
+
+/// Check the instance answers with these credentials, then store them; an empty URL
+/// removes the integration, a `None` token keeps the stored one
+#[tauri::command]
+pub async fn set_home_assistant(url: String, token: Option<String>) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/').to_string();
+    if url.is_empty() {
+        secrets::delete(URL_SECRET);
+        secrets::delete(TOKEN_SECRET);
+        log::info!("🏡 Home Assistant removed");
+        return Ok("Home Assistant removed".to_string());
+    }
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("Home Assistant URL must start with http:// or https://".to_string());
+    }
+
+    let token = match token.filter(|token| !token.trim().is_empty()) {
+        Some(token) => token.trim().to_string(),
+        None => secrets::get(TOKEN_SECRET).ok_or("A long-lived access token is required")?,
+    };
+
+    Connection::new(url.clone(), token.clone())?.get("").await?;
+
+    secrets::set(URL_SECRET, &url)?;
+    secrets::set(TOKEN_SECRET, &token)?;
+
+    log::info!("🏡 Home Assistant connected at {}", url);
+    Ok(format!("Connected to Home Assistant at {}", url))
+}
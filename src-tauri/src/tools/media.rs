@@ -0,0 +1,487 @@
+use serde::Deserialize;
+
+use super::ToolDefinition;
+
+pub const TOOL_NAME: &str = "control_media";
+
+/// Percent the volume moves for "louder" / "quieter"
+const VOLUME_STEP: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Action {
+    Play,
+    Pause,
+    Toggle,
+    Next,
+    Previous,
+    VolumeUp,
+    VolumeDown,
+    SetVolume,
+    Status,
+}
+
+#[derive(Debug, Deserialize)]
+struct Arguments {
+    action: Action,
+    /// 0-100, for `set_volume`
+    #[serde(default)]
+    volume: Option<u32>,
+}
+
+/// Playback commands every platform can send
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Playback {
+    Play,
+    Pause,
+    Toggle,
+    Next,
+    Previous,
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::Playback;
+
+    /// Volume is the player's own MPRIS volume, not the system's
+    pub const VOLUME_SCOPE: &str = "player";
+
+    const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+    const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+    const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+    async fn dbus_send(args: &[&str]) -> Result<String, String> {
+        let output = tokio::process::Command::new("dbus-send")
+            .args(["--session", "--print-reply"])
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run dbus-send: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// The quoted value on a dbus-send reply line, e.g. `string "Playing"`
+    fn quoted(line: &str) -> Option<&str> {
+        let start = line.find('"')? + 1;
+        let end = start + line[start..].rfind('"')?;
+        Some(&line[start..end])
+    }
+
+    async fn property(player: &str, name: &str) -> Result<String, String> {
+        dbus_send(&[
+            &format!("--dest={}", player),
+            MPRIS_PATH,
+            "org.freedesktop.DBus.Properties.Get",
+            &format!("string:{}", PLAYER_INTERFACE),
+            &format!("string:{}", name),
+        ])
+        .await
+    }
+
+    /// The player that's playing, else one that's paused, else any
+    async fn player() -> Result<String, String> {
+        let names = dbus_send(&[
+            "--dest=org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus.ListNames",
+        ])
+        .await?;
+        let players: Vec<String> = names
+            .lines()
+            .filter_map(quoted)
+            .filter(|name| name.starts_with(MPRIS_PREFIX))
+            .map(|name| name.to_string())
+            .collect();
+
+        let mut best: Option<(u8, &String)> = None;
+        for player in &players {
+            let status = property(player, "PlaybackStatus").await.unwrap_or_default();
+            let rank = if status.contains("\"Playing\"") {
+                2
+            } else if status.contains("\"Paused\"") {
+                1
+            } else {
+                0
+            };
+            if best.is_none_or(|(best_rank, _)| rank > best_rank) {
+                best = Some((rank, player));
+            }
+        }
+        best.map(|(_, player)| player.clone())
+            .ok_or_else(|| "No media player is running".to_string())
+    }
+
+    pub async fn send(command: Playback) -> Result<(), String> {
+        let method = match command {
+            Playback::Play => "Play",
+            Playback::Pause => "Pause",
+            Playback::Toggle => "PlayPause",
+            Playback::Next => "Next",
+            Playback::Previous => "Previous",
+        };
+        let player = player().await?;
+        dbus_send(&[
+            &format!("--dest={}", player),
+            MPRIS_PATH,
+            &format!("{}.{}", PLAYER_INTERFACE, method),
+        ])
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn volume() -> Result<u32, String> {
+        let reply = property(&player().await?, "Volume").await?;
+        // "variant       double 0.5"
+        reply
+            .split_whitespace()
+            .last()
+            .and_then(|volume| volume.parse::<f64>().ok())
+            .map(|volume| (volume * 100.0).round() as u32)
+            .ok_or_else(|| "This player doesn't report its volume".to_string())
+    }
+
+    pub async fn set_volume(percent: u32) -> Result<(), String> {
+        let player = player().await?;
+        dbus_send(&[
+            &format!("--dest={}", player),
+            MPRIS_PATH,
+            "org.freedesktop.DBus.Properties.Set",
+            &format!("string:{}", PLAYER_INTERFACE),
+            "string:Volume",
+            &format!("variant:double:{}", percent as f64 / 100.0),
+        ])
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn now_playing() -> Result<String, String> {
+        let player = player().await?;
+        let status = property(&player, "PlaybackStatus").await?;
+        let metadata = property(&player, "Metadata").await?;
+
+        // Metadata is a dict of `string "key"` lines, each followed by its value on a later line
+        let value_of = |key: &str| -> Option<String> {
+            metadata
+                .lines()
+                .skip_while(|line| quoted(line) != Some(key))
+                .skip(1)
+                .find(|line| line.contains("string \""))
+                .and_then(quoted)
+                .map(|value| value.to_string())
+        };
+
+        let app = player.trim_start_matches(MPRIS_PREFIX);
+        let state = status.lines().find_map(quoted).unwrap_or("Stopped");
+        Ok(match (value_of("xesam:title"), value_of("xesam:artist")) {
+            (Some(title), Some(artist)) => format!("{} in {}: {} by {}", state, app, title, artist),
+            (Some(title), None) => format!("{} in {}: {}", state, app, title),
+            _ => format!("{} in {}", state, app),
+        })
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::Playback;
+    use windows::Media::Control::{
+        GlobalSystemMediaTransportControlsSession, GlobalSystemMediaTransportControlsSessionManager,
+        GlobalSystemMediaTransportControlsSessionPlaybackStatus,
+    };
+    use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+    use windows::Win32::Media::Audio::{eConsole, eRender, IMMDeviceEnumerator, MMDeviceEnumerator};
+    use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED};
+
+    /// Volume is the default output device's
+    pub const VOLUME_SCOPE: &str = "system";
+
+    /// The session Windows shows in its media flyout
+    fn current_session() -> Result<GlobalSystemMediaTransportControlsSession, String> {
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        }
+        GlobalSystemMediaTransportControlsSessionManager::RequestAsync()
+            .and_then(|request| request.get())
+            .and_then(|manager| manager.GetCurrentSession())
+            .map_err(|_| "No media is playing".to_string())
+    }
+
+    fn endpoint_volume() -> windows::core::Result<IAudioEndpointVolume> {
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            enumerator
+                .GetDefaultAudioEndpoint(eRender, eConsole)?
+                .Activate(CLSCTX_ALL, None)
+        }
+    }
+
+    async fn blocking<T: Send + 'static>(
+        f: impl FnOnce() -> Result<T, String> + Send + 'static,
+    ) -> Result<T, String> {
+        tauri::async_runtime::spawn_blocking(f)
+            .await
+            .map_err(|e| format!("Media task failed: {}", e))?
+    }
+
+    pub async fn send(command: Playback) -> Result<(), String> {
+        blocking(move || {
+            let session = current_session()?;
+            let request = match command {
+                Playback::Play => session.TryPlayAsync(),
+                Playback::Pause => session.TryPauseAsync(),
+                Playback::Toggle => session.TryTogglePlayPauseAsync(),
+                Playback::Next => session.TrySkipNextAsync(),
+                Playback::Previous => session.TrySkipPreviousAsync(),
+            };
+            match request.and_then(|request| request.get()) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("The player doesn't support that right now".to_string()),
+                Err(e) => Err(format!("Media control failed: {}", e)),
+            }
+        })
+        .await
+    }
+
+    pub async fn volume() -> Result<u32, String> {
+        blocking(|| unsafe {
+            endpoint_volume()
+                .and_then(|volume| volume.GetMasterVolumeLevelScalar())
+                .map(|level| (level * 100.0).round() as u32)
+                .map_err(|e| format!("Failed to read the volume: {}", e))
+        })
+        .await
+    }
+
+    pub async fn set_volume(percent: u32) -> Result<(), String> {
+        blocking(move || unsafe {
+            endpoint_volume()
+                .and_then(|volume| volume.SetMasterVolumeLevelScalar(percent as f32 / 100.0, std::ptr::null()))
+                .map_err(|e| format!("Failed to set the volume: {}", e))
+        })
+        .await
+    }
+
+    pub async fn now_playing() -> Result<String, String> {
+        blocking(|| {
+            let session = current_session()?;
+            let app = session.SourceAppUserModelId().map(|id| id.to_string()).unwrap_or_default();
+            let playing = session
+                .GetPlaybackInfo()
+                .and_then(|info| info.PlaybackStatus())
+                .is_ok_and(|status| status == GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing);
+            let state = if playing { "Playing" } else { "Paused" };
+
+            let properties = session
+                .TryGetMediaPropertiesAsync()
+                .and_then(|request| request.get())
+                .map_err(|e| format!("Failed to read what's playing: {}", e))?;
+            let title = properties.Title().map(|title| title.to_string()).unwrap_or_default();
+            let artist = properties.Artist().map(|artist| artist.to_string()).unwrap_or_default();
+            Ok(match (title.is_empty(), artist.is_empty()) {
+                (false, false) => format!("{} in {}: {} by {}", state, app, title, artist),
+                (false, true) => format!("{} in {}: {}", state, app, title),
+                _ => format!("{} in {}", state, app),
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use crate::identity;
+    use objc2::encode::{Encode, Encoding};
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use std::ffi::c_void;
+
+    use super::Playback;
+
+    /// Volume is the system output volume
+    pub const VOLUME_SCOPE: &str = "system";
+
+    // From IOKit's ev_keymap.h
+    const NX_KEYTYPE_PLAY: isize = 16;
+    const NX_KEYTYPE_NEXT: isize = 17;
+    const NX_KEYTYPE_PREVIOUS: isize = 18;
+    const NS_EVENT_TYPE_SYSTEM_DEFINED: usize = 14;
+    const NS_SUBTYPE_AUX_CONTROL_BUTTONS: i16 = 8;
+    const KCG_HID_EVENT_TAP: u32 = 0;
+
+    #[repr(C)]
+    struct NSPoint {
+        x: f64,
+        y: f64,
+    }
+
+    unsafe impl Encode for NSPoint {
+        const ENCODING: Encoding = Encoding::Struct("CGPoint", &[f64::ENCODING, f64::ENCODING]);
+    }
+
+    #[repr(transparent)]
+    struct CGEventRef(*mut c_void);
+
+    unsafe impl Encode for CGEventRef {
+        const ENCODING: Encoding = Encoding::Pointer(&Encoding::Struct("__CGEvent", &[]));
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventPost(tap: u32, event: CGEventRef);
+    }
+
+    /// Post a media key press and release, as the keyboard's play/next/previous keys do
+    fn press_media_key(key: isize) -> Result<(), String> {
+        for down in [true, false] {
+            let flags: usize = if down { 0xa00 } else { 0xb00 };
+            let data1 = (key << 16) | ((if down { 0xa } else { 0xb }) << 8);
+            unsafe {
+                let event: Option<Retained<AnyObject>> = msg_send![
+                    class!(NSEvent),
+                    otherEventWithType: NS_EVENT_TYPE_SYSTEM_DEFINED,
+                    location: NSPoint { x: 0.0, y: 0.0 },
+                    modifierFlags: flags,
+                    timestamp: 0.0f64,
+                    windowNumber: 0isize,
+                    context: std::ptr::null_mut::<AnyObject>(),
+                    subtype: NS_SUBTYPE_AUX_CONTROL_BUTTONS,
+                    data1: data1,
+                    data2: -1isize
+                ];
+                let event = event.ok_or("Failed to create a media key event")?;
+                let cg_event: CGEventRef = msg_send![&*event, CGEvent];
+                CGEventPost(KCG_HID_EVENT_TAP, cg_event);
+            }
+        }
+        Ok(())
+    }
+
+    async fn osascript(script: &str) -> Result<String, String> {
+        let output = tokio::process::Command::new("osascript")
+            .args(["-e", script])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run osascript: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Media keys only toggle, so play and pause both do whatever the key does
+    pub async fn send(command: Playback) -> Result<(), String> {
+        let key = match command {
+            Playback::Play | Playback::Pause | Playback::Toggle => NX_KEYTYPE_PLAY,
+            Playback::Next => NX_KEYTYPE_NEXT,
+            Playback::Previous => NX_KEYTYPE_PREVIOUS,
+        };
+        press_media_key(key)
+    }
+
+    pub async fn volume() -> Result<u32, String> {
+        osascript("output volume of (get volume settings)")
+            .await?
+            .parse()
+            .map_err(|_| "Output volume unavailable for this device".to_string())
+    }
+
+    pub async fn set_volume(percent: u32) -> Result<(), String> {
+        osascript(&format!("set volume output volume {}", percent)).await.map(|_| ())
+    }
+
+    /// Media keys don't report anything back
+    pub async fn now_playing() -> Result<String, String> {
+        Err(identity::current().render("{name} can't see what's playing on macOS"))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod platform {
+    use super::Playback;
+
+    pub const VOLUME_SCOPE: &str = "system";
+
+    const UNSUPPORTED: &str = "Media control isn't supported on this platform";
+
+    pub async fn send(_command: Playback) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub async fn volume() -> Result<u32, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub async fn set_volume(_percent: u32) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub async fn now_playing() -> Result<String, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: format!(
+            "Control music or other media playing on the computer: play, pause, skip, go back, \
+             change the {} volume, or say what's playing.",
+            platform::VOLUME_SCOPE
+        ),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": [
+                        "play", "pause", "toggle", "next", "previous",
+                        "volume_up", "volume_down", "set_volume", "status",
+                    ],
+                },
+                "volume": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 100,
+                    "description": "Percent, for set_volume",
+                },
+            },
+            "required": ["action"],
+        }),
+    }
+}
+
+pub async fn call(arguments: serde_json::Value) -> Result<String, String> {
+    let arguments: Arguments = serde_json::from_value(arguments)
+        .map_err(|e| format!("Invalid media arguments: {}", e))?;
+
+    let playback = match arguments.action {
+        Action::Play => Some(Playback::Play),
+        Action::Pause => Some(Playback::Pause),
+        Action::Toggle => Some(Playback::Toggle),
+        Action::Next => Some(Playback::Next),
+        Action::Previous => Some(Playback::Previous),
+        _ => None,
+    };
+    if let Some(playback) = playback {
+        platform::send(playback).await?;
+        log::info!("🎵 Media {:?}", playback);
+        return Ok("Done".to_string());
+    }
+
+    let target = match arguments.action {
+        Action::Status => return platform::now_playing().await,
+        Action::VolumeUp => (platform::volume().await? + VOLUME_STEP).min(100),
+        Action::VolumeDown => platform::volume().await?.saturating_sub(VOLUME_STEP),
+        _ => arguments
+            .volume
+            .ok_or("set_volume needs a volume")?
+            .min(100),
+    };
+    platform::set_volume(target).await?;
+    log::info!("🎵 Media volume set to {}%", target);
+    Ok(format!("Volume is now {}%", target))
+}
@@ -0,0 +1,126 @@
+pub mod actions;
+pub mod calendar;
+pub mod clipboard;
+pub mod home_assistant;
+pub mod media;
+pub mod plugins;
+pub mod screenshot;
+
+use crate::profiles;
+use crate::reminders;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+/// A function the model can call, in the JSON-schema shape both the Realtime API
+/// and chat completions expect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+/// What a tool call hands back to the model
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolOutput {
+    pub text: String,
+    /// Base64 JPEG for the frontend to describe with a vision model; function call
+    /// outputs can only carry text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+impl From<String> for ToolOutput {
+    fn from(text: String) -> Self {
+        Self { text, image: None }
+    }
+}
+
+/// Ask before a tool shares something from the user's machine with the model.
+///
+/// Asked on every call, never remembered; closing the dialog counts as no.
+pub async fn confirm(app: &AppHandle, title: &str, message: String) -> bool {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Share".to_string(),
+            "Don't share".to_string(),
+        ))
+        .show(move |accepted| {
+            let _ = tx.send(accepted);
+        });
+    rx.await.unwrap_or(false)
+}
+
+/// Tools that are configured and can run right now; the frontend registers these
+/// with the session on connect
+#[tauri::command]
+pub async fn list_tools(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<Vec<ToolDefinition>, String> {
+    let mut tools = vec![
+        reminders::definition(),
+        clipboard::definition(),
+        screenshot::definition(),
+        media::definition(),
+    ];
+    if home_assistant::is_configured() {
+        tools.push(home_assistant::definition());
+    }
+    if calendar::is_configured() {
+        tools.push(calendar::definition());
+    }
+    let (action_settings, plugin_settings) = {
+        let settings = state.lock().await;
+        (settings.actions.clone(), settings.plugins.clone())
+    };
+    tools.extend(actions::definition(&action_settings));
+    tools.extend(plugins::definitions(&app, &plugin_settings));
+    tools.retain(|tool| profiles::allows_tool(&tool.name));
+    Ok(tools)
+}
+
+/// Run a tool call from the model. `arguments` is the JSON string the model produced;
+/// the result (or error) is for the model to read back to the user.
+#[tauri::command]
+pub async fn call_tool(
+    name: String,
+    arguments: String,
+    app: AppHandle,
+) -> Result<ToolOutput, String> {
+    let arguments: serde_json::Value = serde_json::from_str(if arguments.trim().is_empty() {
+        "{}"
+    } else {
+        &arguments
+    })
+    .map_err(|e| format!("Invalid arguments for {}: {}", name, e))?;
+
+    if !profiles::allows_tool(&name) {
+        return Err(format!("{} is turned off in the current profile", name));
+    }
+
+    log::info!("🛠️ Tool call {} {}", name, arguments);
+    let result = match name.as_str() {
+        home_assistant::TOOL_NAME => home_assistant::call(arguments).await.map(ToolOutput::from),
+        calendar::TOOL_NAME => calendar::call(arguments).await.map(ToolOutput::from),
+        reminders::TOOL_NAME => reminders::call(&app, arguments).map(ToolOutput::from),
+        clipboard::TOOL_NAME => clipboard::call(&app).await.map(ToolOutput::from),
+        screenshot::TOOL_NAME => screenshot::call(&app, arguments).await,
+        media::TOOL_NAME => media::call(arguments).await.map(ToolOutput::from),
+        actions::TOOL_NAME => actions::call(&app, arguments).await.map(ToolOutput::from),
+        _ => plugins::call(&app, &name, arguments)
+            .await
+            .unwrap_or_else(|| Err(format!("Unknown tool: {}", name))),
+    };
+
+    if let Err(e) = &result {
+        log::warn!("Tool {} failed: {}", name, e);
+    }
+    result
+}
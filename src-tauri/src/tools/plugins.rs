@@ -0,0 +1,411 @@
+use crate::identity;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_opener::OpenerExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+
+use super::{ToolDefinition, ToolOutput};
+
+const PLUGINS_DIR: &str = "plugins";
+const MANIFEST_FILE: &str = "plugin.json";
+/// A plugin that doesn't answer a call in time is restarted on the next one
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Plugins run third-party code, so each one stays off until the user enables it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    /// Ids of enabled plugins
+    pub enabled: Vec<String>,
+}
+
+/// `plugins/<id>/plugin.json`.
+///
+/// The plugin is a sidecar process started from `command` in its own folder. It reads
+/// JSON-RPC 2.0 requests from stdin and writes responses to stdout, one per line:
+/// `{"jsonrpc":"2.0","id":1,"method":"call_tool","params":{"name":"...","arguments":{...}}}`
+/// answered with `{"jsonrpc":"2.0","id":1,"result":{"text":"..."}}` or an `error` object.
+/// Anything it writes to stderr ends up in Eva's log.
+#[derive(Debug, Clone, Deserialize)]
+struct Manifest {
+    id: String,
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    description: String,
+    /// Executable, relative to the plugin folder unless absolute
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+    tools: Vec<ToolDefinition>,
+}
+
+struct Plugin {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+/// What the settings UI shows for each plugin folder, including broken ones
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub tools: Vec<String>,
+    pub enabled: bool,
+    /// Why the plugin can't be loaded; such plugins offer no tools
+    pub error: Option<String>,
+}
+
+struct PluginProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    next_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    id: Option<u64>,
+    result: Option<ToolOutput>,
+    error: Option<RpcError>,
+}
+
+type ProcessSlot = Arc<tokio::sync::Mutex<Option<PluginProcess>>>;
+
+/// Running plugin processes by id, each behind its own lock so one slow plugin
+/// doesn't hold up the others
+static PROCESSES: std::sync::Mutex<BTreeMap<String, ProcessSlot>> = std::sync::Mutex::new(BTreeMap::new());
+
+fn plugins_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(PLUGINS_DIR))
+}
+
+fn is_valid_name(name: &str, max_len: usize) -> bool {
+    !name.is_empty()
+        && name.len() <= max_len
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn built_in_tools() -> [&'static str; 7] {
+    [
+        crate::reminders::TOOL_NAME,
+        super::clipboard::TOOL_NAME,
+        super::screenshot::TOOL_NAME,
+        super::media::TOOL_NAME,
+        super::home_assistant::TOOL_NAME,
+        super::calendar::TOOL_NAME,
+        super::actions::TOOL_NAME,
+    ]
+}
+
+fn load_manifest(dir: &Path) -> Result<Manifest, String> {
+    let contents = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|e| format!("Can't read {}: {}", MANIFEST_FILE, e))?;
+    let manifest: Manifest =
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+
+    let folder = dir.file_name().unwrap_or_default().to_string_lossy();
+    if manifest.id != folder {
+        return Err(format!("Id \"{}\" doesn't match its folder \"{}\"", manifest.id, folder));
+    }
+    if !is_valid_name(&manifest.id, 64) {
+        return Err("Id may only contain letters, digits, '_' and '-'".to_string());
+    }
+    if manifest.name.trim().is_empty() {
+        return Err("Name is missing".to_string());
+    }
+    if !command_path(dir, &manifest.command).is_file() {
+        return Err(format!("Command not found: {}", manifest.command));
+    }
+    if manifest.tools.is_empty() {
+        return Err("No tools declared".to_string());
+    }
+    for tool in &manifest.tools {
+        // The Realtime API's limit for function names
+        if !is_valid_name(&tool.name, 64) {
+            return Err(format!("Invalid tool name \"{}\"", tool.name));
+        }
+        if tool.parameters.get("type").and_then(|t| t.as_str()) != Some("object") {
+            return Err(format!("Tool \"{}\" parameters must be a JSON-schema object", tool.name));
+        }
+    }
+    Ok(manifest)
+}
+
+fn command_path(dir: &Path, command: &str) -> PathBuf {
+    let path = Path::new(command);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        dir.join(path)
+    }
+}
+
+/// Every plugin folder in id order, with the reason it can't be used if it's broken.
+/// Tool names already taken by Eva or an earlier enabled plugin count as broken.
+fn discover(app: &AppHandle, enabled: &[String]) -> Vec<(String, Result<Plugin, String>)> {
+    let Ok(dir) = plugins_dir(app) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    let mut taken: HashSet<String> = built_in_tools().iter().map(|name| name.to_string()).collect();
+    let identity = identity::current();
+    dirs.into_iter()
+        .map(|dir| {
+            let id = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let plugin = load_manifest(&dir).and_then(|manifest| {
+                if let Some(tool) = manifest.tools.iter().find(|tool| taken.contains(&tool.name)) {
+                    return Err(format!(
+                        "Tool \"{}\" is already provided by {}",
+                        tool.name,
+                        identity.render("{name} or another plugin")
+                    ));
+                }
+                if enabled.contains(&id) {
+                    taken.extend(manifest.tools.iter().map(|tool| tool.name.clone()));
+                }
+                Ok(Plugin { dir, manifest })
+            });
+            (id, plugin)
+        })
+        .collect()
+}
+
+fn enabled_plugins(app: &AppHandle, settings: &PluginSettings) -> Vec<Plugin> {
+    discover(app, &settings.enabled)
+        .into_iter()
+        .filter(|(id, _)| settings.enabled.contains(id))
+        .filter_map(|(id, plugin)| {
+            plugin
+                .inspect_err(|e| log::warn!("🧩 Plugin {} can't be loaded: {}", id, e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Tools from every enabled, valid plugin
+pub fn definitions(app: &AppHandle, settings: &PluginSettings) -> Vec<ToolDefinition> {
+    enabled_plugins(app, settings)
+        .into_iter()
+        .flat_map(|plugin| plugin.manifest.tools)
+        .collect()
+}
+
+fn process_slot(id: &str) -> ProcessSlot {
+    let mut processes = PROCESSES.lock().unwrap_or_else(|e| e.into_inner());
+    processes
+        .entry(id.to_string())
+        .or_default()
+        .clone()
+}
+
+fn spawn(plugin: &Plugin) -> Result<PluginProcess, String> {
+    let id = plugin.manifest.id.clone();
+    let mut child = tokio::process::Command::new(command_path(&plugin.dir, &plugin.manifest.command))
+        .args(&plugin.manifest.args)
+        .current_dir(&plugin.dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start plugin {}: {}", id, e))?;
+
+    let stdin = child.stdin.take().ok_or("Plugin stdin unavailable")?;
+    let stdout = child.stdout.take().ok_or("Plugin stdout unavailable")?;
+    if let Some(stderr) = child.stderr.take() {
+        tauri::async_runtime::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                log::info!("🧩 [{}] {}", id, line);
+            }
+        });
+    }
+
+    log::info!("🧩 Started plugin {}", plugin.manifest.id);
+    Ok(PluginProcess {
+        child,
+        stdin,
+        stdout: BufReader::new(stdout).lines(),
+        next_id: 1,
+    })
+}
+
+async fn request(process: &mut PluginProcess, tool: &str, arguments: serde_json::Value) -> Result<ToolOutput, String> {
+    let id = process.next_id;
+    process.next_id += 1;
+
+    let mut line = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "call_tool",
+        "params": { "name": tool, "arguments": arguments },
+    })
+    .to_string();
+    line.push('\n');
+    process
+        .stdin
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| format!("Plugin stopped accepting calls: {}", e))?;
+    process.stdin.flush().await.map_err(|e| e.to_string())?;
+
+    // Skip stray output and answers to calls that timed out earlier
+    loop {
+        let line = process
+            .stdout
+            .next_line()
+            .await
+            .map_err(|e| format!("Failed to read from plugin: {}", e))?
+            .ok_or("Plugin exited")?;
+        let Ok(response) = serde_json::from_str::<RpcResponse>(&line) else {
+            continue;
+        };
+        if response.id != Some(id) {
+            continue;
+        }
+        return match (response.result, response.error) {
+            (_, Some(error)) => Err(error.message),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err("Plugin sent an empty response".to_string()),
+        };
+    }
+}
+
+/// Whether `name` belongs to an enabled plugin; `call_tool` asks before giving up on a name
+pub async fn call(app: &AppHandle, name: &str, arguments: serde_json::Value) -> Option<Result<ToolOutput, String>> {
+    let settings = app.state::<SettingsState>().lock().await.plugins.clone();
+    let plugin = enabled_plugins(app, &settings)
+        .into_iter()
+        .find(|plugin| plugin.manifest.tools.iter().any(|tool| tool.name == name))?;
+
+    let slot = process_slot(&plugin.manifest.id);
+    let mut process = slot.lock().await;
+    let mut running = process.take();
+    // Restart plugins that crashed or exited since the last call
+    if let Some(existing) = running.as_mut() {
+        if !matches!(existing.child.try_wait(), Ok(None)) {
+            log::warn!("🧩 Plugin {} exited, restarting", plugin.manifest.id);
+            running = None;
+        }
+    }
+    let running = match running {
+        Some(running) => running,
+        None => match spawn(&plugin) {
+            Ok(spawned) => spawned,
+            Err(e) => return Some(Err(e)),
+        },
+    };
+    let running = process.insert(running);
+
+    let result = match tokio::time::timeout(CALL_TIMEOUT, request(running, name, arguments)).await {
+        Ok(result) => result,
+        Err(_) => {
+            *process = None;
+            Err(format!("Plugin {} didn't answer within {}s", plugin.manifest.id, CALL_TIMEOUT.as_secs()))
+        }
+    };
+    Some(result)
+}
+
+async fn stop(id: &str) {
+    let slot = process_slot(id);
+    if slot.lock().await.take().is_some() {
+        log::info!("🧩 Stopped plugin {}", id);
+    }
+}
+
+#[tauri::command]
+pub async fn list_plugins(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<Vec<PluginInfo>, String> {
+    let enabled = state.lock().await.plugins.enabled.clone();
+    Ok(discover(&app, &enabled)
+        .into_iter()
+        .map(|(id, plugin)| match plugin {
+            Ok(plugin) => PluginInfo {
+                enabled: enabled.contains(&id),
+                id,
+                name: plugin.manifest.name,
+                version: plugin.manifest.version,
+                description: plugin.manifest.description,
+                tools: plugin.manifest.tools.into_iter().map(|tool| tool.name).collect(),
+                error: None,
+            },
+            Err(e) => PluginInfo {
+                enabled: enabled.contains(&id),
+                name: id.clone(),
+                id,
+                version: String::new(),
+                description: String::new(),
+                tools: Vec::new(),
+                error: Some(e),
+            },
+        })
+        .collect())
+}
+
+/// Takes effect on the next connect; disabling also stops the plugin's process
+#[tauri::command]
+pub async fn set_plugin_enabled(
+    id: String,
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.plugins.enabled.retain(|existing| *existing != id);
+    if enabled {
+        settings.plugins.enabled.push(id.clone());
+    }
+    settings.save(&app)?;
+    drop(settings);
+
+    if !enabled {
+        stop(&id).await;
+    }
+    log::info!("🧩 Plugin {} {}", id, if enabled { "enabled" } else { "disabled" });
+    Ok(format!(
+        "Plugin {} {}. Reconnect to apply.",
+        id,
+        if enabled { "enabled" } else { "disabled" }
+    ))
+}
+
+/// Reveal the plugins folder, creating it first so there's somewhere to drop plugins
+#[tauri::command]
+pub async fn open_plugins_folder(app: AppHandle) -> Result<String, String> {
+    let dir = plugins_dir(&app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create plugins folder: {}", e))?;
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open plugins folder: {}", e))?;
+    Ok(dir.display().to_string())
+}
@@ -0,0 +1,150 @@
+use crate::settings::SettingsState;
+use base64::Engine;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use xcap::image::codecs::jpeg::JpegEncoder;
+use xcap::image::imageops::FilterType;
+use xcap::image::{DynamicImage, RgbaImage};
+use xcap::{Monitor, Window};
+
+use super::{ToolDefinition, ToolOutput};
+
+pub const TOOL_NAME: &str = "capture_screen";
+
+/// Longest side sent on; vision models downscale anything larger anyway
+const MAX_DIMENSION: u32 = 1568;
+const JPEG_QUALITY: u8 = 80;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Target {
+    /// The focused window of another app
+    #[default]
+    Window,
+    /// The whole display that window is on
+    Screen,
+}
+
+#[derive(Debug, Deserialize)]
+struct Arguments {
+    #[serde(default)]
+    target: Target,
+    /// What the user wants to know, passed on to the vision model
+    #[serde(default)]
+    question: String,
+}
+
+struct Capture {
+    image: RgbaImage,
+    /// Shown in the confirmation dialog, e.g. `the "Inbox" window (Mail)`
+    label: String,
+}
+
+/// Eva's own windows are never the window the user means
+fn focused_window() -> Option<Window> {
+    let own_pid = std::process::id();
+    Window::all().ok()?.into_iter().find(|window| {
+        window.is_focused().unwrap_or(false)
+            && !window.is_minimized().unwrap_or(true)
+            && window.pid().ok() != Some(own_pid)
+    })
+}
+
+fn capture(target: Target) -> Result<Capture, String> {
+    let window = focused_window();
+
+    if let (Target::Window, Some(window)) = (target, &window) {
+        let title = window.title().unwrap_or_default();
+        let app_name = window.app_name().unwrap_or_default();
+        let image = window
+            .capture_image()
+            .map_err(|e| format!("Failed to capture the window: {}", e))?;
+        return Ok(Capture {
+            image,
+            label: format!("the \"{}\" window ({})", title, app_name),
+        });
+    }
+
+    // The display the user is working on, or the primary one
+    let monitor = match window.and_then(|window| window.current_monitor().ok()) {
+        Some(monitor) => monitor,
+        None => Monitor::all()
+            .map_err(|e| format!("Failed to list displays: {}", e))?
+            .into_iter()
+            .find(|monitor| monitor.is_primary().unwrap_or(false))
+            .ok_or("No display to capture")?,
+    };
+    let image = monitor
+        .capture_image()
+        .map_err(|e| format!("Failed to capture the screen (is screen recording allowed?): {}", e))?;
+    Ok(Capture {
+        image,
+        label: format!("your screen ({})", monitor.name().unwrap_or_default()),
+    })
+}
+
+fn encode_jpeg(image: RgbaImage) -> Result<String, String> {
+    let mut image = DynamicImage::ImageRgba8(image);
+    if image.width().max(image.height()) > MAX_DIMENSION {
+        image = image.resize(MAX_DIMENSION, MAX_DIMENSION, FilterType::Triangle);
+    }
+
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY)
+        .encode_image(&image.to_rgb8())
+        .map_err(|e| format!("Failed to encode screenshot: {}", e))?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(jpeg))
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: TOOL_NAME.to_string(),
+        description: "Look at the user's screen, e.g. for \"what does this error mean\" or \
+            \"summarize this page\". The user is asked to allow it each time."
+            .to_string(),
+        parameters: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "target": {
+                    "type": "string",
+                    "enum": ["window", "screen"],
+                    "description": "The window the user is working in (default), or the whole screen",
+                },
+                "question": {
+                    "type": "string",
+                    "description": "What the user wants to know about it",
+                },
+            },
+        }),
+    }
+}
+
+/// Captured before asking, so the dialog isn't in the picture; dropped unless the user allows it
+pub async fn call(app: &AppHandle, arguments: serde_json::Value) -> Result<ToolOutput, String> {
+    let arguments: Arguments = serde_json::from_value(arguments)
+        .map_err(|e| format!("Invalid screenshot arguments: {}", e))?;
+
+    let target = arguments.target;
+    let capture = tauri::async_runtime::spawn_blocking(move || capture(target))
+        .await
+        .map_err(|e| format!("Screenshot task failed: {}", e))??;
+
+    let identity = app.state::<SettingsState>().lock().await.identity.clone();
+    let message = format!("{} {}.", identity.render("{name} wants to look at"), capture.label);
+    if !super::confirm(app, "Share screenshot?", message).await {
+        log::info!("📸 Screenshot sharing declined");
+        return Err("The user declined to share their screen".to_string());
+    }
+
+    let label = capture.label;
+    let image = tauri::async_runtime::spawn_blocking(move || encode_jpeg(capture.image))
+        .await
+        .map_err(|e| format!("Screenshot task failed: {}", e))??;
+
+    log::info!("📸 Shared a screenshot of {}", label);
+    let mut text = format!("Screenshot of {}.", label);
+    if !arguments.question.trim().is_empty() {
+        text.push_str(&format!(" The user asks: {}", arguments.question.trim()));
+    }
+    Ok(ToolOutput { text, image: Some(image) })
+}
@@ -0,0 +1,70 @@
+use crate::identity::IdentitySettings;
+use crate::overlay;
+use crate::settings::SettingsState;
+use std::sync::Mutex;
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+
+pub const TRAY_ID: &str = "eva";
+pub const MAIN_WINDOW: &str = "main";
+
+/// Last status passed to `set_status`, kept so a rename can redraw the tooltip
+static STATUS: Mutex<Option<String>> = Mutex::new(None);
+
+/// Create the tray icon; its tooltip doubles as a status indicator and a click opens the assistant
+pub fn init(app: &AppHandle, identity: &IdentitySettings) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(identity.render("{name}"))
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                let app = tray.app_handle().clone();
+                show_main_window(&app);
+                tauri::async_runtime::spawn(async move { overlay::release_click_through(&app).await });
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Bring the main window back in front of the user, e.g. when running tray-only
+pub fn show_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    if let Err(e) = window.show().and_then(|_| window.unminimize()).and_then(|_| window.set_focus()) {
+        log::warn!("Failed to show main window: {}", e);
+    }
+}
+
+/// Show a status next to the assistant name, or clear it with `None`
+pub async fn set_status(app: &AppHandle, status: Option<&str>) {
+    if let Ok(mut current) = STATUS.lock() {
+        *current = status.map(str::to_string);
+    }
+    refresh(app).await;
+}
+
+/// Redraw the tooltip and title, e.g. after the assistant is renamed
+pub async fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let identity = app.state::<SettingsState>().lock().await.identity.clone();
+    let status = STATUS.lock().ok().and_then(|status| status.clone());
+    let tooltip = match &status {
+        Some(status) => identity.render(&format!("{{name}} - {}", status)),
+        None => identity.render("{name}"),
+    };
+
+    if let Err(e) = tray.set_tooltip(Some(&tooltip)) {
+        log::warn!("Failed to update tray tooltip: {}", e);
+    }
+    // Only macOS renders a title next to the menu bar icon
+    if let Err(e) = tray.set_title(status.as_deref()) {
+        log::warn!("Failed to update tray title: {}", e);
+    }
+}
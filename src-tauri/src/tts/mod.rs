@@ -0,0 +1,116 @@
+/// Local text-to-speech for when the Realtime API isn't producing audio
+mod piper;
+
+pub use piper::PiperSynthesizer;
+
+use crate::settings::SettingsState;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Turns text into mono PCM16 audio
+pub trait Synthesizer: Send + Sync {
+    /// Returns the samples and their sample rate
+    fn synthesize(&self, text: &str) -> Result<(Vec<i16>, u32), String>;
+}
+
+/// Which local engine speaks responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TtsEngine {
+    #[default]
+    Disabled,
+    Piper,
+}
+
+/// Local text-to-speech settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtsSettings {
+    pub engine: TtsEngine,
+    pub piper_binary: String,
+    pub piper_model_path: String,
+    /// Speaking rate; values above 1.0 speak slower
+    pub length_scale: f32,
+}
+
+impl Default for TtsSettings {
+    fn default() -> Self {
+        Self {
+            engine: TtsEngine::default(),
+            piper_binary: "piper".to_string(),
+            piper_model_path: "models/piper/en_US-amy-medium.onnx".to_string(),
+            length_scale: 1.0,
+        }
+    }
+}
+
+impl TtsSettings {
+    /// Build the configured synthesizer, if any
+    pub fn synthesizer(&self) -> Option<Box<dyn Synthesizer>> {
+        match self.engine {
+            TtsEngine::Disabled => None,
+            TtsEngine::Piper => Some(Box::new(PiperSynthesizer::new(
+                &self.piper_binary,
+                &self.piper_model_path,
+                self.length_scale,
+            ))),
+        }
+    }
+}
+
+/// Synthesized speech ready for the playback service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechAudio {
+    /// Base64 little-endian PCM16, same encoding as Realtime audio deltas
+    pub audio: String,
+    pub sample_rate: u32,
+    pub duration_ms: u64,
+}
+
+impl SpeechAudio {
+    pub fn from_samples(samples: &[i16], sample_rate: u32) -> Self {
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        Self {
+            audio: base64::engine::general_purpose::STANDARD.encode(bytes),
+            sample_rate,
+            duration_ms: samples.len() as u64 * 1000 / sample_rate.max(1) as u64,
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn synthesize_speech(
+    text: String,
+    state: tauri::State<'_, SettingsState>,
+) -> Result<SpeechAudio, String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Nothing to synthesize".to_string());
+    }
+
+    let synthesizer = state.lock().await.tts.synthesizer()
+        .ok_or("Local text-to-speech is disabled")?;
+
+    log::info!("🗣️  Synthesizing {} characters locally", text.len());
+
+    let (samples, sample_rate) = tokio::task::spawn_blocking(move || synthesizer.synthesize(&text))
+        .await
+        .map_err(|e| format!("Speech synthesis task failed: {}", e))??;
+
+    Ok(SpeechAudio::from_samples(&samples, sample_rate))
+}
+
+#[tauri::command]
+pub async fn set_tts_engine(
+    engine: TtsEngine,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.tts.engine = engine;
+    settings.save(&app)?;
+
+    log::info!("Local text-to-speech engine set to {:?}", engine);
+    Ok(format!("Local text-to-speech engine set to {:?}", engine))
+}
@@ -0,0 +1,76 @@
+use super::Synthesizer;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Piper voices are 22.05kHz unless their config says otherwise
+const DEFAULT_PIPER_SAMPLE_RATE: u32 = 22050;
+
+/// Synthesizer backed by the Piper CLI (https://github.com/rhasspy/piper)
+pub struct PiperSynthesizer {
+    binary: String,
+    model_path: String,
+    length_scale: f32,
+}
+
+impl PiperSynthesizer {
+    pub fn new(binary: &str, model_path: &str, length_scale: f32) -> Self {
+        Self {
+            binary: binary.to_string(),
+            model_path: model_path.to_string(),
+            length_scale,
+        }
+    }
+
+    /// Read the voice's sample rate from the `<model>.onnx.json` config next to it
+    fn sample_rate(&self) -> u32 {
+        let config_path = format!("{}.json", self.model_path);
+        std::fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|config| serde_json::from_str::<serde_json::Value>(&config).ok())
+            .and_then(|config| config["audio"]["sample_rate"].as_u64())
+            .map(|rate| rate as u32)
+            .unwrap_or(DEFAULT_PIPER_SAMPLE_RATE)
+    }
+}
+
+impl Synthesizer for PiperSynthesizer {
+    fn synthesize(&self, text: &str) -> Result<(Vec<i16>, u32), String> {
+        if !std::path::Path::new(&self.model_path).exists() {
+            return Err(format!("Piper voice model not found: {}", self.model_path));
+        }
+
+        let mut child = Command::new(&self.binary)
+            .args(["--model", &self.model_path, "--output_raw"])
+            .args(["--length_scale", &self.length_scale.to_string()])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start Piper ({}): {}", self.binary, e))?;
+
+        // Piper treats each line as an utterance, so flatten the response onto one line
+        let line = text.replace(['\r', '\n'], " ");
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "{}", line)
+                .map_err(|e| format!("Failed to send text to Piper: {}", e))?;
+        }
+
+        let output = child.wait_with_output()
+            .map_err(|e| format!("Piper failed: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Piper exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let samples = output.stdout
+            .chunks_exact(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect();
+
+        Ok((samples, self.sample_rate()))
+    }
+}
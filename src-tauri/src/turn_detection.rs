@@ -0,0 +1,88 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// How the end of a spoken turn is found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnDetectionMode {
+    /// The Realtime API listens for silence and answers on its own
+    #[default]
+    ServerVad,
+    /// Eva commits the turn when recording stops, e.g. with push-to-talk
+    Manual,
+}
+
+/// The Realtime API `turn_detection` session config
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TurnDetectionSettings {
+    pub mode: TurnDetectionMode,
+    /// Speech probability (0-1) that counts as speech; higher needs louder speech
+    pub threshold: f32,
+    /// Audio kept from before speech was detected
+    pub prefix_padding_ms: u32,
+    /// Silence that ends the turn
+    pub silence_duration_ms: u32,
+}
+
+impl Default for TurnDetectionSettings {
+    fn default() -> Self {
+        Self {
+            mode: TurnDetectionMode::ServerVad,
+            threshold: 0.5,
+            prefix_padding_ms: 300,
+            silence_duration_ms: 500,
+        }
+    }
+}
+
+impl TurnDetectionSettings {
+    fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err("The VAD threshold must be between 0 and 1".to_string());
+        }
+        if self.prefix_padding_ms > 5_000 {
+            return Err("Prefix padding can be at most 5000ms".to_string());
+        }
+        if !(100..=10_000).contains(&self.silence_duration_ms) {
+            return Err("Silence duration must be between 100 and 10000ms".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn get_turn_detection(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<TurnDetectionSettings, String> {
+    Ok(state.lock().await.turn_detection)
+}
+
+/// Save the turn detection config; the frontend pushes it to the live session with `session.update`
+#[tauri::command]
+pub async fn set_turn_detection(
+    turn_detection: TurnDetectionSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    turn_detection.validate()?;
+
+    let mut settings = state.lock().await;
+    settings.turn_detection = turn_detection;
+    settings.save(&app)?;
+    drop(settings);
+
+    if let Err(e) = app.emit("turn-detection-changed", &turn_detection) {
+        log::error!("Failed to emit turn detection change: {}", e);
+    }
+
+    log::info!("🗣️ Turn detection set to {:?}", turn_detection);
+    Ok(match turn_detection.mode {
+        TurnDetectionMode::ServerVad => format!(
+            "Turns end after {}ms of silence",
+            turn_detection.silence_duration_ms
+        ),
+        TurnDetectionMode::Manual => "Turns end when recording stops".to_string(),
+    })
+}
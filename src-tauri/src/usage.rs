@@ -0,0 +1,265 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+const USAGE_STORE_FILE: &str = "usage.json";
+const USAGE_KEY: &str = "usage";
+/// Fraction of the monthly budget that triggers the early warning
+const BUDGET_WARNING_THRESHOLD: f64 = 0.8;
+
+/// Usage tracking settings; prices are USD per million tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageSettings {
+    /// Monthly spending limit in USD, 0 disables budget warnings
+    pub monthly_budget_usd: f64,
+    pub text_input_price: f64,
+    pub cached_input_price: f64,
+    pub audio_input_price: f64,
+    pub text_output_price: f64,
+    pub audio_output_price: f64,
+}
+
+impl Default for UsageSettings {
+    fn default() -> Self {
+        // gpt-4o-realtime-preview list prices
+        Self {
+            monthly_budget_usd: 0.0,
+            text_input_price: 5.0,
+            cached_input_price: 2.5,
+            audio_input_price: 100.0,
+            text_output_price: 20.0,
+            audio_output_price: 200.0,
+        }
+    }
+}
+
+/// Token counts from a Realtime `response.done` usage block
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub text_input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub audio_input_tokens: u64,
+    pub text_output_tokens: u64,
+    pub audio_output_tokens: u64,
+}
+
+impl TokenUsage {
+    fn cost_usd(&self, prices: &UsageSettings) -> f64 {
+        let uncached_text = self.text_input_tokens.saturating_sub(self.cached_input_tokens);
+        (uncached_text as f64 * prices.text_input_price
+            + self.cached_input_tokens as f64 * prices.cached_input_price
+            + self.audio_input_tokens as f64 * prices.audio_input_price
+            + self.text_output_tokens as f64 * prices.text_output_price
+            + self.audio_output_tokens as f64 * prices.audio_output_price)
+            / 1_000_000.0
+    }
+}
+
+/// Accumulated usage for one day or month
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageTotals {
+    pub tokens: TokenUsage,
+    pub responses: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, other: &UsageTotals) {
+        self.tokens.text_input_tokens += other.tokens.text_input_tokens;
+        self.tokens.cached_input_tokens += other.tokens.cached_input_tokens;
+        self.tokens.audio_input_tokens += other.tokens.audio_input_tokens;
+        self.tokens.text_output_tokens += other.tokens.text_output_tokens;
+        self.tokens.audio_output_tokens += other.tokens.audio_output_tokens;
+        self.responses += other.responses;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+/// Persisted usage history keyed by UTC date (`YYYY-MM-DD`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct UsageHistory {
+    days: BTreeMap<String, UsageTotals>,
+    /// `YYYY-MM` plus level of the last budget warning, so each level fires once a month
+    last_warning: Option<(String, BudgetLevel)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BudgetLevel {
+    Approaching,
+    Exceeded,
+}
+
+/// Event payload for `usage-budget-warning`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetWarningEvent {
+    pub level: BudgetLevel,
+    pub month_cost_usd: f64,
+    pub monthly_budget_usd: f64,
+}
+
+/// Response of `get_usage_stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub today: UsageTotals,
+    pub month: UsageTotals,
+    pub all_time: UsageTotals,
+    /// Daily totals for the current month, oldest first
+    pub days: Vec<(String, UsageTotals)>,
+    pub monthly_budget_usd: f64,
+}
+
+/// Today's UTC date as `YYYY-MM-DD`
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn load_history(app: &AppHandle) -> Result<UsageHistory, String> {
+    let store = app.store(USAGE_STORE_FILE)
+        .map_err(|e| format!("Failed to open usage store: {}", e))?;
+
+    Ok(store.get(USAGE_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn save_history(app: &AppHandle, history: &UsageHistory) -> Result<(), String> {
+    let store = app.store(USAGE_STORE_FILE)
+        .map_err(|e| format!("Failed to open usage store: {}", e))?;
+
+    let value = serde_json::to_value(history)
+        .map_err(|e| format!("Failed to serialize usage: {}", e))?;
+
+    store.set(USAGE_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save usage: {}", e))
+}
+
+fn month_totals(history: &UsageHistory, month: &str) -> UsageTotals {
+    let mut totals = UsageTotals::default();
+    history.days.iter()
+        .filter(|(date, _)| date.starts_with(month))
+        .for_each(|(_, day)| totals.add(day));
+    totals
+}
+
+/// Add one response's token usage to today's totals
+#[tauri::command]
+pub async fn record_usage(
+    usage: TokenUsage,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<UsageTotals, String> {
+    let settings = state.lock().await.usage.clone();
+    let today = today();
+    let month = today[..7].to_string();
+
+    let mut history = load_history(&app)?;
+    let entry = UsageTotals { tokens: usage, responses: 1, cost_usd: usage.cost_usd(&settings) };
+    history.days.entry(today.clone()).or_default().add(&entry);
+    let day_totals = history.days[&today];
+
+    let month_cost_usd = month_totals(&history, &month).cost_usd;
+    let level = if settings.monthly_budget_usd <= 0.0 {
+        None
+    } else if month_cost_usd >= settings.monthly_budget_usd {
+        Some(BudgetLevel::Exceeded)
+    } else if month_cost_usd >= settings.monthly_budget_usd * BUDGET_WARNING_THRESHOLD {
+        Some(BudgetLevel::Approaching)
+    } else {
+        None
+    };
+
+    if let Some(level) = level {
+        let already_warned = matches!(&history.last_warning, Some((warned_month, warned_level)) if *warned_month == month && *warned_level >= level);
+        if !already_warned {
+            history.last_warning = Some((month, level));
+            log::warn!("💸 Usage budget {:?}: ${:.2} of ${:.2}", level, month_cost_usd, settings.monthly_budget_usd);
+
+            let event = BudgetWarningEvent {
+                level,
+                month_cost_usd,
+                monthly_budget_usd: settings.monthly_budget_usd,
+            };
+            if let Err(e) = app.emit("usage-budget-warning", &event) {
+                log::error!("Failed to emit budget warning: {}", e);
+            }
+        }
+    }
+
+    save_history(&app, &history)?;
+    Ok(day_totals)
+}
+
+#[tauri::command]
+pub async fn get_usage_stats(
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<UsageStats, String> {
+    let monthly_budget_usd = state.lock().await.usage.monthly_budget_usd;
+    let today = today();
+    let month = &today[..7];
+    let history = load_history(&app)?;
+
+    let mut all_time = UsageTotals::default();
+    history.days.values().for_each(|day| all_time.add(day));
+
+    Ok(UsageStats {
+        today: history.days.get(&today).copied().unwrap_or_default(),
+        month: month_totals(&history, month),
+        all_time,
+        days: history.days.iter()
+            .filter(|(date, _)| date.starts_with(month))
+            .map(|(date, totals)| (date.clone(), *totals))
+            .collect(),
+        monthly_budget_usd,
+    })
+}
+
+#[tauri::command]
+pub async fn set_usage_budget(
+    monthly_budget_usd: f64,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if !monthly_budget_usd.is_finite() || monthly_budget_usd < 0.0 {
+        return Err("Budget must be a positive amount, or 0 to disable".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.usage.monthly_budget_usd = monthly_budget_usd;
+    settings.save(&app)?;
+
+    log::info!("Monthly usage budget set to ${:.2}", monthly_budget_usd);
+    Ok(format!("Monthly budget set to ${:.2}", monthly_budget_usd))
+}
+
+#[tauri::command]
+pub async fn reset_usage_stats(app: AppHandle) -> Result<String, String> {
+    save_history(&app, &UsageHistory::default())?;
+    log::info!("Usage statistics reset");
+    Ok("Usage statistics reset".to_string())
+}
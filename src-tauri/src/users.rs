@@ -0,0 +1,308 @@
+use crate::chat_window;
+use crate::persona;
+use crate::secrets;
+use crate::settings::SettingsState;
+use crate::speaker_id;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Per-user stores and directories live under `<app data>/users/<slug>/`
+const USERS_DIR: &str = "users";
+const MAX_USER_NAME_LEN: usize = 32;
+
+/// Active user, read from places that can't await the settings lock; empty for the default user
+static ACTIVE_USER: Mutex<String> = Mutex::new(String::new());
+static SWITCH_BY_VOICE: AtomicBool = AtomicBool::new(false);
+
+/// Someone who shares this Eva, with their own history, memory, enrolled voice and persona
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserProfile {
+    pub name: String,
+    /// Persona to switch to with this user; empty keeps the active persona
+    pub persona: String,
+}
+
+/// Named users and who is using Eva now
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    pub users: Vec<UserProfile>,
+    /// Active user; empty for the default user, whose data is what Eva kept before there were users
+    pub active: String,
+    /// Switch to whichever user's enrolled voice said the wake word
+    pub switch_by_voice: bool,
+}
+
+impl UserSettings {
+    fn user(&self, name: &str) -> Option<&UserProfile> {
+        self.users.iter().find(|user| user.name == name)
+    }
+}
+
+/// A user as the settings UI shows it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserStatus {
+    pub name: String,
+    pub persona: String,
+    /// Has their own OpenAI key instead of the shared one
+    pub has_api_key: bool,
+}
+
+/// Returned by `get_users`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsersState {
+    pub users: Vec<UserStatus>,
+    pub active: String,
+    pub switch_by_voice: bool,
+}
+
+/// Event payload for `user-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserChangedEvent {
+    /// `None` for the default user
+    pub active: Option<String>,
+    /// Recognized by voice rather than picked
+    pub automatic: bool,
+}
+
+/// Names become directory names, so keep them to a safe, case-insensitive charset
+fn slug(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn api_key_secret(name: &str) -> String {
+    format!("openai-api-key-{}", slug(name))
+}
+
+pub fn active_user() -> String {
+    ACTIVE_USER.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Path of `file` (a store or directory relative to the app data directory) for `user`; the
+/// default user keeps the top-level one
+pub fn user_file(user: &str, file: &str) -> String {
+    if user.is_empty() {
+        file.to_string()
+    } else {
+        format!("{}/{}/{}", USERS_DIR, slug(user), file)
+    }
+}
+
+/// `user_file` for the active user
+pub fn scoped(file: &str) -> String {
+    user_file(&active_user(), file)
+}
+
+/// Apply the saved user at startup, before anything loads per-user data
+pub fn restore(settings: &UserSettings) {
+    *ACTIVE_USER.lock().unwrap_or_else(|e| e.into_inner()) = settings.active.clone();
+    SWITCH_BY_VOICE.store(settings.switch_by_voice, Ordering::Relaxed);
+}
+
+/// Everything per-user follows `ACTIVE_USER`; the rest is reloaded here and by the frontend on `user-changed`
+async fn switch(app: &AppHandle, name: String, automatic: bool) -> Result<UserChangedEvent, String> {
+    let state = app.state::<SettingsState>();
+    let mut settings = state.lock().await;
+    if !name.is_empty() && settings.users.user(&name).is_none() {
+        return Err(format!("Unknown user: {}", name));
+    }
+    settings.users.active = name.clone();
+    let persona = settings.users.user(&name).map(|user| user.persona.clone()).unwrap_or_default();
+    let persona_event = settings.persona.activate(&persona);
+    settings.save(app)?;
+    let speaker_settings = settings.speaker_id.clone();
+    drop(settings);
+
+    *ACTIVE_USER.lock().unwrap_or_else(|e| e.into_inner()) = name.clone();
+    speaker_id::reload(app, &speaker_settings);
+    // The chat window's history belonged to the previous user
+    chat_window::clear_chat_history(app.clone()).await?;
+    if let Some(event) = persona_event {
+        persona::emit_changed(app, &event);
+    }
+
+    let event = UserChangedEvent { active: Some(name).filter(|name| !name.is_empty()), automatic };
+    if let Err(e) = app.emit("user-changed", &event) {
+        log::error!("Failed to emit user change: {}", e);
+    }
+    Ok(event)
+}
+
+/// After a wake word, switch to the user whose voice it was. Runs in the background so the wake
+/// word isn't held up; the frontend switches history when `user-changed` arrives.
+pub fn spawn_identify(app: &AppHandle) {
+    if !SWITCH_BY_VOICE.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let (speaker_settings, mut candidates) = {
+            let state = app.state::<SettingsState>();
+            let settings = state.lock().await;
+            let names: Vec<String> = settings.users.users.iter().map(|user| user.name.clone()).collect();
+            (settings.speaker_id.clone(), names)
+        };
+        candidates.push(String::new());
+
+        let identify_app = app.clone();
+        let speaker = tokio::task::spawn_blocking(move || {
+            speaker_id::identify_wake_speaker(&identify_app, &speaker_settings, &candidates)
+        })
+        .await
+        .ok()
+        .flatten();
+
+        let Some(speaker) = speaker.filter(|speaker| *speaker != active_user()) else {
+            return;
+        };
+        match switch(&app, speaker.clone(), true).await {
+            Ok(_) => log::info!("👥 Recognized {}", if speaker.is_empty() { "the default user" } else { &speaker }),
+            Err(e) => log::warn!("Failed to switch user: {}", e),
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_users(state: tauri::State<'_, SettingsState>) -> Result<UsersState, String> {
+    let settings = state.lock().await.users.clone();
+    Ok(UsersState {
+        users: settings
+            .users
+            .iter()
+            .map(|user| UserStatus {
+                name: user.name.clone(),
+                persona: user.persona.clone(),
+                has_api_key: secrets::get(&api_key_secret(&user.name)).is_some(),
+            })
+            .collect(),
+        active: settings.active,
+        switch_by_voice: settings.switch_by_voice,
+    })
+}
+
+/// Create or update a user. `api_key`: `None` keeps their key, empty goes back to the shared one.
+#[tauri::command]
+pub async fn save_user(
+    user: UserProfile,
+    api_key: Option<String>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let user = UserProfile { name: user.name.trim().to_string(), persona: user.persona.trim().to_string() };
+    if user.name.is_empty() {
+        return Err("User name cannot be empty".to_string());
+    }
+    if user.name.chars().count() > MAX_USER_NAME_LEN {
+        return Err(format!("User name must be at most {} characters", MAX_USER_NAME_LEN));
+    }
+
+    let mut settings = state.lock().await;
+    if !user.persona.is_empty() && !settings.persona.profiles.iter().any(|persona| persona.name == user.persona) {
+        return Err(format!("Unknown persona: {}", user.persona));
+    }
+    // Two names with the same slug would share a data directory
+    if settings.users.users.iter().any(|existing| existing.name != user.name && slug(&existing.name) == slug(&user.name)) {
+        return Err(format!("The name {} is too close to an existing user's", user.name));
+    }
+    match settings.users.users.iter_mut().find(|existing| existing.name == user.name) {
+        Some(existing) => *existing = user.clone(),
+        None => settings.users.users.push(user.clone()),
+    }
+    settings.save(&app)?;
+    drop(settings);
+
+    match api_key.map(|key| key.trim().to_string()) {
+        Some(key) if key.is_empty() => secrets::delete(&api_key_secret(&user.name)),
+        Some(key) => secrets::set(&api_key_secret(&user.name), &key)?,
+        None => {}
+    }
+
+    log::info!("👥 User '{}' saved", user.name);
+    Ok(format!("User '{}' saved", user.name))
+}
+
+/// Remove a user along with their history, memory, enrolled voice and key
+#[tauri::command]
+pub async fn delete_user(
+    name: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    let before = settings.users.users.len();
+    settings.users.users.retain(|user| user.name != name);
+    if settings.users.users.len() == before {
+        return Err(format!("Unknown user: {}", name));
+    }
+    settings.save(&app)?;
+    let was_active = settings.users.active == name;
+    drop(settings);
+
+    if was_active {
+        switch(&app, String::new(), false).await?;
+    }
+    secrets::delete(&api_key_secret(&name));
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(USERS_DIR)
+        .join(slug(&name));
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}'s data: {}", name, e))?;
+    }
+
+    log::info!("👥 User '{}' deleted", name);
+    Ok(format!("User '{}' deleted", name))
+}
+
+/// Switch to a user, or the default user with an empty name
+#[tauri::command]
+pub async fn switch_user(name: String, app: AppHandle) -> Result<String, String> {
+    switch(&app, name.clone(), false).await?;
+
+    if name.is_empty() {
+        log::info!("👥 Switched to the default user");
+        Ok("Switched to the default user".to_string())
+    } else {
+        log::info!("👥 Switched to user '{}'", name);
+        Ok(format!("Switched to {}", name))
+    }
+}
+
+/// Needs enrolled voices; with voice lock on, only the active user's voice gets through at all
+#[tauri::command]
+pub async fn set_user_switch_by_voice(
+    enabled: bool,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.users.switch_by_voice = enabled;
+    settings.save(&app)?;
+    let identity = settings.identity.clone();
+    drop(settings);
+
+    SWITCH_BY_VOICE.store(enabled, Ordering::Relaxed);
+    log::info!("👥 Switching users by voice {}", if enabled { "on" } else { "off" });
+    Ok(if enabled {
+        identity.render("{name} switches to whoever says the wake word")
+    } else {
+        "Users only change when you switch them".to_string()
+    })
+}
+
+/// The active user's own OpenAI key, if they have one; the frontend falls back to the shared key
+#[tauri::command]
+pub async fn get_user_api_key() -> Result<Option<String>, String> {
+    let user = active_user();
+    Ok(if user.is_empty() { None } else { secrets::get(&api_key_secret(&user)) })
+}
@@ -0,0 +1,234 @@
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// Peaks below this count as silence when finding where an utterance ends
+const SILENCE_AMPLITUDE: i16 = 300;
+/// An utterance never lasts longer than this, so steady background noise can't suppress wake words forever
+const MAX_UTTERANCE_MS: u64 = 10_000;
+
+const DEFAULT_SETTINGS: WakeCooldownSettings = WakeCooldownSettings {
+    default_ms: 2000,
+    per_keyword: BTreeMap::new(),
+    suppress_same_utterance: true,
+    utterance_gap_ms: 400,
+};
+
+static SETTINGS: Mutex<WakeCooldownSettings> = Mutex::new(DEFAULT_SETTINGS);
+
+/// When a wake word may fire again after a detection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WakeCooldownSettings {
+    /// Cooldown for keywords without their own entry
+    pub default_ms: u64,
+    /// Cooldown by lowercase keyword name, as shown to the user (e.g. "hey eva")
+    pub per_keyword: BTreeMap<String, u64>,
+    /// Ignore further detections until the utterance that triggered the last one is over
+    pub suppress_same_utterance: bool,
+    /// Silence that ends an utterance
+    pub utterance_gap_ms: u64,
+}
+
+impl Default for WakeCooldownSettings {
+    fn default() -> Self {
+        DEFAULT_SETTINGS
+    }
+}
+
+impl WakeCooldownSettings {
+    pub fn cooldown_ms(&self, keyword: &str) -> u64 {
+        self.per_keyword
+            .get(&keyword.to_lowercase())
+            .copied()
+            .unwrap_or(self.default_ms)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let too_long = |ms: u64| ms > 60_000;
+        if too_long(self.default_ms) || self.per_keyword.values().copied().any(too_long) {
+            return Err("Cooldowns can be at most 60 seconds".to_string());
+        }
+        if !(100..=5_000).contains(&self.utterance_gap_ms) {
+            return Err("The utterance gap must be between 100 and 5000ms".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Why a detection didn't fire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suppressed {
+    /// Within the keyword's cooldown; milliseconds left
+    Cooldown(u64),
+    /// Still the utterance that triggered the previous detection
+    SameUtterance,
+}
+
+/// Decides which detections fire, on the audio timeline so live and offline detection agree
+#[derive(Debug, Default)]
+pub struct DetectionGate {
+    now_ms: u64,
+    last_fired_ms: HashMap<String, u64>,
+    /// Set at a detection and cleared once its utterance ends
+    utterance_started_ms: Option<u64>,
+    silent_ms: u64,
+}
+
+impl DetectionGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance by one frame; every frame must be passed in, including ones not run through Porcupine
+    pub fn observe_frame(&mut self, max_amplitude: i16, frame_ms: u64) {
+        self.now_ms += frame_ms;
+        if max_amplitude < SILENCE_AMPLITUDE {
+            self.silent_ms += frame_ms;
+        } else {
+            self.silent_ms = 0;
+        }
+
+        if let Some(started) = self.utterance_started_ms {
+            let gap_ms = SETTINGS.lock().unwrap_or_else(|e| e.into_inner()).utterance_gap_ms;
+            if self.silent_ms >= gap_ms || self.now_ms - started >= MAX_UTTERANCE_MS {
+                self.utterance_started_ms = None;
+            }
+        }
+    }
+
+    /// Current position on the audio timeline
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// Whether a detection of `keyword` in the latest frame should fire; a firing detection restarts its cooldown
+    pub fn check(&mut self, keyword: &str) -> Result<(), Suppressed> {
+        let settings = settings();
+        if let Some(last) = self.last_fired_ms.get(keyword) {
+            let elapsed = self.now_ms - last;
+            let cooldown = settings.cooldown_ms(keyword);
+            if elapsed < cooldown {
+                return Err(Suppressed::Cooldown(cooldown - elapsed));
+            }
+        }
+        if settings.suppress_same_utterance && self.utterance_started_ms.is_some() {
+            return Err(Suppressed::SameUtterance);
+        }
+
+        self.last_fired_ms.insert(keyword.to_string(), self.now_ms);
+        self.utterance_started_ms = Some(self.now_ms);
+        self.silent_ms = 0;
+        Ok(())
+    }
+}
+
+fn settings() -> WakeCooldownSettings {
+    SETTINGS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Apply the saved cooldowns at startup
+pub fn restore(settings: &WakeCooldownSettings) {
+    *SETTINGS.lock().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+}
+
+#[tauri::command]
+pub async fn get_wake_cooldown(state: tauri::State<'_, SettingsState>) -> Result<WakeCooldownSettings, String> {
+    Ok(state.lock().await.wake_cooldown.clone())
+}
+
+/// Takes effect on the next detection; listening doesn't restart
+#[tauri::command]
+pub async fn set_wake_cooldown(
+    cooldown: WakeCooldownSettings,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    cooldown.validate()?;
+    let cooldown = WakeCooldownSettings {
+        per_keyword: cooldown
+            .per_keyword
+            .into_iter()
+            .map(|(keyword, ms)| (keyword.trim().to_lowercase(), ms))
+            .filter(|(keyword, _)| !keyword.is_empty())
+            .collect(),
+        ..cooldown
+    };
+
+    let mut settings = state.lock().await;
+    settings.wake_cooldown = cooldown.clone();
+    settings.save(&app)?;
+    drop(settings);
+
+    restore(&cooldown);
+    log::info!("⏱️ Wake word cooldown set to {:?}", cooldown);
+    Ok(format!(
+        "Wake word cooldown set to {:.1}s{}",
+        cooldown.default_ms as f64 / 1000.0,
+        match cooldown.per_keyword.len() {
+            0 => String::new(),
+            n => format!(" ({} keyword override{})", n, if n == 1 { "" } else { "s" }),
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_MS: u64 = 32;
+    const LOUD: i16 = 8000;
+
+    /// Feed `ms` of frames at `amplitude`; the gate's clock only moves with the audio
+    fn advance(gate: &mut DetectionGate, ms: u64, amplitude: i16) {
+        for _ in 0..ms / FRAME_MS {
+            gate.observe_frame(amplitude, FRAME_MS);
+        }
+    }
+
+    // These rely on the default settings: a 2s cooldown and a 400ms utterance gap
+
+    #[test]
+    fn detections_inside_the_cooldown_are_suppressed() {
+        let mut gate = DetectionGate::new();
+        assert_eq!(gate.check("porcupine"), Ok(()));
+
+        advance(&mut gate, 1024, 0);
+        assert_eq!(gate.check("porcupine"), Err(Suppressed::Cooldown(976)));
+        advance(&mut gate, 960, 0);
+        assert_eq!(gate.check("porcupine"), Err(Suppressed::Cooldown(16)));
+
+        // The first frame past the cooldown fires and starts a new one
+        advance(&mut gate, FRAME_MS, 0);
+        assert_eq!(gate.now_ms(), 2016);
+        assert_eq!(gate.check("porcupine"), Ok(()));
+        advance(&mut gate, 480, 0);
+        assert!(matches!(gate.check("porcupine"), Err(Suppressed::Cooldown(_))));
+    }
+
+    #[test]
+    fn cooldowns_are_per_keyword() {
+        let mut gate = DetectionGate::new();
+        assert_eq!(gate.check("porcupine"), Ok(()));
+        advance(&mut gate, 480, 0);
+        assert_eq!(gate.check("bumblebee"), Ok(()));
+        assert!(matches!(gate.check("porcupine"), Err(Suppressed::Cooldown(_))));
+    }
+
+    #[test]
+    fn the_triggering_utterance_suppresses_until_it_ends() {
+        let mut gate = DetectionGate::new();
+        assert_eq!(gate.check("porcupine"), Ok(()));
+
+        // Past the cooldown but still talking
+        advance(&mut gate, 2400, LOUD);
+        assert_eq!(gate.check("porcupine"), Err(Suppressed::SameUtterance));
+
+        advance(&mut gate, 384, 0);
+        assert_eq!(gate.check("porcupine"), Err(Suppressed::SameUtterance));
+        advance(&mut gate, FRAME_MS, 0);
+        assert_eq!(gate.check("porcupine"), Ok(()));
+    }
+}
@@ -1,4 +1,104 @@
+use crate::audio;
+use crate::porcupine_service::{self, WakeWordHandle};
+use crate::profiles;
+use crate::settings::SettingsState;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+pub const DEFAULT_KEYWORD_PATH: &str = "models/Hi-Eva.ppn";
+/// Live detection runs at maximum sensitivity unless turned down
+pub const DEFAULT_SENSITIVITY: f32 = 1.0;
+
+/// Porcupine's built-in English keywords by the names `set_wake_word` and `WAKE_WORD_KEYWORD`
+/// take, with how they're shown
+pub const BUILTIN_KEYWORDS: &[(&str, &str)] = &[
+    ("alexa", "Alexa"),
+    ("computer", "Computer"),
+    ("jarvis", "Jarvis"),
+    ("hey-google", "Hey Google"),
+    ("ok-google", "Ok Google"),
+    ("picovoice", "Picovoice"),
+    ("porcupine", "Porcupine"),
+];
+
+/// Detection counters since startup or the last reset, shared with the processing thread
+static WAKE_WORD_STATS: Mutex<WakeWordStats> = Mutex::new(WakeWordStats {
+    detections: 0,
+    false_triggers: 0,
+    cooldown_skips: 0,
+    utterance_skips: 0,
+    frames_processed: 0,
+    average_detection_amplitude: 0.0,
+    average_detection_peak: 0.0,
+});
+
+/// Porcupine model files; non-English keywords need the language model they were trained with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WakeWordModelSettings {
+    /// Custom `.ppn` keyword; a built-in English keyword is used when the file is missing
+    pub keyword_path: String,
+    /// Porcupine language model (`.pv`), empty for the bundled English model
+    pub model_path: String,
+    /// 0.0 - 1.0; higher catches more wake words along with more false triggers
+    pub sensitivity: f32,
+    /// One of `BUILTIN_KEYWORDS`, used without a keyword file; empty for `WAKE_WORD_KEYWORD` or "computer"
+    pub builtin_keyword: String,
+}
+
+impl Default for WakeWordModelSettings {
+    fn default() -> Self {
+        Self {
+            keyword_path: DEFAULT_KEYWORD_PATH.to_string(),
+            model_path: String::new(),
+            sensitivity: DEFAULT_SENSITIVITY,
+            builtin_keyword: String::new(),
+        }
+    }
+}
+
+impl WakeWordModelSettings {
+    pub fn custom_keyword(&self) -> Option<&Path> {
+        Some(Path::new(&self.keyword_path)).filter(|path| path.exists())
+    }
+
+    pub fn language_model(&self) -> Option<&Path> {
+        Some(Path::new(&self.model_path)).filter(|_| !self.model_path.is_empty())
+    }
+
+    /// Built-in keyword used when there's no keyword file, as (name, display name); unknown
+    /// names fall back to "porcupine"
+    pub fn builtin_keyword(&self) -> (&'static str, &'static str) {
+        let name = Some(self.builtin_keyword.clone())
+            .filter(|name| !name.is_empty())
+            .or_else(|| std::env::var("WAKE_WORD_KEYWORD").ok());
+        let Some(name) = name else {
+            return BUILTIN_KEYWORDS[1];
+        };
+        BUILTIN_KEYWORDS
+            .iter()
+            .copied()
+            .find(|(builtin, _)| *builtin == name)
+            .unwrap_or(BUILTIN_KEYWORDS[BUILTIN_KEYWORDS.len() - 1])
+    }
+
+    /// Display name from the keyword file, e.g. `hey-eva_ja_mac_v3_0_0.ppn` -> "hey eva"
+    pub fn keyword_name(&self) -> Option<String> {
+        let stem = self.custom_keyword()?.file_stem()?.to_string_lossy().into_owned();
+        let name = stem.split('_').next().unwrap_or(&stem);
+        Some(name.replace('-', " "))
+    }
+}
+
+/// What triggered a wake event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WakeSource {
+    WakeWord,
+    PushToTalk,
+}
 
 /// Event payload for wake word detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,6 +106,7 @@ pub struct WakeWordEvent {
     pub keyword: String,
     pub confidence: f32,
     pub timestamp: u64,
+    pub source: WakeSource,
 }
 
 impl WakeWordEvent {
@@ -19,32 +120,374 @@ impl WakeWordEvent {
             keyword,
             confidence,
             timestamp,
+            source: WakeSource::WakeWord,
+        }
+    }
+
+    /// Wake event raised by the push-to-talk key instead of a spoken keyword
+    pub fn push_to_talk() -> Self {
+        Self {
+            source: WakeSource::PushToTalk,
+            ..Self::new("Push to talk".to_string(), 1.0)
+        }
+    }
+}
+
+/// Tuning telemetry returned by `get_wake_word_stats`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WakeWordStats {
+    pub detections: u64,
+    /// Detections the frontend cancelled because no speech followed
+    pub false_triggers: u64,
+    /// Detections ignored because they fell inside the re-trigger cooldown
+    pub cooldown_skips: u64,
+    /// Detections ignored because they repeated within the utterance that already woke Eva
+    pub utterance_skips: u64,
+    pub frames_processed: u64,
+    /// Mean absolute sample value of the frames that triggered a detection
+    pub average_detection_amplitude: f32,
+    /// Mean peak sample value of the frames that triggered a detection
+    pub average_detection_peak: f32,
+}
+
+impl WakeWordStats {
+    fn with<R>(f: impl FnOnce(&mut WakeWordStats) -> R) -> Option<R> {
+        WAKE_WORD_STATS.lock().ok().map(|mut stats| f(&mut stats))
+    }
+
+    pub fn snapshot() -> Option<WakeWordStats> {
+        Self::with(|stats| stats.clone())
+    }
+
+    pub fn record_frame() {
+        Self::with(|stats| stats.frames_processed += 1);
+    }
+
+    pub fn record_cooldown_skip() {
+        Self::with(|stats| stats.cooldown_skips += 1);
+    }
+
+    pub fn record_utterance_skip() {
+        Self::with(|stats| stats.utterance_skips += 1);
+    }
+
+    pub fn record_detection(avg_amplitude: f32, max_amplitude: i16) {
+        Self::with(|stats| {
+            stats.detections += 1;
+            let n = stats.detections as f32;
+            stats.average_detection_amplitude += (avg_amplitude - stats.average_detection_amplitude) / n;
+            stats.average_detection_peak += (max_amplitude as f32 - stats.average_detection_peak) / n;
+        });
+    }
+}
+
+#[tauri::command]
+pub async fn get_wake_word_stats() -> Result<WakeWordStats, String> {
+    WakeWordStats::with(|stats| stats.clone()).ok_or_else(|| "Wake word stats lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub async fn reset_wake_word_stats() -> Result<String, String> {
+    WakeWordStats::with(|stats| *stats = WakeWordStats::default())
+        .ok_or_else(|| "Wake word stats lock poisoned".to_string())?;
+    log::info!("📊 Wake word stats reset");
+    Ok("Wake word stats reset".to_string())
+}
+
+/// Called by the frontend when a wake word activation ends without any speech
+#[tauri::command]
+pub async fn report_false_wake_word() -> Result<(), String> {
+    WakeWordStats::with(|stats| stats.false_triggers += 1)
+        .ok_or_else(|| "Wake word stats lock poisoned".to_string())?;
+    log::info!("🙅 Wake word activation cancelled as a false trigger");
+    Ok(())
+}
+
+/// Switch the keyword and language model files, restarting detection if it's running
+#[tauri::command]
+pub async fn set_wake_word_model(
+    keyword_path: String,
+    model_path: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let keyword_path = keyword_path.trim().to_string();
+    let model_path = model_path.trim().to_string();
+
+    if !keyword_path.is_empty() {
+        if !keyword_path.ends_with(".ppn") {
+            return Err("Keyword file must be a Porcupine .ppn file".to_string());
+        }
+        if !Path::new(&keyword_path).exists() {
+            return Err(format!("Keyword file not found: {}", keyword_path));
         }
     }
+    if !model_path.is_empty() {
+        if !model_path.ends_with(".pv") {
+            return Err("Language model must be a Porcupine .pv file".to_string());
+        }
+        if !Path::new(&model_path).exists() {
+            return Err(format!("Language model not found: {}", model_path));
+        }
+    }
+
+    let mut settings = state.lock().await;
+    settings.wake_word.keyword_path = keyword_path;
+    settings.wake_word.model_path = model_path;
+    settings.save(&app)?;
+    let models = profiles::wake_word_models(&settings);
+    drop(settings);
+
+    let porcupine = app.state::<WakeWordHandle>();
+    porcupine.set_models(models, app.clone()).await
+        .map_err(|e| format!("Failed to restart wake word detection with the new model: {}", e))?;
+    let keyword_name = porcupine.keyword_name();
+
+    log::info!("🎯 Wake word model set - listening for \"{}\"", keyword_name);
+    Ok(format!("Wake word set to \"{}\"", keyword_name))
+}
+
+/// Listen for a built-in keyword ("jarvis", "Hey Google") or a custom `.ppn` file. Running
+/// detection swaps Porcupine in its audio loop without reopening the microphone.
+#[tauri::command]
+pub async fn set_wake_word(
+    keyword_or_model: String,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let keyword_or_model = keyword_or_model.trim();
+    let mut settings = state.lock().await;
+    if keyword_or_model.ends_with(".ppn") {
+        if !Path::new(keyword_or_model).exists() {
+            return Err(format!("Keyword file not found: {}", keyword_or_model));
+        }
+        settings.wake_word.keyword_path = keyword_or_model.to_string();
+    } else {
+        let name = keyword_or_model.to_lowercase().replace(' ', "-");
+        if !BUILTIN_KEYWORDS.iter().any(|(builtin, _)| *builtin == name) {
+            let names: Vec<&str> = BUILTIN_KEYWORDS.iter().map(|(builtin, _)| *builtin).collect();
+            return Err(format!(
+                "Unknown wake word \"{}\"; use a .ppn file or one of: {}",
+                keyword_or_model,
+                names.join(", ")
+            ));
+        }
+        settings.wake_word.keyword_path.clear();
+        settings.wake_word.builtin_keyword = name;
+    }
+    settings.save(&app)?;
+    let models = profiles::wake_word_models(&settings);
+    drop(settings);
+
+    let porcupine = app.state::<WakeWordHandle>();
+    porcupine.set_models(models, app.clone()).await
+        .map_err(|e| format!("Failed to switch the wake word: {}", e))?;
+    let keyword_name = porcupine.keyword_name();
+
+    log::info!("🎯 Wake word switched - listening for \"{}\"", keyword_name);
+    Ok(format!("Wake word set to \"{}\"", keyword_name))
+}
+
+/// Rebuild Porcupine at a new sensitivity, live if detection is running
+#[tauri::command]
+pub async fn set_wake_word_sensitivity(
+    sensitivity: f32,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if !(0.0..=1.0).contains(&sensitivity) {
+        return Err("Sensitivity must be between 0 and 1".to_string());
+    }
+
+    let mut settings = state.lock().await;
+    settings.wake_word.sensitivity = sensitivity;
+    settings.save(&app)?;
+    let models = profiles::wake_word_models(&settings);
+    drop(settings);
+
+    app.state::<WakeWordHandle>()
+        .set_models(models, app.clone())
+        .await
+        .map_err(|e| format!("Failed to restart wake word detection: {}", e))?;
+
+    log::info!("🎯 Wake word sensitivity set to {:.2}", sensitivity);
+    Ok(format!("Wake word sensitivity set to {:.2}", sensitivity))
+}
+
+/// Detections in one recording, from `test_wake_word`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WakeWordTestResult {
+    pub file: String,
+    pub duration_ms: u64,
+    /// Offsets into the recording where the wake word was detected
+    pub detections_ms: Vec<u64>,
+    /// Detections that fell inside the re-trigger cooldown, as live detection would skip them
+    pub cooldown_skips: u32,
+    /// Repeats within one utterance, as live detection would skip them
+    pub utterance_skips: u32,
+    /// The file couldn't be read or processed
+    pub error: Option<String>,
+}
+
+/// Returned by `test_wake_word`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WakeWordTestReport {
+    /// Keyword file tested, or `None` for the built-in keyword
+    pub keyword_path: Option<String>,
+    pub sensitivity: f32,
+    pub files: Vec<WakeWordTestResult>,
+    pub total_detections: usize,
+}
+
+/// Run a WAV file, or every WAV in a directory, through wake word detection offline.
+///
+/// Uses the active profile's models unless `keyword_path` names another `.ppn` to try;
+/// `sensitivity` defaults to the live listener's.
+#[tauri::command]
+pub async fn test_wake_word(
+    path: String,
+    keyword_path: Option<String>,
+    sensitivity: Option<f32>,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<WakeWordTestReport, String> {
+    let settings = state.lock().await;
+    let mut models = profiles::wake_word_models(&settings);
+    drop(settings);
+    let sensitivity = sensitivity.unwrap_or(models.sensitivity);
+    if !(0.0..=1.0).contains(&sensitivity) {
+        return Err("Sensitivity must be between 0 and 1".to_string());
+    }
+
+    if let Some(keyword_path) = keyword_path.map(|path| path.trim().to_string()).filter(|path| !path.is_empty()) {
+        if !keyword_path.ends_with(".ppn") {
+            return Err("Keyword file must be a Porcupine .ppn file".to_string());
+        }
+        if !Path::new(&keyword_path).exists() {
+            return Err(format!("Keyword file not found: {}", keyword_path));
+        }
+        models.keyword_path = keyword_path;
+    }
+
+    let files = audio::wav_files(Path::new(path.trim()))?;
+    if files.is_empty() {
+        return Err(format!("No .wav files found in {}", path));
+    }
+
+    log::info!("🧪 Testing wake word on {} recording(s) at sensitivity {:.2}", files.len(), sensitivity);
+    let keyword_path = models.custom_keyword().map(|path| path.display().to_string());
+    let files = app
+        .state::<WakeWordHandle>()
+        .test_files(models, sensitivity, files)
+        .await
+        .map_err(|e| format!("Wake word test failed: {}", e))?;
+
+    Ok(WakeWordTestReport {
+        keyword_path,
+        sensitivity,
+        total_detections: files.iter().map(|file| file.detections_ms.len()).sum(),
+        files,
+    })
+}
+
+/// Outcome of checking a Picovoice access key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessKeyStatus {
+    Valid,
+    /// Malformed, revoked or unknown to Picovoice
+    InvalidKey,
+    /// The key is activated on as many devices as the account allows
+    ActivationLimitReached,
+    /// Picovoice couldn't be reached to activate the key
+    NetworkNeeded,
+    /// Porcupine failed for a reason that says nothing about the key
+    Failed,
+}
+
+/// Returned by `validate_picovoice_key`; `hint` says what to do about a rejected key
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessKeyValidation {
+    pub status: AccessKeyStatus,
+    pub message: String,
+    pub hint: Option<&'static str>,
+}
+
+impl AccessKeyValidation {
+    /// Sort a Porcupine init error by its status name
+    fn from_init_error(error: String) -> Self {
+        let status_name = error.to_uppercase();
+        let (status, hint) = if status_name.contains("ACTIVATION_LIMIT_REACHED") {
+            (
+                AccessKeyStatus::ActivationLimitReached,
+                Some("Remove an old device from your key at console.picovoice.ai, or use another key"),
+            )
+        } else if status_name.contains("ACTIVATION_ERROR") || status_name.contains("ACTIVATION_THROTTLED") {
+            (
+                AccessKeyStatus::NetworkNeeded,
+                Some("Connect to the internet once so Picovoice can activate the key, then try again"),
+            )
+        } else if status_name.contains("ACTIVATION_REFUSED")
+            || status_name.contains("INVALID_ARGUMENT")
+            || status_name.contains("ACCESSKEY")
+        {
+            (
+                AccessKeyStatus::InvalidKey,
+                Some("Copy the AccessKey from console.picovoice.ai again, without spaces"),
+            )
+        } else {
+            (AccessKeyStatus::Failed, None)
+        };
+        Self { status, message: error, hint }
+    }
+}
+
+/// Try a Picovoice access key by initializing Porcupine with it, without storing it
+/// anywhere, so setup can reject a bad key before it reaches the keychain.
+#[tauri::command]
+pub async fn validate_picovoice_key(key: String) -> Result<AccessKeyValidation, String> {
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Ok(AccessKeyValidation {
+            status: AccessKeyStatus::InvalidKey,
+            message: "No access key entered".to_string(),
+            hint: Some("Get a free AccessKey at console.picovoice.ai"),
+        });
+    }
+
+    let result = tokio::task::spawn_blocking(move || porcupine_service::try_access_key(&key))
+        .await
+        .map_err(|e| format!("Access key check failed: {}", e))?;
+
+    let validation = match result {
+        Ok(()) => AccessKeyValidation {
+            status: AccessKeyStatus::Valid,
+            message: "Access key accepted".to_string(),
+            hint: None,
+        },
+        Err(error) => AccessKeyValidation::from_init_error(error),
+    };
+    log::info!("🔑 Picovoice access key check: {:?}", validation.status);
+    Ok(validation)
 }
 
 /// Wake word detection errors
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum WakeWordError {
+    #[error("Porcupine initialization failed: {0}")]
     PorcupineInit(String),
+    #[error("Audio device error: {0}")]
     AudioDevice(String),
+    #[error("Access key error: {0}")]
     AccessKey(String),
+    #[error("Resampling error: {0}")]
     Resampling(String),
+    #[error("Already listening")]
     AlreadyListening,
+    #[error("Not listening")]
     NotListening,
+    #[error("Wake word listening is off in text-only chat mode")]
+    TextOnlyMode,
+    #[error("Wake word service stopped")]
+    ServiceStopped,
 }
-
-impl std::fmt::Display for WakeWordError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            WakeWordError::PorcupineInit(msg) => write!(f, "Porcupine initialization failed: {}", msg),
-            WakeWordError::AudioDevice(msg) => write!(f, "Audio device error: {}", msg),
-            WakeWordError::AccessKey(msg) => write!(f, "Access key error: {}", msg),
-            WakeWordError::Resampling(msg) => write!(f, "Resampling error: {}", msg),
-            WakeWordError::AlreadyListening => write!(f, "Already listening"),
-            WakeWordError::NotListening => write!(f, "Not listening"),
-        }
-    }
-}
-
-impl std::error::Error for WakeWordError {}
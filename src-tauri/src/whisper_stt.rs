@@ -0,0 +1,176 @@
+use crate::audio::resample_clip;
+use crate::settings::SettingsState;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Whisper models expect 16kHz mono f32 audio
+pub const WHISPER_SAMPLE_RATE: u32 = 16000;
+pub const DEFAULT_WHISPER_MODEL_PATH: &str = "models/ggml-base.en.bin";
+
+/// Where speech gets transcribed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SttBackend {
+    /// Audio is streamed to the Realtime API, which transcribes it server-side
+    #[default]
+    OpenaiRealtime,
+    /// Audio is transcribed locally with whisper.cpp
+    Whisper,
+}
+
+/// Speech-to-text settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SttSettings {
+    pub backend: SttBackend,
+    pub whisper_model_path: String,
+    /// ISO 639-1 language hint, empty for auto-detect
+    pub language: String,
+}
+
+impl Default for SttSettings {
+    fn default() -> Self {
+        Self {
+            backend: SttBackend::default(),
+            whisper_model_path: DEFAULT_WHISPER_MODEL_PATH.to_string(),
+            language: "en".to_string(),
+        }
+    }
+}
+
+/// Result of a local transcription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    pub text: String,
+    pub audio_duration_ms: u64,
+    pub processing_ms: u64,
+}
+
+/// Resample a complete mono clip to 16kHz in one pass
+pub fn resample_for_whisper(samples: Vec<f32>, sample_rate: u32) -> Result<Vec<f32>, String> {
+    resample_clip(samples, sample_rate, WHISPER_SAMPLE_RATE)
+}
+
+#[cfg(feature = "whisper")]
+mod engine {
+    use std::sync::{Arc, Mutex};
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    struct LoadedModel {
+        path: String,
+        context: Arc<WhisperContext>,
+    }
+
+    /// Loading a model takes seconds, so keep the last one around
+    static MODEL: Mutex<Option<LoadedModel>> = Mutex::new(None);
+
+    fn load_model(path: &str) -> Result<Arc<WhisperContext>, String> {
+        let mut model = MODEL.lock().map_err(|_| "Whisper model lock poisoned".to_string())?;
+
+        if let Some(loaded) = model.as_ref() {
+            if loaded.path == path {
+                return Ok(loaded.context.clone());
+            }
+        }
+
+        log::info!("🗣️  Loading Whisper model: {}", path);
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|e| format!("Failed to load Whisper model '{}': {}", path, e))?;
+        let context = Arc::new(context);
+
+        *model = Some(LoadedModel { path: path.to_string(), context: context.clone() });
+        Ok(context)
+    }
+
+    pub fn transcribe(model_path: &str, language: &str, samples: &[f32]) -> Result<String, String> {
+        let context = load_model(model_path)?;
+        let mut state = context.create_state()
+            .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(if language.is_empty() { None } else { Some(language) });
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+
+        state.full(params, samples)
+            .map_err(|e| format!("Whisper transcription failed: {}", e))?;
+
+        let segments = state.full_n_segments()
+            .map_err(|e| format!("Failed to read Whisper segments: {}", e))?;
+
+        let mut text = String::new();
+        for i in 0..segments {
+            let segment = state.full_get_segment_text_lossy(i)
+                .map_err(|e| format!("Failed to read Whisper segment: {}", e))?;
+            text.push_str(&segment);
+        }
+
+        Ok(text.trim().to_string())
+    }
+}
+
+#[cfg(not(feature = "whisper"))]
+mod engine {
+    use crate::identity;
+
+    pub fn transcribe(_model_path: &str, _language: &str, _samples: &[f32]) -> Result<String, String> {
+        Err(identity::current().render("Local transcription is unavailable: {name} was built without the `whisper` feature"))
+    }
+}
+
+/// Transcribe a PCM16 mono clip captured by the frontend
+#[tauri::command]
+pub async fn transcribe_audio(
+    samples: Vec<i16>,
+    sample_rate: u32,
+    state: tauri::State<'_, SettingsState>,
+) -> Result<Transcript, String> {
+    if sample_rate == 0 {
+        return Err("Invalid sample rate".to_string());
+    }
+
+    let settings = state.lock().await.stt.clone();
+    let audio_duration_ms = samples.len() as u64 * 1000 / sample_rate as u64;
+    let started = std::time::Instant::now();
+
+    log::info!("🗣️  Transcribing {}ms of audio locally", audio_duration_ms);
+
+    // Model inference is CPU-bound, keep it off the async runtime
+    let text = tokio::task::spawn_blocking(move || {
+        let samples: Vec<f32> = samples.iter().map(|&s| s as f32 / 32768.0).collect();
+        let samples = resample_for_whisper(samples, sample_rate)?;
+        engine::transcribe(&settings.whisper_model_path, &settings.language, &samples)
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {}", e))??;
+
+    let processing_ms = started.elapsed().as_millis() as u64;
+    log::info!("🗣️  Transcribed in {}ms: \"{}\"", processing_ms, text);
+
+    Ok(Transcript { text, audio_duration_ms, processing_ms })
+}
+
+/// Whether local transcription can run: feature compiled in and model present
+#[tauri::command]
+pub async fn whisper_available(
+    state: tauri::State<'_, SettingsState>,
+) -> Result<bool, String> {
+    let model_path = state.lock().await.stt.whisper_model_path.clone();
+    Ok(cfg!(feature = "whisper") && std::path::Path::new(&model_path).exists())
+}
+
+#[tauri::command]
+pub async fn set_stt_backend(
+    backend: SttBackend,
+    state: tauri::State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut settings = state.lock().await;
+    settings.stt.backend = backend;
+    settings.save(&app)?;
+
+    log::info!("Speech-to-text backend set to {:?}", backend);
+    Ok(format!("Speech-to-text backend set to {:?}", backend))
+}
@@ -2,11 +2,12 @@ import { useState } from "react";
 import PorcupineTest from "./components/PorcupineTest";
 import OpenAIChat from "./components/OpenAIChatNew";
 import AudioDebugTest from "./components/AudioDebugTest";
+import LogViewer from "./components/LogViewer";
 import "./App.css";
 
 function App() {
   const [currentView, setCurrentView] = useState<
-    "main" | "porcupine" | "openai" | "audio-debug"
+    "main" | "porcupine" | "openai" | "audio-debug" | "logs"
   >("main");
 
   return (
@@ -53,6 +54,16 @@ function App() {
         >
           🎤 Wake Word Test
         </button>
+        <button
+          onClick={() => setCurrentView("logs")}
+          className={`px-4 py-2 rounded-lg font-medium transition-colors ${
+            currentView === "logs"
+              ? "bg-blue-600 text-white"
+              : "bg-gray-200 dark:bg-gray-700 text-gray-700 dark:text-gray-300 hover:bg-gray-300 dark:hover:bg-gray-600"
+          }`}
+        >
+          📜 Logs
+        </button>
       </div>
 
       {(() => {
@@ -62,6 +73,8 @@ function App() {
           return <OpenAIChat />;
         } else if (currentView === "audio-debug") {
           return <AudioDebugTest />;
+        } else if (currentView === "logs") {
+          return <LogViewer />;
         } else {
           return (
             <div className="w-full max-w-4xl">
@@ -0,0 +1,98 @@
+import React, { useEffect, useState } from "react";
+import { AllowedAction, getActions, setActions } from "../services/tools";
+
+const EMPTY_ACTION: AllowedAction = { name: "", kind: "app", target: "" };
+
+const ActionsSettings: React.FC = () => {
+  const [actions, setActionList] = useState<AllowedAction[]>([]);
+  const [message, setMessage] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    getActions()
+      .then(setActionList)
+      .catch((error) => console.error("Failed to load actions:", error));
+  }, []);
+
+  const update = (index: number, change: Partial<AllowedAction>) =>
+    setActionList((current) =>
+      current.map((action, i) => (i === index ? { ...action, ...change } : action))
+    );
+
+  const remove = (index: number) =>
+    setActionList((current) => current.filter((_, i) => i !== index));
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    setMessage(null);
+    try {
+      setMessage(`✅ ${await setActions(actions)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">⚡ Actions</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        {actions.map((action, index) => (
+          <div key={index} className="flex gap-2 items-center">
+            <input
+              type="text"
+              value={action.name}
+              onChange={(e) => update(index, { name: e.target.value })}
+              placeholder="Name, e.g. Spotify"
+              className="w-28 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+            <select
+              value={action.kind}
+              onChange={(e) => update(index, { kind: e.target.value as AllowedAction["kind"] })}
+              className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            >
+              <option value="app">App</option>
+              <option value="shell">Shell</option>
+            </select>
+            <input
+              type="text"
+              value={action.target}
+              onChange={(e) => update(index, { target: e.target.value })}
+              placeholder={action.kind === "app" ? "App name or path" : "Shell command"}
+              className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 font-mono"
+            />
+            <button
+              onClick={() => remove(index)}
+              className="text-red-500 hover:text-red-600"
+              title="Remove"
+            >
+              ✕
+            </button>
+          </div>
+        ))}
+        <div className="flex gap-2">
+          <button
+            onClick={() => setActionList((current) => [...current, { ...EMPTY_ACTION }])}
+            className="px-3 py-1 bg-gray-500 text-white rounded hover:bg-gray-600"
+          >
+            Add
+          </button>
+          <button
+            onClick={handleSave}
+            disabled={isSaving}
+            className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600 disabled:opacity-50"
+          >
+            {isSaving ? "Saving..." : "Save"}
+          </button>
+        </div>
+        <div className="text-gray-500 dark:text-gray-400">
+          Eva can only run what's listed here, e.g. "open Spotify".
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default ActionsSettings;
@@ -1,16 +1,77 @@
 import React, { useState } from "react";
-import { openaiRealtimeService } from "../services/openai-simple";
+import {
+  openaiRealtimeService,
+  AzureRealtimeConfig,
+  DEFAULT_AZURE_API_VERSION,
+} from "../services/openai-simple";
+import { OpenAiKeyValidation, validateOpenAIKey } from "../services/diagnostics";
 
 const ApiKeySetup: React.FC = () => {
   const [apiKey, setApiKey] = useState("");
   const [isSet, setIsSet] = useState(false);
+  const [keyCheck, setKeyCheck] = useState<OpenAiKeyValidation | null>(null);
+  const [isChecking, setIsChecking] = useState(false);
+  const [useAzure, setUseAzure] = useState(
+    openaiRealtimeService.getAzureConfig() !== null
+  );
+  const [azureConfig, setAzureConfig] = useState<AzureRealtimeConfig>(
+    openaiRealtimeService.getAzureConfig() ?? {
+      endpoint: "",
+      deployment: "",
+      apiVersion: DEFAULT_AZURE_API_VERSION,
+      apiKey: "",
+    }
+  );
+
+  const isAzureConfigValid =
+    azureConfig.endpoint.trim() !== "" &&
+    azureConfig.deployment.trim() !== "" &&
+    azureConfig.apiKey.trim() !== "";
+
+  const handleSaveAzure = () => {
+    if (!isAzureConfigValid) return;
+    openaiRealtimeService.setAzureConfig({
+      endpoint: azureConfig.endpoint.trim(),
+      deployment: azureConfig.deployment.trim(),
+      apiVersion: azureConfig.apiVersion.trim() || DEFAULT_AZURE_API_VERSION,
+      apiKey: azureConfig.apiKey.trim(),
+    });
+    setAzureConfig((prev) => ({ ...prev, apiKey: "" })); // Clear the key input for security
+    setIsSet(true);
+  };
+
+  const handleToggleAzure = (enabled: boolean) => {
+    setUseAzure(enabled);
+    if (!enabled) {
+      openaiRealtimeService.setAzureConfig(null);
+    }
+  };
+
+  const updateAzureField =
+    (field: keyof AzureRealtimeConfig) =>
+    (e: React.ChangeEvent<HTMLInputElement>) =>
+      setAzureConfig((prev) => ({ ...prev, [field]: e.target.value }));
 
-  const handleSetApiKey = () => {
-    if (apiKey.trim()) {
-      openaiRealtimeService.setApiKey(apiKey.trim());
-      setIsSet(true);
-      setApiKey(""); // Clear the input for security
+  // A key that's rejected or can't reach the Realtime model isn't saved; one that couldn't
+  // be checked (offline, rate limited) is saved with the warning shown
+  const handleSetApiKey = async () => {
+    const key = apiKey.trim();
+    if (!key || isChecking) return;
+
+    setIsChecking(true);
+    try {
+      const check = await validateOpenAIKey(key);
+      setKeyCheck(check);
+      if (check.status === "invalid-key" || check.status === "model-unavailable") return;
+    } catch (error) {
+      console.error("Failed to check API key:", error);
+    } finally {
+      setIsChecking(false);
     }
+
+    openaiRealtimeService.setApiKey(key);
+    setIsSet(true);
+    setApiKey(""); // Clear the input for security
   };
 
   const handleKeyPress = (e: React.KeyboardEvent) => {
@@ -25,7 +86,63 @@ const ApiKeySetup: React.FC = () => {
         🔑 OpenAI API Key Setup
       </h3>
 
-      {!isSet ? (
+      <label className="flex items-center gap-2 mb-3 text-sm text-blue-700 dark:text-blue-400">
+        <input
+          type="checkbox"
+          checked={useAzure}
+          onChange={(e) => handleToggleAzure(e.target.checked)}
+        />
+        Use Azure OpenAI endpoint
+      </label>
+
+      {useAzure ? (
+        <div className="space-y-2">
+          <input
+            type="text"
+            value={azureConfig.endpoint}
+            onChange={updateAzureField("endpoint")}
+            placeholder="https://my-resource.openai.azure.com"
+            className="w-full px-3 py-2 border border-blue-300 dark:border-blue-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
+          />
+          <div className="flex gap-2">
+            <input
+              type="text"
+              value={azureConfig.deployment}
+              onChange={updateAzureField("deployment")}
+              placeholder="Deployment name"
+              className="flex-1 px-3 py-2 border border-blue-300 dark:border-blue-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
+            />
+            <input
+              type="text"
+              value={azureConfig.apiVersion}
+              onChange={updateAzureField("apiVersion")}
+              placeholder={DEFAULT_AZURE_API_VERSION}
+              className="w-48 px-3 py-2 border border-blue-300 dark:border-blue-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
+            />
+          </div>
+          <div className="flex gap-2">
+            <input
+              type="password"
+              value={azureConfig.apiKey}
+              onChange={updateAzureField("apiKey")}
+              placeholder="Azure API key"
+              className="flex-1 px-3 py-2 border border-blue-300 dark:border-blue-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
+            />
+            <button
+              onClick={handleSaveAzure}
+              disabled={!isAzureConfigValid}
+              className="px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
+            >
+              Save
+            </button>
+          </div>
+          {isSet && (
+            <div className="text-green-700 dark:text-green-400">
+              ✅ Azure OpenAI configured! You can now use Eva Chat.
+            </div>
+          )}
+        </div>
+      ) : !isSet ? (
         <div className="space-y-3">
           <p className="text-sm text-blue-700 dark:text-blue-400">
             Enter your OpenAI API key to enable Eva Chat functionality:
@@ -41,12 +158,18 @@ const ApiKeySetup: React.FC = () => {
             />
             <button
               onClick={handleSetApiKey}
-              disabled={!apiKey.trim()}
+              disabled={!apiKey.trim() || isChecking}
               className="px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
             >
-              Set Key
+              {isChecking ? "Checking..." : "Set Key"}
             </button>
           </div>
+          {keyCheck && keyCheck.status !== "valid" && (
+            <div className="text-sm text-red-600 dark:text-red-400">
+              ❌ {keyCheck.detail}
+              {keyCheck.recovery_hint && <div className="text-xs">💡 {keyCheck.recovery_hint}</div>}
+            </div>
+          )}
           <p className="text-xs text-blue-600 dark:text-blue-400">
             🔒 Your API key is stored locally and never sent to anyone except
             OpenAI
@@ -55,6 +178,9 @@ const ApiKeySetup: React.FC = () => {
       ) : (
         <div className="text-green-700 dark:text-green-400">
           ✅ API key has been set! You can now use Eva Chat.
+          {keyCheck && keyCheck.status !== "valid" && (
+            <div className="text-sm text-yellow-700 dark:text-yellow-400">⚠️ {keyCheck.detail}</div>
+          )}
         </div>
       )}
     </div>
@@ -0,0 +1,72 @@
+import React, { useEffect, useState } from "react";
+import {
+  AudioFormatSettings as Settings,
+  RealtimeAudioFormat,
+  getAudioFormat,
+  setAudioFormat,
+} from "../services/audio-format";
+
+const FORMAT_LABELS: Record<RealtimeAudioFormat, string> = {
+  pcm16: "PCM 24kHz (best quality)",
+  g711_ulaw: "G.711 μ-law 8kHz (low bandwidth)",
+  g711_alaw: "G.711 A-law 8kHz (low bandwidth)",
+};
+
+const AudioFormatSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getAudioFormat()
+      .then(setSettings)
+      .catch((error) => console.error("Failed to load audio format:", error));
+  }, []);
+
+  if (!settings) return null;
+
+  const save = async (next: Settings) => {
+    setSettings(next);
+    try {
+      setMessage(`✅ ${await setAudioFormat(next)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+      setSettings(await getAudioFormat());
+    }
+  };
+
+  const formatSelect = (key: keyof Settings) => (
+    <select
+      value={settings[key]}
+      onChange={(e) => save({ ...settings, [key]: e.target.value as RealtimeAudioFormat })}
+      className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+    >
+      {(Object.keys(FORMAT_LABELS) as RealtimeAudioFormat[]).map((format) => (
+        <option key={format} value={format}>
+          {FORMAT_LABELS[format]}
+        </option>
+      ))}
+    </select>
+  );
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🎚️ Audio Encoding</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-2">
+          <span className="w-16">Your voice</span>
+          {formatSelect("input")}
+        </label>
+        <label className="flex items-center gap-2">
+          <span className="w-16">Replies</span>
+          {formatSelect("output")}
+        </label>
+        <div className="text-gray-500 dark:text-gray-400">
+          G.711 uses a sixth of the bandwidth at phone quality. Applies from the next connection.
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default AudioFormatSettings;
@@ -0,0 +1,86 @@
+import React, { useEffect, useState } from "react";
+import { AvatarInfo, importAvatar, listAvatars, setActiveAvatar } from "../services/avatar";
+
+const KIND_LABELS: Record<AvatarInfo["kind"], string> = {
+  vrm: "VRM",
+  live2d: "Live2D",
+};
+
+const AvatarSettings: React.FC = () => {
+  const [avatars, setAvatars] = useState<AvatarInfo[]>([]);
+  const [message, setMessage] = useState<string | null>(null);
+  const [isImporting, setIsImporting] = useState(false);
+
+  const load = async () => {
+    setAvatars(await listAvatars());
+  };
+
+  useEffect(() => {
+    load().catch((error) => console.error("Failed to load avatars:", error));
+  }, []);
+
+  const handleSelect = async (id: string | null) => {
+    setMessage(null);
+    try {
+      await setActiveAvatar(id);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleImport = async () => {
+    setIsImporting(true);
+    setMessage(null);
+    try {
+      const avatar = await importAvatar();
+      setMessage(`✅ Imported ${avatar.name}`);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    } finally {
+      setIsImporting(false);
+    }
+  };
+
+  const activeId = avatars.find((avatar) => avatar.active)?.id ?? null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🧍 Avatar</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="radio"
+            name="avatar"
+            checked={activeId === null}
+            onChange={() => handleSelect(null)}
+          />
+          Default
+        </label>
+        {avatars.map((avatar) => (
+          <label key={avatar.id} className="flex items-center gap-1">
+            <input
+              type="radio"
+              name="avatar"
+              checked={avatar.active}
+              onChange={() => handleSelect(avatar.id)}
+            />
+            {avatar.name}
+            <span className="text-gray-500 dark:text-gray-400">{KIND_LABELS[avatar.kind]}</span>
+          </label>
+        ))}
+        <button
+          onClick={handleImport}
+          disabled={isImporting}
+          className="mt-1 px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600 disabled:opacity-50"
+        >
+          {isImporting ? "Importing..." : "Import .vrm / .model3.json"}
+        </button>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default AvatarSettings;
@@ -0,0 +1,104 @@
+import React, { useEffect, useState } from "react";
+import {
+  CalendarStatus,
+  getCalendar,
+  refreshCalendar,
+  setCalendar,
+} from "../services/tools";
+
+const CalendarSettings: React.FC = () => {
+  const [status, setStatus] = useState<CalendarStatus | null>(null);
+  const [url, setUrl] = useState("");
+  const [username, setUsername] = useState("");
+  const [password, setPassword] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+
+  const load = async () => {
+    const loaded = await getCalendar();
+    setStatus(loaded);
+    setUrl(loaded.url);
+    setUsername(loaded.username ?? "");
+  };
+
+  useEffect(() => {
+    load().catch((error) => console.error("Failed to load calendar settings:", error));
+  }, []);
+
+  const run = async (action: () => Promise<string>) => {
+    setIsBusy(true);
+    setMessage(null);
+    try {
+      const result = await action();
+      setPassword(""); // Clear the input for security
+      setMessage(`✅ ${result}`);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleSave = () =>
+    run(() => setCalendar(url.trim(), username.trim() || null, password || null));
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">📅 Calendar</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        <input
+          type="text"
+          value={url}
+          onChange={(e) => setUrl(e.target.value)}
+          placeholder="ICS / webcal:// subscription URL"
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        />
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={username}
+            onChange={(e) => setUsername(e.target.value)}
+            placeholder="Username (optional)"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          <input
+            type="password"
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+            placeholder={
+              status?.password_configured ? "Password saved in keychain" : "Password (optional)"
+            }
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </div>
+        <div className="flex gap-2">
+          <button
+            onClick={handleSave}
+            disabled={isBusy}
+            className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600 disabled:opacity-50"
+          >
+            {isBusy ? "Checking..." : "Save"}
+          </button>
+          {status?.url && (
+            <button
+              onClick={() => run(refreshCalendar)}
+              disabled={isBusy}
+              className="px-3 py-1 bg-gray-500 text-white rounded hover:bg-gray-600 disabled:opacity-50"
+            >
+              Refresh
+            </button>
+          )}
+        </div>
+        <div className="text-gray-500 dark:text-gray-400">
+          {status?.last_refreshed
+            ? `${status.event_count} events, updated ${new Date(status.last_refreshed).toLocaleTimeString()}`
+            : "For CalDAV, use the calendar's export (.ics) link with your account login."}
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default CalendarSettings;
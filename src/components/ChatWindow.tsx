@@ -0,0 +1,100 @@
+import React, { useEffect, useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { ChatTurn, getChatHistory, onChatHistoryCleared, onChatTurn } from "../services/chat-window";
+
+// The standalone chat and history window. It holds no session of its own: messages typed here
+// go to the main window through send_chat_message, and turns come back as `chat-turn` events.
+const ChatWindow: React.FC = () => {
+  const [turns, setTurns] = useState<ChatTurn[]>([]);
+  const [assistantName, setAssistantName] = useState("Eva");
+  const [input, setInput] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const endRef = useRef<HTMLDivElement>(null);
+
+  useEffect(() => {
+    const unlisteners: Promise<() => void>[] = [];
+
+    getChatHistory()
+      .then(setTurns)
+      .catch((err) => console.error("Failed to load chat history:", err));
+    invoke<{ identity: { assistant_name: string } }>("get_settings")
+      .then((settings) => setAssistantName(settings.identity.assistant_name))
+      .catch((err) => console.error("Failed to load assistant name:", err));
+
+    unlisteners.push(onChatTurn((turn) => setTurns((prev) => [...prev, turn])));
+    unlisteners.push(onChatHistoryCleared(() => setTurns([])));
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, []);
+
+  useEffect(() => {
+    endRef.current?.scrollIntoView({ behavior: "smooth" });
+  }, [turns]);
+
+  const send = async () => {
+    const text = input.trim();
+    if (!text) return;
+    try {
+      await invoke("send_chat_message", { text });
+      setInput("");
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="h-screen flex flex-col bg-white dark:bg-gray-800">
+      <div className="flex-1 overflow-y-auto p-4 space-y-3">
+        {turns.length === 0 && (
+          <div className="text-sm text-gray-500 dark:text-gray-400 text-center">No messages yet</div>
+        )}
+        {turns.map((turn, index) => (
+          <div key={`${turn.timestamp}-${index}`} className={`flex ${turn.role === "user" ? "justify-end" : "justify-start"}`}>
+            <div
+              className={`max-w-[80%] px-3 py-2 rounded-lg ${
+                turn.role === "user" ? "bg-blue-500 text-white" : "bg-purple-500 text-white"
+              }`}
+            >
+              <div className="text-xs opacity-75 mb-1">
+                {turn.role === "user" ? "You" : assistantName} · {new Date(turn.timestamp).toLocaleTimeString()}
+              </div>
+              <div className="whitespace-pre-wrap text-sm">{turn.text}</div>
+            </div>
+          </div>
+        ))}
+        <div ref={endRef} />
+      </div>
+
+      <div className="border-t border-gray-200 dark:border-gray-700 p-3">
+        {error && <div className="text-xs text-red-500 mb-2">❌ {error}</div>}
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={input}
+            onChange={(e) => setInput(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter" && !e.shiftKey) {
+                e.preventDefault();
+                send();
+              }
+            }}
+            placeholder={`Message ${assistantName}...`}
+            className="flex-1 px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:outline-none focus:ring-2 focus:ring-purple-500 dark:bg-gray-700 dark:text-white"
+          />
+          <button
+            onClick={send}
+            disabled={!input.trim()}
+            className="bg-purple-500 hover:bg-purple-600 disabled:bg-gray-400 text-white px-4 py-2 rounded-lg font-medium transition-colors"
+          >
+            Send
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default ChatWindow;
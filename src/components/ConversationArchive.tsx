@@ -0,0 +1,230 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  ConversationSummary,
+  ExportFormat,
+  ReplyRecording,
+  exportConversation,
+  listConversationReplies,
+  listConversations,
+  replayReply,
+  setConversationArchive,
+  setReplyRecording,
+} from "../services/conversations";
+
+interface ArchiveSettings {
+  save_transcripts: boolean;
+  save_audio: boolean;
+  save_replies: boolean;
+  reply_retention_days: number;
+  max_reply_recordings: number;
+}
+
+const ConversationArchive: React.FC = () => {
+  const [settings, setSettings] = useState<ArchiveSettings | null>(null);
+  const [conversations, setConversations] = useState<ConversationSummary[]>([]);
+  const [format, setFormat] = useState<ExportFormat>("markdown");
+  const [includeAudio, setIncludeAudio] = useState(true);
+  const [status, setStatus] = useState<string | null>(null);
+  const [openReplies, setOpenReplies] = useState<{ sessionId: string; replies: ReplyRecording[] } | null>(
+    null
+  );
+
+  const refresh = () => {
+    listConversations()
+      .then(setConversations)
+      .catch((error) => console.error("Failed to list conversations:", error));
+  };
+
+  useEffect(() => {
+    invoke<{ conversations: ArchiveSettings }>("get_settings")
+      .then((loaded) => setSettings(loaded.conversations))
+      .catch((error) => console.error("Failed to load archive settings:", error));
+    refresh();
+  }, []);
+
+  const update = async (next: ArchiveSettings) => {
+    setSettings(next);
+    try {
+      await setConversationArchive(next.save_transcripts, next.save_audio);
+    } catch (error) {
+      console.error("Failed to update archive settings:", error);
+    }
+  };
+
+  const updateReplies = async (next: ArchiveSettings) => {
+    setSettings(next);
+    try {
+      await setReplyRecording(next.save_replies, next.reply_retention_days, next.max_reply_recordings);
+    } catch (error) {
+      setStatus(`❌ ${error}`);
+    }
+  };
+
+  const toggleReplies = async (sessionId: string) => {
+    if (openReplies?.sessionId === sessionId) {
+      setOpenReplies(null);
+      return;
+    }
+    try {
+      setOpenReplies({ sessionId, replies: await listConversationReplies(sessionId) });
+    } catch (error) {
+      setStatus(`❌ ${error}`);
+    }
+  };
+
+  const handleReplay = async (sessionId: string, replyId: string) => {
+    try {
+      await replayReply(sessionId, replyId);
+    } catch (error) {
+      setStatus(`❌ ${error}`);
+    }
+  };
+
+  const handleExport = async (sessionId: string) => {
+    try {
+      const path = await exportConversation(sessionId, format, includeAudio);
+      setStatus(`📦 Saved ${path}`);
+    } catch (error) {
+      setStatus(`❌ ${error}`);
+    }
+  };
+
+  if (!settings) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="flex items-center justify-between mb-2">
+        <div className="text-sm text-gray-600 dark:text-gray-300">🗂️ Conversations</div>
+        <button
+          onClick={refresh}
+          className="px-2 py-1 text-xs bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded text-gray-700 dark:text-gray-200"
+        >
+          🔄
+        </button>
+      </div>
+      <div className="flex flex-wrap gap-3 text-xs text-gray-700 dark:text-gray-200 mb-2">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.save_transcripts}
+            onChange={(e) => update({ ...settings, save_transcripts: e.target.checked })}
+          />
+          Save transcripts
+        </label>
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.save_audio}
+            disabled={!settings.save_transcripts}
+            onChange={(e) => update({ ...settings, save_audio: e.target.checked })}
+          />
+          Save audio
+        </label>
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.save_replies}
+            disabled={!settings.save_transcripts}
+            onChange={(e) => updateReplies({ ...settings, save_replies: e.target.checked })}
+          />
+          Save replies
+        </label>
+        <select
+          value={format}
+          onChange={(e) => setFormat(e.target.value as ExportFormat)}
+          className="bg-white dark:bg-gray-600 rounded px-1"
+        >
+          <option value="markdown">Markdown</option>
+          <option value="json">JSON</option>
+        </select>
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={includeAudio}
+            onChange={(e) => setIncludeAudio(e.target.checked)}
+          />
+          Include audio
+        </label>
+      </div>
+      {settings.save_replies && (
+        <div className="flex items-center gap-1 text-xs text-gray-700 dark:text-gray-200 mb-2">
+          Keep replies
+          <input
+            type="number"
+            min={0}
+            value={settings.reply_retention_days}
+            onChange={(e) => setSettings({ ...settings, reply_retention_days: Number(e.target.value) })}
+            onBlur={() => updateReplies(settings)}
+            className="w-14 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          days (0 = no limit), at most
+          <input
+            type="number"
+            min={1}
+            value={settings.max_reply_recordings}
+            onChange={(e) => setSettings({ ...settings, max_reply_recordings: Number(e.target.value) })}
+            onBlur={() => updateReplies(settings)}
+            className="w-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </div>
+      )}
+      <div className="space-y-1 max-h-32 overflow-y-auto">
+        {conversations.length === 0 && (
+          <div className="text-xs text-gray-500 dark:text-gray-400">No saved conversations</div>
+        )}
+        {conversations.slice(0, 10).map((conversation) => (
+          <div key={conversation.session_id} className="text-xs text-gray-700 dark:text-gray-200">
+            <div className="flex items-center justify-between">
+              <span>
+                {new Date(conversation.started_at).toLocaleString()} · {conversation.turns} turns
+                {conversation.has_audio && " · 🎵"}
+              </span>
+              <div className="flex gap-1">
+                {conversation.has_replies && (
+                  <button
+                    onClick={() => toggleReplies(conversation.session_id)}
+                    className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded"
+                  >
+                    🔁 Replies
+                  </button>
+                )}
+                <button
+                  onClick={() => handleExport(conversation.session_id)}
+                  className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded"
+                >
+                  📦 Export
+                </button>
+              </div>
+            </div>
+            {openReplies?.sessionId === conversation.session_id && (
+              <div className="ml-2 mt-1 space-y-1">
+                {openReplies.replies.length === 0 && (
+                  <div className="text-gray-500 dark:text-gray-400">No replies kept</div>
+                )}
+                {openReplies.replies.map((reply) => (
+                  <div key={reply.reply_id} className="flex items-center gap-2">
+                    <button
+                      onClick={() => handleReplay(conversation.session_id, reply.reply_id)}
+                      className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded"
+                    >
+                      ▶️
+                    </button>
+                    <span className="truncate">
+                      {new Date(reply.timestamp).toLocaleTimeString()} · {reply.text}
+                    </span>
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+        ))}
+      </div>
+      {status && (
+        <div className="mt-2 text-xs text-gray-600 dark:text-gray-300 break-all">{status}</div>
+      )}
+    </div>
+  );
+};
+
+export default ConversationArchive;
@@ -0,0 +1,106 @@
+import React, { useEffect, useState } from "react";
+import {
+  DictationStatus,
+  getDictation,
+  onDictationChanged,
+  setDictation,
+  startDictation,
+  stopDictation,
+} from "../services/dictation";
+
+const PUNCTUATION = ["comma", "period", "question mark", "new line", "new paragraph", "open quote"];
+
+const DictationSettings: React.FC = () => {
+  const [status, setStatus] = useState<DictationStatus | null>(null);
+  const [hotkey, setHotkey] = useState("");
+  const [stopPhrase, setStopPhrase] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getDictation()
+      .then((loaded) => {
+        setStatus(loaded);
+        setHotkey(loaded.hotkey);
+        setStopPhrase(loaded.stop_phrase);
+      })
+      .catch((error) => console.error("Failed to load dictation settings:", error));
+
+    onDictationChanged((active) => setStatus((current) => current && { ...current, active })).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  if (!status) return null;
+
+  const save = async () => {
+    try {
+      setMessage(`✅ ${await setDictation(hotkey, stopPhrase)}`);
+      setStatus(await getDictation());
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const toggle = async () => {
+    try {
+      setMessage(`✅ ${await (status.active ? stopDictation() : startDictation())}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">⌨️ Dictation</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-2">
+          <span className="w-20">Hotkey</span>
+          <input
+            type="text"
+            value={hotkey}
+            onChange={(e) => setHotkey(e.target.value)}
+            placeholder="e.g. CommandOrControl+Alt+D, empty for none"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </label>
+        <label className="flex items-center gap-2">
+          <span className="w-20">Stop phrase</span>
+          <input
+            type="text"
+            value={stopPhrase}
+            onChange={(e) => setStopPhrase(e.target.value)}
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </label>
+        <div className="flex gap-2">
+          <button
+            onClick={save}
+            className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded"
+          >
+            Save
+          </button>
+          <button
+            onClick={toggle}
+            className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded"
+          >
+            {status.active ? "Stop Dictating" : "Start Dictating"}
+          </button>
+        </div>
+        <div className="text-gray-500 dark:text-gray-400">
+          Press the hotkey in any app and speak; Eva types what you say there instead of answering. Say
+          {" "}{PUNCTUATION.map((command) => `"${command}"`).join(", ")} for punctuation. On macOS, Eva needs
+          Accessibility access to type.
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default DictationSettings;
@@ -0,0 +1,85 @@
+import React, { useEffect, useState } from "react";
+import {
+  DndBehavior,
+  DndState,
+  getDndBehavior,
+  getDndState,
+  onDndState,
+  setDndBehavior,
+} from "../services/dnd";
+
+const REASON_LABELS = {
+  focus: "focus mode is on",
+  fullscreen: "a fullscreen app is in front",
+};
+
+const DndSettings: React.FC = () => {
+  const [behavior, setBehavior] = useState<DndBehavior | null>(null);
+  const [state, setState] = useState<DndState | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getDndBehavior()
+      .then(setBehavior)
+      .catch((error) => console.error("Failed to load do-not-disturb settings:", error));
+    getDndState()
+      .then(setState)
+      .catch((error) => console.error("Failed to load do-not-disturb state:", error));
+
+    onDndState(setState).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  if (!behavior) return null;
+
+  const toggle = async (key: keyof Omit<DndBehavior, "poll_interval_secs">, value: boolean) => {
+    const updated = { ...behavior, [key]: value };
+    setBehavior(updated);
+    try {
+      setMessage(`✅ ${await setDndBehavior(updated)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+      setBehavior(behavior);
+    }
+  };
+
+  const checkbox = (key: keyof Omit<DndBehavior, "poll_interval_secs">, label: string, indent = false) => (
+    <label className={`flex items-center gap-1 ${indent ? "pl-4" : ""}`}>
+      <input
+        type="checkbox"
+        checked={behavior[key]}
+        onChange={(e) => toggle(key, e.target.checked)}
+      />
+      {label}
+    </label>
+  );
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔕 Do Not Disturb</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <div>
+          {state?.active && state.reason
+            ? `🔕 Active: ${REASON_LABELS[state.reason]}`
+            : "🔔 Not active"}
+        </div>
+        {checkbox("detect_focus", "Follow the system focus / do-not-disturb mode")}
+        {checkbox("detect_fullscreen", "Treat fullscreen apps as do-not-disturb")}
+        <div className="pt-1">While active:</div>
+        {checkbox("suppress_proactive", "Don't speak up on my own", true)}
+        {checkbox("suppress_cues", "Mute sound cues", true)}
+        {checkbox("suppress_wake", "Ignore the wake word", true)}
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default DndSettings;
@@ -0,0 +1,103 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  EarconCue,
+  EarconSettings as Settings,
+  playEarcon,
+  setEarcon,
+} from "../services/earcons";
+import { NotificationSettings, setNotificationsEnabled } from "../services/notifications";
+
+const CUES: { cue: EarconCue; key: keyof Settings; label: string }[] = [
+  { cue: "listening-start", key: "listening_start", label: "Listening" },
+  { cue: "acknowledgment", key: "acknowledgment", label: "Acknowledged" },
+  { cue: "error", key: "error", label: "Error" },
+  { cue: "alarm", key: "alarm", label: "Timers & alarms" },
+];
+
+const EarconSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [notifications, setNotifications] = useState<NotificationSettings | null>(null);
+
+  useEffect(() => {
+    invoke<{ earcons: Settings; notifications: NotificationSettings }>("get_settings")
+      .then((loaded) => {
+        setSettings(loaded.earcons);
+        setNotifications(loaded.notifications);
+      })
+      .catch((error) => console.error("Failed to load earcon settings:", error));
+  }, []);
+
+  const update = async (
+    cue: EarconCue,
+    key: keyof Settings,
+    options: { enabled?: boolean; volume?: number }
+  ) => {
+    setSettings((prev) => prev && { ...prev, [key]: { ...prev[key], ...options } });
+    try {
+      await setEarcon(cue, options);
+    } catch (error) {
+      console.error("Failed to update earcon:", error);
+    }
+  };
+
+  const toggleNotifications = async (enabled: boolean) => {
+    setNotifications({ enabled });
+    try {
+      await setNotificationsEnabled(enabled);
+    } catch (error) {
+      console.error("Failed to update notifications:", error);
+    }
+  };
+
+  if (!settings) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔔 Sound Cues</div>
+      <div className="space-y-1">
+        {CUES.map(({ cue, key, label }) => (
+          <div key={cue} className="flex items-center gap-2 text-xs">
+            <label className="flex items-center gap-1 w-32 text-gray-700 dark:text-gray-200">
+              <input
+                type="checkbox"
+                checked={settings[key].enabled}
+                onChange={(e) => update(cue, key, { enabled: e.target.checked })}
+              />
+              {label}
+            </label>
+            <input
+              type="range"
+              min="0"
+              max="1"
+              step="0.05"
+              value={settings[key].volume}
+              disabled={!settings[key].enabled}
+              onChange={(e) => update(cue, key, { volume: parseFloat(e.target.value) })}
+              className="flex-1"
+            />
+            <button
+              onClick={() => playEarcon(cue)}
+              disabled={!settings[key].enabled}
+              className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded text-gray-700 dark:text-gray-200"
+            >
+              ▶
+            </button>
+          </div>
+        ))}
+        {notifications && (
+          <label className="flex items-center gap-1 pt-1 text-xs text-gray-700 dark:text-gray-200">
+            <input
+              type="checkbox"
+              checked={notifications.enabled}
+              onChange={(e) => toggleNotifications(e.target.checked)}
+            />
+            Notify me of replies while Eva is minimized
+          </label>
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default EarconSettings;
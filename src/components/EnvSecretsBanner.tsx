@@ -0,0 +1,58 @@
+import React, { useEffect, useState } from "react";
+import { EnvSecretOffer, answerEnvSecretOffers, getEnvSecretOffers } from "../services/secrets";
+import { openaiRealtimeService } from "../services/openai-simple";
+
+// Offers to move keys found in PV_ACCESS_KEY / OPENAI_API_KEY into the keychain
+const EnvSecretsBanner: React.FC = () => {
+  const [offers, setOffers] = useState<EnvSecretOffer[]>([]);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getEnvSecretOffers()
+      .then(setOffers)
+      .catch((error) => console.error("Failed to check for keys in the environment:", error));
+  }, []);
+
+  const handleAnswer = async (accept: boolean) => {
+    try {
+      setMessage(`✅ ${await answerEnvSecretOffers(accept)}`);
+      setOffers([]);
+      if (accept) {
+        await openaiRealtimeService.loadStoredApiKey();
+      }
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  if (offers.length === 0) {
+    return message ? <p className="mb-4 text-sm text-gray-600 dark:text-gray-300">{message}</p> : null;
+  }
+
+  return (
+    <div className="mb-4 p-3 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-200 dark:border-yellow-800 rounded-lg flex items-center justify-between gap-4">
+      <p className="text-sm text-yellow-800 dark:text-yellow-300">
+        🔐 Found {offers.map((offer) => `${offer.label} (${offer.env_var})`).join(" and ")} in the environment.
+        Save {offers.length === 1 ? "it" : "them"} to the system keychain so Eva works without
+        environment variables?
+        {offers.some((offer) => offer.replaces_stored) && " This replaces the key already saved."}
+      </p>
+      <div className="flex gap-2">
+        <button
+          onClick={() => handleAnswer(true)}
+          className="px-4 py-2 bg-yellow-500 hover:bg-yellow-600 text-white rounded-lg font-medium transition-colors whitespace-nowrap"
+        >
+          Save to Keychain
+        </button>
+        <button
+          onClick={() => handleAnswer(false)}
+          className="px-4 py-2 text-yellow-800 dark:text-yellow-300 hover:underline whitespace-nowrap"
+        >
+          Keep Using Env
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default EnvSecretsBanner;
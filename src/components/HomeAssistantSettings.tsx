@@ -0,0 +1,71 @@
+import React, { useEffect, useState } from "react";
+import { getHomeAssistant, setHomeAssistant } from "../services/tools";
+
+const HomeAssistantSettings: React.FC = () => {
+  const [url, setUrl] = useState("");
+  const [token, setToken] = useState("");
+  const [tokenConfigured, setTokenConfigured] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    getHomeAssistant()
+      .then((status) => {
+        setUrl(status.url);
+        setTokenConfigured(status.token_configured);
+      })
+      .catch((error) => console.error("Failed to load Home Assistant settings:", error));
+  }, []);
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    setMessage(null);
+    try {
+      const result = await setHomeAssistant(url.trim(), token.trim() || null);
+      setToken(""); // Clear the input for security
+      setTokenConfigured(url.trim() !== "");
+      setMessage(`✅ ${result}. Reconnect to apply.`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🏡 Home Assistant</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        <input
+          type="text"
+          value={url}
+          onChange={(e) => setUrl(e.target.value)}
+          placeholder="http://homeassistant.local:8123"
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        />
+        <input
+          type="password"
+          value={token}
+          onChange={(e) => setToken(e.target.value)}
+          placeholder={
+            tokenConfigured ? "Token saved in keychain" : "Long-lived access token"
+          }
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        />
+        <button
+          onClick={handleSave}
+          disabled={isSaving}
+          className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600 disabled:opacity-50"
+        >
+          {isSaving ? "Checking..." : "Save"}
+        </button>
+        <div className="text-gray-500 dark:text-gray-400">
+          Then try "turn off the living room lights". Leave the URL empty to disconnect.
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default HomeAssistantSettings;
@@ -0,0 +1,110 @@
+import React, { useCallback, useEffect, useState } from "react";
+import {
+  CaptureHost,
+  CaptureHostsState,
+  InputDevice,
+  InputDeviceSettings as Settings,
+  getCaptureHosts,
+  getInputDeviceSettings,
+  listInputDevices,
+  setCaptureHost,
+  setExcludeMonitors,
+  setInputDevice,
+  setPreferNativeRate,
+} from "../services/input-devices";
+
+const HOST_LABELS: Record<CaptureHost, string> = {
+  system: "WASAPI (shared, event-driven)",
+  asio: "ASIO (low latency, exclusive driver)",
+};
+
+const deviceLabel = (device: InputDevice): string => {
+  let label = device.label;
+  if (device.node_name && device.node_name !== device.label) label += ` (${device.node_name})`;
+  if (device.is_monitor) label += " — monitor";
+  if (device.is_default) label += " — default";
+  return label;
+};
+
+// Which microphone the wake word listens on
+const InputDeviceSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [devices, setDevices] = useState<InputDevice[]>([]);
+  const [hosts, setHosts] = useState<CaptureHostsState | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    setSettings(await getInputDeviceSettings());
+    setHosts(await getCaptureHosts());
+    setDevices(await listInputDevices());
+  }, []);
+
+  useEffect(() => {
+    load().catch((error) => console.error("Failed to load input devices:", error));
+  }, [load]);
+
+  if (!settings) return null;
+
+  const run = async (action: Promise<string>) => {
+    try {
+      setMessage(`✅ ${await action}`);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🎤 Microphone</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        {hosts && hosts.available.length > 1 && (
+          <select
+            value={hosts.selected}
+            onChange={(e) => run(setCaptureHost(e.target.value as CaptureHost))}
+            className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          >
+            {hosts.available.map((host) => (
+              <option key={host} value={host}>
+                {HOST_LABELS[host]}
+              </option>
+            ))}
+          </select>
+        )}
+        <select
+          value={settings.input_device ?? ""}
+          onChange={(e) => run(setInputDevice(e.target.value || null))}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        >
+          <option value="">Automatic</option>
+          {devices.map((device) => (
+            <option key={device.id} value={device.id}>
+              {deviceLabel(device)}
+            </option>
+          ))}
+        </select>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.exclude_monitors}
+            onChange={(e) => run(setExcludeMonitors(e.target.checked))}
+          />
+          Skip monitor sources (they pick up what your speakers play)
+        </label>
+        {hosts && (
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={hosts.prefer_native_rate}
+              onChange={(e) => run(setPreferNativeRate(e.target.checked))}
+            />
+            Open the microphone at 16 kHz when it supports it (no resampling)
+          </label>
+        )}
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default InputDeviceSettings;
@@ -0,0 +1,50 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { CaptureSource } from "../services/audio-capture";
+
+// Lets Eva listen to system audio (e.g. a meeting) instead of the microphone; Windows only
+const InputSourceSettings: React.FC = () => {
+  const [available, setAvailable] = useState(false);
+  const [source, setSource] = useState<CaptureSource>("microphone");
+
+  useEffect(() => {
+    invoke<boolean>("system_audio_available")
+      .then(setAvailable)
+      .catch((error) => console.error("Failed to check system audio support:", error));
+    invoke<CaptureSource>("get_capture_source")
+      .then(setSource)
+      .catch((error) => console.error("Failed to load capture source:", error));
+  }, []);
+
+  const changeSource = async (next: CaptureSource) => {
+    setSource(next);
+    try {
+      await invoke<string>("set_capture_source", { source: next });
+    } catch (error) {
+      console.error("Failed to set capture source:", error);
+    }
+  };
+
+  if (!available) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🎧 What Eva Hears</div>
+      <div className="space-y-2 text-xs">
+        <select
+          value={source}
+          onChange={(e) => changeSource(e.target.value as CaptureSource)}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-700 dark:text-gray-200"
+        >
+          <option value="microphone">Microphone</option>
+          <option value="system-audio">System audio (what's playing on this PC)</option>
+        </select>
+        <div className="text-gray-500 dark:text-gray-400">
+          Applies from the next recording. The wake word still listens to the microphone.
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default InputSourceSettings;
@@ -0,0 +1,65 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { LatencyMetrics, TurnLatency, getLatencyMetrics } from "../services/latency";
+
+const SPAN_LABELS: Record<string, string> = {
+  wake_to_first_audio: "Wake word → first audio",
+  capture_to_send: "Capture → send",
+  api_first_delta: "Request → first reply",
+  playback_start: "Audio → playback",
+};
+
+const LatencyPanel: React.FC = () => {
+  const [metrics, setMetrics] = useState<LatencyMetrics | null>(null);
+
+  const refresh = useCallback(() => {
+    getLatencyMetrics()
+      .then(setMetrics)
+      .catch((error) => console.error("Failed to load latency metrics:", error));
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    refresh();
+    listen<TurnLatency>("latency-summary", refresh).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  if (!metrics || metrics.turns === 0) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-1">
+        ⏱️ Latency (last {metrics.turns} turns)
+      </div>
+      <table className="w-full text-xs text-gray-700 dark:text-gray-200">
+        <thead>
+          <tr className="text-gray-500 dark:text-gray-400">
+            <th className="text-left font-normal"></th>
+            <th className="text-right font-normal">last</th>
+            <th className="text-right font-normal">median</th>
+            <th className="text-right font-normal">p95</th>
+          </tr>
+        </thead>
+        <tbody>
+          {metrics.spans.map((span) => (
+            <tr key={span.span}>
+              <td>{SPAN_LABELS[span.span] ?? span.span}</td>
+              <td className="text-right">{span.last_ms} ms</td>
+              <td className="text-right">{span.p50_ms} ms</td>
+              <td className="text-right">{span.p95_ms} ms</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+};
+
+export default LatencyPanel;
@@ -0,0 +1,151 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { LlmEndpointSettings } from "../services/backend";
+
+const LlmEndpointSetup: React.FC = () => {
+  const [enabled, setEnabled] = useState(false);
+  const [baseUrl, setBaseUrl] = useState("");
+  const [model, setModel] = useState("");
+  const [apiKey, setApiKey] = useState("");
+  const [models, setModels] = useState<string[]>([]);
+  const [message, setMessage] = useState<string | null>(null);
+  const [isLoadingModels, setIsLoadingModels] = useState(false);
+
+  useEffect(() => {
+    invoke<{ llm_endpoint: LlmEndpointSettings }>("get_settings")
+      .then(({ llm_endpoint }) => {
+        setEnabled(llm_endpoint.enabled);
+        setBaseUrl(llm_endpoint.base_url);
+        setModel(llm_endpoint.model);
+      })
+      .catch((error) => console.error("Failed to load chat endpoint settings:", error));
+  }, []);
+
+  const saveEndpoint = (enabledOverride = enabled) =>
+    invoke<string>("set_llm_endpoint", {
+      baseUrl: baseUrl.trim(),
+      model: model.trim(),
+      apiKey: apiKey.trim() || null,
+      enabled: enabledOverride,
+    });
+
+  const handleLoadModels = async () => {
+    setIsLoadingModels(true);
+    setMessage(null);
+    try {
+      // Models are listed from the saved endpoint
+      await saveEndpoint();
+      const available = await invoke<string[]>("list_llm_models");
+      setModels(available);
+      if (available.length === 0) {
+        setMessage("⚠️ The endpoint didn't report any models");
+      } else if (!available.includes(model)) {
+        setModel(available[0]);
+      }
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    } finally {
+      setIsLoadingModels(false);
+    }
+  };
+
+  const handleSave = async () => {
+    try {
+      await saveEndpoint();
+      setApiKey(""); // Clear the input for security
+      setMessage("✅ Chat endpoint saved. Reconnect to apply.");
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleToggle = async (value: boolean) => {
+    setEnabled(value);
+    try {
+      await saveEndpoint(value);
+      setMessage(value ? "✅ Using chat endpoint. Reconnect to apply." : null);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="mb-6 p-4 bg-green-50 dark:bg-green-900/20 border border-green-200 dark:border-green-800 rounded-lg">
+      <label className="flex items-center gap-2 text-sm font-semibold text-green-800 dark:text-green-300">
+        <input
+          type="checkbox"
+          checked={enabled}
+          onChange={(e) => handleToggle(e.target.checked)}
+        />
+        🦙 Use a local / OpenAI-compatible chat model (Ollama, LM Studio...)
+      </label>
+
+      {enabled && (
+        <div className="mt-3 space-y-2">
+          <div className="flex gap-2">
+            <input
+              type="text"
+              value={baseUrl}
+              onChange={(e) => setBaseUrl(e.target.value)}
+              placeholder="http://localhost:11434/v1"
+              className="flex-1 px-3 py-2 border border-green-300 dark:border-green-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-green-500"
+            />
+            <button
+              onClick={handleLoadModels}
+              disabled={!baseUrl.trim() || isLoadingModels}
+              className="px-4 py-2 bg-green-600 hover:bg-green-700 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
+            >
+              {isLoadingModels ? "Loading..." : "Load Models"}
+            </button>
+          </div>
+          <div className="flex gap-2">
+            {models.length > 0 ? (
+              <select
+                value={model}
+                onChange={(e) => setModel(e.target.value)}
+                className="flex-1 px-3 py-2 border border-green-300 dark:border-green-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+              >
+                {models.map((id) => (
+                  <option key={id} value={id}>
+                    {id}
+                  </option>
+                ))}
+              </select>
+            ) : (
+              <input
+                type="text"
+                value={model}
+                onChange={(e) => setModel(e.target.value)}
+                placeholder="Model name"
+                className="flex-1 px-3 py-2 border border-green-300 dark:border-green-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-green-500"
+              />
+            )}
+            <input
+              type="password"
+              value={apiKey}
+              onChange={(e) => setApiKey(e.target.value)}
+              placeholder="API key (optional)"
+              className="w-48 px-3 py-2 border border-green-300 dark:border-green-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-green-500"
+            />
+            <button
+              onClick={handleSave}
+              disabled={!baseUrl.trim() || !model.trim()}
+              className="px-4 py-2 bg-green-600 hover:bg-green-700 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
+            >
+              Save
+            </button>
+          </div>
+          <p className="text-xs text-green-700 dark:text-green-400">
+            Text only — pair with local Whisper for voice input and local voice for speech.
+          </p>
+        </div>
+      )}
+
+      {message && (
+        <div className="mt-2 text-sm text-green-700 dark:text-green-400">{message}</div>
+      )}
+    </div>
+  );
+};
+
+export default LlmEndpointSetup;
@@ -0,0 +1,127 @@
+import React, { useCallback, useEffect, useState } from "react";
+import {
+  LogEntry,
+  LogLevelFilter,
+  formatLogs,
+  getRecentLogs,
+  openLogDirectory,
+} from "../services/logs";
+
+const LEVEL_COLORS: Record<LogEntry["level"], string> = {
+  ERROR: "text-red-500",
+  WARN: "text-yellow-500",
+  INFO: "text-green-500",
+  DEBUG: "text-blue-400",
+  TRACE: "text-gray-400",
+};
+
+const LogViewer: React.FC = () => {
+  const [entries, setEntries] = useState<LogEntry[]>([]);
+  const [level, setLevel] = useState<LogLevelFilter>("info");
+  const [lines, setLines] = useState(200);
+  const [error, setError] = useState<string | null>(null);
+  const [copied, setCopied] = useState(false);
+
+  const refresh = useCallback(async () => {
+    try {
+      setEntries(await getRecentLogs(lines, level));
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }, [lines, level]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const handleCopy = async () => {
+    try {
+      await navigator.clipboard.writeText(formatLogs(entries));
+      setCopied(true);
+      setTimeout(() => setCopied(false), 2000);
+    } catch (err) {
+      setError(`Failed to copy logs: ${err}`);
+    }
+  };
+
+  const handleOpenFolder = async () => {
+    try {
+      await openLogDirectory();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="w-full max-w-5xl p-6 bg-white dark:bg-gray-800 rounded-lg shadow-lg">
+      <div className="flex items-center justify-between mb-4">
+        <h2 className="text-2xl font-bold text-gray-800 dark:text-white">📜 Logs</h2>
+        <div className="flex items-center gap-2 text-sm">
+          <select
+            value={level}
+            onChange={(e) => setLevel(e.target.value as LogLevelFilter)}
+            className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+          >
+            <option value="error">Errors</option>
+            <option value="warn">Warnings+</option>
+            <option value="info">Info+</option>
+            <option value="debug">Debug+</option>
+            <option value="trace">Everything</option>
+          </select>
+          <select
+            value={lines}
+            onChange={(e) => setLines(parseInt(e.target.value, 10))}
+            className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+          >
+            <option value={100}>100 lines</option>
+            <option value={200}>200 lines</option>
+            <option value={1000}>1000 lines</option>
+          </select>
+          <button
+            onClick={refresh}
+            className="px-3 py-1 bg-blue-500 hover:bg-blue-600 text-white rounded"
+          >
+            Refresh
+          </button>
+          <button
+            onClick={handleCopy}
+            disabled={entries.length === 0}
+            className="px-3 py-1 bg-gray-500 hover:bg-gray-600 disabled:bg-gray-400 text-white rounded"
+          >
+            {copied ? "Copied!" : "Copy"}
+          </button>
+          <button
+            onClick={handleOpenFolder}
+            className="px-3 py-1 bg-gray-500 hover:bg-gray-600 text-white rounded"
+          >
+            Open Folder
+          </button>
+        </div>
+      </div>
+
+      {error && (
+        <div className="mb-4 p-3 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg text-sm text-red-700 dark:text-red-300">
+          {error}
+        </div>
+      )}
+
+      <div className="bg-gray-900 text-gray-100 p-4 rounded-lg h-[32rem] overflow-y-auto font-mono text-xs">
+        {entries.length === 0 ? (
+          <div className="text-gray-500">No log entries</div>
+        ) : (
+          entries.map((entry, index) => (
+            <div key={index} className="whitespace-pre-wrap">
+              <span className="text-gray-500">{entry.timestamp}</span>{" "}
+              <span className={LEVEL_COLORS[entry.level]}>{entry.level}</span>{" "}
+              <span className="text-gray-400">{entry.target}:</span>{" "}
+              {entry.message}
+            </div>
+          ))
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default LogViewer;
@@ -0,0 +1,65 @@
+import React, { useEffect, useState } from "react";
+import { MetricsSettings as EndpointSettings, getMetricsSettings, setMetricsEndpoint } from "../services/metrics";
+
+const MetricsSettings: React.FC = () => {
+  const [settings, setSettings] = useState<EndpointSettings | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getMetricsSettings()
+      .then(setSettings)
+      .catch((error) => console.error("Failed to load metrics settings:", error));
+  }, []);
+
+  if (!settings) return null;
+
+  const save = async (next: EndpointSettings) => {
+    setSettings(next);
+    try {
+      setMessage(`✅ ${await setMetricsEndpoint(next.enabled, next.port)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">📈 Metrics Endpoint</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.enabled}
+            onChange={(e) => save({ ...settings, enabled: e.target.checked })}
+          />
+          Serve Prometheus metrics on localhost
+        </label>
+        <div className="flex items-center gap-2">
+          <span>Port</span>
+          <input
+            type="number"
+            min={1}
+            max={65535}
+            value={settings.port}
+            onChange={(e) => setSettings({ ...settings, port: Number(e.target.value) })}
+            className="w-20 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          <button
+            onClick={() => save(settings)}
+            className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600"
+          >
+            Save
+          </button>
+        </div>
+        {settings.enabled && (
+          <div className="text-gray-500 dark:text-gray-400">
+            Scrape http://127.0.0.1:{settings.port}/metrics
+          </div>
+        )}
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default MetricsSettings;
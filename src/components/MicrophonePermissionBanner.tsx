@@ -0,0 +1,43 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+export type MicrophonePermission = "granted" | "denied" | "undetermined";
+
+const MicrophonePermissionBanner: React.FC = () => {
+  const [permission, setPermission] = useState<MicrophonePermission | null>(null);
+
+  useEffect(() => {
+    invoke<MicrophonePermission>("check_microphone_permission")
+      .then(setPermission)
+      .catch((error) => console.error("Failed to check microphone permission:", error));
+  }, []);
+
+  const handleRequest = async () => {
+    try {
+      setPermission(await invoke<MicrophonePermission>("request_microphone_permission"));
+    } catch (error) {
+      console.error("Failed to request microphone permission:", error);
+    }
+  };
+
+  if (permission === null || permission === "granted") return null;
+
+  return (
+    <div className="mb-4 p-3 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-200 dark:border-yellow-800 rounded-lg flex items-center justify-between gap-4">
+      <p className="text-sm text-yellow-800 dark:text-yellow-300">
+        🎙️{" "}
+        {permission === "denied"
+          ? "Microphone access is blocked. Allow Eva in your system privacy settings to use voice and wake words."
+          : "Eva needs microphone access for voice chat and wake words."}
+      </p>
+      <button
+        onClick={handleRequest}
+        className="px-4 py-2 bg-yellow-500 hover:bg-yellow-600 text-white rounded-lg font-medium transition-colors whitespace-nowrap"
+      >
+        {permission === "denied" ? "Open Settings" : "Allow Microphone"}
+      </button>
+    </div>
+  );
+};
+
+export default MicrophonePermissionBanner;
@@ -0,0 +1,134 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  MqttSettings as Settings,
+  MqttStatus,
+  getMqttStatus,
+  setMqttSettings,
+} from "../services/mqtt";
+
+const STATUS_POLL_MS = 5000;
+
+const MqttSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [password, setPassword] = useState("");
+  const [status, setStatus] = useState<MqttStatus | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<{ mqtt: Settings }>("get_settings")
+      .then(({ mqtt }) => setSettings(mqtt))
+      .catch((error) => console.error("Failed to load MQTT settings:", error));
+  }, []);
+
+  // The connection comes and goes with the broker, so keep the badge current
+  useEffect(() => {
+    const refresh = () =>
+      getMqttStatus()
+        .then(setStatus)
+        .catch((error) => console.error("Failed to load MQTT status:", error));
+    refresh();
+    const interval = setInterval(refresh, STATUS_POLL_MS);
+    return () => clearInterval(interval);
+  }, []);
+
+  if (!settings) return null;
+
+  const update = (change: Partial<Settings>) => setSettings({ ...settings, ...change });
+
+  const save = async (next: Settings) => {
+    setSettings(next);
+    try {
+      const result = await setMqttSettings(next, password || null);
+      setPassword(""); // Clear the input for security
+      setMessage(`✅ ${result}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">
+        🏠 Smart Home (MQTT)
+        {status?.enabled && (
+          <span className="ml-2 text-xs">
+            {status.connected ? "🟢 Connected" : "🟡 Connecting..."}
+          </span>
+        )}
+      </div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.enabled}
+            onChange={(e) => save({ ...settings, enabled: e.target.checked })}
+          />
+          Publish events and accept commands
+        </label>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={settings.host}
+            onChange={(e) => update({ host: e.target.value })}
+            placeholder="Broker host"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          <input
+            type="number"
+            min={1}
+            max={65535}
+            value={settings.port}
+            onChange={(e) => update({ port: Number(e.target.value) })}
+            className="w-20 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </div>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={settings.username ?? ""}
+            onChange={(e) => update({ username: e.target.value || null })}
+            placeholder="Username (optional)"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          <input
+            type="password"
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+            placeholder="Password (kept in keychain)"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </div>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={settings.client_id}
+            onChange={(e) => update({ client_id: e.target.value })}
+            placeholder="Client ID"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          <input
+            type="text"
+            value={settings.topic_prefix}
+            onChange={(e) => update({ topic_prefix: e.target.value })}
+            placeholder="Topic prefix"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </div>
+        <button
+          onClick={() => save(settings)}
+          className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600"
+        >
+          Save
+        </button>
+        <div className="text-gray-500 dark:text-gray-400">
+          Commands: {settings.topic_prefix}/command/ask, announce, mute, unmute,
+          toggle-listening
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default MqttSettings;
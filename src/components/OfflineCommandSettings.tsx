@@ -0,0 +1,45 @@
+import React, { useEffect, useState } from "react";
+import { getOfflineIntentsEnabled, setOfflineIntents } from "../services/intents";
+
+const EXAMPLES = ["stop", "volume up", "set volume to 40", "mute the mic", "timer for 10 minutes"];
+
+const OfflineCommandSettings: React.FC = () => {
+  const [enabled, setEnabled] = useState<boolean | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getOfflineIntentsEnabled()
+      .then(setEnabled)
+      .catch((error) => console.error("Failed to load offline command settings:", error));
+  }, []);
+
+  if (enabled === null) return null;
+
+  const handleToggle = async (value: boolean) => {
+    setEnabled(value);
+    try {
+      setMessage(`✅ ${await setOfflineIntents(value)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+      setEnabled(!value);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🧭 Offline Commands</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input type="checkbox" checked={enabled} onChange={(e) => handleToggle(e.target.checked)} />
+          Handle simple commands on this computer, even when offline
+        </label>
+        <div className="text-gray-500 dark:text-gray-400">
+          e.g. {EXAMPLES.map((example) => `"${example}"`).join(", ")}
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default OfflineCommandSettings;
@@ -1,12 +1,132 @@
 import React, { useState, useEffect, useRef, useCallback } from "react";
 import {
   openaiRealtimeService,
+  ConnectionHealth,
   OpenAIEvent,
   RealtimeStatus,
 } from "../services/openai-simple";
+import {
+  getActiveBackend,
+  LlmBackend,
+  syncBackendWithSettings,
+} from "../services/backend";
 import { audioCaptureService } from "../services/audio-capture";
+import { notifyResponse } from "../services/notifications";
+import { publishTranscript } from "../services/mqtt";
+import { reactToReply, setEmotion, showThinking } from "../services/avatar-emotion";
+import { getAutostart } from "../services/autostart";
 import { audioPlaybackService } from "../services/audio-playback";
+import { speakLocally } from "../services/local-tts";
+import { rememberResponse } from "../services/response-cache";
+import {
+  finishLatencyTurn,
+  markLatency,
+  recordSendDelay,
+  startLatencyTurn,
+} from "../services/latency";
+import { recordRealtimeReconnect } from "../services/metrics";
+import { recallMemory, rememberConversation } from "../services/memory";
+import {
+  recordAudio,
+  recordReplyAudio,
+  recordTurn,
+  startConversation,
+} from "../services/conversations";
+import { startUsageTracking } from "../services/usage";
+import { onStateSnapshot, reportRealtimeStatus } from "../services/eva-status";
+import { dictate, onDictationChanged, stopDictation } from "../services/dictation";
+import { watchForFalseTrigger } from "../services/wake-word-stats";
+import { takePreWakeAudio } from "../services/pre-wake";
+import { playEarcon, startEarcons } from "../services/earcons";
+import { startPlaybackSettings } from "../services/playback";
+import { getOverlaySettings, onOverlayChanged } from "../services/overlay";
+import { startDucking, stopDucking } from "../services/ducking";
+import {
+  SessionIdleEvent,
+  stopSessionIdleTimer,
+  touchSessionActivity,
+} from "../services/session-idle";
+import {
+  FollowupClosedEvent,
+  FollowupOpenEvent,
+  closeFollowupWindow,
+  openFollowupWindow,
+} from "../services/followup";
+import { EvaError, describeError, reportRealtimeError } from "../services/errors";
+import {
+  clearChatHistory,
+  closeChatWindow,
+  getOpenWindows,
+  onWindowsChanged,
+  openChatWindow,
+  publishChatTurn,
+} from "../services/chat-window";
+import {
+  TurnDetectionSettings as TurnDetection,
+  getTurnDetection,
+  onTurnDetectionChanged,
+} from "../services/turn-detection";
+import { getAudioFormat, onAudioFormatChanged } from "../services/audio-format";
+import { getActiveProfile, onProfileChanged } from "../services/profiles";
+import { getUserApiKey, onUserChanged } from "../services/users";
 import ApiKeySetup from "./ApiKeySetup";
+import EarconSettings from "./EarconSettings";
+import OutputSettings from "./OutputSettings";
+import InputDeviceSettings from "./InputDeviceSettings";
+import InputSourceSettings from "./InputSourceSettings";
+import TurnDetectionSettings from "./TurnDetectionSettings";
+import AudioFormatSettings from "./AudioFormatSettings";
+import StartupSettings from "./StartupSettings";
+import ProactiveSettings from "./ProactiveSettings";
+import DndSettings from "./DndSettings";
+import PowerSettings from "./PowerSettings";
+import ResponseCacheSettings from "./ResponseCacheSettings";
+import OfflineCommandSettings from "./OfflineCommandSettings";
+import DictationSettings from "./DictationSettings";
+import MqttSettings from "./MqttSettings";
+import ProxySettings from "./ProxySettings";
+import HomeAssistantSettings from "./HomeAssistantSettings";
+import RemindersPanel from "./RemindersPanel";
+import CalendarSettings from "./CalendarSettings";
+import ActionsSettings from "./ActionsSettings";
+import PluginsSettings from "./PluginsSettings";
+import AvatarSettings from "./AvatarSettings";
+import OverlaySettings from "./OverlaySettings";
+import LlmEndpointSetup from "./LlmEndpointSetup";
+import MicrophonePermissionBanner from "./MicrophonePermissionBanner";
+import EnvSecretsBanner from "./EnvSecretsBanner";
+import SecretStoreSettings from "./SecretStoreSettings";
+import PersonaSelector from "./PersonaSelector";
+import ProfileSettings from "./ProfileSettings";
+import UserSettings from "./UserSettings";
+import UsagePanel from "./UsagePanel";
+import LatencyPanel from "./LatencyPanel";
+import MetricsSettings from "./MetricsSettings";
+import ConversationArchive from "./ConversationArchive";
+import PrivacySettings from "./PrivacySettings";
+import VoiceLock from "./VoiceLock";
+
+// Mirrors response_profile::ResponseProfileEvent on the Rust side
+interface ResponseProfileEvent {
+  profile: "brief" | "normal" | "detailed";
+  max_output_tokens: number | null;
+  style_hint: string;
+}
+
+// Mirrors language::LanguageChangedEvent on the Rust side
+interface LanguageChangedEvent {
+  locale: string;
+  language_name: string | null;
+  instructions_hint: string;
+  transcription_language: string;
+  translation: { from: string; to: string } | null;
+}
+
+// Mirrors chat_mode::ChatModeEvent on the Rust side
+interface ChatModeEvent {
+  text_only: boolean;
+  spoken_replies: boolean;
+}
 
 interface Message {
   id: string;
@@ -23,14 +143,44 @@ const OpenAIChat: React.FC = () => {
   const [currentInput, setCurrentInput] = useState("");
   const [status, setStatus] = useState<RealtimeStatus>({
     apiKey: "missing",
+    provider: "openai",
     connected: false,
   });
+  const [health, setHealth] = useState<ConnectionHealth | null>(null);
+  // Re-registers the event listeners when connecting switches backends
+  const [backend, setBackend] = useState<LlmBackend>(getActiveBackend);
+  const [assistantName, setAssistantName] = useState(
+    openaiRealtimeService.getAssistantName()
+  );
   const [isLoading, setIsLoading] = useState(false);
+  const [isOverlay, setIsOverlay] = useState(false);
   const [currentResponse, setCurrentResponse] = useState("");
   const [logs, setLogs] = useState<Array<{ id: string; message: string }>>([]);
 
+  const [useLocalVoice, setUseLocalVoice] = useState(false);
+  const [isMicMuted, setIsMicMuted] = useState(false);
+  const [isTextOnly, setIsTextOnly] = useState(false);
+  const [spokenReplies, setSpokenReplies] = useState(false);
+  const [isFollowupOpen, setIsFollowupOpen] = useState(false);
+  const [isChatWindowOpen, setIsChatWindowOpen] = useState(false);
+
   const messagesEndRef = useRef<HTMLDivElement>(null);
   const currentResponseRef = useRef("");
+  // The latest user message, and the one the current response answers (for the response cache)
+  const lastUserQueryRef = useRef<string | null>(null);
+  const answeringQueryRef = useRef<string | null>(null);
+  const useLocalVoiceRef = useRef(false);
+  const textOnlyRef = useRef(false);
+  // The typed message being answered asked for a voice reply despite text-only mode
+  const spokenTurnRef = useRef(false);
+  // A wake word while a lazy connect is in flight shouldn't start a second one
+  const connectingRef = useRef(false);
+  // Transcripts are typed into the focused app rather than answered
+  const dictatingRef = useRef(false);
+  // Delta ordering: late deltas from interrupted responses must not leak into the next one
+  const activeResponseIdRef = useRef<string | null>(null);
+  const interruptedResponsesRef = useRef(new Set<string>());
+  const lastDeltaSequenceRef = useRef(0);
 
   const addLog = useCallback((message: string) => {
     const timestamp = new Date().toLocaleTimeString();
@@ -42,17 +192,48 @@ const OpenAIChat: React.FC = () => {
   }, []);
 
   const addMessage = useCallback(
-    (message: Omit<Message, "id" | "timestamp">) => {
+    (message: Omit<Message, "id" | "timestamp">, replyId?: string) => {
       const newMessage: Message = {
         id: Date.now().toString(),
         timestamp: new Date(),
         ...message,
       };
       setMessages((prev) => [...prev, newMessage]);
+      if (message.type !== "system") {
+        recordTurn(message.type, message.content, replyId);
+        publishTranscript(message.type, message.content);
+        publishChatTurn(message.type, message.content);
+      }
+      if (message.type === "user") {
+        lastUserQueryRef.current = message.content;
+      }
+      if (message.type === "assistant") {
+        reactToReply(message.content);
+      }
     },
     []
   );
 
+  // Deltas belonging to an interrupted or superseded response, or arriving out of order
+  const isStaleDelta = useCallback((data: any): boolean => {
+    const responseId: string | null = data?.response_id ?? null;
+    if (responseId && interruptedResponsesRef.current.has(responseId)) {
+      return true;
+    }
+    if (
+      responseId &&
+      activeResponseIdRef.current &&
+      responseId !== activeResponseIdRef.current
+    ) {
+      return true;
+    }
+    if (typeof data?.sequence === "number") {
+      if (data.sequence <= lastDeltaSequenceRef.current) return true;
+      lastDeltaSequenceRef.current = data.sequence;
+    }
+    return false;
+  }, []);
+
   // Handle OpenAI events
   const handleOpenAIEvent = useCallback(
     (event: OpenAIEvent) => {
@@ -63,12 +244,13 @@ const OpenAIChat: React.FC = () => {
           addLog(`✅ Realtime session created`);
           addMessage({
             type: "system",
-            content: `Realtime session started with Eva`,
+            content: `Realtime session started with ${assistantName}`,
           });
           break;
 
         case "input_audio_buffer.speech_started":
           addLog("🎤 Speech detected - Eva is listening...");
+          closeFollowupWindow("speech");
           break;
 
         case "input_audio_buffer.speech_stopped":
@@ -77,6 +259,12 @@ const OpenAIChat: React.FC = () => {
 
         case "input_audio_buffer.committed":
           addLog("📤 Audio committed for processing");
+          markLatency("request");
+          playEarcon("acknowledgment");
+          break;
+
+        case "conversation.item.truncated":
+          addLog(`✂️ Eva's reply cut at ${event.data?.audio_end_ms}ms, where playback stopped`);
           break;
 
         case "conversation.item.created":
@@ -103,14 +291,27 @@ const OpenAIChat: React.FC = () => {
 
         case "response.created":
           addLog("🤖 Eva is preparing response...");
+          // A spoken "stop" drops whatever was still arriving for the previous response
+          if (event.data?.response?.intent === "stop" && activeResponseIdRef.current) {
+            interruptedResponsesRef.current.add(activeResponseIdRef.current);
+          }
+          activeResponseIdRef.current = event.data?.response?.id ?? null;
+          answeringQueryRef.current = lastUserQueryRef.current;
+          lastUserQueryRef.current = null;
           setIsLoading(true);
+          showThinking();
           // Reset response accumulation
           currentResponseRef.current = "";
           setCurrentResponse("");
           break;
 
         case "response.text.delta":
+          if (isStaleDelta(event.data)) {
+            console.debug("⏭️ Dropped stale text delta:", event.data?.sequence);
+            break;
+          }
           if (event.data?.delta) {
+            markLatency("firstDelta");
             currentResponseRef.current += event.data.delta;
             setCurrentResponse(currentResponseRef.current);
             addLog(`📝 Text delta: "${event.data.delta}"`);
@@ -123,6 +324,10 @@ const OpenAIChat: React.FC = () => {
           break;
 
         case "response.audio.delta": {
+          if (isStaleDelta(event.data)) {
+            console.debug("⏭️ Dropped stale audio delta:", event.data?.sequence);
+            break;
+          }
           addLog("🔊 Received audio chunk from Eva");
           console.log("🔊 Full audio delta event:", event);
 
@@ -131,14 +336,20 @@ const OpenAIChat: React.FC = () => {
             event.data?.delta || (event as any).delta || event.data?.audio;
 
           if (audioData) {
+            markLatency("firstDelta");
+            markLatency("firstAudio");
             addLog(`🎵 Queueing audio chunk (${audioData.length} chars)`);
             console.log(
               "🎵 Audio delta sample:",
               audioData.substring(0, 100) + "..."
             );
 
+            recordAudio("assistant", audioData);
+            if (event.data?.response_id) {
+              recordReplyAudio(event.data.response_id, audioData);
+            }
             audioPlaybackService
-              .queueAudioChunk(audioData)
+              .queueAudioChunk(audioData, undefined, event.data?.item_id ?? undefined)
               .then(() => {
                 addLog("✅ Audio chunk queued successfully");
               })
@@ -161,24 +372,78 @@ const OpenAIChat: React.FC = () => {
           addLog("🔊 Eva audio response completed");
           break;
 
-        case "response.done":
+        case "response.done": {
+          const responseId: string | undefined = event.data?.response?.id;
+          if (responseId && activeResponseIdRef.current === responseId) {
+            activeResponseIdRef.current = null;
+          }
+          // Offline commands and cached answers never reached the model
+          const local: string | undefined = event.data?.response?.local;
+          const answeredQuery = answeringQueryRef.current;
+          answeringQueryRef.current = null;
+          lastUserQueryRef.current = null;
+          if (event.data?.response?.intent === "stop") {
+            audioPlaybackService.clearQueue();
+            currentResponseRef.current = "";
+            setCurrentResponse("");
+            setIsLoading(false);
+            setEmotion("neutral");
+            finishLatencyTurn(getActiveBackend().id, true);
+            addLog("⏹️ Stopped");
+            break;
+          }
+          // Interrupted responses were already cleared; don't save their partial text
+          if (responseId && interruptedResponsesRef.current.delete(responseId)) {
+            finishLatencyTurn(getActiveBackend().id, true);
+            addLog("⏹️ Interrupted response finished");
+            break;
+          }
+
           addLog("✅ Eva response completed");
+          finishLatencyTurn(getActiveBackend().id, Boolean(local));
+
+          // Speak text-only and local responses with the local TTS voice
+          if (
+            currentResponseRef.current.trim() &&
+            (!textOnlyRef.current || spokenTurnRef.current) &&
+            (useLocalVoiceRef.current ||
+              local ||
+              !getActiveBackend().capabilities.audioOutput)
+          ) {
+            speakLocally(currentResponseRef.current.trim(), responseId).catch((error) => {
+              addLog(`❌ Local voice failed: ${error}`);
+            });
+          }
 
           // Save the complete response to chat history
           if (currentResponseRef.current.trim()) {
-            addMessage({
-              type: "assistant",
-              content: currentResponseRef.current.trim(),
-            });
+            addMessage(
+              {
+                type: "assistant",
+                content: currentResponseRef.current.trim(),
+              },
+              responseId
+            );
             addLog(
               `💾 Saved Eva's response to history: "${currentResponseRef.current.trim()}"`
             );
+            notifyResponse(currentResponseRef.current.trim());
+            if (local) {
+              addLog(local === "cache" ? "⚡ Answered from the response cache" : "🧭 Handled offline");
+            } else if (answeredQuery) {
+              rememberResponse(answeredQuery, currentResponseRef.current.trim()).catch(
+                (error) => console.error("Failed to cache response:", error)
+              );
+            }
           } else {
             // If no text but Eva responded (audio-only response)
-            addMessage({
-              type: "assistant",
-              content: "(Audio response)",
-            });
+            addMessage(
+              {
+                type: "assistant",
+                content: "(Audio response)",
+              },
+              responseId
+            );
             addLog("💾 Saved audio-only response to history");
           }
 
@@ -186,126 +451,883 @@ const OpenAIChat: React.FC = () => {
           currentResponseRef.current = "";
           setCurrentResponse("");
           setIsLoading(false);
+
+          // Stay hot for a follow-up without the wake word
+          if (audioCaptureService.getRecordingState() && !textOnlyRef.current) {
+            openFollowupWindow();
+          }
+          touchSessionActivity();
           break;
+        }
+
+        case "error": {
+          const errorMsg = event.data?.message || "Unknown error";
+          reportRealtimeError(event);
+          setEmotion("confused");
+          addLog(`❌ Error: ${errorMsg}`);
+          addMessage({
+            type: "system",
+            content: `Error: ${errorMsg}`,
+          });
+          setIsLoading(false);
+          break;
+        }
+
+        case "openai-reconnecting":
+          recordRealtimeReconnect(false);
+          addLog(
+            `🔄 Connection lost - reconnecting in ${event.data?.delayMs}ms (attempt ${event.data?.attempt})`
+          );
+          break;
+
+        case "context-pruned":
+          addLog(
+            `🧹 Trimmed ${event.data.removed} old conversation items to stay within the context window (~${event.data.tokensBefore} → ~${event.data.estimatedTokensAfter} tokens)`
+          );
+          break;
+
+        case "openai-reconnected":
+          recordRealtimeReconnect(true);
+          addLog("✅ Reconnected to OpenAI");
+          addMessage({
+            type: "system",
+            content: "Connection restored",
+          });
+          break;
+
+        default:
+          addLog(`📋 Other event: ${event.type}`);
+      }
+    },
+    [addLog, addMessage, isStaleDelta, assistantName]
+  );
+
+  // Keep the assistant's name in sync with the backend identity settings
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupIdentity = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
+
+        const applyName = (name: string) => {
+          openaiRealtimeService.setAssistantName(name);
+          setAssistantName(name);
+        };
+
+        applyName(await invoke<string>("get_assistant_name"));
+        unlisten = await listen<{ assistant_name: string }>(
+          "assistant-name-changed",
+          (event) => applyName(event.payload.assistant_name)
+        );
+      } catch (error) {
+        console.error("Failed to load assistant name:", error);
+      }
+    };
+
+    setupIdentity();
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    startUsageTracking();
+  }, []);
+
+  // Sound cues for listening start, acknowledgment and errors
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    startEarcons()
+      .then((fn) => {
+        unlisten = fn;
+      })
+      .catch((error) => console.error("Failed to listen for earcons:", error));
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // Output device and volume for Eva's voice
+  // In overlay mode the window is borderless and transparent, so drop the backdrop
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getOverlaySettings()
+      .then((settings) => setIsOverlay(settings.enabled))
+      .catch((error) => console.error("Failed to load overlay settings:", error));
+    onOverlayChanged((settings) => setIsOverlay(settings.enabled)).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    startPlaybackSettings()
+      .then((fn) => {
+        unlisten = fn;
+      })
+      .catch((error) => console.error("Failed to apply playback settings:", error));
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // Structured errors from the backend, including Realtime errors reported back by reportRealtimeError
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    import("@tauri-apps/api/event").then(async ({ listen }) => {
+      unlisten = await listen<EvaError>("eva-error", (event) => {
+        console.warn(`⚠️ [${event.payload.code}]`, event.payload.message);
+        if (event.payload.recovery_hint) {
+          addLog(`💡 ${event.payload.recovery_hint}`);
+        }
+      });
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Privacy mute: drop microphone audio everywhere while muted
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const applyMute = (muted: boolean) => {
+      audioCaptureService.setMuted(muted);
+      setIsMicMuted(muted);
+    };
+
+    const setupMute = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
+
+        applyMute(await invoke<boolean>("get_mic_muted"));
+        unlisten = await listen<{ muted: boolean; device_released: boolean }>(
+          "mic-muted",
+          (event) => {
+            applyMute(event.payload.muted);
+            addLog(event.payload.muted ? "🔇 Microphone muted" : "🎤 Microphone unmuted");
+          }
+        );
+      } catch (error) {
+        console.error("Failed to load mute state:", error);
+      }
+    };
+
+    setupMute();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // After a reload, pick up what the backend kept running instead of assuming a fresh start
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+
+    onStateSnapshot(({ status }) => {
+      audioCaptureService.setMuted(status.mic_muted);
+      setIsMicMuted(status.mic_muted);
+      setIsWakeWordActive(status.wake_word === "listening");
+      addLog(`🔄 Synced with ${status.assistant_name}: ${status.phase}`);
+    })
+      .then((stop) => {
+        if (cancelled) stop();
+        else unlisten = stop;
+      })
+      .catch((error) => console.error("Failed to follow state snapshots:", error));
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Text-only chat mode: no capture, and no playback unless typed messages get spoken replies
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const applyMode = ({ text_only: textOnly, spoken_replies }: ChatModeEvent) => {
+      textOnlyRef.current = textOnly;
+      setIsTextOnly(textOnly);
+      setSpokenReplies(spoken_replies);
+      openaiRealtimeService.setTextOnly(textOnly);
+      if (textOnly && audioCaptureService.getRecordingState()) {
+        audioCaptureService.stopCapture();
+        setIsRecording(false);
+      }
+    };
+
+    const setupChatMode = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
+
+        applyMode(await invoke<ChatModeEvent>("get_chat_mode"));
+        unlisten = await listen<ChatModeEvent>(
+          "chat-mode-changed",
+          (event) => {
+            applyMode(event.payload);
+            addLog(
+              !event.payload.text_only
+                ? "🎤 Voice chat mode"
+                : event.payload.spoken_replies
+                ? "💬 Text-only chat mode with spoken replies"
+                : "💬 Text-only chat mode"
+            );
+          }
+        );
+      } catch (error) {
+        console.error("Failed to load chat mode:", error);
+      }
+    };
+
+    setupChatMode();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Typed messages arrive through send_chat_message, whichever surface sent them
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupChatMessages = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+
+        unlisten = await listen<{ text: string; timestamp: number; spoken: boolean }>(
+          "chat-message",
+          async (event) => {
+            const userMessage = event.payload.text;
+            spokenTurnRef.current = event.payload.spoken;
+            addMessage({
+              type: "user",
+              content: userMessage,
+            });
+
+            try {
+              setIsLoading(true);
+              addLog(`📤 Sending text: ${userMessage}`);
+              markLatency("request");
+              await getActiveBackend().sendText(userMessage, event.payload.spoken);
+            } catch (error) {
+              const errorMessage =
+                error instanceof Error ? error.message : "Unknown error";
+              addLog(`❌ Failed to send text: ${errorMessage}`);
+              addMessage({
+                type: "system",
+                content: `Failed to send message: ${errorMessage}`,
+              });
+              setIsLoading(false);
+            }
+          }
+        );
+
+        // Questions from eva://ask or --ask that arrived before this listener existed
+        const { invoke } = await import("@tauri-apps/api/core");
+        const pending = await invoke<string[]>("take_pending_questions");
+        for (const text of pending) {
+          await invoke("send_chat_message", { text });
+        }
+      } catch (error) {
+        console.error("Failed to listen for chat messages:", error);
+      }
+    };
+
+    setupChatMessages();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog, addMessage]);
+
+  // Track the separate chat window so its toggle shows the right state
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getOpenWindows()
+      .then((windows) => setIsChatWindowOpen(windows.chat))
+      .catch((error) => console.error("Failed to load open windows:", error));
+
+    onWindowsChanged((windows) => setIsChatWindowOpen(windows.chat)).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  // Follow --toggle-listening launches and eva://toggle-listening links
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupToggle = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+
+        unlisten = await listen<{ listening: boolean }>("wake-word-toggled", (event) => {
+          setIsWakeWordActive(event.payload.listening);
+          addLog(
+            event.payload.listening
+              ? "🎤 Wake word listening started remotely"
+              : "🔇 Wake word listening stopped remotely"
+          );
+        });
+      } catch (error) {
+        console.error("Failed to listen for wake word toggles:", error);
+      }
+    };
+
+    setupToggle();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Surface the quiet hours scheduler pausing and resuming wake word listening
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupQuietHours = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+
+        unlisten = await listen<{ active: boolean; start: string; end: string }>(
+          "quiet-hours-changed",
+          (event) => {
+            const { active, start, end } = event.payload;
+            addLog(
+              active
+                ? `🌙 Quiet hours (${start}-${end}): wake word paused`
+                : "☀️ Quiet hours ended"
+            );
+          }
+        );
+      } catch (error) {
+        console.error("Failed to listen for quiet hours:", error);
+      }
+    };
+
+    setupQuietHours();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Log when the OS focus mode or a fullscreen app starts holding Eva back
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupDnd = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+
+        unlisten = await listen<{ active: boolean; reason: string | null }>("dnd-state", (event) => {
+          const { active, reason } = event.payload;
+          addLog(active ? `🔕 Do-not-disturb on (${reason})` : "🔔 Do-not-disturb off");
+        });
+      } catch (error) {
+        console.error("Failed to listen for do-not-disturb:", error);
+      }
+    };
+
+    setupDnd();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Keep the session instructions in sync with the active persona
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupPersona = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
+
+        const personas = await invoke<{
+          active: string;
+          profiles: Array<{ name: string; instructions: string }>;
+        }>("get_personas");
+        const active = personas.profiles.find((p) => p.name === personas.active);
+        if (active) {
+          openaiRealtimeService.setInstructions(active.instructions);
+        }
+
+        unlisten = await listen<{ active: string; instructions: string }>(
+          "persona-changed",
+          (event) => {
+            openaiRealtimeService.setInstructions(event.payload.instructions);
+            addLog(`Persona switched to ${event.payload.active}`);
+          }
+        );
+      } catch (error) {
+        console.error("Failed to load persona:", error);
+      }
+    };
+
+    setupPersona();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Apply the response length profile to the session
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupResponseProfile = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
+
+        const apply = (profile: ResponseProfileEvent) =>
+          openaiRealtimeService.setResponseProfile(
+            profile.style_hint,
+            profile.max_output_tokens
+          );
+
+        apply(await invoke<ResponseProfileEvent>("get_response_profile"));
+        unlisten = await listen<ResponseProfileEvent>(
+          "response-profile-changed",
+          (event) => {
+            apply(event.payload);
+            addLog(`💬 Response length set to ${event.payload.profile}`);
+          }
+        );
+      } catch (error) {
+        console.error("Failed to load response profile:", error);
+      }
+    };
+
+    setupResponseProfile();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Server VAD or manual commits, as configured
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupTurnDetection = async () => {
+      try {
+        const apply = (settings: TurnDetection) =>
+          openaiRealtimeService.setTurnDetection(
+            settings.mode === "server_vad"
+              ? {
+                  type: "server_vad",
+                  threshold: settings.threshold,
+                  prefix_padding_ms: settings.prefix_padding_ms,
+                  silence_duration_ms: settings.silence_duration_ms,
+                }
+              : null
+          );
+
+        apply(await getTurnDetection());
+        unlisten = await onTurnDetectionChanged((settings) => {
+          apply(settings);
+          addLog(
+            settings.mode === "server_vad"
+              ? `🗣️ Turns end after ${settings.silence_duration_ms}ms of silence`
+              : "🗣️ Turns end when recording stops"
+          );
+        });
+      } catch (error) {
+        console.error("Failed to load turn detection:", error);
+      }
+    };
+
+    setupTurnDetection();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Wire encoding for the next Realtime connection
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupAudioFormat = async () => {
+      try {
+        openaiRealtimeService.setAudioFormats(await getAudioFormat());
+        unlisten = await onAudioFormatChanged((formats) => {
+          openaiRealtimeService.setAudioFormats(formats);
+          addLog(`🎚️ Audio encoding: ${formats.input} up, ${formats.output} down (from the next connection)`);
+        });
+      } catch (error) {
+        console.error("Failed to load audio format:", error);
+      }
+    };
+
+    setupAudioFormat();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Voice and tools of the active profile; the wake word and persona switch on the Rust side
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupProfile = async () => {
+      try {
+        openaiRealtimeService.setVoice((await getActiveProfile()).voice);
+        unlisten = await onProfileChanged(async (profile) => {
+          openaiRealtimeService.setVoice(profile.voice);
+          await openaiRealtimeService.refreshTools();
+          addLog(
+            profile.active
+              ? `🗂️ Profile "${profile.active}"${profile.automatic ? " (for the app in front)" : ""}`
+              : "🗂️ No profile, using the regular settings"
+          );
+        });
+      } catch (error) {
+        console.error("Failed to load the active profile:", error);
+      }
+    };
+
+    setupProfile();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Each user has their own history, memory and optionally API key; persona and voice lock
+  // switch on the Rust side
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupUser = async () => {
+      try {
+        await openaiRealtimeService.loadStoredApiKey();
+        openaiRealtimeService.setUserApiKey(await getUserApiKey());
+        unlisten = await onUserChanged(async (user) => {
+          openaiRealtimeService.setUserApiKey(await getUserApiKey());
+          setMessages([]);
+          await recallMemory();
+          // The live session still holds the previous user's conversation
+          if (openaiRealtimeService.getStatus().connected) {
+            await openaiRealtimeService.pruneItems(0);
+          }
+          const name = user.active ?? "the default user";
+          addLog(`👥 Switched to ${name}${user.automatic ? " (recognized by voice)" : ""}`);
+          addMessage({ type: "system", content: `Now talking with ${name}` });
+        });
+      } catch (error) {
+        console.error("Failed to load the active user:", error);
+      }
+    };
+
+    setupUser();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog, addMessage]);
+
+  // Respond and transcribe in the configured language
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupLanguage = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
 
-        case "error": {
-          const errorMsg = event.data?.message || "Unknown error";
-          addLog(`❌ Error: ${errorMsg}`);
-          addMessage({
-            type: "system",
-            content: `Error: ${errorMsg}`,
-          });
-          setIsLoading(false);
-          break;
-        }
+        const apply = (language: LanguageChangedEvent) =>
+          openaiRealtimeService.setLanguage(
+            language.instructions_hint,
+            language.transcription_language
+          );
 
-        default:
-          addLog(`📋 Other event: ${event.type}`);
+        apply(await invoke<LanguageChangedEvent>("get_language"));
+        unlisten = await listen<LanguageChangedEvent>("language-changed", (event) => {
+          apply(event.payload);
+          const { translation } = event.payload;
+          addLog(
+            translation
+              ? `🌐 Translating ${translation.from || "auto-detect"} → ${translation.to}`
+              : `🌐 Language set to ${event.payload.language_name ?? "auto-detect"}`
+          );
+        });
+      } catch (error) {
+        console.error("Failed to load language:", error);
       }
-    },
-    [addLog, addMessage]
-  );
+    };
+
+    setupLanguage();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
 
   // Set up event listeners and status updates
   useEffect(() => {
     // Set up OpenAI event listeners for Realtime API
-    openaiRealtimeService.on("session.created", handleOpenAIEvent);
-    openaiRealtimeService.on("response.text.delta", handleOpenAIEvent);
-    openaiRealtimeService.on("response.text.done", handleOpenAIEvent);
-    openaiRealtimeService.on("response.audio.delta", handleOpenAIEvent);
-    openaiRealtimeService.on("response.audio.done", handleOpenAIEvent);
-    openaiRealtimeService.on(
+    const events: OpenAIEvent["type"][] = [
+      "session.created",
+      "response.text.delta",
+      "response.text.done",
+      "response.audio.delta",
+      "response.audio.done",
       "input_audio_buffer.speech_started",
-      handleOpenAIEvent
-    );
-    openaiRealtimeService.on(
       "input_audio_buffer.speech_stopped",
-      handleOpenAIEvent
-    );
-    openaiRealtimeService.on("input_audio_buffer.committed", handleOpenAIEvent);
-    openaiRealtimeService.on("conversation.item.created", handleOpenAIEvent);
-    openaiRealtimeService.on("response.created", handleOpenAIEvent);
-    openaiRealtimeService.on("response.done", handleOpenAIEvent);
-    openaiRealtimeService.on("error", handleOpenAIEvent);
+      "input_audio_buffer.committed",
+      "conversation.item.created",
+      "conversation.item.truncated",
+      "response.created",
+      "response.done",
+      "error",
+      "openai-reconnecting",
+      "openai-reconnected",
+      "context-pruned",
+    ];
+    events.forEach((event) => backend.on(event, handleOpenAIEvent));
 
     // Update status periodically
     const updateStatus = () => {
-      const currentStatus = openaiRealtimeService.getStatus();
+      const currentStatus = backend.getStatus();
       setStatus(currentStatus);
       setIsConnected(currentStatus.connected);
+      reportRealtimeStatus(currentStatus);
+      setHealth(backend.getConnectionHealth());
     };
 
     updateStatus();
     const statusInterval = setInterval(updateStatus, 2000);
 
-    // Cleanup
+    // Cleanup, on the backend the listeners were added to
     return () => {
       clearInterval(statusInterval);
-      openaiRealtimeService.off("session.created", handleOpenAIEvent);
-      openaiRealtimeService.off("response.text.delta", handleOpenAIEvent);
-      openaiRealtimeService.off("response.text.done", handleOpenAIEvent);
-      openaiRealtimeService.off("response.audio.delta", handleOpenAIEvent);
-      openaiRealtimeService.off("response.audio.done", handleOpenAIEvent);
-      openaiRealtimeService.off(
-        "input_audio_buffer.speech_started",
-        handleOpenAIEvent
-      );
-      openaiRealtimeService.off(
-        "input_audio_buffer.speech_stopped",
-        handleOpenAIEvent
-      );
-      openaiRealtimeService.off(
-        "input_audio_buffer.committed",
-        handleOpenAIEvent
-      );
-      openaiRealtimeService.off("conversation.item.created", handleOpenAIEvent);
-      openaiRealtimeService.off("response.created", handleOpenAIEvent);
-      openaiRealtimeService.off("response.done", handleOpenAIEvent);
-      openaiRealtimeService.off("error", handleOpenAIEvent);
-    };
-  }, [handleOpenAIEvent]);
+      events.forEach((event) => backend.off(event, handleOpenAIEvent));
+    };
+  }, [backend, handleOpenAIEvent]);
+
+  // Greetings and check-ins the backend scheduler starts; they go through the normal response pipeline
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupProactive = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const { invoke } = await import("@tauri-apps/api/core");
+
+        unlisten = await listen<{ trigger: string; instructions: string }>(
+          "proactive-prompt",
+          async (event) => {
+            addLog(`🗨️ Speaking up (${event.payload.trigger})`);
+            if (!getActiveBackend().getStatus().connected) {
+              if (connectingRef.current) return;
+              connectingRef.current = true;
+              try {
+                if (!(await handleConnect())) return;
+              } finally {
+                connectingRef.current = false;
+              }
+            }
+            try {
+              await getActiveBackend().prompt(event.payload.instructions);
+            } catch (error) {
+              addLog(`❌ Failed to speak up: ${describeError(error)}`);
+            }
+          }
+        );
+
+        // The start greeting waits for this listener
+        await invoke("proactive_ready");
+      } catch (error) {
+        console.error("Failed to set up proactive prompts:", error);
+      }
+    };
+
+    setupProactive();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // eva://announce, --announce and MQTT announce commands are spoken, not sent to the model
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupAnnounce = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+
+        unlisten = await listen<{ text: string }>("announce", (event) => {
+          addLog(`📢 Announcing: ${event.payload.text}`);
+          speakLocally(event.payload.text).catch((error) => {
+            addLog(`❌ Announcement failed: ${error}`);
+          });
+        });
+      } catch (error) {
+        console.error("Failed to set up announcements:", error);
+      }
+    };
+
+    setupAnnounce();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [addLog]);
+
+  // Follow-up window: stop capturing when nobody speaks after a response
+  useEffect(() => {
+    let unlistenOpen: (() => void) | undefined;
+    let unlistenClosed: (() => void) | undefined;
+
+    const setupFollowup = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+
+        unlistenOpen = await listen<FollowupOpenEvent>("followup-window-open", (event) => {
+          setIsFollowupOpen(true);
+          addLog(`⏳ Listening for a follow-up (${event.payload.duration_ms / 1000}s)`);
+        });
+        unlistenClosed = await listen<FollowupClosedEvent>(
+          "followup-window-closed",
+          async (event) => {
+            setIsFollowupOpen(false);
+            if (event.payload.reason === "timeout" && audioCaptureService.getRecordingState()) {
+              addLog("⏳ No follow-up - say the wake word to talk again");
+              await handleStopRecording();
+            }
+          }
+        );
+      } catch (error) {
+        console.error("Failed to set up follow-up window:", error);
+      }
+    };
+
+    setupFollowup();
+
+    return () => {
+      unlistenOpen?.();
+      unlistenClosed?.();
+    };
+  }, [addLog]);
+
+  // Drop an idle session to save cost; the next wake word reconnects
+  useEffect(() => {
+    if (!isConnected) return;
+    let unlisten: (() => void) | undefined;
+
+    const setupIdleDisconnect = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        unlisten = await listen<SessionIdleEvent>("session-idle-timeout", async (event) => {
+          if (audioCaptureService.getRecordingState() || audioPlaybackService.isCurrentlyPlaying()) {
+            touchSessionActivity();
+            return;
+          }
+          addLog(
+            `💤 Idle for ${event.payload.idle_minutes} min - disconnecting until the next wake word`
+          );
+          await handleDisconnect();
+        });
+      } catch (error) {
+        console.error("Failed to set up idle disconnect:", error);
+      }
+    };
+
+    setupIdleDisconnect();
+
+    return () => {
+      unlisten?.();
+    };
+  }, [isConnected, messages]);
 
   // Scroll to bottom when messages change
   useEffect(() => {
     messagesEndRef.current?.scrollIntoView({ behavior: "smooth" });
   }, [messages, currentResponse]);
 
-  // Listen for wake word events from Porcupine and auto-start detection
+  // Listen for wake word events from Porcupine and auto-start detection.
+  // Detection runs whether or not a session is open; the wake word connects on demand.
   useEffect(() => {
+    let unlistenWakeWord: (() => void) | undefined;
+
     const setupWakeWordListener = async () => {
       try {
         const { listen } = await import("@tauri-apps/api/event");
         const { invoke } = await import("@tauri-apps/api/core");
 
-        // Start wake word detection automatically
-        try {
-          const result = await invoke<string>("start_wake_word");
-          addLog(`🎤 Wake word detection started: ${result}`);
-          setIsWakeWordActive(true);
-        } catch (error) {
-          console.error("Failed to start wake word detection:", error);
-          addLog(`❌ Failed to start wake word detection: ${error}`);
-          setIsWakeWordActive(false);
+        // Start wake word detection automatically, unless a launch at login was told not to
+        const autostart = await getAutostart().catch(() => null);
+        if (autostart?.launched_at_login && !autostart.listen_on_start) {
+          addLog("🚀 Launched at login without listening; wake word detection not started");
+        } else {
+          try {
+            const result = await invoke<string>("start_wake_word");
+            addLog(`🎤 Wake word detection started: ${result}`);
+            setIsWakeWordActive(true);
+          } catch (error) {
+            console.error("Failed to start wake word detection:", error);
+            addLog(`❌ Failed to start wake word detection: ${describeError(error)}`);
+            setIsWakeWordActive(false);
+          }
         }
 
-        const unlisten = await listen("wake-word-detected", async (event) => {
+        unlistenWakeWord = await listen("wake-word-detected", async (event) => {
           const wakeWordData = event.payload as any;
           addLog(`🎯 Wake word detected: "${wakeWordData.keyword}"`);
+          startLatencyTurn(wakeWordData.timestamp ?? Date.now());
 
           // Auto-start voice input when wake word is detected
           if (status.apiKey === "configured") {
             addLog("🤖 Eva activated! Starting voice input...");
+            watchForFalseTrigger();
 
-            // Connect if needed
-            if (!isConnected) {
+            // Connect lazily; the pre-wake buffer holds the user's speech meanwhile
+            if (!getActiveBackend().getStatus().connected) {
+              if (connectingRef.current) return;
+              connectingRef.current = true;
               try {
-                await handleConnect();
-                const startVoiceAfterDelay = () => handleStartVoiceInput();
-                setTimeout(startVoiceAfterDelay, 1000);
-              } catch (error) {
-                addLog("❌ Failed to connect to Eva");
-                console.error(error);
+                addLog("🔌 Connecting on wake word...");
+                if (!(await handleConnect())) {
+                  // handleConnect already reported the failure on the eva-error channel
+                  addLog("❌ Heard the wake word but couldn't connect - check your network and API key");
+                  playEarcon("error");
+                  // Release the held pre-wake audio so it isn't sent with the next wake word
+                  await takePreWakeAudio();
+                  return;
+                }
+              } finally {
+                connectingRef.current = false;
               }
+              const startVoiceAfterDelay = () => handleStartVoiceInput();
+              setTimeout(startVoiceAfterDelay, 1000);
             } else {
               handleStartVoiceInput();
             }
@@ -313,8 +1335,6 @@ const OpenAIChat: React.FC = () => {
             addLog("❌ Please set your OpenAI API key first");
           }
         });
-
-        return unlisten;
       } catch (error) {
         console.error("Failed to set up wake word listener:", error);
       }
@@ -324,6 +1344,7 @@ const OpenAIChat: React.FC = () => {
 
     // Cleanup: stop wake word detection when component unmounts
     return () => {
+      unlistenWakeWord?.();
       const cleanup = async () => {
         try {
           const { invoke } = await import("@tauri-apps/api/core");
@@ -336,19 +1357,165 @@ const OpenAIChat: React.FC = () => {
       };
       cleanup();
     };
-  }, [isConnected, status.apiKey]);
+  }, [status.apiKey]);
+
+  // Focused-window push-to-talk: holding the configured key wakes Eva like the wake word does
+  useEffect(() => {
+    let pushToTalkKey = "Space";
+    let unlistenRelease: (() => void) | undefined;
+
+    const isTypingTarget = (target: EventTarget | null) =>
+      target instanceof HTMLInputElement ||
+      target instanceof HTMLTextAreaElement;
+
+    const handleKeyDown = async (e: KeyboardEvent) => {
+      if (e.code !== pushToTalkKey || e.repeat || isTypingTarget(e.target)) {
+        return;
+      }
+      e.preventDefault();
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke<string>("push_to_talk_pressed");
+      } catch (error) {
+        addLog(`❌ Push-to-talk failed: ${error}`);
+      }
+    };
+
+    const handleKeyUp = async (e: KeyboardEvent) => {
+      if (e.code !== pushToTalkKey || isTypingTarget(e.target)) {
+        return;
+      }
+      e.preventDefault();
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke<string>("push_to_talk_released");
+      } catch (error) {
+        console.error("Failed to release push-to-talk:", error);
+      }
+    };
+
+    const setupPushToTalk = async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const { listen } = await import("@tauri-apps/api/event");
+
+        const settings = await invoke<{
+          push_to_talk: { enabled: boolean; key: string };
+        }>("get_settings");
+        if (!settings.push_to_talk.enabled) {
+          return;
+        }
+        pushToTalkKey = settings.push_to_talk.key;
+
+        // Releasing the key ends the turn without waiting for server VAD
+        unlistenRelease = await listen("push-to-talk-released", async () => {
+          addLog("⌨️ Push-to-talk released - sending turn");
+          await handleStopRecording();
+          try {
+            // handleStopRecording already committed for backends without server VAD
+            const backend = getActiveBackend();
+            if (backend.capabilities.serverTurnDetection) {
+              await backend.commitAudio();
+            }
+          } catch (error) {
+            console.error("Failed to finish push-to-talk turn:", error);
+          }
+        });
+
+        window.addEventListener("keydown", handleKeyDown);
+        window.addEventListener("keyup", handleKeyUp);
+      } catch (error) {
+        console.error("Failed to set up push-to-talk:", error);
+      }
+    };
+
+    setupPushToTalk();
+
+    return () => {
+      window.removeEventListener("keydown", handleKeyDown);
+      window.removeEventListener("keyup", handleKeyUp);
+      unlistenRelease?.();
+    };
+  }, [isConnected]);
+
+  // Dictation: the Realtime session only transcribes, and each transcript is typed into the
+  // focused app
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+
+    const handleTranscript = async (event: OpenAIEvent) => {
+      const text = event.data?.transcript?.trim();
+      if (!dictatingRef.current || !text) return;
+      try {
+        const outcome = await dictate(text);
+        if (outcome.typed) addLog(`⌨️ Typed: "${outcome.typed}"`);
+      } catch (error) {
+        addLog(`❌ Dictation failed: ${describeError(error)}`);
+        await stopDictation().catch(() => undefined);
+      }
+    };
+
+    const handleDictation = async (active: boolean) => {
+      dictatingRef.current = active;
+      openaiRealtimeService.setDictating(active);
+      if (!active) {
+        addLog("⌨️ Dictation stopped");
+        if (audioCaptureService.getRecordingState()) await handleStopRecording();
+        return;
+      }
+
+      addLog("⌨️ Dictating into the focused app...");
+      if (!getActiveBackend().getStatus().connected && !(await handleConnect())) {
+        addLog("❌ Couldn't connect for dictation");
+        await stopDictation().catch(() => undefined);
+        return;
+      }
+      if (!audioCaptureService.getRecordingState()) await handleStartRecording();
+    };
+
+    openaiRealtimeService.on("conversation.item.input_audio_transcription.completed", handleTranscript);
+    onDictationChanged((active) => {
+      handleDictation(active);
+    })
+      .then((stop) => {
+        if (cancelled) stop();
+        else unlisten = stop;
+      })
+      .catch((error) => console.error("Failed to follow dictation:", error));
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      openaiRealtimeService.off("conversation.item.input_audio_transcription.completed", handleTranscript);
+    };
+  }, [isConnected]);
 
-  const handleConnect = async () => {
+  // Resolves to whether the session is up; failures are logged and reported, not thrown
+  const handleConnect = async (): Promise<boolean> => {
     try {
       setIsLoading(true);
-      addLog("🔌 Connecting to OpenAI...");
-      await openaiRealtimeService.connect();
+      const backend = await syncBackendWithSettings();
+      setBackend(backend);
+      const memory = await recallMemory();
+      if (memory?.summary || memory?.facts.length) {
+        addLog(`🧠 Recalled ${memory.facts.length} remembered facts`);
+      }
+      addLog(`🔌 Connecting via ${backend.displayName}...`);
+      await backend.connect();
       addLog("✅ Successfully connected to OpenAI");
+      touchSessionActivity();
+      const conversationId = await startConversation();
+      if (conversationId) {
+        addLog(`🗂️ Archiving conversation ${conversationId}`);
+      }
+      return true;
     } catch (error) {
       const errorMessage =
         error instanceof Error ? error.message : "Unknown error";
       addLog(`❌ Connection failed: ${errorMessage}`);
       console.error("Connection failed:", error);
+      return false;
     } finally {
       setIsLoading(false);
     }
@@ -358,8 +1525,17 @@ const OpenAIChat: React.FC = () => {
     try {
       setIsLoading(true);
       addLog("🔌 Disconnecting from OpenAI...");
-      await openaiRealtimeService.disconnect();
+      await getActiveBackend().disconnect();
+      closeFollowupWindow("cancelled");
+      stopSessionIdleTimer();
       addLog("✅ Disconnected from OpenAI");
+
+      const transcript = messages
+        .filter((m) => m.type !== "system")
+        .map((m) => ({ role: m.type as "user" | "assistant", content: m.content }));
+      rememberConversation(transcript)
+        .then((saved) => saved && addLog("🧠 Conversation saved to memory"))
+        .catch((error) => addLog(`⚠️ Failed to update memory: ${error}`));
     } catch (error) {
       const errorMessage =
         error instanceof Error ? error.message : "Unknown error";
@@ -375,24 +1551,12 @@ const OpenAIChat: React.FC = () => {
     const userMessage = currentInput.trim();
     setCurrentInput("");
 
-    addMessage({
-      type: "user",
-      content: userMessage,
-    });
-
+    touchSessionActivity();
     try {
-      setIsLoading(true);
-      addLog(`📤 Sending text: ${userMessage}`);
-      await openaiRealtimeService.sendMessage(userMessage);
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke<string>("send_chat_message", { text: userMessage });
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : "Unknown error";
-      addLog(`❌ Failed to send text: ${errorMessage}`);
-      addMessage({
-        type: "system",
-        content: `Failed to send message: ${errorMessage}`,
-      });
-      setIsLoading(false);
+      addLog(`❌ Failed to send text: ${error}`);
     }
   };
 
@@ -405,6 +1569,7 @@ const OpenAIChat: React.FC = () => {
       let totalAudioBytes = 0;
 
       await audioCaptureService.startCapture(async (audioData) => {
+        const capturedAt = Date.now();
         try {
           audioChunkCount++;
           totalAudioBytes += audioData.byteLength;
@@ -417,7 +1582,9 @@ const OpenAIChat: React.FC = () => {
           }
 
           // Send audio data directly to OpenAI Realtime API
-          await openaiRealtimeService.sendAudioData(audioData);
+          await getActiveBackend().sendAudio(audioData);
+          recordSendDelay(Date.now() - capturedAt);
+          recordAudio("user", audioData);
         } catch (error) {
           console.error("Failed to send audio data:", error);
           addLog(
@@ -429,6 +1596,7 @@ const OpenAIChat: React.FC = () => {
       });
 
       setIsRecording(true);
+      startDucking("listening");
       addLog("✅ Voice recording started - speak now!");
     } catch (error) {
       const errorMessage =
@@ -439,8 +1607,16 @@ const OpenAIChat: React.FC = () => {
   const handleStopRecording = async () => {
     try {
       addLog("🎤 Stopping voice recording...");
+      closeFollowupWindow("cancelled");
+      stopDucking("listening");
       await audioCaptureService.stopCapture();
 
+      // Backends without server-side VAD only answer once the turn is committed
+      const backend = getActiveBackend();
+      if (!backend.capabilities.serverTurnDetection) {
+        await backend.commitAudio();
+      }
+
       setIsRecording(false);
       addLog("✅ Voice recording stopped");
     } catch (error) {
@@ -453,8 +1629,22 @@ const OpenAIChat: React.FC = () => {
 
   // Voice input triggered by wake word - uses Realtime API
   const handleStartVoiceInput = async () => {
+    if (textOnlyRef.current) {
+      addLog("💬 Text-only mode - type your message instead");
+      return;
+    }
+
     try {
       addLog("🎤 Starting voice input via Realtime API...");
+
+      // Send what was said while the connection and capture stream spun up
+      const preWakeAudio = await takePreWakeAudio();
+      if (preWakeAudio && !audioCaptureService.isMutedState()) {
+        await getActiveBackend().sendAudio(preWakeAudio);
+        recordAudio("user", preWakeAudio);
+        addLog(`⏪ Sent ${preWakeAudio.byteLength} bytes of pre-wake audio`);
+      }
+
       await handleStartRecording();
     } catch (error) {
       const errorMessage =
@@ -466,9 +1656,13 @@ const OpenAIChat: React.FC = () => {
   const handleInterrupt = async () => {
     try {
       addLog("⏹️ Interrupting response...");
+      if (activeResponseIdRef.current) {
+        interruptedResponsesRef.current.add(activeResponseIdRef.current);
+        activeResponseIdRef.current = null;
+      }
       // Clear audio playback queue
       audioPlaybackService.clearQueue();
-      // Interrupt not implemented in simple service
+      await getActiveBackend().interrupt();
       setCurrentResponse("");
       currentResponseRef.current = "";
       setIsLoading(false);
@@ -480,6 +1674,46 @@ const OpenAIChat: React.FC = () => {
     }
   };
 
+  // Per-session switch between OpenAI's voice and the local TTS voice
+  const handleToggleLocalVoice = () => {
+    const enabled = !useLocalVoice;
+    useLocalVoiceRef.current = enabled;
+    setUseLocalVoice(enabled);
+    openaiRealtimeService.setAudioOutputEnabled(!enabled);
+    addLog(
+      enabled
+        ? "🗣️ Using local voice for responses"
+        : "🔊 Using OpenAI voice for responses"
+    );
+  };
+
+  const handleToggleTextOnly = async () => {
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("set_text_only_mode", { enabled: !isTextOnly });
+    } catch (error) {
+      addLog(`❌ Failed to switch chat mode: ${error}`);
+    }
+  };
+
+  const handleToggleSpokenReplies = async () => {
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("set_spoken_replies", { enabled: !spokenReplies });
+    } catch (error) {
+      addLog(`❌ Failed to switch reply mode: ${error}`);
+    }
+  };
+
+  const handleToggleMute = async () => {
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      await invoke("set_mic_muted", { muted: !isMicMuted });
+    } catch (error) {
+      addLog(`❌ Failed to toggle mute: ${describeError(error)}`);
+    }
+  };
+
   const handleTestAudio = async () => {
     try {
       addLog("🎵 Testing audio playback...");
@@ -504,12 +1738,38 @@ const OpenAIChat: React.FC = () => {
 
     if (confirmed) {
       setMessages([]);
+      clearChatHistory();
       addLog("🗑️ Chat history cleared");
     } else {
       addLog("❌ Reset history cancelled");
     }
   };
 
+  const handleForgetLastExchange = async () => {
+    try {
+      const removed = await openaiRealtimeService.removeLastExchange();
+      if (removed === 0) {
+        addLog("ℹ️ Nothing to forget yet");
+        return;
+      }
+      setMessages((prev) => {
+        const lastUser = prev.map((message) => message.type).lastIndexOf("user");
+        return lastUser === -1 ? prev : prev.slice(0, lastUser);
+      });
+      addLog(`↩️ Forgot the last exchange (${removed} item${removed === 1 ? "" : "s"})`);
+    } catch (error) {
+      addLog(`❌ Failed to forget the last exchange: ${error}`);
+    }
+  };
+
+  const handleToggleChatWindow = async () => {
+    try {
+      addLog(`🪟 ${isChatWindowOpen ? await closeChatWindow() : await openChatWindow()}`);
+    } catch (error) {
+      addLog(`❌ Failed to toggle the chat window: ${error}`);
+    }
+  };
+
   const handleKeyPress = (e: React.KeyboardEvent) => {
     if (e.key === "Enter" && !e.shiftKey) {
       e.preventDefault();
@@ -518,17 +1778,41 @@ const OpenAIChat: React.FC = () => {
   };
 
   return (
-    <div className="flex flex-col h-screen bg-gradient-to-br from-purple-100 to-pink-100 dark:from-gray-900 dark:to-purple-900">
+    <div
+      className={`flex flex-col h-screen ${
+        isOverlay
+          ? "bg-transparent"
+          : "bg-gradient-to-br from-purple-100 to-pink-100 dark:from-gray-900 dark:to-purple-900"
+      }`}
+    >
       {/* Header */}
       <div className="bg-white dark:bg-gray-800 shadow-lg p-4 border-b">
         <div className="max-w-4xl mx-auto">
-          <h1 className="text-2xl font-bold text-gray-800 dark:text-white mb-4">
-            💬 Chat with Eva
+          {/* Without a title bar, the heading is where the overlay is dragged from */}
+          <h1
+            data-tauri-drag-region={isOverlay ? true : undefined}
+            className="text-2xl font-bold text-gray-800 dark:text-white mb-4"
+          >
+            💬 Chat with {assistantName}
           </h1>
 
+          {/* Microphone Permission */}
+          <MicrophonePermissionBanner />
+          <EnvSecretsBanner />
+
           {/* API Key Setup */}
           <ApiKeySetup />
 
+          {/* Chat Endpoint Setup */}
+          <LlmEndpointSetup />
+
+          {/* Persona */}
+          <PersonaSelector />
+
+          {/* Profiles */}
+          <ProfileSettings />
+          <UserSettings />
+
           {/* Status */}
           <div className="grid grid-cols-1 md:grid-cols-4 gap-4 mb-4">
             <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg">
@@ -548,13 +1832,32 @@ const OpenAIChat: React.FC = () => {
               <div className="font-semibold text-gray-800 dark:text-white">
                 {isConnected ? "🟢 Connected" : "🔴 Disconnected"}
               </div>
+              {isConnected && health && (
+                <div className="text-xs text-gray-500 dark:text-gray-400">
+                  {health.quality === "good"
+                    ? "📶 Good"
+                    : health.quality === "fair"
+                    ? "📶 Fair"
+                    : "📶 Poor"}
+                  {health.averageLatencyMs !== null &&
+                    ` · ${health.averageLatencyMs}ms`}
+                  {health.droppedFrames > 0 &&
+                    ` · ${health.droppedFrames} dropped`}
+                  {health.uploadCongested &&
+                    ` · 🐢 Slow upload, ${(health.uploadBacklogMs / 1000).toFixed(1)}s behind`}
+                </div>
+              )}
             </div>
             <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg">
               <div className="text-sm text-gray-600 dark:text-gray-300">
                 Recording
               </div>
               <div className="font-semibold text-gray-800 dark:text-white">
-                {isRecording ? "🎤 Active" : "⏸️ Inactive"}
+                {isRecording
+                  ? isFollowupOpen
+                    ? "⏳ Follow-up"
+                    : "🎤 Active"
+                  : "⏸️ Inactive"}
               </div>
             </div>
             <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg">
@@ -567,6 +1870,81 @@ const OpenAIChat: React.FC = () => {
             </div>
           </div>
 
+          {/* Usage */}
+          <UsagePanel />
+
+          {/* Latency */}
+          <LatencyPanel />
+          <MetricsSettings />
+
+          {/* Timers & Reminders */}
+          <RemindersPanel />
+          <CalendarSettings />
+
+          {/* Sound Cues */}
+          <EarconSettings />
+
+          {/* Voice Input */}
+          <InputDeviceSettings />
+          <InputSourceSettings />
+
+          {/* End of Turn */}
+          <TurnDetectionSettings />
+
+          {/* Audio Encoding */}
+          <AudioFormatSettings />
+
+          {/* Voice Output */}
+          <OutputSettings />
+
+          {/* Avatar */}
+          <AvatarSettings />
+          <OverlaySettings />
+
+          {/* Speaking Up */}
+          <ProactiveSettings />
+
+          {/* Do Not Disturb */}
+          <DndSettings />
+
+          {/* Power */}
+          <PowerSettings />
+
+          {/* Answer Cache */}
+          <ResponseCacheSettings />
+
+          {/* Offline Commands */}
+          <OfflineCommandSettings />
+
+          {/* Dictation */}
+          <DictationSettings />
+
+          {/* Launch at Login */}
+          <StartupSettings />
+
+          {/* Network */}
+          <ProxySettings />
+
+          {/* Smart Home */}
+          <MqttSettings />
+          <HomeAssistantSettings />
+
+          {/* Actions */}
+          <ActionsSettings />
+
+          {/* Plugins */}
+          <PluginsSettings />
+
+          {/* Conversation Archive */}
+          <ConversationArchive />
+
+          {/* Privacy */}
+          <PrivacySettings />
+          <SecretStoreSettings />
+
+          {/* Speaker Verification */}
+          <VoiceLock />
+
           {/* Wake Word Info */}
           <div className="mb-4 p-3 bg-purple-50 dark:bg-purple-900/20 border border-purple-200 dark:border-purple-800 rounded-lg">
             <p className="text-sm text-purple-700 dark:text-purple-300">
@@ -598,7 +1976,7 @@ const OpenAIChat: React.FC = () => {
 
             {isConnected && (
               <>
-                {!isRecording ? (
+                {isTextOnly ? null : !isRecording ? (
                   <button
                     onClick={handleStartRecording}
                     disabled={isLoading}
@@ -627,6 +2005,56 @@ const OpenAIChat: React.FC = () => {
               </>
             )}
 
+            {/* Text-only Chat Toggle */}
+            <button
+              onClick={handleToggleTextOnly}
+              className={`${
+                isTextOnly
+                  ? "bg-indigo-600 hover:bg-indigo-700"
+                  : "bg-gray-500 hover:bg-gray-600"
+              } text-white px-4 py-2 rounded-lg font-medium transition-colors`}
+              title="Chat by text only, without microphone capture or audio playback"
+            >
+              {isTextOnly ? "💬 Text Only" : "🎙️ Voice Chat"}
+            </button>
+
+            {/* Spoken Replies Toggle */}
+            {isTextOnly && (
+              <button
+                onClick={handleToggleSpokenReplies}
+                className={`${
+                  spokenReplies
+                    ? "bg-indigo-600 hover:bg-indigo-700"
+                    : "bg-gray-500 hover:bg-gray-600"
+                } text-white px-4 py-2 rounded-lg font-medium transition-colors`}
+                title="Answer typed messages out loud while the microphone stays off"
+              >
+                {spokenReplies ? "🔊 Spoken Replies" : "📝 Text Replies"}
+              </button>
+            )}
+
+            {/* Privacy Mute Toggle */}
+            <button
+              onClick={handleToggleMute}
+              className={`${
+                isMicMuted
+                  ? "bg-red-600 hover:bg-red-700"
+                  : "bg-gray-500 hover:bg-gray-600"
+              } text-white px-4 py-2 rounded-lg font-medium transition-colors`}
+              title="Stop all microphone processing, including wake word detection"
+            >
+              {isMicMuted ? "🔇 Muted" : "🎤 Mic On"}
+            </button>
+
+            {/* Local Voice Toggle */}
+            <button
+              onClick={handleToggleLocalVoice}
+              className="bg-teal-500 hover:bg-teal-600 text-white px-4 py-2 rounded-lg font-medium transition-colors"
+              title="Speak responses with the local Piper voice instead of OpenAI audio"
+            >
+              {useLocalVoice ? "🗣️ Local Voice" : "🔊 OpenAI Voice"}
+            </button>
+
             {/* Test Audio Button */}
             <button
               onClick={handleTestAudio}
@@ -635,6 +2063,29 @@ const OpenAIChat: React.FC = () => {
               🔊 Test Audio
             </button>
 
+            {/* Chat Window Toggle */}
+            <button
+              onClick={handleToggleChatWindow}
+              className={`${
+                isChatWindowOpen
+                  ? "bg-indigo-600 hover:bg-indigo-700"
+                  : "bg-gray-500 hover:bg-gray-600"
+              } text-white px-4 py-2 rounded-lg font-medium transition-colors`}
+              title="Open the conversation in its own window"
+            >
+              🪟 Chat Window
+            </button>
+
+            {/* Forget Last Exchange Button */}
+            <button
+              onClick={handleForgetLastExchange}
+              disabled={!isConnected}
+              className="bg-gray-500 hover:bg-gray-600 disabled:bg-gray-400 text-white px-4 py-2 rounded-lg font-medium transition-colors"
+              title="Remove the last question and answer from Eva's context"
+            >
+              ↩️ Forget Last
+            </button>
+
             {/* Reset History Button */}
             <button
               onClick={handleResetHistory}
@@ -673,7 +2124,7 @@ const OpenAIChat: React.FC = () => {
                     {message.type === "user"
                       ? "You"
                       : message.type === "assistant"
-                      ? "Eva"
+                      ? assistantName
                       : "System"}
                   </div>
                   <div className="whitespace-pre-wrap">{message.content}</div>
@@ -685,7 +2136,9 @@ const OpenAIChat: React.FC = () => {
             {currentResponse && (
               <div className="flex justify-start">
                 <div className="max-w-xs lg:max-w-md px-4 py-2 rounded-lg bg-purple-500 text-white">
-                  <div className="text-sm opacity-75 mb-1">Eva (typing...)</div>
+                  <div className="text-sm opacity-75 mb-1">
+                    {assistantName} (typing...)
+                  </div>
                   <div className="whitespace-pre-wrap">{currentResponse}</div>
                 </div>
               </div>
@@ -695,7 +2148,7 @@ const OpenAIChat: React.FC = () => {
             {isLoading && !currentResponse && (
               <div className="flex justify-start">
                 <div className="max-w-xs lg:max-w-md px-4 py-2 rounded-lg bg-gray-200 dark:bg-gray-700">
-                  <div className="text-sm opacity-75 mb-1">Eva</div>
+                  <div className="text-sm opacity-75 mb-1">{assistantName}</div>
                   <div className="flex space-x-2">
                     <div className="w-2 h-2 bg-purple-500 rounded-full animate-bounce"></div>
                     <div className="w-2 h-2 bg-purple-500 rounded-full animate-bounce delay-100"></div>
@@ -716,7 +2169,7 @@ const OpenAIChat: React.FC = () => {
                 value={currentInput}
                 onChange={(e) => setCurrentInput(e.target.value)}
                 onKeyPress={handleKeyPress}
-                placeholder="Type your message to Eva..."
+                placeholder={`Type your message to ${assistantName}...`}
                 disabled={!isConnected || isLoading}
                 className="flex-1 px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:outline-none focus:ring-2 focus:ring-purple-500 dark:bg-gray-700 dark:text-white"
               />
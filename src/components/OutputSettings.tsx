@@ -0,0 +1,119 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  OutputDevice,
+  PlaybackSettings,
+  listOutputDevices,
+  setOutputDevice,
+  setOutputVolume,
+} from "../services/playback";
+import { DuckingSettings, setDucking } from "../services/ducking";
+
+const OutputSettings: React.FC = () => {
+  const [settings, setSettings] = useState<PlaybackSettings | null>(null);
+  const [devices, setDevices] = useState<OutputDevice[]>([]);
+  const [ducking, setDuckingState] = useState<DuckingSettings | null>(null);
+
+  useEffect(() => {
+    invoke<{ playback: PlaybackSettings; ducking: DuckingSettings }>("get_settings")
+      .then((loaded) => {
+        setSettings(loaded.playback);
+        setDuckingState(loaded.ducking);
+      })
+      .catch((error) => console.error("Failed to load playback settings:", error));
+    listOutputDevices()
+      .then(setDevices)
+      .catch((error) => console.error("Failed to list output devices:", error));
+  }, []);
+
+  const changeDevice = async (name: string | null) => {
+    setSettings((prev) => prev && { ...prev, output_device: name });
+    try {
+      await setOutputDevice(name);
+    } catch (error) {
+      console.error("Failed to set output device:", error);
+    }
+  };
+
+  const changeVolume = async (volume: number) => {
+    setSettings((prev) => prev && { ...prev, volume });
+    try {
+      await setOutputVolume(volume);
+    } catch (error) {
+      console.error("Failed to set output volume:", error);
+    }
+  };
+
+  const changeDucking = async (update: Partial<DuckingSettings>) => {
+    if (!ducking) return;
+    const next = { ...ducking, ...update };
+    setDuckingState(next);
+    try {
+      await setDucking(next.enabled, next.level);
+    } catch (error) {
+      console.error("Failed to update audio ducking:", error);
+    }
+  };
+
+  if (!settings || !ducking) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔈 Eva's Voice</div>
+      <div className="space-y-2 text-xs">
+        <select
+          value={settings.output_device ?? ""}
+          onChange={(e) => changeDevice(e.target.value || null)}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-700 dark:text-gray-200"
+        >
+          <option value="">System default</option>
+          {devices.map((device) => (
+            <option key={device.name} value={device.name}>
+              {device.name}
+              {device.is_default ? " (default)" : ""}
+            </option>
+          ))}
+        </select>
+        <div className="flex items-center gap-2 text-gray-700 dark:text-gray-200">
+          <span className="w-16">Volume</span>
+          <input
+            type="range"
+            min="0"
+            max="1"
+            step="0.05"
+            value={settings.volume}
+            onChange={(e) => changeVolume(parseFloat(e.target.value))}
+            className="flex-1"
+          />
+          <span className="w-10 text-right">{Math.round(settings.volume * 100)}%</span>
+        </div>
+        <div className="flex items-center gap-2 text-gray-700 dark:text-gray-200">
+          <label
+            className="flex items-center gap-1 w-16"
+            title="Lower other apps while Eva listens or speaks. On macOS this lowers the system volume while listening only."
+          >
+            <input
+              type="checkbox"
+              checked={ducking.enabled}
+              onChange={(e) => changeDucking({ enabled: e.target.checked })}
+            />
+            Duck
+          </label>
+          <input
+            type="range"
+            min="0"
+            max="1"
+            step="0.05"
+            value={ducking.level}
+            disabled={!ducking.enabled}
+            onChange={(e) => changeDucking({ level: parseFloat(e.target.value) })}
+            className="flex-1"
+          />
+          <span className="w-10 text-right">{Math.round(ducking.level * 100)}%</span>
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default OutputSettings;
@@ -0,0 +1,94 @@
+import React, { useEffect, useState } from "react";
+import {
+  OverlaySettings as Overlay,
+  PositionPreset,
+  getOverlaySettings,
+  onOverlayChanged,
+  setClickThrough,
+  setOverlayMode,
+  setWindowPositionPreset,
+} from "../services/overlay";
+
+const POSITION_LABELS: Record<PositionPreset, string> = {
+  "top-left": "Top left",
+  "top-right": "Top right",
+  "bottom-left": "Bottom left",
+  "bottom-right": "Bottom right",
+  center: "Center",
+};
+
+const OverlaySettings: React.FC = () => {
+  const [overlay, setOverlay] = useState<Overlay | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getOverlaySettings()
+      .then(setOverlay)
+      .catch((error) => console.error("Failed to load overlay settings:", error));
+    onOverlayChanged(setOverlay).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  const run = async (action: () => Promise<string>) => {
+    try {
+      setMessage(`✅ ${await action()}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  if (!overlay) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🪟 Overlay</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={overlay.enabled}
+            onChange={(e) => run(() => setOverlayMode(e.target.checked))}
+          />
+          Float on top of other apps, without a frame
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          <input
+            type="checkbox"
+            checked={overlay.click_through}
+            disabled={!overlay.enabled}
+            onChange={(e) => run(() => setClickThrough(e.target.checked))}
+          />
+          Let clicks pass through (click the tray icon to undo)
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          Position
+          <select
+            value={overlay.position ?? ""}
+            disabled={!overlay.enabled}
+            onChange={(e) =>
+              run(() => setWindowPositionPreset((e.target.value || null) as PositionPreset | null))
+            }
+            className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          >
+            <option value="">Where I drag it</option>
+            {(Object.keys(POSITION_LABELS) as PositionPreset[]).map((preset) => (
+              <option key={preset} value={preset}>
+                {POSITION_LABELS[preset]}
+              </option>
+            ))}
+          </select>
+        </label>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default OverlaySettings;
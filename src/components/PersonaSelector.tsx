@@ -0,0 +1,241 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+interface PersonaProfile {
+  name: string;
+  instructions: string;
+}
+
+type ResponseProfile = "brief" | "normal" | "detailed";
+
+const LANGUAGES: { locale: string; label: string }[] = [
+  { locale: "", label: "Auto (match my language)" },
+  { locale: "en", label: "English" },
+  { locale: "th-TH", label: "ไทย (Thai)" },
+  { locale: "ja-JP", label: "日本語 (Japanese)" },
+  { locale: "zh-CN", label: "中文 (Chinese)" },
+  { locale: "ko-KR", label: "한국어 (Korean)" },
+  { locale: "vi-VN", label: "Tiếng Việt (Vietnamese)" },
+  { locale: "es", label: "Español (Spanish)" },
+  { locale: "fr", label: "Français (French)" },
+  { locale: "de", label: "Deutsch (German)" },
+];
+
+interface PersonaSettings {
+  active: string;
+  profiles: PersonaProfile[];
+}
+
+const PersonaSelector: React.FC = () => {
+  const [personas, setPersonas] = useState<PersonaSettings | null>(null);
+  const [instructions, setInstructions] = useState("");
+  const [newName, setNewName] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+  const [responseProfile, setResponseProfile] = useState<ResponseProfile>("normal");
+  const [locale, setLocale] = useState("");
+  const [translateFrom, setTranslateFrom] = useState("");
+  const [translateTo, setTranslateTo] = useState("");
+
+  const loadPersonas = useCallback(async () => {
+    const loaded = await invoke<PersonaSettings>("get_personas");
+    setPersonas(loaded);
+    const active = loaded.profiles.find((profile) => profile.name === loaded.active);
+    setInstructions(active?.instructions ?? "");
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    loadPersonas().catch((error) => console.error("Failed to load personas:", error));
+    invoke<{ profile: ResponseProfile }>("get_response_profile")
+      .then((loaded) => setResponseProfile(loaded.profile))
+      .catch((error) => console.error("Failed to load response profile:", error));
+    invoke<{ locale: string; translation: { from: string; to: string } | null }>("get_language")
+      .then((loaded) => {
+        setLocale(loaded.locale);
+        setTranslateFrom(loaded.translation?.from ?? "");
+        setTranslateTo(loaded.translation?.to ?? "");
+      })
+      .catch((error) => console.error("Failed to load language:", error));
+    listen("persona-changed", () => {
+      loadPersonas().catch((error) => console.error("Failed to reload personas:", error));
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, [loadPersonas]);
+
+  const run = async (command: string, args: Record<string, unknown>) => {
+    try {
+      setMessage(`✅ ${await invoke<string>(command, args)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleResponseProfile = async (profile: ResponseProfile) => {
+    setResponseProfile(profile);
+    try {
+      await invoke("set_response_profile", { profile });
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleLanguage = async (next: string) => {
+    setLocale(next);
+    try {
+      await invoke("set_language", { locale: next });
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  // An empty target language turns translation mode off
+  const handleTranslation = async (from: string, to: string) => {
+    setTranslateFrom(from);
+    setTranslateTo(to);
+    try {
+      await invoke("set_translation_mode", { from, to });
+      setMessage(to ? "✅ Eva will speak translations instead of replying" : null);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleSaveAsNew = async () => {
+    if (!newName.trim()) return;
+    await run("save_persona_profile", { name: newName.trim(), instructions });
+    setNewName("");
+  };
+
+  if (!personas) return null;
+
+  return (
+    <div className="mb-6 p-4 bg-pink-50 dark:bg-pink-900/20 border border-pink-200 dark:border-pink-800 rounded-lg">
+      <div className="flex items-center gap-2 mb-3">
+        <h3 className="text-lg font-semibold text-pink-800 dark:text-pink-300">
+          🎭 Persona
+        </h3>
+        <select
+          value={personas.active}
+          onChange={(e) => run("switch_persona", { name: e.target.value })}
+          className="flex-1 px-3 py-2 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+        >
+          {personas.profiles.map((profile) => (
+            <option key={profile.name} value={profile.name}>
+              {profile.name}
+            </option>
+          ))}
+        </select>
+        <button
+          onClick={() => run("delete_persona_profile", { name: personas.active })}
+          disabled={personas.profiles.length <= 1}
+          className="px-3 py-2 bg-red-500 hover:bg-red-600 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
+        >
+          Delete
+        </button>
+      </div>
+
+      <textarea
+        value={instructions}
+        onChange={(e) => setInstructions(e.target.value)}
+        rows={4}
+        className="w-full px-3 py-2 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-pink-500"
+      />
+      <p className="text-xs text-pink-600 dark:text-pink-400 mb-2">
+        Use {"{name}"} for the assistant's name.
+      </p>
+
+      <div className="flex items-center gap-2 mb-2 text-sm text-pink-800 dark:text-pink-300">
+        <span>Response length</span>
+        <select
+          value={responseProfile}
+          onChange={(e) => handleResponseProfile(e.target.value as ResponseProfile)}
+          className="px-2 py-1 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+        >
+          <option value="brief">Brief</option>
+          <option value="normal">Normal</option>
+          <option value="detailed">Detailed</option>
+        </select>
+        <span>Language</span>
+        <select
+          value={locale}
+          onChange={(e) => handleLanguage(e.target.value)}
+          className="px-2 py-1 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+        >
+          {!LANGUAGES.some((language) => language.locale === locale) && (
+            <option value={locale}>{locale}</option>
+          )}
+          {LANGUAGES.map((language) => (
+            <option key={language.locale} value={language.locale}>
+              {language.label}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <div className="flex items-center gap-2 mb-2 text-sm text-pink-800 dark:text-pink-300">
+        <span>Translate from</span>
+        <select
+          value={translateFrom}
+          onChange={(e) => handleTranslation(e.target.value, translateTo)}
+          disabled={!translateTo}
+          className="px-2 py-1 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+        >
+          {LANGUAGES.map((language) => (
+            <option key={language.locale} value={language.locale}>
+              {language.locale ? language.label : "Auto-detect"}
+            </option>
+          ))}
+        </select>
+        <span>into</span>
+        <select
+          value={translateTo}
+          onChange={(e) => handleTranslation(translateFrom, e.target.value)}
+          className="px-2 py-1 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+        >
+          {LANGUAGES.map((language) => (
+            <option key={language.locale} value={language.locale}>
+              {language.locale ? language.label : "Off (reply normally)"}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <div className="flex gap-2">
+        <button
+          onClick={() => run("set_persona", { instructions })}
+          disabled={!instructions.trim()}
+          className="px-4 py-2 bg-pink-600 hover:bg-pink-700 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
+        >
+          Save
+        </button>
+        <input
+          type="text"
+          value={newName}
+          onChange={(e) => setNewName(e.target.value)}
+          placeholder="New persona name"
+          className="flex-1 px-3 py-2 border border-pink-300 dark:border-pink-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white focus:outline-none focus:ring-2 focus:ring-pink-500"
+        />
+        <button
+          onClick={handleSaveAsNew}
+          disabled={!newName.trim() || !instructions.trim()}
+          className="px-4 py-2 bg-pink-600 hover:bg-pink-700 disabled:bg-gray-400 text-white rounded-lg font-medium transition-colors"
+        >
+          Save as New
+        </button>
+      </div>
+
+      {message && (
+        <div className="mt-2 text-sm text-pink-700 dark:text-pink-400">{message}</div>
+      )}
+    </div>
+  );
+};
+
+export default PersonaSelector;
@@ -0,0 +1,91 @@
+import React, { useEffect, useState } from "react";
+import {
+  PluginInfo,
+  listPlugins,
+  openPluginsFolder,
+  setPluginEnabled,
+} from "../services/tools";
+
+const PluginsSettings: React.FC = () => {
+  const [plugins, setPlugins] = useState<PluginInfo[]>([]);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const load = async () => {
+    setPlugins(await listPlugins());
+  };
+
+  useEffect(() => {
+    load().catch((error) => console.error("Failed to load plugins:", error));
+  }, []);
+
+  const handleToggle = async (plugin: PluginInfo, enabled: boolean) => {
+    setMessage(null);
+    try {
+      setMessage(`✅ ${await setPluginEnabled(plugin.id, enabled)}`);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleOpenFolder = async () => {
+    try {
+      await openPluginsFolder();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🧩 Plugins</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        {plugins.length === 0 && (
+          <div className="text-gray-500 dark:text-gray-400">
+            No plugins installed. Each plugin goes in its own folder with a plugin.json.
+          </div>
+        )}
+        {plugins.map((plugin) => (
+          <div key={plugin.id}>
+            <label className="flex items-center gap-1">
+              <input
+                type="checkbox"
+                checked={plugin.enabled}
+                disabled={plugin.error !== null && !plugin.enabled}
+                onChange={(e) => handleToggle(plugin, e.target.checked)}
+              />
+              <span className="font-medium">{plugin.name}</span>
+              {plugin.version && (
+                <span className="text-gray-500 dark:text-gray-400">{plugin.version}</span>
+              )}
+            </label>
+            <div className="pl-4 text-gray-500 dark:text-gray-400">
+              {plugin.error
+                ? `⚠️ ${plugin.error}`
+                : [plugin.description, `Tools: ${plugin.tools.join(", ")}`]
+                    .filter(Boolean)
+                    .join(" · ")}
+            </div>
+          </div>
+        ))}
+        <div className="flex gap-2">
+          <button
+            onClick={handleOpenFolder}
+            className="px-3 py-1 bg-gray-500 text-white rounded hover:bg-gray-600"
+          >
+            Open plugins folder
+          </button>
+          <button
+            onClick={() => load().catch((error) => setMessage(`❌ ${error}`))}
+            className="px-3 py-1 bg-gray-500 text-white rounded hover:bg-gray-600"
+          >
+            Rescan
+          </button>
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default PluginsSettings;
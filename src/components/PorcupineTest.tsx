@@ -1,6 +1,49 @@
 import React, { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { describeError } from "../services/errors";
+import { runDiagnostics } from "../services/diagnostics";
+import {
+  AccessKeyValidation,
+  WakeCooldownSettings,
+  WakeWordTestReport,
+  getWakeCooldown,
+  getWakeWordStats,
+  setWakeCooldown,
+  testWakeWord,
+  validatePicovoiceKey,
+} from "../services/wake-word-stats";
+
+// Mirrors audio::ChannelStrategy on the Rust side
+type ChannelStrategy =
+  | { mode: "left" | "right" | "average" }
+  | { mode: "channel"; index: number };
+
+// Mirrors audio::CaptureBufferSize on the Rust side
+type CaptureBufferSize =
+  | { mode: "default" | "low-latency" }
+  | { mode: "fixed"; frames: number };
+
+interface CaptureBufferInfo {
+  setting: CaptureBufferSize;
+  requested_frames: number | null;
+  device_min_frames: number | null;
+  device_max_frames: number | null;
+  callback_frames: number | null;
+  callback_ms: number | null;
+  sample_rate: number;
+}
+
+// Mirrors wake_word::BUILTIN_KEYWORDS on the Rust side
+const BUILTIN_KEYWORDS: [string, string][] = [
+  ["alexa", "Alexa"],
+  ["computer", "Computer"],
+  ["jarvis", "Jarvis"],
+  ["hey-google", "Hey Google"],
+  ["ok-google", "Ok Google"],
+  ["picovoice", "Picovoice"],
+  ["porcupine", "Porcupine"],
+];
 
 interface WakeWordEvent {
   keyword: string;
@@ -14,6 +57,21 @@ const PorcupineTest: React.FC = () => {
   const [error, setError] = useState<string | null>(null);
   const [logs, setLogs] = useState<string[]>([]);
   const [currentWakeWord, setCurrentWakeWord] = useState<string>("Hi Eva");
+  const [isDebugRecording, setIsDebugRecording] = useState(false);
+  const [keywordPath, setKeywordPath] = useState("");
+  const [languageModelPath, setLanguageModelPath] = useState("");
+  const [channelStrategy, setChannelStrategy] = useState<ChannelStrategy>({ mode: "left" });
+  const [bufferSize, setBufferSize] = useState<CaptureBufferSize>({ mode: "default" });
+  const [bufferInfo, setBufferInfo] = useState<CaptureBufferInfo | null>(null);
+  const [cooldown, setCooldown] = useState<WakeCooldownSettings | null>(null);
+  const [keyToCheck, setKeyToCheck] = useState("");
+  const [keyCheck, setKeyCheck] = useState<AccessKeyValidation | null>(null);
+  const [isCheckingKey, setIsCheckingKey] = useState(false);
+  const [testPath, setTestPath] = useState("");
+  const [testSensitivity, setTestSensitivity] = useState(1.0);
+  const [sensitivity, setSensitivity] = useState(1.0);
+  const [testReport, setTestReport] = useState<WakeWordTestReport | null>(null);
+  const [isTesting, setIsTesting] = useState(false);
 
   const addLog = (message: string) => {
     const timestamp = new Date().toLocaleTimeString();
@@ -35,6 +93,26 @@ const PorcupineTest: React.FC = () => {
     };
 
     fetchCurrentWakeWord();
+
+    invoke<{
+      debug_audio: { enabled: boolean };
+      wake_word: { keyword_path: string; model_path: string; sensitivity: number };
+      audio_input: { channel_strategy: ChannelStrategy; buffer_size: CaptureBufferSize };
+    }>("get_settings")
+      .then((settings) => {
+        setChannelStrategy(settings.audio_input.channel_strategy);
+        setBufferSize(settings.audio_input.buffer_size);
+        setIsDebugRecording(settings.debug_audio.enabled);
+        setKeywordPath(settings.wake_word.keyword_path);
+        setLanguageModelPath(settings.wake_word.model_path);
+        setSensitivity(settings.wake_word.sensitivity);
+        setTestSensitivity(settings.wake_word.sensitivity);
+      })
+      .catch((err) => console.error("Failed to load settings:", err));
+
+    getWakeCooldown()
+      .then(setCooldown)
+      .catch((err) => console.error("Failed to load wake word cooldown:", err));
   }, []);
 
   useEffect(() => {
@@ -63,7 +141,7 @@ const PorcupineTest: React.FC = () => {
       setIsListening(true);
       addLog(`Success: ${result}`);
     } catch (err) {
-      const errorMessage = err as string;
+      const errorMessage = describeError(err);
       setError(errorMessage);
       addLog(`Error: ${errorMessage}`);
     }
@@ -77,7 +155,7 @@ const PorcupineTest: React.FC = () => {
       setIsListening(false);
       addLog(`Success: ${result}`);
     } catch (err) {
-      const errorMessage = err as string;
+      const errorMessage = describeError(err);
       setError(errorMessage);
       addLog(`Error: ${errorMessage}`);
     }
@@ -89,7 +167,7 @@ const PorcupineTest: React.FC = () => {
       addLog(`Current status: ${status}`);
       setIsListening(status.includes("Listening"));
     } catch (err) {
-      const errorMessage = err as string;
+      const errorMessage = describeError(err);
       setError(errorMessage);
       addLog(`Error checking status: ${errorMessage}`);
     }
@@ -102,7 +180,7 @@ const PorcupineTest: React.FC = () => {
       const result = await invoke<string>("test_microphone");
       addLog(`Microphone test result: ${result}`);
     } catch (err) {
-      const errorMessage = err as string;
+      const errorMessage = describeError(err);
       setError(errorMessage);
       addLog(`Microphone test failed: ${errorMessage}`);
     }
@@ -115,23 +193,214 @@ const PorcupineTest: React.FC = () => {
       const result = await invoke<string>("test_audio_levels");
       addLog(`Audio levels test: ${result}`);
     } catch (err) {
-      const errorMessage = err as string;
+      const errorMessage = describeError(err);
       setError(errorMessage);
       addLog(`Audio levels test failed: ${errorMessage}`);
     }
   };
 
+  const runSetupDiagnostics = async () => {
+    try {
+      setError(null);
+      addLog("Running diagnostics...");
+      const report = await runDiagnostics();
+      const icons = { pass: "✅", warn: "⚠️", fail: "❌", skipped: "⏭️" };
+      report.checks.forEach((check) => {
+        const hint = check.recovery_hint ? ` (${check.recovery_hint})` : "";
+        addLog(`${icons[check.status]} ${check.id}: ${check.detail}${hint}`);
+      });
+      addLog(report.ok ? "Diagnostics passed" : "Diagnostics found problems");
+    } catch (err) {
+      const errorMessage = describeError(err);
+      setError(errorMessage);
+      addLog(`Diagnostics failed: ${errorMessage}`);
+    }
+  };
+
+  const showWakeWordStats = async () => {
+    try {
+      const stats = await getWakeWordStats();
+      addLog(
+        `Detections: ${stats.detections}, false triggers: ${stats.false_triggers}, cooldown skips: ${stats.cooldown_skips}, same-utterance skips: ${stats.utterance_skips}, frames: ${stats.frames_processed}`
+      );
+      if (stats.detections > 0) {
+        addLog(
+          `Average amplitude at detection: ${stats.average_detection_amplitude.toFixed(1)} (peak ${stats.average_detection_peak.toFixed(0)})`
+        );
+      }
+    } catch (err) {
+      const errorMessage = describeError(err);
+      addLog(`Failed to load wake word stats: ${errorMessage}`);
+    }
+  };
+
+  const toggleDebugRecording = async () => {
+    try {
+      const result = await invoke<string>("set_debug_audio", {
+        enabled: !isDebugRecording,
+      });
+      setIsDebugRecording(!isDebugRecording);
+      addLog(result);
+    } catch (err) {
+      const errorMessage = describeError(err);
+      addLog(`Failed to toggle debug recording: ${errorMessage}`);
+    }
+  };
+
+  const listDebugRecordings = async () => {
+    try {
+      const recordings = await invoke<
+        { file_name: string; size_bytes: number; duration_ms: number }[]
+      >("list_debug_recordings");
+      if (recordings.length === 0) {
+        addLog("No debug recordings");
+      }
+      recordings.forEach((recording) => {
+        addLog(
+          `${recording.file_name}: ${(recording.duration_ms / 1000).toFixed(1)}s, ${(recording.size_bytes / 1024).toFixed(0)} KB`
+        );
+      });
+    } catch (err) {
+      const errorMessage = describeError(err);
+      addLog(`Failed to list debug recordings: ${errorMessage}`);
+    }
+  };
+
   const refreshWakeWord = async () => {
     try {
       const wakeWord = await invoke<string>("get_current_wake_word");
       setCurrentWakeWord(wakeWord);
       addLog(`Current wake word updated: "${wakeWord}"`);
     } catch (err) {
-      const errorMessage = err as string;
+      const errorMessage = describeError(err);
       addLog(`Failed to refresh wake word: ${errorMessage}`);
     }
   };
 
+  const applyWakeWordModel = async () => {
+    try {
+      const result = await invoke<string>("set_wake_word_model", {
+        keywordPath,
+        modelPath: languageModelPath,
+      });
+      addLog(result);
+      setCurrentWakeWord(await invoke<string>("get_current_wake_word"));
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Failed to set wake word model: ${describeError(err)}`);
+    }
+  };
+
+  // Swapped into the running listener, so the microphone stays open
+  const switchWakeWord = async (keywordOrModel: string) => {
+    try {
+      addLog(await invoke<string>("set_wake_word", { keywordOrModel }));
+      setCurrentWakeWord(await invoke<string>("get_current_wake_word"));
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Failed to switch wake word: ${describeError(err)}`);
+    }
+  };
+
+  // Porcupine is rebuilt on every change, so only once the slider is let go
+  const applySensitivity = async () => {
+    try {
+      addLog(await invoke<string>("set_wake_word_sensitivity", { sensitivity }));
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Failed to set sensitivity: ${describeError(err)}`);
+    }
+  };
+
+  const runRecordingTest = async () => {
+    setIsTesting(true);
+    setTestReport(null);
+    try {
+      const report = await testWakeWord(testPath.trim(), keywordPath.trim(), testSensitivity);
+      setTestReport(report);
+      addLog(
+        `🧪 ${report.total_detections} detections in ${report.files.length} recording(s) at sensitivity ${report.sensitivity}`
+      );
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Wake word test failed: ${describeError(err)}`);
+    } finally {
+      setIsTesting(false);
+    }
+  };
+
+  const applyChannelStrategy = async (strategy: ChannelStrategy) => {
+    setChannelStrategy(strategy);
+    try {
+      addLog(await invoke<string>("set_channel_strategy", { strategy }));
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Failed to set input channel: ${describeError(err)}`);
+    }
+  };
+
+  const refreshBufferInfo = async () => {
+    try {
+      setBufferInfo(await invoke<CaptureBufferInfo>("get_capture_buffer_info"));
+    } catch (err) {
+      console.error("Failed to load capture buffer info:", err);
+    }
+  };
+
+  // The device reports what it settled on once the stream is running
+  useEffect(() => {
+    if (!isListening) return;
+    const timer = setTimeout(refreshBufferInfo, 1000);
+    return () => clearTimeout(timer);
+  }, [isListening, bufferSize]);
+
+  const applyBufferSize = async (next: CaptureBufferSize) => {
+    setBufferSize(next);
+    try {
+      addLog(await invoke<string>("set_capture_buffer_size", { bufferSize: next }));
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Failed to set capture buffer: ${describeError(err)}`);
+    }
+  };
+
+  const applyCooldown = async (next: WakeCooldownSettings) => {
+    setCooldown(next);
+    try {
+      addLog(await setWakeCooldown(next));
+    } catch (err) {
+      setError(describeError(err));
+      addLog(`Failed to set cooldown: ${describeError(err)}`);
+      setCooldown(await getWakeCooldown());
+    }
+  };
+
+  // The override for the keyword being listened for; blank falls back to the default
+  const keywordCooldownKey = currentWakeWord.toLowerCase();
+  const editKeywordCooldown = (seconds: string) => {
+    if (!cooldown) return;
+    const perKeyword = { ...cooldown.per_keyword };
+    if (seconds.trim() === "") {
+      delete perKeyword[keywordCooldownKey];
+    } else {
+      perKeyword[keywordCooldownKey] = Math.round(Math.max(0, parseFloat(seconds) || 0) * 1000);
+    }
+    setCooldown({ ...cooldown, per_keyword: perKeyword });
+  };
+
+  const checkAccessKey = async () => {
+    setIsCheckingKey(true);
+    try {
+      const result = await validatePicovoiceKey(keyToCheck);
+      setKeyCheck(result);
+      addLog(`Access key check: ${result.status}`);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsCheckingKey(false);
+    }
+  };
+
   const clearLogs = () => {
     setLogs([]);
   };
@@ -167,6 +436,31 @@ const PorcupineTest: React.FC = () => {
               console.picovoice.ai
             </a>
           </p>
+          <div className="flex gap-2">
+            <input
+              type="password"
+              value={keyToCheck}
+              onChange={(e) => {
+                setKeyToCheck(e.target.value);
+                setKeyCheck(null);
+              }}
+              placeholder="Paste an access key to check it"
+              className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+            <button
+              onClick={checkAccessKey}
+              disabled={!keyToCheck.trim() || isCheckingKey}
+              className="px-3 py-1 bg-blue-600 hover:bg-blue-700 disabled:bg-gray-400 text-white rounded font-medium transition-colors"
+            >
+              {isCheckingKey ? "Checking..." : "Check Key"}
+            </button>
+          </div>
+          {keyCheck && (
+            <div className="text-xs">
+              {keyCheck.status === "valid" ? "✅" : "❌"} {keyCheck.message}
+              {keyCheck.hint && <div className="text-blue-600 dark:text-blue-500">💡 {keyCheck.hint}</div>}
+            </div>
+          )}
           <p className="text-xs text-blue-600 dark:text-blue-500">
             💡 Tip: Add this to your shell profile (~/.zshrc, ~/.bashrc) to make
             it permanent
@@ -211,6 +505,242 @@ const PorcupineTest: React.FC = () => {
         </div>
       </div>
 
+      {/* Wake Word Model */}
+      <div className="mb-6 p-4 bg-gray-50 dark:bg-gray-700 rounded-lg">
+        <h3 className="text-sm font-semibold text-gray-800 dark:text-white mb-2">
+          🌐 Wake Word Model
+        </h3>
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-2 mb-2">
+          <input
+            type="text"
+            value={keywordPath}
+            onChange={(e) => setKeywordPath(e.target.value)}
+            placeholder="Keyword file (.ppn)"
+            className="px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+          />
+          <input
+            type="text"
+            value={languageModelPath}
+            onChange={(e) => setLanguageModelPath(e.target.value)}
+            placeholder="Language model (.pv), empty for English"
+            className="px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+          />
+        </div>
+        <button
+          onClick={applyWakeWordModel}
+          className="px-3 py-1 text-sm bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded transition-colors"
+        >
+          Apply
+        </button>
+        <div className="flex items-center gap-2 mt-3 text-sm text-gray-700 dark:text-gray-200">
+          <span>🔤 Built-in keyword</span>
+          <select
+            value=""
+            onChange={(e) => e.target.value && switchWakeWord(e.target.value)}
+            className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          >
+            <option value="">Switch to…</option>
+            {BUILTIN_KEYWORDS.map(([id, label]) => (
+              <option key={id} value={id}>
+                {label}
+              </option>
+            ))}
+          </select>
+        </div>
+        <label className="flex items-center gap-2 mt-3 text-sm text-gray-700 dark:text-gray-200">
+          <span>Live sensitivity {sensitivity.toFixed(2)}</span>
+          <input
+            type="range"
+            min={0}
+            max={1}
+            step={0.05}
+            value={sensitivity}
+            onChange={(e) => setSensitivity(Number(e.target.value))}
+            onMouseUp={applySensitivity}
+            onTouchEnd={applySensitivity}
+            onKeyUp={applySensitivity}
+          />
+        </label>
+        <div className="flex items-center gap-2 mt-3 text-sm text-gray-700 dark:text-gray-200">
+          <span>🎚️ Input channel</span>
+          <select
+            value={channelStrategy.mode}
+            onChange={(e) => {
+              const mode = e.target.value as ChannelStrategy["mode"];
+              applyChannelStrategy(
+                mode === "channel" ? { mode, index: 0 } : { mode }
+              );
+            }}
+            className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          >
+            <option value="left">Left</option>
+            <option value="right">Right</option>
+            <option value="average">Average (downmix)</option>
+            <option value="channel">Specific channel</option>
+          </select>
+          {channelStrategy.mode === "channel" && (
+            <input
+              type="number"
+              min={1}
+              value={channelStrategy.index + 1}
+              onChange={(e) =>
+                applyChannelStrategy({
+                  mode: "channel",
+                  index: Math.max(0, parseInt(e.target.value, 10) - 1 || 0),
+                })
+              }
+              className="w-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+          )}
+        </div>
+        <div className="flex items-center gap-2 mt-3 text-sm text-gray-700 dark:text-gray-200">
+          <span>📦 Capture buffer</span>
+          <select
+            value={bufferSize.mode}
+            onChange={(e) => {
+              const mode = e.target.value as CaptureBufferSize["mode"];
+              applyBufferSize(mode === "fixed" ? { mode, frames: 512 } : { mode });
+            }}
+            className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          >
+            <option value="default">Device default</option>
+            <option value="low-latency">Low latency</option>
+            <option value="fixed">Fixed size</option>
+          </select>
+          {bufferSize.mode === "fixed" && (
+            <input
+              type="number"
+              min={16}
+              step={16}
+              value={bufferSize.frames}
+              onChange={(e) =>
+                applyBufferSize({
+                  mode: "fixed",
+                  frames: Math.max(16, parseInt(e.target.value, 10) || 16),
+                })
+              }
+              className="w-20 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+          )}
+        </div>
+        {cooldown && (
+          <>
+            <div className="flex flex-wrap items-center gap-2 mt-3 text-sm text-gray-700 dark:text-gray-200">
+              <span>⏱️ Cooldown</span>
+              <input
+                type="number"
+                min={0}
+                max={60}
+                step={0.5}
+                value={cooldown.default_ms / 1000}
+                onChange={(e) =>
+                  setCooldown({ ...cooldown, default_ms: Math.round(Math.max(0, parseFloat(e.target.value) || 0) * 1000) })
+                }
+                onBlur={() => applyCooldown(cooldown)}
+                className="w-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+              />
+              <span>s, "{currentWakeWord}"</span>
+              <input
+                type="number"
+                min={0}
+                max={60}
+                step={0.5}
+                placeholder="default"
+                value={
+                  cooldown.per_keyword[keywordCooldownKey] != null
+                    ? cooldown.per_keyword[keywordCooldownKey] / 1000
+                    : ""
+                }
+                onChange={(e) => editKeywordCooldown(e.target.value)}
+                onBlur={() => applyCooldown(cooldown)}
+                className="w-20 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+              />
+              <span>s</span>
+              <label className="flex items-center gap-1">
+                <input
+                  type="checkbox"
+                  checked={cooldown.suppress_same_utterance}
+                  onChange={(e) => applyCooldown({ ...cooldown, suppress_same_utterance: e.target.checked })}
+                />
+                Ignore repeats in the same utterance
+              </label>
+            </div>
+            <div className="text-xs text-gray-500 dark:text-gray-400 mt-1">
+              A wake word can't fire again until its cooldown passes
+              {cooldown.suppress_same_utterance &&
+                ` and ${cooldown.utterance_gap_ms} ms of silence has ended the utterance that triggered it`}
+              .
+            </div>
+          </>
+        )}
+        <div className="text-xs text-gray-500 dark:text-gray-400 mt-1">
+          Smaller buffers hear the wake word sooner but use more CPU.
+          {bufferInfo?.callback_frames != null && (
+            <>
+              {" "}Device delivers {bufferInfo.callback_frames} frames
+              ({bufferInfo.callback_ms?.toFixed(1)} ms at {bufferInfo.sample_rate} Hz)
+              {bufferInfo.requested_frames != null && `, ${bufferInfo.requested_frames} requested`}
+              {bufferInfo.device_min_frames != null &&
+                `, supports ${bufferInfo.device_min_frames}–${bufferInfo.device_max_frames}`}
+            </>
+          )}
+        </div>
+      </div>
+
+      {/* Recording Test */}
+      <div className="mb-6 p-4 bg-gray-50 dark:bg-gray-700 rounded-lg">
+        <h3 className="text-sm font-semibold text-gray-800 dark:text-white mb-2">
+          🧪 Test Recordings
+        </h3>
+        <p className="text-xs text-gray-600 dark:text-gray-300 mb-2">
+          Runs a WAV file, or every WAV in a folder, through detection with the keyword file above
+        </p>
+        <div className="flex flex-wrap items-center gap-2 mb-2 text-sm text-gray-700 dark:text-gray-200">
+          <input
+            type="text"
+            value={testPath}
+            onChange={(e) => setTestPath(e.target.value)}
+            placeholder="Recording (.wav) or folder"
+            className="flex-1 px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+          />
+          <span>Sensitivity {testSensitivity.toFixed(2)}</span>
+          <input
+            type="range"
+            min={0}
+            max={1}
+            step={0.05}
+            value={testSensitivity}
+            onChange={(e) => setTestSensitivity(Number(e.target.value))}
+          />
+          <button
+            onClick={runRecordingTest}
+            disabled={!testPath.trim() || isTesting}
+            className="px-3 py-1 text-sm bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded transition-colors disabled:opacity-50"
+          >
+            {isTesting ? "Testing..." : "Run"}
+          </button>
+        </div>
+        {testReport && (
+          <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+            {testReport.files.map((file) => (
+              <div key={file.file} className="break-all">
+                {file.error
+                  ? `❌ ${file.file}: ${file.error}`
+                  : `${file.detections_ms.length > 0 ? "✅" : "⚪"} ${file.file} (${(
+                      file.duration_ms / 1000
+                    ).toFixed(1)}s): ${file.detections_ms.length} detections${
+                      file.detections_ms.length > 0
+                        ? ` at ${file.detections_ms.map((ms) => `${(ms / 1000).toFixed(1)}s`).join(", ")}`
+                        : ""
+                    }${file.cooldown_skips > 0 ? `, ${file.cooldown_skips} within cooldown` : ""}${
+                      file.utterance_skips > 0 ? `, ${file.utterance_skips} repeated in one utterance` : ""
+                    }`}
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+
       {/* Control Buttons */}
       <div className="grid grid-cols-2 md:grid-cols-3 gap-3 mb-6">
         <button
@@ -231,6 +761,34 @@ const PorcupineTest: React.FC = () => {
         >
           🔄 Refresh Wake Word
         </button>
+        <button
+          onClick={runSetupDiagnostics}
+          className="px-4 py-3 rounded-lg font-medium transition-colors bg-teal-500 hover:bg-teal-600 text-white text-sm"
+        >
+          🩺 Run Diagnostics
+        </button>
+        <button
+          onClick={showWakeWordStats}
+          className="px-4 py-3 rounded-lg font-medium transition-colors bg-indigo-500 hover:bg-indigo-600 text-white text-sm"
+        >
+          📊 Wake Word Stats
+        </button>
+        <button
+          onClick={toggleDebugRecording}
+          className={`px-4 py-3 rounded-lg font-medium transition-colors text-white text-sm ${
+            isDebugRecording
+              ? "bg-red-500 hover:bg-red-600"
+              : "bg-gray-500 hover:bg-gray-600"
+          }`}
+        >
+          {isDebugRecording ? "⏺️ Recording Debug Audio" : "⏺️ Record Debug Audio"}
+        </button>
+        <button
+          onClick={listDebugRecordings}
+          className="px-4 py-3 rounded-lg font-medium transition-colors bg-gray-500 hover:bg-gray-600 text-white text-sm"
+        >
+          📂 Debug Recordings
+        </button>
         <button
           onClick={startListening}
           disabled={isListening}
@@ -0,0 +1,95 @@
+import React, { useEffect, useState } from "react";
+import {
+  getPowerState,
+  getSleepInhibit,
+  onPowerState,
+  PowerProfile,
+  PowerState,
+  setPowerProfile,
+  setSleepInhibit,
+  SleepInhibitStatus,
+} from "../services/power";
+
+const PowerSettings: React.FC = () => {
+  const [state, setState] = useState<PowerState | null>(null);
+  const [sleepInhibit, setSleepInhibitState] = useState<SleepInhibitStatus | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    getPowerState()
+      .then(setState)
+      .catch((error) => console.error("Failed to load power state:", error));
+    getSleepInhibit()
+      .then(setSleepInhibitState)
+      .catch((error) => console.error("Failed to load sleep inhibit:", error));
+
+    onPowerState(setState).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  if (!state) return null;
+
+  const changeProfile = async (profile: PowerProfile) => {
+    try {
+      setMessage(`✅ ${await setPowerProfile(profile)}`);
+      setState(await getPowerState());
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const changeSleepInhibit = async (enabled: boolean) => {
+    try {
+      setMessage(`✅ ${await setSleepInhibit(enabled)}`);
+      setSleepInhibitState(await getSleepInhibit());
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔋 Power</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <div>
+          {state.on_battery ? "🔋 On battery" : "🔌 Plugged in"}
+          {state.saving && " · saving power"}
+        </div>
+        <select
+          value={state.profile}
+          onChange={(e) => changeProfile(e.target.value as PowerProfile)}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        >
+          <option value="auto">Save power on battery</option>
+          <option value="performance">Always full speed</option>
+          <option value="power-saver">Always save power</option>
+        </select>
+        <div className="text-gray-500 dark:text-gray-400">
+          Saving power skips wake word processing during silence, so the first syllable after a long quiet
+          spell may occasionally be missed.
+        </div>
+        {sleepInhibit && (
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={sleepInhibit.enabled}
+              onChange={(e) => changeSleepInhibit(e.target.checked)}
+            />
+            Keep the computer awake during conversations and transcriptions
+            {sleepInhibit.active && " ☕"}
+          </label>
+        )}
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default PowerSettings;
@@ -0,0 +1,76 @@
+import React, { useEffect, useState } from "react";
+import { DataPrivacy, getDataPrivacy, purgeAllData, setDataPrivacy } from "../services/privacy";
+
+const PrivacySettings: React.FC = () => {
+  const [privacy, setPrivacy] = useState<DataPrivacy | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getDataPrivacy()
+      .then(setPrivacy)
+      .catch((error) => console.error("Failed to load privacy settings:", error));
+  }, []);
+
+  if (!privacy) return null;
+
+  const save = async (next: DataPrivacy) => {
+    setPrivacy(next);
+    try {
+      setMessage(`✅ ${await setDataPrivacy(next)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handlePurge = async () => {
+    const confirmed = window.confirm(
+      "Delete all saved conversations, recordings, debug audio, memory and cached answers? This action cannot be undone."
+    );
+    if (!confirmed) return;
+    try {
+      const report = await purgeAllData();
+      setMessage(
+        `✅ Deleted ${report.conversations} conversations and ${report.debug_recordings} debug recordings`
+      );
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔒 Privacy</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={privacy.never_store_audio}
+            onChange={(e) => save({ ...privacy, never_store_audio: e.target.checked })}
+          />
+          Never save audio to disk
+        </label>
+        <div className="flex items-center gap-1">
+          Delete conversations after
+          <input
+            type="number"
+            min={0}
+            value={privacy.transcript_retention_days}
+            onChange={(e) => setPrivacy({ ...privacy, transcript_retention_days: Number(e.target.value) })}
+            onBlur={() => save(privacy)}
+            className="w-14 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          days (0 = keep)
+        </div>
+        <button
+          onClick={handlePurge}
+          className="px-3 py-1 bg-red-500 text-white rounded hover:bg-red-600"
+        >
+          🗑️ Delete all data
+        </button>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default PrivacySettings;
@@ -0,0 +1,127 @@
+import React, { useEffect, useState } from "react";
+import {
+  ProactiveSettings as Proactive,
+  TimedGreeting,
+  getProactiveSettings,
+  setProactiveSettings,
+} from "../services/proactive";
+
+const ProactiveSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Proactive | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getProactiveSettings()
+      .then(setSettings)
+      .catch((error) => console.error("Failed to load proactive settings:", error));
+  }, []);
+
+  if (!settings) return null;
+
+  const change = (update: Partial<Proactive>) => setSettings({ ...settings, ...update });
+
+  const changeGreeting = (index: number, update: Partial<TimedGreeting>) =>
+    change({
+      greetings: settings.greetings.map((greeting, i) =>
+        i === index ? { ...greeting, ...update } : greeting
+      ),
+    });
+
+  const handleSave = async () => {
+    try {
+      setMessage(`✅ ${await setProactiveSettings(settings)}`);
+      setSettings(await getProactiveSettings());
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const numberInput = (value: number, onChange: (value: number) => void) => (
+    <input
+      type="number"
+      min={0}
+      value={value}
+      onChange={(e) => onChange(Math.max(0, Number(e.target.value) || 0))}
+      className="w-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+    />
+  );
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🗨️ Speaking Up</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.enabled}
+            onChange={(e) => change({ enabled: e.target.checked })}
+          />
+          Let Eva speak first (never during quiet hours, calls, or while muted)
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          <input
+            type="checkbox"
+            checked={settings.greet_on_start}
+            onChange={(e) => change({ greet_on_start: e.target.checked })}
+          />
+          Greet me when Eva starts
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          Check in after
+          {numberInput(settings.idle_minutes, (idle_minutes) => change({ idle_minutes }))}
+          idle minutes (0 = never)
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          At most
+          {numberInput(settings.max_per_day, (max_per_day) => change({ max_per_day }))}
+          times a day, every
+          {numberInput(settings.min_interval_minutes, (min_interval_minutes) =>
+            change({ min_interval_minutes })
+          )}
+          minutes
+        </label>
+        {settings.greetings.map((greeting, index) => (
+          <div key={index} className="flex items-center gap-2 pl-4">
+            <input
+              type="time"
+              value={greeting.at}
+              onChange={(e) => changeGreeting(index, { at: e.target.value })}
+              className="px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+            <input
+              type="text"
+              value={greeting.message}
+              onChange={(e) => changeGreeting(index, { message: e.target.value })}
+              placeholder="e.g. Good morning, and remind me to drink water"
+              className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+            <button
+              onClick={() => change({ greetings: settings.greetings.filter((_, i) => i !== index) })}
+              className="text-red-500 hover:text-red-600"
+              title="Remove"
+            >
+              ✕
+            </button>
+          </div>
+        ))}
+        <div className="flex gap-2 pt-1">
+          <button
+            onClick={() => change({ greetings: [...settings.greetings, { at: "08:00", message: "" }] })}
+            className="px-3 py-1 bg-gray-500 text-white rounded hover:bg-gray-600"
+          >
+            Add timed greeting
+          </button>
+          <button
+            onClick={handleSave}
+            className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600"
+          >
+            Save
+          </button>
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default ProactiveSettings;
@@ -0,0 +1,221 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  ActiveProfile,
+  AssistantProfile,
+  ProfileSettings as Settings,
+  REALTIME_VOICES,
+  deleteProfile,
+  getActiveProfile,
+  getProfiles,
+  onProfileChanged,
+  saveProfile,
+  setProfileAutoSwitch,
+  switchProfile,
+} from "../services/profiles";
+
+const EMPTY_PROFILE: AssistantProfile = {
+  name: "",
+  keyword_path: "",
+  persona: "",
+  voice: "",
+  tools: null,
+  apps: [],
+};
+
+const splitList = (text: string): string[] =>
+  text
+    .split(",")
+    .map((item) => item.trim())
+    .filter(Boolean);
+
+const ProfileSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [active, setActive] = useState<ActiveProfile | null>(null);
+  const [personas, setPersonas] = useState<string[]>([]);
+  const [draft, setDraft] = useState<AssistantProfile>(EMPTY_PROFILE);
+  // Comma-separated while editing, so typing a comma doesn't get swallowed
+  const [toolsText, setToolsText] = useState("");
+  const [appsText, setAppsText] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    setSettings(await getProfiles());
+    setActive(await getActiveProfile());
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    load().catch((error) => console.error("Failed to load profiles:", error));
+    invoke<{ profiles: { name: string }[] }>("get_personas")
+      .then((loaded) => setPersonas(loaded.profiles.map((profile) => profile.name)))
+      .catch((error) => console.error("Failed to load personas:", error));
+    onProfileChanged(setActive).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, [load]);
+
+  if (!settings) return null;
+
+  const run = async (action: Promise<string>) => {
+    try {
+      setMessage(`✅ ${await action}`);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const edit = (profile: AssistantProfile) => {
+    setDraft(profile);
+    setToolsText(profile.tools?.join(", ") ?? "");
+    setAppsText(profile.apps.join(", "));
+  };
+
+  const save = async () => {
+    await run(
+      saveProfile({
+        ...draft,
+        tools: draft.tools === null ? null : splitList(toolsText),
+        apps: splitList(appsText),
+      })
+    );
+    edit(EMPTY_PROFILE);
+  };
+
+  const inputClass =
+    "w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800";
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🗂️ Profiles</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        <select
+          value={settings.active}
+          onChange={(e) => run(switchProfile(e.target.value))}
+          className={inputClass}
+        >
+          <option value="">No profile (regular settings)</option>
+          {settings.profiles.map((profile) => (
+            <option key={profile.name} value={profile.name}>
+              {profile.name}
+            </option>
+          ))}
+        </select>
+        {active?.automatic && <div>🔄 Using "{active.active}" for the app in front</div>}
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.auto_switch}
+            onChange={(e) => run(setProfileAutoSwitch(e.target.checked))}
+          />
+          Switch with the foreground app
+        </label>
+
+        {settings.profiles.map((profile) => (
+          <div key={profile.name} className="flex items-center gap-2">
+            <span className="flex-1 truncate">
+              {profile.name}
+              {profile.apps.length > 0 && ` — ${profile.apps.join(", ")}`}
+            </span>
+            <button onClick={() => edit(profile)} className="text-blue-500 hover:underline">
+              Edit
+            </button>
+            <button onClick={() => run(deleteProfile(profile.name))} className="text-red-500 hover:underline">
+              Delete
+            </button>
+          </div>
+        ))}
+
+        <div className="space-y-1 pt-2 border-t border-gray-200 dark:border-gray-600">
+          <input
+            type="text"
+            value={draft.name}
+            onChange={(e) => setDraft({ ...draft, name: e.target.value })}
+            placeholder="Profile name, e.g. Coding"
+            className={inputClass}
+          />
+          <input
+            type="text"
+            value={draft.keyword_path}
+            onChange={(e) => setDraft({ ...draft, keyword_path: e.target.value })}
+            placeholder="Wake word .ppn file (blank keeps the current one)"
+            className={inputClass}
+          />
+          <div className="flex gap-2">
+            <select
+              value={draft.persona}
+              onChange={(e) => setDraft({ ...draft, persona: e.target.value })}
+              className={inputClass}
+            >
+              <option value="">Keep the current persona</option>
+              {personas.map((persona) => (
+                <option key={persona} value={persona}>
+                  {persona}
+                </option>
+              ))}
+            </select>
+            <select
+              value={draft.voice}
+              onChange={(e) => setDraft({ ...draft, voice: e.target.value })}
+              className={inputClass}
+            >
+              <option value="">Default voice</option>
+              {REALTIME_VOICES.map((voice) => (
+                <option key={voice} value={voice}>
+                  {voice}
+                </option>
+              ))}
+            </select>
+          </div>
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={draft.tools === null}
+              onChange={(e) => setDraft({ ...draft, tools: e.target.checked ? null : [] })}
+            />
+            All tools
+          </label>
+          {draft.tools !== null && (
+            <input
+              type="text"
+              value={toolsText}
+              onChange={(e) => setToolsText(e.target.value)}
+              placeholder="Tool names, comma-separated"
+              className={inputClass}
+            />
+          )}
+          <input
+            type="text"
+            value={appsText}
+            onChange={(e) => setAppsText(e.target.value)}
+            placeholder="Apps that switch to it, e.g. code, steam.exe"
+            className={inputClass}
+          />
+          <div className="flex gap-2">
+            <button
+              onClick={save}
+              disabled={!draft.name.trim()}
+              className="px-3 py-1 bg-blue-500 disabled:bg-gray-400 text-white rounded hover:bg-blue-600"
+            >
+              Save Profile
+            </button>
+            {draft !== EMPTY_PROFILE && (
+              <button onClick={() => edit(EMPTY_PROFILE)} className="px-3 py-1 text-gray-500 hover:underline">
+                Clear
+              </button>
+            )}
+          </div>
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default ProfileSettings;
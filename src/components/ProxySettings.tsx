@@ -0,0 +1,105 @@
+import React, { useEffect, useState } from "react";
+import { ProxyMode, ProxySettings as Settings, getProxy, setProxy } from "../services/proxy";
+
+const ProxySettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [password, setPassword] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getProxy()
+      .then(setSettings)
+      .catch((error) => console.error("Failed to load proxy settings:", error));
+  }, []);
+
+  if (!settings) return null;
+
+  const update = (change: Partial<Settings>) => setSettings({ ...settings, ...change });
+
+  const save = async (next: Settings) => {
+    setSettings(next);
+    try {
+      const result = await setProxy(next, password || null);
+      setPassword(""); // Clear the input for security
+      setMessage(`✅ ${result}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🌐 Network Proxy</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        <select
+          value={settings.mode}
+          onChange={(e) => {
+            const mode = e.target.value as ProxyMode;
+            // A manual proxy needs a URL before it can be saved
+            if (mode === "manual" && !settings.url.trim()) {
+              update({ mode });
+            } else {
+              save({ ...settings, mode });
+            }
+          }}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        >
+          <option value="system">Use system proxy settings</option>
+          <option value="off">No proxy</option>
+          <option value="manual">Manual proxy</option>
+        </select>
+        {settings.mode === "manual" && (
+          <>
+            <input
+              type="text"
+              value={settings.url}
+              onChange={(e) => update({ url: e.target.value })}
+              placeholder="http://proxy.example.com:8080 or socks5://host:1080"
+              className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+            />
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={settings.username ?? ""}
+                onChange={(e) => update({ username: e.target.value || null })}
+                placeholder="Username (optional)"
+                className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+              />
+              <input
+                type="password"
+                value={password}
+                onChange={(e) => setPassword(e.target.value)}
+                placeholder="Password (kept in keychain)"
+                className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+              />
+            </div>
+            <button
+              onClick={() => save(settings)}
+              disabled={!settings.url.trim()}
+              className="px-3 py-1 bg-blue-500 disabled:bg-gray-400 text-white rounded hover:bg-blue-600"
+            >
+              Save
+            </button>
+          </>
+        )}
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={settings.ca_bundle_path}
+            onChange={(e) => update({ ca_bundle_path: e.target.value })}
+            onBlur={() => save(settings)}
+            placeholder="Extra root certificates (.pem, optional)"
+            className="flex-1 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+        </div>
+        <div className="text-gray-500 dark:text-gray-400">
+          A manual proxy also carries the voice connection, for networks that block WebSockets. Add your
+          organization's root certificate if its network inspects TLS. Changes apply from the next connection.
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default ProxySettings;
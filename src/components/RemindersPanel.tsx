@@ -0,0 +1,98 @@
+import React, { useEffect, useState } from "react";
+import {
+  Reminder,
+  cancelReminder,
+  listReminders,
+  onRemindersChanged,
+} from "../services/reminders";
+
+const KIND_ICONS: Record<Reminder["kind"], string> = {
+  timer: "⏲️",
+  alarm: "⏰",
+  reminder: "📝",
+};
+
+// Re-render often enough for the countdowns to tick
+const TICK_MS = 1000;
+
+function formatRemaining(dueAt: number, now: number): string {
+  const seconds = Math.max(0, Math.round((dueAt - now) / 1000));
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  const rest = seconds % 60;
+  const pad = (value: number) => value.toString().padStart(2, "0");
+  return hours > 0 ? `${hours}:${pad(minutes)}:${pad(rest)}` : `${minutes}:${pad(rest)}`;
+}
+
+const RemindersPanel: React.FC = () => {
+  const [reminders, setReminders] = useState<Reminder[]>([]);
+  const [now, setNow] = useState(Date.now());
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    listReminders()
+      .then(setReminders)
+      .catch((error) => console.error("Failed to load reminders:", error));
+    onRemindersChanged(setReminders).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (reminders.length === 0) return;
+    const interval = setInterval(() => setNow(Date.now()), TICK_MS);
+    return () => clearInterval(interval);
+  }, [reminders.length]);
+
+  const handleCancel = async (id: string) => {
+    try {
+      await cancelReminder(id);
+    } catch (error) {
+      console.error("Failed to cancel reminder:", error);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">⏰ Timers & Reminders</div>
+      {reminders.length === 0 ? (
+        <div className="text-xs text-gray-500 dark:text-gray-400">
+          Nothing set. Try "set a timer for 10 minutes".
+        </div>
+      ) : (
+        <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+          {reminders.map((reminder) => (
+            <div key={reminder.id} className="flex items-center gap-2">
+              <span>{KIND_ICONS[reminder.kind]}</span>
+              <span className="flex-1 truncate">
+                {reminder.label || reminder.kind}
+              </span>
+              <span className="font-mono">
+                {reminder.kind === "timer"
+                  ? formatRemaining(reminder.due_at, now)
+                  : new Date(reminder.due_at).toLocaleTimeString([], {
+                      hour: "2-digit",
+                      minute: "2-digit",
+                    })}
+              </span>
+              <button
+                onClick={() => handleCancel(reminder.id)}
+                className="text-red-500 hover:text-red-600"
+                title="Cancel"
+              >
+                ✕
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default RemindersPanel;
@@ -0,0 +1,110 @@
+import React, { useEffect, useState } from "react";
+import {
+  CachedQueryKind,
+  CachedResponse,
+  ResponseCacheSettings as CacheSettings,
+  clearResponseCache,
+  getResponseCacheSettings,
+  listCachedResponses,
+  setResponseCacheSettings,
+} from "../services/response-cache";
+
+const KIND_LABELS: Record<CachedQueryKind, string> = {
+  "unit-conversion": "📏",
+  "time-zone": "🕐",
+  weather: "🌦️",
+};
+
+const ResponseCacheSettings: React.FC = () => {
+  const [settings, setSettings] = useState<CacheSettings | null>(null);
+  const [entries, setEntries] = useState<CachedResponse[] | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getResponseCacheSettings()
+      .then(setSettings)
+      .catch((error) => console.error("Failed to load response cache settings:", error));
+  }, []);
+
+  if (!settings) return null;
+
+  const save = async (updated: CacheSettings) => {
+    setSettings(updated);
+    try {
+      setMessage(`✅ ${await setResponseCacheSettings(updated)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const showEntries = async () => {
+    try {
+      setEntries(await listCachedResponses());
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      setMessage(`✅ ${await clearResponseCache()}`);
+      setEntries([]);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">⚡ Answer Cache</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={settings.enabled}
+            onChange={(e) => save({ ...settings, enabled: e.target.checked })}
+          />
+          Answer repeated conversions, time zone and weather questions without asking the model
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          Keep weather answers for
+          <input
+            type="number"
+            min={1}
+            value={settings.weather_ttl_minutes}
+            onChange={(e) =>
+              setSettings({ ...settings, weather_ttl_minutes: Math.max(1, Number(e.target.value) || 1) })
+            }
+            onBlur={() => save(settings)}
+            className="w-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+          />
+          minutes
+        </label>
+        <div className="flex gap-2 pt-1">
+          <button
+            onClick={showEntries}
+            className="px-3 py-1 bg-gray-500 text-white rounded hover:bg-gray-600"
+          >
+            Show cached answers
+          </button>
+          <button
+            onClick={handleClear}
+            className="px-3 py-1 bg-blue-500 text-white rounded hover:bg-blue-600"
+          >
+            Clear cache
+          </button>
+        </div>
+        {entries && entries.length === 0 && <div>No cached answers</div>}
+        {entries?.map((entry) => (
+          <div key={entry.query} title={entry.response}>
+            {KIND_LABELS[entry.kind]} "{entry.query}" · {entry.hits} hits · until{" "}
+            {new Date(entry.expires_at * 1000).toLocaleString()}
+          </div>
+        ))}
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default ResponseCacheSettings;
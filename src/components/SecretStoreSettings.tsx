@@ -0,0 +1,60 @@
+import React, { useEffect, useState } from "react";
+import { SecretBackend, SecretStoreStatus, getSecretStore, setSecretBackend } from "../services/secrets";
+
+const BACKEND_LABELS: Record<SecretBackend, string> = {
+  auto: "System keychain, encrypted file if there is none",
+  keychain: "System keychain only",
+  file: "Encrypted file only",
+};
+
+const SecretStoreSettings: React.FC = () => {
+  const [status, setStatus] = useState<SecretStoreStatus | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getSecretStore()
+      .then(setStatus)
+      .catch((error) => console.error("Failed to load secret store:", error));
+  }, []);
+
+  if (!status) return null;
+
+  const save = async (backend: SecretBackend) => {
+    setStatus({ ...status, backend });
+    try {
+      setMessage(`✅ ${await setSecretBackend(backend)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+      setStatus(await getSecretStore());
+    }
+  };
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔐 Key Storage</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <select
+          value={status.backend}
+          onChange={(e) => save(e.target.value as SecretBackend)}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        >
+          {(Object.keys(BACKEND_LABELS) as SecretBackend[]).map((backend) => (
+            <option key={backend} value={backend}>
+              {BACKEND_LABELS[backend]}
+            </option>
+          ))}
+        </select>
+        {!status.keychain_available && (
+          <div>⚠️ No system keychain found (is a Secret Service like GNOME Keyring running?)</div>
+        )}
+        <div className="text-gray-500 dark:text-gray-400">
+          The file is encrypted with a key tied to this machine and user. Keys already saved stay where they
+          are until saved again.
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default SecretStoreSettings;
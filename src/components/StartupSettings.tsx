@@ -0,0 +1,62 @@
+import React, { useEffect, useState } from "react";
+import { AutostartStatus, getAutostart, setAutostart } from "../services/autostart";
+
+const StartupSettings: React.FC = () => {
+  const [status, setStatus] = useState<AutostartStatus | null>(null);
+
+  useEffect(() => {
+    getAutostart()
+      .then(setStatus)
+      .catch((error) => console.error("Failed to load autostart settings:", error));
+  }, []);
+
+  const update = async (change: Partial<AutostartStatus>) => {
+    if (!status) return;
+    const next = { ...status, ...change };
+    setStatus(next);
+    try {
+      await setAutostart(next.enabled, next.start_minimized, next.listen_on_start);
+    } catch (error) {
+      console.error("Failed to update autostart:", error);
+      setStatus(status);
+    }
+  };
+
+  if (!status) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🚀 Startup</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={status.enabled}
+            onChange={(e) => update({ enabled: e.target.checked })}
+          />
+          Start Eva when I log in
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          <input
+            type="checkbox"
+            checked={status.start_minimized}
+            disabled={!status.enabled}
+            onChange={(e) => update({ start_minimized: e.target.checked })}
+          />
+          Stay in the tray
+        </label>
+        <label className="flex items-center gap-1 pl-4">
+          <input
+            type="checkbox"
+            checked={status.listen_on_start}
+            disabled={!status.enabled}
+            onChange={(e) => update({ listen_on_start: e.target.checked })}
+          />
+          Start listening for the wake word
+        </label>
+      </div>
+    </div>
+  );
+};
+
+export default StartupSettings;
@@ -0,0 +1,90 @@
+import React, { useEffect, useState } from "react";
+import {
+  getTurnDetection,
+  setTurnDetection,
+  TurnDetectionMode,
+  TurnDetectionSettings as Settings,
+} from "../services/turn-detection";
+
+const TurnDetectionSettings: React.FC = () => {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getTurnDetection()
+      .then(setSettings)
+      .catch((error) => console.error("Failed to load turn detection:", error));
+  }, []);
+
+  if (!settings) return null;
+
+  const save = async (next: Settings) => {
+    setSettings(next);
+    try {
+      setMessage(`✅ ${await setTurnDetection(next)}`);
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+      setSettings(await getTurnDetection());
+    }
+  };
+
+  const numberInput = (key: "prefix_padding_ms" | "silence_duration_ms", min: number, max: number) => (
+    <input
+      type="number"
+      min={min}
+      max={max}
+      step={50}
+      value={settings[key]}
+      onChange={(e) => setSettings({ ...settings, [key]: Number(e.target.value) })}
+      onBlur={() => save(settings)}
+      className="w-20 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+    />
+  );
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🗣️ End of Turn</div>
+      <div className="space-y-1 text-xs text-gray-700 dark:text-gray-200">
+        <select
+          value={settings.mode}
+          onChange={(e) => save({ ...settings, mode: e.target.value as TurnDetectionMode })}
+          className="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800"
+        >
+          <option value="server_vad">When I stop talking</option>
+          <option value="manual">When I stop recording</option>
+        </select>
+        {settings.mode === "server_vad" && (
+          <>
+            <label className="flex items-center gap-2">
+              Sensitivity
+              <input
+                type="range"
+                min="0"
+                max="1"
+                step="0.05"
+                value={1 - settings.threshold}
+                onChange={(e) => setSettings({ ...settings, threshold: 1 - parseFloat(e.target.value) })}
+                onMouseUp={() => save(settings)}
+                onKeyUp={() => save(settings)}
+                className="flex-1"
+              />
+            </label>
+            <div className="flex items-center gap-1">
+              Answer after
+              {numberInput("silence_duration_ms", 100, 10000)}
+              ms of silence, keeping
+              {numberInput("prefix_padding_ms", 0, 5000)}
+              ms before speech
+            </div>
+          </>
+        )}
+        <div className="text-gray-500 dark:text-gray-400">
+          A longer silence lets you pause mid-sentence; manual mode waits for push-to-talk or the stop button.
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default TurnDetectionSettings;
@@ -0,0 +1,151 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import {
+  BudgetWarning,
+  UsageStats,
+  getUsageStats,
+  setUsageBudget,
+} from "../services/usage";
+import { setIdleDisconnect } from "../services/session-idle";
+
+const REFRESH_INTERVAL_MS = 30000;
+
+const formatUsd = (value: number) => `$${value.toFixed(value < 1 ? 4 : 2)}`;
+
+const UsagePanel: React.FC = () => {
+  const [stats, setStats] = useState<UsageStats | null>(null);
+  const [budget, setBudget] = useState("");
+  const [warning, setWarning] = useState<BudgetWarning | null>(null);
+  const [idleDisconnect, setIdleDisconnectState] = useState<{
+    enabled: boolean;
+    idle_minutes: number;
+  } | null>(null);
+
+  const refresh = useCallback(() => {
+    getUsageStats()
+      .then((loaded) => {
+        setStats(loaded);
+        setBudget((prev) => prev || (loaded.monthly_budget_usd ? String(loaded.monthly_budget_usd) : ""));
+      })
+      .catch((error) => console.error("Failed to load usage stats:", error));
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    refresh();
+    invoke<{ idle_disconnect: { enabled: boolean; idle_minutes: number } }>("get_settings")
+      .then((settings) => setIdleDisconnectState(settings.idle_disconnect))
+      .catch((error) => console.error("Failed to load idle disconnect settings:", error));
+    const interval = setInterval(refresh, REFRESH_INTERVAL_MS);
+    listen<BudgetWarning>("usage-budget-warning", (event) => {
+      setWarning(event.payload);
+      refresh();
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      clearInterval(interval);
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  const handleSaveBudget = async () => {
+    const value = parseFloat(budget || "0");
+    if (Number.isNaN(value)) return;
+    try {
+      await setUsageBudget(value);
+      setWarning(null);
+      refresh();
+    } catch (error) {
+      console.error("Failed to set usage budget:", error);
+    }
+  };
+
+  const handleIdleDisconnect = async (enabled: boolean, idleMinutes: number) => {
+    if (!Number.isFinite(idleMinutes) || idleMinutes < 1) return;
+    setIdleDisconnectState({ enabled, idle_minutes: idleMinutes });
+    try {
+      await setIdleDisconnect(enabled, idleMinutes);
+    } catch (error) {
+      console.error("Failed to set idle disconnect:", error);
+    }
+  };
+
+  if (!stats) return null;
+
+  const budgetUsed =
+    stats.monthly_budget_usd > 0
+      ? Math.min(100, (stats.month.cost_usd / stats.monthly_budget_usd) * 100)
+      : null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="flex items-center justify-between mb-1">
+        <div className="text-sm text-gray-600 dark:text-gray-300">💸 API Usage</div>
+        <div className="flex items-center gap-1 text-xs">
+          <span className="text-gray-500 dark:text-gray-400">Monthly budget $</span>
+          <input
+            type="number"
+            min="0"
+            step="1"
+            value={budget}
+            onChange={(e) => setBudget(e.target.value)}
+            placeholder="none"
+            className="w-20 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+          />
+          <button
+            onClick={handleSaveBudget}
+            className="px-2 py-1 bg-purple-500 hover:bg-purple-600 text-white rounded"
+          >
+            Set
+          </button>
+        </div>
+      </div>
+
+      <div className="font-semibold text-gray-800 dark:text-white">
+        Today {formatUsd(stats.today.cost_usd)} · This month {formatUsd(stats.month.cost_usd)}
+        {budgetUsed !== null && ` (${budgetUsed.toFixed(0)}% of budget)`}
+      </div>
+      <div className="text-xs text-gray-500 dark:text-gray-400">
+        {stats.month.responses} responses · audio in/out{" "}
+        {stats.month.tokens.audio_input_tokens}/{stats.month.tokens.audio_output_tokens} · text in/out{" "}
+        {stats.month.tokens.text_input_tokens}/{stats.month.tokens.text_output_tokens} tokens
+      </div>
+
+      {idleDisconnect && (
+        <label className="flex items-center gap-1 mt-2 text-xs text-gray-600 dark:text-gray-300">
+          <input
+            type="checkbox"
+            checked={idleDisconnect.enabled}
+            onChange={(e) => handleIdleDisconnect(e.target.checked, idleDisconnect.idle_minutes)}
+          />
+          Disconnect after
+          <input
+            type="number"
+            min="1"
+            value={idleDisconnect.idle_minutes}
+            disabled={!idleDisconnect.enabled}
+            onChange={(e) => handleIdleDisconnect(true, parseInt(e.target.value, 10))}
+            className="w-14 px-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+          />
+          idle minutes (the wake word reconnects)
+        </label>
+      )}
+
+      {warning && (
+        <div className="mt-2 text-sm text-red-600 dark:text-red-400">
+          ⚠️{" "}
+          {warning.level === "exceeded"
+            ? "Monthly budget exceeded"
+            : "Approaching monthly budget"}
+          : {formatUsd(warning.month_cost_usd)} of {formatUsd(warning.monthly_budget_usd)}
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default UsagePanel;
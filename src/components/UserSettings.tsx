@@ -0,0 +1,150 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  UsersState,
+  deleteUser,
+  getUsers,
+  onUserChanged,
+  saveUser,
+  setUserSwitchByVoice,
+  switchUser,
+} from "../services/users";
+
+const UserSettings: React.FC = () => {
+  const [state, setState] = useState<UsersState | null>(null);
+  const [personas, setPersonas] = useState<string[]>([]);
+  const [name, setName] = useState("");
+  const [persona, setPersona] = useState("");
+  const [apiKey, setApiKey] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    setState(await getUsers());
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    load().catch((error) => console.error("Failed to load users:", error));
+    invoke<{ profiles: { name: string }[] }>("get_personas")
+      .then((loaded) => setPersonas(loaded.profiles.map((profile) => profile.name)))
+      .catch((error) => console.error("Failed to load personas:", error));
+    onUserChanged(() => {
+      load().catch((error) => console.error("Failed to reload users:", error));
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      unlisten?.();
+    };
+  }, [load]);
+
+  if (!state) return null;
+
+  const run = async (action: Promise<string>) => {
+    try {
+      setMessage(`✅ ${await action}`);
+      await load();
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const save = async () => {
+    await run(saveUser(name, persona, apiKey || null));
+    setName("");
+    setPersona("");
+    setApiKey(""); // Clear the input for security
+  };
+
+  const remove = (user: string) => {
+    if (window.confirm(`Delete ${user} along with their history, memory and enrolled voice?`)) {
+      run(deleteUser(user));
+    }
+  };
+
+  const inputClass =
+    "w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800";
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">👥 Users</div>
+      <div className="space-y-2 text-xs text-gray-700 dark:text-gray-200">
+        <select value={state.active} onChange={(e) => run(switchUser(e.target.value))} className={inputClass}>
+          <option value="">Default user</option>
+          {state.users.map((user) => (
+            <option key={user.name} value={user.name}>
+              {user.name}
+            </option>
+          ))}
+        </select>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={state.switch_by_voice}
+            onChange={(e) => run(setUserSwitchByVoice(e.target.checked))}
+          />
+          Recognize who says the wake word (each user enrolls their voice under Voice Lock)
+        </label>
+
+        {state.users.map((user) => (
+          <div key={user.name} className="flex items-center gap-2">
+            <span className="flex-1 truncate">
+              {user.name}
+              {user.persona && ` — ${user.persona}`}
+              {user.has_api_key && " 🔑"}
+            </span>
+            <button
+              onClick={() => {
+                setName(user.name);
+                setPersona(user.persona);
+              }}
+              className="text-blue-500 hover:underline"
+            >
+              Edit
+            </button>
+            <button onClick={() => remove(user.name)} className="text-red-500 hover:underline">
+              Delete
+            </button>
+          </div>
+        ))}
+
+        <div className="space-y-1 pt-2 border-t border-gray-200 dark:border-gray-600">
+          <input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name"
+            className={inputClass}
+          />
+          <select value={persona} onChange={(e) => setPersona(e.target.value)} className={inputClass}>
+            <option value="">Keep the current persona</option>
+            {personas.map((option) => (
+              <option key={option} value={option}>
+                {option}
+              </option>
+            ))}
+          </select>
+          <input
+            type="password"
+            value={apiKey}
+            onChange={(e) => setApiKey(e.target.value)}
+            placeholder="Own OpenAI API key (optional, kept in keychain)"
+            className={inputClass}
+          />
+          <button
+            onClick={save}
+            disabled={!name.trim()}
+            className="px-3 py-1 bg-blue-500 disabled:bg-gray-400 text-white rounded hover:bg-blue-600"
+          >
+            Save User
+          </button>
+        </div>
+        {message && <div>{message}</div>}
+      </div>
+    </div>
+  );
+};
+
+export default UserSettings;
@@ -0,0 +1,116 @@
+import React, { useEffect, useState } from "react";
+import {
+  ENROLLMENT_SECONDS,
+  SpeakerRejectedEvent,
+  VoiceProfileStatus,
+  clearVoiceProfile,
+  enrollVoice,
+  getVoiceProfileStatus,
+  setVoiceLock,
+} from "../services/voice-lock";
+
+const VoiceLock: React.FC = () => {
+  const [status, setStatus] = useState<VoiceProfileStatus | null>(null);
+  const [enrolling, setEnrolling] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    getVoiceProfileStatus()
+      .then(setStatus)
+      .catch((error) => console.error("Failed to load voice profile:", error));
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setup = async () => {
+      const { listen } = await import("@tauri-apps/api/event");
+      unlisten = await listen<SpeakerRejectedEvent>("speaker-rejected", (event) => {
+        setMessage(
+          `🔒 Ignored an unrecognized voice (${event.payload.similarity.toFixed(2)} < ${event.payload.threshold.toFixed(2)})`
+        );
+      });
+    };
+
+    setup();
+    return () => unlisten?.();
+  }, []);
+
+  const handleEnroll = async () => {
+    setEnrolling(true);
+    setMessage(`🎙️ Keep talking for ${ENROLLMENT_SECONDS} seconds...`);
+    try {
+      setStatus(await enrollVoice());
+      setMessage("✅ Voice enrolled");
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    } finally {
+      setEnrolling(false);
+    }
+  };
+
+  const handleToggle = async (enabled: boolean) => {
+    try {
+      setMessage(await setVoiceLock(enabled));
+      setStatus((prev) => prev && { ...prev, voice_lock: enabled });
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      setMessage(await clearVoiceProfile());
+      setStatus((prev) => prev && { ...prev, enrolled: false, enrollments: 0, voice_lock: false });
+    } catch (error) {
+      setMessage(`❌ ${error}`);
+    }
+  };
+
+  if (!status) return null;
+
+  return (
+    <div className="bg-gray-50 dark:bg-gray-700 p-3 rounded-lg mb-4">
+      <div className="text-sm text-gray-600 dark:text-gray-300 mb-2">🔒 Voice Lock</div>
+      {!status.available && (
+        <div className="text-xs text-gray-500 dark:text-gray-400 mb-2">
+          Needs a build with the speaker-id feature and a speaker embedding model.
+        </div>
+      )}
+      <div className="flex flex-wrap items-center gap-2 text-xs text-gray-700 dark:text-gray-200">
+        <label className="flex items-center gap-1">
+          <input
+            type="checkbox"
+            checked={status.voice_lock}
+            disabled={!status.available || !status.enrolled}
+            onChange={(e) => handleToggle(e.target.checked)}
+          />
+          Only respond to my voice
+        </label>
+        <button
+          onClick={handleEnroll}
+          disabled={!status.available || enrolling}
+          className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded disabled:opacity-50"
+        >
+          {enrolling ? "🎙️ Recording..." : status.enrolled ? "➕ Add Sample" : "🎙️ Enroll Voice"}
+        </button>
+        {status.enrolled && (
+          <>
+            <span>{status.enrollments} sample{status.enrollments === 1 ? "" : "s"}</span>
+            <button
+              onClick={handleClear}
+              className="px-2 py-1 bg-gray-200 dark:bg-gray-600 hover:bg-gray-300 dark:hover:bg-gray-500 rounded"
+            >
+              🗑️ Forget
+            </button>
+          </>
+        )}
+      </div>
+      {message && (
+        <div className="mt-2 text-xs text-gray-600 dark:text-gray-300">{message}</div>
+      )}
+    </div>
+  );
+};
+
+export default VoiceLock;
@@ -1,9 +1,11 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import ChatWindow from "./components/ChatWindow";
+import { isChatWindow } from "./services/chat-window";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isChatWindow() ? <ChatWindow /> : <App />}
   </React.StrictMode>,
 );
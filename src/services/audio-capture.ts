@@ -1,3 +1,15 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+
+// Mirrors audio::CaptureSource on the Rust side
+export type CaptureSource = 'microphone' | 'system-audio';
+
+// Mirrors audio::SystemAudioChunk on the Rust side
+interface SystemAudioChunk {
+  audio: string; // base64 PCM16 at 24kHz
+  sample_rate: number;
+}
+
 export interface AudioCaptureConfig {
   sampleRate: number;
   channels: number;
@@ -8,10 +20,12 @@ export class AudioCaptureService {
   private mediaRecorder: MediaRecorder | null = null;
   private audioStream: MediaStream | null = null;
   private isRecording = false;
+  private isMuted = false;
   private audioContext: AudioContext | null = null;
   private sourceNode: MediaStreamAudioSourceNode | null = null;
   private audioWorkletNode: AudioWorkletNode | null = null;
   private onAudioData?: (audioData: ArrayBuffer) => void;
+  private unlistenSystemAudio: UnlistenFn | null = null;
 
   private readonly config: AudioCaptureConfig = {
     sampleRate: 24000, // OpenAI Realtime API expects 24kHz
@@ -27,6 +41,15 @@ export class AudioCaptureService {
 
     this.onAudioData = onAudioData;
 
+    const source = await invoke<CaptureSource>('get_capture_source').catch((error) => {
+      console.error('Failed to read capture source, using the microphone:', error);
+      return 'microphone' as CaptureSource;
+    });
+    if (source === 'system-audio') {
+      await this.startSystemAudioCapture();
+      return;
+    }
+
     try {
       // Request microphone access with specific constraints for high quality
       this.audioStream = await navigator.mediaDevices.getUserMedia({
@@ -73,6 +96,31 @@ export class AudioCaptureService {
     }
   }
 
+  // System audio is captured natively (WASAPI loopback) and arrives as 24kHz PCM16 events
+  private async startSystemAudioCapture(): Promise<void> {
+    this.unlistenSystemAudio = await listen<SystemAudioChunk>('system-audio', (event) => {
+      if (this.isMuted) return;
+      const binary = atob(event.payload.audio);
+      const bytes = new Uint8Array(binary.length);
+      for (let i = 0; i < binary.length; i++) {
+        bytes[i] = binary.charCodeAt(i);
+      }
+      this.onAudioData?.(bytes.buffer);
+    });
+
+    try {
+      await invoke('start_system_audio_capture');
+    } catch (error) {
+      this.unlistenSystemAudio();
+      this.unlistenSystemAudio = null;
+      console.error('Failed to start system audio capture:', error);
+      throw error;
+    }
+
+    this.isRecording = true;
+    console.log('🔁 System audio capture started');
+  }
+
   private startRealAudioCapture(analyser: AnalyserNode): void {
     const bufferLength = 2048; // Use analyser.fftSize for proper buffer size
     const audioBuffer = new Float32Array(bufferLength);
@@ -101,8 +149,10 @@ export class AudioCaptureService {
           // Convert to 16-bit PCM format expected by OpenAI
           const pcm16Buffer = this.convertToPCM16(audioBuffer);
           
-          // Send the audio data
-          this.onAudioData?.(pcm16Buffer);
+          // Send the audio data, unless privacy mute is on
+          if (!this.isMuted) {
+            this.onAudioData?.(pcm16Buffer);
+          }
           console.debug(`🎤 Captured ${audioBuffer.length} samples, RMS: ${rms.toFixed(4)}`);
         }
         
@@ -152,6 +202,13 @@ export class AudioCaptureService {
 
     this.isRecording = false;
 
+    // Stop system audio capture if that was the source
+    if (this.unlistenSystemAudio) {
+      this.unlistenSystemAudio();
+      this.unlistenSystemAudio = null;
+      await invoke('stop_system_audio_capture').catch(console.error);
+    }
+
     // Clean up audio worklet node
     if (this.audioWorkletNode) {
       this.audioWorkletNode.disconnect();
@@ -187,6 +244,15 @@ export class AudioCaptureService {
     console.log('🎤 Audio capture stopped');
   }
 
+  // Drop captured audio without tearing down the stream
+  setMuted(muted: boolean): void {
+    this.isMuted = muted;
+  }
+
+  isMutedState(): boolean {
+    return this.isMuted;
+  }
+
   getRecordingState(): boolean {
     return this.isRecording;
   }
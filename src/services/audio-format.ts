@@ -0,0 +1,22 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors audio_format::AudioFormatSettings on the Rust side
+export type RealtimeAudioFormat = "pcm16" | "g711_ulaw" | "g711_alaw";
+
+export interface AudioFormatSettings {
+  input: RealtimeAudioFormat;
+  output: RealtimeAudioFormat;
+}
+
+export function getAudioFormat(): Promise<AudioFormatSettings> {
+  return invoke<AudioFormatSettings>("get_audio_format");
+}
+
+export function setAudioFormat(audioFormat: AudioFormatSettings): Promise<string> {
+  return invoke<string>("set_audio_format", { audioFormat });
+}
+
+export function onAudioFormatChanged(callback: (settings: AudioFormatSettings) => void): Promise<() => void> {
+  return listen<AudioFormatSettings>("audio-format-changed", (event) => callback(event.payload));
+}
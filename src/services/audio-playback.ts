@@ -1,10 +1,25 @@
+import { startDucking, stopDucking } from './ducking';
+import { markLatency } from './latency';
+import { MOUTH_FRAME_RATE, MouthAnalyser, REST_FRAME, emitMouthFrame } from './lip-sync';
+
 export class AudioPlaybackService {
   private audioContext: AudioContext | null = null;
-  private audioQueue: ArrayBuffer[] = [];
+  private audioQueue: { data: ArrayBuffer; sampleRate: number; itemId?: string }[] = [];
   private isPlaying = false;
   private currentTime = 0;
   private readonly sampleRate = 24000; // OpenAI Realtime API uses 24kHz
   private isInitialized = false;
+  // Eva's voice goes through this so its volume is independent of sound cues
+  private voiceGain: GainNode | null = null;
+  private volume = 1;
+  private outputDeviceName: string | null = null;
+  // Lip-sync frames for the avatar, timed against the audio clock
+  private mouth = new MouthAnalyser();
+  private mouthTimers: ReturnType<typeof setTimeout>[] = [];
+  private activeSource: AudioBufferSourceNode | null = null;
+  // The Realtime item being heard, so an interruption can cut it where the user stopped listening.
+  // `startAt` is on the audio clock; `stoppedAtMs` is set once playback is cleared.
+  private playingItem: { id: string; startAt: number; durationMs: number; stoppedAtMs: number | null } | null = null;
 
   private async ensureInitialized(): Promise<void> {
     if (this.isInitialized) return;
@@ -12,6 +27,11 @@ export class AudioPlaybackService {
     try {
       this.audioContext = new AudioContext({ sampleRate: this.sampleRate });
       console.log(`🎵 Audio context created: ${this.audioContext.state}, sampleRate: ${this.audioContext.sampleRate}`);
+
+      this.voiceGain = this.audioContext.createGain();
+      this.voiceGain.gain.value = this.volume;
+      this.voiceGain.connect(this.audioContext.destination);
+      await this.applyOutputDevice();
       
       // Resume audio context on user interaction if needed
       if (this.audioContext.state === 'suspended') {
@@ -34,7 +54,9 @@ export class AudioPlaybackService {
     }
   };
 
-  async queueAudioChunk(base64Audio: string): Promise<void> {
+  // sampleRate defaults to the Realtime API rate; local TTS voices pass their own.
+  // `itemId` names the Realtime item the audio belongs to, for getPlayedAudio.
+  async queueAudioChunk(base64Audio: string, sampleRate: number = this.sampleRate, itemId?: string): Promise<void> {
     console.log(`🎵 Attempting to queue audio chunk (${base64Audio.length} chars)`);
     
     // Ensure initialization
@@ -71,7 +93,7 @@ export class AudioPlaybackService {
       console.log(`✅ Converted to ArrayBuffer: ${arrayBuffer.byteLength} bytes`);
 
       // Add to queue
-      this.audioQueue.push(arrayBuffer);
+      this.audioQueue.push({ data: arrayBuffer, sampleRate, itemId });
       console.log(`📤 Added to queue. Queue length: ${this.audioQueue.length}`);
       
       // Start playing if not already playing
@@ -94,34 +116,42 @@ export class AudioPlaybackService {
 
     this.isPlaying = true;
     this.currentTime = this.audioContext.currentTime;
+    markLatency('playbackStart');
+    startDucking('speaking');
 
     while (this.audioQueue.length > 0) {
-      const audioBuffer = this.audioQueue.shift();
-      if (audioBuffer) {
-        await this.playAudioBuffer(audioBuffer);
+      const chunk = this.audioQueue.shift();
+      if (chunk) {
+        await this.playAudioBuffer(chunk.data, chunk.sampleRate, chunk.itemId);
       }
     }
 
     this.isPlaying = false;
+    this.stopMouth();
+    // The backend waits a moment before restoring, so the next chunk doesn't pump the volume
+    stopDucking('speaking');
   }
 
-  private async playAudioBuffer(arrayBuffer: ArrayBuffer): Promise<void> {
+  private async playAudioBuffer(arrayBuffer: ArrayBuffer, sampleRate: number, itemId?: string): Promise<void> {
     if (!this.audioContext) return;
 
     try {
       console.log(`🎵 Playing audio buffer: ${arrayBuffer.byteLength} bytes`);
       
       // For PCM16 audio from OpenAI, we need to convert it properly
-      const audioBuffer = await this.convertPCM16ToAudioBuffer(arrayBuffer);
+      const audioBuffer = await this.convertPCM16ToAudioBuffer(arrayBuffer, sampleRate);
       console.log(`✅ Converted to AudioBuffer: ${audioBuffer.duration.toFixed(3)}s, ${audioBuffer.numberOfChannels} channels, ${audioBuffer.sampleRate}Hz`);
       
       const source = this.audioContext.createBufferSource();
       source.buffer = audioBuffer;
-      source.connect(this.audioContext.destination);
+      source.connect(this.voiceGain ?? this.audioContext.destination);
       
       // Schedule the audio to play at the current time
       source.start(this.currentTime);
       console.log(`🔊 Audio started at ${this.currentTime.toFixed(3)}s`);
+      this.scheduleMouthFrames(audioBuffer, this.currentTime);
+      this.activeSource = source;
+      this.trackItem(itemId, audioBuffer.duration);
       
       // Update current time for next chunk
       this.currentTime += audioBuffer.duration;
@@ -129,6 +159,9 @@ export class AudioPlaybackService {
       // Wait for the audio to finish
       await new Promise<void>((resolve) => {
         source.onended = () => {
+          if (this.activeSource === source) {
+            this.activeSource = null;
+          }
           console.log('🔇 Audio chunk playback ended');
           resolve();
         };
@@ -139,7 +172,54 @@ export class AudioPlaybackService {
     }
   }
 
-  private async convertPCM16ToAudioBuffer(arrayBuffer: ArrayBuffer): Promise<AudioBuffer> {
+  // Chunks of one item play back to back, so its start and total length give the played offset
+  private trackItem(itemId: string | undefined, durationSecs: number): void {
+    if (!itemId) {
+      this.playingItem = null;
+      return;
+    }
+    if (this.playingItem?.id !== itemId || this.playingItem.stoppedAtMs !== null) {
+      this.playingItem = { id: itemId, startAt: this.currentTime, durationMs: 0, stoppedAtMs: null };
+    }
+    this.playingItem.durationMs += durationSecs * 1000;
+  }
+
+  private playedMs(item: { startAt: number; durationMs: number; stoppedAtMs: number | null }): number {
+    if (item.stoppedAtMs !== null) return item.stoppedAtMs;
+    const elapsedMs = this.audioContext ? (this.audioContext.currentTime - item.startAt) * 1000 : 0;
+    return Math.round(Math.min(item.durationMs, Math.max(0, elapsedMs)));
+  }
+
+  // How much of the latest Realtime item's audio the user heard; each item is reported once
+  takePlayedAudio(): { itemId: string; audioEndMs: number } | null {
+    const item = this.playingItem;
+    if (!item) return null;
+    this.playingItem = null;
+    return { itemId: item.id, audioEndMs: this.playedMs(item) };
+  }
+
+  // Emit `avatar-mouth` frames as the buffer plays, starting at `startAt` on the audio clock
+  private scheduleMouthFrames(audioBuffer: AudioBuffer, startAt: number): void {
+    if (!this.audioContext) return;
+
+    const frames = this.mouth.analyse(audioBuffer.getChannelData(0), audioBuffer.sampleRate);
+    const delayMs = Math.max(0, (startAt - this.audioContext.currentTime) * 1000);
+    this.mouthTimers = this.mouthTimers.concat(
+      frames.map((frame, index) =>
+        setTimeout(() => emitMouthFrame(frame), delayMs + (index * 1000) / MOUTH_FRAME_RATE)
+      )
+    );
+  }
+
+  // Drop pending frames and close the mouth
+  private stopMouth(): void {
+    this.mouthTimers.forEach(clearTimeout);
+    this.mouthTimers = [];
+    this.mouth.reset();
+    emitMouthFrame(REST_FRAME);
+  }
+
+  private async convertPCM16ToAudioBuffer(arrayBuffer: ArrayBuffer, sampleRate: number): Promise<AudioBuffer> {
     if (!this.audioContext) {
       throw new Error('Audio context not initialized');
     }
@@ -161,7 +241,7 @@ export class AudioPlaybackService {
     const audioBuffer = this.audioContext.createBuffer(
       1, // mono
       int16Array.length,
-      this.sampleRate
+      sampleRate
     );
 
     // Convert int16 to float32 and copy to audio buffer
@@ -174,10 +254,83 @@ export class AudioPlaybackService {
     return audioBuffer;
   }
 
+  // Play a short cue right away, alongside whatever is queued
+  async playCue(base64Audio: string, sampleRate: number = this.sampleRate): Promise<void> {
+    await this.ensureInitialized();
+    if (!this.audioContext) return;
+
+    const audioData = atob(base64Audio);
+    const arrayBuffer = new ArrayBuffer(audioData.length);
+    const view = new Uint8Array(arrayBuffer);
+    for (let i = 0; i < audioData.length; i++) {
+      view[i] = audioData.charCodeAt(i);
+    }
+
+    const source = this.audioContext.createBufferSource();
+    source.buffer = await this.convertPCM16ToAudioBuffer(arrayBuffer, sampleRate);
+    source.connect(this.audioContext.destination);
+    source.start();
+  }
+
+  setVolume(volume: number): void {
+    this.volume = volume;
+    if (this.voiceGain) {
+      this.voiceGain.gain.value = volume;
+    }
+  }
+
+  // `name` is a device name from list_output_devices; null follows the system default
+  async setOutputDevice(name: string | null): Promise<void> {
+    this.outputDeviceName = name;
+    if (this.audioContext) {
+      await this.applyOutputDevice();
+    }
+  }
+
+  private async applyOutputDevice(): Promise<void> {
+    const context = this.audioContext as (AudioContext & { setSinkId?: (id: string) => Promise<void> }) | null;
+    if (!context) return;
+
+    if (!context.setSinkId) {
+      if (this.outputDeviceName) {
+        console.warn('⚠️ This webview cannot choose an output device; using the system default');
+      }
+      return;
+    }
+
+    let sinkId = '';
+    if (this.outputDeviceName) {
+      // Backend names come from the OS audio API; browser labels usually match or contain them
+      const name = this.outputDeviceName;
+      const devices = await navigator.mediaDevices.enumerateDevices();
+      const device = devices.find(
+        (d) => d.kind === 'audiooutput' && d.label !== '' && (d.label.includes(name) || name.includes(d.label))
+      );
+      if (device) {
+        sinkId = device.deviceId;
+      } else {
+        console.warn(`⚠️ Output device "${name}" not available to the webview; using the system default`);
+      }
+    }
+
+    try {
+      await context.setSinkId(sinkId);
+      console.log(`🔈 Output device: ${this.outputDeviceName ?? 'system default'}`);
+    } catch (error) {
+      console.error('❌ Failed to set output device:', error);
+    }
+  }
+
   // Clear the queue and stop playback
   clearQueue(): void {
+    if (this.playingItem && this.playingItem.stoppedAtMs === null) {
+      this.playingItem.stoppedAtMs = this.playedMs(this.playingItem);
+    }
     this.audioQueue = [];
+    this.activeSource?.stop();
+    this.activeSource = null;
     this.isPlaying = false;
+    this.stopMouth();
   }
 
   // Check if audio is currently playing
@@ -0,0 +1,21 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors autostart::AutostartStatus on the Rust side
+export interface AutostartStatus {
+  enabled: boolean;
+  launched_at_login: boolean;
+  start_minimized: boolean;
+  listen_on_start: boolean;
+}
+
+export function getAutostart(): Promise<AutostartStatus> {
+  return invoke<AutostartStatus>("get_autostart");
+}
+
+export function setAutostart(
+  enabled: boolean,
+  startMinimized: boolean,
+  listenOnStart: boolean
+): Promise<string> {
+  return invoke<string>("set_autostart", { enabled, startMinimized, listenOnStart });
+}
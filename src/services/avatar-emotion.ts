@@ -0,0 +1,66 @@
+import { emit } from "@tauri-apps/api/event";
+
+// Expressions the desktop character can switch between
+export type Emotion = "neutral" | "happy" | "thinking" | "confused";
+
+// Payload of the `avatar-emotion` event
+export interface EmotionEvent {
+  emotion: Emotion;
+}
+
+// A reply's expression is held this long before the face relaxes again
+const EXPRESSION_HOLD_MS = 6000;
+
+const HAPPY_PATTERNS = [
+  /!\s*$/,
+  /[😀😃😄😁😊🙂😍🥳🎉]/u,
+  /\b(great|glad|awesome|wonderful|congrat\w*|nice|love|happy|haha|yay|perfect|excellent|fantastic)\b/i,
+];
+
+const CONFUSED_PATTERNS = [
+  /[🤔😕😵]/u,
+  /\b(not sure|i don'?t know|no idea|unclear|confus\w*|could you (clarify|repeat)|what do you mean|didn'?t (catch|understand))\b/i,
+];
+
+let resetTimer: ReturnType<typeof setTimeout> | null = null;
+let current: Emotion = "neutral";
+
+// A cheap keyword classifier; it only needs to pick an expression, not read the room
+export function classifyEmotion(text: string): Emotion {
+  const score = (patterns: RegExp[]) => patterns.filter((pattern) => pattern.test(text)).length;
+  const happy = score(HAPPY_PATTERNS);
+  const confused = score(CONFUSED_PATTERNS);
+  if (confused > 0 && confused >= happy) return "confused";
+  if (happy > 0) return "happy";
+  return "neutral";
+}
+
+function send(emotion: Emotion): void {
+  if (emotion === current) return;
+  current = emotion;
+  emit("avatar-emotion", { emotion } satisfies EmotionEvent).catch((error) =>
+    console.error("Failed to emit avatar-emotion:", error)
+  );
+}
+
+// Show an expression; anything but neutral fades back after a while unless `hold` is set
+export function setEmotion(emotion: Emotion, hold = false): void {
+  if (resetTimer) {
+    clearTimeout(resetTimer);
+    resetTimer = null;
+  }
+  send(emotion);
+  if (emotion !== "neutral" && !hold) {
+    resetTimer = setTimeout(() => send("neutral"), EXPRESSION_HOLD_MS);
+  }
+}
+
+// Eva is working on a reply; held until the reply arrives
+export function showThinking(): void {
+  setEmotion("thinking", true);
+}
+
+// Pick the expression for a finished reply
+export function reactToReply(text: string): void {
+  setEmotion(classifyEmotion(text));
+}
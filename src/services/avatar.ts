@@ -0,0 +1,35 @@
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors avatar::AvatarInfo on the Rust side
+export interface AvatarInfo {
+  id: string;
+  name: string;
+  kind: "vrm" | "live2d";
+  entry: string; // path on the avatar protocol, e.g. "my-model/model.vrm"
+  active: boolean;
+}
+
+export function listAvatars(): Promise<AvatarInfo[]> {
+  return invoke<AvatarInfo[]>("list_avatars");
+}
+
+// Without a path the backend shows a file picker
+export function importAvatar(path: string | null = null): Promise<AvatarInfo> {
+  return invoke<AvatarInfo>("import_avatar", { path });
+}
+
+// null goes back to the built-in look
+export function setActiveAvatar(id: string | null): Promise<string> {
+  return invoke<string>("set_active_avatar", { id });
+}
+
+// URL a VRM/Live2D viewer can load the model (and the files next to it) from
+export function avatarUrl(avatar: AvatarInfo): string {
+  return convertFileSrc(avatar.entry, "avatar");
+}
+
+// Called with the new active avatar id; returns the unlisten function
+export function onAvatarChanged(callback: (id: string | null) => void): Promise<() => void> {
+  return listen<string | null>("avatar-changed", (event) => callback(event.payload));
+}
@@ -0,0 +1,201 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import { nextResponseSequence, openaiRealtimeService } from "../openai-simple";
+import {
+  BackendCapabilities,
+  BackendEvent,
+  BackendEventHandler,
+  BackendStatus,
+  LlmBackend,
+} from "./types";
+import { answerLocally, replayLocalResponse } from "./local-response";
+
+interface ChatMessage {
+  role: "system" | "user" | "assistant";
+  content: string;
+}
+
+interface LlmDeltaEvent {
+  request_id: string;
+  delta: string;
+}
+
+interface LlmDoneEvent {
+  request_id: string;
+  text: string;
+  cancelled: boolean;
+}
+
+export interface LlmEndpointSettings {
+  enabled: boolean;
+  base_url: string;
+  model: string;
+  api_key: string;
+}
+
+const MAX_HISTORY_MESSAGES = 40;
+
+// Text-only backend for OpenAI-compatible chat-completions servers such as Ollama or LM Studio.
+// Requests go through the Rust side so local servers don't need CORS configured.
+export class ChatCompletionsBackend implements LlmBackend {
+  readonly id = "chat-completions";
+  readonly displayName = "OpenAI-compatible chat endpoint";
+  readonly capabilities: BackendCapabilities = {
+    audioInput: false,
+    audioOutput: false,
+    serverTurnDetection: false,
+  };
+
+  private history: ChatMessage[] = [];
+  private connected = false;
+  private activeRequestId: string | null = null;
+  private unlisteners: UnlistenFn[] = [];
+  private readonly eventHandlers = new Map<string, BackendEventHandler[]>();
+
+  async connect(): Promise<void> {
+    if (this.connected) return;
+
+    this.unlisteners = await Promise.all([
+      listen<LlmDeltaEvent>("llm-delta", ({ payload }) => {
+        if (payload.request_id !== this.activeRequestId) return;
+        this.emit("response.text.delta", {
+          delta: payload.delta,
+          response_id: payload.request_id,
+          item_id: payload.request_id,
+          sequence: nextResponseSequence(),
+        });
+      }),
+      listen<LlmDoneEvent>("llm-done", ({ payload }) => {
+        if (payload.request_id !== this.activeRequestId) return;
+        this.activeRequestId = null;
+        if (payload.text) {
+          this.history.push({ role: "assistant", content: payload.text });
+        }
+        this.emit("response.text.done", { text: payload.text });
+        this.emit("response.done", {
+          response: {
+            id: payload.request_id,
+            status: payload.cancelled ? "cancelled" : "completed",
+          },
+        });
+      }),
+    ]);
+
+    this.history = [];
+    this.connected = true;
+    this.emit("session.created", { session: { id: this.id } });
+    console.log("✅ Chat endpoint backend ready");
+  }
+
+  async disconnect(): Promise<void> {
+    await this.interrupt();
+    this.unlisteners.forEach((unlisten) => unlisten());
+    this.unlisteners = [];
+    this.connected = false;
+  }
+
+  async sendText(text: string): Promise<void> {
+    // Offline commands and cached answers work even without the endpoint
+    const local = await answerLocally(text);
+    if (local) {
+      if (local.intent === "stop") {
+        await this.interrupt();
+      } else if (this.connected && local.text) {
+        this.history.push({ role: "user", content: text }, { role: "assistant", content: local.text });
+      }
+      replayLocalResponse(local, (type, data) => this.emit(type, data));
+      return;
+    }
+
+    if (!this.connected) {
+      throw new Error("Chat endpoint backend is not connected");
+    }
+
+    this.history.push({ role: "user", content: text });
+    this.history = this.history.slice(-MAX_HISTORY_MESSAGES);
+    await this.complete([this.systemMessage(), ...this.history]);
+  }
+
+  async prompt(instructions: string): Promise<void> {
+    if (!this.connected) {
+      throw new Error("Chat endpoint backend is not connected");
+    }
+
+    // Not kept in history; only the reply is
+    await this.complete([
+      this.systemMessage(),
+      ...this.history,
+      { role: "system", content: instructions },
+    ]);
+  }
+
+  private async complete(messages: ChatMessage[]): Promise<void> {
+    const requestId = crypto.randomUUID();
+    this.activeRequestId = requestId;
+    this.emit("response.created", { response: { id: requestId } });
+
+    try {
+      await invoke<string>("chat_completion", { requestId, messages });
+    } catch (error) {
+      if (this.activeRequestId === requestId) {
+        this.activeRequestId = null;
+      }
+      this.emit("error", {
+        type: "chat_completion_error",
+        message: `Chat endpoint request failed: ${error}`,
+      });
+    }
+  }
+
+  async sendAudio(_pcm16: ArrayBuffer): Promise<void> {
+    // Text only - pair with LocalSttBackend for voice input
+  }
+
+  async commitAudio(): Promise<void> {}
+
+  async interrupt(): Promise<void> {
+    if (!this.activeRequestId) return;
+    await invoke("cancel_chat_completion", { requestId: this.activeRequestId });
+  }
+
+  getStatus(): BackendStatus {
+    return {
+      apiKey: "configured",
+      provider: "openai",
+      connected: this.connected,
+    };
+  }
+
+  // Each reply is its own request; there's no connection to watch
+  getConnectionHealth(): null {
+    return null;
+  }
+
+  on(event: BackendEvent["type"], handler: BackendEventHandler): void {
+    if (!this.eventHandlers.has(event)) {
+      this.eventHandlers.set(event, []);
+    }
+    this.eventHandlers.get(event)!.push(handler);
+  }
+
+  off(event: BackendEvent["type"], handler: BackendEventHandler): void {
+    const handlers = this.eventHandlers.get(event);
+    if (handlers) {
+      const index = handlers.indexOf(handler);
+      if (index > -1) {
+        handlers.splice(index, 1);
+      }
+    }
+  }
+
+  private systemMessage(): ChatMessage {
+    return { role: "system", content: openaiRealtimeService.getInstructions() };
+  }
+
+  private emit(type: BackendEvent["type"], data: any): void {
+    const event: BackendEvent = { type, data };
+    this.eventHandlers.get(type)?.forEach((handler) => handler(event));
+  }
+}
+
+export const chatCompletionsBackend = new ChatCompletionsBackend();
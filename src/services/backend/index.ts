@@ -0,0 +1,71 @@
+import { invoke } from "@tauri-apps/api/core";
+import { chatCompletionsBackend, LlmEndpointSettings } from "./chat-completions";
+import { LocalSttBackend } from "./local-stt";
+import { openaiRealtimeBackend } from "./openai-realtime";
+import { LlmBackend } from "./types";
+
+export * from "./types";
+export type { LlmEndpointSettings } from "./chat-completions";
+
+const ACTIVE_BACKEND_STORAGE_KEY = "llm_backend";
+const DEFAULT_BACKEND_ID = openaiRealtimeBackend.id;
+
+const backends = new Map<string, LlmBackend>();
+
+// Whether local transcripts go to the chat endpoint instead of the Realtime API
+let useChatEndpoint = false;
+
+export function registerBackend(backend: LlmBackend): void {
+  if (backends.has(backend.id)) {
+    console.warn(`LLM backend "${backend.id}" is already registered, replacing it`);
+  }
+  backends.set(backend.id, backend);
+}
+
+export function listBackends(): LlmBackend[] {
+  return Array.from(backends.values());
+}
+
+export function getActiveBackend(): LlmBackend {
+  const id = localStorage.getItem(ACTIVE_BACKEND_STORAGE_KEY) ?? DEFAULT_BACKEND_ID;
+  return backends.get(id) ?? backends.get(DEFAULT_BACKEND_ID)!;
+}
+
+// Callers should disconnect the previous backend before switching
+export function setActiveBackend(id: string): LlmBackend {
+  const backend = backends.get(id);
+  if (!backend) {
+    throw new Error(`Unknown LLM backend: ${id}`);
+  }
+  localStorage.setItem(ACTIVE_BACKEND_STORAGE_KEY, id);
+  console.log(`🔀 Active LLM backend: ${backend.displayName}`);
+  return backend;
+}
+
+// Pick the backend matching the speech-to-text and chat endpoint choices stored in the backend settings
+export async function syncBackendWithSettings(): Promise<LlmBackend> {
+  try {
+    const settings = await invoke<{
+      stt: { backend: "openai-realtime" | "whisper" };
+      llm_endpoint: LlmEndpointSettings;
+    }>("get_settings");
+    useChatEndpoint = settings.llm_endpoint.enabled;
+
+    let id = useChatEndpoint ? chatCompletionsBackend.id : openaiRealtimeBackend.id;
+    if (settings.stt.backend === "whisper") {
+      id = localSttBackend.id;
+    }
+    return setActiveBackend(id);
+  } catch (error) {
+    console.error("Failed to load backend settings:", error);
+    return getActiveBackend();
+  }
+}
+
+const localSttBackend = new LocalSttBackend(() =>
+  useChatEndpoint ? chatCompletionsBackend : openaiRealtimeBackend
+);
+
+registerBackend(openaiRealtimeBackend);
+registerBackend(localSttBackend);
+registerBackend(chatCompletionsBackend);
@@ -0,0 +1,47 @@
+import { handleOfflineIntent } from "../intents";
+import { nextResponseSequence } from "../openai-simple";
+import { lookupCachedResponse } from "../response-cache";
+import { BackendEvent } from "./types";
+
+// An answer that didn't need the model
+export interface LocalResponse {
+  text: string;
+  source: "intent" | "cache";
+  intent?: string; // the offline command's kind
+}
+
+// Offline commands first, then cached answers; null means the model has to answer
+export async function answerLocally(text: string): Promise<LocalResponse | null> {
+  const outcome = await handleOfflineIntent(text);
+  if (outcome) {
+    return { text: outcome.reply, source: "intent", intent: outcome.intent.kind };
+  }
+  const cached = await lookupCachedResponse(text);
+  if (cached) {
+    return { text: cached.response, source: "cache" };
+  }
+  return null;
+}
+
+// Play a local answer through the usual response events, marked with `local` (and `intent`)
+// so the UI speaks it with the local voice and doesn't cache it again
+export function replayLocalResponse(
+  response: LocalResponse,
+  emit: (type: BackendEvent["type"], data: any) => void
+): void {
+  const id = `local-${crypto.randomUUID()}`;
+  const marker = { local: response.source, intent: response.intent };
+  emit("response.created", { response: { id, ...marker } });
+  if (response.text) {
+    emit("response.text.delta", {
+      delta: response.text,
+      response_id: id,
+      item_id: id,
+      sequence: nextResponseSequence(),
+    });
+  }
+  emit("response.text.done", { text: response.text });
+  emit("response.done", {
+    response: { id, status: "completed", ...marker },
+  });
+}
@@ -0,0 +1,140 @@
+import { invoke } from "@tauri-apps/api/core";
+import { ConnectionHealth } from "../openai-simple";
+import {
+  BackendCapabilities,
+  BackendEvent,
+  BackendEventHandler,
+  BackendStatus,
+  LlmBackend,
+} from "./types";
+
+interface Transcript {
+  text: string;
+  audio_duration_ms: number;
+  processing_ms: number;
+}
+
+const CAPTURE_SAMPLE_RATE = 24000; // AudioCaptureService output rate
+
+// Transcribes speech locally with whisper.cpp and hands the text to a text-capable backend
+export class LocalSttBackend implements LlmBackend {
+  readonly id = "whisper-stt";
+  readonly displayName = "Local Whisper + text backend";
+
+  get capabilities(): BackendCapabilities {
+    return {
+      audioInput: true,
+      audioOutput: this.getTextBackend().capabilities.audioOutput,
+      serverTurnDetection: false,
+    };
+  }
+
+  private audioChunks: Int16Array[] = [];
+  private readonly eventHandlers = new Map<string, BackendEventHandler[]>();
+
+  constructor(private readonly getTextBackend: () => LlmBackend) {}
+
+  connect(): Promise<void> {
+    return this.getTextBackend().connect();
+  }
+
+  disconnect(): Promise<void> {
+    this.audioChunks = [];
+    return this.getTextBackend().disconnect();
+  }
+
+  sendText(text: string, spoken?: boolean): Promise<void> {
+    return this.getTextBackend().sendText(text, spoken);
+  }
+
+  prompt(instructions: string): Promise<void> {
+    return this.getTextBackend().prompt(instructions);
+  }
+
+  async sendAudio(pcm16: ArrayBuffer): Promise<void> {
+    this.audioChunks.push(new Int16Array(pcm16));
+  }
+
+  async commitAudio(): Promise<void> {
+    const totalLength = this.audioChunks.reduce((sum, chunk) => sum + chunk.length, 0);
+    if (totalLength === 0) {
+      console.warn("⚠️ No audio captured for local transcription");
+      return;
+    }
+
+    const samples = new Int16Array(totalLength);
+    let offset = 0;
+    for (const chunk of this.audioChunks) {
+      samples.set(chunk, offset);
+      offset += chunk.length;
+    }
+    this.audioChunks = [];
+
+    this.emit("input_audio_buffer.committed", {});
+
+    try {
+      const transcript = await invoke<Transcript>("transcribe_audio", {
+        samples: Array.from(samples),
+        sampleRate: CAPTURE_SAMPLE_RATE,
+      });
+      console.log(
+        `🗣️ Local transcript in ${transcript.processing_ms}ms: "${transcript.text}"`
+      );
+
+      if (!transcript.text) return;
+
+      // Same shape as a Realtime transcription so the UI treats both alike
+      this.emit("conversation.item.created", {
+        item: {
+          type: "message",
+          role: "user",
+          content: [{ type: "input_audio", transcript: transcript.text }],
+        },
+      });
+
+      await this.getTextBackend().sendText(transcript.text);
+    } catch (error) {
+      this.emit("error", {
+        type: "transcription_error",
+        message: `Local transcription failed: ${error}`,
+      });
+    }
+  }
+
+  async interrupt(): Promise<void> {
+    this.audioChunks = [];
+    await this.getTextBackend().interrupt();
+  }
+
+  getStatus(): BackendStatus {
+    return this.getTextBackend().getStatus();
+  }
+
+  getConnectionHealth(): ConnectionHealth | null {
+    return this.getTextBackend().getConnectionHealth();
+  }
+
+  on(event: BackendEvent["type"], handler: BackendEventHandler): void {
+    if (!this.eventHandlers.has(event)) {
+      this.eventHandlers.set(event, []);
+    }
+    this.eventHandlers.get(event)!.push(handler);
+    this.getTextBackend().on(event, handler);
+  }
+
+  off(event: BackendEvent["type"], handler: BackendEventHandler): void {
+    const handlers = this.eventHandlers.get(event);
+    if (handlers) {
+      const index = handlers.indexOf(handler);
+      if (index > -1) {
+        handlers.splice(index, 1);
+      }
+    }
+    this.getTextBackend().off(event, handler);
+  }
+
+  private emit(type: BackendEvent["type"], data: any): void {
+    const event: BackendEvent = { type, data };
+    this.eventHandlers.get(type)?.forEach((handler) => handler(event));
+  }
+}
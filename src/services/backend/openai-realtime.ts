@@ -0,0 +1,89 @@
+import { audioPlaybackService } from "../audio-playback";
+import { ConnectionHealth, openaiRealtimeService, OpenAIRealtimeService } from "../openai-simple";
+import {
+  BackendCapabilities,
+  BackendEvent,
+  BackendEventHandler,
+  BackendStatus,
+  LlmBackend,
+} from "./types";
+import { answerLocally, replayLocalResponse } from "./local-response";
+
+export class OpenAIRealtimeBackend implements LlmBackend {
+  readonly id = "openai-realtime";
+  readonly displayName = "OpenAI Realtime";
+  // Turn detection can be switched to manual commits at runtime
+  get capabilities(): BackendCapabilities {
+    return {
+      audioInput: true,
+      audioOutput: true,
+      serverTurnDetection: this.service.usesServerVad(),
+    };
+  }
+
+  constructor(private readonly service: OpenAIRealtimeService) {}
+
+  connect(): Promise<void> {
+    return this.service.connect();
+  }
+
+  disconnect(): Promise<void> {
+    return this.service.disconnect();
+  }
+
+  async sendText(text: string, spoken?: boolean): Promise<void> {
+    // Offline commands and cached answers skip the API; the session won't see that exchange
+    const local = await answerLocally(text);
+    if (local) {
+      if (local.intent === "stop") {
+        await this.interrupt();
+      }
+      replayLocalResponse(local, (type, data) => this.service.dispatchLocalEvent(type, data));
+      return;
+    }
+    return this.service.sendMessage(text, spoken);
+  }
+
+  prompt(instructions: string): Promise<void> {
+    return this.service.createResponse(instructions);
+  }
+
+  sendAudio(pcm16: ArrayBuffer): Promise<void> {
+    return this.service.sendAudioData(pcm16);
+  }
+
+  async commitAudio(): Promise<void> {
+    await this.service.commitAudioBuffer();
+    await this.service.createResponse();
+  }
+
+  // Stop the response and cut Eva's reply where playback stopped, so the session
+  // doesn't remember her saying things the user never heard
+  async interrupt(): Promise<void> {
+    const played = audioPlaybackService.takePlayedAudio();
+    await this.service.cancelResponse();
+    if (played) {
+      await this.service.truncateItem(played.itemId, played.audioEndMs);
+    }
+  }
+
+  getStatus(): BackendStatus {
+    return this.service.getStatus();
+  }
+
+  getConnectionHealth(): ConnectionHealth {
+    return this.service.getConnectionHealth();
+  }
+
+  on(event: BackendEvent["type"], handler: BackendEventHandler): void {
+    this.service.on(event, handler);
+  }
+
+  off(event: BackendEvent["type"], handler: BackendEventHandler): void {
+    this.service.off(event, handler);
+  }
+}
+
+export const openaiRealtimeBackend = new OpenAIRealtimeBackend(
+  openaiRealtimeService
+);
@@ -0,0 +1,35 @@
+import { ConnectionHealth, OpenAIEvent, RealtimeStatus } from "../openai-simple";
+
+// Backends re-emit provider events in the Realtime API event shape so the UI stays provider-agnostic
+export type BackendEvent = OpenAIEvent;
+export type BackendEventHandler = (event: BackendEvent) => void;
+export type BackendStatus = RealtimeStatus;
+
+export interface BackendCapabilities {
+  audioInput: boolean; // accepts streamed PCM16 microphone audio
+  audioOutput: boolean; // produces response.audio.delta events
+  serverTurnDetection: boolean; // ends audio turns itself; otherwise callers must commitAudio()
+}
+
+export interface LlmBackend {
+  readonly id: string;
+  readonly displayName: string;
+  readonly capabilities: BackendCapabilities;
+
+  connect(): Promise<void>;
+  disconnect(): Promise<void>;
+  // `spoken` asks for a voice reply even when the session is text-only; defaults to the session's mode
+  sendText(text: string, spoken?: boolean): Promise<void>;
+  // Respond without a user turn, steered by `instructions` (proactive greetings and check-ins)
+  prompt(instructions: string): Promise<void>;
+  sendAudio(pcm16: ArrayBuffer): Promise<void>;
+  // End the user's audio turn and ask for a response
+  commitAudio(): Promise<void>;
+  interrupt(): Promise<void>;
+  getStatus(): BackendStatus;
+  // Heartbeat and latency of a live connection; null for request/response backends
+  getConnectionHealth(): ConnectionHealth | null;
+
+  on(event: BackendEvent["type"], handler: BackendEventHandler): void;
+  off(event: BackendEvent["type"], handler: BackendEventHandler): void;
+}
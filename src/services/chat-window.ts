@@ -0,0 +1,60 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors chat_window::ChatTurn on the Rust side
+export interface ChatTurn {
+  role: "user" | "assistant";
+  text: string;
+  timestamp: number;
+}
+
+// Payload of the `windows-changed` event
+export interface OpenWindows {
+  main: boolean;
+  chat: boolean;
+}
+
+// The window this page was opened as; the chat window is loaded with ?window=chat
+export function isChatWindow(): boolean {
+  return new URLSearchParams(window.location.search).get("window") === "chat";
+}
+
+export function getOpenWindows(): Promise<OpenWindows> {
+  return invoke<OpenWindows>("get_open_windows");
+}
+
+export function openChatWindow(): Promise<string> {
+  return invoke<string>("open_chat_window");
+}
+
+export function closeChatWindow(): Promise<string> {
+  return invoke<string>("close_chat_window");
+}
+
+export function onWindowsChanged(callback: (windows: OpenWindows) => void): Promise<() => void> {
+  return listen<OpenWindows>("windows-changed", (event) => callback(event.payload));
+}
+
+export function getChatHistory(): Promise<ChatTurn[]> {
+  return invoke<ChatTurn[]>("get_chat_history");
+}
+
+// Called by the main window; the chat window hears it as `chat-turn`
+export function publishChatTurn(role: ChatTurn["role"], text: string): void {
+  if (!text.trim()) return;
+  invoke("publish_chat_turn", { role, text }).catch((error) =>
+    console.error("Failed to publish chat turn:", error)
+  );
+}
+
+export function clearChatHistory(): void {
+  invoke("clear_chat_history").catch((error) => console.error("Failed to clear chat history:", error));
+}
+
+export function onChatTurn(callback: (turn: ChatTurn) => void): Promise<() => void> {
+  return listen<ChatTurn>("chat-turn", (event) => callback(event.payload));
+}
+
+export function onChatHistoryCleared(callback: () => void): Promise<() => void> {
+  return listen("chat-history-cleared", () => callback());
+}
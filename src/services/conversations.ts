@@ -0,0 +1,129 @@
+import { invoke } from "@tauri-apps/api/core";
+import { audioPlaybackService } from "./audio-playback";
+
+// Mirrors conversations::ConversationStart / ConversationSummary on the Rust side
+interface ConversationStart {
+  session_id: string;
+  save_audio: boolean;
+  save_replies: boolean;
+}
+
+export interface ConversationSummary {
+  session_id: string;
+  started_at: number;
+  turns: number;
+  has_audio: boolean;
+  has_replies: boolean;
+}
+
+export interface ReplyRecording {
+  reply_id: string;
+  text: string;
+  timestamp: number;
+}
+
+interface ReplyAudio {
+  audio: string; // base64 PCM16
+  sample_rate: number;
+  duration_ms: number;
+}
+
+export type ExportFormat = "markdown" | "json";
+export type Speaker = "user" | "assistant";
+
+let current: ConversationStart | null = null;
+
+function toBase64(buffer: ArrayBuffer): string {
+  const bytes = new Uint8Array(buffer);
+  let binary = "";
+  const chunkSize = 8192;
+  for (let i = 0; i < bytes.length; i += chunkSize) {
+    binary += String.fromCharCode.apply(null, Array.from(bytes.subarray(i, i + chunkSize)));
+  }
+  return btoa(binary);
+}
+
+// Start archiving a session; returns its id, or null when archiving is disabled
+export async function startConversation(): Promise<string | null> {
+  try {
+    current = await invoke<ConversationStart | null>("start_conversation");
+  } catch (error) {
+    console.error("Failed to start conversation archive:", error);
+    current = null;
+  }
+  return current?.session_id ?? null;
+}
+
+export function currentConversationId(): string | null {
+  return current?.session_id ?? null;
+}
+
+// `replyId` links an assistant turn to its recording, see recordReplyAudio
+export function recordTurn(role: Speaker, text: string, replyId?: string): void {
+  if (!current || !text.trim()) return;
+  invoke("append_conversation_turn", {
+    sessionId: current.session_id,
+    role,
+    text,
+    replyId: current.save_replies ? replyId ?? null : null,
+  }).catch((error) => console.error("Failed to archive turn:", error));
+}
+
+// PCM16 at 24kHz, as an ArrayBuffer from capture or base64 from a response delta
+export function recordAudio(speaker: Speaker, audio: ArrayBuffer | string): void {
+  if (!current?.save_audio) return;
+  invoke("append_conversation_audio", {
+    sessionId: current.session_id,
+    speaker,
+    audio: typeof audio === "string" ? audio : toBase64(audio),
+  }).catch((error) => console.error("Failed to archive audio:", error));
+}
+
+// One reply's speech, kept as its own recording; PCM16 at 24kHz unless `sampleRate` says otherwise
+export function recordReplyAudio(replyId: string, audio: string, sampleRate?: number): void {
+  if (!current?.save_replies) return;
+  invoke("append_reply_audio", {
+    sessionId: current.session_id,
+    replyId,
+    audio,
+    sampleRate: sampleRate ?? null,
+  }).catch((error) => console.error("Failed to save reply audio:", error));
+}
+
+export function listConversationReplies(sessionId: string): Promise<ReplyRecording[]> {
+  return invoke<ReplyRecording[]>("list_conversation_replies", { sessionId });
+}
+
+// Replay a saved reply through the playback service
+export async function replayReply(sessionId: string, replyId: string): Promise<void> {
+  const reply = await invoke<ReplyAudio>("read_reply_audio", { sessionId, replyId });
+  await audioPlaybackService.queueAudioChunk(reply.audio, reply.sample_rate);
+}
+
+export function listConversations(): Promise<ConversationSummary[]> {
+  return invoke<ConversationSummary[]>("list_conversations");
+}
+
+// Prompts for a folder and returns the zip path
+export function exportConversation(
+  sessionId: string,
+  format: ExportFormat,
+  includeAudio: boolean
+): Promise<string> {
+  return invoke<string>("export_conversation", { sessionId, format, includeAudio });
+}
+
+export function setConversationArchive(
+  saveTranscripts: boolean,
+  saveAudio: boolean
+): Promise<string> {
+  return invoke<string>("set_conversation_archive", { saveTranscripts, saveAudio });
+}
+
+export function setReplyRecording(
+  saveReplies: boolean,
+  retentionDays: number,
+  maxRecordings: number
+): Promise<string> {
+  return invoke<string>("set_reply_recording", { saveReplies, retentionDays, maxRecordings });
+}
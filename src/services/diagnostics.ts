@@ -0,0 +1,42 @@
+import { invoke } from "@tauri-apps/api/core";
+import { OPENAI_REALTIME_MODEL, openaiRealtimeService } from "./openai-simple";
+
+// Mirrors diagnostics::DiagnosticsReport on the Rust side
+export type CheckStatus = "pass" | "warn" | "fail" | "skipped";
+
+export interface DiagnosticCheck {
+  id:
+    | "picovoice-access-key"
+    | "microphone-permission"
+    | "input-devices"
+    | "porcupine-init"
+    | "openai-auth";
+  status: CheckStatus;
+  detail: string;
+  recovery_hint: string | null;
+}
+
+export interface DiagnosticsReport {
+  ok: boolean;
+  checks: DiagnosticCheck[];
+}
+
+// Mirrors diagnostics::OpenAiKeyValidation on the Rust side
+export interface OpenAiKeyValidation {
+  status: "valid" | "invalid-key" | "model-unavailable" | "quota-exceeded" | "unreachable" | "unexpected";
+  model: string;
+  detail: string;
+  recovery_hint: string | null;
+}
+
+// Checks a key against the Realtime model before it is saved
+export function validateOpenAIKey(key: string): Promise<OpenAiKeyValidation> {
+  return invoke<OpenAiKeyValidation>("validate_openai_key", { key, model: OPENAI_REALTIME_MODEL });
+}
+
+// The OpenAI key only exists in the frontend, so it's handed over for the auth check
+export function runDiagnostics(): Promise<DiagnosticsReport> {
+  return invoke<DiagnosticsReport>("run_diagnostics", {
+    openaiApiKey: openaiRealtimeService.getOpenAIApiKey(),
+  });
+}
@@ -0,0 +1,41 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors dictation::DictationStatus / DictationOutcome on the Rust side
+export interface DictationStatus {
+  active: boolean;
+  hotkey: string;
+  stop_phrase: string;
+}
+
+export interface DictationOutcome {
+  typed: string;
+  stopped: boolean; // the stop phrase ended dictation
+}
+
+export function getDictation(): Promise<DictationStatus> {
+  return invoke<DictationStatus>("get_dictation");
+}
+
+export function startDictation(): Promise<string> {
+  return invoke<string>("start_dictation");
+}
+
+export function stopDictation(): Promise<string> {
+  return invoke<string>("stop_dictation");
+}
+
+// An empty hotkey leaves dictation to the button and the stop phrase
+export function setDictation(hotkey: string, stopPhrase: string): Promise<string> {
+  return invoke<string>("set_dictation", { hotkey, stopPhrase });
+}
+
+// Type one transcript into the focused app
+export function dictate(text: string): Promise<DictationOutcome> {
+  return invoke<DictationOutcome>("dictate", { text });
+}
+
+// Follow dictation starting and stopping (hotkey, stop phrase or buttons); returns the unlisten function
+export function onDictationChanged(handler: (active: boolean) => void): Promise<() => void> {
+  return listen<{ active: boolean }>("dictation-changed", (event) => handler(event.payload.active));
+}
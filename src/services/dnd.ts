@@ -0,0 +1,37 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+export type DndReason = "focus" | "fullscreen";
+
+// Payload of the `dnd-state` event
+export interface DndState {
+  active: boolean;
+  reason: DndReason | null;
+}
+
+// Mirrors dnd::DndSettings on the Rust side
+export interface DndBehavior {
+  detect_focus: boolean;
+  detect_fullscreen: boolean;
+  suppress_proactive: boolean;
+  suppress_cues: boolean;
+  suppress_wake: boolean;
+  poll_interval_secs: number;
+}
+
+export function getDndState(): Promise<DndState> {
+  return invoke<DndState>("get_dnd_state");
+}
+
+export async function getDndBehavior(): Promise<DndBehavior> {
+  const settings = await invoke<{ dnd: DndBehavior }>("get_settings");
+  return settings.dnd;
+}
+
+export function setDndBehavior(behavior: DndBehavior): Promise<string> {
+  return invoke<string>("set_dnd_behavior", { behavior });
+}
+
+export function onDndState(callback: (state: DndState) => void): Promise<() => void> {
+  return listen<DndState>("dnd-state", (event) => callback(event.payload));
+}
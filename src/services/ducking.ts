@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors ducking::DuckReason / DuckingSettings on the Rust side
+export type DuckReason = "listening" | "speaking";
+
+export interface DuckingSettings {
+  enabled: boolean;
+  level: number;
+}
+
+// No-ops in the backend when ducking is disabled
+export function startDucking(reason: DuckReason): void {
+  invoke<boolean>("start_ducking", { reason }).catch((error) =>
+    console.error("Failed to duck audio:", error)
+  );
+}
+
+export function stopDucking(reason: DuckReason): void {
+  invoke<boolean>("stop_ducking", { reason }).catch((error) =>
+    console.error("Failed to restore ducked audio:", error)
+  );
+}
+
+export function setDucking(enabled: boolean, level: number): Promise<string> {
+  return invoke<string>("set_ducking", { enabled, level });
+}
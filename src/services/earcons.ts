@@ -0,0 +1,48 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { audioPlaybackService } from "./audio-playback";
+
+// Mirrors earcons::EarconCue / EarconSettings on the Rust side
+export type EarconCue = "listening-start" | "acknowledgment" | "error" | "alarm";
+
+export interface CueSettings {
+  enabled: boolean;
+  volume: number;
+}
+
+export interface EarconSettings {
+  listening_start: CueSettings;
+  acknowledgment: CueSettings;
+  error: CueSettings;
+  alarm: CueSettings;
+}
+
+interface EarconEvent {
+  cue: EarconCue;
+  audio: string; // base64 PCM16
+  sample_rate: number;
+  duration_ms: number;
+}
+
+// Play cues the backend renders; returns the unlisten function
+export function startEarcons(): Promise<() => void> {
+  return listen<EarconEvent>("earcon", (event) => {
+    audioPlaybackService
+      .playCue(event.payload.audio, event.payload.sample_rate)
+      .catch((error) => console.error("Failed to play earcon:", error));
+  });
+}
+
+// For transitions only the frontend sees; disabled cues are skipped by the backend
+export function playEarcon(cue: EarconCue): void {
+  invoke("play_earcon", { cue }).catch((error) =>
+    console.error("Failed to play earcon:", error)
+  );
+}
+
+export function setEarcon(
+  cue: EarconCue,
+  options: { enabled?: boolean; volume?: number }
+): Promise<string> {
+  return invoke<string>("set_earcon", { cue, ...options });
+}
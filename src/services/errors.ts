@@ -0,0 +1,52 @@
+import { invoke } from "@tauri-apps/api/core";
+import { OpenAIEvent } from "./openai-simple";
+
+// Mirrors error::EvaError's serialized form; also the payload of `eva-error` events
+export interface EvaError {
+  code: string;
+  message: string;
+  recovery_hint: string | null;
+}
+
+type RealtimeErrorKind = "connection" | "authentication" | "quota-exceeded" | "session";
+
+// Errors raised by local backends rather than the Realtime API
+const LOCAL_ERROR_TYPES = ["transcription_error", "chat_completion_error"];
+
+export function isEvaError(error: unknown): error is EvaError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    "message" in error
+  );
+}
+
+// Human-readable text for anything a command can reject with
+export function describeError(error: unknown): string {
+  if (isEvaError(error)) {
+    return error.recovery_hint
+      ? `${error.message} (${error.recovery_hint})`
+      : error.message;
+  }
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
+
+function realtimeErrorKind(event: OpenAIEvent): RealtimeErrorKind {
+  const type = event.data?.type;
+  if (type === "connection_error" || type === "reconnect_failed") return "connection";
+  if (type === "quota_exceeded") return "quota-exceeded";
+  if (event.data?.error?.code === "invalid_api_key") return "authentication";
+  return "session";
+}
+
+// Forward a Realtime `error` event to the backend so it goes out on the shared `eva-error` channel
+export function reportRealtimeError(event: OpenAIEvent): void {
+  if (LOCAL_ERROR_TYPES.includes(event.data?.type)) return;
+
+  const message = event.data?.message || event.data?.error?.message || "Unknown error";
+  invoke("report_realtime_error", {
+    error: { kind: realtimeErrorKind(event), message },
+  }).catch((error) => console.error("Failed to report realtime error:", error));
+}
@@ -0,0 +1,65 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { InputDevice } from "./input-devices";
+import { RealtimeStatus } from "./openai-simple";
+
+// Mirrors status::EvaStatus on the Rust side; fields may be added but never removed
+export type WakeWordState = "listening" | "stopped" | "paused-for-call" | "paused-for-quiet-hours";
+export type EvaPhase = "idle" | "waiting-for-wake-word" | "conversing" | "suspended-for-call";
+
+export interface EvaStatus {
+  assistant_name: string;
+  phase: EvaPhase;
+  wake_word: WakeWordState;
+  mic_muted: boolean;
+  openai: {
+    connected: boolean;
+    reconnecting: boolean;
+    provider: "openai" | "azure" | null;
+    session_id: string | null;
+  };
+  audio: {
+    input_device: string | null;
+    input_sample_rate: number | null;
+  };
+}
+
+// Mirrors status::StateSnapshot on the Rust side
+export interface StateSnapshot {
+  status: EvaStatus;
+  input_devices: InputDevice[];
+  settings_digest: string;
+}
+
+export function getEvaStatus(): Promise<EvaStatus> {
+  return invoke<EvaStatus>("eva_status");
+}
+
+// Follow snapshots and ask for one now, since the one sent on page load may have arrived
+// before this listener; returns the unlisten function
+export async function onStateSnapshot(handler: (snapshot: StateSnapshot) => void): Promise<() => void> {
+  const unlisten = await listen<StateSnapshot>("eva-state-snapshot", (event) => handler(event.payload));
+  await invoke("request_state_sync").catch((error) =>
+    console.error("Failed to request state sync:", error)
+  );
+  return unlisten;
+}
+
+let lastReported = "";
+
+// Share the frontend-owned connection state with the backend; unchanged states are skipped
+export function reportRealtimeStatus(status: RealtimeStatus): void {
+  const payload = {
+    connected: status.connected,
+    reconnecting: status.reconnecting ?? false,
+    provider: status.provider,
+    session_id: status.sessionId ?? null,
+  };
+  const serialized = JSON.stringify(payload);
+  if (serialized === lastReported) return;
+  lastReported = serialized;
+
+  invoke("report_realtime_status", { status: payload }).catch((error) =>
+    console.error("Failed to report realtime status:", error)
+  );
+}
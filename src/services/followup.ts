@@ -0,0 +1,29 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors followup::FollowupCloseReason on the Rust side
+export type FollowupCloseReason = "timeout" | "speech" | "cancelled";
+
+export interface FollowupOpenEvent {
+  duration_ms: number;
+}
+
+export interface FollowupClosedEvent {
+  reason: FollowupCloseReason;
+}
+
+// Keep listening after a response; the backend emits followup-window-closed on timeout
+export function openFollowupWindow(): void {
+  invoke<boolean>("open_followup_window").catch((error) =>
+    console.error("Failed to open follow-up window:", error)
+  );
+}
+
+export function closeFollowupWindow(reason: FollowupCloseReason): void {
+  invoke<boolean>("close_followup_window", { reason }).catch((error) =>
+    console.error("Failed to close follow-up window:", error)
+  );
+}
+
+export function setFollowupWindow(enabled: boolean, windowSecs: number): Promise<string> {
+  return invoke<string>("set_followup_window", { enabled, windowSecs });
+}
@@ -0,0 +1,57 @@
+import { describe, expect, it } from "vitest";
+import { G711Law, decodeG711, encodeG711 } from "./g711";
+
+const LAWS: G711Law[] = ["ulaw", "alaw"];
+
+// One 8kHz sample's worth of 24kHz audio, all at `value`
+function held(value: number): Int16Array {
+  return Int16Array.of(value, value, value);
+}
+
+describe("G.711", () => {
+  it("encodes silence and full scale to the standard bytes", () => {
+    expect(encodeG711(held(0), "ulaw")).toEqual(Uint8Array.of(0xff));
+    expect(encodeG711(held(0), "alaw")).toEqual(Uint8Array.of(0xd5));
+    expect(encodeG711(held(32767), "ulaw")).toEqual(Uint8Array.of(0x80));
+    expect(encodeG711(held(-32768), "ulaw")).toEqual(Uint8Array.of(0x00));
+    expect(encodeG711(held(32767), "alaw")).toEqual(Uint8Array.of(0xaa));
+    expect(encodeG711(held(-32768), "alaw")).toEqual(Uint8Array.of(0x2a));
+  });
+
+  it("decodes the standard bytes", () => {
+    expect(decodeG711(Uint8Array.of(0xff), "ulaw")).toEqual(held(0));
+    expect(decodeG711(Uint8Array.of(0x80), "ulaw")).toEqual(held(32124));
+    expect(decodeG711(Uint8Array.of(0x00), "ulaw")).toEqual(held(-32124));
+    expect(decodeG711(Uint8Array.of(0xd5), "alaw")).toEqual(held(8));
+    expect(decodeG711(Uint8Array.of(0xaa), "alaw")).toEqual(held(32256));
+    expect(decodeG711(Uint8Array.of(0x2a), "alaw")).toEqual(held(-32256));
+  });
+
+  it.each(LAWS)("re-encodes every decoded %s byte to itself", (law) => {
+    for (let byte = 0; byte < 256; byte++) {
+      // μ-law has a negative zero, which encodes as positive zero
+      const expected = law === "ulaw" && byte === 0x7f ? 0xff : byte;
+      expect(encodeG711(decodeG711(Uint8Array.of(byte), law), law)[0]).toBe(expected);
+    }
+  });
+
+  it.each(LAWS)("round-trips %s samples within the quantization step", (law) => {
+    for (let value = -32768; value <= 32767; value += 97) {
+      const [decoded] = decodeG711(encodeG711(held(value), law), law);
+      expect(Math.abs(decoded - value)).toBeLessThanOrEqual(Math.max(Math.abs(value), 256) / 25);
+    }
+  });
+
+  it("resamples between 24kHz and 8kHz", () => {
+    // Groups of three are averaged; the trailing partial group is dropped
+    const encoded = encodeG711(Int16Array.of(0, 300, 600, 30000, 30000, 30000, 5), "ulaw");
+    expect(encoded).toEqual(
+      Uint8Array.of(encodeG711(held(300), "ulaw")[0], encodeG711(held(30000), "ulaw")[0])
+    );
+
+    // Decoding interpolates towards the next sample and holds the last one
+    expect(decodeG711(Uint8Array.of(0xff, 0x80), "ulaw")).toEqual(
+      Int16Array.of(0, 10708, 21416, 32124, 32124, 32124)
+    );
+  });
+});
@@ -0,0 +1,79 @@
+// G.711 codecs for the Realtime API's g711_ulaw / g711_alaw formats. The wire carries 8kHz
+// samples while capture and playback stay at 24kHz PCM16, so these also resample by 3.
+
+export type G711Law = "ulaw" | "alaw";
+
+const RESAMPLE_FACTOR = 3; // 24kHz <-> 8kHz
+const ULAW_BIAS = 0x84;
+const ULAW_CLIP = 32635;
+const ALAW_SEGMENT_ENDS = [0x1f, 0x3f, 0x7f, 0xff, 0x1ff, 0x3ff, 0x7ff, 0xfff];
+
+function linearToUlaw(sample: number): number {
+  const sign = sample < 0 ? 0x80 : 0;
+  const magnitude = Math.min(Math.abs(sample), ULAW_CLIP) + ULAW_BIAS;
+  let exponent = 7;
+  for (let mask = 0x4000; (magnitude & mask) === 0 && exponent > 0; mask >>= 1) {
+    exponent--;
+  }
+  const mantissa = (magnitude >> (exponent + 3)) & 0x0f;
+  return ~(sign | (exponent << 4) | mantissa) & 0xff;
+}
+
+function ulawToLinear(byte: number): number {
+  const value = ~byte & 0xff;
+  const exponent = (value >> 4) & 0x07;
+  const magnitude = ((((value & 0x0f) << 3) + ULAW_BIAS) << exponent) - ULAW_BIAS;
+  return value & 0x80 ? -magnitude : magnitude;
+}
+
+function linearToAlaw(sample: number): number {
+  let value = sample >> 3;
+  let mask = 0xd5;
+  if (value < 0) {
+    mask = 0x55;
+    value = -value - 1;
+  }
+
+  const segment = ALAW_SEGMENT_ENDS.findIndex((end) => value <= end);
+  if (segment === -1) return 0x7f ^ mask;
+  const mantissa = segment < 2 ? (value >> 1) & 0x0f : (value >> segment) & 0x0f;
+  return ((segment << 4) | mantissa) ^ mask;
+}
+
+function alawToLinear(byte: number): number {
+  const value = byte ^ 0x55;
+  const segment = (value & 0x70) >> 4;
+  let magnitude = (value & 0x0f) << 4;
+  if (segment === 0) {
+    magnitude += 8;
+  } else {
+    magnitude = (magnitude + 0x108) << (segment - 1);
+  }
+  return value & 0x80 ? magnitude : -magnitude;
+}
+
+// 24kHz PCM16 -> 8kHz G.711; averaging each group of three samples is a rough low-pass
+// against aliasing. A trailing partial group is dropped.
+export function encodeG711(pcm16: Int16Array, law: G711Law): Uint8Array {
+  const encode = law === "ulaw" ? linearToUlaw : linearToAlaw;
+  const encoded = new Uint8Array(Math.floor(pcm16.length / RESAMPLE_FACTOR));
+  for (let i = 0; i < encoded.length; i++) {
+    const offset = i * RESAMPLE_FACTOR;
+    encoded[i] = encode(Math.round((pcm16[offset] + pcm16[offset + 1] + pcm16[offset + 2]) / RESAMPLE_FACTOR));
+  }
+  return encoded;
+}
+
+// 8kHz G.711 -> 24kHz PCM16, interpolating linearly between decoded samples
+export function decodeG711(bytes: Uint8Array, law: G711Law): Int16Array {
+  const decode = law === "ulaw" ? ulawToLinear : alawToLinear;
+  const decoded = Array.from(bytes, decode);
+  const pcm16 = new Int16Array(decoded.length * RESAMPLE_FACTOR);
+  decoded.forEach((sample, i) => {
+    const next = decoded[i + 1] ?? sample;
+    for (let step = 0; step < RESAMPLE_FACTOR; step++) {
+      pcm16[i * RESAMPLE_FACTOR + step] = Math.round(sample + ((next - sample) * step) / RESAMPLE_FACTOR);
+    }
+  });
+  return pcm16;
+}
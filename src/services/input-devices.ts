@@ -0,0 +1,53 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors audio::InputDevice on the Rust side
+export interface InputDevice {
+  id: string;
+  label: string;
+  node_name: string | null;
+  is_default: boolean;
+  is_monitor: boolean;
+}
+
+export interface InputDeviceSettings {
+  input_device: string | null;
+  exclude_monitors: boolean;
+}
+
+export function listInputDevices(): Promise<InputDevice[]> {
+  return invoke<InputDevice[]>("list_input_devices");
+}
+
+export async function getInputDeviceSettings(): Promise<InputDeviceSettings> {
+  const settings = await invoke<{ audio_input: InputDeviceSettings }>("get_settings");
+  return settings.audio_input;
+}
+
+export function setInputDevice(id: string | null): Promise<string> {
+  return invoke<string>("set_input_device", { id });
+}
+
+export function setExcludeMonitors(enabled: boolean): Promise<string> {
+  return invoke<string>("set_exclude_monitors", { enabled });
+}
+
+// Mirrors audio::CaptureHost on the Rust side
+export type CaptureHost = "system" | "asio";
+
+export interface CaptureHostsState {
+  available: CaptureHost[];
+  selected: CaptureHost;
+  prefer_native_rate: boolean;
+}
+
+export function getCaptureHosts(): Promise<CaptureHostsState> {
+  return invoke<CaptureHostsState>("get_capture_hosts");
+}
+
+export function setCaptureHost(host: CaptureHost): Promise<string> {
+  return invoke<string>("set_capture_host", { host });
+}
+
+export function setPreferNativeRate(enabled: boolean): Promise<string> {
+  return invoke<string>("set_prefer_native_rate", { enabled });
+}
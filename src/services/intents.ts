@@ -0,0 +1,35 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors intents::Intent on the Rust side
+export type Intent =
+  | { kind: "stop" }
+  | { kind: "mute" }
+  | { kind: "unmute" }
+  | { kind: "volume-up" }
+  | { kind: "volume-down" }
+  | { kind: "set-volume"; percent: number }
+  | { kind: "timer"; seconds: number };
+
+export interface IntentOutcome {
+  intent: Intent;
+  reply: string; // empty for stop
+}
+
+// Runs `text` as an offline command if it is one; null means ask the model
+export async function handleOfflineIntent(text: string): Promise<IntentOutcome | null> {
+  try {
+    return await invoke<IntentOutcome | null>("handle_offline_intent", { text });
+  } catch (error) {
+    console.error("Offline command matching failed:", error);
+    return null;
+  }
+}
+
+export async function getOfflineIntentsEnabled(): Promise<boolean> {
+  const settings = await invoke<{ intents: { enabled: boolean } }>("get_settings");
+  return settings.intents.enabled;
+}
+
+export function setOfflineIntents(enabled: boolean): Promise<string> {
+  return invoke<string>("set_offline_intents", { enabled });
+}
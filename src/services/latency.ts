@@ -0,0 +1,95 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors latency::TurnLatency on the Rust side
+export interface TurnLatency {
+  wake_to_first_audio_ms: number | null;
+  capture_to_send_ms: number | null;
+  api_first_delta_ms: number | null;
+  playback_start_ms: number | null;
+  backend: string;
+  recorded_at: number;
+}
+
+export interface SpanStats {
+  span: string;
+  count: number;
+  last_ms: number;
+  average_ms: number;
+  p50_ms: number;
+  p95_ms: number;
+}
+
+export interface LatencyMetrics {
+  turns: number;
+  spans: SpanStats[];
+  recent: TurnLatency[];
+}
+
+// Points in a turn, as Date.now() milliseconds so the wake word's Rust timestamp compares directly
+type Mark = "wake" | "request" | "firstDelta" | "firstAudio" | "playbackStart";
+
+let marks: Partial<Record<Mark, number>> = {};
+let sendDelayTotal = 0;
+let sendDelayCount = 0;
+
+// Only the first occurrence of each mark in a turn counts; a turn opens with the wake word
+// or the user's request, so playback of announcements and the like is ignored
+export function markLatency(mark: Mark, at: number = Date.now()): void {
+  const open = marks.wake !== undefined || marks.request !== undefined;
+  if ((open || mark === "request") && marks[mark] === undefined) {
+    marks[mark] = at;
+  }
+}
+
+// The wake word starts a fresh turn
+export function startLatencyTurn(wakeAt: number): void {
+  marks = { wake: wakeAt };
+  sendDelayTotal = 0;
+  sendDelayCount = 0;
+}
+
+// A microphone chunk took `ms` from capture to the backend
+export function recordSendDelay(ms: number): void {
+  // Chunks after the response started belong to the next turn
+  if (marks.firstDelta === undefined) {
+    sendDelayTotal += ms;
+    sendDelayCount++;
+  }
+}
+
+function span(from: Mark, to: Mark): number | null {
+  const start = marks[from];
+  const end = marks[to];
+  return start !== undefined && end !== undefined && end >= start ? end - start : null;
+}
+
+// Report the finished turn; `discard` drops it (interrupted or answered locally)
+export function finishLatencyTurn(backend: string, discard = false): void {
+  const turn: Omit<TurnLatency, "recorded_at"> = {
+    wake_to_first_audio_ms: span("wake", "firstAudio"),
+    capture_to_send_ms: sendDelayCount ? Math.round(sendDelayTotal / sendDelayCount) : null,
+    api_first_delta_ms: span("request", "firstDelta"),
+    playback_start_ms: span("firstAudio", "playbackStart"),
+    backend,
+  };
+  marks = {};
+  sendDelayTotal = 0;
+  sendDelayCount = 0;
+
+  const measured = [
+    turn.wake_to_first_audio_ms,
+    turn.capture_to_send_ms,
+    turn.api_first_delta_ms,
+    turn.playback_start_ms,
+  ].some((ms) => ms !== null);
+  if (discard || !measured) {
+    return;
+  }
+  invoke("record_latency_turn", { turn }).catch((error) =>
+    console.error("Failed to record turn latency:", error)
+  );
+}
+
+export function getLatencyMetrics(): Promise<LatencyMetrics> {
+  return invoke<LatencyMetrics>("get_latency_metrics");
+}
@@ -0,0 +1,72 @@
+import { emit } from "@tauri-apps/api/event";
+
+// Mouth shapes an avatar can map to its own blend shapes or sprites
+export type Viseme = "rest" | "closed" | "open" | "round" | "wide" | "fricative";
+
+// Payload of the `avatar-mouth` event
+export interface MouthFrame {
+  amplitude: number; // 0 (closed) - 1 (wide open), smoothed
+  viseme: Viseme;
+}
+
+export const MOUTH_FRAME_RATE = 30;
+
+// RMS that counts as a fully open mouth; speech rarely peaks much above this
+const FULL_OPEN_RMS = 0.25;
+// Below this the mouth is at rest
+const SILENCE_RMS = 0.01;
+// Opening is quick and closing a little slower, so syllables don't flicker
+const ATTACK = 0.6;
+const RELEASE = 0.3;
+
+export const REST_FRAME: MouthFrame = { amplitude: 0, viseme: "rest" };
+
+// A rough guess from loudness and zero-crossing rate (a cheap brightness measure);
+// good enough to vary the mouth shape, not a phoneme recogniser
+function guessViseme(rms: number, zeroCrossingRate: number): Viseme {
+  if (rms < SILENCE_RMS) return "rest";
+  if (zeroCrossingRate > 0.3) return "fricative";
+  if (rms < SILENCE_RMS * 3) return "closed";
+  if (zeroCrossingRate < 0.06) return "round";
+  if (zeroCrossingRate > 0.15) return "wide";
+  return "open";
+}
+
+// Turns audio into mouth frames; keeps the envelope between chunks so it stays smooth
+export class MouthAnalyser {
+  private amplitude = 0;
+
+  // One frame per 1/MOUTH_FRAME_RATE seconds of `samples`
+  analyse(samples: Float32Array, sampleRate: number): MouthFrame[] {
+    const frameLength = Math.max(1, Math.round(sampleRate / MOUTH_FRAME_RATE));
+    const frames: MouthFrame[] = [];
+
+    for (let start = 0; start < samples.length; start += frameLength) {
+      const end = Math.min(start + frameLength, samples.length);
+      let sumSquares = 0;
+      let crossings = 0;
+      for (let i = start; i < end; i++) {
+        sumSquares += samples[i] * samples[i];
+        if (i > start && (samples[i] >= 0) !== (samples[i - 1] >= 0)) crossings++;
+      }
+      const rms = Math.sqrt(sumSquares / (end - start));
+      const target = Math.min(1, rms / FULL_OPEN_RMS);
+      const rate = target > this.amplitude ? ATTACK : RELEASE;
+      this.amplitude += (target - this.amplitude) * rate;
+
+      frames.push({
+        amplitude: Number(this.amplitude.toFixed(3)),
+        viseme: guessViseme(rms, crossings / (end - start)),
+      });
+    }
+    return frames;
+  }
+
+  reset(): void {
+    this.amplitude = 0;
+  }
+}
+
+export function emitMouthFrame(frame: MouthFrame): void {
+  emit("avatar-mouth", frame).catch((error) => console.error("Failed to emit avatar-mouth:", error));
+}
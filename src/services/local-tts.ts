@@ -0,0 +1,22 @@
+import { invoke } from "@tauri-apps/api/core";
+import { audioPlaybackService } from "./audio-playback";
+import { recordReplyAudio } from "./conversations";
+
+export interface SpeechAudio {
+  audio: string; // base64 PCM16
+  sample_rate: number;
+  duration_ms: number;
+}
+
+// Speak text with the locally configured TTS engine (Piper) through the playback service;
+// `replyId` saves the speech as that reply's recording
+export async function speakLocally(text: string, replyId?: string): Promise<void> {
+  const speech = await invoke<SpeechAudio>("synthesize_speech", { text });
+  console.log(
+    `🗣️ Local TTS produced ${speech.duration_ms}ms of audio at ${speech.sample_rate}Hz`
+  );
+  if (replyId) {
+    recordReplyAudio(replyId, speech.audio, speech.sample_rate);
+  }
+  await audioPlaybackService.queueAudioChunk(speech.audio, speech.sample_rate);
+}
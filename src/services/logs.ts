@@ -0,0 +1,29 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors logging::LogEntry on the Rust side
+export interface LogEntry {
+  timestamp: string;
+  level: "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE";
+  target: string;
+  message: string;
+}
+
+export type LogLevelFilter = "error" | "warn" | "info" | "debug" | "trace";
+
+export function getRecentLogs(
+  lines: number,
+  levelFilter?: LogLevelFilter
+): Promise<LogEntry[]> {
+  return invoke<LogEntry[]>("get_recent_logs", { lines, levelFilter });
+}
+
+export function openLogDirectory(): Promise<string> {
+  return invoke<string>("open_log_directory");
+}
+
+// Plain-text form for pasting into bug reports
+export function formatLogs(entries: LogEntry[]): string {
+  return entries
+    .map((entry) => `${entry.timestamp} ${entry.level} ${entry.target}: ${entry.message}`)
+    .join("\n");
+}
@@ -0,0 +1,100 @@
+import { invoke } from "@tauri-apps/api/core";
+import { openaiRealtimeService } from "./openai-simple";
+import { proxiedFetch } from "./proxy";
+
+export interface ConversationMemory {
+  summary: string;
+  facts: string[];
+  updated_at: number;
+}
+
+export interface TranscriptLine {
+  role: "user" | "assistant";
+  content: string;
+}
+
+const SUMMARY_MODEL = "gpt-4o-mini";
+const CHAT_COMPLETIONS_URL = "https://api.openai.com/v1/chat/completions";
+const MIN_USER_TURNS = 1;
+
+const SUMMARY_PROMPT = `You maintain a long-term memory about the user of a voice assistant.
+Given the previous memory and a new conversation, reply with JSON:
+{"summary": "<2-4 sentence summary of what matters about the user and recent topics>",
+ "facts": ["<new durable facts or preferences about the user, one short sentence each>"]}
+Only include facts useful in future conversations. Never include secrets such as passwords or API keys.`;
+
+// Text block injected into the session instructions
+export function formatMemory(memory: ConversationMemory): string {
+  const lines: string[] = [];
+  if (memory.summary) {
+    lines.push(memory.summary);
+  }
+  memory.facts.forEach((fact) => lines.push(`- ${fact}`));
+  return lines.join("\n");
+}
+
+// Load stored memory into the realtime service so the next session starts with it
+export async function recallMemory(): Promise<ConversationMemory | null> {
+  try {
+    const memory = await invoke<ConversationMemory>("get_memory");
+    openaiRealtimeService.setMemory(formatMemory(memory));
+    return memory;
+  } catch (error) {
+    console.error("Failed to load conversation memory:", error);
+    return null;
+  }
+}
+
+// Summarize a finished conversation and merge it into the stored memory
+export async function rememberConversation(transcript: TranscriptLine[]): Promise<boolean> {
+  if (transcript.filter((line) => line.role === "user").length < MIN_USER_TURNS) {
+    return false;
+  }
+
+  const apiKey = openaiRealtimeService.getOpenAIApiKey();
+  if (!apiKey) {
+    console.warn("🧠 Skipping memory summary: needs an OpenAI API key");
+    return false;
+  }
+
+  const previous = await invoke<ConversationMemory>("get_memory");
+  const conversation = transcript
+    .map((line) => `${line.role === "user" ? "User" : "Assistant"}: ${line.content}`)
+    .join("\n");
+
+  const response = await proxiedFetch(CHAT_COMPLETIONS_URL, {
+    method: "POST",
+    headers: {
+      "Content-Type": "application/json",
+      Authorization: `Bearer ${apiKey}`,
+    },
+    body: JSON.stringify({
+      model: SUMMARY_MODEL,
+      response_format: { type: "json_object" },
+      messages: [
+        { role: "system", content: SUMMARY_PROMPT },
+        {
+          role: "user",
+          content: `Previous memory:\n${formatMemory(previous) || "(none)"}\n\nConversation:\n${conversation}`,
+        },
+      ],
+    }),
+  });
+
+  if (!response.ok) {
+    throw new Error(`Memory summary failed: ${response.status} ${response.statusText}`);
+  }
+
+  const body = await response.json();
+  const result = JSON.parse(body.choices[0].message.content) as {
+    summary?: string;
+    facts?: string[];
+  };
+
+  await invoke<string>("save_memory", {
+    summary: result.summary ?? previous.summary,
+    facts: result.facts ?? [],
+  });
+  await recallMemory();
+  return true;
+}
@@ -0,0 +1,23 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors metrics::MetricsSettings on the Rust side
+export interface MetricsSettings {
+  enabled: boolean;
+  port: number;
+}
+
+export async function getMetricsSettings(): Promise<MetricsSettings> {
+  const settings = await invoke<{ metrics: MetricsSettings }>("get_settings");
+  return settings.metrics;
+}
+
+export function setMetricsEndpoint(enabled: boolean, port: number): Promise<string> {
+  return invoke<string>("set_metrics_endpoint", { enabled, port });
+}
+
+// Counted for the metrics endpoint: every attempt, and the ones that got through
+export function recordRealtimeReconnect(succeeded: boolean): void {
+  invoke("record_realtime_reconnect", { succeeded }).catch((error) =>
+    console.error("Failed to record reconnect:", error)
+  );
+}
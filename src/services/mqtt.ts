@@ -0,0 +1,37 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Speaker } from "./conversations";
+
+// Mirrors integrations::mqtt::MqttSettings on the Rust side
+export interface MqttSettings {
+  enabled: boolean;
+  host: string;
+  port: number;
+  client_id: string;
+  username: string | null;
+  topic_prefix: string;
+}
+
+export interface MqttStatus {
+  enabled: boolean;
+  connected: boolean;
+}
+
+// Published as <prefix>/event/transcript; dropped by the backend when MQTT is off
+export function publishTranscript(role: Speaker, text: string): void {
+  if (!text.trim()) return;
+  invoke("mqtt_publish_transcript", { role, text }).catch((error) =>
+    console.error("Failed to publish transcript:", error)
+  );
+}
+
+export function getMqttStatus(): Promise<MqttStatus> {
+  return invoke<MqttStatus>("get_mqtt_status");
+}
+
+// A null password keeps the stored one, an empty string removes it
+export function setMqttSettings(
+  settings: MqttSettings,
+  password: string | null
+): Promise<string> {
+  return invoke<string>("set_mqtt_settings", { settings, password });
+}
@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors notifications::NotificationSettings on the Rust side
+export interface NotificationSettings {
+  enabled: boolean;
+}
+
+// The backend only shows it when the main window is minimized or hidden
+export function notifyResponse(text: string): void {
+  invoke("notify_response", { text }).catch((error) =>
+    console.error("Failed to show reply notification:", error)
+  );
+}
+
+export function setNotificationsEnabled(enabled: boolean): Promise<string> {
+  return invoke<string>("set_notifications_enabled", { enabled });
+}
@@ -1,6 +1,17 @@
+import { invoke } from '@tauri-apps/api/core';
+import { loadMockRealtimeServer } from './realtime-mock';
+import { RealtimeTransport, RealtimeTransportFactory, relayTransport, webSocketTransport } from './realtime-transport';
+import { usesProxyRelay } from './proxy';
+import { AudioFormatSettings, RealtimeAudioFormat } from './audio-format';
+import { G711Law, decodeG711, encodeG711 } from './g711';
+import { callTool, listTools, ToolDefinition } from './tools';
+import { getStoredOpenAIKey } from './secrets';
+
 export interface RealtimeStatus {
   apiKey: 'configured' | 'missing';
+  provider: 'openai' | 'azure';
   connected: boolean;
+  reconnecting?: boolean;
   sessionId?: string;
 }
 
@@ -8,15 +19,212 @@ export interface OpenAIEvent {
   type: 'session.created' | 'response.text.delta' | 'response.text.done' | 
         'response.audio.delta' | 'response.audio.done' | 'error' | 'input_audio_buffer.speech_started' | 
         'input_audio_buffer.speech_stopped' | 'conversation.item.created' | 'response.created' |
-        'response.done' | 'input_audio_buffer.committed';
+        'response.done' | 'input_audio_buffer.committed' | 'conversation.item.truncated' |
+        'conversation.item.deleted' | 'context-pruned' | 'openai-reconnecting' | 'openai-reconnected' |
+        'conversation.item.input_audio_transcription.completed';
   data: any;
 }
 
+// An item in the server-side conversation of the current session
+export interface ConversationItem {
+  id: string;
+  type: 'message' | 'function_call' | 'function_call_output';
+  role: 'user' | 'assistant' | 'system' | null;
+  text: string; // typed text or transcript; empty until an audio item's transcript arrives
+  audio: boolean;
+}
+
+// Data of a `context-pruned` event
+export interface ContextPruned {
+  removed: number;
+  tokensBefore: number;
+  estimatedTokensAfter: number;
+  summarized: boolean;
+}
+
+function conversationItemText(item: any): string {
+  if (item.type === 'function_call') return `${item.name}(${item.arguments ?? ''})`;
+  if (item.type === 'function_call_output') return item.output ?? '';
+  return (item.content ?? []).map((part: any) => part.text ?? part.transcript ?? '').join('');
+}
+
+function conversationItemHasAudio(item: any): boolean {
+  return (item.content ?? []).some((part: any) => part.type === 'input_audio' || part.type === 'audio');
+}
+
+// Rough relative size of an item in the context; audio costs several times its transcript
+function estimateItemTokens(item: ConversationItem): number {
+  const textTokens = Math.ceil(item.text.length / 4) + 4;
+  return item.audio ? textTokens * 5 : textTokens;
+}
+
+function truncateLine(text: string, maxChars: number): string {
+  const line = text.replace(/\s+/g, ' ').trim();
+  return line.length > maxChars ? `${line.slice(0, maxChars - 1)}…` : line;
+}
+
+// Stamped onto response.text.delta / response.audio.delta by every backend
+export interface ResponseDeltaOrder {
+  response_id: string | null;
+  item_id: string | null;
+  sequence: number; // increases across all responses, never reused
+}
+
+let responseDeltaSequence = 0;
+
+export function nextResponseSequence(): number {
+  return ++responseDeltaSequence;
+}
+
+export interface AzureRealtimeConfig {
+  endpoint: string; // e.g. https://my-resource.openai.azure.com
+  deployment: string;
+  apiVersion: string;
+  apiKey: string;
+}
+
+const AZURE_CONFIG_STORAGE_KEY = 'azure_openai_config';
+export const DEFAULT_AZURE_API_VERSION = '2024-10-01-preview';
+// Mirrors persona::DEFAULT_INSTRUCTIONS on the Rust side
+export const DEFAULT_INSTRUCTIONS = 'You are {name}, a very cute AI assistant. Respond in a friendly, helpful, and slightly playful manner. Keep your responses concise but warm.';
+export const OPENAI_REALTIME_MODEL = 'gpt-4o-realtime-preview-2024-10-01';
+const DEFAULT_VOICE = 'alloy';
+const OPENAI_REALTIME_URL = `wss://api.openai.com/v1/realtime?model=${OPENAI_REALTIME_MODEL}`;
+
+export interface ConnectionHealth {
+  connected: boolean;
+  quality: 'good' | 'fair' | 'poor' | 'disconnected';
+  latencyMs: number | null;
+  averageLatencyMs: number | null;
+  lastEventAt: number | null;
+  lastHeartbeatAt: number | null;
+  missedHeartbeats: number;
+  droppedFrames: number;
+  uploadCongested: boolean;
+  uploadBacklogMs: number; // captured audio not yet on the wire, roughly
+}
+
+const HEARTBEAT_INTERVAL_MS = 15000;
+const HEARTBEAT_TIMEOUT_MS = 10000;
+const MAX_MISSED_HEARTBEATS = 2;
+const LATENCY_SAMPLE_COUNT = 10;
+
+// Captured PCM16 is batched into ~100ms input_audio_buffer.append messages
+const INPUT_SAMPLE_RATE = 24000;
+const AUDIO_SEND_INTERVAL_MS = 100;
+const AUDIO_SEND_CHUNK_BYTES = (INPUT_SAMPLE_RATE * 2 * AUDIO_SEND_INTERVAL_MS) / 1000;
+// Lets a backlog (e.g. pre-wake audio) drain faster than real time without a burst
+const AUDIO_SEND_MAX_CHUNKS_PER_TICK = 3;
+// Hold off while the socket still has this much unsent data
+const AUDIO_SEND_MAX_BUFFERED_BYTES = 64 * 1024;
+// Oldest audio is dropped past this much backlog (5s)
+const AUDIO_SEND_MAX_PENDING_BYTES = AUDIO_SEND_CHUNK_BYTES * 50;
+// Overflows are reported as `audio-overflow` events at most this often
+const AUDIO_OVERFLOW_REPORT_INTERVAL_MS = 1000;
+// Past this much unsent audio (1s) the upload counts as congested...
+const AUDIO_CONGESTION_BACKLOG_BYTES = AUDIO_SEND_CHUNK_BYTES * 10;
+// ...and the sender switches to fewer, larger appends, one at a time once the socket has drained
+const AUDIO_DEGRADED_CHUNK_BYTES = AUDIO_SEND_CHUNK_BYTES * 3;
+const INPUT_BYTES_PER_MS = (INPUT_SAMPLE_RATE * 2) / 1000;
+// Bytes per millisecond of audio on the wire, before base64
+const WIRE_BYTES_PER_MS: Record<RealtimeAudioFormat, number> = { pcm16: INPUT_BYTES_PER_MS, g711_ulaw: 8, g711_alaw: 8 };
+const G711_LAWS: Partial<Record<RealtimeAudioFormat, G711Law>> = { g711_ulaw: 'ulaw', g711_alaw: 'alaw' };
+
+// The Realtime model's context window; past it responses start failing
+const CONTEXT_LIMIT_TOKENS = 128000;
+// Oldest items are pruned once a response's context passes 80% of the limit, down to about half
+const CONTEXT_PRUNE_AT_TOKENS = CONTEXT_LIMIT_TOKENS * 0.8;
+const CONTEXT_PRUNE_TO_TOKENS = CONTEXT_LIMIT_TOKENS * 0.5;
+// The newest items are never pruned, so the exchange in progress keeps its context
+const CONTEXT_KEEP_ITEMS = 6;
+// Pruned messages are condensed into one system message of at most this many characters
+const CONTEXT_SUMMARY_MAX_CHARS = 2000;
+const CONTEXT_SUMMARY_LINE_MAX_CHARS = 200;
+const CONTEXT_SUMMARY_HEADER = 'Earlier in this conversation (condensed):';
+
+// Errors an interruption can race into; nothing is wrong when they happen
+const IGNORED_ERROR_CODES = ['response_cancel_not_active', 'item_truncate_invalid_item_id', 'item_delete_invalid_item_id'];
+
+// The Realtime API `turn_detection` session field
+export interface TurnDetectionConfig {
+  type: 'server_vad';
+  threshold: number;
+  prefix_padding_ms: number;
+  silence_duration_ms: number;
+  create_response?: boolean;
+}
+
+const DEFAULT_TURN_DETECTION: TurnDetectionConfig = {
+  type: 'server_vad',
+  threshold: 0.5,
+  prefix_padding_ms: 300,
+  silence_duration_ms: 500
+};
+
+export interface ReconnectConfig {
+  enabled: boolean;
+  initialDelayMs: number;
+  maxDelayMs: number;
+  multiplier: number;
+  maxAttempts: number; // 0 = retry forever
+}
+
+const DEFAULT_RECONNECT_CONFIG: ReconnectConfig = {
+  enabled: true,
+  initialDelayMs: 1000,
+  maxDelayMs: 30000,
+  multiplier: 2,
+  maxAttempts: 0,
+};
+
 export class OpenAIRealtimeService {
   private isConnected = false;
   private sessionId?: string;
-  private websocket: WebSocket | null = null;
+  private websocket: RealtimeTransport | null = null;
+  private transportFactory: RealtimeTransportFactory = webSocketTransport;
   private readonly eventHandlers = new Map<string, ((event: OpenAIEvent) => void)[]>();
+  private reconnectConfig: ReconnectConfig = { ...DEFAULT_RECONNECT_CONFIG };
+  private reconnectAttempt = 0;
+  private reconnectTimer: ReturnType<typeof setTimeout> | null = null;
+  private manualDisconnect = false;
+  private assistantName = 'Eva';
+  private instructions = DEFAULT_INSTRUCTIONS;
+  private memory = '';
+  private styleHint = '';
+  private languageHint = '';
+  private transcriptionLanguage = '';
+  private maxResponseOutputTokens: number | 'inf' = 'inf';
+  private audioOutputEnabled = true;
+  private textOnly = false;
+  // null leaves committing turns to the client
+  private turnDetection: TurnDetectionConfig | null = DEFAULT_TURN_DETECTION;
+  private dictating = false;
+  private tools: ToolDefinition[] = [];
+  private voice = DEFAULT_VOICE;
+  private userApiKey: string | null = null;
+  // From the keychain (or OPENAI_API_KEY) when no key was entered here
+  private storedApiKey: string | null = null;
+  private heartbeatTimer: ReturnType<typeof setInterval> | null = null;
+  private pendingHeartbeatAt: number | null = null;
+  private lastHeartbeatAt: number | null = null;
+  private lastEventAt: number | null = null;
+  private latencySamples: number[] = [];
+  private missedHeartbeats = 0;
+  private droppedFrames = 0;
+  private pendingAudio = new Uint8Array(AUDIO_SEND_MAX_PENDING_BYTES);
+  private pendingAudioBytes = 0;
+  private audioSendTimer: ReturnType<typeof setInterval> | null = null;
+  private unreportedOverflowBytes = 0;
+  private totalOverflowBytes = 0;
+  private lastOverflowReportAt = 0;
+  private uploadCongested = false;
+  private audioFormats: AudioFormatSettings = { input: 'pcm16', output: 'pcm16' };
+  // The formats of the current session; changes wait for the next connect
+  private wireFormats: AudioFormatSettings = { input: 'pcm16', output: 'pcm16' };
+  // Mirror of the server's conversation, in order; each session starts empty
+  private items: ConversationItem[] = [];
+  // Context size the server reported for the latest response
+  private contextTokens = 0;
 
   async connect(): Promise<void> {
     if (this.isConnected) {
@@ -24,94 +232,516 @@ export class OpenAIRealtimeService {
       return;
     }
 
+    this.manualDisconnect = false;
+    this.cancelReconnect();
+
     try {
-      // Check for API key
-      const apiKey = import.meta.env.VITE_OPENAI_API_KEY || 
-                    localStorage.getItem('openai_api_key') ||
-                    (globalThis as any).OPENAI_API_KEY;
-      
-      if (!apiKey) {
-        throw new Error('OpenAI API key not found');
-      }
+      await this.openSocket();
+      console.log('✅ Connected to OpenAI Realtime API successfully');
+    } catch (error) {
+      console.error('Failed to connect to OpenAI:', error);
+      this.emit('error', { 
+        message: error instanceof Error ? error.message : 'Connection failed',
+        type: 'connection_error'
+      });
+      throw error;
+    }
+  }
 
-      // Connect to OpenAI Realtime API via WebSocket
-      const url = 'wss://api.openai.com/v1/realtime?model=gpt-4o-realtime-preview-2024-10-01';
-      
-      this.websocket = new WebSocket(url, [
+  private getApiKey(): string | null {
+    const azure = this.getAzureConfig();
+    if (azure) {
+      return azure.apiKey || null;
+    }
+
+    return this.userApiKey ||
+           import.meta.env.VITE_OPENAI_API_KEY || 
+           localStorage.getItem('openai_api_key') ||
+           this.storedApiKey ||
+           (globalThis as any).OPENAI_API_KEY ||
+           null;
+  }
+
+  getAzureConfig(): AzureRealtimeConfig | null {
+    const stored = localStorage.getItem(AZURE_CONFIG_STORAGE_KEY);
+    if (!stored) return null;
+
+    try {
+      return JSON.parse(stored) as AzureRealtimeConfig;
+    } catch {
+      console.warn('Ignoring invalid Azure OpenAI config');
+      return null;
+    }
+  }
+
+  // Pass null to switch back to api.openai.com
+  setAzureConfig(config: AzureRealtimeConfig | null): void {
+    if (config) {
+      localStorage.setItem(AZURE_CONFIG_STORAGE_KEY, JSON.stringify(config));
+      console.log(`✅ Azure OpenAI endpoint configured: ${config.endpoint} (${config.deployment})`);
+    } else {
+      localStorage.removeItem(AZURE_CONFIG_STORAGE_KEY);
+      console.log('✅ Azure OpenAI endpoint removed, using api.openai.com');
+    }
+  }
+
+  // Browsers can't set WebSocket headers, so credentials travel as subprotocols (OpenAI) or query params (Azure)
+  private getConnectionTarget(apiKey: string): { url: string; protocols: string[] } {
+    const azure = this.getAzureConfig();
+    if (azure) {
+      const host = azure.endpoint.trim().replace(/^https?:\/\//, '').replace(/\/+$/, '');
+      const params = new URLSearchParams({
+        'api-version': azure.apiVersion || DEFAULT_AZURE_API_VERSION,
+        deployment: azure.deployment,
+        'api-key': apiKey,
+      });
+      return { url: `wss://${host}/openai/realtime?${params.toString()}`, protocols: [] };
+    }
+
+    return {
+      url: OPENAI_REALTIME_URL,
+      protocols: [
         'realtime',
         `openai-insecure-api-key.${apiKey}`,
         'openai-beta.realtime-v1'
-      ]);
-
-      await new Promise<void>((resolve, reject) => {
-        if (!this.websocket) return reject(new Error('WebSocket creation failed'));
-
-        this.websocket.onopen = () => {
-          console.log('🔌 WebSocket connected to OpenAI Realtime API');
-          
-          // Send session configuration
-          this.sendRealtimeEvent({
-            type: 'session.update',
-            session: {
-              modalities: ['text', 'audio'],
-              instructions: 'You are Eva, a very cute AI assistant. Respond in a friendly, helpful, and slightly playful manner. Keep your responses concise but warm.',
-              voice: 'alloy',
-              input_audio_format: 'pcm16',
-              output_audio_format: 'pcm16',
-              input_audio_transcription: {
-                model: 'whisper-1'
-              },
-              turn_detection: {
-                type: 'server_vad',
-                threshold: 0.5,
-                prefix_padding_ms: 300,
-                silence_duration_ms: 500
-              }
-            }
-          });
-          
-          this.isConnected = true;
-          this.sessionId = `session_${Date.now()}`;
-          resolve();
-        };
-
-        this.websocket.onmessage = (event) => {
-          try {
-            const data = JSON.parse(event.data);
-            this.handleRealtimeEvent(data);
-          } catch (error) {
-            console.error('Failed to parse realtime event:', error);
-          }
-        };
-
-        this.websocket.onerror = (error) => {
-          console.error('WebSocket error:', error);
-          this.emit('error', { 
-            message: 'WebSocket connection failed',
-            type: 'connection_error'
-          });
+      ],
+    };
+  }
+
+  private getSessionConfig(): any {
+    return {
+      modalities: this.replyModalities(!this.textOnly),
+      instructions: this.getInstructions(),
+      max_response_output_tokens: this.maxResponseOutputTokens,
+      voice: this.voice,
+      input_audio_format: this.wireFormats.input,
+      output_audio_format: this.wireFormats.output,
+      input_audio_transcription: this.transcriptionLanguage
+        ? { model: 'whisper-1', language: this.transcriptionLanguage }
+        : { model: 'whisper-1' },
+      // No audio input in text-only mode, so there is nothing for server VAD to detect
+      turn_detection: this.textOnly ? null : this.dictationTurnDetection(),
+      tools: this.tools.map((tool) => ({ type: 'function', ...tool })),
+      tool_choice: this.tools.length > 0 ? 'auto' : 'none'
+    };
+  }
+
+  // Open the WebSocket and configure the session; shared by connect() and reconnects
+  private async openSocket(): Promise<void> {
+    // Check for API key
+    const apiKey = this.getApiKey();
+    
+    if (!apiKey && !this.usesMockServer()) {
+      throw new Error('OpenAI API key not found');
+    }
+
+    // Tools configured since the last connect (e.g. Home Assistant) join the new session
+    this.tools = await listTools();
+
+    // Connect to OpenAI (or Azure OpenAI) Realtime API via WebSocket
+    const { url, protocols } = this.getConnectionTarget(apiKey ?? '');
+    this.wireFormats = { ...this.audioFormats };
+    this.items = [];
+    this.contextTokens = 0;
+    
+    // A manual proxy or custom CA only applies on the Rust side, which relays the socket
+    const factory = !this.usesMockServer() && usesProxyRelay() ? relayTransport : this.transportFactory;
+    const websocket = factory(url, protocols);
+    this.websocket = websocket;
+
+    await new Promise<void>((resolve, reject) => {
+      let opened = false;
+
+      websocket.onopen = () => {
+        console.log('🔌 WebSocket connected to OpenAI Realtime API');
+        opened = true;
+        
+        // Send session configuration
+        this.sendRealtimeEvent({
+          type: 'session.update',
+          session: this.getSessionConfig()
+        });
+        
+        this.isConnected = true;
+        this.sessionId = `session_${Date.now()}`;
+        this.startHeartbeat();
+        resolve();
+      };
+
+      websocket.onmessage = (event) => {
+        try {
+          const data = JSON.parse(event.data);
+          this.handleRealtimeEvent(data);
+        } catch (error) {
+          console.error('Failed to parse realtime event:', error);
+        }
+      };
+
+      websocket.onerror = (error) => {
+        console.error('WebSocket error:', error);
+        if (!opened) {
           reject(new Error('WebSocket connection failed'));
-        };
+        }
+      };
+
+      websocket.onclose = (event) => {
+        console.log('🔌 WebSocket disconnected:', event.code, event.reason);
+        // Ignore close events from sockets we've already replaced
+        if (this.websocket !== websocket) return;
+
+        this.isConnected = false;
+        this.sessionId = undefined;
+        this.websocket = null;
+        this.stopHeartbeat();
+        this.stopAudioSender();
 
-        this.websocket.onclose = (event) => {
-          console.log('🔌 WebSocket disconnected:', event.code, event.reason);
-          this.isConnected = false;
-          this.sessionId = undefined;
-        };
+        if (!opened) {
+          reject(new Error(`WebSocket closed before opening (code ${event.code})`));
+        } else if (!this.manualDisconnect) {
+          this.scheduleReconnect();
+        }
+      };
+    });
+  }
+
+  private scheduleReconnect(): void {
+    const config = this.reconnectConfig;
+    if (!config.enabled || this.manualDisconnect) return;
+
+    if (config.maxAttempts > 0 && this.reconnectAttempt >= config.maxAttempts) {
+      console.error(`❌ Giving up reconnecting after ${this.reconnectAttempt} attempts`);
+      this.reconnectAttempt = 0;
+      this.emit('error', {
+        type: 'reconnect_failed',
+        message: 'Lost connection to OpenAI and could not reconnect'
       });
+      return;
+    }
 
-      console.log('✅ Connected to OpenAI Realtime API successfully');
+    this.reconnectAttempt++;
+    const delayMs = Math.min(
+      config.initialDelayMs * Math.pow(config.multiplier, this.reconnectAttempt - 1),
+      config.maxDelayMs
+    );
+
+    console.log(`🔄 Reconnecting to OpenAI in ${delayMs}ms (attempt ${this.reconnectAttempt})`);
+    this.emit('openai-reconnecting', { attempt: this.reconnectAttempt, delayMs });
+
+    this.reconnectTimer = setTimeout(async () => {
+      this.reconnectTimer = null;
+      try {
+        await this.openSocket();
+        console.log(`✅ Reconnected to OpenAI after ${this.reconnectAttempt} attempt(s)`);
+        this.emit('openai-reconnected', { attempts: this.reconnectAttempt, sessionId: this.sessionId });
+        this.reconnectAttempt = 0;
+      } catch (error) {
+        console.warn('Reconnect attempt failed:', error);
+        this.scheduleReconnect();
+      }
+    }, delayMs);
+  }
+
+  private cancelReconnect(): void {
+    if (this.reconnectTimer) {
+      clearTimeout(this.reconnectTimer);
+      this.reconnectTimer = null;
+    }
+    this.reconnectAttempt = 0;
+  }
+
+  setReconnectConfig(config: Partial<ReconnectConfig>): void {
+    this.reconnectConfig = { ...this.reconnectConfig, ...config };
+    if (!this.reconnectConfig.enabled) {
+      this.cancelReconnect();
+    }
+  }
+
+  // Rename the assistant; a live session picks up the new instructions immediately
+  setAssistantName(name: string): void {
+    this.assistantName = name;
+    if (this.isConnected) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { instructions: this.getSessionConfig().instructions }
+      });
+    }
+  }
+
+  // Swap the persona template ({name} is filled in with the assistant name) on the live session
+  setInstructions(template: string): void {
+    this.instructions = template;
+    if (this.isConnected) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { instructions: this.getInstructions() }
+      });
+    }
+  }
+
+  getInstructions(): string {
+    let instructions = this.instructions.split('{name}').join(this.assistantName);
+    if (this.styleHint) {
+      instructions = `${instructions}\n\n${this.styleHint}`;
+    }
+    if (this.languageHint) {
+      instructions = `${instructions}\n\n${this.languageHint}`;
+    }
+    if (!this.memory) return instructions;
+    return `${instructions}\n\nWhat you remember about the user from earlier conversations:\n${this.memory}`;
+  }
+
+  // Response length profile: caps output tokens and appends a style hint to the instructions
+  setResponseProfile(styleHint: string, maxOutputTokens: number | null): void {
+    this.styleHint = styleHint;
+    this.maxResponseOutputTokens = maxOutputTokens ?? 'inf';
+    if (this.isConnected) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: {
+          instructions: this.getInstructions(),
+          max_response_output_tokens: this.maxResponseOutputTokens
+        }
+      });
+    }
+  }
+
+  // Conversation language: instructions hint plus an ISO 639-1 transcription hint ('' to auto-detect)
+  setLanguage(instructionsHint: string, transcriptionLanguage: string): void {
+    this.languageHint = instructionsHint;
+    this.transcriptionLanguage = transcriptionLanguage;
+    if (this.isConnected) {
+      const { instructions, input_audio_transcription } = this.getSessionConfig();
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { instructions, input_audio_transcription }
+      });
+    }
+  }
+
+  // Recalled memory is appended to the instructions of the next session
+  setMemory(memory: string): void {
+    this.memory = memory;
+  }
+
+  // Plain OpenAI key for REST calls; Azure realtime deployments can't serve chat completions
+  getOpenAIApiKey(): string | null {
+    return this.getAzureConfig() ? null : this.getApiKey();
+  }
+
+  // Text-only responses let a local TTS voice speak instead of OpenAI's audio
+  setAudioOutputEnabled(enabled: boolean): void {
+    this.audioOutputEnabled = enabled;
+    if (this.isConnected) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { modalities: this.getSessionConfig().modalities }
+      });
+    }
+  }
+
+  // Plain chat client mode: text in, text out, no audio on either side
+  setTextOnly(enabled: boolean): void {
+    this.textOnly = enabled;
+    if (this.isConnected) {
+      const { modalities, turn_detection } = this.getSessionConfig();
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { modalities, turn_detection }
+      });
+    }
+  }
+
+  // While dictating, turns are only transcribed; nothing is answered
+  private dictationTurnDetection(): TurnDetectionConfig | null {
+    if (!this.dictating || !this.turnDetection) return this.turnDetection;
+    return { ...this.turnDetection, create_response: false };
+  }
+
+  // Dictation mode: transcripts go to whatever listens for them instead of getting a reply,
+  // and are dropped from the conversation once transcribed
+  setDictating(enabled: boolean): void {
+    this.dictating = enabled;
+    if (this.isConnected) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { turn_detection: this.getSessionConfig().turn_detection }
+      });
+    }
+  }
+
+  // Server VAD parameters, or null to end turns with commitAudioBuffer()
+  setTurnDetection(config: TurnDetectionConfig | null): void {
+    this.turnDetection = config;
+    if (this.isConnected) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { turn_detection: this.getSessionConfig().turn_detection }
+      });
+    }
+  }
+
+  // Wire encoding for mic and reply audio, e.g. G.711 on a slow uplink. Everything outside this
+  // service stays 24kHz PCM16; the change applies from the next connect.
+  setAudioFormats(formats: AudioFormatSettings): void {
+    this.audioFormats = formats;
+  }
+
+  async loadStoredApiKey(): Promise<void> {
+    try {
+      this.storedApiKey = await getStoredOpenAIKey();
     } catch (error) {
-      console.error('Failed to connect to OpenAI:', error);
-      this.emit('error', { 
-        message: error instanceof Error ? error.message : 'Connection failed',
-        type: 'connection_error'
+      console.error('Failed to load the stored OpenAI key:', error);
+    }
+  }
+
+  // The active user's own key, used instead of the shared one from the next connect
+  setUserApiKey(apiKey: string | null): void {
+    this.userApiKey = apiKey;
+  }
+
+  // Reply voice, empty for the default. The API only changes it before the session's first
+  // spoken reply; after that it applies from the next connect.
+  setVoice(voice: string): void {
+    const next = voice || DEFAULT_VOICE;
+    if (next === this.voice) return;
+    this.voice = next;
+    if (this.isConnected && !this.items.some((item) => item.role === 'assistant' && item.audio)) {
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { voice: next }
       });
-      throw error;
     }
   }
 
+  // Re-read the available tools, e.g. after a profile switch, and offer them to the live session
+  async refreshTools(): Promise<void> {
+    this.tools = await listTools();
+    if (this.isConnected) {
+      const { tools, tool_choice } = this.getSessionConfig();
+      this.sendRealtimeEvent({
+        type: 'session.update',
+        session: { tools, tool_choice }
+      });
+    }
+  }
+
+  // Whether the server ends turns on its own; otherwise the client has to commit
+  usesServerVad(): boolean {
+    return this.turnDetection !== null && !this.textOnly;
+  }
+
+  // Audio only when OpenAI's voice is in use; the local voice speaks text replies itself
+  private replyModalities(spoken: boolean): string[] {
+    return spoken && this.audioOutputEnabled ? ['text', 'audio'] : ['text'];
+  }
+
+  isTextOnly(): boolean {
+    return this.textOnly;
+  }
+
+  isAudioOutputEnabled(): boolean {
+    return this.audioOutputEnabled;
+  }
+
+  getAssistantName(): string {
+    return this.assistantName;
+  }
+
+  getReconnectConfig(): ReconnectConfig {
+    return { ...this.reconnectConfig };
+  }
+
+  isReconnecting(): boolean {
+    return this.reconnectTimer !== null;
+  }
+
+  // The Realtime API has no ping event, so an empty session.update doubles as a heartbeat:
+  // it's acknowledged with session.updated and leaves the session untouched
+  private startHeartbeat(): void {
+    this.stopHeartbeat();
+    this.missedHeartbeats = 0;
+
+    this.heartbeatTimer = setInterval(() => {
+      if (this.pendingHeartbeatAt !== null) {
+        if (Date.now() - this.pendingHeartbeatAt < HEARTBEAT_TIMEOUT_MS) return;
+
+        this.missedHeartbeats++;
+        console.warn(`💔 Heartbeat timed out (${this.missedHeartbeats} missed)`);
+        this.pendingHeartbeatAt = null;
+
+        if (this.missedHeartbeats >= MAX_MISSED_HEARTBEATS) {
+          console.error('💔 Connection unresponsive, forcing reconnect');
+          // onclose takes care of scheduling the reconnect
+          this.websocket?.close();
+          return;
+        }
+      }
+
+      this.pendingHeartbeatAt = Date.now();
+      this.sendRealtimeEvent({ type: 'session.update', session: {} });
+    }, HEARTBEAT_INTERVAL_MS);
+  }
+
+  private stopHeartbeat(): void {
+    if (this.heartbeatTimer) {
+      clearInterval(this.heartbeatTimer);
+      this.heartbeatTimer = null;
+    }
+    this.pendingHeartbeatAt = null;
+  }
+
+  private recordHeartbeatAck(): void {
+    if (this.pendingHeartbeatAt === null) return;
+
+    const latency = Date.now() - this.pendingHeartbeatAt;
+    this.pendingHeartbeatAt = null;
+    this.lastHeartbeatAt = Date.now();
+    this.missedHeartbeats = 0;
+    this.latencySamples = [...this.latencySamples.slice(-(LATENCY_SAMPLE_COUNT - 1)), latency];
+  }
+
+  getConnectionHealth(): ConnectionHealth {
+    const latencyMs = this.latencySamples.length > 0
+      ? this.latencySamples[this.latencySamples.length - 1]
+      : null;
+    const averageLatencyMs = this.latencySamples.length > 0
+      ? Math.round(this.latencySamples.reduce((sum, value) => sum + value, 0) / this.latencySamples.length)
+      : null;
+
+    let quality: ConnectionHealth['quality'];
+    if (!this.isConnected) {
+      quality = 'disconnected';
+    } else if (
+      this.missedHeartbeats > 0 ||
+      this.uploadCongested ||
+      (averageLatencyMs !== null && averageLatencyMs > 1000)
+    ) {
+      quality = 'poor';
+    } else if (averageLatencyMs !== null && averageLatencyMs > 300) {
+      quality = 'fair';
+    } else {
+      quality = 'good';
+    }
+
+    return {
+      connected: this.isConnected,
+      quality,
+      latencyMs,
+      averageLatencyMs,
+      lastEventAt: this.lastEventAt,
+      lastHeartbeatAt: this.lastHeartbeatAt,
+      missedHeartbeats: this.missedHeartbeats,
+      droppedFrames: this.droppedFrames,
+      uploadCongested: this.uploadCongested,
+      uploadBacklogMs: this.uploadBacklogMs(),
+    };
+  }
+
   async disconnect(): Promise<void> {
+    this.manualDisconnect = true;
+    this.cancelReconnect();
+    this.stopHeartbeat();
+    this.stopAudioSender();
     if (this.websocket) {
       this.websocket.close();
       this.websocket = null;
@@ -125,11 +755,15 @@ export class OpenAIRealtimeService {
     if (this.websocket && this.websocket.readyState === WebSocket.OPEN) {
       this.websocket.send(JSON.stringify(event));
     } else {
+      if (event.type === 'input_audio_buffer.append') {
+        this.droppedFrames++;
+      }
       console.warn('Cannot send event: WebSocket not connected');
     }
   }
 
   private handleRealtimeEvent(event: any): void {
+    this.lastEventAt = Date.now();
     console.log('📨 Realtime event:', event.type);
     console.log('🔍 Event keys:', Object.keys(event));
     console.log('🔍 Full event data:', JSON.stringify(event, null, 2));
@@ -141,6 +775,7 @@ export class OpenAIRealtimeService {
       
       case 'session.updated':
         console.log('Session updated');
+        this.recordHeartbeatAck();
         break;
 
       case 'input_audio_buffer.speech_started':
@@ -156,15 +791,36 @@ export class OpenAIRealtimeService {
         break;
 
       case 'conversation.item.created':
+        this.trackItem(event.item, event.previous_item_id);
         this.emit('conversation.item.created', event);
         break;
 
+      case 'conversation.item.deleted':
+        this.items = this.items.filter((item) => item.id !== event.item_id);
+        this.emit('conversation.item.deleted', event);
+        break;
+
+      case 'conversation.item.input_audio_transcription.completed':
+        this.updateItem(event.item_id, event.transcript ?? '', true);
+        this.emit('conversation.item.input_audio_transcription.completed', event);
+        if (this.dictating) {
+          this.deleteItem(event.item_id).catch((error) =>
+            console.error('Failed to drop dictated item:', error)
+          );
+        }
+        break;
+
+      case 'conversation.item.truncated':
+        console.log(`✂️ Item ${event.item_id} truncated at ${event.audio_end_ms}ms`);
+        this.emit('conversation.item.truncated', event);
+        break;
+
       case 'response.created':
         this.emit('response.created', event);
         break;
 
       case 'response.text.delta':
-        this.emit('response.text.delta', event);
+        this.emit('response.text.delta', this.withDeltaOrder(event));
         break;
 
       case 'response.text.done':
@@ -173,7 +829,7 @@ export class OpenAIRealtimeService {
 
       case 'response.audio.delta':
         console.log('🔊 Raw response.audio.delta event:', JSON.stringify(event, null, 2));
-        this.emit('response.audio.delta', event);
+        this.emit('response.audio.delta', this.withDeltaOrder(this.decodeAudioDelta(event)));
         break;
 
       case 'response.audio.done':
@@ -182,7 +838,9 @@ export class OpenAIRealtimeService {
 
       case 'response.done':
         console.log('📝 Response details:', event.response);
-        if (event.response?.status === 'failed') {
+        if (event.response?.status === 'cancelled') {
+          console.log(`⏹️ Response cancelled (${event.response.status_details?.reason ?? 'client'})`);
+        } else if (event.response?.status === 'failed') {
           const error = event.response.status_details?.error;
           if (error?.type === 'insufficient_quota') {
             console.error('💳 QUOTA EXCEEDED: Please check your OpenAI billing');
@@ -198,10 +856,23 @@ export class OpenAIRealtimeService {
             });
           }
         }
+        for (const item of event.response?.output ?? []) {
+          if (item?.id) this.updateItem(item.id, conversationItemText(item), conversationItemHasAudio(item));
+        }
+        if (event.response?.usage) {
+          this.contextTokens = (event.response.usage.input_tokens ?? 0) + (event.response.usage.output_tokens ?? 0);
+        }
         this.emit('response.done', event);
+        this.runToolCalls(event.response?.output ?? []);
+        this.pruneContext();
         break;
 
       case 'error':
+        // Cancelling just as a response finished, or truncating audio the server no longer has, is harmless
+        if (IGNORED_ERROR_CODES.includes(event.error?.code)) {
+          console.log(`🔍 Ignoring ${event.error.code}: ${event.error.message}`);
+          break;
+        }
         this.emit('error', event);
         break;
 
@@ -211,7 +882,128 @@ export class OpenAIRealtimeService {
     }
   }
 
-  async sendMessage(content: string): Promise<void> {
+  private trackItem(item: any, previousItemId?: string | null): void {
+    if (!item?.id || this.items.some((existing) => existing.id === item.id)) return;
+    const tracked: ConversationItem = {
+      id: item.id,
+      type: item.type,
+      role: item.role ?? null,
+      text: conversationItemText(item),
+      audio: conversationItemHasAudio(item),
+    };
+    // Items can be inserted mid-conversation or at its start; without a known predecessor they go at the end
+    if (previousItemId === 'root') {
+      this.items.unshift(tracked);
+      return;
+    }
+    const previous = previousItemId ? this.items.findIndex((existing) => existing.id === previousItemId) : -1;
+    if (previous === -1) {
+      this.items.push(tracked);
+    } else {
+      this.items.splice(previous + 1, 0, tracked);
+    }
+  }
+
+  private updateItem(itemId: string, text: string, audio: boolean): void {
+    const item = this.items.find((existing) => existing.id === itemId);
+    if (!item) return;
+    if (text) item.text = text;
+    item.audio = item.audio || audio;
+  }
+
+  // Long sessions would eventually overflow the context window and every response would fail.
+  // Once the latest response gets close, the oldest items are deleted and condensed into a
+  // system message at the start of the conversation.
+  private pruneContext(): void {
+    const prunable = this.items.length - CONTEXT_KEEP_ITEMS;
+    if (this.contextTokens < CONTEXT_PRUNE_AT_TOKENS || prunable <= 0) return;
+
+    // Estimates only give proportions; scaled to the reported size they say what each item costs
+    const estimates = this.items.map(estimateItemTokens);
+    const scale = this.contextTokens / estimates.reduce((sum, tokens) => sum + tokens, 0);
+    const tokensToFree = this.contextTokens - CONTEXT_PRUNE_TO_TOKENS;
+    let count = 0;
+    let freed = 0;
+    while (count < prunable && freed < tokensToFree) {
+      freed += estimates[count] * scale;
+      count++;
+    }
+    // A tool result without its call would be rejected, so they go together
+    while (count < prunable && this.items[count].type === 'function_call_output') {
+      freed += estimates[count] * scale;
+      count++;
+    }
+
+    const removed = this.items.slice(0, count);
+    const summary = this.condenseItems(removed);
+    for (const item of removed) {
+      this.sendRealtimeEvent({ type: 'conversation.item.delete', item_id: item.id });
+    }
+    if (summary) {
+      this.sendRealtimeEvent({
+        type: 'conversation.item.create',
+        previous_item_id: 'root',
+        item: { type: 'message', role: 'system', content: [{ type: 'input_text', text: summary }] }
+      });
+    }
+
+    const pruned: ContextPruned = {
+      removed: removed.length,
+      tokensBefore: this.contextTokens,
+      estimatedTokensAfter: Math.round(this.contextTokens - freed),
+      summarized: summary !== null,
+    };
+    console.log(`🧹 Pruned ${pruned.removed} conversation items (~${pruned.tokensBefore} → ~${pruned.estimatedTokensAfter} tokens)`);
+    // Not pruned again until a response reports the new size
+    this.contextTokens = pruned.estimatedTokensAfter;
+    this.emit('context-pruned', pruned);
+  }
+
+  // One line per message, folding in an earlier summary; the newest lines win when it's too long
+  private condenseItems(items: ConversationItem[]): string | null {
+    const lines = items.flatMap((item) => {
+      if (item.type !== 'message' || !item.text) return [];
+      if (item.role === 'system' && item.text.startsWith(CONTEXT_SUMMARY_HEADER)) {
+        return item.text.slice(CONTEXT_SUMMARY_HEADER.length).split('\n').filter(Boolean);
+      }
+      return [`${item.role}: ${truncateLine(item.text, CONTEXT_SUMMARY_LINE_MAX_CHARS)}`];
+    });
+
+    let text = '';
+    for (const line of lines.reverse()) {
+      if (text.length + line.length + 1 > CONTEXT_SUMMARY_MAX_CHARS) break;
+      text = `${line}\n${text}`;
+    }
+    return text ? `${CONTEXT_SUMMARY_HEADER}\n${text.trimEnd()}` : null;
+  }
+
+  // Function calls arrive as output items of a finished response; their results go back
+  // as function_call_output items and a new response lets the model answer with them
+  private async runToolCalls(output: any[]): Promise<void> {
+    const calls = output.filter((item) => item?.type === 'function_call');
+    if (calls.length === 0) return;
+
+    for (const call of calls) {
+      console.log(`🛠️ Tool call: ${call.name}`, call.arguments);
+      const result = await callTool(call.name, call.arguments ?? '{}');
+      this.sendRealtimeEvent({
+        type: 'conversation.item.create',
+        item: {
+          type: 'function_call_output',
+          call_id: call.call_id,
+          output: result
+        }
+      });
+    }
+
+    this.sendRealtimeEvent({
+      type: 'response.create'
+    });
+  }
+
+  // `spoken` overrides the session's modalities for this reply, e.g. a voice answer to a typed
+  // message in text-only mode
+  async sendMessage(content: string, spoken?: boolean): Promise<void> {
     if (!this.isConnected || !this.websocket) {
       throw new Error('Not connected to OpenAI Realtime API');
     }
@@ -236,7 +1028,8 @@ export class OpenAIRealtimeService {
 
       // Trigger response
       this.sendRealtimeEvent({
-        type: 'response.create'
+        type: 'response.create',
+        ...(spoken !== undefined && { response: { modalities: this.replyModalities(spoken) } })
       });
 
     } catch (error) {
@@ -251,6 +1044,7 @@ export class OpenAIRealtimeService {
 
   async sendAudioData(audioData: ArrayBuffer): Promise<void> {
     if (!this.isConnected || !this.websocket) {
+      this.droppedFrames++;
       throw new Error('Not connected to OpenAI Realtime API');
     }
 
@@ -261,22 +1055,18 @@ export class OpenAIRealtimeService {
         return;
       }
 
-      // Check audio content for debugging
+      // Check audio content for debugging, without copying the buffer
       const int16View = new Int16Array(audioData);
-      const samples = Array.from(int16View.slice(0, 5));
-      const maxSample = Math.max(...int16View.map(Math.abs));
-      
-      console.debug(`📤 Sending audio: ${audioData.byteLength} bytes, max amplitude: ${maxSample}, samples: [${samples.join(', ')}...]`);
+      let maxSample = 0;
+      for (let i = 0; i < int16View.length; i++) {
+        maxSample = Math.max(maxSample, Math.abs(int16View[i]));
+      }
 
-      // Convert ArrayBuffer to base64 for transmission
-      const base64Audio = this.arrayBufferToBase64(audioData);
-      
-      // Send audio data to OpenAI with proper event format
-      this.sendRealtimeEvent({
-        type: 'input_audio_buffer.append',
-        audio: base64Audio
-      });
-      
+      console.debug(`📤 Queueing audio: ${audioData.byteLength} bytes, max amplitude: ${maxSample}, samples: [${int16View.subarray(0, 5).join(', ')}...]`);
+
+      // The sender timer batches and paces the actual append messages
+      this.queueAudio(new Uint8Array(audioData));
+      this.startAudioSender();
     } catch (error) {
       console.error('Failed to send audio data:', error);
       throw error;
@@ -288,31 +1078,255 @@ export class OpenAIRealtimeService {
       throw new Error('Not connected to OpenAI Realtime API');
     }
 
+    // Everything captured so far has to reach the server before the commit
+    this.flushAudio(Infinity, true);
     this.sendRealtimeEvent({
       type: 'input_audio_buffer.commit'
     });
   }
 
-  async createResponse(): Promise<void> {
+  // `extraInstructions` steer this one response, e.g. a greeting Eva starts on her own
+  async createResponse(extraInstructions?: string): Promise<void> {
     if (!this.isConnected || !this.websocket) {
       throw new Error('Not connected to OpenAI Realtime API');
     }
 
     this.sendRealtimeEvent({
-      type: 'response.create'
+      type: 'response.create',
+      ...(extraInstructions && {
+        // Response instructions replace the session's, so keep those too
+        response: { instructions: `${this.getInstructions()}\n\n${extraInstructions}` }
+      })
     });
   }
 
-  private arrayBufferToBase64(buffer: ArrayBuffer): string {
-    // Use a more reliable method for ArrayBuffer to base64 conversion
-    const bytes = new Uint8Array(buffer);
+  async cancelResponse(): Promise<void> {
+    if (!this.isConnected || !this.websocket) {
+      throw new Error('Not connected to OpenAI Realtime API');
+    }
+
+    this.sendRealtimeEvent({
+      type: 'response.cancel'
+    });
+  }
+
+  // Cut an assistant item's audio at `audioEndMs`, dropping its unheard transcript too, so the
+  // model's context matches what the user actually heard
+  async truncateItem(itemId: string, audioEndMs: number): Promise<void> {
+    if (!this.isConnected || !this.websocket) {
+      throw new Error('Not connected to OpenAI Realtime API');
+    }
+
+    this.sendRealtimeEvent({
+      type: 'conversation.item.truncate',
+      item_id: itemId,
+      content_index: 0,
+      audio_end_ms: audioEndMs
+    });
+  }
+
+  listItems(): ConversationItem[] {
+    return this.items.map((item) => ({ ...item }));
+  }
+
+  // Remove an item from the model's context; the server confirms with conversation.item.deleted
+  async deleteItem(itemId: string): Promise<void> {
+    if (!this.isConnected || !this.websocket) {
+      throw new Error('Not connected to OpenAI Realtime API');
+    }
+
+    this.sendRealtimeEvent({
+      type: 'conversation.item.delete',
+      item_id: itemId
+    });
+  }
+
+  // Items can't be changed in place, so a copy with the new text is created at the same
+  // position and the original deleted. The copy has a new id.
+  async editItem(itemId: string, text: string): Promise<void> {
+    const index = this.items.findIndex((item) => item.id === itemId);
+    if (index === -1) {
+      throw new Error(`Unknown conversation item ${itemId}`);
+    }
+    const item = this.items[index];
+    if (item.type !== 'message' || !item.role) {
+      throw new Error('Only messages can be edited');
+    }
+
+    await this.deleteItem(itemId);
+    this.sendRealtimeEvent({
+      type: 'conversation.item.create',
+      previous_item_id: index > 0 ? this.items[index - 1].id : 'root',
+      item: {
+        type: 'message',
+        role: item.role,
+        content: [{ type: item.role === 'assistant' ? 'text' : 'input_text', text }]
+      }
+    });
+  }
+
+  // "Forget that": the last user message and everything after it. Returns how many items went.
+  async removeLastExchange(): Promise<number> {
+    const start = this.items.map((item) => item.role).lastIndexOf('user');
+    if (start === -1) return 0;
+    return this.deleteItems(this.items.slice(start));
+  }
+
+  // Keep only the newest `keepLast` items, to bound the context of a long session
+  async pruneItems(keepLast: number): Promise<number> {
+    return this.deleteItems(this.items.slice(0, Math.max(0, this.items.length - keepLast)));
+  }
+
+  private async deleteItems(items: ConversationItem[]): Promise<number> {
+    for (const item of items) {
+      await this.deleteItem(item.id);
+    }
+    return items.length;
+  }
+
+  private queueAudio(bytes: Uint8Array): void {
+    if (bytes.length >= AUDIO_SEND_MAX_PENDING_BYTES) {
+      bytes = bytes.subarray(bytes.length - AUDIO_SEND_MAX_PENDING_BYTES);
+    }
+
+    const overflow = this.pendingAudioBytes + bytes.length - AUDIO_SEND_MAX_PENDING_BYTES;
+    if (overflow > 0) {
+      // Drop the oldest audio rather than flood the socket
+      this.pendingAudio.copyWithin(0, overflow, this.pendingAudioBytes);
+      this.pendingAudioBytes -= overflow;
+      this.droppedFrames++;
+      this.recordOverflow(overflow);
+    }
+
+    this.pendingAudio.set(bytes, this.pendingAudioBytes);
+    this.pendingAudioBytes += bytes.length;
+  }
+
+  private recordOverflow(bytes: number): void {
+    this.unreportedOverflowBytes += bytes;
+    this.totalOverflowBytes += bytes;
+
+    const now = Date.now();
+    if (now - this.lastOverflowReportAt < AUDIO_OVERFLOW_REPORT_INTERVAL_MS) return;
+    this.lastOverflowReportAt = now;
+
+    const droppedMs = Math.round(this.unreportedOverflowBytes / INPUT_BYTES_PER_MS);
+    const totalDroppedMs = Math.round(this.totalOverflowBytes / INPUT_BYTES_PER_MS);
+    this.unreportedOverflowBytes = 0;
+
+    console.warn(`⚠️ Audio send backlog full, dropped ${droppedMs}ms (${totalDroppedMs}ms total)`);
+    invoke('report_audio_overflow', {
+      source: 'realtime-send',
+      droppedMs,
+      totalDroppedMs,
+    }).catch((error) => console.error('Failed to report audio overflow:', error));
+  }
+
+  // Send up to `maxChunks` full chunks; `includePartial` also sends a trailing short chunk
+  private flushAudio(maxChunks: number, includePartial = false, chunkBytes = AUDIO_SEND_CHUNK_BYTES): void {
+    let sent = 0;
+    while (
+      sent < maxChunks &&
+      this.pendingAudioBytes >= (includePartial ? 1 : chunkBytes)
+    ) {
+      const length = Math.min(chunkBytes, this.pendingAudioBytes);
+      this.sendRealtimeEvent({
+        type: 'input_audio_buffer.append',
+        audio: this.encodeAudio(this.pendingAudio.subarray(0, length))
+      });
+      this.pendingAudio.copyWithin(0, length, this.pendingAudioBytes);
+      this.pendingAudioBytes -= length;
+      sent++;
+    }
+  }
+
+  private startAudioSender(): void {
+    if (this.audioSendTimer) return;
+
+    this.audioSendTimer = setInterval(() => {
+      const socketBacklog = this.websocket?.bufferedAmount ?? 0;
+      this.updateUploadCongestion();
+
+      if (this.uploadCongested) {
+        // Degraded mode: audio waits here until the network has taken the previous append
+        if (socketBacklog === 0) {
+          this.flushAudio(1, false, AUDIO_DEGRADED_CHUNK_BYTES);
+        }
+        return;
+      }
+      if (socketBacklog > AUDIO_SEND_MAX_BUFFERED_BYTES) {
+        return;
+      }
+      this.flushAudio(AUDIO_SEND_MAX_CHUNKS_PER_TICK);
+    }, AUDIO_SEND_INTERVAL_MS);
+  }
+
+  // Queued audio plus what the socket hasn't sent yet; bufferedAmount counts base64 JSON, so it's approximate
+  private uploadBacklogMs(): number {
+    const socketBacklogMs = ((this.websocket?.bufferedAmount ?? 0) * 3) / 4 / WIRE_BYTES_PER_MS[this.wireFormats.input];
+    return Math.round(this.pendingAudioBytes / INPUT_BYTES_PER_MS + socketBacklogMs);
+  }
+
+  // Enter degraded mode when the backlog passes a second of audio, and leave it once it's
+  // down to a single chunk, so the state doesn't flap on a borderline connection
+  private updateUploadCongestion(): void {
+    const backlogMs = this.uploadBacklogMs();
+    const backlogBytes = backlogMs * INPUT_BYTES_PER_MS;
+    if (!this.uploadCongested && backlogBytes > AUDIO_CONGESTION_BACKLOG_BYTES) {
+      this.setUploadCongested(true, backlogMs);
+    } else if (this.uploadCongested && backlogBytes < AUDIO_SEND_CHUNK_BYTES) {
+      this.setUploadCongested(false, backlogMs);
+    }
+  }
+
+  private setUploadCongested(congested: boolean, backlogMs: number): void {
+    this.uploadCongested = congested;
+    if (congested) {
+      console.warn(`🐢 Audio upload ${backlogMs}ms behind, switching to degraded streaming`);
+    } else {
+      console.log('📶 Audio upload caught up');
+    }
+    invoke('report_audio_congestion', { congested, backlogMs })
+      .catch((error) => console.error('Failed to report audio congestion:', error));
+  }
+
+  // Queued audio belongs to the old session, so it's discarded rather than sent after a reconnect
+  private stopAudioSender(): void {
+    if (this.audioSendTimer) {
+      clearInterval(this.audioSendTimer);
+      this.audioSendTimer = null;
+    }
+    this.pendingAudioBytes = 0;
+    if (this.uploadCongested) {
+      this.setUploadCongested(false, 0);
+    }
+  }
+
+  // Captured 24kHz PCM16 in the session's input format, base64 for input_audio_buffer.append
+  private encodeAudio(pcm16: Uint8Array): string {
+    const law = G711_LAWS[this.wireFormats.input];
+    if (!law) return this.bytesToBase64(pcm16);
+    const samples = new Int16Array(pcm16.buffer, pcm16.byteOffset, Math.floor(pcm16.length / 2));
+    return this.bytesToBase64(encodeG711(samples, law));
+  }
+
+  // Reply audio arrives in the session's output format; playback and lip sync expect 24kHz PCM16
+  private decodeAudioDelta(event: any): any {
+    const law = G711_LAWS[this.wireFormats.output];
+    if (!law || typeof event.delta !== 'string') return event;
+    const encoded = Uint8Array.from(atob(event.delta), (char) => char.charCodeAt(0));
+    const pcm16 = decodeG711(encoded, law);
+    return { ...event, delta: this.bytesToBase64(new Uint8Array(pcm16.buffer)) };
+  }
+
+  private bytesToBase64(bytes: Uint8Array): string {
     let binary = '';
     
     // Process in chunks to avoid stack overflow for large buffers
     const chunkSize = 8192;
     for (let i = 0; i < bytes.length; i += chunkSize) {
       const chunk = bytes.subarray(i, i + chunkSize);
-      binary += String.fromCharCode.apply(null, Array.from(chunk));
+      binary += String.fromCharCode.apply(null, chunk as unknown as number[]);
     }
     
     return btoa(binary);
@@ -320,13 +1334,13 @@ export class OpenAIRealtimeService {
 
   getStatus(): RealtimeStatus {
     // Check if API key is available
-    const apiKey = import.meta.env.VITE_OPENAI_API_KEY || 
-                   localStorage.getItem('openai_api_key') ||
-                   (globalThis as any).OPENAI_API_KEY;
+    const apiKey = this.getApiKey();
     
     return {
-      apiKey: apiKey ? 'configured' : 'missing',
+      apiKey: apiKey || this.usesMockServer() ? 'configured' : 'missing',
+      provider: this.getAzureConfig() ? 'azure' : 'openai',
       connected: this.isConnected,
+      reconnecting: this.isReconnecting(),
       sessionId: this.sessionId,
     };
   }
@@ -354,6 +1368,31 @@ export class OpenAIRealtimeService {
     }
   }
 
+  // Lets consumers drop deltas from interrupted responses and detect reordering
+  private withDeltaOrder(event: any): any {
+    const order: ResponseDeltaOrder = {
+      response_id: event.response_id ?? null,
+      item_id: event.item_id ?? null,
+      sequence: nextResponseSequence(),
+    };
+    return { ...event, ...order };
+  }
+
+  // Connect somewhere other than the real API, e.g. the mock server; null goes back to WebSocket.
+  // Takes effect on the next connect.
+  setTransport(factory: RealtimeTransportFactory | null): void {
+    this.transportFactory = factory ?? webSocketTransport;
+  }
+
+  private usesMockServer(): boolean {
+    return this.transportFactory !== webSocketTransport;
+  }
+
+  // Deliver an event that didn't come from the server, such as a cached answer
+  dispatchLocalEvent(type: OpenAIEvent['type'], data: any): void {
+    this.emit(type, data);
+  }
+
   private emit(type: OpenAIEvent['type'], data: any): void {
     const event: OpenAIEvent = { type, data };
     const handlers = this.eventHandlers.get(type);
@@ -365,3 +1404,10 @@ export class OpenAIRealtimeService {
 
 // Export singleton instance
 export const openaiRealtimeService = new OpenAIRealtimeService();
+
+// EVA_MOCK_REALTIME swaps the API for canned responses, for testing without a key
+loadMockRealtimeServer().then((factory) => {
+  if (factory) {
+    openaiRealtimeService.setTransport(factory);
+  }
+});
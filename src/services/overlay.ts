@@ -0,0 +1,33 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors overlay::OverlaySettings on the Rust side
+export type PositionPreset = "top-left" | "top-right" | "bottom-left" | "bottom-right" | "center";
+
+export interface OverlaySettings {
+  enabled: boolean;
+  click_through: boolean;
+  position: PositionPreset | null; // null keeps wherever the window was dragged
+}
+
+export async function getOverlaySettings(): Promise<OverlaySettings> {
+  const settings = await invoke<{ overlay: OverlaySettings }>("get_settings");
+  return settings.overlay;
+}
+
+// Called whenever overlay mode or its options change; returns the unlisten function
+export function onOverlayChanged(callback: (settings: OverlaySettings) => void): Promise<() => void> {
+  return listen<OverlaySettings>("overlay-changed", (event) => callback(event.payload));
+}
+
+export function setOverlayMode(enabled: boolean): Promise<string> {
+  return invoke<string>("set_overlay_mode", { enabled });
+}
+
+export function setClickThrough(enabled: boolean): Promise<string> {
+  return invoke<string>("set_click_through", { enabled });
+}
+
+export function setWindowPositionPreset(preset: PositionPreset | null): Promise<string> {
+  return invoke<string>("set_window_position_preset", { preset });
+}
@@ -0,0 +1,40 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { audioPlaybackService } from "./audio-playback";
+
+// Mirrors playback::PlaybackSettings / OutputDevice on the Rust side
+export interface PlaybackSettings {
+  output_device: string | null;
+  volume: number;
+}
+
+export interface OutputDevice {
+  name: string;
+  is_default: boolean;
+}
+
+function apply(settings: PlaybackSettings): void {
+  audioPlaybackService.setVolume(settings.volume);
+  audioPlaybackService
+    .setOutputDevice(settings.output_device)
+    .catch((error) => console.error("Failed to set output device:", error));
+}
+
+// Apply saved playback settings and follow changes; returns the unlisten function
+export async function startPlaybackSettings(): Promise<() => void> {
+  const settings = await invoke<{ playback: PlaybackSettings }>("get_settings");
+  apply(settings.playback);
+  return listen<PlaybackSettings>("playback-settings-changed", (event) => apply(event.payload));
+}
+
+export function listOutputDevices(): Promise<OutputDevice[]> {
+  return invoke<OutputDevice[]>("list_output_devices");
+}
+
+export function setOutputDevice(name: string | null): Promise<string> {
+  return invoke<string>("set_output_device", { name });
+}
+
+export function setOutputVolume(volume: number): Promise<string> {
+  return invoke<string>("set_output_volume", { volume });
+}
@@ -0,0 +1,39 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors power::PowerProfile on the Rust side
+export type PowerProfile = "auto" | "performance" | "power-saver";
+
+// Payload of the `power-state` event
+export interface PowerState {
+  profile: PowerProfile;
+  on_battery: boolean;
+  saving: boolean;
+}
+
+export function getPowerState(): Promise<PowerState> {
+  return invoke<PowerState>("get_power_state");
+}
+
+export function setPowerProfile(profile: PowerProfile): Promise<string> {
+  return invoke<string>("set_power_profile", { profile });
+}
+
+export function onPowerState(callback: (state: PowerState) => void): Promise<() => void> {
+  return listen<PowerState>("power-state", (event) => callback(event.payload));
+}
+
+// Mirrors sleep_inhibit::SleepInhibitStatus on the Rust side
+export interface SleepInhibitStatus {
+  enabled: boolean;
+  reasons: ("conversation" | "transcription")[];
+  active: boolean;
+}
+
+export function getSleepInhibit(): Promise<SleepInhibitStatus> {
+  return invoke<SleepInhibitStatus>("get_sleep_inhibit");
+}
+
+export function setSleepInhibit(enabled: boolean): Promise<string> {
+  return invoke<string>("set_sleep_inhibit", { enabled });
+}
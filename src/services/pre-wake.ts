@@ -0,0 +1,27 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors pre_wake::PreWakeAudio on the Rust side
+interface PreWakeAudio {
+  audio: string; // base64 PCM16 at 24kHz
+  sample_rate: number;
+  duration_ms: number;
+}
+
+// Audio the wake word stream captured before the frontend opened its own microphone stream
+export async function takePreWakeAudio(): Promise<ArrayBuffer | null> {
+  try {
+    const preWake = await invoke<PreWakeAudio | null>("take_pre_wake_audio");
+    if (!preWake) return null;
+
+    const binary = atob(preWake.audio);
+    const bytes = new Uint8Array(binary.length);
+    for (let i = 0; i < binary.length; i++) {
+      bytes[i] = binary.charCodeAt(i);
+    }
+    console.log(`⏪ Prepending ${preWake.duration_ms}ms of pre-wake audio`);
+    return bytes.buffer;
+  } catch (error) {
+    console.error("Failed to take pre-wake audio:", error);
+    return null;
+  }
+}
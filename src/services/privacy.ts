@@ -0,0 +1,35 @@
+import { invoke } from "@tauri-apps/api/core";
+import { currentConversationId, startConversation } from "./conversations";
+
+// The stored-data half of privacy::PrivacySettings on the Rust side
+export interface DataPrivacy {
+  never_store_audio: boolean;
+  transcript_retention_days: number; // 0 keeps transcripts
+}
+
+export interface PurgeReport {
+  conversations: number;
+  debug_recordings: number;
+}
+
+export async function getDataPrivacy(): Promise<DataPrivacy> {
+  const settings = await invoke<{ privacy: DataPrivacy }>("get_settings");
+  return settings.privacy;
+}
+
+export function setDataPrivacy(privacy: DataPrivacy): Promise<string> {
+  return invoke<string>("set_data_privacy", {
+    neverStoreAudio: privacy.never_store_audio,
+    transcriptRetentionDays: privacy.transcript_retention_days,
+  });
+}
+
+// Wipes saved conversations, debug audio, memory and cached answers
+export async function purgeAllData(): Promise<PurgeReport> {
+  const report = await invoke<PurgeReport>("purge_all_data");
+  // The running conversation's archive went with the rest; keep archiving in a fresh one
+  if (currentConversationId()) {
+    await startConversation();
+  }
+  return report;
+}
@@ -0,0 +1,25 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors proactive::ProactiveSettings on the Rust side
+export interface TimedGreeting {
+  at: string; // local "HH:MM"
+  message: string;
+}
+
+export interface ProactiveSettings {
+  enabled: boolean;
+  greet_on_start: boolean;
+  greetings: TimedGreeting[];
+  idle_minutes: number; // 0 turns the idle check-in off
+  min_interval_minutes: number;
+  max_per_day: number;
+}
+
+export async function getProactiveSettings(): Promise<ProactiveSettings> {
+  const settings = await invoke<{ proactive: ProactiveSettings }>("get_settings");
+  return settings.proactive;
+}
+
+export function setProactiveSettings(settings: ProactiveSettings): Promise<string> {
+  return invoke<string>("set_proactive_settings", { settings });
+}
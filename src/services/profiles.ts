@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors profiles::REALTIME_VOICES on the Rust side
+export const REALTIME_VOICES = ["alloy", "ash", "ballad", "coral", "echo", "sage", "shimmer", "verse"];
+
+// Mirrors profiles::AssistantProfile on the Rust side
+export interface AssistantProfile {
+  name: string;
+  keyword_path: string; // empty keeps the configured wake word
+  persona: string; // empty keeps the active persona
+  voice: string; // empty for the default
+  tools: string[] | null; // null offers every tool
+  apps: string[]; // executable names that switch to this profile
+}
+
+export interface ProfileSettings {
+  profiles: AssistantProfile[];
+  active: string; // empty when no profile is chosen
+  auto_switch: boolean;
+}
+
+// Payload of the `profile-changed` event
+export interface ActiveProfile {
+  active: string | null;
+  automatic: boolean;
+  voice: string;
+  tools: string[] | null;
+}
+
+export function getProfiles(): Promise<ProfileSettings> {
+  return invoke<ProfileSettings>("get_profiles");
+}
+
+export function getActiveProfile(): Promise<ActiveProfile> {
+  return invoke<ActiveProfile>("get_active_profile");
+}
+
+export function saveProfile(profile: AssistantProfile): Promise<string> {
+  return invoke<string>("save_profile", { profile });
+}
+
+export function deleteProfile(name: string): Promise<string> {
+  return invoke<string>("delete_profile", { name });
+}
+
+// An empty name goes back to the regular settings
+export function switchProfile(name: string): Promise<string> {
+  return invoke<string>("switch_profile", { name });
+}
+
+export function setProfileAutoSwitch(enabled: boolean): Promise<string> {
+  return invoke<string>("set_profile_auto_switch", { enabled });
+}
+
+export function onProfileChanged(callback: (profile: ActiveProfile) => void): Promise<() => void> {
+  return listen<ActiveProfile>("profile-changed", (event) => callback(event.payload));
+}
@@ -0,0 +1,63 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+
+// Mirrors proxy::ProxySettings on the Rust side
+export type ProxyMode = "system" | "off" | "manual";
+
+export interface ProxySettings {
+  mode: ProxyMode;
+  url: string; // http://, socks5:// or socks5h://
+  username: string | null;
+  ca_bundle_path: string; // PEM file of extra root certificates, empty for none
+}
+
+interface ProxiedResponse {
+  status: number;
+  status_text: string;
+  body: string;
+}
+
+// The webview only knows the system proxy and certificate store, so a manual proxy or a
+// custom CA means going through the Rust side
+let relayThroughBackend = false;
+
+function needsRelay(proxy: ProxySettings): boolean {
+  return proxy.mode === "manual" || proxy.ca_bundle_path !== "";
+}
+
+export function usesProxyRelay(): boolean {
+  return relayThroughBackend;
+}
+
+export function getProxy(): Promise<ProxySettings> {
+  return invoke<ProxySettings>("get_proxy");
+}
+
+// A null password keeps the stored one, an empty string removes it
+export function setProxy(proxy: ProxySettings, password: string | null): Promise<string> {
+  return invoke<string>("set_proxy", { proxy, password });
+}
+
+export function onProxyChanged(callback: (proxy: ProxySettings) => void): Promise<UnlistenFn> {
+  return listen<ProxySettings>("proxy-changed", (event) => callback(event.payload));
+}
+
+// fetch() for OpenAI's REST API that follows the manual proxy and custom CA when set
+export async function proxiedFetch(url: string, init: RequestInit = {}): Promise<Response> {
+  if (!relayThroughBackend) {
+    return fetch(url, init);
+  }
+
+  const response = await invoke<ProxiedResponse>("proxied_request", {
+    url,
+    method: init.method ?? "GET",
+    headers: Object.fromEntries(new Headers(init.headers).entries()),
+    body: typeof init.body === "string" ? init.body : null,
+  });
+  return new Response(response.body, { status: response.status, statusText: response.status_text });
+}
+
+getProxy()
+  .then((proxy) => (relayThroughBackend = needsRelay(proxy)))
+  .catch((error) => console.error("Failed to load proxy settings:", error));
+onProxyChanged((proxy) => (relayThroughBackend = needsRelay(proxy)));
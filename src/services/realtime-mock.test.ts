@@ -0,0 +1,215 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { MockRealtimeScript, MockRealtimeServer } from "./realtime-mock";
+import { OpenAIRealtimeService } from "./openai-simple";
+import { OpenAIRealtimeBackend } from "./backend/openai-realtime";
+import { BackendEvent } from "./backend/types";
+
+// No tools, offline intents or cached answers, so every question reaches the server
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(async (command: string) => (command === "list_tools" ? [] : null)),
+}));
+vi.mock("@tauri-apps/api/event", () => ({ emit: vi.fn(), listen: vi.fn() }));
+
+const SCRIPT: MockRealtimeScript = [
+  [
+    { type: "response.created" },
+    { type: "response.audio_transcript.delta", delta: "Hello there", delay_ms: 50 },
+    { type: "response.done", response: { status: "completed", output: [] }, delay_ms: 50 },
+  ],
+];
+
+// Base64 PCM16 at the capture rate, every sample at `amplitude`
+function pcm16(amplitude: number, ms: number): string {
+  const bytes = new Uint8Array(new Int16Array((24000 * ms) / 1000).fill(amplitude).buffer);
+  let binary = "";
+  for (const byte of bytes) {
+    binary += String.fromCharCode(byte);
+  }
+  return btoa(binary);
+}
+
+// Open a session the way the client does and collect every server event
+function connect() {
+  const server = new MockRealtimeServer(SCRIPT, { index: 0 });
+  const received: any[] = [];
+  server.onmessage = (event) => received.push(JSON.parse(event.data));
+  vi.runAllTimers();
+  const send = (event: object) => server.send(JSON.stringify(event));
+  return { server, received, send };
+}
+
+function useNodeGlobals() {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    // Node has no WebSocket; the mock only needs its ready states
+    vi.stubGlobal("WebSocket", { CONNECTING: 0, OPEN: 1, CLOSING: 2, CLOSED: 3 });
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+    vi.unstubAllGlobals();
+  });
+}
+
+describe("MockRealtimeServer", () => {
+  useNodeGlobals();
+
+  it("answers speech followed by silence with the scripted response", () => {
+    const { received, send } = connect();
+    expect(received.map((event) => event.type)).toEqual(["session.created"]);
+
+    send({ type: "input_audio_buffer.append", audio: pcm16(8000, 200) });
+    send({ type: "input_audio_buffer.append", audio: pcm16(0, 600) });
+    vi.runAllTimers();
+
+    expect(received.map((event) => event.type)).toEqual([
+      "session.created",
+      "input_audio_buffer.speech_started",
+      "input_audio_buffer.speech_stopped",
+      "input_audio_buffer.committed",
+      "conversation.item.created",
+      "response.created",
+      "response.audio_transcript.delta",
+      "response.done",
+    ]);
+
+    const [created, delta, done] = received.slice(-3);
+    expect(delta.delta).toBe("Hello there");
+    expect(delta.response_id).toBe(created.response.id);
+    expect(done.response).toMatchObject({ id: created.response.id, status: "completed" });
+  });
+
+  it("waits for the client to commit when turn detection is off", () => {
+    const { received, send } = connect();
+    send({ type: "session.update", session: { turn_detection: null } });
+    send({ type: "input_audio_buffer.append", audio: pcm16(8000, 200) });
+    send({ type: "input_audio_buffer.append", audio: pcm16(0, 600) });
+    vi.runAllTimers();
+    expect(received.map((event) => event.type)).toEqual(["session.created", "session.updated"]);
+
+    send({ type: "input_audio_buffer.commit" });
+    send({ type: "response.create" });
+    vi.runAllTimers();
+
+    expect(received.map((event) => event.type).slice(2)).toEqual([
+      "input_audio_buffer.committed",
+      "conversation.item.created",
+      "response.created",
+      "response.audio_transcript.delta",
+      "response.done",
+    ]);
+  });
+});
+
+const TEXT_SCRIPT: MockRealtimeScript = [
+  [
+    { type: "response.created" },
+    { type: "response.text.delta", delta: "It's ", delay_ms: 50 },
+    { type: "response.text.delta", delta: "noon.", delay_ms: 50 },
+    { type: "response.done", response: { status: "completed", output: [] }, delay_ms: 50 },
+  ],
+];
+
+const EVENT_TYPES: BackendEvent["type"][] = [
+  "session.created",
+  "input_audio_buffer.speech_started",
+  "input_audio_buffer.speech_stopped",
+  "input_audio_buffer.committed",
+  "conversation.item.created",
+  "response.created",
+  "response.text.delta",
+  "response.done",
+];
+
+// The real service behind the LlmBackend boundary, talking to the mock instead of the API
+async function connectBackend() {
+  const service = new OpenAIRealtimeService();
+  service.setTransport(() => new MockRealtimeServer(TEXT_SCRIPT, { index: 0 }));
+  const backend = new OpenAIRealtimeBackend(service);
+
+  const received: BackendEvent[] = [];
+  EVENT_TYPES.forEach((type) => backend.on(type, (event) => received.push(event)));
+
+  const connecting = backend.connect();
+  await vi.advanceTimersByTimeAsync(0);
+  await connecting;
+  return { backend, received };
+}
+
+describe("OpenAIRealtimeBackend against the mock server", () => {
+  useNodeGlobals();
+
+  beforeEach(() => {
+    const storage = new Map<string, string>();
+    vi.stubGlobal("localStorage", {
+      getItem: (key: string) => storage.get(key) ?? null,
+      setItem: (key: string, value: string) => storage.set(key, value),
+      removeItem: (key: string) => storage.delete(key),
+    });
+    vi.stubGlobal(
+      "CloseEvent",
+      class extends Event {
+        readonly code: number;
+        readonly reason: string;
+        constructor(type: string, init: { code?: number; reason?: string } = {}) {
+          super(type);
+          this.code = init.code ?? 0;
+          this.reason = init.reason ?? "";
+        }
+      }
+    );
+  });
+
+  it("answers a typed question with ordered text deltas", async () => {
+    const { backend, received } = await connectBackend();
+    expect(backend.getStatus().connected).toBe(true);
+    expect(received.map((event) => event.type)).toEqual(["session.created"]);
+
+    await backend.sendText("What time is it?");
+    await vi.advanceTimersByTimeAsync(200);
+
+    expect(received.map((event) => event.type)).toEqual([
+      "session.created",
+      "conversation.item.created",
+      "response.created",
+      "response.text.delta",
+      "response.text.delta",
+      "response.done",
+    ]);
+
+    const [, item, created, first, second, done] = received.map((event) => event.data);
+    expect(item.item).toMatchObject({ role: "user", content: [{ type: "input_text", text: "What time is it?" }] });
+    expect([first.delta, second.delta]).toEqual(["It's ", "noon."]);
+    expect(first.response_id).toBe(created.response.id);
+    expect(second.sequence).toBeGreaterThan(first.sequence);
+    expect(done.response).toMatchObject({ id: created.response.id, status: "completed" });
+
+    await backend.disconnect();
+    expect(backend.getStatus().connected).toBe(false);
+  });
+
+  it("ends a spoken turn with server VAD and answers it", async () => {
+    const { backend, received } = await connectBackend();
+    expect(backend.capabilities.serverTurnDetection).toBe(true);
+
+    const speech = Uint8Array.from(atob(pcm16(8000, 200)), (c) => c.charCodeAt(0));
+    const silence = Uint8Array.from(atob(pcm16(0, 600)), (c) => c.charCodeAt(0));
+    await backend.sendAudio(speech.buffer);
+    await backend.sendAudio(silence.buffer);
+    await vi.advanceTimersByTimeAsync(1000);
+
+    expect(received.map((event) => event.type)).toEqual([
+      "session.created",
+      "input_audio_buffer.speech_started",
+      "input_audio_buffer.speech_stopped",
+      "input_audio_buffer.committed",
+      "conversation.item.created",
+      "response.created",
+      "response.text.delta",
+      "response.text.delta",
+      "response.done",
+    ]);
+
+    await backend.disconnect();
+  });
+});
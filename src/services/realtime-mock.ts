@@ -0,0 +1,231 @@
+import { invoke } from "@tauri-apps/api/core";
+import { RealtimeTransport, RealtimeTransportFactory } from "./realtime-transport";
+
+// A Realtime API server event, optionally held back for `delay_ms` after the previous one
+export interface MockServerEvent {
+  type: string;
+  delay_ms?: number;
+  [field: string]: any;
+}
+
+// One canned response per entry, played in order and then from the start again
+export type MockRealtimeScript = MockServerEvent[][];
+
+// Used when EVA_MOCK_REALTIME doesn't name a script
+const DEFAULT_SCRIPT: MockRealtimeScript = [
+  [
+    { type: "response.created", delay_ms: 100 },
+    { type: "response.text.delta", delta: "This is a canned reply ", delay_ms: 100 },
+    { type: "response.text.delta", delta: "from the mock Realtime server.", delay_ms: 100 },
+    { type: "response.text.done", text: "This is a canned reply from the mock Realtime server." },
+    { type: "response.done", response: { status: "completed", output: [] } },
+  ],
+];
+
+// Rough stand-in for server VAD: PCM16 peaks above this count as speech...
+const SPEECH_THRESHOLD = 1000;
+// ...and the turn ends after this much audio without any, unless the session sets its own
+const SPEECH_END_SILENCE_MS = 500;
+const INPUT_SAMPLE_RATE = 24000;
+
+// Answers the Realtime client like the API would, replaying a script instead of asking a model.
+// Nothing leaves the machine and no API key is needed, so conversations run deterministically.
+export class MockRealtimeServer implements RealtimeTransport {
+  readyState: number = WebSocket.CONNECTING;
+  readonly bufferedAmount = 0;
+  onopen: ((event: Event) => void) | null = null;
+  onmessage: ((event: MessageEvent) => void) | null = null;
+  onerror: ((event: Event) => void) | null = null;
+  onclose: ((event: CloseEvent) => void) | null = null;
+
+  private nextEventId = 0;
+  private nextResponseId = 0;
+  private pendingTimers: ReturnType<typeof setTimeout>[] = [];
+  private activeResponseId: string | null = null;
+  private bufferedAudioMs = 0;
+  private speaking = false;
+  private silenceMs = 0;
+  // Like the API, a null `turn_detection` leaves committing to the client
+  private serverVad = true;
+  private speechEndSilenceMs = SPEECH_END_SILENCE_MS;
+
+  constructor(private readonly script: MockRealtimeScript, private turn: { index: number }) {
+    setTimeout(() => {
+      if (this.readyState !== WebSocket.CONNECTING) return;
+      this.readyState = WebSocket.OPEN;
+      this.onopen?.(new Event("open"));
+      this.deliver({ type: "session.created", session: { id: `sess_mock_${Date.now()}` } });
+    }, 0);
+  }
+
+  send(data: string): void {
+    if (this.readyState !== WebSocket.OPEN) return;
+    const event = JSON.parse(data);
+
+    switch (event.type) {
+      case "session.update":
+        this.updateTurnDetection(event.session ?? {});
+        this.deliver({ type: "session.updated", session: event.session ?? {} });
+        break;
+      case "conversation.item.create":
+        this.deliver({
+          type: "conversation.item.created",
+          previous_item_id: event.previous_item_id ?? null,
+          item: { id: `item_mock_created_${this.nextEventId}`, ...event.item },
+        });
+        break;
+      case "conversation.item.delete":
+        this.deliver({ type: "conversation.item.deleted", item_id: event.item_id });
+        break;
+      case "input_audio_buffer.append":
+        this.receiveAudio(event.audio ?? "");
+        break;
+      case "input_audio_buffer.commit":
+        this.commit();
+        break;
+      case "response.create":
+        this.respond();
+        break;
+      case "response.cancel":
+        this.cancel();
+        break;
+      case "conversation.item.truncate":
+        this.deliver({
+          type: "conversation.item.truncated",
+          item_id: event.item_id,
+          content_index: event.content_index,
+          audio_end_ms: event.audio_end_ms,
+        });
+        break;
+    }
+  }
+
+  close(): void {
+    if (this.readyState === WebSocket.CLOSED) return;
+    this.pendingTimers.forEach(clearTimeout);
+    this.pendingTimers = [];
+    this.readyState = WebSocket.CLOSED;
+    this.onclose?.(new CloseEvent("close", { code: 1000, reason: "Mock server closed" }));
+  }
+
+  private deliver(event: object): void {
+    if (this.readyState !== WebSocket.OPEN) return;
+    const data = JSON.stringify({ event_id: `event_mock_${++this.nextEventId}`, ...event });
+    this.onmessage?.(new MessageEvent("message", { data }));
+  }
+
+  private receiveAudio(base64: string): void {
+    const bytes = Uint8Array.from(atob(base64), (c) => c.charCodeAt(0));
+    const samples = new Int16Array(bytes.buffer, 0, bytes.length >> 1);
+    let peak = 0;
+    for (let i = 0; i < samples.length; i++) {
+      peak = Math.max(peak, Math.abs(samples[i]));
+    }
+    const chunkMs = (samples.length * 1000) / INPUT_SAMPLE_RATE;
+    this.bufferedAudioMs += chunkMs;
+    if (!this.serverVad) return;
+
+    if (peak >= SPEECH_THRESHOLD) {
+      this.silenceMs = 0;
+      if (!this.speaking) {
+        this.speaking = true;
+        this.deliver({ type: "input_audio_buffer.speech_started", audio_start_ms: Math.round(this.bufferedAudioMs) });
+      }
+    } else if (this.speaking) {
+      this.silenceMs += chunkMs;
+      if (this.silenceMs >= this.speechEndSilenceMs) {
+        this.deliver({ type: "input_audio_buffer.speech_stopped", audio_end_ms: Math.round(this.bufferedAudioMs) });
+        this.commit();
+        this.respond();
+      }
+    }
+  }
+
+  private updateTurnDetection(session: Record<string, any>): void {
+    if (!("turn_detection" in session)) return;
+    this.serverVad = session.turn_detection !== null;
+    this.speechEndSilenceMs = session.turn_detection?.silence_duration_ms ?? SPEECH_END_SILENCE_MS;
+  }
+
+  private commit(): void {
+    if (this.bufferedAudioMs === 0) {
+      this.deliver({
+        type: "error",
+        error: { type: "invalid_request_error", code: "input_audio_buffer_commit_empty", message: "Buffer is empty" },
+      });
+      return;
+    }
+    const itemId = `item_mock_${this.nextEventId}`;
+    this.deliver({ type: "input_audio_buffer.committed", item_id: itemId });
+    this.deliver({ type: "conversation.item.created", item: { id: itemId, type: "message", role: "user" } });
+    this.bufferedAudioMs = 0;
+    this.speaking = false;
+    this.silenceMs = 0;
+  }
+
+  // Play the next scripted response, filling in the ids a real server would assign
+  private respond(): void {
+    if (this.script.length === 0) return;
+    const events = this.script[this.turn.index % this.script.length];
+    this.turn.index++;
+
+    const responseId = `resp_mock_${++this.nextResponseId}`;
+    const itemId = `item_mock_${responseId}`;
+    this.activeResponseId = responseId;
+
+    let at = 0;
+    for (const { delay_ms, ...event } of events) {
+      at += delay_ms ?? 0;
+      const timer = setTimeout(() => {
+        this.pendingTimers = this.pendingTimers.filter((pending) => pending !== timer);
+        if (this.activeResponseId !== responseId) return;
+        if (event.type === "response.done") {
+          this.activeResponseId = null;
+        }
+        this.deliver(this.withIds(event, responseId, itemId));
+      }, at);
+      this.pendingTimers.push(timer);
+    }
+  }
+
+  private withIds(event: Record<string, any>, responseId: string, itemId: string): object {
+    if (event.type === "response.created" || event.type === "response.done") {
+      return { ...event, response: { id: responseId, ...event.response } };
+    }
+    if (event.type.startsWith("response.")) {
+      return { response_id: responseId, item_id: itemId, ...event };
+    }
+    return event;
+  }
+
+  private cancel(): void {
+    if (!this.activeResponseId) {
+      this.deliver({
+        type: "error",
+        error: { type: "invalid_request_error", code: "response_cancel_not_active", message: "No active response" },
+      });
+      return;
+    }
+    const responseId = this.activeResponseId;
+    this.pendingTimers.forEach(clearTimeout);
+    this.pendingTimers = [];
+    this.activeResponseId = null;
+    this.deliver({ type: "response.done", response: { id: responseId, status: "cancelled", status_details: { type: "cancelled", reason: "client_cancelled" }, output: [] } });
+  }
+}
+
+// The mock server, if EVA_MOCK_REALTIME is set; the script carries on across reconnects
+export async function loadMockRealtimeServer(): Promise<RealtimeTransportFactory | null> {
+  try {
+    const script = await invoke<MockRealtimeScript | null>("get_mock_realtime_script");
+    if (!script) return null;
+
+    const turn = { index: 0 };
+    const replies = script.length > 0 ? script : DEFAULT_SCRIPT;
+    console.warn(`🧪 Using the mock Realtime server (${replies.length} scripted responses)`);
+    return () => new MockRealtimeServer(replies, turn);
+  } catch (error) {
+    console.error("Failed to load the mock Realtime script:", error);
+    return null;
+  }
+}
@@ -0,0 +1,115 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+
+// The part of WebSocket the Realtime service uses, so the server at the other end can be swapped out
+export interface RealtimeTransport {
+  readonly readyState: number; // WebSocket.CONNECTING / OPEN / CLOSING / CLOSED
+  readonly bufferedAmount: number;
+  onopen: ((event: Event) => void) | null;
+  onmessage: ((event: MessageEvent) => void) | null;
+  onerror: ((event: Event) => void) | null;
+  onclose: ((event: CloseEvent) => void) | null;
+  send(data: string): void;
+  close(): void;
+}
+
+export type RealtimeTransportFactory = (url: string, protocols: string[]) => RealtimeTransport;
+
+export const webSocketTransport: RealtimeTransportFactory = (url, protocols) =>
+  new WebSocket(url, protocols);
+
+// Mirrors realtime_relay::RelayEvent on the Rust side
+type RelayEvent =
+  | { kind: "message"; id: number; data: string }
+  | { kind: "error"; id: number; message: string }
+  | { kind: "closed"; id: number; code: number; reason: string };
+
+// The WebSocket opened by the Rust side instead, which can reach the API through a manual proxy
+class RelayTransport implements RealtimeTransport {
+  readyState: number = WebSocket.CONNECTING;
+  bufferedAmount = 0;
+  onopen: ((event: Event) => void) | null = null;
+  onmessage: ((event: MessageEvent) => void) | null = null;
+  onerror: ((event: Event) => void) | null = null;
+  onclose: ((event: CloseEvent) => void) | null = null;
+
+  private id: number | null = null;
+  // Events can arrive before the connect call returns the id they belong to
+  private early: RelayEvent[] = [];
+  private unlisten: Promise<UnlistenFn>;
+  // Each send waits for the previous one so events reach the server in order
+  private sending: Promise<unknown> = Promise.resolve();
+
+  constructor(url: string, protocols: string[]) {
+    this.unlisten = listen<RelayEvent>("realtime-relay", ({ payload }) => {
+      if (this.id === null) {
+        this.early.push(payload);
+      } else if (payload.id === this.id) {
+        this.handle(payload);
+      }
+    });
+
+    this.unlisten
+      .then(() => invoke<number>("realtime_relay_connect", { url, protocols }))
+      .then((id) => {
+        this.id = id;
+        if (this.readyState === WebSocket.CLOSING) {
+          invoke("realtime_relay_close", { id });
+          return;
+        }
+        this.readyState = WebSocket.OPEN;
+        this.onopen?.(new Event("open"));
+        this.early.filter((event) => event.id === id).forEach((event) => this.handle(event));
+        this.early = [];
+      })
+      .catch((error) => {
+        console.error("Realtime relay failed to connect:", error);
+        this.onerror?.(new Event("error"));
+        this.finish(1006, String(error));
+      });
+  }
+
+  send(data: string): void {
+    if (this.readyState !== WebSocket.OPEN || this.id === null) return;
+    const id = this.id;
+    this.bufferedAmount += data.length;
+    this.sending = this.sending
+      .then(() => invoke("realtime_relay_send", { id, data }))
+      .catch((error) => console.error("Realtime relay send failed:", error))
+      .finally(() => (this.bufferedAmount -= data.length));
+  }
+
+  close(): void {
+    if (this.readyState === WebSocket.CLOSING || this.readyState === WebSocket.CLOSED) return;
+    const connecting = this.readyState === WebSocket.CONNECTING;
+    this.readyState = WebSocket.CLOSING;
+    if (!connecting && this.id !== null) {
+      invoke("realtime_relay_close", { id: this.id });
+    }
+  }
+
+  private handle(event: RelayEvent): void {
+    switch (event.kind) {
+      case "message":
+        this.onmessage?.(new MessageEvent("message", { data: event.data }));
+        break;
+      case "error":
+        console.error("Realtime relay error:", event.message);
+        this.onerror?.(new Event("error"));
+        break;
+      case "closed":
+        this.finish(event.code, event.reason);
+        break;
+    }
+  }
+
+  private finish(code: number, reason: string): void {
+    if (this.readyState === WebSocket.CLOSED) return;
+    this.readyState = WebSocket.CLOSED;
+    this.unlisten.then((unlisten) => unlisten());
+    this.onclose?.(new CloseEvent("close", { code, reason }));
+  }
+}
+
+export const relayTransport: RealtimeTransportFactory = (url, protocols) =>
+  new RelayTransport(url, protocols);
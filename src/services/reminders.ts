@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors reminders::Reminder on the Rust side
+export type ReminderKind = "timer" | "alarm" | "reminder";
+
+export interface Reminder {
+  id: string;
+  kind: ReminderKind;
+  label: string;
+  due_at: number; // unix ms
+  created_at: number; // unix ms
+}
+
+export function listReminders(): Promise<Reminder[]> {
+  return invoke<Reminder[]>("list_reminders");
+}
+
+export function cancelReminder(id: string): Promise<string> {
+  return invoke<string>("cancel_reminder", { id });
+}
+
+// Pending reminders, soonest first, whenever one is added, cancelled or fires; returns the unlisten function
+export function onRemindersChanged(handler: (reminders: Reminder[]) => void): Promise<() => void> {
+  return listen<Reminder[]>("reminders-changed", (event) => handler(event.payload));
+}
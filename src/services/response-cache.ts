@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type CachedQueryKind = "unit-conversion" | "time-zone" | "weather";
+
+// Mirrors response_cache::CacheEntry on the Rust side
+export interface CachedResponse {
+  query: string; // normalized
+  kind: CachedQueryKind;
+  response: string;
+  created_at: number; // unix seconds
+  expires_at: number;
+  hits: number;
+}
+
+export interface ResponseCacheSettings {
+  enabled: boolean;
+  weather_ttl_minutes: number;
+  max_entries: number;
+}
+
+export async function getResponseCacheSettings(): Promise<ResponseCacheSettings> {
+  const settings = await invoke<{ response_cache: ResponseCacheSettings }>("get_settings");
+  return settings.response_cache;
+}
+
+export function setResponseCacheSettings(settings: ResponseCacheSettings): Promise<string> {
+  return invoke<string>("set_response_cache_settings", { settings });
+}
+
+// A fresh cached answer to `query`; null when caching is off or nothing matches
+export async function lookupCachedResponse(query: string): Promise<CachedResponse | null> {
+  try {
+    return await invoke<CachedResponse | null>("lookup_cached_response", { query });
+  } catch (error) {
+    console.error("Response cache lookup failed:", error);
+    return null;
+  }
+}
+
+// The Rust side decides whether the question is cacheable at all
+export function rememberResponse(query: string, response: string): Promise<boolean> {
+  return invoke<boolean>("remember_response", { query, response });
+}
+
+export function listCachedResponses(): Promise<CachedResponse[]> {
+  return invoke<CachedResponse[]>("list_cached_responses");
+}
+
+export function clearResponseCache(): Promise<string> {
+  return invoke<string>("clear_response_cache");
+}
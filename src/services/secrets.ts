@@ -0,0 +1,37 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors secret_migration::EnvSecretOffer on the Rust side
+export interface EnvSecretOffer {
+  env_var: string;
+  label: string;
+  replaces_stored: boolean;
+}
+
+export function getEnvSecretOffers(): Promise<EnvSecretOffer[]> {
+  return invoke<EnvSecretOffer[]>("get_env_secret_offers");
+}
+
+// Accepting copies them into the keychain; declining keeps using the environment without asking again
+export function answerEnvSecretOffers(accept: boolean): Promise<string> {
+  return invoke<string>("answer_env_secret_offers", { accept });
+}
+
+export function getStoredOpenAIKey(): Promise<string | null> {
+  return invoke<string | null>("get_stored_openai_key");
+}
+
+// Mirrors secrets::SecretBackend on the Rust side
+export type SecretBackend = "auto" | "keychain" | "file";
+
+export interface SecretStoreStatus {
+  backend: SecretBackend;
+  keychain_available: boolean;
+}
+
+export function getSecretStore(): Promise<SecretStoreStatus> {
+  return invoke<SecretStoreStatus>("get_secret_store");
+}
+
+export function setSecretBackend(backend: SecretBackend): Promise<string> {
+  return invoke<string>("set_secret_backend", { backend });
+}
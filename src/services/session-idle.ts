@@ -0,0 +1,23 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Mirrors session_idle::SessionIdleEvent on the Rust side
+export interface SessionIdleEvent {
+  idle_minutes: number;
+}
+
+// Restart the idle disconnect timer; the backend emits session-idle-timeout when it runs out
+export function touchSessionActivity(): void {
+  invoke("touch_session_activity").catch((error) =>
+    console.error("Failed to reset idle timer:", error)
+  );
+}
+
+export function stopSessionIdleTimer(): void {
+  invoke("stop_session_idle_timer").catch((error) =>
+    console.error("Failed to stop idle timer:", error)
+  );
+}
+
+export function setIdleDisconnect(enabled: boolean, idleMinutes: number): Promise<string> {
+  return invoke<string>("set_idle_disconnect", { enabled, idleMinutes });
+}
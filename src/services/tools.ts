@@ -0,0 +1,157 @@
+import { invoke } from "@tauri-apps/api/core";
+import { openaiRealtimeService } from "./openai-simple";
+import { proxiedFetch } from "./proxy";
+
+// Mirrors tools::ToolDefinition on the Rust side
+export interface ToolDefinition {
+  name: string;
+  description: string;
+  parameters: Record<string, unknown>;
+}
+
+// Mirrors tools::home_assistant::HomeAssistantStatus
+export interface HomeAssistantStatus {
+  url: string;
+  token_configured: boolean;
+}
+
+// Only tools that are configured right now; an empty list if the backend can't be reached
+export async function listTools(): Promise<ToolDefinition[]> {
+  try {
+    return await invoke<ToolDefinition[]>("list_tools");
+  } catch (error) {
+    console.error("Failed to list tools:", error);
+    return [];
+  }
+}
+
+// Mirrors tools::ToolOutput
+interface ToolOutput {
+  text: string;
+  image?: string; // base64 JPEG
+}
+
+const VISION_MODEL = "gpt-4o-mini";
+const CHAT_COMPLETIONS_URL = "https://api.openai.com/v1/chat/completions";
+
+const VISION_PROMPT = `Describe this screenshot for a voice assistant that will answer the user.
+Transcribe the important text exactly, and focus on what the user asks about.`;
+
+// Function call outputs are text only, so a screenshot is turned into a description first
+async function describeImage(image: string, context: string): Promise<string> {
+  const apiKey = openaiRealtimeService.getOpenAIApiKey();
+  if (!apiKey) {
+    throw new Error("Looking at the screen needs an OpenAI API key");
+  }
+
+  const response = await proxiedFetch(CHAT_COMPLETIONS_URL, {
+    method: "POST",
+    headers: {
+      "Content-Type": "application/json",
+      Authorization: `Bearer ${apiKey}`,
+    },
+    body: JSON.stringify({
+      model: VISION_MODEL,
+      messages: [
+        { role: "system", content: VISION_PROMPT },
+        {
+          role: "user",
+          content: [
+            { type: "text", text: context },
+            { type: "image_url", image_url: { url: `data:image/jpeg;base64,${image}` } },
+          ],
+        },
+      ],
+    }),
+  });
+  if (!response.ok) {
+    throw new Error(`Vision request failed: ${response.status} ${response.statusText}`);
+  }
+
+  const data = await response.json();
+  return data.choices?.[0]?.message?.content ?? "";
+}
+
+// Failures come back as text too, so the model can tell the user what went wrong
+export async function callTool(name: string, args: string): Promise<string> {
+  try {
+    const output = await invoke<ToolOutput>("call_tool", { name, arguments: args });
+    if (!output.image) return output.text;
+    return `${output.text}\n\n${await describeImage(output.image, output.text)}`;
+  } catch (error) {
+    return `Error: ${error}`;
+  }
+}
+
+export function getHomeAssistant(): Promise<HomeAssistantStatus> {
+  return invoke<HomeAssistantStatus>("get_home_assistant");
+}
+
+// An empty URL removes the integration; a null token keeps the stored one
+export function setHomeAssistant(url: string, token: string | null): Promise<string> {
+  return invoke<string>("set_home_assistant", { url, token });
+}
+
+// Mirrors tools::calendar::CalendarStatus
+export interface CalendarStatus {
+  url: string;
+  username: string | null;
+  password_configured: boolean;
+  last_refreshed: number | null; // unix ms
+  event_count: number;
+}
+
+export function getCalendar(): Promise<CalendarStatus> {
+  return invoke<CalendarStatus>("get_calendar");
+}
+
+// An empty URL removes the calendar; a null password keeps the stored one
+export function setCalendar(
+  url: string,
+  username: string | null,
+  password: string | null
+): Promise<string> {
+  return invoke<string>("set_calendar", { url, username, password });
+}
+
+export function refreshCalendar(): Promise<string> {
+  return invoke<string>("refresh_calendar");
+}
+
+// Mirrors tools::actions::AllowedAction
+export interface AllowedAction {
+  name: string;
+  kind: "app" | "shell";
+  target: string; // app name/path, or the shell snippet
+}
+
+export function getActions(): Promise<AllowedAction[]> {
+  return invoke<AllowedAction[]>("get_actions");
+}
+
+export function setActions(actions: AllowedAction[]): Promise<string> {
+  return invoke<string>("set_actions", { actions });
+}
+
+// Mirrors tools::plugins::PluginInfo
+export interface PluginInfo {
+  id: string;
+  name: string;
+  version: string;
+  description: string;
+  tools: string[];
+  enabled: boolean;
+  error: string | null; // why the plugin can't be loaded
+}
+
+export function listPlugins(): Promise<PluginInfo[]> {
+  return invoke<PluginInfo[]>("list_plugins");
+}
+
+export function setPluginEnabled(id: string, enabled: boolean): Promise<string> {
+  return invoke<string>("set_plugin_enabled", { id, enabled });
+}
+
+export function openPluginsFolder(): Promise<string> {
+  return invoke<string>("open_plugins_folder");
+}
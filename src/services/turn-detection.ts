@@ -0,0 +1,24 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors turn_detection::TurnDetectionSettings on the Rust side
+export type TurnDetectionMode = "server_vad" | "manual";
+
+export interface TurnDetectionSettings {
+  mode: TurnDetectionMode;
+  threshold: number;
+  prefix_padding_ms: number;
+  silence_duration_ms: number;
+}
+
+export function getTurnDetection(): Promise<TurnDetectionSettings> {
+  return invoke<TurnDetectionSettings>("get_turn_detection");
+}
+
+export function setTurnDetection(turnDetection: TurnDetectionSettings): Promise<string> {
+  return invoke<string>("set_turn_detection", { turnDetection });
+}
+
+export function onTurnDetectionChanged(callback: (settings: TurnDetectionSettings) => void): Promise<() => void> {
+  return listen<TurnDetectionSettings>("turn-detection-changed", (event) => callback(event.payload));
+}
@@ -0,0 +1,69 @@
+import { invoke } from "@tauri-apps/api/core";
+import { OpenAIEvent, openaiRealtimeService } from "./openai-simple";
+
+export interface TokenUsage {
+  text_input_tokens: number;
+  cached_input_tokens: number;
+  audio_input_tokens: number;
+  text_output_tokens: number;
+  audio_output_tokens: number;
+}
+
+export interface UsageTotals {
+  tokens: TokenUsage;
+  responses: number;
+  cost_usd: number;
+}
+
+export interface UsageStats {
+  today: UsageTotals;
+  month: UsageTotals;
+  all_time: UsageTotals;
+  days: Array<[string, UsageTotals]>;
+  monthly_budget_usd: number;
+}
+
+export interface BudgetWarning {
+  level: "approaching" | "exceeded";
+  month_cost_usd: number;
+  monthly_budget_usd: number;
+}
+
+// Realtime usage block: https://platform.openai.com/docs/api-reference/realtime-server-events/response/done
+function toTokenUsage(usage: any): TokenUsage {
+  const input = usage.input_token_details ?? {};
+  const output = usage.output_token_details ?? {};
+  return {
+    text_input_tokens: input.text_tokens ?? 0,
+    cached_input_tokens: input.cached_tokens ?? 0,
+    audio_input_tokens: input.audio_tokens ?? 0,
+    text_output_tokens: output.text_tokens ?? 0,
+    audio_output_tokens: output.audio_tokens ?? 0,
+  };
+}
+
+const handleResponseDone = (event: OpenAIEvent) => {
+  const usage = event.data?.response?.usage;
+  if (!usage) return;
+
+  invoke<UsageTotals>("record_usage", { usage: toTokenUsage(usage) })
+    .then((today) => console.log(`💸 Today's usage: $${today.cost_usd.toFixed(4)}`))
+    .catch((error) => console.error("Failed to record usage:", error));
+};
+
+let tracking = false;
+
+// Record token usage for every Realtime response; safe to call more than once
+export function startUsageTracking(): void {
+  if (tracking) return;
+  tracking = true;
+  openaiRealtimeService.on("response.done", handleResponseDone);
+}
+
+export function getUsageStats(): Promise<UsageStats> {
+  return invoke<UsageStats>("get_usage_stats");
+}
+
+export function setUsageBudget(monthlyBudgetUsd: number): Promise<string> {
+  return invoke<string>("set_usage_budget", { monthlyBudgetUsd });
+}
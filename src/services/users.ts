@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Mirrors users::UserStatus on the Rust side
+export interface UserStatus {
+  name: string;
+  persona: string; // empty keeps the active persona
+  has_api_key: boolean;
+}
+
+export interface UsersState {
+  users: UserStatus[];
+  active: string; // empty for the default user
+  switch_by_voice: boolean;
+}
+
+// Payload of the `user-changed` event
+export interface UserChanged {
+  active: string | null;
+  automatic: boolean;
+}
+
+export function getUsers(): Promise<UsersState> {
+  return invoke<UsersState>("get_users");
+}
+
+// `apiKey`: null keeps the user's key, empty goes back to the shared one
+export function saveUser(name: string, persona: string, apiKey: string | null): Promise<string> {
+  return invoke<string>("save_user", { user: { name, persona }, apiKey });
+}
+
+export function deleteUser(name: string): Promise<string> {
+  return invoke<string>("delete_user", { name });
+}
+
+// An empty name switches to the default user
+export function switchUser(name: string): Promise<string> {
+  return invoke<string>("switch_user", { name });
+}
+
+export function setUserSwitchByVoice(enabled: boolean): Promise<string> {
+  return invoke<string>("set_user_switch_by_voice", { enabled });
+}
+
+export function getUserApiKey(): Promise<string | null> {
+  return invoke<string | null>("get_user_api_key");
+}
+
+export function onUserChanged(callback: (user: UserChanged) => void): Promise<() => void> {
+  return listen<UserChanged>("user-changed", (event) => callback(event.payload));
+}
@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/core";
+import { audioCaptureService } from "./audio-capture";
+
+// Mirrors speaker_id::VoiceProfileStatus on the Rust side
+export interface VoiceProfileStatus {
+  enrolled: boolean;
+  enrollments: number;
+  voice_lock: boolean;
+  available: boolean;
+}
+
+export interface SpeakerRejectedEvent {
+  similarity: number;
+  threshold: number;
+}
+
+const CAPTURE_SAMPLE_RATE = 24000; // AudioCaptureService output rate
+export const ENROLLMENT_SECONDS = 6;
+
+export function getVoiceProfileStatus(): Promise<VoiceProfileStatus> {
+  return invoke<VoiceProfileStatus>("get_voice_profile_status");
+}
+
+// Record the user talking for a few seconds and add it to their voice profile
+export async function enrollVoice(
+  seconds: number = ENROLLMENT_SECONDS
+): Promise<VoiceProfileStatus> {
+  if (audioCaptureService.getRecordingState()) {
+    throw new Error("Stop the current recording before enrolling");
+  }
+
+  const chunks: Int16Array[] = [];
+  await audioCaptureService.startCapture((audioData) => {
+    chunks.push(new Int16Array(audioData));
+  });
+  await new Promise((resolve) => setTimeout(resolve, seconds * 1000));
+  await audioCaptureService.stopCapture();
+
+  const samples = new Int16Array(chunks.reduce((sum, chunk) => sum + chunk.length, 0));
+  let offset = 0;
+  for (const chunk of chunks) {
+    samples.set(chunk, offset);
+    offset += chunk.length;
+  }
+
+  return invoke<VoiceProfileStatus>("enroll_voice", {
+    samples: Array.from(samples),
+    sampleRate: CAPTURE_SAMPLE_RATE,
+  });
+}
+
+export function setVoiceLock(enabled: boolean): Promise<string> {
+  return invoke<string>("set_voice_lock", { enabled });
+}
+
+export function clearVoiceProfile(): Promise<string> {
+  return invoke<string>("clear_voice_profile");
+}
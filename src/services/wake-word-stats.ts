@@ -0,0 +1,102 @@
+import { invoke } from "@tauri-apps/api/core";
+import { getActiveBackend } from "./backend";
+
+// Mirrors wake_word::WakeWordStats on the Rust side
+export interface WakeWordStats {
+  detections: number;
+  false_triggers: number;
+  cooldown_skips: number;
+  utterance_skips: number;
+  frames_processed: number;
+  average_detection_amplitude: number;
+  average_detection_peak: number;
+}
+
+// Mirrors wake_word::WakeWordTestResult / WakeWordTestReport on the Rust side
+export interface WakeWordTestResult {
+  file: string;
+  duration_ms: number;
+  detections_ms: number[];
+  cooldown_skips: number;
+  utterance_skips: number;
+  error: string | null;
+}
+
+export interface WakeWordTestReport {
+  keyword_path: string | null; // null for the built-in keyword
+  sensitivity: number;
+  files: WakeWordTestResult[];
+  total_detections: number;
+}
+
+// Mirrors wake_cooldown::WakeCooldownSettings on the Rust side
+export interface WakeCooldownSettings {
+  default_ms: number;
+  per_keyword: Record<string, number>; // lowercase keyword name -> cooldown
+  suppress_same_utterance: boolean;
+  utterance_gap_ms: number;
+}
+
+// How long a wake activation may go without speech before it counts as a false trigger
+const FALSE_TRIGGER_TIMEOUT_MS = 8000;
+
+// Mirrors wake_word::AccessKeyValidation on the Rust side
+export interface AccessKeyValidation {
+  status: "valid" | "invalid_key" | "activation_limit_reached" | "network_needed" | "failed";
+  message: string;
+  hint: string | null;
+}
+
+// Checks a Picovoice key with Picovoice without storing it
+export function validatePicovoiceKey(key: string): Promise<AccessKeyValidation> {
+  return invoke<AccessKeyValidation>("validate_picovoice_key", { key });
+}
+
+export function getWakeWordStats(): Promise<WakeWordStats> {
+  return invoke<WakeWordStats>("get_wake_word_stats");
+}
+
+// Runs a WAV file, or every WAV in a directory, through detection offline;
+// an empty keyword path tests the configured model
+export function testWakeWord(
+  path: string,
+  keywordPath: string,
+  sensitivity: number
+): Promise<WakeWordTestReport> {
+  return invoke<WakeWordTestReport>("test_wake_word", {
+    path,
+    keywordPath: keywordPath || null,
+    sensitivity,
+  });
+}
+
+export function getWakeCooldown(): Promise<WakeCooldownSettings> {
+  return invoke<WakeCooldownSettings>("get_wake_cooldown");
+}
+
+export function setWakeCooldown(cooldown: WakeCooldownSettings): Promise<string> {
+  return invoke<string>("set_wake_cooldown", { cooldown });
+}
+
+export function resetWakeWordStats(): Promise<string> {
+  return invoke<string>("reset_wake_word_stats");
+}
+
+// Call on each wake word detection; reports a false trigger if nobody speaks afterwards
+export function watchForFalseTrigger(): void {
+  const backend = getActiveBackend();
+
+  const onSpeech = () => {
+    clearTimeout(timer);
+    backend.off("input_audio_buffer.speech_started", onSpeech);
+  };
+
+  const timer = setTimeout(() => {
+    backend.off("input_audio_buffer.speech_started", onSpeech);
+    invoke("report_false_wake_word").catch((error) =>
+      console.error("Failed to report false wake word:", error)
+    );
+  }, FALSE_TRIGGER_TIMEOUT_MS);
+
+  backend.on("input_audio_buffer.speech_started", onSpeech);
+}